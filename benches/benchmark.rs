@@ -5,13 +5,15 @@
 //! 実装するベンチマーク:
 //! - TC-P-010: Small File Processing Speed（< 1秒）
 //! - TC-P-011: Batch Processing Throughput（50ファイル/分以上）
+//! - ワークシートXML走査（100万行、メタデータ解析フェーズのみ）
 //!
 //! メモリ使用量の測定は別途、valgrindやheaptrackなどのツールを使用してください。
 
 use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use std::fs::File;
-use std::io::{Cursor, Read, Seek, Write};
+use std::io::{Cursor, Read};
 use std::path::PathBuf;
+use std::time::Duration;
 use xlsxzero::ConverterBuilder;
 
 /// テストフィクスチャのパスを取得
@@ -212,6 +214,58 @@ fn benchmark_large_file(c: &mut Criterion) {
     group.finish();
 }
 
+/// 100万行のワークブックを生成（ワークシートXML走査のベンチマーク用）
+///
+/// 共有文字列の列と数値の列からなる、非表示行・列のない一般的なデータシートです。
+fn generate_million_rows() -> Vec<u8> {
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Item").unwrap();
+    worksheet.write_string(0, 1, "Value").unwrap();
+    for row in 1..1_000_000u32 {
+        worksheet
+            .write_string(row, 0, format!("item{}", row % 100))
+            .unwrap();
+        worksheet.write_number(row, 1, row as f64).unwrap();
+    }
+    workbook.save_to_buffer().unwrap()
+}
+
+/// ワークシートXML走査のベンチマーク（100万行）
+///
+/// 変換レポートのメタデータ解析フェーズ（ワークシートXMLの走査を含む）の時間のみを計測する。
+/// フィクスチャの生成と変換に時間がかかるため、環境変数 `BENCH_1M_ROWS=true` を
+/// 設定した場合のみ実行する。
+fn benchmark_worksheet_scan(c: &mut Criterion) {
+    if std::env::var("BENCH_1M_ROWS").is_err() {
+        eprintln!(
+            "Info: 1M-row worksheet scan benchmark skipped. Set BENCH_1M_ROWS=true to enable."
+        );
+        return;
+    }
+
+    let data = generate_million_rows();
+    let converter = ConverterBuilder::new().build().unwrap();
+
+    let mut group = c.benchmark_group("worksheet_scan");
+    group.sample_size(10);
+
+    group.bench_function("metadata_1m_rows", |b| {
+        b.iter_custom(|iters| {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let report = converter
+                    .convert_with_report(Cursor::new(black_box(&data)), std::io::sink())
+                    .unwrap();
+                total += report.timings.metadata;
+            }
+            total
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group! {
     name = benches;
     config = Criterion::default()
@@ -229,4 +283,13 @@ criterion_group! {
     targets = benchmark_large_file
 }
 
-criterion_main!(benches, large_benches);
+// 100万行のワークシートXML走査のベンチマーク
+criterion_group! {
+    name = scan_benches;
+    config = Criterion::default()
+        .measurement_time(std::time::Duration::from_secs(60))
+        .warm_up_time(std::time::Duration::from_secs(1));
+    targets = benchmark_worksheet_scan
+}
+
+criterion_main!(benches, large_benches, scan_benches);
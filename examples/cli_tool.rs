@@ -6,7 +6,7 @@
 use std::fs::File;
 use std::io::{self, Write};
 use std::process;
-use xlsxzero::{ConverterBuilder, SheetSelector, XlsxToMdError};
+use xlsxzero::{ConverterBuilder, OutputFormat, SheetSelector, XlsxToMdError};
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -17,12 +17,20 @@ fn main() {
         eprintln!("  --sheet-index <n>    Select sheet by index (0-based)");
         eprintln!("  --sheet-name <name>  Select sheet by name");
         eprintln!("  --all-sheets        Convert all sheets (default)");
+        eprintln!("  --format <name>      Output format (built-in or registered plugin)");
         eprintln!("  --stdout            Write output to stdout instead of file");
         eprintln!("\nExamples:");
         eprintln!("  {} input.xlsx output.md", args[0]);
         eprintln!("  {} input.xlsx output.md --sheet-index 0", args[0]);
         eprintln!("  {} input.xlsx output.md --sheet-name \"Sheet1\"", args[0]);
         eprintln!("  {} input.xlsx - --stdout", args[0]);
+        eprintln!("\nFormats:");
+        for name in OutputFormat::NAMES {
+            eprintln!("  {}", name);
+        }
+        for plugin in xlsxzero::registered_formats() {
+            eprintln!("  {:<20} {}", plugin.name(), plugin.description());
+        }
         process::exit(1);
     }
 
@@ -32,6 +40,7 @@ fn main() {
 
     // Parse options
     let mut sheet_selector = SheetSelector::All;
+    let mut format = OutputFormat::Markdown.to_string();
     let mut i = 3;
     while i < args.len() {
         match args[i].as_str() {
@@ -55,6 +64,14 @@ fn main() {
                 sheet_selector = SheetSelector::Name(args[i + 1].clone());
                 i += 2;
             }
+            "--format" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --format requires a value");
+                    process::exit(1);
+                }
+                format = args[i + 1].clone();
+                i += 2;
+            }
             "--all-sheets" => {
                 sheet_selector = SheetSelector::All;
                 i += 1;
@@ -71,7 +88,13 @@ fn main() {
     }
 
     // Convert Excel file
-    match convert_excel(input_path, output_path, &sheet_selector, use_stdout) {
+    match convert_excel(
+        input_path,
+        output_path,
+        &sheet_selector,
+        &format,
+        use_stdout,
+    ) {
         Ok(_) => {
             if !use_stdout {
                 println!("Conversion completed: {} -> {}", input_path, output_path);
@@ -88,11 +111,14 @@ fn convert_excel(
     input_path: &str,
     output_path: &str,
     sheet_selector: &SheetSelector,
+    format: &str,
     use_stdout: bool,
 ) -> Result<(), XlsxToMdError> {
-    // Build converter with specified sheet selector
+    // Build converter with specified sheet selector and output format
+    // (plugins registered with xlsxzero::register_format are accepted by name)
     let converter = ConverterBuilder::new()
         .with_sheet_selector(sheet_selector.clone())
+        .with_format_name(format)
         .build()?;
 
     // Open input file
@@ -103,12 +129,12 @@ fn convert_excel(
         // Write to stdout
         let stdout = io::stdout();
         let mut handle = stdout.lock();
-        converter.convert(input, &mut handle)?;
+        converter.try_convert(input, &mut handle)?;
         handle.flush()?;
     } else {
         // Write to file
         let output = File::create(output_path)?;
-        converter.convert(input, output)?;
+        converter.try_convert(input, output)?;
     }
 
     Ok(())
@@ -142,8 +168,8 @@ fn handle_error(error: XlsxToMdError) {
             eprintln!("UTF-8 Conversion Error: {}", utf8_err);
             eprintln!("The file contains invalid UTF-8 characters.");
         }
-        XlsxToMdError::Zip(msg) => {
-            eprintln!("ZIP Archive Error: {}", msg);
+        XlsxToMdError::Zip { .. } | XlsxToMdError::Xml { .. } => {
+            eprintln!("{}", error);
             eprintln!("The file may be corrupted or not a valid ZIP archive.");
         }
         XlsxToMdError::ParseInt(parse_int_err) => {
@@ -154,5 +180,19 @@ fn handle_error(error: XlsxToMdError) {
             eprintln!("Security Violation: {}", msg);
             eprintln!("The file violates security constraints (e.g., file size limit).");
         }
+        XlsxToMdError::Internal(msg) => {
+            eprintln!("Internal Error: {}", msg);
+            eprintln!("Please report this issue with the input file if possible.");
+        }
+        XlsxToMdError::SheetNotFound { .. } | XlsxToMdError::SheetIndexOutOfRange { .. } => {
+            eprintln!("Sheet Selection Error: {}", error);
+            eprintln!("Please check the sheet name or index.");
+        }
+        other => {
+            eprintln!("Error: {}", other);
+            if let (Some(sheet), Some(cell)) = (other.sheet(), other.cell()) {
+                eprintln!("  Location: {}!{}", sheet, cell);
+            }
+        }
     }
 }
@@ -68,24 +68,14 @@ pub fn convert_excel_to_markdown_custom(
 
     // Set merge strategy
     if let Some(ref strategy) = merge_strategy {
-        match strategy.as_str() {
-            "data_duplication" => {
-                builder = builder.with_merge_strategy(xlsxzero::MergeStrategy::DataDuplication)
-            }
-            "html_fallback" => {
-                builder = builder.with_merge_strategy(xlsxzero::MergeStrategy::HtmlFallback)
-            }
-            _ => return Err(format!("Invalid merge strategy: {}", strategy)),
-        }
+        let strategy: xlsxzero::MergeStrategy = strategy.parse().map_err(|e| format!("{}", e))?;
+        builder = builder.with_merge_strategy(strategy);
     }
 
     // Set date format
     if let Some(ref format) = date_format {
-        if format == "iso8601" {
-            builder = builder.with_date_format(xlsxzero::DateFormat::Iso8601);
-        } else {
-            builder = builder.with_date_format(xlsxzero::DateFormat::Custom(format.clone()));
-        }
+        let format: xlsxzero::DateFormat = format.parse().map_err(|e| format!("{}", e))?;
+        builder = builder.with_date_format(format);
     }
 
     let converter = builder.build().map_err(|e| format!("Failed to create converter: {}", e))?;
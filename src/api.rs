@@ -1,6 +1,61 @@
 //! Public API Types
 //!
 //! 公開APIで使用する列挙型を定義するモジュール。
+//!
+//! # 文字列名
+//!
+//! 値を持たない設定の列挙型と`DateFormat`は、CLI・WASM・FFIなどで文字列として受け渡すための
+//! 名前を持ちます。名前はバリアント名のスネークケース（例: `MergeStrategy::HtmlFallback`は
+//! `html_fallback`）で、`Display`・`as_str()`・serdeのシリアライズで出力し、`FromStr`・serdeの
+//! デシリアライズで読み込みます。読み込みでは大文字・小文字を区別せず、`-`は`_`として扱います
+//! （例: `HTML-Fallback`）。名前は互換性を保証する公開APIの一部であり、変更しません。
+//!
+//! | 列挙型 | 名前 |
+//! |--------|------|
+//! | `MergeStrategy` | `data_duplication`, `html_fallback` |
+//! | `DuplicationPlacement` | `all`, `first_only`, `middle` |
+//! | `DateFormat` | `iso8601`（それ以外の文字列は`Custom`のフォーマット文字列） |
+//! | `FormulaMode` | `cached_value`, `formula`, `both` |
+//! | `OutputFormat` | `markdown`（読み込みのみ`md`も可）, `html`, `json`, `csv`, `xml` |
+//! | `NumberOutput` | `formatted`, `raw` |
+//! | `JsonCellMode` | `plain`, `typed` |
+//! | `JsonKeyMode` | `column_letter`, `header`, `normalized_header` |
+//! | `JsonDocumentMode` | `sheets`, `lines`, `records` |
+//! | `JsonStyle` | `pretty`, `minified` |
+//! | `HyperlinkMode` | `inline`, `footnote`, `text_only`, `url_only` |
+//! | `HeaderUnits` | `off`, `detect`, `detect_and_strip` |
+//! | `RowDedupe` | `off`, `consecutive_with_count` |
+//! | `BlankRows` | `keep`, `remove`, `remove_with_marker` |
+//! | `SortOrder` | `ascending`, `descending` |
+//! | `ColumnType` | `number`, `date`, `text`, `bool` |
+//! | `ValidationPolicy` | `report`, `fail` |
+//! | `WrapStrategy` | `wrap`, `truncate` |
+//! | `ChunkHeader` | `repeat`, `first_only` |
+//! | `ProtectedSheets` | `convert`, `skip`, `flag` |
+//! | `ConditionalFormats` | `ignore`, `metadata`, `annotate`, `marker` |
+//! | `DuplicateSheets` | `keep`, `skip`, `note` |
+//! | `OutputOverflow` | `error`, `truncate` |
+//! | `Preset` | `rag`, `faithful`, `compact`, `machine_readable` |
+//!
+//! 値を持つバリアントがある列挙型（`SheetSelector`、`ColumnSelector`、`NullFormulaPolicy`、
+//! `CellImageMode`、`Validation`）は文字列名を持ちません。
+//!
+//! ```rust
+//! use xlsxzero::{MergeStrategy, OutputFormat};
+//!
+//! # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+//! let strategy: MergeStrategy = "html-fallback".parse()?;
+//! assert_eq!(strategy, MergeStrategy::HtmlFallback);
+//! assert_eq!(strategy.to_string(), "html_fallback");
+//! assert_eq!("MD".parse::<OutputFormat>()?, OutputFormat::Markdown);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{ConfigError, XlsxToMdError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
 
 /// セル結合の処理戦略
 ///
@@ -55,6 +110,28 @@ pub enum MergeStrategy {
     HtmlFallback,
 }
 
+/// 縦方向に結合されたセルの値を配置する行
+///
+/// `MergeStrategy::DataDuplication`で、複数行にまたがる結合セルの親セルの値を
+/// どの行に出力するかを指定します。値を出力しない行のセルは空になります。
+/// 横方向の複製（同じ行の結合範囲内の列）には影響しません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplicationPlacement {
+    /// 結合範囲のすべての行に複製（デフォルト）
+    ///
+    /// 各行が単独で意味を持つため、RAGのチャンク分割に適しています。
+    All,
+
+    /// 結合範囲の最初の行のみに出力
+    FirstOnly,
+
+    /// 結合範囲の中央の行のみに出力（行数が偶数の場合は中央の上側の行）
+    ///
+    /// Excelで縦方向に中央揃えされたラベルの見た目に近い出力になります。
+    Middle,
+}
+
 /// 日付の出力形式
 ///
 /// Excelの日付セルをMarkdownに変換する際の出力形式を指定します。
@@ -112,6 +189,13 @@ pub enum FormulaMode {
     /// 数式そのものを文字列として出力します。
     /// 例: `=SUM(A1:A10)` → `=SUM(A1:A10)`
     Formula,
+
+    /// キャッシュされた結果値と数式文字列を併せて出力
+    ///
+    /// 結果値と数式を`ConverterBuilder::with_formula_template`のテンプレート
+    /// （デフォルト: `{value} (={formula})`）で組み合わせて出力します。数式のないセルは結果値のみです。
+    /// 例: `=SUM(A1:D1)` → `100 (=SUM(A1:D1))`
+    Both,
 }
 
 /// シート選択方式
@@ -215,4 +299,1201 @@ pub enum OutputFormat {
     /// Data1,Data2
     /// ```
     Csv,
+
+    /// XML形式
+    ///
+    /// セルデータを単純なXML（SpreadsheetMLに似た構造）で出力します。
+    /// 各シートは`<sheet>`要素、各行は`<row>`要素（`r`: 行番号）、各セルは`<cell>`要素
+    /// （`r`: セル参照、`t`: 値の種類）として表現され、空のセルは出力されません。
+    /// 複数のシートがすべてXML形式の場合は、`<workbook>`要素で囲んだ1つの文書になります。
+    ///
+    /// `t`は`n`（数値）、`d`（日付・時刻）、`s`（文字列）、`b`（真偽値）、`e`（エラー）の
+    /// いずれかです。文書のスキーマはリポジトリの`schema/xlsxzero.xsd`を参照してください。
+    ///
+    /// # 出力例
+    ///
+    /// ```xml
+    /// <sheet name="Sheet1">
+    ///   <row r="1">
+    ///     <cell r="A1" t="s">Header1</cell>
+    ///     <cell r="B1" t="s">Header2</cell>
+    ///   </row>
+    ///   <row r="2">
+    ///     <cell r="A2" t="s">Data1</cell>
+    ///     <cell r="B2" t="n">1234.56</cell>
+    ///   </row>
+    /// </sheet>
+    /// ```
+    Xml,
+}
+
+impl OutputFormat {
+    /// 出力フォーマットに対応するファイル拡張子を取得
+    ///
+    /// # 戻り値
+    ///
+    /// * `"md"`、`"html"`、`"json"`、`"csv"`、`"xml"`のいずれか（先頭のピリオドは含みません）
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "md",
+            OutputFormat::Html => "html",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Xml => "xml",
+        }
+    }
+}
+
+/// 数値セルの出力方式
+///
+/// 数値書式（例: `#,##0.00`）が適用されたセルを、出力フォーマットごとに
+/// 書式適用後の文字列で出力するか、生の数値で出力するかを指定します。
+///
+/// 書式適用後の文字列（例: `1,234.56`）は桁区切りのカンマを含むため、
+/// CSVのフィールド分割やJSONの数値型として扱えません。
+/// そのため、デフォルトではCSV/JSON/XMLは`Raw`、Markdown/HTMLは`Formatted`になります。
+/// 出力フォーマットごとの設定は`ConverterBuilder::with_number_output()`で変更できます。
+///
+/// 日付として判定された数値セルは、この設定にかかわらず日付文字列として出力されます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NumberOutput {
+    /// 数値書式を適用した文字列を出力
+    ///
+    /// 例: `1234.56`（書式 `#,##0.00`）→ `1,234.56`
+    Formatted,
+
+    /// 生の数値を出力
+    ///
+    /// 例: `1234.56`（書式 `#,##0.00`）→ `1234.56`
+    ///
+    /// JSON形式では文字列ではなく数値として出力されます。
+    Raw,
+}
+
+/// JSON出力におけるセルの表現方式
+///
+/// `OutputFormat::Json`で各セルをどのような値として出力するかを指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonCellMode {
+    /// 単一の値として出力（デフォルト）
+    ///
+    /// 各セルは文字列（`NumberOutput::Raw`の場合、数値セルは数値）として出力されます。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"A": "Sales", "B": 1234.56}
+    /// ```
+    Plain,
+
+    /// 型付きオブジェクトとして出力
+    ///
+    /// 各セルを`type`（値の種類）、`raw`（元の値: 数値・シリアル値・文字列など）、
+    /// `formatted`（数値書式適用後の文字列）を持つオブジェクトとして出力します。
+    /// 利用側で元の値から再計算できるよう、両方の値を保持します。
+    ///
+    /// `type`は`"number"`、`"date"`、`"time"`、`"datetime"`、`"string"`、`"bool"`、
+    /// `"error"`、`"empty"`のいずれかです。日付・時刻は数値書式から判定し、
+    /// `raw`にはExcelのシリアル値を出力します。
+    /// エラー値は`raw`にExcelでの表記（例: `"#DIV/0!"`）を、`error`に`CellError`の識別子
+    /// （例: `"div0"`）を出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"A": {"type": "string", "raw": "Sales", "formatted": "Sales"},
+    ///  "B": {"type": "number", "raw": 1234.56, "formatted": "1,234.56"}}
+    /// ```
+    Typed,
+}
+
+/// JSON出力における行オブジェクトのキー
+///
+/// `OutputFormat::Json`で各行のセルをどのキーで出力するかを指定します。
+/// `Header`と`NormalizedHeader`では先頭行を見出しとして扱い、`rows`には2行目以降のみを出力します。
+/// あわせて、キーと元の見出し・列名の対応を`columns`配列として出力します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonKeyMode {
+    /// 列名（A, B, C, ...）をキーとして使用（デフォルト）
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"A": "Weight (kg)", "B": "Unit Price"}
+    /// ```
+    ColumnLetter,
+
+    /// 先頭行の見出し（前後の空白を除去）をキーとして使用
+    ///
+    /// 見出しが空の列は列名をキーとし、重複するキーには`_2`、`_3`...を付加します。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"Weight (kg)": "12.5", "Unit Price": "300"}
+    /// ```
+    Header,
+
+    /// 先頭行の見出しを正規化したキーを使用
+    ///
+    /// 月次ファイルなどで見出しの表記が少し異なっても同じキーになるよう、
+    /// 末尾の単位表記（`(kg)`、`[USD]`、`（円）`など）を除去し、小文字化したうえで、
+    /// 空白や記号の連続を`_`にまとめます（例: `" Unit  Price (USD) "` → `unit_price`）。
+    /// 正規化後に空になる列は小文字の列名をキーとし、重複するキーには`_2`、`_3`...を付加します。
+    /// 元の見出しは`columns`配列の`header`に保持されます。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"columns": [{"key": "weight", "header": "Weight (kg)", "column": "A"}],
+    ///  "rows": [{"weight": "12.5"}]}
+    /// ```
+    NormalizedHeader,
+}
+
+/// 複数シートのJSON文書の構造
+///
+/// `OutputFormat::Json`で複数のシートを1つの文書に出力する場合の構造を指定します。
+/// 各シートのオブジェクトには、シート名が`name`として含まれます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonDocumentMode {
+    /// 全シートを`sheets`配列に含む1つのJSONオブジェクトとして出力（デフォルト）
+    ///
+    /// シートが1つの場合は、そのシートのオブジェクトのみを出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"sheets": [{"name": "Sheet1", "rows": []}, {"name": "Sheet2", "rows": []}]}
+    /// ```
+    Sheets,
+
+    /// 1シートを1行のJSONとして出力（NDJSON / JSON Lines）
+    ///
+    /// 各シートのオブジェクトを改行を含まない1行で出力し、シートの見出しや区切りは出力しません。
+    /// シートが1つの場合も1行で出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"name": "Sheet1", "rows": []}
+    /// {"name": "Sheet2", "rows": []}
+    /// ```
+    Lines,
+
+    /// 各シートを行オブジェクトの配列（レコード形式）として出力
+    ///
+    /// シートのオブジェクト（`name`、`columns`、`metadata`など）で囲まず、`rows`の配列のみを
+    /// 出力するため、多くのデータ処理ツールでそのまま読み込めます。`JsonKeyMode::Header`と
+    /// 組み合わせると、各行は見出しをキーとするオブジェクトになります。
+    /// 複数のシートは、シート名をキーとする1つのJSONオブジェクトにまとめます。
+    /// 変更履歴の付録は出力しません。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// [{"Name": "Alice", "Age": 30}, {"Name": "Bob", "Age": 25}]
+    /// ```
+    Records,
+}
+
+/// JSONの書式
+///
+/// `JsonDocumentMode::Lines`（NDJSON）は1シートを1行で出力するため、常に`Minified`で出力します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum JsonStyle {
+    /// 字下げと改行を含めて出力（デフォルト）
+    ///
+    /// 出力を目で確認するデバッグ用途に適しています。
+    Pretty,
+
+    /// 空白と改行を含めずに1行で出力
+    ///
+    /// 出力サイズを抑える本番の処理や、ログ・メッセージキューへの送信に適しています。
+    Minified,
+}
+
+/// 値のない数式セルの扱い
+///
+/// キャッシュ値を持たない数式セル（計算されずに保存されたブックなど）の出力方法を指定します。
+/// デフォルトでは空セルと区別されないため、データ品質チェックで未計算のブックを
+/// 検出したい場合に`Marker`または`Flag`を使用します。
+///
+/// `FormulaMode::Formula`の場合は数式文字列が出力されるため、この設定は表示文字列に影響しません。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NullFormulaPolicy {
+    /// 空セルとして出力（デフォルト）
+    Empty,
+
+    /// 指定したマーカー文字列を出力（例: `#NOVALUE`）
+    ///
+    /// 型付きJSON（`JsonCellMode::Typed`）では、`"formula_no_value": true`も出力されます。
+    Marker(String),
+
+    /// 表示文字列は空のまま、型付きJSONで`"formula_no_value": true`を出力
+    ///
+    /// JSON以外の出力フォーマットでは`Empty`と同じ出力になります。
+    Flag,
+}
+
+/// ハイパーリンクの出力方式
+///
+/// ハイパーリンクを持つセルを、どのように出力するかを指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HyperlinkMode {
+    /// 出力フォーマットに応じたリンクとして出力（デフォルト）
+    ///
+    /// | 出力フォーマット | 出力 |
+    /// |------------------|------|
+    /// | Markdown | セル内のMarkdownリンク（`[Example](https://example.com)`） |
+    /// | HTML | セル内の`<a href="https://example.com">Example</a>` |
+    /// | JSON | 表示テキストと`url`（値のみのセルは`{"value": "Example", "url": "..."}`） |
+    /// | XML | 表示テキストと`<cell>`要素の`href`属性 |
+    /// | CSV | 表示テキストのみ |
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | [Example](https://example.com) |
+    /// ```
+    Inline,
+
+    /// セルには表示テキストと脚注の参照を出力し、URLはシート末尾の脚注セクションに出力
+    ///
+    /// 長いURLで表の列幅が広がるのを防ぎます。同じURLを参照するセルは同じ脚注を共有します。
+    /// Markdown以外の出力フォーマットでは`Inline`と同じ出力になります。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Example[^1] |
+    ///
+    /// [^1]: https://example.com
+    /// ```
+    Footnote,
+
+    /// 表示テキストのみを出力し、URLは出力しない
+    ///
+    /// ハイパーリンク情報の解析自体を省略するため、変換時間も短縮されます。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Example |
+    /// ```
+    TextOnly,
+
+    /// 表示テキストの代わりにURLのみを出力
+    ///
+    /// CSVなど、リンクの構文を持たない出力フォーマットでURLを残す場合に使用します。
+    /// JSON・XMLの`url`・`href`は`Inline`と同様に出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```csv
+    /// https://example.com
+    /// ```
+    UrlOnly,
+}
+
+/// 見出しに含まれる単位の扱い
+///
+/// 先頭行（見出し）の末尾に括弧で記載された単位（`Weight (kg)`、`Price [USD]`、`金額（円）`など）を
+/// 列ごとの単位として検出します。括弧内が空の場合や16文字を超える場合、
+/// 見出し全体が括弧の場合は単位として扱いません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HeaderUnits {
+    /// 単位を検出しない（デフォルト）
+    Off,
+
+    /// 単位を検出し、JSON出力の`columns`配列に列ごとの`unit`として出力
+    ///
+    /// 見出しの表示はそのままです。単位のない列の`unit`は`null`になります。
+    ///
+    /// # 出力例
+    ///
+    /// ```json
+    /// {"columns": [{"key": "A", "header": "Weight (kg)", "column": "A", "unit": "kg"}]}
+    /// ```
+    Detect,
+
+    /// 単位を検出し、見出しの表示から単位を除去
+    ///
+    /// すべての出力フォーマットで見出しから単位表記を除去します（例: `Weight (kg)` → `Weight`）。
+    /// JSON出力では`Detect`と同様に`columns`配列に`unit`を出力します。
+    DetectAndStrip,
+}
+
+/// 同一内容の行の集約方式
+///
+/// ログなどを書き出したブックでは、まったく同じ内容の行が大量に連続することがあります。
+/// 連続する同一行を1行に集約することで、出力サイズを大幅に削減できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RowDedupe {
+    /// 集約しない（デフォルト）
+    Off,
+
+    /// 連続する同一内容の行を1行に集約し、最終列のセルに行数（`×N`）を付記
+    ///
+    /// 先頭行（見出し）は集約の対象外です。結合セルを含む行は集約しません。
+    /// `MergeStrategy::HtmlFallback`で結合セルを含むシートでは、結合構造を保持するため
+    /// 集約を行いません。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Level | Message      |
+    /// |-------|--------------|
+    /// | WARN  | retrying ×3  |
+    /// | INFO  | done         |
+    /// ```
+    ConsecutiveWithCount,
+}
+
+/// 空行（すべてのセルが空の行）の扱い
+///
+/// 表の区切りとして挿入された空行を除去すると、表を1つの連続した表として出力できます。
+/// 除去した行の元の行番号を出力に残すと、出力を元のファイルと照合する必要がある場合に
+/// 行の対応を復元できます。
+///
+/// 結合セルの一部を含む行は空行として扱いません。`MergeStrategy::HtmlFallback`で
+/// 結合セルを含むシートでは、結合構造を保持するため除去を行いません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlankRows {
+    /// 空行をそのまま出力（デフォルト）
+    Keep,
+
+    /// 空行を除去
+    Remove,
+
+    /// 空行を除去し、除去した行のシート上の行番号（1始まり）を出力
+    ///
+    /// Markdown・HTML・XMLでは表の後にHTML（XML）コメント、CSVでは`#`で始まるコメント行、
+    /// JSONではシートのオブジェクトの`removed_blank_rows`配列として出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Name  | Price |
+    /// |-------|-------|
+    /// | Apple | 100   |
+    /// | Pear  | 120   |
+    ///
+    /// <!-- removed blank rows: 3, 6-7 -->
+    /// ```
+    RemoveWithMarker,
+}
+
+/// 空の行・列の刈り込み方式
+///
+/// 一度入力した後に内容を消去したシートでは、使用範囲が実際の内容より大きいままになり、
+/// 大量の空行・空列が出力されることがあります。刈り込みはグリッドの構築後、出力の前に行います。
+///
+/// 結合セルの一部を含む行・列は空として扱いません。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TrimMode {
+    /// 刈り込まない（デフォルト）
+    None,
+
+    /// 末尾（下端・右端）の空行・空列を除去
+    ///
+    /// 表示内容のある範囲の左上の位置は変わりません。
+    Trailing,
+
+    /// 先頭・末尾の空行・空列と、表の途中の空行を除去
+    ///
+    /// 表の途中の空行は`BlankRows::Remove`と同様に除去します（除去した行番号は記録しません）。
+    /// `MergeStrategy::HtmlFallback`で結合セルを含むシートでは、表の途中の空行は除去しません。
+    All,
+}
+
+/// 列の指定方式
+///
+/// `ConverterBuilder::with_sort()`などで、出力する表の列を指定する方法です。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColumnSelector {
+    /// 出力する表の列番号（0始まり）
+    ///
+    /// 例: `ColumnSelector::Index(0)` は表の先頭列を指定
+    Index(usize),
+
+    /// 先頭行（見出し）の文字列
+    ///
+    /// 前後の空白を除いて比較し、完全に一致する列がない場合は大文字小文字を区別せずに比較します。
+    /// 例: `ColumnSelector::Header("Date".to_string())`
+    Header(String),
+}
+
+/// 並べ替えの順序
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SortOrder {
+    /// 昇順
+    Ascending,
+
+    /// 降順
+    Descending,
+}
+
+/// 列の値の検証ルール
+///
+/// `ConverterBuilder::with_validations()`で指定し、出力する表の見出しを除く各行を検証します。
+/// 検証は行の絞り込み・計算列の追加・並べ替えの後（定数列の除去と行の集約の前）の表に対して行い、
+/// 計算列も検証できます。違反は`WarningKind::ValidationFailed`の警告として
+/// `SheetReport::warnings`に記録し、`ValidationPolicy::Fail`の場合は変換をエラーにします。
+///
+/// 結合セルの親以外のセルは、親セルの値を持つものとして検証の対象外です。
+/// 指定した列がないシートは、ルールごとに1件の違反として記録します。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Validation {
+    /// 列の値が指定した型であること（空のセルは対象外）
+    ///
+    /// 型はフォーマット前のセルの値で判定します（日付の表示形式の数値は`ColumnType::Date`）。
+    /// エラー値（`#N/A`など）はどの型にも該当しません。
+    Type(ColumnSelector, ColumnType),
+
+    /// 列に空のセル（空白文字のみを含む）がないこと
+    NotNull(ColumnSelector),
+
+    /// 列の値（表示文字列）が重複しないこと（空のセルは対象外）
+    ///
+    /// キー列（IDなど）の一意性の確認に使用します。
+    Unique(ColumnSelector),
+}
+
+/// 列の値の型（`Validation::Type`で使用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColumnType {
+    /// 数値（日付・時刻を除く）
+    Number,
+
+    /// 日付・時刻
+    Date,
+
+    /// 文字列
+    Text,
+
+    /// 論理値
+    Bool,
+}
+
+/// 列の値の検証に違反した場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationPolicy {
+    /// 違反を警告として記録し、変換を継続（デフォルト）
+    Report,
+
+    /// 違反があるシートで変換を中止し、`XlsxToMdError::Validation`を返す
+    ///
+    /// 変換した表を自動処理のパイプラインに渡す前に、不正なデータを止める場合に使用します。
+    Fail,
+}
+
+/// 列幅の上限を超えるセルの扱い
+///
+/// `ConverterBuilder::with_column_width_limit()`で、Markdownの表の列幅の上限とともに指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WrapStrategy {
+    /// `<br>`で折り返す
+    ///
+    /// 空白の位置で折り返し、上限より長い語は上限の幅ごとに分割します。
+    Wrap,
+
+    /// 上限の幅で切り詰め、末尾に省略記号（`…`）を付加
+    ///
+    /// 省略記号は`ConverterBuilder::with_truncation_marker()`で変更できます。
+    /// 脚注の参照（`[^1]`）は切り詰めずに残します。
+    Truncate,
+}
+
+/// 分割した表の見出し行の扱い
+///
+/// `ConverterBuilder::with_table_chunk_rows()`で、表を分割する行数とともに指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChunkHeader {
+    /// 分割したすべての表に先頭行（見出し）を繰り返す
+    ///
+    /// 分割した表を個別に検索・埋め込みしても、各列の意味が失われません。
+    Repeat,
+
+    /// 先頭の表のみに見出しを出力し、以降の表の見出し行は空にする
+    FirstOnly,
+}
+
+/// 保護されたシートの扱い
+///
+/// シートの保護（「シートの保護」で設定する`<sheetProtection>`）は編集を制限するだけで、
+/// データは暗号化されないため、保護されたシートも通常どおり変換できます。
+/// 運用上の方針で保護されたシートを除外したり、出力で識別したりする場合に指定します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProtectedSheets {
+    /// 保護の有無にかかわらず変換（デフォルト）
+    Convert,
+
+    /// 保護されたシートを出力しない
+    ///
+    /// 除外したシートは`WarningKind::ProtectedSheet`の警告で確認できます。
+    Skip,
+
+    /// 変換し、保護されていることを記録
+    ///
+    /// シート単位のメタデータ（`ConverterBuilder::include_metadata(true)`の場合のフロントマター、
+    /// JSONの`metadata`オブジェクト、XMLの`<sheet>`要素の属性）に`protected: true`を出力し、
+    /// `WarningKind::ProtectedSheet`の警告を記録します。
+    Flag,
+}
+
+/// 条件付き書式のルールの扱い
+///
+/// 条件付き書式（重複する値の強調表示、上位10項目など）のルールは、値だけでは表れない
+/// 業務上の意味（「重複は要確認」「上位は重点顧客」など）を表します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConditionalFormats {
+    /// 条件付き書式を解析しない（デフォルト）
+    Ignore,
+
+    /// ルールをJSONの各シートの`conditional_formats`配列に出力
+    ///
+    /// 各ルールは適用範囲（`range`）、種類（`type`）、優先順位（`priority`）と、
+    /// ルールに応じた演算子・数式・順位・しきい値などからなるオブジェクトです。
+    Metadata,
+
+    /// `Metadata`に加え、単純なルールに該当するセルに注記を付加
+    ///
+    /// 注記はMarkdownでは脚注、型付きJSON（`JsonCellMode::Typed`）では各セルの`highlight: true`と
+    /// `highlights`配列です。
+    /// 対象のルールは重複する値・一意の値、上位・下位（件数・割合）、平均より上・下、
+    /// 定数との比較（`cellIs`）、文字列を含む・で始まる・で終わる（`containsText`など）です。
+    /// 数式によるルール、カラースケール・データバー・アイコンセットは注記しません。
+    Annotate,
+
+    /// `Metadata`に加え、`Annotate`と同じルールに該当するセルの表示文字列の末尾に`⚠`を付加
+    ///
+    /// 脚注を使わずにセル内で強調表示を示すため、Markdown以外の出力フォーマットでも同じ表示になります。
+    /// 複数のルールに該当するセルにも`⚠`は1つのみ付加します。
+    /// 型付きJSON（`JsonCellMode::Typed`）では表示文字列を変更せず、`Annotate`と同様に
+    /// 各セルに`highlight: true`と`highlights`配列を出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | INV-1 ⚠ | 120 |
+    /// ```
+    Marker,
+}
+
+/// セルのコメント（メモ・スレッド形式のコメント）の扱い
+///
+/// レビューのコメントには、値だけでは分からない前提や注意点が記録されていることが多くあります。
+/// スレッド形式のコメントは、返信を含めてスレッド全体を1つのコメントとして出力します。
+/// 各コメントは作成者名を付加して出力します（例: `Alice: Please check`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CommentMode {
+    /// コメントを解析しない（デフォルト）
+    Skip,
+
+    /// コメントをセルの表示文字列の末尾に付加
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | 1200 💬 Alice: Includes tax |
+    /// ```
+    Inline,
+
+    /// セルには脚注の参照を出力し、コメントはシート末尾の脚注セクションに出力
+    ///
+    /// Markdown以外の出力フォーマットでは`Inline`と同じ出力になります。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | 1200[^1] |
+    ///
+    /// [^1]: Alice: Includes tax
+    /// ```
+    Footnotes,
+}
+
+/// シートに配置されたグラフの扱い
+///
+/// グラフ（xl/charts/chart*.xml）はセルの値ではないため、通常の変換では出力されません。
+/// グラフのXMLには、参照先のセル範囲の値のキャッシュ（系列名・項目・値）が記録されているため、
+/// グラフのタイトルと系列の概要、またはキャッシュされたデータを出力できます。
+/// グラフはシートの表の後に、配置位置の行・列の順で出力します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChartMode {
+    /// グラフを解析しない（デフォルト）
+    Skip,
+
+    /// グラフのタイトル・系列名・項目の範囲を1行の注記として出力
+    ///
+    /// Markdownでは引用、HTMLでは段落、CSVでは`#`で始まるコメント行、XMLではコメントとして出力します。
+    /// JSON形式では何も出力しません。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// > Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec
+    /// ```
+    Placeholder,
+
+    /// `Placeholder`の注記に続けて、キャッシュされた系列のデータを表として出力
+    ///
+    /// 表は項目を行、系列を列とします。MarkdownとHTML以外の出力フォーマットでは
+    /// `Placeholder`と同じ出力になります。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// > Chart: 'Revenue by Quarter' — series: 2023, 2024 over Q1..Q2
+    ///
+    /// |    | 2023 | 2024 |
+    /// |----|------|------|
+    /// | Q1 | 120  | 150  |
+    /// | Q2 | 130  | 170  |
+    /// ```
+    Data,
+}
+
+/// シートに配置されたピボットテーブルの扱い
+///
+/// ピボットテーブルの集計結果は、最後に更新した時点の値がシートのセルとして保存されています。
+/// 集計元のデータはピボットキャッシュ（xl/pivotCache/）に保存されているため、
+/// 元データの表を出力すると、集計結果だけでは分からない明細を確認できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PivotMode {
+    /// ピボットテーブルの集計結果をシートのセルとしてのみ出力（デフォルト）
+    Cells,
+
+    /// シートのセルに加えて、ピボットキャッシュに保存された元データを
+    /// `Pivot: <名前>`の見出しの下に独立した表として出力
+    ///
+    /// 見出しと表はシートの表の後に、ピボットテーブルの配置範囲の行・列の順で出力します。
+    /// Markdown・HTML・CSVのみ対象で、JSON・XML形式では何も出力しません。
+    /// 元データを保存しない設定のピボットテーブルは、フィールド名の行のみを出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// ## Pivot: SalesPivot
+    ///
+    /// | Region | Amount |
+    /// |--------|--------|
+    /// | East   | 120    |
+    /// | West   | 80     |
+    /// ```
+    SourceData,
+}
+
+/// セルの入力規則（ドロップダウンリストなど）の扱い
+///
+/// リストの入力規則は、列が取り得る値の一覧（「未着手・対応中・完了」など）を表します。
+/// 値の一覧は、数式に直接記載されている場合と、セル範囲・名前付き範囲を参照している場合
+/// （別のシート・非表示のシートの範囲を含む）に解決します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DataValidations {
+    /// 入力規則を解析しない（デフォルト）
+    Ignore,
+
+    /// 入力規則をJSONの各シートの`data_validations`配列に出力
+    ///
+    /// 各ルールは適用範囲（`range`）、種類（`type`）と、ルールに応じた演算子（`operator`）・
+    /// 数式（`formulas`）、リストの値の一覧（`allowed`）からなるオブジェクトです。
+    Metadata,
+
+    /// `Metadata`に加え、リストの入力規則が適用された列の見出しに値の一覧を付加
+    ///
+    /// 値の一覧が長い場合は、先頭の20件のみを付加します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Status (allowed: Open, Closed, Pending) |
+    /// ```
+    Annotate,
+}
+
+/// 内容がほぼ同一のシート（重複シート）の扱い
+///
+/// 「Sheet1 (2)」のようにシートを複製したまま残しているワークブックでは、
+/// 同じ表が何度も出力されます。各シートの行の内容を正規化してハッシュ化し、
+/// 先に出力するシートと行の90%以上が一致するシートを重複シートとして検出します。
+/// 空のシートは重複シートとして扱いません。
+///
+/// 検出した重複シートは、`ConversionReport::duplicate_sheets`と
+/// `WarningKind::DuplicateSheet`の警告で確認できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplicateSheets {
+    /// 重複シートを検出せず、すべてのシートを出力（デフォルト）
+    Keep,
+
+    /// 重複シートを出力から除外
+    Skip,
+
+    /// 重複シートの内容の代わりに、重複元のシートを示す注記を出力
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// > Near-duplicate of sheet "Sheet1" (similarity: 100%); content omitted.
+    /// ```
+    Note,
+}
+
+/// セル内の画像の出力方式
+///
+/// Excelの「セルに配置」した画像や`IMAGE()`関数のセルは、値として`#VALUE!`エラーが
+/// 保存されているため、そのままでは画像の列が空欄やエラーとして出力されます。
+/// セルの値メタデータ（`vm`属性）からセル内の画像を検出し、代わりに出力する文字列を指定します。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CellImageMode {
+    /// セルに保存された値をそのまま出力
+    Value,
+
+    /// 指定した文字列を出力（デフォルト: `[image]`）
+    ///
+    /// 例: `CellImageMode::Placeholder("[photo]".to_string())`
+    Placeholder(String),
+
+    /// ワークブック内の画像ファイルへの参照を出力
+    ///
+    /// 画像ファイルのパーツ名を`[image: パーツ名]`の形式で出力します。
+    /// Web上の画像（`IMAGE()`関数）など、画像ファイルに解決できない場合は`[image]`を出力します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Product | Photo                            |
+    /// |---------|----------------------------------|
+    /// | Apple   | [image: xl/media/image1.png]     |
+    /// ```
+    Reference,
+}
+
+/// 出力サイズが上限（`ConverterBuilder::with_max_output_size()`）を超えた場合の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OutputOverflow {
+    /// 変換を中止し、`XlsxToMdError::SecurityViolation`を返す（デフォルト）
+    Error,
+
+    /// 上限に収まる位置で出力を打ち切り、打ち切ったことを示す注記を付加
+    ///
+    /// 上限を超えたシートは、上限に収まる最後の行（改行の位置）で打ち切り、
+    /// 以降のシートは出力しません。JSON形式のシートは途中で打ち切ると不正なJSONになるため、
+    /// 上限に収まらないシートを出力しません（注記も付加しません）。
+    /// 打ち切った場合は`WarningKind::OutputTruncated`の警告を記録します。
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// | Item | Qty |
+    /// |------|-----|
+    /// | a    | 1   |
+    ///
+    /// > Output truncated: exceeded the maximum output size of 1024 bytes.
+    /// ```
+    Truncate,
+}
+
+/// 行の内容による絞り込み条件
+///
+/// `ConverterBuilder::with_content_filter()`で使用します。
+/// 各行のセルの表示文字列（出力フォーマット適用後）をタブ区切りで連結した文字列に対して、
+/// 正規表現（`regex`クレートの構文）で判定します。
+/// 多数のファイルに共通する定型文の行（「社外秘」など）を出力から除くために使用できます。
+///
+/// * `exclude_rows_matching`に一致する行は、先頭行を含むすべての行から除去します。
+/// * `include_rows_matching`を指定した場合、残った行のうち先頭行（見出し）以外は、
+///   一致する行のみを出力します。
+///
+/// 正規表現は`ConverterBuilder::build()`時に検証し、不正な場合は
+/// `XlsxToMdError::Config`を返します。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::ContentFilter;
+///
+/// let filter = ContentFilter {
+///     include_rows_matching: None,
+///     exclude_rows_matching: Some("(?i)confidential".to_string()),
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContentFilter {
+    /// 出力する行の条件（`None`の場合はすべての行）
+    pub include_rows_matching: Option<String>,
+
+    /// 除去する行の条件（`None`の場合は除去しない）
+    pub exclude_rows_matching: Option<String>,
+}
+
+/// 用途別の設定プリセット
+///
+/// `ConverterBuilder::preset()`で使用し、用途に応じた推奨設定の組み合わせを一括で適用します。
+/// プリセット適用後も、各`with_*`メソッドで個別の設定を上書きできます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Preset {
+    /// RAG（検索拡張生成）向け
+    ///
+    /// LLMへの入力や埋め込みに適した設定です。
+    ///
+    /// * 出力フォーマット: Markdown
+    /// * セル結合: データ重複フィル（各行が単独で意味を持つように値を複製）
+    /// * 非表示要素: スキップ
+    /// * メタデータ: 含める（文字体系・ハッシュ値によるルーティングや重複排除用）
+    /// * 空の行・列: 先頭・末尾と表の途中の空行を除去（`TrimMode::All`）
+    /// * 連続する同一行: 1行に集約して行数を付記（`RowDedupe::ConsecutiveWithCount`）
+    /// * 表の分割: 50行ごとに見出しを繰り返した表に分割（`ChunkHeader::Repeat`）
+    ///   （空行で区切られるため、下流のチャンク分割で表の途中が見出しなしで切り出されない）
+    Rag,
+
+    /// 元のブックへの忠実性を重視
+    ///
+    /// 見た目と構造をできるだけ保持する設定です。
+    ///
+    /// * 出力フォーマット: Markdown
+    /// * セル結合: HTMLフォールバック（結合構造を`rowspan`/`colspan`で保持）
+    /// * 非表示要素: 含める
+    /// * 数値: 書式適用後の文字列
+    Faithful,
+
+    /// 出力サイズの最小化
+    ///
+    /// トークン数や保存容量を抑える設定です。
+    ///
+    /// * 出力フォーマット: Markdown
+    /// * セル結合: データ重複フィル
+    /// * 非表示要素: スキップ
+    /// * 数値: 生の数値（書式による桁区切りや装飾を省略）
+    /// * 使用範囲: 肥大化したシートを表示内容のある範囲に自動縮小
+    Compact,
+
+    /// プログラムでの処理向け
+    ///
+    /// 下流のシステムで値を再計算・検証できる設定です。
+    ///
+    /// * 出力フォーマット: JSON
+    /// * セル表現: 型付きオブジェクト（`raw`と`formatted`の両方を保持）
+    /// * 非表示要素: スキップ
+    /// * メタデータ: 含める
+    MachineReadable,
+}
+
+/// 文字列名を持つ列挙型に`as_str()`・`Display`・`FromStr`・serdeの実装を追加する（内部用）
+///
+/// `名前 | 別名`の別名は`FromStr`でのみ受け付けます。
+macro_rules! named_options {
+    ($($ty:ident { $($variant:ident => $name:literal $(| $alias:literal)*),+ $(,)? })+) => {$(
+        impl $ty {
+            /// 文字列名の一覧（バリアントの定義順）
+            pub const NAMES: &'static [&'static str] = &[$($name),+];
+
+            /// 文字列名を取得（`Display`・serdeの出力と同じ）
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($ty::$variant => $name,)+
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = XlsxToMdError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match normalize_name(s).as_str() {
+                    $($name $(| $alias)* => Ok($ty::$variant),)+
+                    _ => Err(invalid_name(stringify!($ty), s, Self::NAMES)),
+                }
+            }
+        }
+
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                name.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    )+};
+}
+
+named_options! {
+    MergeStrategy {
+        DataDuplication => "data_duplication",
+        HtmlFallback => "html_fallback",
+    }
+    DuplicationPlacement {
+        All => "all",
+        FirstOnly => "first_only",
+        Middle => "middle",
+    }
+    FormulaMode {
+        CachedValue => "cached_value",
+        Formula => "formula",
+        Both => "both",
+    }
+    OutputFormat {
+        Markdown => "markdown" | "md",
+        Html => "html",
+        Json => "json",
+        Csv => "csv",
+        Xml => "xml",
+    }
+    NumberOutput {
+        Formatted => "formatted",
+        Raw => "raw",
+    }
+    JsonCellMode {
+        Plain => "plain",
+        Typed => "typed",
+    }
+    JsonKeyMode {
+        ColumnLetter => "column_letter",
+        Header => "header",
+        NormalizedHeader => "normalized_header",
+    }
+    JsonDocumentMode {
+        Sheets => "sheets",
+        Lines => "lines",
+        Records => "records",
+    }
+    JsonStyle {
+        Pretty => "pretty",
+        Minified => "minified",
+    }
+    HyperlinkMode {
+        Inline => "inline",
+        Footnote => "footnote",
+        TextOnly => "text_only",
+        UrlOnly => "url_only",
+    }
+    HeaderUnits {
+        Off => "off",
+        Detect => "detect",
+        DetectAndStrip => "detect_and_strip",
+    }
+    RowDedupe {
+        Off => "off",
+        ConsecutiveWithCount => "consecutive_with_count",
+    }
+    BlankRows {
+        Keep => "keep",
+        Remove => "remove",
+        RemoveWithMarker => "remove_with_marker",
+    }
+    SortOrder {
+        Ascending => "ascending",
+        Descending => "descending",
+    }
+    ColumnType {
+        Number => "number",
+        Date => "date",
+        Text => "text",
+        Bool => "bool",
+    }
+    ValidationPolicy {
+        Report => "report",
+        Fail => "fail",
+    }
+    WrapStrategy {
+        Wrap => "wrap",
+        Truncate => "truncate",
+    }
+    ChunkHeader {
+        Repeat => "repeat",
+        FirstOnly => "first_only",
+    }
+    ProtectedSheets {
+        Convert => "convert",
+        Skip => "skip",
+        Flag => "flag",
+    }
+    ConditionalFormats {
+        Ignore => "ignore",
+        Metadata => "metadata",
+        Annotate => "annotate",
+        Marker => "marker",
+    }
+    DuplicateSheets {
+        Keep => "keep",
+        Skip => "skip",
+        Note => "note",
+    }
+    OutputOverflow {
+        Error => "error",
+        Truncate => "truncate",
+    }
+    Preset {
+        Rag => "rag",
+        Faithful => "faithful",
+        Compact => "compact",
+        MachineReadable => "machine_readable",
+    }
+}
+
+/// `DateFormat::Iso8601`の文字列名
+const ISO8601_NAME: &str = "iso8601";
+
+impl fmt::Display for DateFormat {
+    /// `iso8601`、またはカスタム形式のフォーマット文字列
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateFormat::Iso8601 => f.write_str(ISO8601_NAME),
+            DateFormat::Custom(format) => f.write_str(format),
+        }
+    }
+}
+
+impl FromStr for DateFormat {
+    type Err = XlsxToMdError;
+
+    /// `iso8601`（大文字・小文字を区別しない）は`Iso8601`、それ以外は`Custom`
+    ///
+    /// フォーマット文字列の妥当性は`ConverterBuilder::build()`で検証するため、
+    /// 空でない文字列の変換は失敗しません。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if normalize_name(s) == ISO8601_NAME {
+            Ok(DateFormat::Iso8601)
+        } else if s.is_empty() {
+            Err(invalid_name("DateFormat", s, &[ISO8601_NAME]))
+        } else {
+            Ok(DateFormat::Custom(s.to_string()))
+        }
+    }
+}
+
+impl Serialize for DateFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DateFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// 文字列名を正規化（前後の空白を除去、小文字化、`-`を`_`に置換）（内部ヘルパー）
+pub(crate) fn normalize_name(value: &str) -> String {
+    value.trim().to_ascii_lowercase().replace('-', "_")
+}
+
+/// 不正な文字列名のエラーを生成（内部ヘルパー）
+fn invalid_name(type_name: &'static str, value: &str, names: &[&str]) -> XlsxToMdError {
+    XlsxToMdError::Config(ConfigError::UnknownName {
+        kind: type_name,
+        value: value.to_string(),
+        expected: names.iter().map(|name| name.to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_options_round_trip() {
+        for name in MergeStrategy::NAMES {
+            assert_eq!(name.parse::<MergeStrategy>().unwrap().as_str(), *name);
+        }
+        for name in Preset::NAMES {
+            assert_eq!(name.parse::<Preset>().unwrap().to_string(), *name);
+        }
+        assert_eq!(
+            " Text-Only ".parse::<HyperlinkMode>().unwrap(),
+            HyperlinkMode::TextOnly
+        );
+        assert_eq!(
+            "md".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Markdown
+        );
+        assert_eq!(OutputFormat::Markdown.to_string(), "markdown");
+    }
+
+    #[test]
+    fn test_named_options_invalid() {
+        match "yaml".parse::<OutputFormat>() {
+            Err(XlsxToMdError::Config(ConfigError::UnknownName {
+                kind,
+                value,
+                expected,
+            })) => {
+                assert_eq!(kind, "OutputFormat");
+                assert_eq!(value, "yaml");
+                assert_eq!(expected, ["markdown", "html", "json", "csv", "xml"]);
+            }
+            other => panic!("Expected Config error, got {:?}", other),
+        }
+        assert!("".parse::<DateFormat>().is_err());
+    }
+
+    #[test]
+    fn test_date_format_from_str() {
+        assert_eq!(
+            "ISO8601".parse::<DateFormat>().unwrap(),
+            DateFormat::Iso8601
+        );
+        assert_eq!(
+            "%Y/%m/%d".parse::<DateFormat>().unwrap(),
+            DateFormat::Custom("%Y/%m/%d".to_string())
+        );
+        assert_eq!(DateFormat::Iso8601.to_string(), "iso8601");
+    }
+
+    #[test]
+    fn test_named_options_serde() {
+        assert_eq!(
+            serde_json::to_string(&JsonKeyMode::NormalizedHeader).unwrap(),
+            r#""normalized_header""#
+        );
+        let mode: JsonDocumentMode = serde_json::from_str(r#""LINES""#).unwrap();
+        assert_eq!(mode, JsonDocumentMode::Lines);
+        assert!(serde_json::from_str::<SortOrder>(r#""sideways""#).is_err());
+        assert_eq!(
+            serde_json::to_string(&DateFormat::Custom("%d.%m.%Y".to_string())).unwrap(),
+            r#""%d.%m.%Y""#
+        );
+    }
 }
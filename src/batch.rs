@@ -0,0 +1,362 @@
+//! Batch Module
+//!
+//! 複数のワークブックをまとめて変換する`BatchConverter`を提供するモジュール。
+//! ファイルは並列に変換し（`parallel`フィーチャー）、ファイルごとのエラーを収集するため、
+//! 破損したワークブックがあってもバッチ全体は中断しません。
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::builder::Converter;
+use crate::error::XlsxToMdError;
+use crate::report::ConversionReport;
+
+/// バッチ変換で変換するワークブックの拡張子（小文字）
+const WORKBOOK_EXTENSIONS: &[&str] = &["xlsx", "xlsm", "xls"];
+
+/// バッチ変換の入力（ファイルのパスまたはリーダー）
+///
+/// パスは`From`で変換できます（`&str`、`String`、`&Path`、`PathBuf`）。
+///
+/// # 使用例
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use xlsxzero::BatchInput;
+///
+/// let from_path = BatchInput::from("reports/q1.xlsx");
+/// let from_reader = BatchInput::reader("upload-42", Cursor::new(Vec::<u8>::new()));
+/// assert_eq!(from_path.name(), "reports/q1.xlsx");
+/// assert_eq!(from_reader.name(), "upload-42");
+/// ```
+pub struct BatchInput {
+    /// 入力の名前（`BatchItemReport::name`に使用）
+    name: String,
+
+    /// 入力元
+    source: BatchSource,
+}
+
+/// バッチ変換の入力元（内部ヘルパー）
+enum BatchSource {
+    /// ファイルのパス（変換時に開く）
+    Path(PathBuf),
+
+    /// リーダー
+    Reader(Box<dyn ReadSeek + Send>),
+}
+
+/// `Read + Seek`をトレイトオブジェクトとして保持するためのトレイト（内部ヘルパー）
+trait ReadSeek: Read + Seek {}
+
+impl<T: Read + Seek> ReadSeek for T {}
+
+impl BatchInput {
+    /// ファイルのパスから入力を生成する
+    ///
+    /// ファイルは変換時に開きます。開けない場合は、そのファイルの結果が`XlsxToMdError::Io`になります。
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            name: path.display().to_string(),
+            source: BatchSource::Path(path),
+        }
+    }
+
+    /// リーダーから入力を生成する
+    ///
+    /// # 引数
+    ///
+    /// * `name` - 入力の名前（`BatchItemReport::name`に使用）
+    /// * `reader` - Excelファイルを読み込むためのリーダー
+    pub fn reader(name: impl Into<String>, reader: impl Read + Seek + Send + 'static) -> Self {
+        Self {
+            name: name.into(),
+            source: BatchSource::Reader(Box::new(reader)),
+        }
+    }
+
+    /// 入力の名前を取得する
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for BatchInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match &self.source {
+            BatchSource::Path(path) => format!("Path({})", path.display()),
+            BatchSource::Reader(_) => "Reader".to_string(),
+        };
+        f.debug_struct("BatchInput")
+            .field("name", &self.name)
+            .field("source", &source)
+            .finish()
+    }
+}
+
+impl From<PathBuf> for BatchInput {
+    fn from(path: PathBuf) -> Self {
+        Self::path(path)
+    }
+}
+
+impl From<&Path> for BatchInput {
+    fn from(path: &Path) -> Self {
+        Self::path(path)
+    }
+}
+
+impl From<&str> for BatchInput {
+    fn from(path: &str) -> Self {
+        Self::path(path)
+    }
+}
+
+impl From<String> for BatchInput {
+    fn from(path: String) -> Self {
+        Self::path(path)
+    }
+}
+
+/// バッチ変換の1ファイル分の結果
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BatchItemReport {
+    /// 入力の名前（パスの場合はパス、リーダーの場合は指定した名前）
+    pub name: String,
+
+    /// 変換結果（`BatchConverter::convert_dir()`ではファイルに書き出すため空）
+    pub output: String,
+
+    /// 変換結果を書き出したファイルのパス（`BatchConverter::convert_dir()`の場合のみ）
+    pub output_path: Option<PathBuf>,
+
+    /// 変換の処理統計
+    pub report: ConversionReport,
+}
+
+/// バッチ変換の1ファイル分の処理（内部ヘルパー）
+struct BatchJob {
+    /// 入力
+    input: BatchInput,
+
+    /// 変換結果を書き出すファイルのパス（Noneの場合は`BatchItemReport::output`に格納）
+    destination: Option<PathBuf>,
+}
+
+/// 複数のワークブックをまとめて変換するコンバーター
+///
+/// すべてのファイルを同じ`Converter`の設定で変換します。ファイルはrayonのスレッドプールで
+/// 並列に変換し（ファイル内のシートは順に処理）、結果は入力の順に返します。
+/// `parallel`フィーチャーが無効な場合は、ファイルを1つずつ順に変換します。
+/// 変換に失敗したファイル（パニックを含む）は、そのファイルの結果がエラーになるのみで、
+/// 他のファイルの変換は継続します。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::{BatchConverter, ConverterBuilder};
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let batch = BatchConverter::new(ConverterBuilder::new().build()?);
+/// for (path, result) in ["a.xlsx", "b.xlsx"].iter().zip(batch.convert(["a.xlsx", "b.xlsx"])) {
+///     match result {
+///         Ok(item) => println!("{}: {} bytes", path, item.output.len()),
+///         Err(e) => eprintln!("{}: {}", path, e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchConverter {
+    /// ファイルごとの変換に使用するコンバーター
+    converter: Converter,
+}
+
+impl BatchConverter {
+    /// ファイルごとの変換に使用するコンバーターを指定して生成する
+    pub fn new(converter: Converter) -> Self {
+        Self { converter }
+    }
+
+    /// 入力を並列に変換し、ファイルごとの結果を入力の順に返す
+    ///
+    /// # 引数
+    ///
+    /// * `inputs` - 変換する入力（パスまたは`BatchInput`）
+    ///
+    /// # 戻り値
+    ///
+    /// 入力ごとの結果（入力の順）
+    pub fn convert<I>(&self, inputs: I) -> Vec<Result<BatchItemReport, XlsxToMdError>>
+    where
+        I: IntoIterator,
+        I::Item: Into<BatchInput>,
+    {
+        let jobs = inputs
+            .into_iter()
+            .map(|input| BatchJob {
+                input: input.into(),
+                destination: None,
+            })
+            .collect();
+        self.run(jobs)
+    }
+
+    /// ディレクトリ内のワークブックを変換し、出力先ディレクトリにファイルとして書き出す
+    ///
+    /// `input_dir`の直下にある拡張子が`.xlsx`・`.xlsm`・`.xls`のファイルをファイル名の順に変換し、
+    /// `output_dir`に同じファイル名で出力フォーマットの拡張子のファイル（例: `report.md`）を書き出します。
+    /// 拡張子のみが異なるワークブック（例: `report.xlsx`と`report.xls`）は、出力先が重複しないよう
+    /// 元の拡張子を残したファイル名（例: `report.xlsx.md`、`report.xls.md`）で書き出します。
+    /// 出力先ディレクトリが存在しない場合は作成します。変換に失敗したファイルは書き出しません。
+    ///
+    /// # 引数
+    ///
+    /// * `input_dir` - 変換するワークブックのあるディレクトリ
+    /// * `output_dir` - 出力先ディレクトリ
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<Result<BatchItemReport, XlsxToMdError>>)` - ファイルごとの結果（ファイル名の順）
+    /// * `Err(XlsxToMdError::Io)` - ディレクトリの読み込みまたは作成に失敗した場合
+    pub fn convert_dir(
+        &self,
+        input_dir: impl AsRef<Path>,
+        output_dir: impl AsRef<Path>,
+    ) -> Result<Vec<Result<BatchItemReport, XlsxToMdError>>, XlsxToMdError> {
+        let output_dir = output_dir.as_ref();
+        let mut paths = Vec::new();
+        for entry in std::fs::read_dir(input_dir)? {
+            let path = entry?.path();
+            let is_workbook = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| WORKBOOK_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+            if is_workbook && path.is_file() {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        std::fs::create_dir_all(output_dir)?;
+
+        // 大文字・小文字を区別しないファイルシステムを考慮し、小文字で重複を判定する
+        let stem_key = |path: &Path| {
+            path.file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_lowercase()
+        };
+        let mut stem_counts: HashMap<String, usize> = HashMap::new();
+        for path in &paths {
+            *stem_counts.entry(stem_key(path)).or_default() += 1;
+        }
+
+        let extension = self.converter.output_format().file_extension();
+        let jobs = paths
+            .into_iter()
+            .map(|path| {
+                let base = if stem_counts[&stem_key(&path)] > 1 {
+                    path.file_name()
+                } else {
+                    path.file_stem()
+                };
+                let base = base.unwrap_or_default().to_string_lossy();
+                let destination = output_dir.join(format!("{}.{}", base, extension));
+                BatchJob {
+                    input: BatchInput::path(path),
+                    destination: Some(destination),
+                }
+            })
+            .collect();
+        Ok(self.run(jobs))
+    }
+
+    /// 入力を並列に変換（内部ヘルパー）
+    ///
+    /// 出力先のパスを指定した入力は、変換結果をファイルに書き出します。
+    fn run(&self, jobs: Vec<BatchJob>) -> Vec<Result<BatchItemReport, XlsxToMdError>> {
+        // 並列処理のワーカーが入力を取り出せるよう、要素ごとに排他制御する
+        let jobs: Vec<Mutex<Option<BatchJob>>> =
+            jobs.into_iter().map(|job| Mutex::new(Some(job))).collect();
+        let mut results = Vec::with_capacity(jobs.len());
+        let completed = crate::ordered::for_each_ordered(
+            &jobs,
+            |_, job| {
+                let job = job.lock().ok().and_then(|mut job| job.take());
+                Ok::<_, XlsxToMdError>(match job {
+                    Some(job) => self.convert_one(job),
+                    None => Err(XlsxToMdError::Internal(
+                        "batch input was already consumed".to_string(),
+                    )),
+                })
+            },
+            |_, result| {
+                results.push(result);
+                Ok(())
+            },
+        );
+        debug_assert!(completed.is_ok());
+        results
+    }
+
+    /// 1つの入力を変換（内部ヘルパー）
+    fn convert_one(&self, job: BatchJob) -> Result<BatchItemReport, XlsxToMdError> {
+        let BatchJob {
+            input: BatchInput { name, source },
+            destination,
+        } = job;
+        let reader: Box<dyn ReadSeek + Send> = match source {
+            BatchSource::Path(path) => Box::new(BufReader::new(File::open(path)?)),
+            BatchSource::Reader(reader) => reader,
+        };
+
+        match destination {
+            Some(path) => {
+                let file = File::create(&path)?;
+                let report = crate::convert::catch_panic(|| {
+                    self.converter.convert_with_report(reader, file)
+                });
+                match report {
+                    Ok(report) => Ok(BatchItemReport {
+                        name,
+                        output: String::new(),
+                        output_path: Some(path),
+                        report,
+                    }),
+                    Err(e) => {
+                        // 変換に失敗したファイルの書きかけの出力は残さない
+                        let _ = std::fs::remove_file(&path);
+                        Err(e)
+                    }
+                }
+            }
+            None => {
+                let mut output = Vec::new();
+                let report = crate::convert::catch_panic(|| {
+                    self.converter.convert_with_report(reader, &mut output)
+                })?;
+                let output = String::from_utf8(output).map_err(|e| {
+                    XlsxToMdError::Internal(format!("conversion output is not UTF-8: {}", e))
+                })?;
+                Ok(BatchItemReport {
+                    name,
+                    output,
+                    output_path: None,
+                    report,
+                })
+            }
+        }
+    }
+}
+
+impl From<Converter> for BatchConverter {
+    fn from(converter: Converter) -> Self {
+        Self::new(converter)
+    }
+}
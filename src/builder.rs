@@ -2,12 +2,30 @@
 //!
 //! Fluent Builder APIを提供し、`Converter`インスタンスを段階的に構築する。
 
-use crate::api::{DateFormat, FormulaMode, MergeStrategy, OutputFormat, SheetSelector};
-use crate::error::XlsxToMdError;
-use crate::types::CellRange;
+use crate::api::{
+    BlankRows, CellImageMode, ChartMode, ChunkHeader, ColumnSelector, CommentMode,
+    ConditionalFormats, ContentFilter, DataValidations, DateFormat, DuplicateSheets,
+    DuplicationPlacement, FormulaMode, HeaderUnits, HyperlinkMode, JsonCellMode, JsonDocumentMode,
+    JsonKeyMode, JsonStyle, MergeStrategy, NullFormulaPolicy, NumberOutput, OutputFormat,
+    OutputOverflow, PivotMode, Preset, ProtectedSheets, RowDedupe, SheetSelector, SortOrder,
+    TrimMode, Validation, ValidationPolicy, WrapStrategy,
+};
+use crate::error::{ConfigError, XlsxToMdError};
+use crate::expr::ComputedColumn;
+use crate::grid::{RowFilter, WidthLimit};
+use crate::images::{ExtractedImage, ImageSink};
+use crate::parser::MetadataParts;
+use crate::pipeline::{PipelineStage, Stage, Stages, TextTransformer};
+use crate::plugin::FormatHandle;
+use crate::progress::{ProgressEvent, ProgressHandle};
+use crate::security::SecurityLimits;
+use crate::types::{split_sheet_prefix, CellRange};
 use chrono::NaiveDate;
-use rayon::prelude::*;
-use std::io::{Cursor, Read, Seek, Write};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// 変換処理の設定を保持する内部構造体
 #[derive(Debug, Clone)]
@@ -18,20 +36,465 @@ pub(crate) struct ConversionConfig {
     /// セル結合戦略
     pub merge_strategy: MergeStrategy,
 
+    /// 縦方向に結合されたセルの値を配置する行（`MergeStrategy::DataDuplication`の場合のみ）
+    pub duplication_placement: DuplicationPlacement,
+
     /// 日付形式
     pub date_format: DateFormat,
 
     /// 数式出力モード
     pub formula_mode: FormulaMode,
 
+    /// `FormulaMode::Both`で結果値と数式を組み合わせるテンプレート（`{value}`と`{formula}`を置換）
+    pub formula_template: String,
+
     /// 非表示要素を含めるか
     pub include_hidden: bool,
 
+    /// 高さ・幅が0の行・列を非表示として扱うか
+    pub zero_size_as_hidden: bool,
+
     /// セル範囲制限（Option: Noneの場合は全範囲）
     pub range: Option<CellRange>,
 
+    /// シート単位のセル範囲制限（シート名, 範囲）（指定したシートでは`range`に代えて適用）
+    pub sheet_ranges: Vec<(String, CellRange)>,
+
+    /// A1形式の範囲指定として解析できなかった文字列（`build()`でエラー）
+    pub invalid_range: Option<String>,
+
+    /// 名前付き範囲による範囲制限（Option: Noneの場合は`range`とシート選択を使用）
+    pub named_range: Option<String>,
+
     /// 出力フォーマット
     pub output_format: OutputFormat,
+
+    /// 名前で指定した独自の出力フォーマット（`build()`で`format_plugin`に解決）
+    pub format_name: Option<String>,
+
+    /// 独自の出力フォーマット（Noneの場合は`output_format`で出力）
+    pub format_plugin: Option<FormatHandle>,
+
+    /// シート単位で上書きする出力フォーマット（シート名, 出力フォーマット）
+    pub sheet_formats: Vec<(String, OutputFormat)>,
+
+    /// シート間の区切り（Noneの場合はフォーマットごとの既定の区切り）
+    pub sheet_separator: Option<String>,
+
+    /// 複数シートのHTML文書の先頭にシートへのナビゲーションを出力するか
+    pub include_html_navigation: bool,
+
+    /// 出力フォーマットごとの数値出力方式（未指定のフォーマットはデフォルト値）
+    pub number_outputs: Vec<(OutputFormat, NumberOutput)>,
+
+    /// JSON出力におけるセルの表現方式
+    pub json_cell_mode: JsonCellMode,
+
+    /// JSON出力における行オブジェクトのキー
+    pub json_key_mode: JsonKeyMode,
+
+    /// 複数シートのJSON文書の構造
+    pub json_document_mode: JsonDocumentMode,
+
+    /// JSONの書式（NDJSONでは常に`JsonStyle::Minified`）
+    pub json_style: JsonStyle,
+
+    /// 見出しに含まれる単位の扱い
+    pub header_units: HeaderUnits,
+
+    /// シート単位のメタデータ（フロントマター）を出力に含めるか
+    pub include_metadata: bool,
+
+    /// フロントマターに追加する独自の項目（キーの順に出力）
+    pub front_matter_extra: BTreeMap<String, String>,
+
+    /// クレートのバージョンと設定のハッシュ値（フィンガープリント）を出力に含めるか
+    pub include_fingerprint: bool,
+
+    /// シート内のハイパーリンクの一覧を出力に含めるか
+    pub include_link_inventory: bool,
+
+    /// セルの名前付きスタイル名を出力に含めるか（型付きJSONのみ）
+    pub include_cell_styles: bool,
+
+    /// セル単位のフォントの書式（太字・斜体・取り消し線）を出力に反映するか（Markdown・HTMLのみ）
+    pub include_font_styles: bool,
+
+    /// セルの背景色（塗りつぶしの色）を出力に反映するか（HTMLのみ）
+    pub include_fill_colors: bool,
+
+    /// 元のシート上のセル参照（A1形式）を出力に含めるか（JSON・Markdownのみ）
+    pub cell_references: bool,
+
+    /// 見出しスタイルの行で表を区切るか（Markdownのみ）
+    pub split_on_heading_styles: bool,
+
+    /// 値のない数式セルの扱い
+    pub null_formula_policy: NullFormulaPolicy,
+
+    /// 肥大化した使用範囲を表示内容のある範囲に自動縮小するか
+    pub auto_shrink_used_range: bool,
+
+    /// ハイパーリンクの出力方式
+    pub hyperlink_mode: HyperlinkMode,
+
+    /// 表示テキストとして短縮するURLの文字数の上限（Markdownのみ）
+    pub url_shortening: Option<usize>,
+
+    /// セル内の画像の出力方式
+    pub cell_image_mode: CellImageMode,
+
+    /// 図形として配置された画像の抽出先（Option: Noneの場合は抽出しない）
+    pub image_sink: Option<ImageSink>,
+
+    /// 同一内容の行の集約方式
+    pub row_dedupe: RowDedupe,
+
+    /// 空行の扱い
+    pub blank_rows: BlankRows,
+
+    /// 空の行・列の刈り込み方式
+    pub trim_empty: TrimMode,
+
+    /// 内容がほぼ同一のシートの扱い
+    pub duplicate_sheets: DuplicateSheets,
+
+    /// 出力サイズの上限（バイト、シートの出力の合計）
+    pub max_output_size: Option<u64>,
+
+    /// 出力サイズが上限を超えた場合の扱い
+    pub output_overflow: OutputOverflow,
+
+    /// シート単位の上限（行数, 表示内容のあるセルの数, 処理時間）
+    pub per_sheet_limits: Option<(usize, usize, Duration)>,
+
+    /// 出力する表の行数の上限（見出しの行を含む）
+    pub max_rows: Option<usize>,
+
+    /// 出力する表の表示内容のあるセルの数の上限
+    pub max_cells: Option<usize>,
+
+    /// 行数・セル数の上限で省略した行数を示す注記（`{count}`を省略した行数に置換）
+    pub truncation_notice: String,
+
+    /// すべての行が同じ値を持つ列を表から除去し、表の前に出力するか
+    pub constant_column_folding: bool,
+
+    /// XMLパーツの不正なUTF-8を置換文字に置き換えて変換を継続するか
+    pub encoding_fallback: bool,
+
+    /// 直前の値で空のセルを埋める列（指定順）
+    pub forward_fill: Vec<ColumnSelector>,
+
+    /// 行の内容による絞り込み条件
+    pub content_filter: ContentFilter,
+
+    /// 末尾に追加する計算列（列名, 式）（追加順）
+    pub computed_columns: Vec<(String, String)>,
+
+    /// 行の並べ替え（並べ替えに使用する列, 順序, 数値を考慮して比較するか）
+    pub sort: Option<(ColumnSelector, SortOrder, bool)>,
+
+    /// 列の値の検証ルール（指定順）
+    pub validations: Vec<Validation>,
+
+    /// 列の値の検証に違反した場合の扱い
+    pub validation_policy: ValidationPolicy,
+
+    /// 保護されたシートの扱い
+    pub protected_sheets: ProtectedSheets,
+
+    /// 共有ブックの変更履歴を付録として出力に含めるか
+    pub include_change_history: bool,
+
+    /// 条件付き書式のルールの扱い
+    pub conditional_formats: ConditionalFormats,
+
+    /// セルのコメントの扱い
+    pub comment_mode: CommentMode,
+
+    /// セルの入力規則の扱い
+    pub data_validations: DataValidations,
+
+    /// シートに配置されたグラフの扱い
+    pub chart_mode: ChartMode,
+
+    /// シートに配置されたピボットテーブルの扱い
+    pub pivot_mode: PivotMode,
+
+    /// Markdownの表の列幅の上限（表示幅, 上限を超えるセルの扱い）
+    pub column_width_limit: Option<(usize, WrapStrategy)>,
+
+    /// 列幅の上限で切り詰めたセルの末尾に付加する文字列
+    pub truncation_marker: String,
+
+    /// Markdownの表を分割する行数（1つの表のデータ行数の上限, 見出し行の扱い）
+    pub table_chunk_rows: Option<(usize, ChunkHeader)>,
+
+    /// 各ステージの完了後に実行する独自の処理
+    pub stages: Stages,
+
+    /// 進捗の通知先
+    pub progress: Option<ProgressHandle>,
+
+    /// 進捗（`ProgressEvent::RowsProcessed`）を通知する行数の間隔
+    pub progress_interval: usize,
+
+    /// セキュリティ制限
+    pub security: SecurityLimits,
+}
+
+impl ConversionConfig {
+    /// 出力に影響する設定のハッシュ値（XXH3 64bit）を計算
+    ///
+    /// 指定順に依存しないよう、フォーマットごとの設定は並べ替えてからハッシュ化します。
+    /// 出力内容に影響しないセキュリティ制限（結合範囲の展開の上限を除く）と
+    /// フィンガープリント出力の有無と進捗の通知先は含めません。
+    /// 独自のステージやテキスト変換は、登録の有無と対象ステージのみを反映します。
+    pub fn fingerprint_hash(&self) -> u64 {
+        let mut canonical = self.clone();
+        canonical.sheet_formats.sort_by(|(a, _), (b, _)| a.cmp(b));
+        canonical.sheet_ranges.sort_by(|(a, _), (b, _)| a.cmp(b));
+        canonical
+            .number_outputs
+            .sort_by_key(|(format, _)| format.file_extension());
+        canonical.security = SecurityLimits {
+            max_merged_cells: self.security.max_merged_cells,
+            ..SecurityLimits::default()
+        };
+        canonical.include_fingerprint = false;
+        canonical.progress = None;
+        canonical.progress_interval = 0;
+        // 検証は出力を変更しない
+        canonical.validations.clear();
+        canonical.validation_policy = ValidationPolicy::Report;
+
+        xxhash_rust::xxh3::xxh3_64(format!("{:?}", canonical).as_bytes())
+    }
+
+    /// 進捗の通知先が設定されている場合にイベントを通知
+    pub(crate) fn emit_progress(&self, event: ProgressEvent<'_>) {
+        if let Some(progress) = &self.progress {
+            progress.emit(event);
+        }
+    }
+
+    /// 変換に必要なXMLメタデータの要素を取得
+    ///
+    /// 非表示要素を含める場合は非表示行・列の情報を、`HyperlinkMode::TextOnly`でリンク一覧を
+    /// 出力しない場合はハイパーリンクの情報を解析しません。
+    /// セルの名前付きスタイルは、スタイル名の出力か見出しスタイルによる区切りを行う場合のみ解析します。
+    /// セル内の画像は、`CellImageMode::Value`の場合は解析しません。
+    /// 動的配列数式の溢れた範囲は、`FormulaMode::Formula`の場合のみ解析します。
+    /// シートの保護は、`ProtectedSheets::Convert`の場合は解析しません。
+    /// 共有ブックの変更履歴は、変更履歴を出力に含める場合のみ解析します。
+    /// 条件付き書式は、`ConditionalFormats::Ignore`の場合は解析しません。
+    /// セルのコメントは、`CommentMode::Skip`の場合は解析しません。
+    /// 入力規則は、`DataValidations::Ignore`の場合は解析しません。
+    /// グラフは、`ChartMode::Skip`の場合は解析しません。
+    /// ピボットテーブルは、`PivotMode::Cells`の場合は解析しません。
+    pub fn metadata_parts(&self) -> MetadataParts {
+        let mut parts = MetadataParts::ALL;
+        if self.include_hidden {
+            parts = parts.without(MetadataParts::HIDDEN);
+        }
+        if self.hyperlink_mode == HyperlinkMode::TextOnly && !self.include_link_inventory {
+            parts = parts.without(MetadataParts::HYPERLINKS);
+        }
+        if !self.include_cell_styles && !self.split_on_heading_styles {
+            parts = parts.without(MetadataParts::CELL_STYLES);
+        }
+        if !self.include_font_styles {
+            parts = parts.without(MetadataParts::FONT_STYLES);
+        }
+        if !self.include_fill_colors {
+            parts = parts.without(MetadataParts::FILL_COLORS);
+        }
+        if self.cell_image_mode == CellImageMode::Value {
+            parts = parts.without(MetadataParts::CELL_IMAGES);
+        }
+        if self.image_sink.is_none() {
+            parts = parts.without(MetadataParts::DRAWINGS);
+        }
+        if self.formula_mode != FormulaMode::Formula {
+            parts = parts.without(MetadataParts::SPILL_RANGES);
+        }
+        if self.protected_sheets == ProtectedSheets::Convert {
+            parts = parts.without(MetadataParts::PROTECTION);
+        }
+        if !self.include_change_history {
+            parts = parts.without(MetadataParts::REVISIONS);
+        }
+        if self.conditional_formats == ConditionalFormats::Ignore {
+            parts = parts.without(MetadataParts::CONDITIONAL_FORMATS);
+        }
+        if !self.include_metadata {
+            parts = parts.without(MetadataParts::VISUALS);
+        }
+        if self.comment_mode == CommentMode::Skip {
+            parts = parts.without(MetadataParts::COMMENTS);
+        }
+        if self.data_validations == DataValidations::Ignore {
+            parts = parts.without(MetadataParts::DATA_VALIDATIONS);
+        }
+        if self.chart_mode == ChartMode::Skip {
+            parts = parts.without(MetadataParts::CHARTS);
+        }
+        if self.pivot_mode == PivotMode::Cells {
+            parts = parts.without(MetadataParts::PIVOT_TABLES);
+        }
+        parts
+    }
+
+    /// JSONを空白と改行を含めずに1行で出力するか
+    ///
+    /// `JsonStyle::Minified`の場合と、NDJSON（`JsonDocumentMode::Lines`）の場合に`true`を返します。
+    pub fn json_minified(&self) -> bool {
+        self.json_style == JsonStyle::Minified || self.json_document_mode == JsonDocumentMode::Lines
+    }
+
+    /// Markdownのフロントマターを出力するか
+    ///
+    /// メタデータを含める場合、またはフロントマターの追加項目がある場合に`true`を返します。
+    pub fn front_matter_enabled(&self) -> bool {
+        self.include_metadata || !self.front_matter_extra.is_empty()
+    }
+
+    /// ハイパーリンクのURLを脚注として出力するか
+    ///
+    /// `HyperlinkMode::Footnote`かつMarkdown形式の場合のみ`true`を返します。
+    pub fn hyperlink_footnotes(&self) -> bool {
+        self.hyperlink_mode == HyperlinkMode::Footnote
+            && self.output_format == OutputFormat::Markdown
+            && self.format_plugin.is_none()
+    }
+
+    /// セルのコメントを脚注として出力するか
+    ///
+    /// `CommentMode::Footnotes`かつMarkdown形式の場合のみ`true`を返します。
+    pub fn comment_footnotes(&self) -> bool {
+        self.comment_mode == CommentMode::Footnotes
+            && self.output_format == OutputFormat::Markdown
+            && self.format_plugin.is_none()
+    }
+
+    /// シートをグリッドを構築せずに行単位で出力できる設定か
+    ///
+    /// CSVと、列幅の上限・表の分割・見出しスタイルによる区切り・セル参照のないMarkdownの表で、
+    /// グリッド全体を必要とする処理（空行の除去、並べ替え、計算列、行数の上限など）、
+    /// 独自のステージ、値のない数式セルのマーカー、脚注を使用しない場合に`true`を返します。
+    /// 行の絞り込み（`ContentFilter`）と計算列は`Converter`で判定します。
+    pub fn streams_rows(&self) -> bool {
+        let plain_table = match self.output_format {
+            OutputFormat::Csv => true,
+            OutputFormat::Markdown => {
+                !self.split_on_heading_styles
+                    && self.column_width_limit.is_none()
+                    && self.table_chunk_rows.is_none()
+                    && !self.cell_references
+            }
+            _ => false,
+        };
+        plain_table
+            && self.format_plugin.is_none()
+            && self.formula_mode == FormulaMode::CachedValue
+            && self.null_formula_policy == NullFormulaPolicy::Empty
+            && self.stages.is_empty()
+            && !self.include_metadata
+            && !self.include_link_inventory
+            && !self.hyperlink_footnotes()
+            && self.image_sink.is_none()
+            && self.protected_sheets != ProtectedSheets::Flag
+            && self.conditional_formats != ConditionalFormats::Annotate
+            && self.conditional_formats != ConditionalFormats::Marker
+            && self.comment_mode == CommentMode::Skip
+            && self.data_validations != DataValidations::Annotate
+            && self.chart_mode == ChartMode::Skip
+            && self.pivot_mode == PivotMode::Cells
+            && self.blank_rows == BlankRows::Keep
+            && self.trim_empty == TrimMode::None
+            && self.forward_fill.is_empty()
+            && self.sort.is_none()
+            && self.validations.is_empty()
+            && !self.constant_column_folding
+            && self.row_dedupe == RowDedupe::Off
+            && self.header_units == HeaderUnits::Off
+            && self.per_sheet_limits.is_none()
+            && self.max_rows.is_none()
+            && self.max_cells.is_none()
+    }
+
+    /// Markdownの表に適用する列幅の上限を取得
+    pub fn markdown_width_limit(&self) -> Option<WidthLimit> {
+        self.column_width_limit.map(|(width, strategy)| WidthLimit {
+            width,
+            strategy,
+            marker: self.truncation_marker.clone(),
+        })
+    }
+
+    /// 指定したシートに適用する出力フォーマットを取得
+    ///
+    /// `sheet_formats`に指定がない場合は`output_format`を返します。
+    pub fn output_format_for(&self, sheet_name: &str) -> OutputFormat {
+        self.sheet_formats
+            .iter()
+            .find(|(name, _)| name == sheet_name)
+            .map(|(_, format)| *format)
+            .unwrap_or(self.output_format)
+    }
+
+    /// 指定したシートに適用するセル範囲制限を取得
+    ///
+    /// `sheet_ranges`に指定がない場合は`range`を返します。
+    pub fn range_for(&self, sheet_name: &str) -> Option<CellRange> {
+        self.sheet_ranges
+            .iter()
+            .find(|(name, _)| name == sheet_name)
+            .map(|(_, range)| *range)
+            .or(self.range)
+    }
+
+    /// 指定したシートに適用する設定を取得
+    ///
+    /// シート単位の出力フォーマットまたはセル範囲制限が指定されている場合のみ、
+    /// `output_format`・`range`を置き換えた設定の複製を返します。
+    pub fn for_sheet(&self, sheet_name: &str) -> Cow<'_, ConversionConfig> {
+        let format = self.output_format_for(sheet_name);
+        let range = self.range_for(sheet_name);
+        if format == self.output_format && range == self.range {
+            Cow::Borrowed(self)
+        } else {
+            let mut config = self.clone();
+            if format != self.output_format {
+                config.output_format = format;
+                config.format_plugin = None;
+            }
+            config.range = range;
+            Cow::Owned(config)
+        }
+    }
+
+    /// 現在の出力フォーマットに適用する数値出力方式を取得
+    ///
+    /// 明示的に指定されていない場合、CSV/JSON/XMLは`NumberOutput::Raw`、
+    /// Markdown/HTMLは`NumberOutput::Formatted`を返します。
+    /// 型付きJSON（`JsonCellMode::Typed`）では元の値を`raw`として別に出力するため、
+    /// 表示文字列には常に`NumberOutput::Formatted`を使用します。
+    pub fn number_output(&self) -> NumberOutput {
+        if self.output_format == OutputFormat::Json && self.json_cell_mode == JsonCellMode::Typed {
+            return NumberOutput::Formatted;
+        }
+
+        self.number_outputs
+            .iter()
+            .find(|(format, _)| *format == self.output_format)
+            .map(|(_, mode)| *mode)
+            .unwrap_or(match self.output_format {
+                OutputFormat::Json | OutputFormat::Csv | OutputFormat::Xml => NumberOutput::Raw,
+                _ => NumberOutput::Formatted,
+            })
+    }
 }
 
 impl Default for ConversionConfig {
@@ -39,11 +502,75 @@ impl Default for ConversionConfig {
         Self {
             sheet_selector: SheetSelector::All,
             merge_strategy: MergeStrategy::DataDuplication,
+            duplication_placement: DuplicationPlacement::All,
             date_format: DateFormat::Iso8601,
             formula_mode: FormulaMode::CachedValue,
+            formula_template: "{value} (={formula})".to_string(),
             include_hidden: false,
+            zero_size_as_hidden: false,
             range: None,
+            sheet_ranges: Vec::new(),
+            invalid_range: None,
+            named_range: None,
             output_format: OutputFormat::Markdown,
+            format_name: None,
+            format_plugin: None,
+            sheet_formats: Vec::new(),
+            sheet_separator: None,
+            include_html_navigation: false,
+            number_outputs: Vec::new(),
+            json_cell_mode: JsonCellMode::Plain,
+            json_key_mode: JsonKeyMode::ColumnLetter,
+            json_document_mode: JsonDocumentMode::Sheets,
+            json_style: JsonStyle::Pretty,
+            header_units: HeaderUnits::Off,
+            include_metadata: false,
+            front_matter_extra: BTreeMap::new(),
+            include_fingerprint: false,
+            include_link_inventory: false,
+            include_cell_styles: false,
+            include_font_styles: false,
+            include_fill_colors: false,
+            cell_references: false,
+            split_on_heading_styles: false,
+            null_formula_policy: NullFormulaPolicy::Empty,
+            auto_shrink_used_range: false,
+            hyperlink_mode: HyperlinkMode::Inline,
+            url_shortening: None,
+            cell_image_mode: CellImageMode::Placeholder("[image]".to_string()),
+            image_sink: None,
+            row_dedupe: RowDedupe::Off,
+            blank_rows: BlankRows::Keep,
+            trim_empty: TrimMode::None,
+            duplicate_sheets: DuplicateSheets::Keep,
+            max_output_size: None,
+            output_overflow: OutputOverflow::Error,
+            per_sheet_limits: None,
+            max_rows: None,
+            max_cells: None,
+            truncation_notice: "… {count} more rows omitted".to_string(),
+            constant_column_folding: false,
+            encoding_fallback: false,
+            forward_fill: Vec::new(),
+            content_filter: ContentFilter::default(),
+            computed_columns: Vec::new(),
+            sort: None,
+            validations: Vec::new(),
+            validation_policy: ValidationPolicy::Report,
+            protected_sheets: ProtectedSheets::Convert,
+            include_change_history: false,
+            conditional_formats: ConditionalFormats::Ignore,
+            comment_mode: CommentMode::Skip,
+            data_validations: DataValidations::Ignore,
+            chart_mode: ChartMode::Skip,
+            pivot_mode: PivotMode::Cells,
+            column_width_limit: None,
+            table_chunk_rows: None,
+            truncation_marker: "…".to_string(),
+            stages: Stages::default(),
+            progress: None,
+            progress_interval: 1000,
+            security: SecurityLimits::default(),
         }
     }
 }
@@ -102,6 +629,103 @@ impl ConverterBuilder {
         }
     }
 
+    /// 環境変数からデフォルト設定を読み込んだビルダーインスタンスを生成する
+    ///
+    /// コンテナ上のサービスなどで、設定をコードに埋め込まずに環境変数で与えるためのコンストラクタです。
+    /// 環境変数の値は`new()`（または`XLSXZERO_PRESET`で指定したプリセット）のデフォルト値を上書きし、
+    /// 戻り値のビルダーに対する`with_*`メソッドの呼び出しは環境変数の値をさらに上書きします。
+    /// 設定されていない環境変数は無視されます。
+    ///
+    /// # 環境変数
+    ///
+    /// | 環境変数 | 値 |
+    /// |---|---|
+    /// | `XLSXZERO_PRESET` | `rag`, `faithful`, `compact`, `machine_readable` |
+    /// | `XLSXZERO_FORMAT` | `markdown`, `html`, `json`, `csv`, `xml`、登録された独自のフォーマット名 |
+    /// | `XLSXZERO_MERGE_STRATEGY` | `data_duplication`, `html_fallback` |
+    /// | `XLSXZERO_DATE_FORMAT` | `iso8601`、またはカスタム形式文字列（例: `%Y/%m/%d`） |
+    /// | `XLSXZERO_FORMULA_MODE` | `cached_value`, `formula`, `both` |
+    /// | `XLSXZERO_INCLUDE_HIDDEN` | `true`, `false`（`1`, `0`, `yes`, `no`も可） |
+    /// | `XLSXZERO_INCLUDE_METADATA` | `true`, `false`（`1`, `0`, `yes`, `no`も可） |
+    /// | `XLSXZERO_JSON_CELL_MODE` | `plain`, `typed` |
+    /// | `XLSXZERO_MAX_INPUT_SIZE` | 入力ファイルの最大サイズ（バイト数） |
+    ///
+    /// 値の大文字・小文字は区別せず、`-`は`_`と同じものとして扱います。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ConverterBuilder)`: 環境変数の値がすべて有効な場合
+    /// * `Err(XlsxToMdError::Config)`: 不正な値の環境変数がある場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, SheetSelector};
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// // XLSXZERO_FORMAT=json などを環境変数で与え、シート選択のみコードで指定
+    /// let converter = ConverterBuilder::from_env()?
+    ///     .with_sheet_selector(SheetSelector::Index(0))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Result<Self, XlsxToMdError> {
+        crate::env::builder_from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// 用途別のプリセット設定を持つビルダーインスタンスを生成する
+    ///
+    /// 各プリセットの設定内容は`Preset`のドキュメントを参照してください。
+    /// プリセットに含まれない設定項目は`new()`と同じデフォルト値になります。
+    ///
+    /// # 引数
+    ///
+    /// * `preset: Preset`: 適用するプリセット
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, Preset, SheetSelector};
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// // RAG向けの設定に、シート選択のみ上書き
+    /// let converter = ConverterBuilder::preset(Preset::Rag)
+    ///     .with_sheet_selector(SheetSelector::Index(0))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn preset(preset: Preset) -> Self {
+        let builder = Self::new();
+        match preset {
+            Preset::Rag => builder
+                .with_output_format(OutputFormat::Markdown)
+                .with_merge_strategy(MergeStrategy::DataDuplication)
+                .include_hidden(false)
+                .include_metadata(true)
+                .trim_empty(TrimMode::All)
+                .with_row_dedupe(RowDedupe::ConsecutiveWithCount)
+                .with_table_chunk_rows(50, ChunkHeader::Repeat),
+            Preset::Faithful => builder
+                .with_output_format(OutputFormat::Markdown)
+                .with_merge_strategy(MergeStrategy::HtmlFallback)
+                .include_hidden(true)
+                .with_number_output(OutputFormat::Markdown, NumberOutput::Formatted),
+            Preset::Compact => builder
+                .with_output_format(OutputFormat::Markdown)
+                .with_merge_strategy(MergeStrategy::DataDuplication)
+                .include_hidden(false)
+                .with_number_output(OutputFormat::Markdown, NumberOutput::Raw)
+                .auto_shrink_used_range(true),
+            Preset::MachineReadable => builder
+                .with_output_format(OutputFormat::Json)
+                .with_json_cell_mode(JsonCellMode::Typed)
+                .include_hidden(false)
+                .include_metadata(true),
+        }
+    }
+
     /// 変換対象のシートを選択する
     ///
     /// # 引数
@@ -149,6 +773,29 @@ impl ConverterBuilder {
         self
     }
 
+    /// 縦方向に結合されたセルの値を配置する行を指定する
+    ///
+    /// `MergeStrategy::DataDuplication`の場合に、複数行にまたがる結合セル（例: 縦に結合した
+    /// 分類のラベル）の値をすべての行に複製するか、最初の行・中央の行のみに出力するかを指定します。
+    /// `MergeStrategy::HtmlFallback`の場合は無視されます。
+    ///
+    /// # 引数
+    ///
+    /// * `placement: DuplicationPlacement`: 値を配置する行（デフォルト: `DuplicationPlacement::All`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, DuplicationPlacement};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_duplication_placement(DuplicationPlacement::FirstOnly);
+    /// ```
+    pub fn with_duplication_placement(mut self, placement: DuplicationPlacement) -> Self {
+        self.config.duplication_placement = placement;
+        self
+    }
+
     /// 日付の出力形式を指定する
     ///
     /// # 引数
@@ -192,6 +839,28 @@ impl ConverterBuilder {
         self
     }
 
+    /// `FormulaMode::Both`で結果値と数式を組み合わせるテンプレートを指定する
+    ///
+    /// `{value}`はフォーマット済みの結果値、`{formula}`は先頭の`=`を除いた数式に置き換えられます。
+    ///
+    /// # 引数
+    ///
+    /// * `template` - テンプレート（デフォルト: `{value} (={formula})`、改行を含む場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, FormulaMode};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_formula_mode(FormulaMode::Both)
+    ///     .with_formula_template("{value} [={formula}]");
+    /// ```
+    pub fn with_formula_template(mut self, template: impl Into<String>) -> Self {
+        self.config.formula_template = template.into();
+        self
+    }
+
     /// 非表示要素（非表示シート、行、列）を出力に含めるかを指定する
     ///
     /// # 引数
@@ -200,6 +869,10 @@ impl ConverterBuilder {
     ///   * `true`: 非表示要素を含める
     ///   * `false`: 非表示要素をスキップ（デフォルト）
     ///
+    /// `true`の場合、非表示（`hidden`）・完全に非表示（`veryHidden`）のシートは見出しに
+    /// 表示状態が付記されます（例: `# SecretSheet (hidden)`、`# Macros (very hidden)`）。
+    /// 読み手や後段のポリシーフィルタが、隠された領域の内容であることを識別できます。
+    ///
     /// # Phase I制限事項
     ///
     /// Phase Iでは `calamine` ライブラリの制限により、非表示行・非表示列の情報を取得できません。
@@ -220,62 +893,1989 @@ impl ConverterBuilder {
         self
     }
 
-    /// 処理対象のセル範囲を制限する
+    /// 高さが0の行・幅が0の列を非表示として扱うかを指定する
     ///
-    /// 範囲外のセルは無視されます。
+    /// `hidden="1"`を持たない行・列でも、高さ（`ht="0"`）・幅（`width="0"`）が0の場合は
+    /// Excel上では見えません。`true`の場合、これらの行・列を非表示の行・列と同じく扱い、
+    /// `include_hidden(false)`（デフォルト）では出力から除外します。
+    /// 非表示として扱った行数・列数は`SheetReport::zero_size_rows`・`zero_size_cols`に記録されます。
+    ///
+    /// # 引数
+    ///
+    /// * `enabled: bool`:
+    ///   * `true`: 高さ・幅が0の行・列を非表示として扱う
+    ///   * `false`: 高さ・幅に関わらず出力に含める（デフォルト）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_zero_size_as_hidden(true);
+    /// ```
+    pub fn with_zero_size_as_hidden(mut self, enabled: bool) -> Self {
+        self.config.zero_size_as_hidden = enabled;
+        self
+    }
+
+    /// シート単位のメタデータを出力に含めるかを指定する
+    ///
+    /// メタデータには、シート名、シート内テキストの主要な文字体系
+    /// （`latin`、`cjk`、`cyrillic`、`other`）、およびシート内容のハッシュ値
+    /// （`xxh3:`で始まる16桁の16進数）が含まれます。
+    /// 多言語のRAGパイプラインで、文書を適切な埋め込みモデルに振り分ける用途や、
+    /// 複数ファイル間で同一内容のシートを重複排除する用途を想定しています。
+    ///
+    /// ハッシュ値はセルの座標と元の値から計算されるため、書式や出力フォーマットに依存しません。
+    ///
+    /// スパークラインやデータバーのあるシートでは、変換で失われるそれらの描画位置（`range`）と
+    /// 参照元の範囲（`source`）を`sparklines`・`data_bars`として含めます。
+    ///
+    /// * Markdown: 文書の先頭にYAMLフロントマターとして出力
+    /// * JSON: 各シートの`metadata`オブジェクトとして出力
+    /// * HTML / CSV: 出力されません
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`:
+    ///   * `true`: メタデータを含める
+    ///   * `false`: メタデータを含めない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// ---
+    /// sheets:
+    ///   - name: "Sheet1"
+    ///     script: cjk
+    ///     content_hash: "xxh3:0123456789abcdef"
+    /// ---
+    ///
+    /// # Sheet1
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_metadata(true);
+    /// ```
+    pub fn include_metadata(mut self, include: bool) -> Self {
+        self.config.include_metadata = include;
+        self
+    }
+
+    /// Markdownのフロントマターに独自の項目を追加する
+    ///
+    /// テナントID、取り込み元のシステム、取り込み日時など、出力文書に付与したい来歴情報を
+    /// 後処理なしでフロントマターに含めるために使用します。項目はキーの順に、
+    /// シートのメタデータ（`sheets`）より前に出力されます。値は常に文字列として出力されます。
+    /// 複数回呼び出した場合は項目が追加され、同じキーは後から指定した値で上書きされます。
+    ///
+    /// 追加の項目がある場合、`include_metadata(false)`でもフロントマターを出力します
+    /// （`sheets`は含まれません）。Markdown以外の形式では出力されません。
+    /// キーが空の場合と`sheets`の場合は、`build()`時に`XlsxToMdError::Config`を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `extra: impl IntoIterator<Item = (String, String)>`: 追加する項目（キー, 値）
+    ///
+    /// # 出力例
+    ///
+    /// ```markdown
+    /// ---
+    /// ingested_at: "2025-01-01T00:00:00Z"
+    /// tenant_id: "acme"
+    /// ---
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use std::collections::HashMap;
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let extra = HashMap::from([
+    ///     ("tenant_id".to_string(), "acme".to_string()),
+    ///     ("ingested_at".to_string(), "2025-01-01T00:00:00Z".to_string()),
+    /// ]);
+    /// let builder = ConverterBuilder::new().with_front_matter_extra(extra);
+    /// ```
+    pub fn with_front_matter_extra(
+        mut self,
+        extra: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.config.front_matter_extra.extend(extra);
+        self
+    }
+
+    /// 変換に使用したバージョンと設定のフィンガープリントを出力に含めるかを指定する
+    ///
+    /// フィンガープリントは、クレートのバージョンと出力に影響する設定のハッシュ値からなります。
+    /// 変換結果をキャッシュする場合に、バージョンアップや設定変更による再変換の要否を判定できます。
+    /// 値は`Converter::fingerprint()`でも取得できます。
+    ///
+    /// 出力フォーマットごとの出力先：
+    /// * Markdown / HTML / XML: 文書の先頭（フロントマターがある場合はその直後）にコメントとして出力
+    /// * CSV: 文書の先頭に`#`で始まるコメント行として出力
+    /// * JSON: 各シートの`fingerprint`オブジェクト（`version`、`config_hash`）として出力
     ///
     /// # 引数
     ///
-    /// * `start: (u32, u32)`: 開始セル座標 (row, col)（0始まり）
-    /// * `end: (u32, u32)`: 終了セル座標 (row, col)（0始まり）
+    /// * `include: bool`:
+    ///   * `true`: フィンガープリントを含める
+    ///   * `false`: フィンガープリントを含めない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// <!-- xlsxzero 0.1.0 config:xxh3:0123456789abcdef -->
+    ///
+    /// # Sheet1
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_fingerprint(true);
+    /// ```
+    pub fn include_fingerprint(mut self, include: bool) -> Self {
+        self.config.include_fingerprint = include;
+        self
+    }
+
+    /// シート内のハイパーリンクの一覧（リンク一覧）を出力に含めるかを指定する
+    ///
+    /// 同じURLを参照するセルをまとめ、URLごとに参照元のセル（A1記法）を列挙します。
+    /// URLはシート内で最初に出現したセルの順（行優先）に並びます。
+    /// `HyperlinkMode`の設定に関係なく出力されます。
+    ///
+    /// 出力フォーマットごとの出力先：
+    /// * Markdown: 表の後（脚注セクションの前）に`## Links`の見出しと箇条書きで出力
+    /// * HTML: 表の後に`<h2>Links</h2>`の見出しとリストで出力
+    /// * CSV: 表の後に`#`で始まるコメント行として出力
+    /// * JSON: 各シートの`links`配列（`url`、`cells`）として出力
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`:
+    ///   * `true`: リンク一覧を含める
+    ///   * `false`: リンク一覧を含めない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// ## Links
+    ///
+    /// - https://example.com: A2, B5
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_link_inventory(true);
+    /// ```
+    pub fn include_link_inventory(mut self, include: bool) -> Self {
+        self.config.include_link_inventory = include;
+        self
+    }
+
+    /// セルの名前付きスタイル名を出力に含めるかを指定する
+    ///
+    /// "Heading 1"、"Input"、"Calculation"、"Warning Text"などの名前付きスタイル（セルのスタイル）は
+    /// セルの役割を表します。`true`の場合、型付きJSON（`JsonCellMode::Typed`）の各セルに
+    /// スタイル名を`style`として含めます。既定のスタイル（"Normal"）のセルには含めません。
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`:
+    ///   * `true`: スタイル名を含める
+    ///   * `false`: スタイル名を含めない（デフォルト）
+    ///
+    /// # 出力例（JSON）
+    ///
+    /// ```json
+    /// {"type": "number", "raw": 120, "formatted": "120", "style": "Input"}
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, JsonCellMode, OutputFormat};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_cell_mode(JsonCellMode::Typed)
+    ///     .include_cell_styles(true);
+    /// ```
+    pub fn include_cell_styles(mut self, include: bool) -> Self {
+        self.config.include_cell_styles = include;
+        self
+    }
+
+    /// セル単位のフォントの書式（太字・斜体・取り消し線）を出力に反映するかを指定する
+    ///
+    /// セル全体に設定された書式（xl/styles.xmlの`<fonts>`）を、Markdownでは表示文字列の
+    /// 書式（太字: `**text**`、斜体: `*text*`、取り消し線: `~~text~~`）として、HTMLでは
+    /// `<td>`要素の`class`属性（`bold`、`italic`、`strike`）として出力します。
+    /// リッチテキストのセルは、文字列の一部に設定された書式を優先します。
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`:
+    ///   * `true`: フォントの書式を反映する
+    ///   * `false`: フォントの書式を反映しない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// | **Item** | **Amount** |
+    /// |----------|------------|
+    /// | ~~Rent~~ | 1200       |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_font_styles(true);
+    /// ```
+    pub fn include_font_styles(mut self, include: bool) -> Self {
+        self.config.include_font_styles = include;
+        self
+    }
+
+    /// セルの背景色（塗りつぶしの色）を出力に反映するかを指定する（HTML形式のみ）
+    ///
+    /// セルの塗りつぶし（xl/styles.xmlの`<fills>`）の色を、`<td>`要素の`style`属性
+    /// （例: `style="background-color:#FFFF00"`）として出力します。
+    /// 色がRGBで指定された塗りつぶしのみ対象とし、テーマの色・インデックスの色は出力しません。
+    /// Markdown形式・JSON形式・CSV形式では無視されます。
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`:
+    ///   * `true`: 背景色を反映する
+    ///   * `false`: 背景色を反映しない（デフォルト）
+    ///
+    /// # 出力例（HTML）
+    ///
+    /// ```html
+    /// <td style="background-color:#FFFF00">Overdue</td>
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Html)
+    ///     .include_fill_colors(true);
+    /// ```
+    pub fn include_fill_colors(mut self, include: bool) -> Self {
+        self.config.include_fill_colors = include;
+        self
+    }
+
+    /// 元のシート上のセル参照（A1形式）を出力に含めるかを指定する
+    ///
+    /// 出力から回答の根拠となったExcelのセルを特定できるよう、各セルの元の位置を出力します。
+    /// 非表示行の除外、空行の除去、並べ替えなどを行っても、元のシート上の位置を示します。
+    ///
+    /// * JSON形式: 各セルに`ref`（例: `"B3"`）を含めます。`JsonCellMode::Plain`では、
+    ///   各セルを`value`と`ref`を持つオブジェクトとして出力します。
+    /// * Markdown形式: 表の先頭に列名（`A`、`B`、...）の行を、左端に行番号の列を付加します
+    ///   （元のシートの先頭行は見出しではなく1行目のデータとして出力されます）。
+    ///
+    /// 計算列など元のセルがない位置には参照を出力しません。
+    ///
+    /// # 引数
+    ///
+    /// * `enabled: bool`:
+    ///   * `true`: セル参照を含める
+    ///   * `false`: セル参照を含めない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```text
+    /// |     | A     | B   |
+    /// | --- | ----- | --- |
+    /// | 1   | Name  | Age |
+    /// | 2   | Alice | 30  |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_cell_references(true);
+    /// ```
+    pub fn with_cell_references(mut self, enabled: bool) -> Self {
+        self.config.cell_references = enabled;
+        self
+    }
+
+    /// 見出しスタイルの行で表を区切るかを指定する
+    ///
+    /// `true`の場合、Markdown形式の出力で、表示内容のあるセルがすべて見出しスタイル
+    /// （"Title"、"Heading 1"〜"Heading 4"）の行をMarkdownの見出しとして出力し、
+    /// 表をその前後で別々の表に分割します。見出しの後の最初の行が、それぞれの表の見出し行になります。
+    /// シート名の見出し（`#`）の下位となるよう、"Title"と"Heading 1"は`##`、"Heading 2"は`###`、
+    /// "Heading 3"は`####`、"Heading 4"は`#####`として出力します。
+    /// `MergeStrategy::HtmlFallback`で結合セルを含むシートには適用されません。
+    ///
+    /// # 引数
+    ///
+    /// * `split: bool`:
+    ///   * `true`: 見出しスタイルの行で表を区切る
+    ///   * `false`: 1つの表として出力する（デフォルト）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .split_on_heading_styles(true);
+    /// ```
+    pub fn split_on_heading_styles(mut self, split: bool) -> Self {
+        self.config.split_on_heading_styles = split;
+        self
+    }
+
+    /// 肥大化した使用範囲を自動的に縮小するかを指定する
+    ///
+    /// 使用範囲（行数 × 列数）が10万セル以上で、表示内容のあるセルが1%未満のシートを
+    /// 肥大化と判定し、末尾の空白行・列を除去して表示内容のある範囲に縮小します。
+    /// 肥大化の検出は設定にかかわらず常に行われ、`ConversionReport::warnings`に
+    /// `WarningKind::UsedRangeBloat`として報告されます。
+    ///
+    /// # 引数
+    ///
+    /// * `enabled: bool`:
+    ///   * `true`: 肥大化したシートを縮小する
+    ///   * `false`: 縮小せず、警告のみ報告する（デフォルト）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .auto_shrink_used_range(true);
+    /// ```
+    pub fn auto_shrink_used_range(mut self, enabled: bool) -> Self {
+        self.config.auto_shrink_used_range = enabled;
+        self
+    }
+
+    /// 変換パイプラインに独自の処理を追加する
+    ///
+    /// 指定したステージの完了後に、シートごとに`hook`が呼び出されます。
+    /// 同じステージに複数の処理を登録した場合は、登録順に実行されます。
+    ///
+    /// # 引数
+    ///
+    /// * `stage: Stage`: 処理を挿入するステージ（このステージの完了後に実行）
+    /// * `hook: impl PipelineStage`: 実行する処理
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, Stage, StageContext, XlsxToMdError};
+    ///
+    /// // フォーマット後の表示文字列を置換
+    /// let builder = ConverterBuilder::new().with_stage(
+    ///     Stage::Format,
+    ///     |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+    ///         for cell in context.cells_mut() {
+    ///             cell.text = cell.text.replace("N/A", "-");
+    ///         }
+    ///         Ok(())
+    ///     },
+    /// );
+    /// ```
+    pub fn with_stage(mut self, stage: Stage, hook: impl PipelineStage + 'static) -> Self {
+        self.config.stages.push(stage, Arc::new(hook));
+        self
+    }
+
+    /// セル単位のテキスト変換（翻訳など）を設定する
+    ///
+    /// フォーマット後、シート内の文字列セルの値を重複を除いてまとめて`transformer`に渡し、
+    /// 変換結果で表示文字列を置き換えます。`transformer`はシートごとに1回呼び出されます。
+    ///
+    /// `transformer`にはMarkdownのエスケープやリンク構文を含まないセル単位のテキストが渡され、
+    /// 変換結果には通常のセルと同じエスケープが適用されるため、表の構造は崩れません。
+    /// 数値・日付・論理値のセルは対象外です。リッチテキストのセルは、変換後は書式（太字・斜体）が失われます。
+    ///
+    /// 変換は`Stage::Format`に登録された`PipelineStage`より前に実行されます。
+    ///
+    /// # 引数
+    ///
+    /// * `transformer: impl TextTransformer`: テキスト変換
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, XlsxToMdError};
+    ///
+    /// # fn translate(texts: &[&str]) -> Vec<String> { texts.iter().map(|t| t.to_string()).collect() }
+    /// let builder = ConverterBuilder::new().with_text_transformer(
+    ///     |texts: &[&str]| -> Result<Vec<String>, XlsxToMdError> {
+    ///         // 翻訳APIを1回だけ呼び出す
+    ///         Ok(translate(texts))
+    ///     },
+    /// );
+    /// ```
+    pub fn with_text_transformer(mut self, transformer: impl TextTransformer + 'static) -> Self {
+        self.config
+            .stages
+            .set_text_transformer(Arc::new(transformer));
+        self
+    }
+
+    /// 変換の進捗を通知するコールバックを設定する
+    ///
+    /// シートの処理の開始・完了と、`with_progress_interval()`で指定した行数の解析ごとに
+    /// `ProgressEvent`が通知されます。進捗バーの表示や、処理に時間のかかるシートのログ出力に使用します。
+    /// シートは並列に処理されるため、コールバックは複数のスレッドから呼び出されることがあります。
+    ///
+    /// コールバックは変換の処理中に同期的に呼び出されるため、時間のかかる処理は避けてください。
+    ///
+    /// # 引数
+    ///
+    /// * `callback` - 進捗のイベントを受け取るコールバック
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, ProgressEvent};
+    ///
+    /// let builder = ConverterBuilder::new().with_progress(|event| match event {
+    ///     ProgressEvent::RowsProcessed { sheet, rows, total_rows } => {
+    ///         eprintln!("{}: {}/{} rows", sheet, rows, total_rows);
+    ///     }
+    ///     ProgressEvent::SheetFinished { sheet, elapsed, .. } => {
+    ///         eprintln!("{}: done in {:?}", sheet, elapsed);
+    ///     }
+    ///     _ => {}
+    /// });
+    /// ```
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&ProgressEvent<'_>) + Send + Sync + 'static,
+    {
+        self.config.progress = Some(ProgressHandle(Arc::new(callback)));
+        self
+    }
+
+    /// 進捗（`ProgressEvent::RowsProcessed`）を通知する行数の間隔を指定する
+    ///
+    /// # 引数
+    ///
+    /// * `rows` - 通知する行数の間隔（デフォルト: 1000、0の場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_progress(|event| eprintln!("{:?}", event))
+    ///     .with_progress_interval(10_000);
+    /// ```
+    pub fn with_progress_interval(mut self, rows: usize) -> Self {
+        self.config.progress_interval = rows;
+        self
+    }
+
+    /// 処理対象のセル範囲を制限する
+    ///
+    /// 範囲外のセルは無視されます。
+    ///
+    /// # 引数
+    ///
+    /// * `start: (u32, u32)`: 開始セル座標 (row, col)（0始まり）
+    /// * `end: (u32, u32)`: 終了セル座標 (row, col)（0始まり）
+    ///
+    /// # 制約
+    ///
+    /// * `start.0 <= end.0` かつ `start.1 <= end.1` でなければならない
+    /// * 制約違反の場合、`build()`時に`XlsxToMdError::Config`を返す
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // A1:C10の範囲を処理（0始まりなので、row 0-9, col 0-2）
+    /// let builder = ConverterBuilder::new()
+    ///     .with_range((0, 0), (9, 2));
+    /// ```
+    pub fn with_range(mut self, start: (u32, u32), end: (u32, u32)) -> Self {
+        use crate::types::CellCoord;
+        self.config.range = Some(CellRange::new(
+            CellCoord::new(start.0, start.1),
+            CellCoord::new(end.0, end.1),
+        ));
+        self
+    }
+
+    /// 処理対象のセル範囲をA1形式で制限する
+    ///
+    /// `with_range()`と同じく範囲外のセルは無視されます。範囲は`"B2:F100"`のようなA1形式で、
+    /// 絶対参照の`$`、単一のセル（`"B2"`）、列全体（`"A:C"`）、行全体（`"1:5"`）も指定できます。
+    /// `"Sheet2!A1:C10"`（空白などを含むシート名は`"'Q1 Data'!A1:C10"`）のようにシート名を付けると、
+    /// そのシートのみに適用される範囲になり、他のシートには`with_range()`などの範囲が適用されます。
+    /// シートごとに異なる範囲を指定する場合は、複数回呼び出します。
+    ///
+    /// # エラー
+    ///
+    /// A1形式として解析できない場合、`build()`が`XlsxToMdError::Config`を返します。
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// // すべてのシートでB2:F100、"Sheet2"シートのみA1:C10を処理
+    /// let converter = ConverterBuilder::new()
+    ///     .with_range_a1("B2:F100")
+    ///     .with_range_a1("Sheet2!A1:C10")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_range_a1(mut self, range: &str) -> Self {
+        let (sheet_name, area) = match split_sheet_prefix(range) {
+            Some((sheet_name, area)) => (Some(sheet_name), area),
+            None => (None, range),
+        };
+        match (sheet_name, area.parse::<CellRange>()) {
+            (Some(sheet_name), Ok(area)) => {
+                self.config
+                    .sheet_ranges
+                    .retain(|(name, _)| *name != sheet_name);
+                self.config.sheet_ranges.push((sheet_name, area));
+            }
+            (None, Ok(area)) => self.config.range = Some(area),
+            (_, Err(_)) => self.config.invalid_range = Some(range.to_string()),
+        }
+        self
+    }
+
+    /// 名前付き範囲（Excelの「名前の定義」）が指す範囲のみを処理する
+    ///
+    /// 名前の参照先のシートのみを変換し、範囲外のセルは無視されます。
+    /// シートの選択（`with_sheet_selector`）と範囲制限（`with_range`）より優先されます。
+    /// 名前は大文字・小文字を区別せずに照合します。シートレベルの名前は
+    /// `"Sheet1!SalesTable"`のようにシート名で修飾して指定できます。
+    ///
+    /// 名前が見つからない場合や、参照先が単一のセル範囲でない場合（複数の範囲、数式など）は、
+    /// 変換時に`XlsxToMdError::Config`を返します。旧形式（XLS）のワークブックには対応していません。
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // "SalesTable"（例: Sheet1!$B$2:$D$10）の範囲のみを変換
+    /// let builder = ConverterBuilder::new()
+    ///     .with_named_range("SalesTable");
+    /// ```
+    pub fn with_named_range(mut self, name: impl Into<String>) -> Self {
+        self.config.named_range = Some(name.into());
+        self
+    }
+
+    /// 出力フォーマットを指定する
+    ///
+    /// # 引数
+    ///
+    /// * `format: OutputFormat`: 出力フォーマット（Markdown, HTML, JSON, CSV）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    ///
+    /// // JSON形式で出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json);
+    ///
+    /// // CSV形式で出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Csv);
+    /// ```
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.config.output_format = format;
+        self.config.format_name = None;
+        self
+    }
+
+    /// 出力フォーマットを名前で指定する
+    ///
+    /// 組み込みのフォーマット（`OutputFormat::NAMES`）に加えて、`register_format()`などで
+    /// 登録した独自のフォーマット（`FormatPlugin`）を選択できます。CLIやWASMのラッパーで、
+    /// 利用者が指定したフォーマット名をそのまま渡す場合に使用します。
+    /// 独自のフォーマットは`build()`の時点で検索するため、それまでに登録してください。
+    ///
+    /// 独自のフォーマットの`FormatCapabilities::supports_merged_cells`が`false`の場合、
+    /// セル結合戦略は`MergeStrategy::DataDuplication`になります。
+    ///
+    /// # 引数
+    ///
+    /// * `name`: フォーマット名（例: `"json"`、`"my-org-wiki"`）
+    ///
+    /// # エラー
+    ///
+    /// 組み込みのフォーマットにも登録されたフォーマットにも該当しない場合、
+    /// `build()`が`XlsxToMdError::Config`を返します。
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let format = std::env::args().nth(1).unwrap_or_else(|| "markdown".to_string());
+    /// let converter = ConverterBuilder::new().with_format_name(&format).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_format_name(mut self, name: &str) -> Self {
+        match name.parse::<OutputFormat>() {
+            Ok(format) => return self.with_output_format(format),
+            Err(_) => self.config.format_name = Some(name.to_string()),
+        }
+        self
+    }
+
+    /// シート単位で出力フォーマットを上書きする
+    ///
+    /// 指定したシートのみ、`with_output_format()`の指定に代えてこの出力フォーマットで出力します。
+    /// データのシートはCSV、注記のシートはMarkdownのように、1回の変換で異なるフォーマットを
+    /// 混在させる場合に使用します。`Converter::convert_to_sink()`と`DirectorySink`を組み合わせると、
+    /// シートごとに対応する拡張子のファイルが出力されます。
+    ///
+    /// 同じシートに対して複数回呼び出した場合は、最後の指定が有効になります。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name: impl Into<String>`: 対象のシート名（完全一致）
+    /// * `format: OutputFormat`: このシートに適用する出力フォーマット
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    ///
+    /// // "Data"シートのみCSV、その他のシートはMarkdownで出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Markdown)
+    ///     .with_sheet_override("Data", OutputFormat::Csv);
+    /// ```
+    pub fn with_sheet_override(
+        mut self,
+        sheet_name: impl Into<String>,
+        format: OutputFormat,
+    ) -> Self {
+        let sheet_name = sheet_name.into();
+        self.config
+            .sheet_formats
+            .retain(|(name, _)| *name != sheet_name);
+        self.config.sheet_formats.push((sheet_name, format));
+        self
+    }
+
+    /// シート間の区切りを指定する
+    ///
+    /// 複数のシートを1つの文書に出力する場合に、2番目以降のシートの前に出力する文字列です。
+    /// 文字列はそのまま出力されるため、必要な改行を含めて指定してください。
+    /// デフォルトでは、Markdownは水平線（`---`）、その他のフォーマットは空行で区切ります。
+    ///
+    /// 複数のシートをJSON形式で出力する場合は、文書全体が1つのJSONオブジェクト
+    /// （`JsonDocumentMode::Lines`では1シート1行）に、HTML形式で出力する場合は
+    /// シートごとの`<section>`からなるHTML文書になるため、この区切りは使用されません。`Converter::convert_to_sink()`でも使用されません。
+    ///
+    /// # 引数
+    ///
+    /// * `separator: impl Into<String>`: シート間の区切り
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // シートごとに改ページ用のコメントを挟む
+    /// let builder = ConverterBuilder::new().with_sheet_separator("\n<!-- pagebreak -->\n\n");
+    /// ```
+    pub fn with_sheet_separator(mut self, separator: impl Into<String>) -> Self {
+        self.config.sheet_separator = Some(separator.into());
+        self
+    }
+
+    /// 複数シートのHTML文書にシートへのナビゲーションを含めるかを指定する
+    ///
+    /// 複数のシートをHTML形式で出力すると、シートごとの`<section id="...">`からなる
+    /// 1つのHTML文書になります。`true`の場合は、文書の先頭に各セクションへのリンクの一覧
+    /// （`<nav>`）を出力します。シートが1つの場合は、従来どおり表のみを出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `include: bool`: ナビゲーションを含めるか（デフォルト: `false`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Html)
+    ///     .include_html_navigation(true);
+    /// ```
+    pub fn include_html_navigation(mut self, include: bool) -> Self {
+        self.config.include_html_navigation = include;
+        self
+    }
+
+    /// 入力ファイルの最大サイズを指定する
+    ///
+    /// 入力データがこのサイズを超える場合、変換時に`XlsxToMdError::SecurityViolation`を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `bytes: u64`: 最大サイズ（バイト、デフォルト: 2GB）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // 50MBを超える入力を拒否
+    /// let builder = ConverterBuilder::new()
+    ///     .with_max_input_size(50 * 1024 * 1024);
+    /// ```
+    pub fn with_max_input_size(mut self, bytes: u64) -> Self {
+        self.config.security.max_input_file_size = bytes;
+        self
+    }
+
+    /// 1つの結合範囲を展開する最大セル数を指定する
+    ///
+    /// `MergeStrategy::DataDuplication`では結合範囲のすべてのセルに値を複製するため、
+    /// `A1:XFD1048576`のような巨大な結合範囲は処理が終わらないか、メモリを使い果たします。
+    /// セル数（シートのデータ範囲に収まる部分）が上限を超える結合範囲は値を複製せず、
+    /// `MergeStrategy::HtmlFallback`と同じく左上のセルのみに値を残し、
+    /// `WarningKind::MergedRegionLimit`の警告を記録します。
+    ///
+    /// # 引数
+    ///
+    /// * `cells: u64`: 最大セル数（デフォルト: 1,000,000）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // 1万セルを超える結合範囲は複製しない
+    /// let builder = ConverterBuilder::new()
+    ///     .with_max_merged_cells(10_000);
+    /// ```
+    pub fn with_max_merged_cells(mut self, cells: u64) -> Self {
+        self.config.security.max_merged_cells = cells;
+        self
+    }
+
+    /// セキュリティ制限をまとめて指定する
+    ///
+    /// 入力ファイルのサイズ、ZIPアーカイブのファイル数・展開後のサイズ、結合範囲の展開などの上限を
+    /// 置き換えます。信頼できる巨大なファイルを扱う場合は上限を引き上げ、
+    /// 不特定のユーザーからの入力を扱う場合は引き下げます。
+    /// 上限を超えた場合、変換時に`XlsxToMdError::SecurityViolation`を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `limits: SecurityLimits`: セキュリティ制限（デフォルト: `SecurityLimits::default()`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, SecurityLimits};
+    ///
+    /// // 4GBまでの入力と8GBまでの展開を許可
+    /// let builder = ConverterBuilder::new().with_security_limits(SecurityLimits {
+    ///     max_input_file_size: 4 * 1024 * 1024 * 1024,
+    ///     max_decompressed_size: 8 * 1024 * 1024 * 1024,
+    ///     ..SecurityLimits::default()
+    /// });
+    /// ```
+    pub fn with_security_limits(mut self, limits: SecurityLimits) -> Self {
+        self.config.security = limits;
+        self
+    }
+
+    /// 出力サイズの上限を指定する
+    ///
+    /// 異常なワークブックから巨大な出力（数GBのMarkdownなど）が生成され、
+    /// 後続のシステムに渡されることを防ぎます。
+    /// 上限は全シートの出力の合計（フロントマター・シート見出し・区切りを除く）に適用され、
+    /// 出力を書き込む前に判定します。上限を超えた場合の扱いは
+    /// `with_output_overflow()`で指定します（デフォルトは`XlsxToMdError::SecurityViolation`）。
+    ///
+    /// # 引数
+    ///
+    /// * `bytes: u64`: 最大サイズ（バイト、デフォルト: 上限なし）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputOverflow};
+    ///
+    /// // 出力を10MBまでに打ち切る
+    /// let builder = ConverterBuilder::new()
+    ///     .with_max_output_size(10 * 1024 * 1024)
+    ///     .with_output_overflow(OutputOverflow::Truncate);
+    /// ```
+    pub fn with_max_output_size(mut self, bytes: u64) -> Self {
+        self.config.max_output_size = Some(bytes);
+        self
+    }
+
+    /// 出力サイズが上限を超えた場合の扱いを指定する
+    ///
+    /// # 引数
+    ///
+    /// * `overflow: OutputOverflow`: 上限を超えた場合の扱い（デフォルト: `OutputOverflow::Error`）
+    pub fn with_output_overflow(mut self, overflow: OutputOverflow) -> Self {
+        self.config.output_overflow = overflow;
+        self
+    }
+
+    /// シート単位の上限（行数・セル数・処理時間）を指定する
+    ///
+    /// 多数のシートを持つワークブックで、1つの巨大なシートや処理に時間のかかるシートのために
+    /// ワークブック全体の変換が失敗しないよう、上限を超えたシートを打ち切って変換を継続します。
+    /// 打ち切ったシートは、上限を超えた行以降を出力せず、出力の末尾に注記
+    /// （例: `> Sheet truncated after 1000 of 250000 rows: exceeded the per-sheet limit of 1000 rows.`、
+    /// JSON形式を除く）を付加し、`WarningKind::SheetTruncated`の警告を記録します。
+    ///
+    /// - 行数: 範囲の指定・使用範囲の縮小の後の表の行数（見出しの行を含む）
+    /// - セル数: 表示内容のあるセルの数。上限を超える行以降を打ち切ります
+    /// - 処理時間: シートの処理の開始からの経過時間。セルのフォーマット中に判定し、
+    ///   上限を超えた時点でフォーマットを中止して、フォーマット済みの行までを出力します
+    ///   （シートの解析自体は中断しません）
+    ///
+    /// 上限を設けない項目には`usize::MAX`または`Duration::MAX`を指定してください。
+    /// 打ち切りは行の絞り込み・並べ替えなどの前に行います。
+    ///
+    /// # 引数
+    ///
+    /// * `rows` - 1シートの行数の上限（1以上）
+    /// * `cells` - 1シートの表示内容のあるセルの数の上限（1以上）
+    /// * `duration` - 1シートの処理時間の上限（0より大きい値）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // 1シートあたり1万行・10万セル・5秒を超える部分は出力しない
+    /// let builder = ConverterBuilder::new()
+    ///     .with_per_sheet_limits(10_000, 100_000, Duration::from_secs(5));
+    /// ```
+    pub fn with_per_sheet_limits(mut self, rows: usize, cells: usize, duration: Duration) -> Self {
+        self.config.per_sheet_limits = Some((rows, cells, duration));
+        self
+    }
+
+    /// 出力する表の行数の上限を指定する
+    ///
+    /// RAGのチャンク分割などで出力サイズを抑えるため、上限を超えた行を省略し、
+    /// 表の後に省略した行数の注記（例: `… 12,480 more rows omitted`、JSON形式を除く）を出力します。
+    /// 変換は失敗せず、警告も記録しません。省略した行数は`SheetReport::omitted_rows`に記録されます。
+    ///
+    /// `with_per_sheet_limits()`と異なり、行の絞り込み・並べ替えなどをすべて適用した後の表に対して
+    /// 適用します。行数は見出しの行を含みます。
+    ///
+    /// # 引数
+    ///
+    /// * `rows` - 1シートの表の行数の上限（1以上、0の場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // 見出しを含めて先頭の100行のみを出力
+    /// let builder = ConverterBuilder::new().with_max_rows(100);
+    /// ```
+    pub fn with_max_rows(mut self, rows: usize) -> Self {
+        self.config.max_rows = Some(rows);
+        self
+    }
+
+    /// 出力する表の表示内容のあるセルの数の上限を指定する
+    ///
+    /// 表示内容のあるセル（空白のみのセルを除く）の数が上限を超える行以降を省略し、
+    /// `with_max_rows()`と同じく省略した行数の注記を出力します。
+    /// 両方を指定した場合は、より少ない行数になる上限を適用します。
+    ///
+    /// # 引数
+    ///
+    /// * `cells` - 1シートの表示内容のあるセルの数の上限（1以上、0の場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new().with_max_cells(5_000);
+    /// ```
+    pub fn with_max_cells(mut self, cells: usize) -> Self {
+        self.config.max_cells = Some(cells);
+        self
+    }
+
+    /// 行数・セル数の上限で行を省略した場合の注記を指定する
+    ///
+    /// `{count}`は省略した行数（3桁ごとに`,`で区切った数値）に置き換えられます。
+    /// Markdownでは引用、HTMLでは段落、CSVでは`#`で始まるコメント行、XMLではコメントとして出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `notice` - 注記（デフォルト: `… {count} more rows omitted`、改行を含む場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_max_rows(100)
+    ///     .with_truncation_notice("（以下{count}行省略）");
+    /// ```
+    pub fn with_truncation_notice(mut self, notice: impl Into<String>) -> Self {
+        self.config.truncation_notice = notice.into();
+        self
+    }
+
+    /// 出力フォーマットごとの数値出力方式を指定する
+    ///
+    /// 同じ出力フォーマットに対して複数回呼び出した場合は、最後の指定が有効になります。
+    ///
+    /// # 引数
+    ///
+    /// * `format: OutputFormat`: 対象の出力フォーマット
+    /// * `mode: NumberOutput`: 数値出力方式（デフォルト: CSV/JSON/XMLは`Raw`、Markdown/HTMLは`Formatted`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, NumberOutput, OutputFormat};
+    ///
+    /// // CSVでも書式適用後の文字列（例: "1,234.56"）を出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Csv)
+    ///     .with_number_output(OutputFormat::Csv, NumberOutput::Formatted);
+    ///
+    /// // Markdownで生の数値（例: 1234.56）を出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_number_output(OutputFormat::Markdown, NumberOutput::Raw);
+    /// ```
+    pub fn with_number_output(mut self, format: OutputFormat, mode: NumberOutput) -> Self {
+        self.config.number_outputs.retain(|(f, _)| *f != format);
+        self.config.number_outputs.push((format, mode));
+        self
+    }
+
+    /// JSON出力におけるセルの表現方式を指定する
+    ///
+    /// # 引数
+    ///
+    /// * `mode: JsonCellMode`: セルの表現方式（デフォルト: `JsonCellMode::Plain`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, JsonCellMode, OutputFormat};
+    ///
+    /// // 各セルを {"type", "raw", "formatted"} のオブジェクトとして出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_cell_mode(JsonCellMode::Typed);
+    /// ```
+    pub fn with_json_cell_mode(mut self, mode: JsonCellMode) -> Self {
+        self.config.json_cell_mode = mode;
+        self
+    }
+
+    /// JSON出力における行オブジェクトのキーを指定する
+    ///
+    /// `JsonKeyMode::Header`または`JsonKeyMode::NormalizedHeader`の場合は先頭行を見出しとして扱い、
+    /// 見出しから作成したキーで2行目以降を出力します。キーと元の見出しの対応は
+    /// `columns`配列（`key`、`header`、`column`）として出力されます。
+    ///
+    /// # 引数
+    ///
+    /// * `mode: JsonKeyMode`: キーの決定方式（デフォルト: `JsonKeyMode::ColumnLetter`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, JsonKeyMode, OutputFormat};
+    ///
+    /// // 見出し "Unit Price (USD)" の列を "unit_price" キーで出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_key_mode(JsonKeyMode::NormalizedHeader);
+    /// ```
+    pub fn with_json_key_mode(mut self, mode: JsonKeyMode) -> Self {
+        self.config.json_key_mode = mode;
+        self
+    }
+
+    /// 複数シートのJSON文書の構造を指定する
+    ///
+    /// デフォルトでは、複数のシートを`{"sheets": [...]}`の1つのJSONオブジェクトとして出力します。
+    /// `JsonDocumentMode::Lines`では、1シートを1行のJSONとして出力します（NDJSON）。
+    /// `JsonDocumentMode::Records`では、各シートを行オブジェクトの配列として出力し、
+    /// 複数のシートはシート名をキーとする1つのオブジェクトにまとめます。
+    ///
+    /// # 引数
+    ///
+    /// * `mode: JsonDocumentMode`: JSON文書の構造（デフォルト: `JsonDocumentMode::Sheets`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, JsonDocumentMode, OutputFormat};
+    ///
+    /// // シートごとに1行のJSONを出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_document_mode(JsonDocumentMode::Lines);
+    /// ```
+    pub fn with_json_document_mode(mut self, mode: JsonDocumentMode) -> Self {
+        self.config.json_document_mode = mode;
+        self
+    }
+
+    /// JSONの書式を指定する
+    ///
+    /// デフォルトでは字下げと改行を含めて出力します（`JsonStyle::Pretty`）。
+    /// `JsonStyle::Minified`では、複数シートの`{"sheets": [...]}`を含む文書全体を
+    /// 空白と改行を含めずに1行で出力します。NDJSON（`JsonDocumentMode::Lines`）は
+    /// 1シートを1行で出力するため、指定にかかわらず`Minified`で出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `style: JsonStyle`: JSONの書式（デフォルト: `JsonStyle::Pretty`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, JsonStyle, OutputFormat};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_style(JsonStyle::Minified);
+    /// ```
+    pub fn with_json_style(mut self, style: JsonStyle) -> Self {
+        self.config.json_style = style;
+        self
+    }
+
+    /// 見出しに含まれる単位の扱いを指定する
+    ///
+    /// 先頭行（見出し）の末尾の括弧内の単位（`Weight (kg)`の`kg`など）を検出し、
+    /// JSON出力の`columns`配列に列ごとの`unit`として出力します。
+    /// `HeaderUnits::DetectAndStrip`の場合は、見出しの表示からも単位を除去します。
+    ///
+    /// # 引数
+    ///
+    /// * `units: HeaderUnits`: 単位の扱い（デフォルト: `HeaderUnits::Off`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, HeaderUnits, JsonKeyMode, OutputFormat};
+    ///
+    /// // 見出し "Weight (kg)" を "weight" キー、単位 "kg" として出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Json)
+    ///     .with_json_key_mode(JsonKeyMode::NormalizedHeader)
+    ///     .with_header_units(HeaderUnits::DetectAndStrip);
+    /// ```
+    pub fn with_header_units(mut self, units: HeaderUnits) -> Self {
+        self.config.header_units = units;
+        self
+    }
+
+    /// 値のない数式セルの扱いを指定する
+    ///
+    /// # 引数
+    ///
+    /// * `policy: NullFormulaPolicy`: 値のない数式セルの扱い（デフォルト: `NullFormulaPolicy::Empty`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, NullFormulaPolicy};
+    ///
+    /// // 未計算の数式セルを "#NOVALUE" として出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_null_formula_policy(NullFormulaPolicy::Marker("#NOVALUE".to_string()));
+    /// ```
+    pub fn with_null_formula_policy(mut self, policy: NullFormulaPolicy) -> Self {
+        self.config.null_formula_policy = policy;
+        self
+    }
+
+    /// ハイパーリンクの出力方式を指定する
+    ///
+    /// # 引数
+    ///
+    /// * `mode: HyperlinkMode`: ハイパーリンクの出力方式（デフォルト: `HyperlinkMode::Inline`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, HyperlinkMode};
+    ///
+    /// // URLをシート末尾の脚注セクションに出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_hyperlink_mode(HyperlinkMode::Footnote);
+    /// ```
+    pub fn with_hyperlink_mode(mut self, mode: HyperlinkMode) -> Self {
+        self.config.hyperlink_mode = mode;
+        self
+    }
+
+    /// リンクテキストとして表示する長いURLを短縮する（Markdownのみ）
+    ///
+    /// セルの値がURL自体のハイパーリンク（値のないセルを含む）で、URLが上限の文字数を超える場合に、
+    /// リンクテキストをホスト名と省略記号（例: `example.com/…`）に置き換えます。
+    /// リンク先には元のURLをそのまま出力するため、表を読みやすく保ったまま情報は失われません。
+    /// `HyperlinkMode::TextOnly`ではURLを出力しないため短縮しません。
+    ///
+    /// なお、ハイパーリンクに表示テキスト（`display`属性）やヒント（`tooltip`属性）がある場合は、
+    /// 短縮の設定に関係なく、セルの値よりそれらを優先してリンクテキストとします。
+    ///
+    /// # 引数
+    ///
+    /// * `max_chars: usize`: 短縮せずに表示するURLの文字数の上限（デフォルト: 短縮しない）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// // | [example.com/…](https://example.com/very/long/path?with=query) |
+    /// let builder = ConverterBuilder::new()
+    ///     .with_url_shortening(40);
+    /// ```
+    pub fn with_url_shortening(mut self, max_chars: usize) -> Self {
+        self.config.url_shortening = Some(max_chars);
+        self
+    }
+
+    /// セル内の画像（「セルに配置」した画像、`IMAGE()`関数）の出力方式を指定する
+    ///
+    /// # 引数
+    ///
+    /// * `mode: CellImageMode`: 出力方式（デフォルト: `CellImageMode::Placeholder("[image]")`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{CellImageMode, ConverterBuilder};
+    ///
+    /// // 画像ファイルへの参照（例: `[image: xl/media/image1.png]`）を出力
+    /// let builder = ConverterBuilder::new()
+    ///     .with_cell_image_mode(CellImageMode::Reference);
+    /// ```
+    pub fn with_cell_image_mode(mut self, mode: CellImageMode) -> Self {
+        self.config.cell_image_mode = mode;
+        self
+    }
+
+    /// 図形として配置された画像（「セルの上に配置」した画像）を抽出し、指定したディレクトリに書き出す
+    ///
+    /// 画像ファイル（xl/media/*）を`sheet<シートの順序>_img<シート内の順序>.<拡張子>`の名前で書き出し、
+    /// 画像の配置位置の左上のセルに画像の参照を出力します。参照はMarkdownでは`![代替テキスト](パス)`、
+    /// HTMLでは`<img>`要素、それ以外の出力フォーマットではパスです。パスは指定したディレクトリの
+    /// パス（区切りは`/`）とファイル名を連結したものです。出力するファイルと同じディレクトリからの
+    /// 相対パスを指定すると、出力から画像を参照できます。
+    ///
+    /// 画像はシートの内容に含めるため、使用範囲の外側（右側・下側）に配置した画像は表の範囲を広げます。
+    /// 使用範囲の上側・左側に配置した画像は、最も近い表の端のセルに出力します。
+    /// 範囲制限（`with_range()`など）の外側に配置した画像は抽出しません。
+    ///
+    /// ディレクトリが存在しない場合は作成します。コールバックで受け取る場合は`with_image_handler()`を使用します。
+    ///
+    /// # 引数
+    ///
+    /// * `dir` - 画像ファイルの出力先ディレクトリ
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// | Product | Photo                              |
+    /// |---------|------------------------------------|
+    /// | Chair   | ![Front view](images/sheet1_img1.png) |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .extract_images_to("images");
+    /// ```
+    pub fn extract_images_to(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.image_sink = Some(ImageSink::Directory(dir.into()));
+        self
+    }
+
+    /// 図形として配置された画像（「セルの上に配置」した画像）を抽出し、コールバックに渡す
+    ///
+    /// `extract_images_to()`と同様に画像の参照を出力し、画像ファイルを書き出す代わりに
+    /// `ExtractedImage`をコールバックに渡します。出力に記載するパスは`images/<ファイル名>`です。
+    /// シートは並列に処理されるため、コールバックは複数のスレッドから呼び出されることがあります。
+    /// コールバックがエラーを返した場合、変換を中断してそのエラーを返します。
+    ///
+    /// # 引数
+    ///
+    /// * `handler` - 抽出した画像を受け取るコールバック
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new().with_image_handler(|image| {
+    ///     println!("{}!{}: {} ({} bytes)", image.sheet, image.cell, image.path, image.data.len());
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn with_image_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ExtractedImage<'_>) -> Result<(), XlsxToMdError> + Send + Sync + 'static,
+    {
+        self.config.image_sink = Some(ImageSink::Handler(Arc::new(handler)));
+        self
+    }
+
+    /// 連続する同一内容の行の集約方式を指定する
+    ///
+    /// # 引数
+    ///
+    /// * `dedupe: RowDedupe`: 行の集約方式（デフォルト: `RowDedupe::Off`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, RowDedupe};
+    ///
+    /// // 連続する同一行を1行にまとめ、行数を「×N」で付記
+    /// let builder = ConverterBuilder::new()
+    ///     .with_row_dedupe(RowDedupe::ConsecutiveWithCount);
+    /// ```
+    pub fn with_row_dedupe(mut self, dedupe: RowDedupe) -> Self {
+        self.config.row_dedupe = dedupe;
+        self
+    }
+
+    /// 空行（すべてのセルが空の行）の扱いを指定する
+    ///
+    /// 空行の除去は、グリッドの構築直後（行の絞り込みや集約より前）に行います。
+    /// 除去した行数は`SheetReport::removed_blank_rows`に記録されます。
+    ///
+    /// # 引数
+    ///
+    /// * `mode: BlankRows`: 空行の扱い（デフォルト: `BlankRows::Keep`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{BlankRows, ConverterBuilder};
+    ///
+    /// // 表の区切りの空行を除去し、元の行番号を注記として残す
+    /// let builder = ConverterBuilder::new()
+    ///     .with_blank_rows(BlankRows::RemoveWithMarker);
+    /// ```
+    pub fn with_blank_rows(mut self, mode: BlankRows) -> Self {
+        self.config.blank_rows = mode;
+        self
+    }
+
+    /// 空の行・列の刈り込み方式を指定する
+    ///
+    /// 内容を消去したセルが使用範囲に残っているシートで、先頭・末尾の空行・空列を除去します。
+    /// 刈り込みは空行の除去（`with_blank_rows()`）の直後に行います。
+    /// セル参照（`with_cell_references()`）は刈り込み後も元のシート上の位置を指します。
+    ///
+    /// # 引数
+    ///
+    /// * `mode: TrimMode`: 刈り込み方式（デフォルト: `TrimMode::None`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, TrimMode};
+    ///
+    /// // 先頭・末尾の空行・空列と、表の途中の空行を除去
+    /// let builder = ConverterBuilder::new().trim_empty(TrimMode::All);
+    /// ```
+    pub fn trim_empty(mut self, mode: TrimMode) -> Self {
+        self.config.trim_empty = mode;
+        self
+    }
+
+    /// 内容がほぼ同一のシート（重複シート）の扱いを指定する
+    ///
+    /// 先に出力するシートと行の内容の大部分が一致するシートを重複シートとして検出し、
+    /// 出力から除外するか、重複元を示す注記に置き換えます。
+    /// 検出した重複シートは`ConversionReport::duplicate_sheets`で確認できます。
+    ///
+    /// # 引数
+    ///
+    /// * `mode: DuplicateSheets`: 重複シートの扱い（デフォルト: `DuplicateSheets::Keep`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, DuplicateSheets};
+    ///
+    /// // 「Sheet1 (2)」のような複製シートを出力しない
+    /// let builder = ConverterBuilder::new()
+    ///     .with_duplicate_sheets(DuplicateSheets::Skip);
+    /// ```
+    pub fn with_duplicate_sheets(mut self, mode: DuplicateSheets) -> Self {
+        self.config.duplicate_sheets = mode;
+        self
+    }
+
+    /// すべての行が同じ値を持つ列（定数列）を表から除去するかを指定する
+    ///
+    /// 見出し以外のすべての行が同じ値（例: Regionがすべて`EMEA`）の列を表から除去し、
+    /// 表の前に「見出し: 値」の形式で出力します。表の列数を減らし、出力サイズを削減できます。
+    /// 除去した列は`SheetReport::folded_columns`で確認できます。
+    ///
+    /// JSON形式では、列名（`A`、`B`、...）が変わらないよう適用しません。
+    ///
+    /// # 引数
+    ///
+    /// * `fold: bool`:
+    ///   * `true`: 定数列を除去する
+    ///   * `false`: 除去しない（デフォルト）
+    ///
+    /// # 出力例（Markdown）
+    ///
+    /// ```markdown
+    /// - Region: EMEA
+    ///
+    /// | Product | Sales |
+    /// |---------|-------|
+    /// | Apple   | 100   |
+    /// | Orange  | 200   |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_constant_column_folding(true);
+    /// ```
+    pub fn with_constant_column_folding(mut self, fold: bool) -> Self {
+        self.config.constant_column_folding = fold;
+        self
+    }
+
+    /// XMLパーツに不正なUTF-8が含まれる場合の扱いを指定する
+    ///
+    /// 一部のサードパーティ製ツールは、共有文字列などにWindows-1252（Latin-1）の
+    /// バイト列をそのまま書き込みます。デフォルトでは、このようなファイルの変換は失敗します。
+    ///
+    /// `true`の場合は、不正なバイト列を置換文字（U+FFFD）に置き換えて変換を継続し、
+    /// `ConversionReport::warnings`に`WarningKind::InvalidEncoding`の警告を記録します。
+    /// すべてのXMLパーツを事前に展開して検証するため、変換時間が増加します。
+    ///
+    /// # 引数
+    ///
+    /// * `lossy: bool`:
+    ///   * `true`: 不正なバイト列を置換文字に置き換える
+    ///   * `false`: 変換をエラーとする（デフォルト）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_encoding_fallback(true);
+    /// ```
+    pub fn with_encoding_fallback(mut self, lossy: bool) -> Self {
+        self.config.encoding_fallback = lossy;
+        self
+    }
+
+    /// 行の内容による絞り込み条件を指定する
+    ///
+    /// 各行のセルの表示文字列をタブ区切りで連結した文字列を正規表現で判定し、
+    /// 「社外秘」などの定型文の行を出力から除去したり、特定の行のみを出力したりします。
+    /// 判定方法の詳細は`ContentFilter`を参照してください。
+    /// 除去した行数は`SheetReport::filtered_rows`に記録されます。
+    ///
+    /// `MergeStrategy::HtmlFallback`で結合セルを含むシートでは、結合構造を保持するため
+    /// 絞り込みを行いません。
+    ///
+    /// # 引数
+    ///
+    /// * `filter` - 絞り込み条件（デフォルト: 絞り込みなし）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ContentFilter, ConverterBuilder};
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new()
+    ///     .with_content_filter(ContentFilter {
+    ///         include_rows_matching: None,
+    ///         exclude_rows_matching: Some("Confidential — internal use only".to_string()),
+    ///     })
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_content_filter(mut self, filter: ContentFilter) -> Self {
+        self.config.content_filter = filter;
+        self
+    }
+
+    /// 各行の既存の列から計算する列を末尾に追加する
+    ///
+    /// 先頭行の見出し名で列を参照する簡単な式を、行の絞り込み後の各行で評価し、
+    /// 結果の列を表の末尾（最終列の右）に追加します。出力後のCSVなどを再度処理することなく、
+    /// よく使う列の追加（金額の計算、氏名の連結など）を行えます。
+    /// 複数回呼び出した場合は、呼び出した順に列を追加します（先に追加した計算列も参照できます）。
+    ///
+    /// 式では数値・文字列（`'...'`または`"..."`）・列の参照（`amount`、空白などを含む見出しは
+    /// `[Unit Price]`）に対して、`+` `-` `*` `/`、文字列連結の`&`、括弧と関数
+    /// `concat`、`upper`、`lower`、`trim`、`round`、`abs`を使用できます。
+    /// 数値として扱えない値の演算は`#VALUE!`、0による除算は`#DIV/0!`になります。
+    ///
+    /// 参照する列が見出しにないシートでは計算列を追加せず、
+    /// `WarningKind::ComputedColumnSkipped`の警告を記録します。
+    ///
+    /// # 引数
+    ///
+    /// * `name` - 列名（見出し）
+    /// * `expr` - 式（`build()`で検証し、不正な場合は`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new()
+    ///     .with_computed_column("total", "amount * rate")
+    ///     .with_computed_column("full_name", "concat(first, ' ', last)")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_computed_column(mut self, name: &str, expr: &str) -> Self {
+        self.config
+            .computed_columns
+            .push((name.to_string(), expr.to_string()));
+        self
+    }
+
+    /// 指定した列の空のセルを、上の行の直前の値で埋める（前方補完）
+    ///
+    /// グループのラベル（分類名など）を列の最初の行にだけ記入したり、縦に結合したりした表を、
+    /// 各行が単独で意味を持つフラットなレコードに変換します（NDJSONやRAGのチャンク分割向け）。
+    /// 結合の有無にかかわらず、先頭行（見出し）を除く行で、表示文字列が空のセルに
+    /// 直前の空でないセルの値を複製します。すべてのセルが空の行は埋めません。
+    /// 列の結合セルは結合を解除し、各行が値を持つものとして出力します。
+    ///
+    /// 複数回呼び出した場合は、指定したすべての列を埋めます。行の絞り込み・並べ替えの前に適用します。
+    /// 指定した列がないシートや、`MergeStrategy::HtmlFallback`で結合セルを含むシートには適用しません。
+    ///
+    /// # 引数
+    ///
+    /// * `column` - 埋める列
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ColumnSelector, ConverterBuilder};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_forward_fill(ColumnSelector::Index(0))
+    ///     .with_forward_fill(ColumnSelector::Header("Region".to_string()));
+    /// ```
+    pub fn with_forward_fill(mut self, column: ColumnSelector) -> Self {
+        self.config.forward_fill.push(column);
+        self
+    }
+
+    /// 見出しを除く行を指定した列の値で並べ替える
+    ///
+    /// 書き出されたままのシートは行の順序が一定でないことが多く、並べ替えることで
+    /// 出力の差分比較や検索結果が安定します。行の絞り込みと計算列の追加の後に、
+    /// 先頭行（見出し）を除く行を安定ソートで並べ替えます（値が等しい行は元の順序を保ちます）。
+    /// 値が空の行は、順序によらず末尾に配置します。
+    ///
+    /// 指定した列がないシートや、`MergeStrategy::HtmlFallback`で結合セルを含むシートは
+    /// 並べ替えません。
+    ///
+    /// # 引数
+    ///
+    /// * `column` - 並べ替えに使用する列
+    /// * `order` - 並べ替えの順序
+    /// * `numeric_aware` - 数値を考慮して比較するか
+    ///   * `true`: 数値・日付のセルを値で比較して文字列より前に配置し、
+    ///     文字列は数字の並びを数値として比較する（`item2` < `item10`）
+    ///   * `false`: 表示文字列を比較する
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ColumnSelector, ConverterBuilder, SortOrder};
+    ///
+    /// let builder = ConverterBuilder::new().with_sort(
+    ///     ColumnSelector::Header("Date".to_string()),
+    ///     SortOrder::Descending,
+    ///     true,
+    /// );
+    /// ```
+    pub fn with_sort(
+        mut self,
+        column: ColumnSelector,
+        order: SortOrder,
+        numeric_aware: bool,
+    ) -> Self {
+        self.config.sort = Some((column, order, numeric_aware));
+        self
+    }
+
+    /// 列の値の検証ルールを追加する
+    ///
+    /// 変換した表を自動処理のパイプラインに渡す場合に、列の型（`Validation::Type`）、
+    /// 空のセルがないこと（`Validation::NotNull`）、キー列の値の一意性（`Validation::Unique`）を
+    /// 確認します。行の絞り込み・計算列の追加・並べ替えの後の表の、見出しを除く各行を検証します。
+    /// 違反はルールごとに`WarningKind::ValidationFailed`の警告として`SheetReport::warnings`に
+    /// 記録します。違反で変換を中止する場合は`with_validation_policy()`を指定してください。
+    ///
+    /// 複数回呼び出した場合は、すべてのルールを指定順に検証します。
+    ///
+    /// # 引数
+    ///
+    /// * `validations` - 検証ルール
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ColumnSelector, ColumnType, ConverterBuilder, Validation, ValidationPolicy};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_validations([
+    ///         Validation::Unique(ColumnSelector::Header("ID".to_string())),
+    ///         Validation::NotNull(ColumnSelector::Header("Customer".to_string())),
+    ///         Validation::Type(ColumnSelector::Header("Amount".to_string()), ColumnType::Number),
+    ///     ])
+    ///     .with_validation_policy(ValidationPolicy::Fail);
+    /// ```
+    pub fn with_validations<I>(mut self, validations: I) -> Self
+    where
+        I: IntoIterator<Item = Validation>,
+    {
+        self.config.validations.extend(validations);
+        self
+    }
+
+    /// 列の値の検証に違反した場合の扱いを指定する
+    ///
+    /// `ValidationPolicy::Fail`の場合、違反があるシートで変換を中止し、
+    /// シートのすべての違反を含む`XlsxToMdError::Validation`を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `policy` - 違反した場合の扱い（デフォルト: `ValidationPolicy::Report`）
+    pub fn with_validation_policy(mut self, policy: ValidationPolicy) -> Self {
+        self.config.validation_policy = policy;
+        self
+    }
+
+    /// Markdownの表の列幅の上限を指定する
+    ///
+    /// 説明文などの長いセルが1つでもあると、列幅を揃えるための空白で表のすべての行が
+    /// 数千文字になります。最も広いセルの表示幅（全角文字は2）が`width`を超える列では、
+    /// 上限を超えるセルを`strategy`に従って折り返すか切り詰め、列幅を上限までに抑えます。
+    ///
+    /// Markdown形式の表のみに適用します（HTMLフォールバックの表やほかの出力形式には影響しません）。
+    ///
+    /// # 引数
+    ///
+    /// * `width` - 列幅の上限（表示幅、3未満の場合は`build()`時に`XlsxToMdError::Config`）
+    /// * `strategy` - 上限を超えるセルの扱い
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, WrapStrategy};
+    ///
+    /// let builder = ConverterBuilder::new().with_column_width_limit(40, WrapStrategy::Wrap);
+    /// ```
+    pub fn with_column_width_limit(mut self, width: usize, strategy: WrapStrategy) -> Self {
+        self.config.column_width_limit = Some((width, strategy));
+        self
+    }
+
+    /// 列幅の上限で切り詰めたセルの末尾に付加する文字列を指定する
+    ///
+    /// `WrapStrategy::Truncate`で切り詰めたセルに、省略記号の代わりに付加します
+    /// （例: 日本語の文書では`（略）`、ASCIIのみの出力では`...`）。
+    /// 切り詰めた内容と付加する文字列の表示幅の合計は列幅の上限を超えません。
+    /// 切り詰めは書記素クラスタ（結合文字や絵文字のシーケンス）の途中では行いません。
+    /// 切り詰めたセルの数は`SheetReport::truncated_cells`に記録されます。
+    ///
+    /// # 引数
+    ///
+    /// * `marker` - 付加する文字列（デフォルト: `…`、`|`や改行を含む場合は`build()`時に`XlsxToMdError::Config`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, WrapStrategy};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_column_width_limit(40, WrapStrategy::Truncate)
+    ///     .with_truncation_marker("（略）");
+    /// ```
+    pub fn with_truncation_marker(mut self, marker: impl Into<String>) -> Self {
+        self.config.truncation_marker = marker.into();
+        self
+    }
+
+    /// Markdownの表を指定した行数ごとに分割する
+    ///
+    /// 行数の多い表を、データ行（先頭行を除く行）が`rows`行以下の表に分割し、空行で区切って出力します。
+    /// RAGのチャンク分割で表の途中が切り出されても、`ChunkHeader::Repeat`の場合は
+    /// 各表に見出し行があるため、列の意味が失われません。
+    ///
+    /// Markdown形式の表のみに適用します（HTMLフォールバックの表やほかの出力形式には影響しません）。
+    /// 見出しスタイルで区切る場合（`split_on_heading_styles`）は、区切った表ごとに分割します。
+    ///
+    /// # 引数
+    ///
+    /// * `rows` - 1つの表のデータ行数の上限（0の場合は`build()`時に`XlsxToMdError::Config`）
+    /// * `header` - 分割した表の見出し行の扱い
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ChunkHeader, ConverterBuilder};
+    ///
+    /// let builder = ConverterBuilder::new().with_table_chunk_rows(100, ChunkHeader::Repeat);
+    /// ```
+    pub fn with_table_chunk_rows(mut self, rows: usize, header: ChunkHeader) -> Self {
+        self.config.table_chunk_rows = Some((rows, header));
+        self
+    }
+
+    /// 保護されたシートの扱いを指定する
+    ///
+    /// シートの保護は編集を制限するだけでデータは暗号化されないため、デフォルトでは
+    /// 保護されたシートも通常どおり変換します。運用上の方針で除外したり、
+    /// 出力で識別したりする場合に指定します。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - 保護されたシートの扱い（デフォルト: `ProtectedSheets::Convert`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, ProtectedSheets};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_protected_sheets(ProtectedSheets::Skip);
+    /// ```
+    pub fn with_protected_sheets(mut self, mode: ProtectedSheets) -> Self {
+        self.config.protected_sheets = mode;
+        self
+    }
+
+    /// 条件付き書式のルールの扱いを指定する
+    ///
+    /// `ConditionalFormats::Metadata`では、シートの条件付き書式のルール（適用範囲、種類、
+    /// 数式・しきい値）をJSONの各シートの`conditional_formats`配列に出力します。
+    /// `ConditionalFormats::Annotate`では、さらに重複する値・上位10項目などの単純なルールに
+    /// 該当するセルに注記（Markdownでは脚注、型付きJSONでは`highlight: true`と`highlights`配列）を
+    /// 付加します。`ConditionalFormats::Marker`では、注記の代わりに該当するセルの末尾に`⚠`を付加します。
+    ///
+    /// ルールはシートの元のセル位置で評価するため、非表示の行・列のセルも評価の対象です。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - 条件付き書式のルールの扱い（デフォルト: `ConditionalFormats::Ignore`）
+    ///
+    /// # 出力例（Markdown、`ConditionalFormats::Annotate`）
+    ///
+    /// ```markdown
+    /// | Invoice | Amount |
+    /// |---------|--------|
+    /// | INV-1[^1] | 120 |
+    /// | INV-1[^1] | 80 |
+    ///
+    /// [^1]: Conditional format: duplicate value in A2:A3
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConditionalFormats, ConverterBuilder};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_conditional_formats(ConditionalFormats::Annotate);
+    /// ```
+    pub fn with_conditional_formats(mut self, mode: ConditionalFormats) -> Self {
+        self.config.conditional_formats = mode;
+        self
+    }
+
+    /// セルのコメント（メモ・スレッド形式のコメント）の扱いを指定する
+    ///
+    /// `CommentMode::Inline`では、コメントをセルの表示文字列の末尾に`💬`を付けて付加します。
+    /// `CommentMode::Footnotes`では、セルに脚注の参照を付加し、コメントをシート末尾の脚注セクションに
+    /// 出力します（Markdown以外の出力フォーマットでは`Inline`と同じ出力）。
+    /// スレッド形式のコメントは返信を含めて出力し、各コメントには作成者名を付加します。
+    ///
+    /// 出力されないセル（非表示の行・列、範囲制限外、値の範囲外の空のセル）のコメントは出力しません。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - セルのコメントの扱い（デフォルト: `CommentMode::Skip`）
+    ///
+    /// # 出力例（Markdown、`CommentMode::Footnotes`）
+    ///
+    /// ```markdown
+    /// | Item | Amount |
+    /// |------|--------|
+    /// | Rent | 1200[^1] |
+    ///
+    /// [^1]: Alice: Includes tax
+    ///     Bob: Confirmed with the landlord
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{CommentMode, ConverterBuilder};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_comments(CommentMode::Inline);
+    /// ```
+    pub fn include_comments(mut self, mode: CommentMode) -> Self {
+        self.config.comment_mode = mode;
+        self
+    }
+
+    /// セルの入力規則（ドロップダウンリストなど）の扱いを指定する
+    ///
+    /// `DataValidations::Metadata`では、シートの入力規則（適用範囲、種類、演算子・数式、
+    /// リストの値の一覧）をJSONの各シートの`data_validations`配列に出力します。
+    /// `DataValidations::Annotate`では、さらにリストの入力規則が適用された列の見出しに
+    /// 値の一覧を付加します（見出しの行より下のデータ行に適用された入力規則のみ）。
+    ///
+    /// リストの値の一覧が別のシートのセル範囲・名前付き範囲を参照している場合は、
+    /// 参照先のシートが変換の対象外（非表示のシートを含む）でも値を解決します。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - 入力規則の扱い（デフォルト: `DataValidations::Ignore`）
+    ///
+    /// # 出力例（Markdown、`DataValidations::Annotate`）
+    ///
+    /// ```markdown
+    /// | Task | Status (allowed: Open, Closed, Pending) |
+    /// |------|------------------------------------------|
+    /// | Audit | Open |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, DataValidations};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .with_data_validations(DataValidations::Annotate);
+    /// ```
+    pub fn with_data_validations(mut self, mode: DataValidations) -> Self {
+        self.config.data_validations = mode;
+        self
+    }
+
+    /// シートに配置されたグラフの扱いを指定する
+    ///
+    /// `ChartMode::Placeholder`では、グラフのタイトル・系列名・項目の範囲を示す注記を出力します。
+    /// `ChartMode::Data`では、注記に続けてグラフにキャッシュされた系列のデータを表として出力します
+    /// （MarkdownとHTMLのみ、それ以外の出力フォーマットでは注記のみ）。
+    /// 注記と表はシートの表の後に、グラフの配置位置の行・列の順で出力します。
+    /// JSON形式では何も出力しません。
+    ///
+    /// 範囲制限（`with_range()`など）の外側に配置したグラフは出力しません。
+    /// グラフシート（シート全体がグラフのシート）は対象外です。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - グラフの扱い（デフォルト: `ChartMode::Skip`）
+    ///
+    /// # 出力例（Markdown、`ChartMode::Placeholder`）
+    ///
+    /// ```markdown
+    /// | Month | 2023 | 2024 |
+    /// |-------|------|------|
+    /// | Jan   | 120  | 150  |
+    ///
+    /// > Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::{ChartMode, ConverterBuilder};
+    ///
+    /// let builder = ConverterBuilder::new()
+    ///     .include_charts(ChartMode::Data);
+    /// ```
+    pub fn include_charts(mut self, mode: ChartMode) -> Self {
+        self.config.chart_mode = mode;
+        self
+    }
+
+    /// シートに配置されたピボットテーブルの扱いを指定する
+    ///
+    /// ピボットテーブルの集計結果は、最後に更新した時点の値としてシートのセルに出力されます。
+    /// `PivotMode::SourceData`では、これに加えてピボットキャッシュ（xl/pivotCache/）に保存された
+    /// 元データを、`Pivot: <名前>`の見出しの下に独立した表として出力します
+    /// （Markdown・HTML・CSVのみ）。見出しと表はシートの表の後に、ピボットテーブルの
+    /// 配置範囲の行・列の順で出力します。
+    ///
+    /// 範囲制限（`with_range()`など）の外側に配置したピボットテーブルは出力しません。
+    /// 元データの表には`with_max_rows()`などの上限は適用しません。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - ピボットテーブルの扱い（デフォルト: `PivotMode::Cells`）
+    ///
+    /// # 出力例（Markdown、`PivotMode::SourceData`）
+    ///
+    /// ```markdown
+    /// | Row Labels | Sum of Amount |
+    /// |------------|---------------|
+    /// | East       | 120           |
     ///
-    /// # 制約
+    /// ## Pivot: SalesPivot
     ///
-    /// * `start.0 <= end.0` かつ `start.1 <= end.1` でなければならない
-    /// * 制約違反の場合、`build()`時に`XlsxToMdError::Config`を返す
+    /// | Region | Amount |
+    /// |--------|--------|
+    /// | East   | 120    |
+    /// ```
     ///
     /// # 使用例
     ///
-    /// ```rust,no_run
-    /// use xlsxzero::ConverterBuilder;
+    /// ```rust
+    /// use xlsxzero::{ConverterBuilder, PivotMode};
     ///
-    /// // A1:C10の範囲を処理（0始まりなので、row 0-9, col 0-2）
     /// let builder = ConverterBuilder::new()
-    ///     .with_range((0, 0), (9, 2));
+    ///     .include_pivot_tables(PivotMode::SourceData);
     /// ```
-    pub fn with_range(mut self, start: (u32, u32), end: (u32, u32)) -> Self {
-        use crate::types::CellCoord;
-        self.config.range = Some(CellRange::new(
-            CellCoord::new(start.0, start.1),
-            CellCoord::new(end.0, end.1),
-        ));
+    pub fn include_pivot_tables(mut self, mode: PivotMode) -> Self {
+        self.config.pivot_mode = mode;
         self
     }
 
-    /// 出力フォーマットを指定する
+    /// 共有ブックの変更履歴を出力に含めるかを指定する
+    ///
+    /// 共有ブックの変更履歴（xl/revisions/）には、誰がいつどのセルを変更したかが記録されています。
+    /// `true`の場合、変更履歴を解析して`ConversionReport::revisions`に格納し、
+    /// すべてのシートの後に`Change history`の付録として出力します。
+    /// 変更されたセルごとに、日時・ユーザー・シート・セル・変更前後の値を出力します。
+    /// 変更履歴がないワークブックでは何も出力しません。
+    ///
+    /// 出力フォーマットごとの出力先：
+    /// * Markdown: `# Change history`の見出しと表
+    /// * HTML: 表（複数シートのHTML文書では`<section>`）
+    /// * CSV: `#`で始まるコメント行
+    /// * JSON: 文書全体のオブジェクトの`changes`配列（NDJSONでは`changes`配列を持つ最後の行）
+    /// * XML: `<workbook>`内の`<changes>`要素
+    ///
+    /// `convert_to_sink()`ではシートごとに出力するため付録は出力されず、
+    /// 変更履歴はレポートからのみ取得できます。
     ///
     /// # 引数
     ///
-    /// * `format: OutputFormat`: 出力フォーマット（Markdown, HTML, JSON, CSV）
+    /// * `include: bool`:
+    ///   * `true`: 変更履歴を含める
+    ///   * `false`: 変更履歴を含めない（デフォルト）
     ///
-    /// # 使用例
+    /// # 出力例（Markdown）
     ///
-    /// ```rust,no_run
-    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    /// ```markdown
+    /// # Change history
     ///
-    /// // JSON形式で出力
-    /// let builder = ConverterBuilder::new()
-    ///     .with_output_format(OutputFormat::Json);
+    /// | Date | User | Sheet | Cell | Old value | New value |
+    /// |---|---|---|---|---|---|
+    /// | 2024-05-01T09:30:00Z | Ada | Budget | B2 | 120 | 150 |
+    /// ```
+    ///
+    /// # 使用例
+    ///
+    /// ```rust
+    /// use xlsxzero::ConverterBuilder;
     ///
-    /// // CSV形式で出力
     /// let builder = ConverterBuilder::new()
-    ///     .with_output_format(OutputFormat::Csv);
+    ///     .include_change_history(true);
     /// ```
-    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
-        self.config.output_format = format;
+    pub fn include_change_history(mut self, include: bool) -> Self {
+        self.config.include_change_history = include;
         self
     }
 
+    /// 構築中の設定を参照する（テスト用）
+    #[cfg(test)]
+    pub(crate) fn config(&self) -> &ConversionConfig {
+        &self.config
+    }
+
     /// 設定を検証し、`Converter`インスタンスを生成する
     ///
     /// # 戻り値
@@ -285,9 +2885,15 @@ impl ConverterBuilder {
     ///
     /// # 発生し得るエラー
     ///
-    /// * `XlsxToMdError::Config(String)`: 設定の検証に失敗した場合
+    /// * `XlsxToMdError::Config(ConfigError::InvalidValue)`: 設定の検証に失敗した場合
     ///   * 範囲指定の開始座標が終了座標より大きい
     ///   * カスタム日付形式が不正な書式文字列
+    ///   * フロントマターの追加項目のキーが空または`sheets`
+    ///   * 計算列の列名が空
+    /// * `XlsxToMdError::Config(ConfigError::InvalidSyntax)`: 行の絞り込み条件が不正な正規表現、
+    ///   または計算列の式が不正な場合
+    /// * `XlsxToMdError::Config(ConfigError::UnknownName)`: `with_format_name`の名前が
+    ///   組み込み・登録済みのフォーマットにない場合
     ///
     /// # 使用例
     ///
@@ -302,20 +2908,30 @@ impl ConverterBuilder {
     /// # }
     /// ```
     pub fn build(self) -> Result<Converter, XlsxToMdError> {
+        let invalid = |option, value: String, reason: &str| {
+            Err(ConfigError::InvalidValue {
+                option,
+                value,
+                reason: reason.to_string(),
+            }
+            .into())
+        };
+
         // 1. セル範囲の検証
+        if let Some(range) = &self.config.invalid_range {
+            return invalid(
+                "with_range_a1",
+                range.clone(),
+                "expected A1 notation such as 'B2:F100' or 'Sheet2!A1:C10'",
+            );
+        }
         if let Some(range) = &self.config.range {
             if range.start.row > range.end.row {
-                return Err(XlsxToMdError::Config(format!(
-                    "Invalid range: start row ({}) > end row ({})",
-                    range.start.row, range.end.row
-                )));
+                return invalid("with_range", range.to_string(), "start row > end row");
             }
 
             if range.start.col > range.end.col {
-                return Err(XlsxToMdError::Config(format!(
-                    "Invalid range: start col ({}) > end col ({})",
-                    range.start.col, range.end.col
-                )));
+                return invalid("with_range", range.to_string(), "start col > end col");
             }
         }
 
@@ -323,18 +2939,132 @@ impl ConverterBuilder {
         if let DateFormat::Custom(ref format_str) = self.config.date_format {
             // テスト用の日付でフォーマット試行
             let test_date = NaiveDate::from_ymd_opt(2025, 1, 1)
-                .ok_or_else(|| XlsxToMdError::Config("Failed to create test date".to_string()))?;
+                .ok_or_else(|| XlsxToMdError::Internal("Failed to create test date".to_string()))?;
             let formatted = test_date.format(format_str).to_string();
             if formatted.is_empty() {
-                return Err(XlsxToMdError::Config(format!(
-                    "Invalid date format string: '{}'",
-                    format_str
-                )));
+                return invalid(
+                    "with_date_format",
+                    format_str.clone(),
+                    "produces an empty string",
+                );
+            }
+        }
+
+        // 3. フロントマターの追加項目の検証
+        for key in self.config.front_matter_extra.keys() {
+            if key.trim().is_empty() || key == "sheets" {
+                return invalid(
+                    "with_front_matter_extra",
+                    key.clone(),
+                    "keys must be non-empty and not 'sheets'",
+                );
+            }
+        }
+
+        // 4. 列幅の上限の検証（区切り行の最小幅と同じ3以上）
+        if let Some((limit, _)) = self.config.column_width_limit {
+            if limit < 3 {
+                return invalid(
+                    "with_column_width_limit",
+                    limit.to_string(),
+                    "must be at least 3",
+                );
             }
         }
+        if let Some((0, _)) = self.config.table_chunk_rows {
+            return invalid(
+                "with_table_chunk_rows",
+                "0".to_string(),
+                "must be at least 1",
+            );
+        }
+        if self.config.truncation_marker.contains(['|', '\n', '\r']) {
+            return invalid(
+                "with_truncation_marker",
+                self.config.truncation_marker.clone(),
+                "must not contain '|' or line breaks",
+            );
+        }
+
+        // 5. シート単位の上限の検証
+        if let Some((rows, cells, duration)) = self.config.per_sheet_limits {
+            if rows == 0 || cells == 0 || duration.is_zero() {
+                return invalid(
+                    "with_per_sheet_limits",
+                    format!("{} rows, {} cells, {:?}", rows, cells, duration),
+                    "must be greater than 0",
+                );
+            }
+        }
+
+        // 6. 出力する表の上限の検証
+        if self.config.max_rows == Some(0) {
+            return invalid("with_max_rows", "0".to_string(), "must be greater than 0");
+        }
+        if self.config.max_cells == Some(0) {
+            return invalid("with_max_cells", "0".to_string(), "must be greater than 0");
+        }
+        if self.config.truncation_notice.contains(['\n', '\r']) {
+            return invalid(
+                "with_truncation_notice",
+                self.config.truncation_notice.clone(),
+                "must not contain line breaks",
+            );
+        }
+        if self.config.formula_template.contains(['\n', '\r']) {
+            return invalid(
+                "with_formula_template",
+                self.config.formula_template.clone(),
+                "must not contain line breaks",
+            );
+        }
+
+        // 7. 進捗の通知間隔の検証
+        if self.config.progress_interval == 0 {
+            return invalid(
+                "with_progress_interval",
+                "0".to_string(),
+                "must be greater than 0 rows",
+            );
+        }
+
+        // 8. 名前で指定した独自の出力フォーマットの解決
+        let mut config = self.config;
+        if let Some(name) = &config.format_name {
+            let plugin = crate::plugin::find_format(name).ok_or_else(|| {
+                let mut names: Vec<String> =
+                    OutputFormat::NAMES.iter().map(|n| n.to_string()).collect();
+                names.extend(
+                    crate::plugin::registered_formats()
+                        .iter()
+                        .map(|plugin| plugin.name().to_string()),
+                );
+                XlsxToMdError::Config(ConfigError::UnknownName {
+                    kind: "output format",
+                    value: name.clone(),
+                    expected: names,
+                })
+            })?;
+            let capabilities = plugin.capabilities();
+            config.output_format = capabilities.layout;
+            if !capabilities.supports_merged_cells {
+                config.merge_strategy = MergeStrategy::DataDuplication;
+            }
+            config.format_plugin = Some(FormatHandle(plugin));
+        }
+
+        // 9. 行の絞り込み条件（正規表現）の検証とコンパイル
+        let row_filter = RowFilter::compile(&config.content_filter)?;
 
-        // 3. Converterインスタンス生成
-        Ok(Converter::new(self.config))
+        // 10. 計算列の式の検証とコンパイル
+        let computed_columns = config
+            .computed_columns
+            .iter()
+            .map(|(name, expr)| ComputedColumn::compile(name, expr))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 11. Converterインスタンス生成
+        Ok(Converter::new(config, row_filter, computed_columns))
     }
 }
 
@@ -357,253 +3087,72 @@ impl ConverterBuilder {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Converter {
     /// 変換設定
-    config: ConversionConfig,
+    pub(crate) config: ConversionConfig,
 
     /// セルフォーマッター
-    formatter: crate::formatter::CellFormatter,
+    pub(crate) formatter: crate::formatter::CellFormatter,
+
+    /// 設定のハッシュ値（フィンガープリント用）
+    pub(crate) config_hash: u64,
+
+    /// コンパイル済みの行の絞り込み条件
+    pub(crate) row_filter: Option<RowFilter>,
+
+    /// コンパイル済みの計算列（追加順）
+    pub(crate) computed_columns: Vec<ComputedColumn>,
 }
 
 impl Converter {
-    pub(crate) fn new(config: ConversionConfig) -> Self {
+    pub(crate) fn new(
+        config: ConversionConfig,
+        row_filter: Option<RowFilter>,
+        computed_columns: Vec<ComputedColumn>,
+    ) -> Self {
         Self {
             formatter: crate::formatter::CellFormatter::new(),
+            config_hash: config.fingerprint_hash(),
             config,
+            row_filter,
+            computed_columns,
         }
     }
 
-    /// ExcelファイルをMarkdown形式に変換
-    ///
-    /// # 引数
-    ///
-    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
-    /// * `output` - Markdown出力先のライター（Writeトレイトを実装）
-    ///
-    /// # 戻り値
-    ///
-    /// * `Ok(())` - 変換に成功した場合
-    /// * `Err(XlsxToMdError)` - エラーが発生した場合
-    ///
-    /// # 処理フロー
-    ///
-    /// 1. WorkbookParserの初期化
-    /// 2. シート選択
-    /// 3. 各シートについて処理（ループ）
-    ///    - シートのパース
-    ///    - セルのフォーマット
-    ///    - グリッドの構築
-    ///    - Markdown/HTML出力（戦略に応じて）
-    /// 4. 出力バッファをフラッシュ
-    ///
-    /// # 使用例
-    ///
-    /// ## ファイルからファイルへの変換
-    ///
-    /// ```rust,no_run
-    /// use xlsxzero::ConverterBuilder;
-    /// use std::fs::File;
-    ///
-    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
-    /// let converter = ConverterBuilder::new().build()?;
-    /// let input = File::open("example.xlsx")?;
-    /// let output = File::create("output.md")?;
-    /// converter.convert(input, output)?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    ///
-    /// 注意: `File`は`Clone`を実装していないため、メモリバッファを使用する場合は`Cursor`を使用してください。
-    ///
-    /// ## メモリバッファからの変換
-    ///
-    /// ```rust,no_run
-    /// use xlsxzero::ConverterBuilder;
-    /// use std::io::Cursor;
-    ///
-    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
-    /// let converter = ConverterBuilder::new().build()?;
-    /// let excel_data: Vec<u8> = vec![]; // Excelファイルのバイト列
-    /// let mut markdown_output = Vec::new();
-    /// converter.convert(Cursor::new(excel_data), &mut markdown_output)?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    ///
-    /// ## 標準出力への変換
-    ///
-    /// ```rust,no_run
-    /// use xlsxzero::ConverterBuilder;
-    /// use std::fs::File;
-    ///
-    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
-    /// let converter = ConverterBuilder::new().build()?;
-    /// let input = File::open("example.xlsx")?;
-    /// converter.convert(input, std::io::stdout())?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    ///
-    /// 注意: `File`は`Clone`を実装していないため、実際の使用では`File`を直接使用できますが、
-    /// メモリバッファを使用する場合は`Cursor`を使用してください。
-    pub fn convert<R: Read + Seek, W: Write>(
-        &self,
-        mut input: R,
-        mut output: W,
-    ) -> Result<(), XlsxToMdError> {
-        use std::io::{BufWriter, Write};
-
-        // 1. 入力データをメモリに読み込む（並列処理のため）
-        use crate::security::SecurityConfig;
-        let security_config = SecurityConfig::default();
-        let mut buffer = Vec::new();
-        let bytes_read = input.read_to_end(&mut buffer)?;
-
-        if bytes_read as u64 > security_config.max_input_file_size {
-            return Err(XlsxToMdError::SecurityViolation(format!(
-                "Input file size exceeds maximum: {} bytes (max: {} bytes)",
-                bytes_read, security_config.max_input_file_size
-            )));
-        }
-
-        // 2. WorkbookParserの初期化（Phase II: メタデータも取得）
-        // 非表示行・列の情報を取得するため、open_with_metadata()を使用
-        let parser = crate::parser::WorkbookParser::open_with_metadata(Cursor::new(buffer.clone()))?;
-
-        // 3. シート選択
-        let sheet_names =
-            parser.select_sheets(&self.config.sheet_selector, self.config.include_hidden)?;
-
-        // 4. メタデータを1回だけ解析して再利用（並列処理の効率化）
-        // メタデータを抽出（WorkbookParserから取得）
-        let metadata = parser.metadata()
-            .ok_or_else(|| XlsxToMdError::Config("Metadata not available".to_string()))?
-            .clone();
-
-        // 5. 各シートの処理を並列化
-        // 各シートの処理結果（出力文字列）を並列に計算
-        let sheet_outputs: Result<Vec<(usize, String)>, XlsxToMdError> = sheet_names
-            .par_iter()
-            .enumerate()
-            .map(|(sheet_idx, sheet_name)| {
-                // 各シート処理でワークブックを再オープン（メモリ内のデータを使用）
-                // メタデータは既に解析済みなので再利用
-                let mut parser = crate::parser::WorkbookParser::open_with_existing_metadata(
-                    Cursor::new(buffer.clone()),
-                    metadata.clone(),
-                )?;
-
-                // シートのパース
-                let (metadata, raw_cells) = parser.parse_sheet(sheet_name, &self.config)?;
-
-                // セルのフォーマット
-                let mut formatted_cells = Vec::new();
-                for raw_cell in &raw_cells {
-                    let content =
-                        self.formatter
-                            .format_cell(raw_cell, &self.config, metadata.is_1904)?;
-                    formatted_cells.push((raw_cell.coord, content));
-                }
-
-                // グリッドの構築
-                let grid = crate::grid::LogicalGrid::build(
-                    raw_cells,
-                    formatted_cells,
-                    &metadata,
-                    self.config.merge_strategy,
-                )?;
-
-                // 出力フォーマッターを取得
-                let formatter = crate::output::OutputFormatter::from_format(self.config.output_format);
-
-                // 出力フォーマットに応じて出力
-                let mut output_buffer = Vec::new();
-                formatter.render(&grid, &mut output_buffer, &metadata.merged_regions)?;
-
-                let output_string = String::from_utf8(output_buffer).map_err(|e| {
-                    XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
-                })?;
-
-                Ok((sheet_idx, output_string))
-            })
-            .collect();
-
-        let mut sheet_outputs = sheet_outputs?;
-
-        // 結果をインデックス順にソート（並列処理の順序を保証）
-        sheet_outputs.sort_by_key(|(idx, _)| *idx);
-
-        // 5. 結果を順序付きで出力
-        let mut writer = BufWriter::new(&mut output);
-        for (sheet_idx, (_, sheet_output)) in sheet_outputs.iter().enumerate() {
-            // シート間の区切り（Markdown形式の場合のみ）
-            if sheet_idx > 0 && self.config.output_format == crate::api::OutputFormat::Markdown {
-                writeln!(writer, "\n---\n")?;
-            } else if sheet_idx > 0 {
-                // 他のフォーマットの場合は改行のみ
-                writeln!(writer)?;
-            }
-
-            // シート名をヘッダーとして出力（Markdown形式の場合のみ）
-            if self.config.output_format == crate::api::OutputFormat::Markdown {
-                writeln!(writer, "# {}\n", sheet_names[sheet_idx])?;
-            } else if self.config.output_format == crate::api::OutputFormat::Json {
-                // JSON形式の場合は、シート名を含める（既にformatterで処理済みの場合はスキップ）
-                // ここでは既にJSONが生成されているので、そのまま出力
-            } else {
-                // CSV/HTML形式の場合は、シート名をコメントとして出力
-                if self.config.output_format == crate::api::OutputFormat::Csv {
-                    writeln!(writer, "# Sheet: {}\n", sheet_names[sheet_idx])?;
-                } else if self.config.output_format == crate::api::OutputFormat::Html {
-                    writeln!(writer, "<!-- Sheet: {} -->\n", sheet_names[sheet_idx])?;
-                }
-            }
-
-            // シートの出力
-            write!(writer, "{}", sheet_output)?;
-        }
-
-        // 6. フラッシュ
-        writer.flush()?;
-
-        Ok(())
+    /// 出力フォーマットを取得（`BatchConverter`の出力ファイルの拡張子に使用）
+    pub(crate) fn output_format(&self) -> OutputFormat {
+        self.config.output_format
     }
 
-    /// ExcelファイルをMarkdown形式の文字列に変換
-    ///
-    /// # 引数
+    /// 変換に使用するバージョンと設定のフィンガープリントを取得
     ///
-    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// クレートのバージョンと、出力に影響する設定のハッシュ値（XXH3 64bit）からなる文字列です。
+    /// 同じバージョン・同じ設定のConverterは常に同じ値を返すため、変換結果のキャッシュの
+    /// 無効化判定に使用できます。`ConverterBuilder::include_fingerprint(true)`の場合、
+    /// 同じ値が出力にも含まれます。
     ///
     /// # 戻り値
     ///
-    /// * `Ok(String)` - 変換されたMarkdown文字列
-    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    /// フィンガープリント（例: `xlsxzero 0.1.0 config:xxh3:0123456789abcdef`）
     ///
     /// # 使用例
     ///
-    /// ```rust,no_run
-    /// use std::fs::File;
+    /// ```rust
     /// use xlsxzero::ConverterBuilder;
     ///
     /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
     /// let converter = ConverterBuilder::new().build()?;
-    /// let input = File::open("example.xlsx")?;
-    /// let markdown = converter.convert_to_string(input)?;
-    /// println!("{}", markdown);
+    /// assert!(converter.fingerprint().starts_with("xlsxzero "));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn convert_to_string<R: Read + Seek>(&self, input: R) -> Result<String, XlsxToMdError> {
-        let mut buffer = Vec::new();
-        self.convert(input, &mut buffer)?;
-
-        let result = String::from_utf8(buffer).map_err(|e| {
-            XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
-        })?;
-
-        Ok(result)
+    pub fn fingerprint(&self) -> String {
+        format!(
+            "xlsxzero {} config:{}",
+            env!("CARGO_PKG_VERSION"),
+            crate::output::config_hash_string(self.config_hash)
+        )
     }
 }
 
@@ -648,6 +3197,57 @@ mod tests {
         assert_eq!(builder.config.merge_strategy, MergeStrategy::HtmlFallback);
     }
 
+    #[test]
+    fn test_with_forward_fill() {
+        let builder = ConverterBuilder::new()
+            .with_forward_fill(ColumnSelector::Index(0))
+            .with_forward_fill(ColumnSelector::Header("Region".to_string()));
+        assert_eq!(
+            builder.config.forward_fill,
+            [
+                ColumnSelector::Index(0),
+                ColumnSelector::Header("Region".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_validations() {
+        let builder = ConverterBuilder::new()
+            .with_validations([Validation::Unique(ColumnSelector::Index(0))])
+            .with_validations(vec![Validation::NotNull(ColumnSelector::Index(1))]);
+        assert_eq!(
+            builder.config.validations,
+            [
+                Validation::Unique(ColumnSelector::Index(0)),
+                Validation::NotNull(ColumnSelector::Index(1))
+            ]
+        );
+        assert_eq!(builder.config.validation_policy, ValidationPolicy::Report);
+
+        // 検証は出力を変更しないため、フィンガープリントに影響しない
+        let fail = builder.with_validation_policy(ValidationPolicy::Fail);
+        assert_eq!(fail.config.validation_policy, ValidationPolicy::Fail);
+        assert_eq!(
+            fail.config.fingerprint_hash(),
+            ConverterBuilder::new().config.fingerprint_hash()
+        );
+    }
+
+    #[test]
+    fn test_with_duplication_placement() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(
+            builder.config.duplication_placement,
+            DuplicationPlacement::All
+        );
+        let builder = builder.with_duplication_placement(DuplicationPlacement::Middle);
+        assert_eq!(
+            builder.config.duplication_placement,
+            DuplicationPlacement::Middle
+        );
+    }
+
     #[test]
     fn test_with_date_format() {
         let builder = ConverterBuilder::new()
@@ -679,6 +3279,37 @@ mod tests {
         assert_eq!(range.end, CellCoord::new(9, 2));
     }
 
+    #[test]
+    fn test_with_range_a1() {
+        let builder = ConverterBuilder::new()
+            .with_range_a1("B2:F100")
+            .with_range_a1("Sheet2!A1:C10")
+            .with_range_a1("'Q1 Data'!A:C")
+            .with_range_a1("Sheet2!A1:B2");
+        let config = builder.config();
+        assert_eq!(config.range, Some("B2:F100".parse().unwrap()));
+        assert_eq!(config.sheet_ranges.len(), 2);
+        assert_eq!(config.range_for("Sheet2"), Some("A1:B2".parse().unwrap()));
+        assert_eq!(config.range_for("Q1 Data"), Some("A:C".parse().unwrap()));
+        assert_eq!(config.range_for("Sheet1"), config.range);
+
+        // 範囲の指定があるシートのみ設定を複製する
+        assert!(matches!(config.for_sheet("Sheet1"), Cow::Borrowed(_)));
+        assert_eq!(
+            config.for_sheet("Sheet2").range,
+            Some(CellRange::new(CellCoord::new(0, 0), CellCoord::new(1, 1)))
+        );
+
+        let result = ConverterBuilder::new().with_range_a1("B2:").build();
+        match result {
+            Err(XlsxToMdError::Config(ConfigError::InvalidValue { option, value, .. })) => {
+                assert_eq!(option, "with_range_a1");
+                assert_eq!(value, "B2:");
+            }
+            other => panic!("Expected Config error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_build_success() {
         let result = ConverterBuilder::new().build();
@@ -690,10 +3321,16 @@ mod tests {
         let result = ConverterBuilder::new().with_range((10, 0), (0, 0)).build();
         assert!(result.is_err());
         match result {
-            Err(XlsxToMdError::Config(msg)) => {
-                assert!(msg.contains("start row"));
+            Err(XlsxToMdError::Config(ConfigError::InvalidValue {
+                option,
+                value,
+                reason,
+            })) => {
+                assert_eq!(option, "with_range");
+                assert_eq!(value, "A11:A1");
+                assert!(reason.contains("start row"));
             }
-            _ => panic!("Expected Config error"),
+            other => panic!("Expected Config error, got {:?}", other),
         }
     }
 
@@ -702,10 +3339,47 @@ mod tests {
         let result = ConverterBuilder::new().with_range((0, 10), (0, 0)).build();
         assert!(result.is_err());
         match result {
-            Err(XlsxToMdError::Config(msg)) => {
-                assert!(msg.contains("start col"));
+            Err(XlsxToMdError::Config(ConfigError::InvalidValue { option, reason, .. })) => {
+                assert_eq!(option, "with_range");
+                assert!(reason.contains("start col"));
+            }
+            other => panic!("Expected Config error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_build_with_invalid_content_filter() {
+        let result = ConverterBuilder::new()
+            .with_content_filter(ContentFilter {
+                include_rows_matching: Some("(unclosed".to_string()),
+                exclude_rows_matching: None,
+            })
+            .build();
+        match result {
+            Err(XlsxToMdError::Config(ConfigError::InvalidSyntax { option, text, .. })) => {
+                assert_eq!(option, "with_content_filter");
+                assert_eq!(text, "(unclosed");
+            }
+            other => panic!("Expected Config error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_build_with_invalid_computed_column() {
+        let result = ConverterBuilder::new()
+            .with_computed_column("total", "amount * (rate")
+            .build();
+        match result {
+            Err(XlsxToMdError::Config(ConfigError::InvalidSyntax {
+                option,
+                text,
+                message,
+            })) => {
+                assert_eq!(option, "with_computed_column");
+                assert_eq!(text, "amount * (rate");
+                assert!(message.contains("'total'"), "Got: {}", message);
             }
-            _ => panic!("Expected Config error"),
+            other => panic!("Expected Config error, got {:?}", other),
         }
     }
 
@@ -725,10 +3399,10 @@ mod tests {
             .build();
         assert!(result.is_err());
         match result {
-            Err(XlsxToMdError::Config(msg)) => {
-                assert!(msg.contains("Invalid date format"));
+            Err(XlsxToMdError::Config(ConfigError::InvalidValue { option, .. })) => {
+                assert_eq!(option, "with_date_format");
             }
-            _ => panic!("Expected Config error"),
+            other => panic!("Expected Config error, got {:?}", other),
         }
     }
 
@@ -768,6 +3442,241 @@ mod tests {
     }
 
     // Converter構造体のテスト
+    #[test]
+    fn test_with_number_output() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.number_output(), NumberOutput::Formatted);
+
+        let builder = ConverterBuilder::new().with_output_format(OutputFormat::Json);
+        assert_eq!(builder.config.number_output(), NumberOutput::Raw);
+
+        let builder = ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .with_number_output(OutputFormat::Csv, NumberOutput::Raw)
+            .with_number_output(OutputFormat::Csv, NumberOutput::Formatted);
+        assert_eq!(builder.config.number_output(), NumberOutput::Formatted);
+        assert_eq!(builder.config.number_outputs.len(), 1);
+    }
+
+    #[test]
+    fn test_preset() {
+        let rag = ConverterBuilder::preset(Preset::Rag);
+        assert_eq!(rag.config.output_format, OutputFormat::Markdown);
+        assert_eq!(rag.config.merge_strategy, MergeStrategy::DataDuplication);
+        assert!(rag.config.include_metadata);
+        assert!(!rag.config.include_hidden);
+        assert_eq!(rag.config.trim_empty, TrimMode::All);
+        assert_eq!(rag.config.row_dedupe, RowDedupe::ConsecutiveWithCount);
+        assert_eq!(rag.config.table_chunk_rows, Some((50, ChunkHeader::Repeat)));
+
+        let faithful = ConverterBuilder::preset(Preset::Faithful);
+        assert_eq!(faithful.config.merge_strategy, MergeStrategy::HtmlFallback);
+        assert!(faithful.config.include_hidden);
+        assert_eq!(faithful.config.number_output(), NumberOutput::Formatted);
+
+        let compact = ConverterBuilder::preset(Preset::Compact);
+        assert_eq!(compact.config.output_format, OutputFormat::Markdown);
+        assert_eq!(compact.config.number_output(), NumberOutput::Raw);
+        assert!(compact.config.auto_shrink_used_range);
+
+        let machine = ConverterBuilder::preset(Preset::MachineReadable);
+        assert_eq!(machine.config.output_format, OutputFormat::Json);
+        assert_eq!(machine.config.json_cell_mode, JsonCellMode::Typed);
+        assert!(machine.config.include_metadata);
+
+        // プリセット適用後も個別の設定で上書きできる
+        let overridden = ConverterBuilder::preset(Preset::MachineReadable)
+            .with_output_format(OutputFormat::Csv)
+            .include_metadata(false);
+        assert_eq!(overridden.config.output_format, OutputFormat::Csv);
+        assert!(!overridden.config.include_metadata);
+        assert!(overridden.build().is_ok());
+    }
+
+    #[test]
+    fn test_with_json_cell_mode() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.json_cell_mode, JsonCellMode::Plain);
+
+        let builder = ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_json_cell_mode(JsonCellMode::Typed);
+        assert_eq!(builder.config.json_cell_mode, JsonCellMode::Typed);
+        // 型付きJSONの表示文字列は常に書式適用後の値
+        assert_eq!(builder.config.number_output(), NumberOutput::Formatted);
+    }
+
+    #[test]
+    fn test_with_json_key_mode() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.json_key_mode, JsonKeyMode::ColumnLetter);
+
+        let builder = ConverterBuilder::new().with_json_key_mode(JsonKeyMode::NormalizedHeader);
+        assert_eq!(builder.config.json_key_mode, JsonKeyMode::NormalizedHeader);
+    }
+
+    #[test]
+    fn test_with_front_matter_extra() {
+        let builder = ConverterBuilder::new()
+            .with_front_matter_extra([("tenant_id".to_string(), "a".to_string())])
+            .with_front_matter_extra([("tenant_id".to_string(), "b".to_string())]);
+        assert_eq!(builder.config.front_matter_extra["tenant_id"], "b");
+        assert!(builder.config.front_matter_enabled());
+        assert!(builder.build().is_ok());
+
+        // 予約済みのキーと空のキーは使用できない
+        for key in ["sheets", " "] {
+            let result = ConverterBuilder::new()
+                .with_front_matter_extra([(key.to_string(), "x".to_string())])
+                .build();
+            assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+        }
+    }
+
+    #[test]
+    fn test_with_json_document_mode() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.json_document_mode, JsonDocumentMode::Sheets);
+
+        let builder = ConverterBuilder::new().with_json_document_mode(JsonDocumentMode::Lines);
+        assert_eq!(builder.config.json_document_mode, JsonDocumentMode::Lines);
+    }
+
+    #[test]
+    fn test_with_json_style() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.json_style, JsonStyle::Pretty);
+        assert!(!builder.config.json_minified());
+
+        let builder = ConverterBuilder::new().with_json_style(JsonStyle::Minified);
+        assert!(builder.config.json_minified());
+
+        // NDJSONは常に1行
+        let builder = ConverterBuilder::new().with_json_document_mode(JsonDocumentMode::Lines);
+        assert!(builder.config.json_minified());
+    }
+
+    #[test]
+    fn test_with_max_output_size() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.max_output_size, None);
+        assert_eq!(builder.config.output_overflow, OutputOverflow::Error);
+
+        let builder = ConverterBuilder::new()
+            .with_max_output_size(1024)
+            .with_output_overflow(OutputOverflow::Truncate);
+        assert_eq!(builder.config.max_output_size, Some(1024));
+        assert_eq!(builder.config.output_overflow, OutputOverflow::Truncate);
+    }
+
+    #[test]
+    fn test_with_per_sheet_limits() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.per_sheet_limits, None);
+
+        let limits = (100, usize::MAX, Duration::from_secs(5));
+        let builder = ConverterBuilder::new().with_per_sheet_limits(limits.0, limits.1, limits.2);
+        assert_eq!(builder.config.per_sheet_limits, Some(limits));
+        assert!(builder.build().is_ok());
+
+        // 0の上限は拒否
+        for (rows, cells, duration) in [
+            (0, 10, Duration::from_secs(1)),
+            (10, 0, Duration::from_secs(1)),
+            (10, 10, Duration::ZERO),
+        ] {
+            let result = ConverterBuilder::new()
+                .with_per_sheet_limits(rows, cells, duration)
+                .build();
+            assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+        }
+    }
+
+    #[test]
+    fn test_with_duplicate_sheets() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.duplicate_sheets, DuplicateSheets::Keep);
+
+        let builder = ConverterBuilder::new().with_duplicate_sheets(DuplicateSheets::Skip);
+        assert_eq!(builder.config.duplicate_sheets, DuplicateSheets::Skip);
+    }
+
+    #[test]
+    fn test_with_null_formula_policy() {
+        let builder = ConverterBuilder::new();
+        assert_eq!(builder.config.null_formula_policy, NullFormulaPolicy::Empty);
+
+        let builder = ConverterBuilder::new()
+            .with_null_formula_policy(NullFormulaPolicy::Marker("#NOVALUE".to_string()));
+        assert_eq!(
+            builder.config.null_formula_policy,
+            NullFormulaPolicy::Marker("#NOVALUE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_with_sheet_override() {
+        let builder = ConverterBuilder::new()
+            .with_output_format(OutputFormat::Markdown)
+            .with_sheet_override("Data", OutputFormat::Json)
+            .with_sheet_override("Data", OutputFormat::Csv);
+        let config = builder.config();
+        assert_eq!(config.sheet_formats.len(), 1);
+        assert_eq!(config.output_format_for("Data"), OutputFormat::Csv);
+        assert_eq!(config.output_format_for("Notes"), OutputFormat::Markdown);
+
+        // 上書きがあるシートのみ設定を複製する
+        assert!(matches!(config.for_sheet("Notes"), Cow::Borrowed(_)));
+        let data_config = config.for_sheet("Data");
+        assert_eq!(data_config.output_format, OutputFormat::Csv);
+        assert_eq!(data_config.number_output(), NumberOutput::Raw);
+    }
+
+    #[test]
+    fn test_fingerprint_hash() {
+        let hash = |builder: ConverterBuilder| builder.config().fingerprint_hash();
+        let base = hash(ConverterBuilder::new());
+
+        // 同じ設定なら同じハッシュ値（指定順に依存しない）
+        assert_eq!(base, hash(ConverterBuilder::new()));
+        assert_eq!(
+            hash(
+                ConverterBuilder::new()
+                    .with_number_output(OutputFormat::Csv, NumberOutput::Formatted)
+                    .with_number_output(OutputFormat::Json, NumberOutput::Formatted)
+            ),
+            hash(
+                ConverterBuilder::new()
+                    .with_number_output(OutputFormat::Json, NumberOutput::Formatted)
+                    .with_number_output(OutputFormat::Csv, NumberOutput::Formatted)
+            )
+        );
+
+        // 出力に影響しない設定は含めない
+        assert_eq!(
+            base,
+            hash(ConverterBuilder::new().with_max_input_size(1024))
+        );
+        assert_eq!(
+            base,
+            hash(ConverterBuilder::new().include_fingerprint(true))
+        );
+
+        // 出力に影響する設定が変わればハッシュ値も変わる
+        assert_ne!(
+            base,
+            hash(ConverterBuilder::new().with_output_format(OutputFormat::Json))
+        );
+        assert_ne!(
+            base,
+            hash(ConverterBuilder::new().with_date_format(DateFormat::Custom("%Y".to_string())))
+        );
+        assert_ne!(
+            base,
+            hash(ConverterBuilder::new().with_sheet_override("Data", OutputFormat::Csv))
+        );
+    }
+
     #[test]
     fn test_converter_new() {
         let _converter = ConverterBuilder::new().build().unwrap();
@@ -0,0 +1,134 @@
+//! Chunks Module
+//!
+//! `Converter::convert_to_chunks()`で返す、シート単位のチャンクのイテレーターを提供するモジュール。
+
+use super::driver::SheetQueue;
+use super::sheet::RenderedSheet;
+use crate::builder::Converter;
+use crate::error::XlsxToMdError;
+use crate::output::DocumentLayout;
+use crate::parser::WorkbookParserPool;
+use std::collections::VecDeque;
+
+/// シート単位のチャンクを出力順に返すイテレーター（`Converter::convert_to_chunks()`の内部実装）
+///
+/// 未変換のシートがある場合は、チャンクを使い切るたびに並列処理できる数のシートを変換します。
+pub(super) struct Chunks<'a> {
+    /// 変換に使用するコンバーター
+    converter: &'a Converter,
+
+    /// シートを1つの文書にまとめるレイアウト
+    layout: DocumentLayout,
+
+    /// 先頭のチャンクに含める文書の先頭（出力後はNone）
+    header: Option<Vec<u8>>,
+
+    /// 文書全体のシート数
+    sheet_count: usize,
+
+    /// 未変換のシートを変換するパーサーのプールと、シート名（出力順）
+    pending_sheets: Option<(WorkbookParserPool, Vec<String>)>,
+
+    /// 次に変換するシートのインデックス
+    next_sheet: usize,
+
+    /// 生成済みで、まだ返していないチャンク（またはエラー）
+    ready: VecDeque<Result<String, XlsxToMdError>>,
+}
+
+impl<'a> Chunks<'a> {
+    /// 出力順のシートからチャンクのイテレーターを生成
+    ///
+    /// 変換済みのシートはすべてチャンクにし、未変換のシートはイテレーターを進めるたびに変換します。
+    pub(super) fn new(
+        converter: &'a Converter,
+        layout: DocumentLayout,
+        sheets: SheetQueue,
+    ) -> Result<Self, XlsxToMdError> {
+        let mut header = Vec::new();
+        converter.write_document_header(&mut header, sheets.rendered())?;
+        layout.write_prologue(&mut header)?;
+        let mut chunks = Self {
+            converter,
+            layout,
+            header: Some(header),
+            sheet_count: sheets.len(),
+            pending_sheets: None,
+            next_sheet: 0,
+            ready: VecDeque::new(),
+        };
+        match sheets {
+            SheetQueue::Pending {
+                parsers,
+                sheet_names,
+            } => chunks.pending_sheets = Some((parsers, sheet_names)),
+            SheetQueue::Rendered(sheets) => {
+                for (sheet_idx, sheet) in sheets.into_iter().enumerate() {
+                    let chunk = chunks.chunk(sheet_idx, &sheet);
+                    chunks.ready.push_back(chunk);
+                }
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// 1シート分のチャンクを生成（内部ヘルパー）
+    ///
+    /// 先頭のチャンクには文書の先頭を、最後のチャンクには文書の末尾を含めます。
+    fn chunk(&mut self, sheet_idx: usize, sheet: &RenderedSheet) -> Result<String, XlsxToMdError> {
+        // シートの出力は1回の確保で連結する
+        let mut chunk = self.header.take().unwrap_or_default();
+        chunk.reserve(sheet.output.len() + 32);
+        self.layout.write_sheet(
+            &mut chunk,
+            sheet_idx,
+            &sheet.info.heading(),
+            sheet.format,
+            &sheet.output,
+        )?;
+        if sheet_idx + 1 == self.sheet_count {
+            self.layout.write_epilogue(&mut chunk)?;
+        }
+        String::from_utf8(chunk)
+            .map_err(|e| XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+    }
+
+    /// 並列処理できる数の未変換のシートを変換し、チャンクを生成（内部ヘルパー）
+    ///
+    /// エラーが発生した場合は、それまでのチャンクの後にエラーを追加し、以降のシートは変換しません。
+    fn render_next(&mut self) {
+        let Some((parsers, sheet_names)) = self.pending_sheets.take() else {
+            return;
+        };
+        let start = self.next_sheet;
+        let end = (start + crate::ordered::concurrency()).min(sheet_names.len());
+        let converter = self.converter;
+        let result = converter.render_loaded_sheets(
+            &parsers,
+            &sheet_names[start..end],
+            start,
+            |idx, sheet| {
+                let chunk = self.chunk(idx, &sheet)?;
+                self.ready.push_back(Ok(chunk));
+                Ok(())
+            },
+        );
+        self.next_sheet = end;
+        match result {
+            Err(e) => self.ready.push_back(Err(e)),
+            Ok(()) if end < sheet_names.len() => self.pending_sheets = Some((parsers, sheet_names)),
+            Ok(()) => {}
+        }
+    }
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = Result<String, XlsxToMdError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ready.is_empty() {
+            self.render_next();
+        }
+        self.ready.pop_front()
+    }
+}
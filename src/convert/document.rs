@@ -0,0 +1,143 @@
+//! Document Assembly Module
+//!
+//! 変換したシートを文書として出力する際の、文書の先頭（フロントマターとフィンガープリント）、
+//! レイアウトとシート単位の本文を生成するモジュール。
+
+use super::sheet::RenderedSheet;
+use crate::api::OutputFormat;
+use crate::builder::Converter;
+use crate::error::XlsxToMdError;
+use crate::output::DocumentLayout;
+use crate::sink::{OutputSink, SheetDocument};
+use crate::types::SheetInfo;
+use std::borrow::Cow;
+use std::io::Write;
+
+impl Converter {
+    /// 文書の先頭（フロントマターとフィンガープリント）を出力（内部ヘルパー）
+    ///
+    /// フロントマターはMarkdown形式で、メタデータまたは追加の項目を含める場合のみ出力します。
+    pub(super) fn write_document_header<W: Write>(
+        &self,
+        writer: &mut W,
+        sheets: &[RenderedSheet],
+    ) -> Result<(), XlsxToMdError> {
+        // フロントマター（Markdown形式でメタデータまたは追加の項目を含める場合のみ）
+        if self.config.front_matter_enabled() && self.config.output_format == OutputFormat::Markdown
+        {
+            let infos: Vec<SheetInfo> = if self.config.include_metadata {
+                sheets.iter().map(|sheet| sheet.info.clone()).collect()
+            } else {
+                Vec::new()
+            };
+            crate::output::write_front_matter(writer, &self.config.front_matter_extra, &infos)?;
+        }
+
+        // フィンガープリント（JSON形式では各シートのオブジェクトに含める）
+        if self.config.include_fingerprint {
+            self.write_fingerprint(writer, self.config.output_format)?;
+        }
+        Ok(())
+    }
+
+    /// 変換したシートを1つの文書にまとめるレイアウトを生成（内部ヘルパー）
+    pub(super) fn document_layout(
+        &self,
+        sheets: &[RenderedSheet],
+        revisions: &[crate::report::Revision],
+    ) -> DocumentLayout {
+        let sheets: Vec<(&str, OutputFormat)> = sheets
+            .iter()
+            .map(|sheet| (sheet.name.as_str(), sheet.format))
+            .collect();
+        DocumentLayout::from_config(&self.config, &sheets, revisions)
+    }
+
+    /// フィンガープリントを出力フォーマットに応じたコメントとして出力（内部ヘルパー）
+    ///
+    /// JSON形式では各シートのオブジェクトに含めるため、何も出力しません。
+    pub(super) fn write_fingerprint<W: Write>(
+        &self,
+        writer: &mut W,
+        format: OutputFormat,
+    ) -> Result<(), XlsxToMdError> {
+        match format {
+            OutputFormat::Markdown | OutputFormat::Html | OutputFormat::Xml => {
+                writeln!(writer, "<!-- {} -->\n", self.fingerprint())?
+            }
+            OutputFormat::Csv => writeln!(writer, "# {}\n", self.fingerprint())?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 選択されたシート名から文書のレイアウトを生成（内部ヘルパー）
+    ///
+    /// シートを除外する処理がない場合、レイアウトはシート名と出力フォーマットのみで決まります。
+    pub(super) fn streaming_layout(
+        &self,
+        sheet_names: &[String],
+        revisions: &[crate::report::Revision],
+    ) -> DocumentLayout {
+        let sheets: Vec<(&str, OutputFormat)> = sheet_names
+            .iter()
+            .map(|name| (name.as_str(), self.config.for_sheet(name).output_format))
+            .collect();
+        DocumentLayout::from_config(&self.config, &sheets, revisions)
+    }
+
+    /// シート単位で出力する1シートの本文を生成（内部ヘルパー）
+    ///
+    /// Markdown形式のシートには、設定に応じてフロントマターとフィンガープリントを先頭に付加します。
+    pub(super) fn sheet_content<'a>(
+        &self,
+        sheet: &'a RenderedSheet,
+    ) -> Result<Cow<'a, str>, XlsxToMdError> {
+        let mut header = Vec::new();
+        if self.config.front_matter_enabled() && sheet.format == OutputFormat::Markdown {
+            let infos = if self.config.include_metadata {
+                std::slice::from_ref(&sheet.info)
+            } else {
+                &[]
+            };
+            crate::output::write_front_matter(&mut header, &self.config.front_matter_extra, infos)?;
+        }
+        if self.config.include_fingerprint {
+            self.write_fingerprint(&mut header, sheet.format)?;
+        }
+        let content = if header.is_empty() {
+            Cow::Borrowed(sheet.output.as_str())
+        } else {
+            let mut content = String::from_utf8(header).map_err(|e| {
+                XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+            })?;
+            content.push_str(&sheet.output);
+            Cow::Owned(content)
+        };
+        Ok(content)
+    }
+
+    /// 1シートの出力を出力先（`OutputSink`）に渡す（内部ヘルパー）
+    ///
+    /// 本文は`sheet_content()`で生成します。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(u64)` - 出力先に渡した内容のバイト数
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（出力先のエラーを含む）
+    pub(super) fn write_to_sink<S: OutputSink + ?Sized>(
+        &self,
+        sink: &mut S,
+        sheet_idx: usize,
+        sheet: &RenderedSheet,
+    ) -> Result<u64, XlsxToMdError> {
+        let content = self.sheet_content(sheet)?;
+        sink.write_sheet(&SheetDocument {
+            name: &sheet.name,
+            index: sheet_idx,
+            format: sheet.format,
+            content: &content,
+        })?;
+        Ok(content.len() as u64)
+    }
+}
@@ -0,0 +1,409 @@
+//! Sheet Driver Module
+//!
+//! `Converter`の変換メソッドが共通に使用する、シート単位のドライバーを提供するモジュール。
+//!
+//! 読み込み済みのワークブックのシートを並列に処理し、出力順に受け渡します
+//! （`Converter::for_each_sheet()`）。重複シートの検出と出力サイズの上限など、
+//! すべてのシートの変換結果を必要とする設定の判定と適用もここで行うため、
+//! 各変換メソッドはシートを受け取って出力するだけです（`Converter::load_sheets()`）。
+
+use super::sheet::RenderedSheet;
+use crate::api::{DuplicateSheets, OutputFormat, OutputOverflow};
+use crate::builder::Converter;
+use crate::error::{SecurityLimit, XlsxToMdError};
+use crate::output::DocumentLayout;
+use crate::parser::{SharedBuffer, WorkbookParser, WorkbookParserPool, XlsxMetadataParser};
+use crate::report::{ConversionReport, ConversionWarning, DuplicateSheet, WarningKind};
+use std::io::{Cursor, Read, Seek};
+use std::time::{Duration, Instant};
+
+impl Converter {
+    /// すべてのシートを変換してから出力する必要があるか（内部ヘルパー）
+    ///
+    /// 重複シートの検出と出力サイズの上限は、すべてのシートの変換結果を必要とします。
+    /// シートを1つの文書に連結する場合は、Markdown形式のフロントマターへのシートの
+    /// メタデータの出力も同様です。
+    ///
+    /// # 引数
+    ///
+    /// * `document` - シートを1つの文書に連結して出力するか
+    pub(super) fn buffers_sheets(&self, document: bool) -> bool {
+        self.config.duplicate_sheets != DuplicateSheets::Keep
+            || self.config.max_output_size.is_some()
+            || (document
+                && self.config.include_metadata
+                && self.config.output_format == OutputFormat::Markdown)
+    }
+
+    /// 入力を読み込み、出力順に受け渡すシートを準備（内部ヘルパー）
+    ///
+    /// すべてのシートを変換してから出力する必要がある場合（`buffers_sheets()`）は、
+    /// すべてのシートを変換し、重複シートの検出と出力サイズの上限を適用します。
+    /// それ以外の場合、シートは`SheetQueue::for_each()`で変換が完了したものから受け渡します。
+    ///
+    /// 入力サイズと読み込み・ZIP走査・メタデータ解析（・シート処理）の時間を`report`に記録します。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー
+    /// * `report` - 処理統計の記録先
+    /// * `document` - シートを1つの文書に連結して出力するか
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok((DocumentLayout, SheetQueue))` - 文書のレイアウトと、出力順のシート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub(super) fn load_sheets<R: Read + Seek>(
+        &self,
+        input: R,
+        report: &mut ConversionReport,
+        document: bool,
+    ) -> Result<(DocumentLayout, SheetQueue), XlsxToMdError> {
+        // 1-4. 入力の読み込み、メタデータの解析、シート選択
+        let (parsers, sheet_names) = self.load_workbook(input, report)?;
+        if !self.buffers_sheets(document) {
+            // シートを除外する処理がないため、レイアウトはシート名と出力フォーマットのみで決まる
+            let layout = self.streaming_layout(&sheet_names, &report.revisions);
+            return Ok((
+                layout,
+                SheetQueue::Pending {
+                    parsers,
+                    sheet_names,
+                },
+            ));
+        }
+
+        // 5. 各シートの処理を並列化
+        let phase_start = Instant::now();
+        let mut sheets = Vec::with_capacity(sheet_names.len());
+        self.render_loaded_sheets(&parsers, &sheet_names, 0, |_, sheet| {
+            sheets.push(sheet);
+            Ok(())
+        })?;
+        report.timings.sheets = phase_start.elapsed();
+
+        // 6. 重複シートの検出
+        if self.config.duplicate_sheets != DuplicateSheets::Keep {
+            self.handle_duplicate_sheets(&mut sheets, report)?;
+        }
+
+        // 7. 出力サイズの上限
+        if let Some(max_output_size) = self.config.max_output_size {
+            self.limit_output_size(&mut sheets, report, max_output_size)?;
+        }
+
+        let layout = self.document_layout(&sheets, &report.revisions);
+        Ok((layout, SheetQueue::Rendered(sheets)))
+    }
+
+    /// 読み込み済みのワークブックのシートを並列に処理し、出力順に`emit`に渡す（内部ヘルパー）
+    ///
+    /// すべての変換メソッドが使用するシート単位のドライバーです。各シートの処理結果を
+    /// 並列に計算し、完了したものから出力順に受け取ります。入力データは複製せずに共有し、
+    /// ワークブックを開いたパーサーはシート間で再利用します。
+    ///
+    /// # 引数
+    ///
+    /// * `parsers` - `load_workbook()`で開いたパーサーのプール
+    /// * `sheet_names` - 処理するシート名（出力順）
+    /// * `first_index` - `sheet_names`の先頭のシートの、出力全体でのインデックス
+    /// * `process` - 1シートを処理する関数（パーサーとシートの情報を受け取る）
+    /// * `emit` - 処理結果を受け取る関数（出力全体でのインデックスと処理結果を受け取る）
+    pub(super) fn for_each_sheet<T, P, E>(
+        &self,
+        parsers: &WorkbookParserPool,
+        sheet_names: &[String],
+        first_index: usize,
+        process: P,
+        mut emit: E,
+    ) -> Result<(), XlsxToMdError>
+    where
+        T: Send,
+        P: Fn(&mut WorkbookParser<Cursor<SharedBuffer>>, SheetTask<'_>) -> Result<T, XlsxToMdError>
+            + Sync,
+        E: FnMut(usize, T) -> Result<(), XlsxToMdError>,
+    {
+        let metadata = parsers.metadata();
+        crate::ordered::for_each_ordered(
+            sheet_names,
+            |offset, sheet_name| {
+                let open_start = Instant::now();
+                parsers.with_parser(|parser| {
+                    let task = SheetTask {
+                        name: sheet_name,
+                        index: first_index + offset,
+                        metadata,
+                        open_time: open_start.elapsed(),
+                    };
+                    process(parser, task)
+                })
+            },
+            |offset, value| emit(first_index + offset, value),
+        )
+    }
+
+    /// 読み込み済みのワークブックのシートを並列に変換し、出力順に`emit`に渡す（内部ヘルパー）
+    ///
+    /// # 引数
+    ///
+    /// * `parsers` - `load_workbook()`で開いたパーサーのプール
+    /// * `sheet_names` - 変換するシート名（出力順）
+    /// * `first_index` - `sheet_names`の先頭のシートの、出力全体でのインデックス
+    /// * `emit` - 変換したシートを受け取る関数（出力全体でのインデックスとシートを受け取る）
+    pub(super) fn render_loaded_sheets<F>(
+        &self,
+        parsers: &WorkbookParserPool,
+        sheet_names: &[String],
+        first_index: usize,
+        emit: F,
+    ) -> Result<(), XlsxToMdError>
+    where
+        F: FnMut(usize, RenderedSheet) -> Result<(), XlsxToMdError>,
+    {
+        self.for_each_sheet(
+            parsers,
+            sheet_names,
+            first_index,
+            |parser, task| {
+                // 入力データは全体の入力サイズとして計上済み（シートごとの複製はない）
+                let mut sheet =
+                    self.convert_sheet(parser, task.name, task.index, task.metadata, 0)?;
+                // プールにパーサーがない場合はワークブックを開く時間を含む
+                sheet.report.timings.parse += task.open_time;
+                Ok(sheet)
+            },
+            emit,
+        )
+    }
+
+    /// 内容がほぼ同一のシートを検出し、設定に応じて除外または注記に置き換える（内部ヘルパー）
+    ///
+    /// 検出した重複シートは`report`の`duplicate_sheets`と警告に記録します。
+    fn handle_duplicate_sheets(
+        &self,
+        sheets: &mut Vec<RenderedSheet>,
+        report: &mut ConversionReport,
+    ) -> Result<(), XlsxToMdError> {
+        let signatures: Vec<&[u64]> = sheets.iter().map(|s| s.signature.as_slice()).collect();
+        let found = crate::duplicates::find_duplicates(&signatures);
+
+        let skip = self.config.duplicate_sheets == DuplicateSheets::Skip;
+        let mut skipped = vec![false; sheets.len()];
+        for (idx, original) in found.into_iter().enumerate() {
+            let Some((original, similarity)) = original else {
+                continue;
+            };
+            let duplicate = DuplicateSheet {
+                sheet: sheets[idx].name.clone(),
+                duplicate_of: sheets[original].name.clone(),
+                similarity,
+                skipped: skip,
+            };
+            report.warnings.push(ConversionWarning::sheet(
+                &duplicate.sheet,
+                WarningKind::DuplicateSheet,
+                format!(
+                    "near-duplicate of sheet '{}' (similarity {:.1}%){}",
+                    duplicate.duplicate_of,
+                    similarity * 100.0,
+                    if skip { "; skipped" } else { "" }
+                ),
+            ));
+            if skip {
+                skipped[idx] = true;
+            } else {
+                let sheet = &mut sheets[idx];
+                let mut output = Vec::new();
+                crate::output::write_duplicate_note(
+                    &mut output,
+                    sheet.format,
+                    &duplicate,
+                    self.config.json_minified(),
+                )?;
+                sheet.output = String::from_utf8(output).map_err(|e| {
+                    XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+                })?;
+                sheet.report.output_size = sheet.output.len() as u64;
+            }
+            report.duplicate_sheets.push(duplicate);
+        }
+
+        let mut skipped = skipped.into_iter();
+        sheets.retain(|_| !skipped.next().unwrap_or(false));
+        Ok(())
+    }
+
+    /// シートの出力の合計を上限以下に制限（内部ヘルパー）
+    ///
+    /// `OutputOverflow::Error`では上限を超えた時点でエラーを返します。
+    /// `OutputOverflow::Truncate`では上限を超えたシートを行単位で打ち切って注記を付加し、
+    /// 以降のシートを除外して`report`に警告を記録します。
+    fn limit_output_size(
+        &self,
+        sheets: &mut Vec<RenderedSheet>,
+        report: &mut ConversionReport,
+        max_output_size: u64,
+    ) -> Result<(), XlsxToMdError> {
+        let total: u64 = sheets.iter().map(|s| s.output.len() as u64).sum();
+        if total <= max_output_size {
+            return Ok(());
+        }
+        if self.config.output_overflow == OutputOverflow::Error {
+            return Err(SecurityLimit::OutputSize {
+                size: total,
+                max: max_output_size,
+            }
+            .into());
+        }
+
+        // 上限に収まるシートまでを残し、上限を超えたシートを打ち切る
+        let mut used = 0u64;
+        let mut kept = 0;
+        let mut truncated_sheet = None;
+        for sheet in sheets.iter_mut() {
+            let remaining = max_output_size - used;
+            if sheet.output.len() as u64 <= remaining {
+                used += sheet.output.len() as u64;
+                kept += 1;
+                continue;
+            }
+            // JSON・XMLは途中で打ち切ると不正になるため、シートごと除外する
+            if !matches!(sheet.format, OutputFormat::Json | OutputFormat::Xml) {
+                // 改行はASCIIのため、バイト列で探しても文字の途中で切れることはない
+                let end = sheet.output.as_bytes()[..remaining as usize]
+                    .iter()
+                    .rposition(|&b| b == b'\n')
+                    .map_or(0, |idx| idx + 1);
+                sheet.output.truncate(end);
+                let note = format!(
+                    "Output truncated: exceeded the maximum output size of {} bytes.",
+                    max_output_size
+                );
+                let mut marker = Vec::new();
+                crate::output::write_truncation_marker(&mut marker, sheet.format, &note)?;
+                sheet.output.push_str(&String::from_utf8_lossy(&marker));
+                sheet.report.output_size = sheet.output.len() as u64;
+                truncated_sheet = Some(sheet.name.clone());
+                kept += 1;
+            }
+            break;
+        }
+
+        let omitted: Vec<String> = sheets
+            .drain(kept..)
+            .map(|sheet| format!("'{}'", sheet.name))
+            .collect();
+        let mut message = format!(
+            "output of {} bytes exceeds the maximum of {} bytes",
+            total, max_output_size
+        );
+        if let Some(name) = truncated_sheet {
+            message.push_str(&format!("; sheet '{}' truncated", name));
+        }
+        if !omitted.is_empty() {
+            message.push_str(&format!("; omitted sheets: {}", omitted.join(", ")));
+        }
+        report.warnings.push(ConversionWarning::workbook(
+            WarningKind::OutputTruncated,
+            message,
+        ));
+        Ok(())
+    }
+}
+
+/// シート単位のドライバーから処理に渡す1シートの情報（内部ヘルパー）
+pub(super) struct SheetTask<'a> {
+    /// シート名
+    pub(super) name: &'a str,
+
+    /// 出力全体でのシートのインデックス
+    pub(super) index: usize,
+
+    /// 解析済みのXMLメタデータ
+    pub(super) metadata: &'a XlsxMetadataParser,
+
+    /// パーサーを借りるまでの時間（プールにパーサーがない場合はワークブックを開く時間を含む）
+    pub(super) open_time: Duration,
+}
+
+/// 出力順に受け渡すシート（`Converter::load_sheets()`で準備）
+pub(super) enum SheetQueue {
+    /// 未変換のシート（変換が完了したものから受け渡す）
+    Pending {
+        /// シートを変換するパーサーのプール
+        parsers: WorkbookParserPool,
+        /// 選択されたシート名（出力順）
+        sheet_names: Vec<String>,
+    },
+
+    /// 変換済みのシート（重複シートの検出と出力サイズの上限を適用済み）
+    Rendered(Vec<RenderedSheet>),
+}
+
+impl SheetQueue {
+    /// 文書全体のシート数
+    pub(super) fn len(&self) -> usize {
+        match self {
+            Self::Pending { sheet_names, .. } => sheet_names.len(),
+            Self::Rendered(sheets) => sheets.len(),
+        }
+    }
+
+    /// 変換済みのシートを取得（未変換の場合は空）
+    ///
+    /// 文書の先頭（フロントマター）にシートのメタデータを出力する場合に使用します。
+    pub(super) fn rendered(&self) -> &[RenderedSheet] {
+        match self {
+            Self::Pending { .. } => &[],
+            Self::Rendered(sheets) => sheets,
+        }
+    }
+
+    /// シートを出力順に1つずつ`emit`に渡し、戻り値を出力順に取得
+    ///
+    /// 未変換のシートは並列に変換し、変換が完了したものから渡します。渡したシートは保持しません。
+    /// シートの処理時間（`emit`の時間を除く）を`report.timings.sheets`に、
+    /// `emit`の時間を`report.timings.write`に加算します。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<T>)` - `emit`の戻り値（出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（`emit`のエラーを含む）
+    pub(super) fn for_each<T, F>(
+        self,
+        converter: &Converter,
+        report: &mut ConversionReport,
+        mut emit: F,
+    ) -> Result<Vec<T>, XlsxToMdError>
+    where
+        F: FnMut(usize, RenderedSheet) -> Result<T, XlsxToMdError>,
+    {
+        let phase_start = Instant::now();
+        let mut results = Vec::new();
+        let mut emit_time = Duration::ZERO;
+        let mut timed_emit = |sheet_idx, sheet| {
+            let emit_start = Instant::now();
+            results.push(emit(sheet_idx, sheet)?);
+            emit_time += emit_start.elapsed();
+            Ok(())
+        };
+        let sheets_time = match self {
+            Self::Pending {
+                parsers,
+                sheet_names,
+            } => {
+                converter.render_loaded_sheets(&parsers, &sheet_names, 0, &mut timed_emit)?;
+                phase_start.elapsed()
+            }
+            Self::Rendered(sheets) => {
+                for (sheet_idx, sheet) in sheets.into_iter().enumerate() {
+                    timed_emit(sheet_idx, sheet)?;
+                }
+                Duration::ZERO
+            }
+        };
+        report.timings.sheets += sheets_time.saturating_sub(emit_time);
+        report.timings.write += emit_time;
+        Ok(results)
+    }
+}
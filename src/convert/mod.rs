@@ -0,0 +1,1143 @@
+//! Convert Module
+//!
+//! `Converter`の変換メソッド（`convert()`、`convert_to_sink()`、`convert_to_chunks()`など）を
+//! 提供するモジュール。
+//!
+//! 各変換メソッドは、入力の読み込みとシート選択（`workbook`）の後、共通のシート単位の
+//! ドライバー（`driver`）からシートを出力順に受け取り、それぞれの形式で出力します。
+//! 1シートの変換は`sheet`、グリッドを構築しない行単位の出力は`stream`で行います。
+
+mod chunks;
+mod document;
+mod driver;
+mod sheet;
+mod stream;
+mod workbook;
+
+use crate::builder::Converter;
+use crate::document::GridDocument;
+use crate::error::XlsxToMdError;
+use crate::inspect::WorkbookInfo;
+use crate::model::{SheetModel, WorkbookModel};
+use crate::report::{ConversionReport, ConversionWarning, SheetReport};
+use crate::sink::{OutputSink, SheetOutput};
+use chunks::Chunks;
+use std::io::{Cursor, Read, Seek, Write};
+use std::time::{Duration, Instant};
+
+impl Converter {
+    /// ExcelファイルをMarkdown形式に変換
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `output` - Markdown出力先のライター（Writeトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 変換に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 処理フロー
+    ///
+    /// 1. WorkbookParserの初期化
+    /// 2. シート選択
+    /// 3. 各シートについて処理（ループ）
+    ///    - シートのパース
+    ///    - セルのフォーマット
+    ///    - グリッドの構築
+    ///    - Markdown/HTML出力（戦略に応じて）
+    /// 4. 出力バッファをフラッシュ
+    ///
+    /// # 使用例
+    ///
+    /// ## ファイルからファイルへの変換
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let output = File::create("output.md")?;
+    /// converter.convert(input, output)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// 注意: `File`は`Clone`を実装していないため、メモリバッファを使用する場合は`Cursor`を使用してください。
+    ///
+    /// ## メモリバッファからの変換
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::io::Cursor;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let excel_data: Vec<u8> = vec![]; // Excelファイルのバイト列
+    /// let mut markdown_output = Vec::new();
+    /// converter.convert(Cursor::new(excel_data), &mut markdown_output)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ## 標準出力への変換
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// converter.convert(input, std::io::stdout())?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// 注意: `File`は`Clone`を実装していないため、実際の使用では`File`を直接使用できますが、
+    /// メモリバッファを使用する場合は`Cursor`を使用してください。
+    pub fn convert<R: Read + Seek, W: Write>(
+        &self,
+        input: R,
+        output: W,
+    ) -> Result<(), XlsxToMdError> {
+        self.convert_with_report(input, output)?;
+        Ok(())
+    }
+
+    /// Excelファイルを変換し、処理統計のレポートを返す
+    ///
+    /// 変換処理自体は`convert()`と同じです。
+    /// 加えて、フェーズ別（ZIP走査、メタデータ解析、シートごとの解析・フォーマット・レンダリング）の
+    /// 処理時間と推定ピークメモリ使用量を`ConversionReport`として返します。
+    ///
+    /// シートは並列に変換し、変換が完了したシートから出力順に書き出します。重複シートの検出、
+    /// 出力サイズの上限、Markdown形式のフロントマターへのシートのメタデータの出力を使用する場合は、
+    /// すべてのシートを変換した後に書き出します。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `output` - 出力先のライター（Writeトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ConversionReport)` - 変換に成功した場合、処理統計のレポート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（エラー前に変換したシートは出力済みの場合がある）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let mut output = Vec::new();
+    /// let report = converter.convert_with_report(input, &mut output)?;
+    ///
+    /// for sheet in &report.sheets {
+    ///     eprintln!("{}: {:?}", sheet.name, sheet.timings.total());
+    /// }
+    /// eprintln!("peak memory (estimated): {} bytes", report.peak_memory_estimate);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_with_report<R: Read + Seek, W: Write>(
+        &self,
+        input: R,
+        mut output: W,
+    ) -> Result<ConversionReport, XlsxToMdError> {
+        use std::io::BufWriter;
+
+        let total_start = Instant::now();
+        let mut report = ConversionReport::default();
+
+        // 1-5. 入力の読み込みとシート選択（すべてのシートを必要とする設定では各シートの変換）
+        let (layout, sheets) = self.load_sheets(input, &mut report, true)?;
+
+        // 6. 文書の先頭
+        let phase_start = Instant::now();
+        let mut writer = CountingWriter::new(BufWriter::new(&mut output));
+        self.write_document_header(&mut writer, sheets.rendered())?;
+        layout.write_prologue(&mut writer)?;
+        report.timings.write = phase_start.elapsed();
+
+        // 7. 変換が完了したシートから順に出力
+        let reports = sheets.for_each(self, &mut report, |sheet_idx, sheet| {
+            layout.write_sheet(
+                &mut writer,
+                sheet_idx,
+                &sheet.info.heading(),
+                sheet.format,
+                &sheet.output,
+            )?;
+            Ok(sheet.report)
+        })?;
+
+        // 8. 文書の末尾とフラッシュ
+        let phase_start = Instant::now();
+        layout.write_epilogue(&mut writer)?;
+        writer.flush()?;
+        report.output_size = writer.bytes_written();
+        report.timings.write += phase_start.elapsed();
+
+        // 9. レポートの集計
+        finish_report(&mut report, reports, total_start);
+
+        Ok(report)
+    }
+
+    /// Excelファイルを入力全体をメモリに読み込まずに変換し、シートごとに逐次出力
+    ///
+    /// `convert_with_report()`は入力全体をメモリに読み込み、シートを並列に変換します。
+    /// このメソッドはリーダーから直接ワークブックを開き、シートを1つずつ順に
+    /// 変換して、変換が完了したシートから出力先に書き出します（書き出し後にフラッシュします）。
+    /// 入力データ全体や変換済みのシートの出力を保持しないため、サーバー環境などで数百MBの
+    /// ワークブックを扱う場合のメモリ使用量を抑えられます。出力は`convert()`と同じ文書になります。
+    ///
+    /// CSVとMarkdownの表のシートは、グリッドを構築せずにシートのXMLから1行ずつフォーマットして
+    /// 書き出します（メモリ使用量は1行分のセルに比例します）。Markdownの列幅の計算のため、
+    /// シートのXMLは書き出す前にもう一度読み込みます。次のシートは、`convert()`と同じく
+    /// シート全体のセルを読み込んでから変換します（メモリ使用量はそのシートの大きさに比例します）。
+    ///
+    /// * 結合セルを含むシート、使用範囲が肥大化したシート、旧形式（.xls）のワークブックのシート
+    /// * CSV・Markdown以外の出力フォーマット、列幅の上限・表の分割・見出しスタイルによる区切り・
+    ///   セル参照を使用するMarkdown
+    /// * 数式の出力、値のない数式セルのマーカー、ハイパーリンク・コメントの脚注など、
+    ///   グリッド全体を必要とする設定（空行の除去、並べ替え、行の絞り込み、計算列、行数の上限、
+    ///   独自のステージなど）を使用する場合
+    ///
+    /// すべてのシートの変換結果を必要とする次の設定を使用する場合は、`convert_with_report()`と
+    /// 同じく、入力全体を読み込んですべてのシートを変換してから書き出します。
+    ///
+    /// * 重複シートの検出（`with_duplicate_sheets()`）
+    /// * 出力サイズの上限（`with_max_output_size()`）
+    /// * Markdown形式のフロントマターへのシートのメタデータの出力（`include_metadata(true)`）
+    ///
+    /// それ以外の場合、不正なUTF-8の修復（`with_encoding_fallback()`）は行いません。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `output` - 出力先のライター（Writeトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ConversionReport)` - 変換に成功した場合、処理統計のレポート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（エラー前に変換したシートと、行単位で
+    ///   書き出し中のシートのエラー前の行は出力済み）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("large.xlsx")?;
+    /// let output = File::create("large.md")?;
+    /// converter.convert_sheet_by_sheet(input, output)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_sheet_by_sheet<R: Read + Seek, W: Write>(
+        &self,
+        input: R,
+        mut output: W,
+    ) -> Result<ConversionReport, XlsxToMdError> {
+        use std::io::BufWriter;
+
+        // すべてのシートの変換結果を必要とする設定では、`convert_with_report()`と同じく
+        // すべてのシートを変換してから出力する
+        if self.buffers_sheets(true) {
+            return self.convert_with_report(input, output);
+        }
+
+        let total_start = Instant::now();
+        let mut report = ConversionReport::default();
+
+        // 1-4. ワークブックを開き、メタデータの解析とシート選択
+        let (mut parser, metadata, sheet_names) =
+            self.load_workbook_unbuffered(input, &mut report)?;
+
+        // 5. 文書の先頭（レイアウトはシート名と出力フォーマットのみで決まる）
+        let mut writer = CountingWriter::new(BufWriter::new(&mut output));
+        let layout = self.streaming_layout(&sheet_names, &report.revisions);
+        self.write_document_header(&mut writer, &[])?;
+        layout.write_prologue(&mut writer)?;
+
+        // 6. 各シートを順に変換し、完了したシートから書き出す
+        let mut reports = Vec::with_capacity(sheet_names.len());
+        let mut write_time = Duration::ZERO;
+        let phase_start = Instant::now();
+        for (sheet_idx, sheet_name) in sheet_names.iter().enumerate() {
+            // 行単位で出力できるシートは、グリッドを構築せずに1行ずつ書き出す
+            if let Some(sheet_report) = self.stream_sheet(
+                &mut parser,
+                sheet_name,
+                sheet_idx,
+                &metadata,
+                &layout,
+                &mut writer,
+            )? {
+                writer.flush()?;
+                reports.push(sheet_report);
+                continue;
+            }
+            let sheet = self.convert_sheet(&mut parser, sheet_name, sheet_idx, &metadata, 0)?;
+            let write_start = Instant::now();
+            layout.write_sheet(
+                &mut writer,
+                sheet_idx,
+                &sheet.info.heading(),
+                sheet.format,
+                &sheet.output,
+            )?;
+            writer.flush()?;
+            write_time += write_start.elapsed();
+            reports.push(sheet.report);
+        }
+        report.timings.sheets = phase_start.elapsed().saturating_sub(write_time);
+
+        // 7. 文書の末尾とフラッシュ
+        let write_start = Instant::now();
+        layout.write_epilogue(&mut writer)?;
+        writer.flush()?;
+        report.output_size = writer.bytes_written();
+        report.timings.write = write_time + write_start.elapsed();
+
+        // 8. レポートの集計
+        finish_report(&mut report, reports, total_start);
+
+        Ok(report)
+    }
+
+    /// Excelファイルを変換し、シートごとの結果を出力先（`OutputSink`）に渡す
+    ///
+    /// シートは1つの文書に連結されず、出力順に1シートずつ`OutputSink::write_sheet()`に渡されます。
+    /// 重複シートの検出と出力サイズの上限を使用しない場合、各シートは変換が完了した時点で
+    /// （先行するシートの受け渡しの後に）渡されるため、すべてのシートの出力を同時に保持しません。
+    /// `ConverterBuilder::with_sheet_override()`と組み合わせると、1回の変換でシートごとに
+    /// 異なるフォーマット（例: データはCSV、注記はMarkdown）の出力を生成できます。
+    ///
+    /// シート見出しやシート間の区切りは付加されません。メタデータを含める設定の場合、
+    /// Markdown形式のシートには、そのシートのフロントマターが先頭に付加されます。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `sink` - シートごとの出力先
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ConversionReport)` - 変換に成功した場合、処理統計のレポート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（出力先のエラーを含む）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, DirectorySink, OutputFormat};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new()
+    ///     .with_output_format(OutputFormat::Markdown)
+    ///     .with_sheet_override("Data", OutputFormat::Csv)
+    ///     .build()?;
+    ///
+    /// // out/Data.csv と out/<その他のシート>.md を出力
+    /// let mut sink = DirectorySink::new("out");
+    /// converter.convert_to_sink(File::open("example.xlsx")?, &mut sink)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_to_sink<R: Read + Seek, S: OutputSink + ?Sized>(
+        &self,
+        input: R,
+        sink: &mut S,
+    ) -> Result<ConversionReport, XlsxToMdError> {
+        let total_start = Instant::now();
+        let mut report = ConversionReport::default();
+
+        // 1-5. 入力の読み込みとシート選択（すべてのシートを必要とする設定では各シートの変換）
+        let (_, sheets) = self.load_sheets(input, &mut report, false)?;
+
+        // 6. 変換が完了したシートから順に出力先へ渡す
+        let written = sheets.for_each(self, &mut report, |sheet_idx, sheet| {
+            let size = self.write_to_sink(sink, sheet_idx, &sheet)?;
+            Ok((size, sheet.report))
+        })?;
+        report.output_size = written.iter().map(|(size, _)| size).sum();
+
+        // 7. レポートの集計
+        let reports = written.into_iter().map(|(_, report)| report).collect();
+        finish_report(&mut report, reports, total_start);
+
+        Ok(report)
+    }
+
+    /// Excelファイルを変換し、シートごとの結果を構造化して返す
+    ///
+    /// シートは1つの文書に連結されず、出力順に1シートずつ`SheetOutput`
+    /// （シート名、出力順、出力フォーマット、本文、行数・列数）として返されます。
+    /// 連結された文書をシートの区切りで分割し直す必要がないため、シートごとに
+    /// 別の文書として索引付けする場合などに使用できます。
+    ///
+    /// 本文は`convert_to_sink()`で出力先に渡される内容と同じです（シート見出しやシート間の
+    /// 区切りを含まず、メタデータを含める設定の場合、Markdown形式のシートにはそのシートの
+    /// フロントマターが先頭に付加されます）。重複シートの検出と出力サイズの上限は
+    /// `convert()`と同様に適用されます。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<SheetOutput>)` - シートごとの変換結果（出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// for sheet in converter.convert_sheets(File::open("example.xlsx")?)? {
+    ///     println!("{} ({}x{}): {} bytes", sheet.name, sheet.rows, sheet.cols, sheet.content.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_sheets<R: Read + Seek>(
+        &self,
+        input: R,
+    ) -> Result<Vec<SheetOutput>, XlsxToMdError> {
+        let mut report = ConversionReport::default();
+        let (_, sheets) = self.load_sheets(input, &mut report, false)?;
+
+        sheets.for_each(self, &mut report, |index, sheet| {
+            Ok(SheetOutput {
+                content: self.sheet_content(&sheet)?.into_owned(),
+                name: sheet.name,
+                index,
+                format: sheet.format,
+                rows: sheet.report.rows,
+                cols: sheet.report.cols,
+            })
+        })
+    }
+
+    /// Excelファイルを変換し、出力をシート単位のチャンクとして返す
+    ///
+    /// チャンクを順に連結すると`convert_to_string()`の結果と同じ文書になります。
+    /// 各チャンクは1シート分の出力（見出しや区切りを含む）で、先頭のチャンクには
+    /// フロントマターとフィンガープリントも含まれます。複数のシートを1つのJSONオブジェクトとして
+    /// 出力する場合、オブジェクトの開始は先頭のチャンク、終了は最後のチャンクに含まれます。
+    /// 巨大な出力を1つの`String`に連結せずに、シートごとに書き出し・送信できます。
+    ///
+    /// シートは並列処理できる数ずつ変換し、チャンクは変換が完了したシートから順に返します。
+    /// 重複シートの検出、出力サイズの上限、Markdown形式のフロントマターへのシートのメタデータの
+    /// 出力はすべてのシートを必要とするため、使用する場合はすべてのシートを変換した後に返します。
+    ///
+    /// 入力の読み込みでエラーが発生した場合は、そのエラーのみを返します。
+    /// シートの変換でエラーが発生した場合は、それまでのチャンクの後にエラーを返し、以降は何も返しません。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// シート単位の出力（`Ok(String)`）またはエラー（`Err(XlsxToMdError)`）を返すイテレーター
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use std::io::Write;
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let mut output = File::create("example.md")?;
+    /// for chunk in converter.convert_to_chunks(input) {
+    ///     output.write_all(chunk?.as_bytes())?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_to_chunks<R: Read + Seek>(
+        &self,
+        input: R,
+    ) -> impl Iterator<Item = Result<String, XlsxToMdError>> + '_ {
+        let mut report = ConversionReport::default();
+        // 重複シートの検出などはすべてのシートを必要とするため、使用する場合は変換後にチャンクを生成する
+        let chunks = self
+            .load_sheets(input, &mut report, true)
+            .and_then(|(layout, sheets)| Chunks::new(self, layout, sheets));
+        let (chunks, error) = match chunks {
+            Ok(chunks) => (Some(chunks), None),
+            Err(e) => (None, Some(e)),
+        };
+        error
+            .map(Err)
+            .into_iter()
+            .chain(chunks.into_iter().flatten())
+    }
+
+    /// Excelファイルを解析し、シートごとの編集可能なグリッドを取得
+    ///
+    /// 各シートは`convert()`と同じ設定で解析・フォーマットされ、出力前のグリッド
+    /// （`GridDocument`）として出力順に返されます。セルを編集した後、
+    /// `GridDocument::render()`で任意の出力フォーマットに出力できます。
+    ///
+    /// 重複シートの検出と出力サイズの上限は、文書全体の出力時に行う処理のため適用されません。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<GridDocument>)` - シートごとのグリッド（出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, OutputFormat};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let mut grids = converter.convert_to_grids(File::open("example.xlsx")?)?;
+    /// grids[0].set_cell(0, 0, "Name");
+    /// let html = grids[0].render(OutputFormat::Html)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_to_grids<R: Read + Seek>(
+        &self,
+        input: R,
+    ) -> Result<Vec<GridDocument>, XlsxToMdError> {
+        let mut report = ConversionReport::default();
+        let (parsers, sheet_names) = self.load_workbook(input, &mut report)?;
+
+        // 各シートのグリッドを並列に構築（結果は出力順）
+        let mut grids = Vec::with_capacity(sheet_names.len());
+        self.for_each_sheet(
+            &parsers,
+            &sheet_names,
+            0,
+            |parser, task| {
+                let built = self.build_sheet(parser, task.name, task.index, task.metadata, true)?;
+                built.notify_finished(&self.config, task.name);
+                Ok(GridDocument::new(
+                    built.config.into_owned(),
+                    self.config_hash,
+                    built.sheet,
+                ))
+            },
+            |_, grid| {
+                grids.push(grid);
+                Ok(())
+            },
+        )?;
+        Ok(grids)
+    }
+
+    /// Excelファイルを解析し、シート → 行 → 型付きセルの文書モデルを取得
+    ///
+    /// JSON形式の出力（`OutputFormat::Json`）の元になるモデルを、JSONの出力と解析を経由せずに
+    /// 返します。各シートは`convert()`と同じ設定で解析・フォーマットされ、セルのキーは
+    /// `JsonKeyMode`に従います。出力フォーマットとJSON固有の設定（セルの表現方式、文書の形式など）は
+    /// 無視されます。
+    ///
+    /// 重複シートの検出と出力サイズの上限は、文書全体の出力時に行う処理のため適用されません。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(WorkbookModel)` - ワークブックの文書モデル（シートは出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let model = converter.convert_to_model(File::open("example.xlsx")?)?;
+    /// println!("{} rows", model.sheets[0].rows.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_to_model<R: Read + Seek>(
+        &self,
+        input: R,
+    ) -> Result<WorkbookModel, XlsxToMdError> {
+        let mut report = ConversionReport::default();
+        let (parsers, sheet_names) = self.load_workbook(input, &mut report)?;
+
+        // 各シートのモデルを並列に構築（結果は出力順）
+        let mut model = WorkbookModel::default();
+        self.for_each_sheet(
+            &parsers,
+            &sheet_names,
+            0,
+            |parser, task| {
+                let built =
+                    self.build_sheet(parser, task.name, task.index, task.metadata, false)?;
+                built.notify_finished(&self.config, task.name);
+                Ok(SheetModel::from_grid(
+                    &built.sheet.grid,
+                    &built.sheet.info,
+                    built.config.json_key_mode,
+                    Some(&built.sheet.merged_regions),
+                ))
+            },
+            |_, sheet| {
+                model.sheets.push(sheet);
+                Ok(())
+            },
+        )?;
+        Ok(model)
+    }
+
+    /// Excelファイルを変換せずに、ワークブックの情報を取得
+    ///
+    /// シート名の一覧と、ワークブックに埋め込まれたサムネイル画像（`docProps/thumbnail.jpeg`など）を
+    /// 返します。文書管理の画面などで、スプレッドシートを描画せずにプレビューを表示する場合に使用します。
+    ///
+    /// シートの選択などの変換の設定は無視され、セキュリティ制限（入力ファイル・画像ファイルのサイズ、
+    /// ZIPアーカイブ内のファイル数の上限）のみが適用されます。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(WorkbookInfo)` - ワークブックの情報
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let info = converter.inspect(File::open("example.xlsx")?)?;
+    /// if let Some(thumbnail) = info.thumbnail {
+    ///     std::fs::write("preview.jpeg", &thumbnail.bytes)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn inspect<R: Read + Seek>(&self, input: R) -> Result<WorkbookInfo, XlsxToMdError> {
+        let security_config = &self.config.security;
+        let buffer = self.read_input(input)?;
+
+        // 旧形式（XLS）のワークブックはサムネイルを持たない
+        let thumbnail = if crate::parser::is_legacy_xls(&buffer) {
+            None
+        } else {
+            let mut archive =
+                zip::ZipArchive::new(Cursor::new(buffer.as_slice())).map_err(XlsxToMdError::zip)?;
+            crate::security::check_archive(&mut archive, security_config)?;
+            crate::parser::read_thumbnail(&mut archive, security_config)?
+        };
+
+        let parser = crate::parser::WorkbookParser::open(Cursor::new(buffer.as_slice()))?;
+        Ok(WorkbookInfo {
+            sheet_names: parser.get_sheet_names(),
+            thumbnail,
+        })
+    }
+
+    /// ExcelファイルをMessagePack形式に変換し、処理統計のレポートを返す（`msgpack`フィーチャー）
+    ///
+    /// 各シートを型付きJSON（`OutputFormat::Json`、`JsonCellMode::Typed`）と同じ構造の
+    /// マップとして、シート数にかかわらず`{"sheets": [...]}`の形でエンコードします。
+    /// サービス間で変換結果を受け渡す場合に、受け側でのJSONの解析を省略できます。
+    ///
+    /// 出力フォーマット・JSONのセル表現方式・シート単位の出力フォーマットの設定は無視され、
+    /// それ以外の設定（シートの選択、日付形式、キーの方式など）は`convert()`と同様に適用されます。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `output` - 出力先のライター（Writeトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ConversionReport)` - 変換に成功した場合、処理統計のレポート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let mut output = Vec::new();
+    /// converter.convert_to_msgpack(input, &mut output)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "msgpack")]
+    pub fn convert_to_msgpack<R: Read + Seek, W: Write>(
+        &self,
+        input: R,
+        mut output: W,
+    ) -> Result<ConversionReport, XlsxToMdError> {
+        use std::io::BufWriter;
+
+        let total_start = Instant::now();
+        let mut report = ConversionReport::default();
+
+        use crate::api::{JsonCellMode, JsonDocumentMode, OutputFormat};
+
+        // 型付きJSONとして変換する（フィンガープリントは元の設定の値を使用）
+        let mut config = self.config.clone();
+        config.output_format = OutputFormat::Json;
+        config.json_cell_mode = JsonCellMode::Typed;
+        config.json_document_mode = JsonDocumentMode::Lines;
+        config.sheet_formats.clear();
+        let mut converter = Converter::new(
+            config,
+            self.row_filter.clone(),
+            self.computed_columns.clone(),
+        );
+        converter.config_hash = self.config_hash;
+        let (_, sheets) = converter.load_sheets(input, &mut report, false)?;
+
+        // 各シートのJSONを解析し、MessagePackにエンコードして出力
+        let parsed = sheets.for_each(&converter, &mut report, |_, sheet| {
+            let value: serde_json::Value =
+                serde_json::from_str(&sheet.output).map_err(XlsxToMdError::json)?;
+            Ok((value, sheet.report))
+        })?;
+        let phase_start = Instant::now();
+        let (values, reports): (Vec<_>, Vec<_>) = parsed.into_iter().unzip();
+        let document = serde_json::json!({ "sheets": values });
+        let mut writer = CountingWriter::new(BufWriter::new(&mut output));
+        crate::output::msgpack::write_value(&mut writer, &document)?;
+        writer.flush()?;
+        report.output_size = writer.bytes_written();
+        report.timings.write += phase_start.elapsed();
+
+        finish_report(&mut report, reports, total_start);
+        Ok(report)
+    }
+
+    /// URLのワークブックをダウンロードし、文字列に変換する（`fetch`フィーチャー）
+    ///
+    /// HTTP(S)でダウンロードした後、`convert_to_string()`と同じ設定で変換します。
+    /// TLSにはrustlsを使用します。入力ファイルのサイズの上限（`ConverterBuilder::with_max_input_size()`）は
+    /// ダウンロード中に適用し、`Content-Length`が上限を超える場合は本文を受信しません。
+    ///
+    /// この関数は、ダウンロードと変換が完了するまで呼び出し元のスレッドをブロックします。
+    /// 非同期ランタイムからは`convert_url_async()`を使用してください。
+    ///
+    /// # 引数
+    ///
+    /// * `url` - ワークブックのURL（`http`または`https`）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - 変換された文字列
+    /// * `Err(XlsxToMdError)` - ダウンロードに失敗した場合は`XlsxToMdError::Fetch`、
+    ///   サイズの上限を超えた場合は`XlsxToMdError::SecurityViolation`
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new()
+    ///     .with_max_input_size(50 * 1024 * 1024)
+    ///     .build()?;
+    /// let markdown = converter.convert_url("https://example.com/report.xlsx")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "fetch")]
+    pub fn convert_url(&self, url: &str) -> Result<String, XlsxToMdError> {
+        let buffer = crate::fetch::download(url, self.config.security.max_input_file_size)?;
+        self.convert_to_string(Cursor::new(buffer))
+    }
+
+    /// URLのワークブックをダウンロードし、文字列に変換する（非同期版、`fetch`フィーチャー）
+    ///
+    /// 処理内容は`convert_url()`と同じです。ダウンロードと変換は専用のスレッドで行うため、
+    /// 特定の非同期ランタイムに依存せず、非同期ランタイムの実行スレッドをブロックしません。
+    ///
+    /// # 引数
+    ///
+    /// * `url` - ワークブックのURL（`http`または`https`）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - 変換された文字列
+    /// * `Err(XlsxToMdError)` - ダウンロードに失敗した場合は`XlsxToMdError::Fetch`、
+    ///   サイズの上限を超えた場合は`XlsxToMdError::SecurityViolation`
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{Converter, XlsxToMdError};
+    ///
+    /// async fn handle(converter: &Converter, url: &str) -> Result<String, XlsxToMdError> {
+    ///     converter.convert_url_async(url).await
+    /// }
+    /// ```
+    #[cfg(feature = "fetch")]
+    pub async fn convert_url_async(&self, url: &str) -> Result<String, XlsxToMdError> {
+        let converter = self.clone();
+        crate::fetch::Download::spawn(
+            url,
+            self.config.security.max_input_file_size,
+            move |buffer| converter.convert_to_string(Cursor::new(buffer)),
+        )
+        .await
+    }
+
+    /// 非同期の入力を変換し、非同期の出力に書き込む（`tokio`フィーチャー）
+    ///
+    /// 処理内容は`convert()`と同じです。入力を入力ファイルのサイズの上限まで読み込んだ後、
+    /// 解析と変換はtokioのブロッキングスレッドプール（`spawn_blocking`）で実行し、
+    /// 変換した出力を順に`output`に書き込みます。非同期ランタイムの実行スレッドはブロックしません。
+    ///
+    /// 入力サイズは末尾へのシークで判定し、上限を超える場合は読み込みません。
+    /// 出力の書き込みに失敗した場合は変換を中止し、書き込みのエラーを返します。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むための非同期リーダー（`AsyncRead + AsyncSeek`）
+    /// * `output` - 出力先の非同期ライター（`AsyncWrite`、完了時にフラッシュ）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 変換に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（ブロッキングタスクの異常終了は`XlsxToMdError::Internal`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{Converter, XlsxToMdError};
+    ///
+    /// async fn handle(converter: &Converter, body: Vec<u8>) -> Result<Vec<u8>, XlsxToMdError> {
+    ///     let mut markdown = Vec::new();
+    ///     converter
+    ///         .convert_async(std::io::Cursor::new(body), &mut markdown)
+    ///         .await?;
+    ///     Ok(markdown)
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn convert_async<R, W>(
+        &self,
+        mut input: R,
+        mut output: W,
+    ) -> Result<(), XlsxToMdError>
+    where
+        R: tokio::io::AsyncRead + tokio::io::AsyncSeek + Unpin,
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use crate::error::SecurityLimit;
+        use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+        // 1. 入力サイズの上限（読み込まずに末尾へのシークで判定）
+        let max_input_file_size = self.config.security.max_input_file_size;
+        let input_size = input.seek(std::io::SeekFrom::End(0)).await?;
+        if input_size > max_input_file_size {
+            return Err(SecurityLimit::InputSize {
+                size: input_size,
+                max: max_input_file_size,
+            }
+            .into());
+        }
+        input.rewind().await?;
+        let mut buffer = Vec::with_capacity(input_size as usize);
+        input
+            .take(max_input_file_size.saturating_add(1))
+            .read_to_end(&mut buffer)
+            .await?;
+
+        // 2. ブロッキングスレッドで変換し、出力をチャネル経由で受け取る
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(ASYNC_OUTPUT_CHANNEL_CAPACITY);
+        let converter = self.clone();
+        let task = tokio::task::spawn_blocking(move || {
+            converter.convert(Cursor::new(buffer), ChannelWriter(sender))
+        });
+
+        // 3. 受け取った出力を順に書き込む（失敗した場合はチャネルを閉じて変換を中止）
+        let mut write_result = Ok(());
+        while let Some(chunk) = receiver.recv().await {
+            if let Err(e) = output.write_all(&chunk).await {
+                write_result = Err(e);
+                break;
+            }
+        }
+        drop(receiver);
+
+        let converted = task
+            .await
+            .map_err(|e| XlsxToMdError::Internal(format!("conversion task failed: {}", e)))?;
+        write_result?;
+        converted?;
+        output.flush().await?;
+        Ok(())
+    }
+
+    /// ExcelファイルをMarkdown形式の文字列に変換
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - 変換されたMarkdown文字列
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use std::fs::File;
+    /// use xlsxzero::ConverterBuilder;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let markdown = converter.convert_to_string(input)?;
+    /// println!("{}", markdown);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn convert_to_string<R: Read + Seek>(&self, input: R) -> Result<String, XlsxToMdError> {
+        // チャンクは生成された順に追加し、すべてのチャンクを同時に保持しない
+        let mut result = String::new();
+        for chunk in self.convert_to_chunks(input) {
+            result.push_str(&chunk?);
+        }
+
+        Ok(result)
+    }
+
+    /// Excelファイルを変換する（パニックしない版）
+    ///
+    /// 変換処理自体は`convert()`と同じです。
+    /// 加えて、不正な入力などにより変換処理中にパニックが発生した場合も
+    /// 呼び出し元へ伝播させず、`XlsxToMdError::Internal`として返します。
+    /// サービスなど、1つの入力の失敗でプロセスやスレッドを停止させたくない用途に使用します。
+    ///
+    /// パニックの捕捉には`std::panic::catch_unwind`を使用するため、`panic = "abort"`で
+    /// ビルドした場合は捕捉できません。また、パニックフックによるメッセージの出力は抑制しません。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `output` - 出力先のライター（Writeトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 変換に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（パニックは`XlsxToMdError::Internal`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, ErrorKind};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    /// let mut output = Vec::new();
+    /// if let Err(e) = converter.try_convert(input, &mut output) {
+    ///     eprintln!("conversion failed ({:?}): {}", e.kind(), e);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_convert<R: Read + Seek, W: Write>(
+        &self,
+        input: R,
+        output: W,
+    ) -> Result<(), XlsxToMdError> {
+        catch_panic(|| self.convert(input, output))
+    }
+
+    /// ExcelファイルをMarkdown形式の文字列に変換する（パニックしない版）
+    ///
+    /// 変換処理自体は`convert_to_string()`と同じです。
+    /// 変換処理中のパニックは、`try_convert()`と同様に`XlsxToMdError::Internal`として返します。
+    ///
+    /// # 引数
+    ///
+    /// * `input` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - 変換されたMarkdown文字列
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（パニックは`XlsxToMdError::Internal`）
+    pub fn try_convert_to_string<R: Read + Seek>(&self, input: R) -> Result<String, XlsxToMdError> {
+        catch_panic(|| self.convert_to_string(input))
+    }
+}
+
+/// シート単位のレポートを集計し、推定ピークメモリ使用量と全体の処理時間を記録（内部ヘルパー）
+fn finish_report(report: &mut ConversionReport, sheets: Vec<SheetReport>, total_start: Instant) {
+    report.sheets = sheets;
+    // ワークブック全体の警告の後にシートの警告を追加
+    let sheet_warnings: Vec<ConversionWarning> = report
+        .sheets
+        .iter()
+        .flat_map(|sheet| sheet.warnings.iter().cloned())
+        .collect();
+    report.warnings.extend(sheet_warnings);
+    report.peak_memory_estimate = estimate_peak_memory(
+        report.input_size,
+        &report.sheets,
+        crate::ordered::concurrency(),
+    );
+    report.timings.total = total_start.elapsed();
+}
+
+/// 推定ピークメモリ使用量を算出（内部ヘルパー）
+///
+/// 入力バッファ、同時に処理されるシート（最大でスレッド数）の作業領域の合計、
+/// および書き込み待ちの全シートの出力から概算します。
+fn estimate_peak_memory(input_size: u64, sheets: &[SheetReport], concurrency: usize) -> u64 {
+    let mut sheet_estimates: Vec<u64> = sheets.iter().map(|s| s.memory_estimate).collect();
+    sheet_estimates.sort_unstable_by(|a, b| b.cmp(a));
+
+    let concurrent_peak: u64 = sheet_estimates.iter().take(concurrency.max(1)).sum();
+    let pending_output: u64 = sheets.iter().map(|s| s.output_size).sum();
+
+    input_size + concurrent_peak + pending_output
+}
+
+/// 処理中のパニックを捕捉し、`XlsxToMdError::Internal`に変換（内部ヘルパー）
+///
+/// 並列処理（rayon）のワーカースレッドで発生したパニックも呼び出し元のスレッドへ
+/// 伝播されるため、ここで捕捉されます。
+pub(crate) fn catch_panic<T>(
+    f: impl FnOnce() -> Result<T, XlsxToMdError>,
+) -> Result<T, XlsxToMdError> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(XlsxToMdError::Internal(format!(
+            "conversion panicked: {}",
+            message
+        )))
+    })
+}
+
+/// 非同期の変換で、出力をまとめて送るチャネルの容量（チャンク数）
+#[cfg(feature = "tokio")]
+const ASYNC_OUTPUT_CHANNEL_CAPACITY: usize = 16;
+
+/// 書き込んだデータを非同期の出力側にチャネルで送るライター（内部ヘルパー）
+///
+/// ブロッキングスレッドから使用し、受信側が閉じている場合は`BrokenPipe`を返します。
+#[cfg(feature = "tokio")]
+struct ChannelWriter(tokio::sync::mpsc::Sender<Vec<u8>>);
+
+#[cfg(feature = "tokio")]
+impl Write for ChannelWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        self.0.blocking_send(buf.to_vec()).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::BrokenPipe, "async output closed")
+        })?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 書き込んだバイト数を計測するライター（内部ヘルパー）
+struct CountingWriter<W: Write> {
+    inner: W,
+    bytes_written: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            bytes_written: 0,
+        }
+    }
+
+    fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes_written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_peak_memory() {
+        let sheet = |memory_estimate, output_size| SheetReport {
+            memory_estimate,
+            output_size,
+            ..Default::default()
+        };
+        let sheets = vec![sheet(100, 10), sheet(300, 20), sheet(200, 30)];
+
+        // 同時処理数1: 最大のシートのみ
+        assert_eq!(estimate_peak_memory(1000, &sheets, 1), 1000 + 300 + 60);
+        // 同時処理数2: 上位2シート
+        assert_eq!(estimate_peak_memory(1000, &sheets, 2), 1000 + 500 + 60);
+        // シート数を超える同時処理数
+        assert_eq!(estimate_peak_memory(1000, &sheets, 8), 1000 + 600 + 60);
+        assert_eq!(estimate_peak_memory(1000, &[], 4), 1000);
+    }
+}
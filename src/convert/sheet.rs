@@ -0,0 +1,1141 @@
+//! Sheet Conversion Module
+//!
+//! 1シートを Parse → Format → GridBuild → Render の各ステージで変換するモジュール。
+//!
+//! `Converter`の変換メソッドは、共通のシート単位のドライバー（`driver`モジュール）から
+//! このモジュールの`convert_sheet()`（出力まで）または`build_sheet()`（グリッドまで）を呼び出します。
+
+use crate::api::{
+    BlankRows, ColumnSelector, ConditionalFormats, DataValidations, DuplicateSheets, FormulaMode,
+    HeaderUnits, JsonCellMode, MergeStrategy, OutputFormat, ProtectedSheets, RowDedupe,
+    ValidationPolicy, WrapStrategy,
+};
+use crate::builder::{ConversionConfig, Converter};
+use crate::document::{render_sheet, SheetGrid};
+use crate::error::XlsxToMdError;
+use crate::grid::LogicalGrid;
+use crate::images::{image_file_name, image_reference, ExtractedImage};
+use crate::output::LinkInventory;
+use crate::parser::{
+    CellComment, ConditionalFormat, DataValidation, DrawingImage, SheetVisuals, WorkbookParser,
+    XlsxMetadataParser,
+};
+use crate::progress::ProgressEvent;
+use crate::report::{ConversionWarning, FoldedColumn, SheetReport, SheetTimings, WarningKind};
+use crate::script::detect_script;
+use crate::types::{CellCoord, CellValue, RawCellData, SheetInfo, SheetMetadata, SheetVisibility};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek};
+use std::time::{Duration, Instant};
+
+/// シートの処理時間の上限を判定するセル数の間隔
+const LIMIT_CHECK_INTERVAL: usize = 1024;
+
+/// 見出しに付加するリストの入力規則の値の上限（超えた分は省略記号で示す）
+const MAX_ANNOTATED_VALUES: usize = 20;
+
+impl Converter {
+    /// 1シートを変換（内部ヘルパー）
+    ///
+    /// Parse → Format → GridBuild → Render の各ステージを順に実行し、
+    /// 各ステージの完了後に登録された`PipelineStage`を呼び出します。
+    ///
+    /// # 引数
+    ///
+    /// * `parser` - ワークブックのパーサー（シート間で共有可能）
+    /// * `sheet_name` - シート名
+    /// * `sheet_index` - シートの出力順（0始まり、脚注のラベルに使用）
+    /// * `metadata` - 解析済みのXMLメタデータ
+    /// * `workbook_bytes` - シート処理のためにメモリに保持しているワークブックのデータ量
+    ///   （推定メモリ使用量に加算）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(RenderedSheet)` - 変換済みのシート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub(super) fn convert_sheet<RS: Read + Seek>(
+        &self,
+        parser: &mut WorkbookParser<RS>,
+        sheet_name: &str,
+        sheet_index: usize,
+        metadata: &XlsxMetadataParser,
+        workbook_bytes: usize,
+    ) -> Result<RenderedSheet, XlsxToMdError> {
+        // 1-3. Parse → Format → GridBuild
+        let BuiltSheet {
+            config,
+            mut sheet,
+            warnings,
+            mut timings,
+            filtered_rows,
+            removed_rows,
+            zero_size,
+            cell_count,
+            raw_cells_bytes,
+            content_bytes,
+            signature,
+            truncation,
+            omitted_rows,
+            started,
+        } = self.build_sheet(parser, sheet_name, sheet_index, metadata, false)?;
+
+        // 4. Render: 出力フォーマットに応じて出力
+        let step_start = Instant::now();
+        let mut output_string = render_sheet(&config, self.config_hash, &mut sheet, content_bytes)?;
+        let truncated_cells = truncated_cell_count(&config, &sheet);
+        // 出力する表の上限で行を省略した場合の注記
+        if omitted_rows > 0 {
+            let note = config.truncation_notice.replace(
+                "{count}",
+                &crate::format::FormatParser::add_thousand_separators(&omitted_rows.to_string()),
+            );
+            let mut marker = Vec::new();
+            crate::output::write_truncation_marker(&mut marker, config.output_format, &note)?;
+            output_string.push_str(&String::from_utf8_lossy(&marker));
+        }
+        // シート単位の上限で打ち切った場合の注記
+        if let Some(description) = truncation {
+            let note = format!("Sheet {}.", description);
+            let mut marker = Vec::new();
+            crate::output::write_truncation_marker(&mut marker, config.output_format, &note)?;
+            output_string.push_str(&String::from_utf8_lossy(&marker));
+        }
+        self.append_placed_objects(sheet_name, &config, metadata, &mut output_string)?;
+        timings.render = step_start.elapsed();
+
+        // シート処理の推定メモリ使用量
+        // （ワークブックのデータ + セルデータ + グリッド + 出力）
+        let grid = &sheet.grid;
+        let grid_bytes =
+            grid.get_rows() * grid.get_cols() * std::mem::size_of::<crate::grid::Cell>();
+        let memory_estimate = (workbook_bytes
+            + raw_cells_bytes
+            + 2 * content_bytes
+            + grid_bytes
+            + output_string.len()) as u64;
+
+        let sheet_report = SheetReport {
+            name: sheet_name.to_string(),
+            rows: grid.get_rows(),
+            cols: grid.get_cols(),
+            cells: cell_count,
+            output_size: output_string.len() as u64,
+            memory_estimate,
+            warnings,
+            folded_columns: sheet.folded_columns,
+            filtered_rows,
+            removed_blank_rows: removed_rows,
+            zero_size_rows: zero_size.0,
+            zero_size_cols: zero_size.1,
+            truncated_cells,
+            omitted_rows,
+            timings,
+        };
+        self.config.emit_progress(ProgressEvent::SheetFinished {
+            sheet: sheet_name,
+            index: sheet_index,
+            rows: sheet_report.rows,
+            elapsed: started.elapsed(),
+        });
+
+        Ok(RenderedSheet {
+            name: sheet_name.to_string(),
+            format: config.output_format,
+            output: output_string,
+            info: sheet.info,
+            report: sheet_report,
+            signature,
+        })
+    }
+
+    /// シートに配置されたグラフとピボットテーブルの元データを出力の末尾に付加（内部ヘルパー）
+    ///
+    /// 範囲制限の外側に配置したものは出力しません。
+    fn append_placed_objects(
+        &self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        metadata: &XlsxMetadataParser,
+        output: &mut String,
+    ) -> Result<(), XlsxToMdError> {
+        let in_range = |row: u32, col: u32| {
+            config
+                .range
+                .is_none_or(|range| range.contains(CellCoord::new(row, col)))
+        };
+
+        let charts: Vec<_> = metadata
+            .charts(sheet_name)
+            .iter()
+            .filter(|chart| in_range(chart.row, chart.col))
+            .cloned()
+            .collect();
+        if !charts.is_empty() {
+            let mut section = Vec::new();
+            crate::output::write_charts(
+                &mut section,
+                config.output_format,
+                config.chart_mode,
+                &charts,
+                |text| self.formatter.format_text(text),
+            )?;
+            output.push_str(&String::from_utf8_lossy(&section));
+        }
+
+        let pivots: Vec<_> = metadata
+            .pivot_tables(sheet_name)
+            .iter()
+            .filter(|pivot| in_range(pivot.row, pivot.col))
+            .cloned()
+            .collect();
+        if !pivots.is_empty() {
+            let mut section = Vec::new();
+            crate::output::write_pivot_tables(
+                &mut section,
+                config.output_format,
+                config.pivot_mode,
+                &pivots,
+                |text| self.formatter.format_text(text),
+            )?;
+            output.push_str(&String::from_utf8_lossy(&section));
+        }
+        Ok(())
+    }
+
+    /// シート単位の設定を取得（内部ヘルパー）
+    ///
+    /// シート単位の出力フォーマットを反映し、名前付き範囲を指定した場合は
+    /// 参照先の範囲を範囲制限として適用します。
+    pub(super) fn sheet_config(
+        &self,
+        sheet_name: &str,
+        metadata: &XlsxMetadataParser,
+    ) -> Result<Cow<'_, ConversionConfig>, XlsxToMdError> {
+        let mut config = self.config.for_sheet(sheet_name);
+        if let Some(name) = &config.named_range {
+            let (_, range) = Self::resolve_named_range(name, metadata)?;
+            config.to_mut().range = Some(range);
+        }
+        Ok(config)
+    }
+
+    /// 1シートを解析し、出力前のグリッドを構築（内部ヘルパー）
+    ///
+    /// Parse → Format → GridBuild の各ステージを順に実行し、
+    /// 各ステージの完了後に登録された`PipelineStage`を呼び出します。
+    ///
+    /// # 引数
+    ///
+    /// * `parser` - ワークブックのパーサー（シート間で共有可能）
+    /// * `sheet_name` - シート名
+    /// * `sheet_index` - シートの出力順（0始まり、脚注のラベルに使用）
+    /// * `metadata` - 解析済みのXMLメタデータ
+    /// * `keep_raw_cells` - 元のセルデータをグリッドとともに保持するか
+    ///   （`GridDocument`で別の出力フォーマットに再フォーマットする場合）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(BuiltSheet)` - 構築済みのシート
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub(super) fn build_sheet<RS: Read + Seek>(
+        &self,
+        parser: &mut WorkbookParser<RS>,
+        sheet_name: &str,
+        sheet_index: usize,
+        metadata: &XlsxMetadataParser,
+        keep_raw_cells: bool,
+    ) -> Result<BuiltSheet<'_>, XlsxToMdError> {
+        let sheet_start = Instant::now();
+        let mut timings = SheetTimings::default();
+        let mut warnings = Vec::new();
+        self.config.emit_progress(ProgressEvent::SheetStarted {
+            sheet: sheet_name,
+            index: sheet_index,
+        });
+
+        // 保護されたシートの識別（除外する場合はシート選択で除外済み）
+        let protected = self.config.protected_sheets == ProtectedSheets::Flag
+            && metadata.is_sheet_protected(sheet_name);
+        if protected {
+            warnings.push(ConversionWarning::sheet(
+                sheet_name,
+                WarningKind::ProtectedSheet,
+                "sheet is protected".to_string(),
+            ));
+        }
+
+        // 入力規則（解析しない設定の場合は空）
+        let data_validations = Self::resolve_data_validations(parser, sheet_name, metadata)?;
+        let config = self.sheet_config(sheet_name, metadata)?;
+
+        let step_start = Instant::now();
+
+        // 非表示のシートは、非表示シートを含める設定の場合に見出しで識別する
+        let visibility = if config.include_hidden {
+            parser.sheet_visibility(sheet_name)
+        } else {
+            SheetVisibility::Visible
+        };
+
+        // 1. Parse: シートのパース
+        let (mut sheet_metadata, mut raw_cells) = parser.parse_sheet(sheet_name, &config)?;
+        config.stages.run_parse(sheet_name, &mut raw_cells)?;
+        timings.parse = step_start.elapsed();
+
+        // 2. Format: セルのフォーマット
+        let step_start = Instant::now();
+        let mut cells = self.format_cells(
+            sheet_name,
+            &config,
+            raw_cells,
+            sheet_metadata.is_1904,
+            sheet_start,
+        )?;
+        // 図形として配置された画像を抽出し、配置位置のセルに画像の参照を出力
+        let drawing_images = metadata.drawing_images(sheet_name);
+        if config.image_sink.is_some() && !drawing_images.is_empty() {
+            let sheet_number = parser
+                .get_sheet_names()
+                .iter()
+                .position(|name| name == sheet_name)
+                .unwrap_or(sheet_index)
+                + 1;
+            self.place_images(
+                sheet_name,
+                sheet_number,
+                &config,
+                &sheet_metadata,
+                drawing_images,
+                &mut cells,
+            )?;
+        }
+        timings.format = step_start.elapsed();
+
+        let cell_count = cells.raw.iter().filter(|c| !c.value.is_empty()).count();
+        let raw_cells_bytes = cells.raw.len() * std::mem::size_of::<RawCellData>();
+        let content_bytes: usize = cells.formatted.iter().map(|(_, c)| c.len()).sum();
+
+        // 3. GridBuild: グリッドの構築
+        let step_start = Instant::now();
+        let links = config
+            .include_link_inventory
+            .then(|| LinkInventory::collect(&cells.raw));
+        // 条件付き書式のルール（解析しない設定の場合は空）
+        let conditional_formats = metadata.conditional_formats(sheet_name).to_vec();
+        let notes = self.collect_notes(
+            &config,
+            &cells.raw,
+            &sheet_metadata,
+            metadata.comments(sheet_name),
+            &conditional_formats,
+        );
+        // 元のシート上の座標をキーとする（グリッドのセルの`source`と同じ座標）
+        let source_cells: HashMap<CellCoord, RawCellData> = if keep_raw_cells {
+            cells
+                .raw
+                .iter()
+                .map(|cell| {
+                    let coord = CellCoord::new(
+                        sheet_metadata.first_row + cell.coord.row,
+                        sheet_metadata.first_col + cell.coord.col,
+                    );
+                    (coord, cell.clone())
+                })
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let is_1904 = sheet_metadata.is_1904;
+        let (mut grid, truncation) = self.build_grid(
+            sheet_name,
+            &config,
+            cells,
+            &mut sheet_metadata,
+            notes,
+            &mut warnings,
+        )?;
+        let removed_blank_rows = self.clean_grid(
+            sheet_name,
+            &config,
+            &mut grid,
+            &mut sheet_metadata,
+            &mut warnings,
+        );
+        let filtered_rows = self.transform_rows(sheet_name, &config, &mut grid, &mut warnings)?;
+        let (folded_columns, column_units) =
+            self.finish_grid(&config, &mut grid, &data_validations);
+        config.stages.run_grid_build(sheet_name, &mut grid)?;
+
+        // 出力する表の上限を超えた行の省略
+        let kept = [
+            config.max_rows,
+            config.max_cells.map(|cells| grid.rows_within_cells(cells)),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        let omitted_rows = match kept {
+            Some(kept) => {
+                let omitted = grid.get_rows().saturating_sub(kept);
+                Self::truncate_rows(&mut grid, &mut sheet_metadata, kept);
+                omitted
+            }
+            None => 0,
+        };
+        timings.grid_build = step_start.elapsed();
+
+        // 重複シートの検出に使用する行のシグネチャ（検出する場合のみ計算）
+        let signature = if self.config.duplicate_sheets == DuplicateSheets::Keep {
+            Vec::new()
+        } else {
+            grid.row_signature()
+        };
+
+        // 除去した空行の行番号は、出力に注記する場合のみ保持
+        let removed_rows = removed_blank_rows.len();
+        let removed_blank_rows = if config.blank_rows == BlankRows::RemoveWithMarker {
+            removed_blank_rows
+        } else {
+            Vec::new()
+        };
+
+        // シート単位の情報（メタデータ出力時のみ文字体系の判定とハッシュ計算を行う）
+        let mut info = SheetInfo {
+            name: sheet_name.to_string(),
+            script: None,
+            content_hash: None,
+            tables: Vec::new(),
+            links,
+            column_units,
+            protected,
+            conditional_formats,
+            data_validations,
+            visuals: SheetVisuals::default(),
+            visibility,
+            removed_blank_rows,
+        };
+        if config.include_metadata {
+            info.script = detect_script(grid.texts());
+            info.content_hash = Some(grid.content_hash());
+            info.tables = grid.table_hashes();
+            // スパークラインとデータバー
+            info.visuals = metadata.visuals(sheet_name).cloned().unwrap_or_default();
+        }
+
+        let zero_size = (
+            sheet_metadata.zero_size_rows.len(),
+            sheet_metadata.zero_size_cols.len(),
+        );
+        Ok(BuiltSheet {
+            config,
+            sheet: SheetGrid {
+                index: sheet_index,
+                grid,
+                merged_regions: sheet_metadata.merged_regions,
+                info,
+                folded_columns,
+                raw_cells: source_cells,
+                is_1904,
+            },
+            warnings,
+            timings,
+            filtered_rows,
+            removed_rows,
+            zero_size,
+            cell_count,
+            raw_cells_bytes,
+            content_bytes,
+            signature,
+            truncation,
+            omitted_rows,
+            started: sheet_start,
+        })
+    }
+
+    /// Format: セルをフォーマットし、テキスト変換とFormatステージを実行（内部ヘルパー）
+    ///
+    /// シート単位の処理時間の上限を超えた場合は、以降のセルをフォーマットせずに
+    /// フォーマットできなかったセルの最初の行を記録します。
+    fn format_cells(
+        &self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        mut raw_cells: Vec<RawCellData>,
+        is_1904: bool,
+        sheet_start: Instant,
+    ) -> Result<SheetCells, XlsxToMdError> {
+        let mut formatted_cells = Vec::new();
+        // 生の数値として出力したセルの数値（構造化出力で数値として出力する）
+        let mut raw_numbers = HashMap::new();
+        // 処理時間の上限を超えた場合に、フォーマットできなかったセルの最初の行
+        let mut timed_out_row = None;
+        for (cell_idx, raw_cell) in raw_cells.iter().enumerate() {
+            // 処理時間の上限は一定のセル数ごとに判定する
+            if let Some((_, _, max_duration)) = config.per_sheet_limits {
+                if cell_idx % LIMIT_CHECK_INTERVAL == 0 && sheet_start.elapsed() > max_duration {
+                    timed_out_row = raw_cells[cell_idx..].iter().map(|c| c.coord.row).min();
+                    break;
+                }
+            }
+            let content = self
+                .formatter
+                .format_cell(raw_cell, config, is_1904)
+                .map_err(|e| e.at_cell(sheet_name, || raw_cell.coord.to_a1_notation()))?;
+            if let Some(n) = self.formatter.raw_number(raw_cell, config) {
+                raw_numbers.insert(raw_cell.coord, n);
+            }
+            formatted_cells.push((raw_cell.coord, content));
+        }
+        // テキスト変換（翻訳など）はセル単位のプレーンテキストに適用し、結果を再エスケープする
+        // 数式モードで数式を出力するセルは対象外
+        let formula_mode = config.formula_mode;
+        config.stages.run_text_transformer(
+            &raw_cells,
+            &mut formatted_cells,
+            |raw_cell| formula_mode == FormulaMode::CachedValue || raw_cell.formula.is_none(),
+            |raw_cell, text| {
+                self.formatter
+                    .format_transformed_text(raw_cell, text, config)
+            },
+        )?;
+        config.stages.run_format(
+            sheet_name,
+            &mut raw_cells,
+            &mut formatted_cells,
+            &mut raw_numbers,
+        )?;
+        Ok(SheetCells {
+            raw: raw_cells,
+            formatted: formatted_cells,
+            raw_numbers,
+            timed_out_row,
+        })
+    }
+
+    /// 図形として配置された画像を抽出し、配置位置のセルに画像の参照を出力（内部ヘルパー）
+    ///
+    /// 配置位置にセルがない場合はセルを追加し、行優先の順序を保ちます。
+    fn place_images(
+        &self,
+        sheet_name: &str,
+        sheet_number: usize,
+        config: &ConversionConfig,
+        metadata: &SheetMetadata,
+        images: &[DrawingImage],
+        cells: &mut SheetCells,
+    ) -> Result<(), XlsxToMdError> {
+        let Some(sink) = &config.image_sink else {
+            return Ok(());
+        };
+        let mut added_cells = false;
+        let images = images.iter().filter(|image| {
+            config
+                .range
+                .is_none_or(|range| range.contains(CellCoord::new(image.row, image.col)))
+        });
+        for (image_idx, image) in images.enumerate() {
+            let file_name = image_file_name(sheet_number, image_idx + 1, &image.part);
+            let path = sink.image_path(&file_name);
+            sink.write(&ExtractedImage {
+                sheet: sheet_name,
+                cell: &CellCoord::new(image.row, image.col).to_a1_notation(),
+                file_name: &file_name,
+                path: &path,
+                source: &image.part,
+                description: image.description.as_deref(),
+                data: &image.data,
+            })?;
+            let description = self
+                .formatter
+                .format_text(image.description.as_deref().unwrap_or_default());
+            let reference = image_reference(config.output_format, &path, &description);
+
+            // 使用範囲の上側・左側に配置した画像は、最も近い表の端のセルに出力する
+            let coord = CellCoord::new(
+                image.row.saturating_sub(metadata.first_row),
+                image.col.saturating_sub(metadata.first_col),
+            );
+            cells.raw_numbers.remove(&coord);
+            match cells.formatted.iter_mut().find(|(c, _)| *c == coord) {
+                Some((_, content)) if !content.is_empty() => {
+                    content.push(' ');
+                    content.push_str(&reference);
+                }
+                Some((_, content)) => *content = reference,
+                None => {
+                    cells.raw.push(RawCellData {
+                        coord,
+                        value: CellValue::String(path),
+                        format_id: None,
+                        format_string: None,
+                        formula: None,
+                        hyperlink: None,
+                        rich_text: None,
+                        style_name: None,
+                        font: None,
+                        fill_color: None,
+                    });
+                    cells.formatted.push((coord, reference));
+                    added_cells = true;
+                }
+            }
+        }
+        // 追加したセルを含め、行優先の順序を保つ
+        if added_cells {
+            cells
+                .raw
+                .sort_by_key(|cell| (cell.coord.row, cell.coord.col));
+            cells
+                .formatted
+                .sort_by_key(|(coord, _)| (coord.row, coord.col));
+        }
+        Ok(())
+    }
+
+    /// グリッドのセルに付加する脚注・コメント・条件付き書式の注記を収集（内部ヘルパー）
+    ///
+    /// コメントはシートの絶対座標で保持されているため、使用範囲の先頭からの座標に変換します。
+    fn collect_notes(
+        &self,
+        config: &ConversionConfig,
+        raw_cells: &[RawCellData],
+        metadata: &SheetMetadata,
+        comments: Option<&HashMap<(u32, u32), Vec<CellComment>>>,
+        conditional_formats: &[ConditionalFormat],
+    ) -> CellNotes {
+        let link_footnotes = if config.hyperlink_footnotes() {
+            raw_cells
+                .iter()
+                .filter_map(|cell| {
+                    cell.hyperlink
+                        .as_ref()
+                        .map(|link| (cell.coord, link.url.clone()))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let comments = match comments {
+            Some(comments) if !comments.is_empty() => {
+                let (first_row, first_col) = (metadata.first_row, metadata.first_col);
+                raw_cells
+                    .iter()
+                    .filter_map(|cell| {
+                        let thread = comments
+                            .get(&(first_row + cell.coord.row, first_col + cell.coord.col))?;
+                        let text = if config.comment_footnotes() {
+                            thread
+                                .iter()
+                                .map(|comment| comment.to_text())
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        } else {
+                            thread
+                                .iter()
+                                .map(|comment| {
+                                    format!("💬 {}", self.formatter.format_text(&comment.to_text()))
+                                })
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        };
+                        Some((cell.coord, text))
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        // 条件付き書式の単純なルールに該当するセルの注記
+        let highlights = if matches!(
+            config.conditional_formats,
+            ConditionalFormats::Annotate | ConditionalFormats::Marker
+        ) {
+            conditional_formats
+                .iter()
+                .filter_map(|format| Some((format, format.description()?)))
+                .flat_map(|(format, description)| {
+                    format
+                        .matching_cells(raw_cells)
+                        .into_iter()
+                        .map(move |coord| (coord, description.clone()))
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        CellNotes {
+            link_footnotes,
+            comments,
+            highlights,
+        }
+    }
+
+    /// GridBuild: フォーマット済みのセルからグリッドを構築し、セルの注記を付加（内部ヘルパー）
+    ///
+    /// 上限を超える結合範囲は値を複製せず、左上のセルのみに残します。
+    /// シート単位の上限を超えた行以降は打ち切ります。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok((グリッド, 打ち切りの説明))` - 打ち切りの説明はシート単位の上限で打ち切った場合のみ
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    fn build_grid(
+        &self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        cells: SheetCells,
+        metadata: &mut SheetMetadata,
+        notes: CellNotes,
+        warnings: &mut Vec<ConversionWarning>,
+    ) -> Result<(LogicalGrid, Option<String>), XlsxToMdError> {
+        // 上限を超える結合範囲は値を複製せず、左上のセルのみに残す
+        let mut oversized_regions = Vec::new();
+        if config.merge_strategy == MergeStrategy::DataDuplication {
+            let (rows, cols) = LogicalGrid::grid_size(&cells.raw);
+            let max_cells = config.security.max_merged_cells;
+            let (kept, oversized): (Vec<_>, Vec<_>) = std::mem::take(&mut metadata.merged_regions)
+                .into_iter()
+                .partition(|region| region.cell_count_within(rows, cols) <= max_cells);
+            metadata.merged_regions = kept;
+            for region in &oversized {
+                warnings.push(ConversionWarning::sheet(
+                    sheet_name,
+                    WarningKind::MergedRegionLimit,
+                    format!(
+                        "merged region {}:{} spans {} cells (max: {}); value kept in the top-left cell only",
+                        region.range.start.to_a1_notation(),
+                        region.range.end.to_a1_notation(),
+                        region.cell_count_within(rows, cols),
+                        max_cells
+                    ),
+                ));
+            }
+            oversized_regions = oversized;
+        }
+        let mut grid =
+            LogicalGrid::build(cells.raw, cells.formatted, metadata, config.merge_strategy)?;
+        grid.set_raw_numbers(&cells.raw_numbers);
+        if !oversized_regions.is_empty() {
+            grid.mark_merged_children(&oversized_regions);
+        }
+        // シート単位の上限を超えた行以降の打ち切り
+        let truncation = match config.per_sheet_limits {
+            Some(limits) => {
+                self.apply_sheet_limits(&mut grid, metadata, limits, cells.timed_out_row)
+            }
+            None => None,
+        };
+        if let Some(description) = &truncation {
+            warnings.push(ConversionWarning::sheet(
+                sheet_name,
+                WarningKind::SheetTruncated,
+                format!("sheet {}", description),
+            ));
+        }
+        for (coord, url) in notes.link_footnotes {
+            grid.add_footnote(coord, url);
+        }
+        for (coord, text) in notes.comments {
+            if config.comment_footnotes() {
+                grid.add_footnote(coord, text);
+            } else {
+                grid.append_comment(coord, &text);
+            }
+        }
+        // 型付きJSONでは、マーカーの代わりにセルのオブジェクトに強調表示を出力する
+        let highlight_marker = config.conditional_formats == ConditionalFormats::Marker
+            && !(config.output_format == OutputFormat::Json
+                && config.json_cell_mode == JsonCellMode::Typed);
+        let mut marked = HashSet::new();
+        for (coord, description) in notes.highlights {
+            if !highlight_marker {
+                grid.add_highlight(coord, description);
+            } else if marked.insert(coord) {
+                grid.mark_highlight(coord);
+            }
+        }
+        if config.merge_strategy == MergeStrategy::DataDuplication {
+            grid.apply_duplication_placement(
+                &metadata.merged_regions,
+                config.duplication_placement,
+            );
+        }
+        metadata.merged_regions.extend(oversized_regions);
+        Ok((grid, truncation))
+    }
+
+    /// 空行の除去、空の行・列の刈り込みと使用範囲の肥大化の検出（内部ヘルパー）
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した空行のシート上の行番号（1始まり）
+    fn clean_grid(
+        &self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        grid: &mut LogicalGrid,
+        metadata: &mut SheetMetadata,
+        warnings: &mut Vec<ConversionWarning>,
+    ) -> Vec<u32> {
+        // 空行の除去（シート上の行番号に変換して記録）
+        let removed_blank_rows: Vec<u32> = if config.blank_rows == BlankRows::Keep {
+            Vec::new()
+        } else {
+            grid.remove_blank_rows(&mut metadata.merged_regions)
+                .into_iter()
+                .map(|row| metadata.first_row + row + 1)
+                .collect()
+        };
+        // 空の行・列の刈り込み
+        grid.trim_empty(config.trim_empty, &mut metadata.merged_regions);
+
+        // 使用範囲の肥大化の検出（設定に応じて縮小）
+        if grid.is_bloated() {
+            let (rows, cols) = (grid.get_rows(), grid.get_cols());
+            let mut message = format!(
+                "used range of {} rows x {} columns contains only {} non-empty cells",
+                rows,
+                cols,
+                grid.non_blank_count()
+            );
+            if config.auto_shrink_used_range {
+                grid.shrink_to_content();
+                message.push_str(&format!(
+                    "; shrunk to {} rows x {} columns",
+                    grid.get_rows(),
+                    grid.get_cols()
+                ));
+            }
+            warnings.push(ConversionWarning::sheet(
+                sheet_name,
+                WarningKind::UsedRangeBloat,
+                message,
+            ));
+        }
+        removed_blank_rows
+    }
+
+    /// 前方補完、行の絞り込み、計算列の追加、並べ替えと列の値の検証（内部ヘルパー）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(usize)` - 絞り込みで除外した行数
+    /// * `Err(XlsxToMdError)` - 検証の違反でエラーにする設定で、違反があった場合
+    fn transform_rows(
+        &self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        grid: &mut LogicalGrid,
+        warnings: &mut Vec<ConversionWarning>,
+    ) -> Result<usize, XlsxToMdError> {
+        // 前方補完（グループのラベルを各行に複製）
+        for column in &config.forward_fill {
+            if let Some(col) = find_column(grid, column) {
+                grid.forward_fill(col);
+            }
+        }
+
+        // 行の内容による絞り込み
+        let filtered_rows = match &self.row_filter {
+            Some(filter) => grid.filter_rows(filter),
+            None => 0,
+        };
+
+        // 計算列の追加（見出しにない列を参照する計算列は追加しない）
+        for column in &self.computed_columns {
+            if let Err(missing) = column.apply(grid, &self.formatter) {
+                warnings.push(ConversionWarning::sheet(
+                    sheet_name,
+                    WarningKind::ComputedColumnSkipped,
+                    format!(
+                        "computed column '{}' skipped: column '{}' not found in the header row",
+                        column.name(),
+                        missing
+                    ),
+                ));
+            }
+        }
+
+        // 行の並べ替え
+        if let Some((column, order, numeric_aware)) = &config.sort {
+            if let Some(col) = find_column(grid, column) {
+                grid.sort_rows(col, *order, *numeric_aware);
+            }
+        }
+
+        // 列の値の検証
+        let violations = crate::validation::validate(grid, &config.validations);
+        if !violations.is_empty() && config.validation_policy == ValidationPolicy::Fail {
+            return Err(XlsxToMdError::Validation {
+                sheet: sheet_name.to_string(),
+                message: violations.join("; "),
+            });
+        }
+        warnings.extend(violations.into_iter().map(|message| {
+            ConversionWarning::sheet(sheet_name, WarningKind::ValidationFailed, message)
+        }));
+        Ok(filtered_rows)
+    }
+
+    /// 定数列の除去、連続する同一行の集約と見出しの注記（内部ヘルパー）
+    ///
+    /// # 戻り値
+    ///
+    /// `(除去した定数列, 検出した見出しの単位)`（単位は検出する設定の場合のみ）
+    fn finish_grid(
+        &self,
+        config: &ConversionConfig,
+        grid: &mut LogicalGrid,
+        data_validations: &[DataValidation],
+    ) -> (Vec<FoldedColumn>, Option<Vec<Option<String>>>) {
+        // 定数列の除去（JSON・XML形式では列名・セル参照が変わるため適用しない）
+        let folded_columns = if config.constant_column_folding
+            && !matches!(config.output_format, OutputFormat::Json | OutputFormat::Xml)
+        {
+            grid.fold_constant_columns()
+        } else {
+            Vec::new()
+        };
+
+        // 連続する同一行の集約
+        if config.row_dedupe == RowDedupe::ConsecutiveWithCount {
+            grid.dedupe_consecutive_rows();
+        }
+
+        // 見出しの単位の検出（設定に応じて見出しから除去）
+        let column_units = match config.header_units {
+            HeaderUnits::Detect => Some(grid.detect_header_units(false)),
+            HeaderUnits::DetectAndStrip => Some(grid.detect_header_units(true)),
+            _ => None,
+        };
+
+        // リストの入力規則の値の一覧を見出しに付加（見出しより下の行に適用された入力規則のみ）
+        if config.data_validations == DataValidations::Annotate {
+            grid.annotate_header(|header| {
+                let allowed = data_validations
+                    .iter()
+                    .filter(|validation| validation.is_list())
+                    .find(|validation| {
+                        validation.ranges.iter().any(|range| {
+                            range.start.col <= header.col
+                                && header.col <= range.end.col
+                                && range.end.row > header.row
+                        })
+                    })?
+                    .allowed
+                    .as_ref()
+                    .filter(|allowed| !allowed.is_empty())?;
+                let mut values: Vec<String> = allowed
+                    .iter()
+                    .take(MAX_ANNOTATED_VALUES)
+                    .map(|value| self.formatter.format_text(value))
+                    .collect();
+                if allowed.len() > MAX_ANNOTATED_VALUES {
+                    values.push("…".to_string());
+                }
+                Some(format!("(allowed: {})", values.join(", ")))
+            });
+        }
+        (folded_columns, column_units)
+    }
+
+    /// シート単位の上限を超えた行以降を打ち切る（内部ヘルパー）
+    ///
+    /// 行数・セル数・処理時間のうち、最も少ない行数になる上限で打ち切り、
+    /// 打ち切った行にかかる結合範囲を残した行までに縮めます。
+    ///
+    /// # 引数
+    ///
+    /// * `grid` - 構築直後のグリッド
+    /// * `metadata` - シートのメタデータ（結合範囲を調整）
+    /// * `limits` - シート単位の上限（行数, セル数, 処理時間）
+    /// * `timed_out_row` - 処理時間の上限を超えた場合に、フォーマットできなかったセルの最初の行
+    ///
+    /// # 戻り値
+    ///
+    /// 打ち切った場合は打ち切りの説明（例: `truncated after 1000 of 5000 rows: exceeded the
+    /// per-sheet limit of 1000 rows`）、打ち切らなかった場合は`None`
+    fn apply_sheet_limits(
+        &self,
+        grid: &mut LogicalGrid,
+        metadata: &mut SheetMetadata,
+        (max_rows, max_cells, max_duration): (usize, usize, Duration),
+        timed_out_row: Option<u32>,
+    ) -> Option<String> {
+        let total = grid.get_rows();
+        let mut limits = vec![
+            (
+                max_rows,
+                format!("the per-sheet limit of {} rows", max_rows),
+            ),
+            (
+                grid.rows_within_cells(max_cells),
+                format!("the per-sheet limit of {} cells", max_cells),
+            ),
+        ];
+        if let Some(row) = timed_out_row {
+            limits.push((
+                row as usize,
+                format!("the per-sheet time limit of {:?}", max_duration),
+            ));
+        }
+        let (kept, reason) = limits
+            .into_iter()
+            .filter(|(kept, _)| *kept < total)
+            .min_by_key(|(kept, _)| *kept)?;
+
+        Self::truncate_rows(grid, metadata, kept);
+
+        Some(format!(
+            "truncated after {} of {} rows: exceeded {}",
+            kept, total, reason
+        ))
+    }
+
+    /// 先頭から`rows`行を残して以降の行を除去し、除去した行にかかる結合範囲を
+    /// 残した行までに縮める（内部ヘルパー）
+    fn truncate_rows(grid: &mut LogicalGrid, metadata: &mut SheetMetadata, rows: usize) {
+        if grid.truncate_rows(rows) == 0 {
+            return;
+        }
+        let last_row = rows as u32;
+        metadata
+            .merged_regions
+            .retain(|region| region.parent.row < last_row);
+        for region in &mut metadata.merged_regions {
+            region.range.end.row = region.range.end.row.min(last_row - 1);
+        }
+    }
+}
+
+/// 列の指定（列番号または見出し）から列番号を取得（内部ヘルパー）
+fn find_column(grid: &LogicalGrid, column: &ColumnSelector) -> Option<usize> {
+    match column {
+        ColumnSelector::Index(col) => Some(*col),
+        ColumnSelector::Header(name) => grid.find_header(name),
+    }
+}
+
+/// フォーマット済みのセル（内部ヘルパー）
+struct SheetCells {
+    /// セルデータ（行優先の順序）
+    raw: Vec<RawCellData>,
+
+    /// セルの表示文字列（`raw`と同じ順序）
+    formatted: Vec<(CellCoord, String)>,
+
+    /// 生の数値として出力したセルの数値
+    raw_numbers: HashMap<CellCoord, f64>,
+
+    /// 処理時間の上限を超えた場合に、フォーマットできなかったセルの最初の行
+    timed_out_row: Option<u32>,
+}
+
+/// グリッドのセルに付加する注記（内部ヘルパー）
+struct CellNotes {
+    /// ハイパーリンクの脚注（セル, URL）
+    link_footnotes: Vec<(CellCoord, String)>,
+
+    /// セルのコメント（セル, コメントの文字列）
+    comments: Vec<(CellCoord, String)>,
+
+    /// 条件付き書式の単純なルールに該当するセル（セル, ルールの説明）
+    highlights: Vec<(CellCoord, String)>,
+}
+
+/// 構築済みの1シート（内部ヘルパー）
+pub(super) struct BuiltSheet<'a> {
+    /// シート単位の出力フォーマットを反映した設定
+    pub(super) config: Cow<'a, ConversionConfig>,
+
+    /// 出力前のグリッド
+    pub(super) sheet: SheetGrid,
+
+    /// シート単位の警告
+    warnings: Vec<ConversionWarning>,
+
+    /// ステージごとの処理時間（Renderを除く）
+    timings: SheetTimings,
+
+    /// 絞り込みで除外した行数
+    filtered_rows: usize,
+
+    /// 除去した空行の数
+    removed_rows: usize,
+
+    /// 非表示として扱った、高さが0の行数と幅が0の列数
+    zero_size: (usize, usize),
+
+    /// 値を持つセルの数
+    cell_count: usize,
+
+    /// セルデータの推定メモリ使用量
+    raw_cells_bytes: usize,
+
+    /// セルの表示文字列の合計バイト数
+    content_bytes: usize,
+
+    /// 重複シートの検出に使用する行のシグネチャ（検出しない場合は空）
+    signature: Vec<u64>,
+
+    /// シート単位の上限で打ち切った場合の説明
+    truncation: Option<String>,
+
+    /// 出力する表の行数・セル数の上限で省略した行数
+    omitted_rows: usize,
+
+    /// シートの処理の開始時刻
+    started: Instant,
+}
+
+impl BuiltSheet<'_> {
+    /// シートの処理の完了を通知（内部ヘルパー）
+    pub(super) fn notify_finished(&self, config: &ConversionConfig, sheet_name: &str) {
+        config.emit_progress(ProgressEvent::SheetFinished {
+            sheet: sheet_name,
+            index: self.sheet.index,
+            rows: self.sheet.grid.get_rows(),
+            elapsed: self.started.elapsed(),
+        });
+    }
+}
+
+/// 変換済みの1シート（内部ヘルパー）
+pub(super) struct RenderedSheet {
+    /// シート名
+    pub(super) name: String,
+
+    /// 適用された出力フォーマット
+    pub(super) format: OutputFormat,
+
+    /// シートの出力
+    pub(super) output: String,
+
+    /// シート単位の情報
+    pub(super) info: SheetInfo,
+
+    /// シート単位の処理統計
+    pub(super) report: SheetReport,
+
+    /// 重複シートの検出に使用する行のシグネチャ（検出しない場合は空）
+    pub(super) signature: Vec<u64>,
+}
+
+/// 列幅の上限で切り詰めたセルの数を取得（内部ヘルパー）
+///
+/// `MarkdownFormatter`と同じく、HTMLフォールバックで出力した表とMarkdown以外の形式は数えません。
+fn truncated_cell_count(config: &ConversionConfig, sheet: &SheetGrid) -> usize {
+    match config.column_width_limit {
+        Some((width, WrapStrategy::Truncate))
+            if config.output_format == OutputFormat::Markdown
+                && config.format_plugin.is_none()
+                && (config.merge_strategy != MergeStrategy::HtmlFallback
+                    || sheet.merged_regions.is_empty()) =>
+        {
+            sheet
+                .grid
+                .count_truncated_cells(width, config.split_on_heading_styles)
+        }
+        _ => 0,
+    }
+}
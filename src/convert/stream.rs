@@ -0,0 +1,191 @@
+//! Row Streaming Module
+//!
+//! グリッドを構築せずに、シートのXMLから1行ずつフォーマットして出力するモジュール。
+//!
+//! `Converter::convert_sheet_by_sheet()`で、行単位で出力できる設定
+//! （`ConversionConfig::streams_rows()`）のCSV・Markdownのシートに使用します。
+
+use super::CountingWriter;
+use crate::api::OutputFormat;
+use crate::builder::{ConversionConfig, Converter};
+use crate::error::XlsxToMdError;
+use crate::output::DocumentLayout;
+use crate::parser::{WorkbookParser, XlsxMetadataParser};
+use crate::progress::ProgressEvent;
+use crate::report::{SheetReport, SheetTimings};
+use crate::types::{RawCellData, SheetVisibility};
+use std::io::{Read, Seek, Write};
+use std::time::Instant;
+
+impl Converter {
+    /// 1シートをグリッドを構築せずに行単位で出力（内部ヘルパー）
+    ///
+    /// 行単位で出力できる設定（`ConversionConfig::streams_rows()`）で、結合セルのない
+    /// XLSXのワークシートの場合に、シートのXMLを読み込みながら1行ずつ出力先に書き出します。
+    /// 出力は`convert_sheet()`と同じです。Markdownの列幅の計算と使用範囲の肥大化の判定には
+    /// シート全体が必要なため、書き出す前にシートのXMLをもう一度読み込みます。
+    ///
+    /// # 引数
+    ///
+    /// * `parser` - ワークブックのパーサー
+    /// * `sheet_name` - シート名
+    /// * `sheet_index` - シートの出力順（0始まり）
+    /// * `metadata` - 解析済みのXMLメタデータ
+    /// * `layout` - 文書のレイアウト（シート間の区切りと見出し）
+    /// * `writer` - 出力先のライター
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Some(SheetReport))` - 書き出したシートの処理統計
+    /// * `Ok(None)` - 行単位で出力できないシートの場合（何も書き出さない）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（書き出し中のシートは途中まで出力済み）
+    pub(super) fn stream_sheet<RS: Read + Seek, W: Write>(
+        &self,
+        parser: &mut WorkbookParser<RS>,
+        sheet_name: &str,
+        sheet_index: usize,
+        metadata: &XlsxMetadataParser,
+        layout: &DocumentLayout,
+        writer: &mut CountingWriter<W>,
+    ) -> Result<Option<SheetReport>, XlsxToMdError> {
+        let started = Instant::now();
+        let config = self.sheet_config(sheet_name, metadata)?;
+        if !config.streams_rows()
+            || self.row_filter.is_some()
+            || !self.computed_columns.is_empty()
+            || !layout.has_plain_sheets()
+        {
+            return Ok(None);
+        }
+        let markdown = config.output_format == OutputFormat::Markdown;
+        let mut timings = SheetTimings::default();
+
+        // 1. グリッドの行数・列数の走査（結合セルを含むシートはグリッドを構築して変換）
+        let step_start = Instant::now();
+        let Some(scan) = parser.scan_sheet(sheet_name, &config)? else {
+            return Ok(None);
+        };
+        if !scan.metadata.merged_regions.is_empty() {
+            return Ok(None);
+        }
+        let is_1904 = scan.metadata.is_1904;
+
+        // 2. Markdownの列幅と、使用範囲の肥大化の判定（肥大化したシートはグリッドを構築して変換）
+        let mut col_widths = vec![3; scan.cols];
+        if markdown || crate::grid::is_bloated_size(scan.rows, scan.cols, 0) {
+            let mut non_blank = 0;
+            parser.stream_rows(sheet_name, &config, &scan, |_, cells| {
+                let contents = self.format_row(sheet_name, cells, &config, is_1904, scan.cols)?;
+                non_blank += contents.iter().filter(|c| !c.trim().is_empty()).count();
+                crate::grid::update_markdown_widths(
+                    &mut col_widths,
+                    contents.iter().map(|c| c.trim()),
+                );
+                Ok(())
+            })?;
+            if crate::grid::is_bloated_size(scan.rows, scan.cols, non_blank) {
+                return Ok(None);
+            }
+        }
+        timings.parse = step_start.elapsed();
+
+        // 3. シートの区切りと見出しの後に、1行ずつフォーマットして書き出す
+        self.config.emit_progress(ProgressEvent::SheetStarted {
+            sheet: sheet_name,
+            index: sheet_index,
+        });
+        let step_start = Instant::now();
+        let visibility = if config.include_hidden {
+            parser.sheet_visibility(sheet_name)
+        } else {
+            SheetVisibility::Visible
+        };
+        layout.write_sheet_heading(
+            writer,
+            sheet_index,
+            &visibility.heading(sheet_name),
+            config.output_format,
+        )?;
+        let body_start = writer.bytes_written();
+        let separator = crate::grid::markdown_separator(&col_widths);
+        let mut cell_count = 0;
+        parser.stream_rows(sheet_name, &config, &scan, |row_idx, cells| {
+            // 指定した行数ごとに、出力済みの行数を通知
+            if row_idx > 0 && row_idx.is_multiple_of(config.progress_interval) {
+                config.emit_progress(ProgressEvent::RowsProcessed {
+                    sheet: sheet_name,
+                    rows: row_idx,
+                    total_rows: scan.rows,
+                });
+            }
+            cell_count += cells.iter().filter(|c| !c.value.is_empty()).count();
+            let contents = self.format_row(sheet_name, cells, &config, is_1904, scan.cols)?;
+            if markdown {
+                crate::grid::write_markdown_row(
+                    writer,
+                    contents.iter().map(|c| c.trim()),
+                    &col_widths,
+                )?;
+                // 最初の行の後に区切り行を挿入
+                if row_idx == 0 {
+                    writeln!(writer, "{}", separator)?;
+                }
+            } else {
+                crate::output::write_csv_row(writer, contents.iter().map(String::as_str))?;
+            }
+            Ok(())
+        })?;
+        timings.render = step_start.elapsed();
+
+        // 保持するのは1行分のセルデータと表示文字列、列幅のみ
+        let memory_estimate = (scan.cols
+            * (std::mem::size_of::<RawCellData>()
+                + std::mem::size_of::<String>()
+                + std::mem::size_of::<usize>())) as u64;
+        let sheet_report = SheetReport {
+            name: sheet_name.to_string(),
+            rows: scan.rows,
+            cols: scan.cols,
+            cells: cell_count,
+            output_size: writer.bytes_written() - body_start,
+            memory_estimate,
+            warnings: Vec::new(),
+            folded_columns: Vec::new(),
+            filtered_rows: 0,
+            removed_blank_rows: 0,
+            zero_size_rows: scan.metadata.zero_size_rows.len(),
+            zero_size_cols: scan.metadata.zero_size_cols.len(),
+            truncated_cells: 0,
+            omitted_rows: 0,
+            timings,
+        };
+        self.config.emit_progress(ProgressEvent::SheetFinished {
+            sheet: sheet_name,
+            index: sheet_index,
+            rows: sheet_report.rows,
+            elapsed: started.elapsed(),
+        });
+        Ok(Some(sheet_report))
+    }
+
+    /// 1行分のセルデータをフォーマットし、列ごとの表示文字列を取得（内部ヘルパー）
+    ///
+    /// セルデータのない列は空文字列です。
+    fn format_row(
+        &self,
+        sheet_name: &str,
+        cells: &[RawCellData],
+        config: &ConversionConfig,
+        is_1904: bool,
+        cols: usize,
+    ) -> Result<Vec<String>, XlsxToMdError> {
+        let mut contents = vec![String::new(); cols];
+        for cell in cells {
+            contents[cell.coord.col as usize] =
+                self.formatter
+                    .format_cell(cell, config, is_1904)
+                    .map_err(|e| e.at_cell(sheet_name, || cell.coord.to_a1_notation()))?;
+        }
+        Ok(contents)
+    }
+}
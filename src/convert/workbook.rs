@@ -0,0 +1,322 @@
+//! Workbook Loading Module
+//!
+//! 変換の入力を読み込み、XMLメタデータの解析と変換するシートの選択を行うモジュール。
+//!
+//! 入力全体をメモリに読み込む`load_workbook()`（並列処理用）と、リーダーから直接開く
+//! `load_workbook_unbuffered()`（`Converter::convert_sheet_by_sheet()`用）を提供します。
+
+use crate::api::ProtectedSheets;
+use crate::builder::Converter;
+use crate::error::{ConfigError, SecurityLimit, XlsxToMdError};
+use crate::parser::DataValidation;
+use crate::progress::ProgressEvent;
+use crate::report::{ConversionReport, ConversionWarning, WarningKind};
+use crate::types::CellRange;
+use std::io::{Cursor, Read, Seek};
+use std::sync::Arc;
+use std::time::Instant;
+
+impl Converter {
+    /// 入力を読み込み、メタデータの解析とシートの選択を行う（内部ヘルパー）
+    ///
+    /// 入力サイズと読み込み・ZIP走査・メタデータ解析の時間を`report`に記録します。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok((パーサーのプール, シート名))` - プールは不正なUTF-8の修復後の入力データと
+    ///   解析済みのメタデータを共有し、シートの選択で開いたパーサーを再利用する。
+    ///   シート名は選択されたシート（出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub(super) fn load_workbook<R: Read + Seek>(
+        &self,
+        input: R,
+        report: &mut ConversionReport,
+    ) -> Result<(crate::parser::WorkbookParserPool, Vec<String>), XlsxToMdError> {
+        // 1. 入力データをメモリに読み込む（並列処理のため）
+        let phase_start = Instant::now();
+        let security_config = &self.config.security;
+        let mut buffer = self.read_input(input)?;
+        report.input_size = buffer.len() as u64;
+        report.timings.read_input = phase_start.elapsed();
+        // 旧形式（XLS）はZIPアーカイブではないため、XMLパーツの修復とメタデータの解析を省略する
+        let legacy_xls = crate::parser::is_legacy_xls(&buffer);
+
+        // 2. WorkbookParserの初期化（ZIPアーカイブの走査とワークブック構造の読み込み）
+        let phase_start = Instant::now();
+        // 不正なUTF-8を含むXMLパーツは、設定に応じて置換文字に置き換える
+        if self.config.encoding_fallback && !legacy_xls {
+            if let Some(repaired) = crate::parser::repair_invalid_utf8(&buffer, security_config)? {
+                report.warnings.push(ConversionWarning::workbook(
+                    WarningKind::InvalidEncoding,
+                    format!(
+                        "invalid UTF-8 in {} replaced with U+FFFD",
+                        repaired.parts.join(", ")
+                    ),
+                ));
+                buffer = repaired.buffer;
+            }
+        }
+        // 入力データは以降、複製せずにパーサー間で共有する
+        let buffer = crate::parser::SharedBuffer::new(buffer);
+        // calamineが展開を始める前に、実際に展開したサイズと圧縮率でZIPアーカイブを検証する
+        // （ZIPアーカイブとして開けない入力は、calamineで形式を判定してエラーにする）
+        let archive = match zip::ZipArchive::new(Cursor::new(buffer.as_ref())) {
+            Ok(mut archive) if !legacy_xls => {
+                crate::security::check_archive(&mut archive, security_config)?;
+                Some(archive)
+            }
+            _ => None,
+        };
+        let parser = crate::parser::WorkbookParser::open(Cursor::new(buffer.clone()))?;
+        report.timings.zip_scan = phase_start.elapsed();
+
+        // 3. XMLメタデータを1回だけ解析して再利用（並列処理の効率化）
+        // 非表示行・列、ハイパーリンクなどの情報を取得する
+        let phase_start = Instant::now();
+        // 変換設定で不要な要素（非表示行・列、ハイパーリンク）は解析しない
+        let metadata = if legacy_xls {
+            report.warnings.push(ConversionWarning::workbook(
+                WarningKind::LegacyFormat,
+                "legacy .xls (BIFF) workbook: hidden rows/columns, hyperlinks, rich text, \
+                 cell styles and conditional formats are not extracted"
+                    .to_string(),
+            ));
+            crate::parser::XlsxMetadataParser::default()
+        } else {
+            match archive {
+                Some(archive) => crate::parser::XlsxMetadataParser::from_checked_archive(
+                    archive,
+                    self.config.metadata_parts(),
+                )?,
+                None => crate::parser::XlsxMetadataParser::with_parts(
+                    Cursor::new(buffer.as_ref()),
+                    self.config.metadata_parts(),
+                    security_config,
+                )?,
+            }
+        };
+        let metadata = Arc::new(metadata);
+        report.timings.metadata = phase_start.elapsed();
+        report.revisions = metadata.revisions().to_vec();
+
+        // 4. シート選択（設定に応じて保護されたシートを除外）
+        let sheet_names = self.select_sheets(&parser, &metadata, report)?;
+        self.config.emit_progress(ProgressEvent::WorkbookLoaded {
+            sheets: sheet_names.len(),
+        });
+
+        // シートの選択で開いたパーサーは、プールの最初のパーサーとして再利用する
+        let parser = parser.with_metadata(Arc::clone(&metadata));
+        Ok((
+            crate::parser::WorkbookParserPool::new(buffer, metadata, parser),
+            sheet_names,
+        ))
+    }
+
+    /// 入力をメモリに読み込まずに開き、メタデータの解析とシートの選択を行う（内部ヘルパー）
+    ///
+    /// `load_workbook()`と異なり、入力全体の複製を作らず、リーダーから直接ワークブックを開きます。
+    /// 入力サイズは末尾へのシークで判定し、読み込み時間は記録しません。
+    /// 不正なUTF-8の修復は入力データの書き換えが必要なため行いません。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok((パーサー, メタデータ, シート名))` - シート名は選択されたシート（出力順）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    #[allow(clippy::type_complexity)]
+    pub(super) fn load_workbook_unbuffered<R: Read + Seek>(
+        &self,
+        mut input: R,
+        report: &mut ConversionReport,
+    ) -> Result<
+        (
+            crate::parser::WorkbookParser<R>,
+            Arc<crate::parser::XlsxMetadataParser>,
+            Vec<String>,
+        ),
+        XlsxToMdError,
+    > {
+        // 1. 入力サイズの上限（読み込まずに末尾へのシークで判定）
+        let max_input_file_size = self.config.security.max_input_file_size;
+        let input_size = input.seek(std::io::SeekFrom::End(0))?;
+        if input_size > max_input_file_size {
+            return Err(SecurityLimit::InputSize {
+                size: input_size,
+                max: max_input_file_size,
+            }
+            .into());
+        }
+        report.input_size = input_size;
+        input.rewind()?;
+        let mut signature = Vec::new();
+        (&mut input).take(8).read_to_end(&mut signature)?;
+        let legacy_xls = crate::parser::is_legacy_xls(&signature);
+
+        // 2. XMLメタデータの解析（ZIPアーカイブのパーツを個別に読み込む）
+        let phase_start = Instant::now();
+        let metadata = if legacy_xls {
+            report.warnings.push(ConversionWarning::workbook(
+                WarningKind::LegacyFormat,
+                "legacy .xls (BIFF) workbook: hidden rows/columns, hyperlinks, rich text, \
+                 cell styles and conditional formats are not extracted"
+                    .to_string(),
+            ));
+            crate::parser::XlsxMetadataParser::default()
+        } else {
+            input.rewind()?;
+            crate::parser::XlsxMetadataParser::with_parts(
+                &mut input,
+                self.config.metadata_parts(),
+                &self.config.security,
+            )?
+        };
+        let metadata = Arc::new(metadata);
+        report.timings.metadata = phase_start.elapsed();
+        report.revisions = metadata.revisions().to_vec();
+
+        // 3. WorkbookParserの初期化（リーダーから直接開く）
+        let phase_start = Instant::now();
+        input.rewind()?;
+        let parser = crate::parser::WorkbookParser::open_unbuffered(
+            input,
+            legacy_xls,
+            Arc::clone(&metadata),
+        )?;
+        report.timings.zip_scan = phase_start.elapsed();
+
+        // 4. シート選択（設定に応じて保護されたシートを除外）
+        let sheet_names = self.select_sheets(&parser, &metadata, report)?;
+        self.config.emit_progress(ProgressEvent::WorkbookLoaded {
+            sheets: sheet_names.len(),
+        });
+
+        Ok((parser, metadata, sheet_names))
+    }
+
+    /// 変換するシートを選択（内部ヘルパー）
+    ///
+    /// 保護されたシートを除外する設定の場合、除外したシートを`report`の警告に記録します。
+    pub(super) fn select_sheets<RS: Read + Seek>(
+        &self,
+        parser: &crate::parser::WorkbookParser<RS>,
+        metadata: &crate::parser::XlsxMetadataParser,
+        report: &mut ConversionReport,
+    ) -> Result<Vec<String>, XlsxToMdError> {
+        // 名前付き範囲を指定した場合は、参照先のシートのみを変換する
+        if let Some(name) = &self.config.named_range {
+            let (sheet_name, _) = Self::resolve_named_range(name, metadata)?;
+            return Ok(vec![sheet_name.to_string()]);
+        }
+
+        let mut sheet_names =
+            parser.select_sheets(&self.config.sheet_selector, self.config.include_hidden)?;
+        if self.config.protected_sheets == ProtectedSheets::Skip {
+            sheet_names.retain(|name| {
+                let protected = metadata.is_sheet_protected(name);
+                if protected {
+                    report.warnings.push(ConversionWarning::sheet(
+                        name,
+                        WarningKind::ProtectedSheet,
+                        "protected sheet skipped".to_string(),
+                    ));
+                }
+                !protected
+            });
+        }
+        Ok(sheet_names)
+    }
+
+    /// 名前付き範囲の参照先のシート名と範囲を取得（内部ヘルパー）
+    pub(super) fn resolve_named_range<'m>(
+        name: &str,
+        metadata: &'m crate::parser::XlsxMetadataParser,
+    ) -> Result<(&'m str, CellRange), XlsxToMdError> {
+        metadata.defined_name_range(name).ok_or_else(|| {
+            XlsxToMdError::Config(ConfigError::NamedRangeNotFound {
+                name: name.to_string(),
+            })
+        })
+    }
+
+    /// シートの入力規則を取得し、リストの参照先を値の一覧に解決（内部ヘルパー）
+    ///
+    /// 参照先のセル範囲は、シート名で修飾されていない場合は同じシートの範囲として、
+    /// セル範囲でない場合は名前付き範囲として解決します。参照先のシートごとに1回だけ読み込みます。
+    /// 解決できない参照先（関数、存在しないシート・名前）のリストは値の一覧を設定しません。
+    ///
+    /// # 引数
+    ///
+    /// * `parser` - ワークブックのパーサー
+    /// * `sheet_name` - シート名
+    /// * `metadata` - 解析済みのXMLメタデータ
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<DataValidation>)` - 入力規則（記録順、解析しない設定の場合は空）
+    /// * `Err(XlsxToMdError)` - 参照先のシートの読み込みに失敗した場合
+    pub(super) fn resolve_data_validations<RS: Read + Seek>(
+        parser: &mut crate::parser::WorkbookParser<RS>,
+        sheet_name: &str,
+        metadata: &crate::parser::XlsxMetadataParser,
+    ) -> Result<Vec<DataValidation>, XlsxToMdError> {
+        let mut validations = metadata.data_validations(sheet_name).to_vec();
+
+        // 参照先のシート -> (入力規則のインデックス, 範囲)
+        let mut targets: Vec<(String, Vec<(usize, CellRange)>)> = Vec::new();
+        for (idx, validation) in validations.iter().enumerate() {
+            if validation.allowed.is_some() {
+                continue;
+            }
+            let Some((sheet, reference)) = validation.list_reference() else {
+                continue;
+            };
+            let target = match (reference.parse::<CellRange>(), sheet) {
+                (Ok(range), sheet) => {
+                    Some((sheet.unwrap_or_else(|| sheet_name.to_string()), range))
+                }
+                (Err(_), None) => metadata
+                    .defined_name_range(reference)
+                    .map(|(sheet, range)| (sheet.to_string(), range)),
+                (Err(_), Some(_)) => None,
+            };
+            let Some((sheet, range)) = target else {
+                continue;
+            };
+            if !parser.get_sheet_names().contains(&sheet) {
+                continue;
+            }
+            match targets.iter_mut().find(|(name, _)| *name == sheet) {
+                Some((_, ranges)) => ranges.push((idx, range)),
+                None => targets.push((sheet, vec![(idx, range)])),
+            }
+        }
+
+        for (sheet, ranges) in targets {
+            let (indices, ranges): (Vec<usize>, Vec<CellRange>) = ranges.into_iter().unzip();
+            let values = parser.range_values(&sheet, &ranges)?;
+            for (idx, values) in indices.into_iter().zip(values) {
+                validations[idx].allowed = Some(values);
+            }
+        }
+        Ok(validations)
+    }
+
+    /// 入力データを入力ファイルのサイズの上限まで読み込む（内部ヘルパー）
+    pub(super) fn read_input<R: Read>(&self, input: R) -> Result<Vec<u8>, XlsxToMdError> {
+        let max_input_file_size = self.config.security.max_input_file_size;
+        // 上限を1バイト超えた時点で読み込みを打ち切る
+        let mut buffer = Vec::new();
+        let bytes_read = input
+            .take(max_input_file_size.saturating_add(1))
+            .read_to_end(&mut buffer)?;
+
+        if bytes_read as u64 > max_input_file_size {
+            return Err(SecurityLimit::InputSize {
+                size: bytes_read as u64,
+                max: max_input_file_size,
+            }
+            .into());
+        }
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,381 @@
+//! Document Module
+//!
+//! 変換途中のシートのグリッドを編集し、任意の出力フォーマットで再出力するための
+//! `GridDocument`を提供するモジュール。
+//!
+//! 通常の変換（`Converter::convert()`など）も、シートごとに構築したグリッド（`SheetGrid`）を
+//! このモジュールの`render_sheet()`で出力します。
+
+use crate::api::{JsonKeyMode, OutputFormat};
+use crate::builder::ConversionConfig;
+use crate::error::XlsxToMdError;
+use crate::formatter::CellFormatter;
+use crate::grid::{Cell, LogicalGrid};
+use crate::model::SheetModel;
+use crate::output::{Footnotes, OutputFormatter};
+use crate::plugin::FormatHandle;
+use crate::report::FoldedColumn;
+use crate::script::detect_script;
+use crate::types::{CellCoord, CellValue, MergedRegion, RawCellData, SheetInfo};
+use std::collections::HashMap;
+
+/// 出力前の1シート分のグリッド（内部用）
+#[derive(Debug, Clone)]
+pub(crate) struct SheetGrid {
+    /// シートの出力順（0始まり、脚注の識別子に使用）
+    pub index: usize,
+
+    /// グリッド
+    pub grid: LogicalGrid,
+
+    /// 結合セル範囲のリスト
+    pub merged_regions: Vec<MergedRegion>,
+
+    /// シート単位の情報
+    pub info: SheetInfo,
+
+    /// 表から除去した定数列
+    pub folded_columns: Vec<FoldedColumn>,
+
+    /// 元のセルデータ（元のシート上の座標がキー、`GridDocument`の場合のみ保持）
+    pub raw_cells: HashMap<CellCoord, RawCellData>,
+
+    /// 1904年エポックを使用するか（元のセルデータの再フォーマットに使用）
+    pub is_1904: bool,
+}
+
+/// シートのグリッドを出力フォーマットに応じて出力し、出力文字列を返す
+///
+/// Markdown形式では脚注を採番してセルに参照を付加するため、`sheet`のグリッドを変更します。
+/// `Stage::Render`のパイプラインステージも適用します。
+///
+/// # 引数
+///
+/// * `config` - 変換設定（`format_plugin`または`output_format`が出力フォーマット）
+/// * `config_hash` - フィンガープリントに使用する設定のハッシュ値
+/// * `sheet` - 出力するシート
+/// * `content_bytes` - セルの表示文字列の合計バイト数（出力バッファの確保に使用）
+///
+/// # 戻り値
+///
+/// * `Ok(String)` - シートの出力
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn render_sheet(
+    config: &ConversionConfig,
+    config_hash: u64,
+    sheet: &mut SheetGrid,
+    content_bytes: usize,
+) -> Result<String, XlsxToMdError> {
+    let grid = &mut sheet.grid;
+
+    // 出力フォーマッターを取得
+    let mut formatter = OutputFormatter::from_config(config);
+    if config.include_fingerprint {
+        formatter.set_config_hash(config_hash);
+    }
+
+    // 出力サイズの概算（セルの内容 + セルごとの区切り）で確保し、再確保を減らす
+    let mut output_buffer =
+        Vec::with_capacity(content_bytes + grid.get_rows() * (grid.get_cols() + 1) * 3);
+    // Markdown形式では脚注を採番してセルに参照を付加し、表の後に脚注セクションを出力
+    let mut footnotes = Footnotes::new(sheet.index);
+    if config.output_format == OutputFormat::Markdown && config.format_plugin.is_none() {
+        grid.resolve_footnotes(&mut footnotes);
+    }
+    crate::output::write_folded_columns(
+        &mut output_buffer,
+        config.output_format,
+        &sheet.folded_columns,
+    )?;
+    match &config.format_plugin {
+        // 独自の出力フォーマットには、対応する機能に合わせて構築した文書モデルを渡す
+        Some(FormatHandle(plugin)) => {
+            let capabilities = plugin.capabilities();
+            let key_mode = if capabilities.needs_header_row {
+                JsonKeyMode::Header
+            } else {
+                JsonKeyMode::ColumnLetter
+            };
+            let merged_regions = capabilities
+                .supports_merged_cells
+                .then_some(sheet.merged_regions.as_slice());
+            let model = SheetModel::from_grid(grid, &sheet.info, key_mode, merged_regions);
+            plugin.render(&model, &mut output_buffer)?;
+        }
+        // セル参照を付加する場合は、列名の行と行番号の列を追加したグリッドを出力
+        None if config.cell_references && config.output_format == OutputFormat::Markdown => {
+            let merged_regions: Vec<MergedRegion> = sheet
+                .merged_regions
+                .iter()
+                .map(|region| region.offset(1, 1))
+                .collect();
+            formatter.render(
+                &grid.with_reference_headers(),
+                &mut output_buffer,
+                &merged_regions,
+                &sheet.info,
+            )?
+        }
+        None => formatter.render(grid, &mut output_buffer, &sheet.merged_regions, &sheet.info)?,
+    }
+    crate::output::write_removed_rows_marker(
+        &mut output_buffer,
+        config.output_format,
+        &sheet.info.removed_blank_rows,
+    )?;
+    if let Some(links) = &sheet.info.links {
+        links.write(&mut output_buffer, config.output_format)?;
+    }
+    footnotes.write_markdown(&mut output_buffer)?;
+
+    let mut output_string = String::from_utf8(output_buffer)
+        .map_err(|e| XlsxToMdError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))?;
+    config
+        .stages
+        .run_render(&sheet.info.name, &mut output_string)?;
+    Ok(output_string)
+}
+
+/// 編集可能なシートのグリッド
+///
+/// `Converter::convert_to_grids()`で取得します。解析・フォーマット後のセルの表示文字列を
+/// プログラムから編集（誤字の修正、計算した列の追加など）し、任意の出力フォーマットで
+/// 出力できます。出力後のMarkdownなどのテキストを編集する必要はありません。
+///
+/// セルの表示文字列は変換時の設定（数値の出力方式、日付形式など）でフォーマット済みです。
+/// 変換時と異なる出力フォーマットで出力する場合は、編集していないセルを元のセルの値から
+/// 出力フォーマットに合わせて再フォーマットします（`render()`を参照）。
+/// 行・列の番号は、出力する表の先頭を0とする位置です。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::{ConverterBuilder, OutputFormat};
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let converter = ConverterBuilder::new().build()?;
+/// let mut grids = converter.convert_to_grids(File::open("example.xlsx")?)?;
+///
+/// for grid in &mut grids {
+///     // 誤字を修正し、行番号の列を追加する
+///     if grid.cell(0, 0) == Some("Nmae") {
+///         grid.set_cell(0, 0, "Name");
+///     }
+///     let numbers: Vec<String> = (0..grid.rows())
+///         .map(|row| if row == 0 { "No.".to_string() } else { row.to_string() })
+///         .collect();
+///     grid.push_column(numbers);
+///
+///     println!("{}", grid.render(OutputFormat::Markdown)?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridDocument {
+    /// シートに適用した変換設定
+    config: ConversionConfig,
+
+    /// フィンガープリントに使用する設定のハッシュ値
+    config_hash: u64,
+
+    /// シートのグリッド
+    sheet: SheetGrid,
+}
+
+impl GridDocument {
+    pub(crate) fn new(config: ConversionConfig, config_hash: u64, sheet: SheetGrid) -> Self {
+        Self {
+            config,
+            config_hash,
+            sheet,
+        }
+    }
+
+    /// シート名を取得
+    pub fn name(&self) -> &str {
+        &self.sheet.info.name
+    }
+
+    /// グリッドを取得（内部用）
+    #[cfg(feature = "write")]
+    pub(crate) fn grid(&self) -> &LogicalGrid {
+        &self.sheet.grid
+    }
+
+    /// 行数を取得
+    pub fn rows(&self) -> usize {
+        self.sheet.grid.get_rows()
+    }
+
+    /// 列数を取得
+    pub fn cols(&self) -> usize {
+        self.sheet.grid.get_cols()
+    }
+
+    /// セルの表示文字列を取得
+    ///
+    /// # 引数
+    ///
+    /// * `row` - 行番号（0始まり）
+    /// * `col` - 列番号（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - セルの表示文字列（空のセルは空文字列）
+    /// * `None` - グリッド外の座標の場合
+    pub fn cell(&self, row: u32, col: u32) -> Option<&str> {
+        self.sheet
+            .grid
+            .get_cell(CellCoord::new(row, col))
+            .map(|cell| cell.content())
+    }
+
+    /// セルの値（フォーマット前）を取得
+    ///
+    /// # 引数
+    ///
+    /// * `row` - 行番号（0始まり）
+    /// * `col` - 列番号（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&CellValue)` - セルの値
+    /// * `None` - グリッド外の座標の場合
+    pub fn cell_value(&self, row: u32, col: u32) -> Option<&CellValue> {
+        self.sheet
+            .grid
+            .get_cell(CellCoord::new(row, col))
+            .map(|cell| &cell.value)
+    }
+
+    /// セルを文字列に置き換える
+    ///
+    /// 文字列はセルの値と同じくエスケープ（`|`、改行など）して表示文字列とし、
+    /// セルの値は`CellValue::String`になります。
+    /// グリッド外の座標の場合は、その座標を含むように空のセルでグリッドを拡張します。
+    ///
+    /// # 引数
+    ///
+    /// * `row` - 行番号（0始まり）
+    /// * `col` - 列番号（0始まり）
+    /// * `text` - 新しい文字列
+    pub fn set_cell(&mut self, row: u32, col: u32, text: &str) {
+        self.sheet
+            .grid
+            .set_cell(CellCoord::new(row, col), text_cell(text));
+    }
+
+    /// 末尾（最終列の右）に列を追加
+    ///
+    /// 各文字列を先頭行から順に配置します。文字列はセルの値と同じくエスケープされます。
+    /// 文字列が行数より少ない場合は残りの行を空のセルとし、多い場合は行を追加します。
+    ///
+    /// # 引数
+    ///
+    /// * `cells` - 追加する列の文字列（先頭行から順）
+    pub fn push_column<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let cells = cells.into_iter().map(|text| text_cell(text.as_ref()));
+        self.sheet.grid.push_column(cells.collect());
+    }
+
+    /// 指定した出力フォーマットで出力する
+    ///
+    /// 変換時と同じ設定（メタデータ、リンク一覧、フィンガープリントなど）で出力します。
+    /// メタデータを含める場合、文字体系とハッシュ値は編集後のセルから再計算します。
+    /// 何度でも出力でき、出力によってグリッドは変更されません。
+    ///
+    /// 変換時と異なる出力フォーマットを指定した場合、表示文字列が変換時のフォーマット結果のままの
+    /// セルは、元のセルの値から指定した出力フォーマットの設定（数値の出力方式、リンクやフォントの
+    /// 書式の構文など）で再フォーマットします。`set_cell()`などで編集したセルや、
+    /// テキスト変換などで変換後に内容が変わったセルは、表示文字列をそのまま出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `format` - 出力フォーマット
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - シートの出力
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn render(&self, format: OutputFormat) -> Result<String, XlsxToMdError> {
+        let mut config = self.config.clone();
+        config.output_format = format;
+
+        let mut sheet = self.sheet.clone();
+        if format != self.config.output_format {
+            reformat_cells(&mut sheet, &self.config, &config)?;
+        }
+        if config.include_metadata {
+            sheet.info.script = detect_script(sheet.grid.texts());
+            sheet.info.content_hash = Some(sheet.grid.content_hash());
+            sheet.info.tables = sheet.grid.table_hashes();
+        }
+        let content_bytes = sheet.grid.texts().map(str::len).sum();
+        render_sheet(&config, self.config_hash, &mut sheet, content_bytes)
+    }
+}
+
+/// 変換時のフォーマット結果のままのセルを、別の出力フォーマットの設定で再フォーマット（内部ヘルパー）
+///
+/// 元のセルデータを変換時の設定でフォーマットした結果が表示文字列と一致するセルのみを対象とし、
+/// 結合セルの子セル（`MergeStrategy::DataDuplication`で複製した値）は親セルの結果を引き継ぎます。
+fn reformat_cells(
+    sheet: &mut SheetGrid,
+    built: &ConversionConfig,
+    target: &ConversionConfig,
+) -> Result<(), XlsxToMdError> {
+    let formatter = CellFormatter::new();
+    // 再フォーマットしたセルの座標と、変更前の表示文字列
+    let mut reformatted = HashMap::new();
+    for (coord, cell) in sheet.grid.cells_mut() {
+        let Some(raw_cell) = cell.source.and_then(|source| sheet.raw_cells.get(&source)) else {
+            continue;
+        };
+        if formatter.format_cell(raw_cell, built, sheet.is_1904)? != cell.content() {
+            continue;
+        }
+        let content = formatter.format_cell(raw_cell, target, sheet.is_1904)?;
+        let previous = cell.content().to_string();
+        cell.set_content(content);
+        cell.number = formatter.raw_number(raw_cell, target);
+        reformatted.insert(coord, previous);
+    }
+
+    let mut children = Vec::new();
+    for row in 0..sheet.grid.get_rows() {
+        for (col, cell) in sheet.grid.get_row(row).iter().enumerate() {
+            let coord = CellCoord::new(row as u32, col as u32);
+            let Some(parent) = cell.merge_parent.filter(|_| cell.is_merged) else {
+                continue;
+            };
+            let (Some(previous), Some(parent_cell)) =
+                (reformatted.get(&parent), sheet.grid.get_cell(parent))
+            else {
+                continue;
+            };
+            if !reformatted.contains_key(&coord) && cell.content() == previous {
+                children.push((coord, parent_cell.content().to_string(), parent_cell.number));
+            }
+        }
+    }
+    for (coord, content, number) in children {
+        if let Some(cell) = sheet.grid.get_cell_mut(coord) {
+            cell.set_content(content);
+            cell.number = number;
+        }
+    }
+    Ok(())
+}
+
+/// 文字列をエスケープして文字列のセルを生成（内部ヘルパー）
+fn text_cell(text: &str) -> Cell {
+    let mut cell = Cell::new(CellFormatter::new().format_text(text));
+    cell.value = CellValue::String(text.to_string());
+    cell
+}
@@ -0,0 +1,102 @@
+//! Duplicates Module
+//!
+//! 内容がほぼ同一のシート（重複シート）の検出を提供するモジュール。
+//!
+//! 「Sheet1 (2)」のようにシートを複製したワークブックでは、同じ表が何度も出力されます。
+//! 各シートの行のシグネチャ（`LogicalGrid::row_signature()`）を比較し、
+//! 先に出力するシートと一致する行の割合が閾値以上のシートを重複シートとして検出します。
+
+/// 重複シートとみなす類似度の下限
+pub(crate) const SIMILARITY_THRESHOLD: f64 = 0.9;
+
+/// 2つのシグネチャの類似度（多重集合のJaccard係数）を計算
+///
+/// 一致する行の数を、いずれかのシートに含まれる行の数で割った値（0.0〜1.0）です。
+/// 両方が空の場合は0.0を返します（空のシートは重複として扱わない）。
+///
+/// # 引数
+///
+/// * `a` - 昇順に並んだシグネチャ
+/// * `b` - 昇順に並んだシグネチャ
+pub(crate) fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    let (mut i, mut j, mut common) = (0, 0, 0usize);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                common += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    let union = a.len() + b.len() - common;
+    if union == 0 {
+        0.0
+    } else {
+        common as f64 / union as f64
+    }
+}
+
+/// シートごとに、重複元のシートと類似度を求める
+///
+/// 各シートを、それより前にある重複でないシートと比較し、類似度が閾値以上のうち
+/// 最も類似度の高いシート（同じ値の場合は前のシート）を重複元とします。
+///
+/// # 引数
+///
+/// * `signatures` - シートごとのシグネチャ（出力順）
+///
+/// # 戻り値
+///
+/// シートごとの重複元のインデックスと類似度（重複でないシートは`None`）
+pub(crate) fn find_duplicates(signatures: &[&[u64]]) -> Vec<Option<(usize, f64)>> {
+    let mut duplicates: Vec<Option<(usize, f64)>> = Vec::with_capacity(signatures.len());
+    for (idx, signature) in signatures.iter().enumerate() {
+        let mut best: Option<(usize, f64)> = None;
+        for original in (0..idx).filter(|&i| duplicates[i].is_none()) {
+            let score = similarity(signatures[original], signature);
+            if score >= SIMILARITY_THRESHOLD && best.is_none_or(|(_, s)| score > s) {
+                best = Some((original, score));
+            }
+        }
+        duplicates.push(best);
+    }
+    duplicates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity(&[1, 2, 3], &[1, 2, 3]), 1.0);
+        assert_eq!(similarity(&[1, 2, 3], &[4, 5, 6]), 0.0);
+        // 多重集合として比較する
+        assert_eq!(similarity(&[1, 1, 2], &[1, 2]), 2.0 / 3.0);
+        assert_eq!(similarity(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let original: Vec<u64> = (0..20).collect();
+        // 1行だけ異なる複製（19/21 ≈ 0.905）
+        let mut copy = original.clone();
+        copy[19] = 100;
+        let other: Vec<u64> = (50..70).collect();
+
+        let found = find_duplicates(&[&original, &other, &copy, &copy, &[], &[]]);
+        assert_eq!(found[0], None);
+        assert_eq!(found[1], None);
+        assert_eq!(found[2].map(|(i, _)| i), Some(0));
+        assert!(found[2].unwrap().1 > SIMILARITY_THRESHOLD);
+        // 重複シートは重複元にならない
+        assert_eq!(found[3].map(|(i, _)| i), Some(0));
+        // 空のシートは重複として扱わない
+        assert_eq!(found[4], None);
+        assert_eq!(found[5], None);
+    }
+}
@@ -0,0 +1,207 @@
+//! Environment Module
+//!
+//! 環境変数から`ConverterBuilder`のデフォルト設定を読み込むモジュール。
+//! 対応する環境変数の一覧は`ConverterBuilder::from_env()`を参照してください。
+
+use crate::api::{
+    normalize_name, DateFormat, FormulaMode, JsonCellMode, MergeStrategy, OutputFormat, Preset,
+};
+use crate::builder::ConverterBuilder;
+use crate::error::{ConfigError, XlsxToMdError};
+use std::str::FromStr;
+
+/// プリセット
+const ENV_PRESET: &str = "XLSXZERO_PRESET";
+/// 出力フォーマット
+const ENV_FORMAT: &str = "XLSXZERO_FORMAT";
+/// セル結合戦略
+const ENV_MERGE_STRATEGY: &str = "XLSXZERO_MERGE_STRATEGY";
+/// 日付形式
+const ENV_DATE_FORMAT: &str = "XLSXZERO_DATE_FORMAT";
+/// 数式出力モード
+const ENV_FORMULA_MODE: &str = "XLSXZERO_FORMULA_MODE";
+/// 非表示要素を含めるか
+const ENV_INCLUDE_HIDDEN: &str = "XLSXZERO_INCLUDE_HIDDEN";
+/// メタデータを含めるか
+const ENV_INCLUDE_METADATA: &str = "XLSXZERO_INCLUDE_METADATA";
+/// JSONのセル表現方式
+const ENV_JSON_CELL_MODE: &str = "XLSXZERO_JSON_CELL_MODE";
+/// 入力ファイルの最大サイズ（バイト）
+const ENV_MAX_INPUT_SIZE: &str = "XLSXZERO_MAX_INPUT_SIZE";
+
+/// 環境変数の参照関数からビルダーを生成
+///
+/// # 引数
+///
+/// * `lookup` - 環境変数名から値を取得する関数（未設定の場合は`None`）
+///
+/// # 戻り値
+///
+/// * `Ok(ConverterBuilder)` - すべての値が有効な場合
+/// * `Err(XlsxToMdError::Config)` - 不正な値がある場合
+pub(crate) fn builder_from_lookup<F>(lookup: F) -> Result<ConverterBuilder, XlsxToMdError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    // 空文字列は未設定として扱う
+    let get = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+
+    // プリセットを基準に、個別の環境変数で上書きする
+    let mut builder = match get(ENV_PRESET) {
+        Some(value) => ConverterBuilder::preset(parse::<Preset>(ENV_PRESET, &value)?),
+        None => ConverterBuilder::new(),
+    };
+
+    if let Some(value) = get(ENV_FORMAT) {
+        // 登録された独自の出力フォーマットの名前も受け付ける
+        builder = match crate::plugin::find_format(&value) {
+            Some(_) => builder.with_format_name(&value),
+            None => builder.with_output_format(parse::<OutputFormat>(ENV_FORMAT, &value)?),
+        };
+    }
+
+    if let Some(value) = get(ENV_MERGE_STRATEGY) {
+        builder = builder.with_merge_strategy(parse::<MergeStrategy>(ENV_MERGE_STRATEGY, &value)?);
+    }
+
+    if let Some(value) = get(ENV_DATE_FORMAT) {
+        // iso8601以外はカスタム形式文字列として扱う（妥当性はbuild()時に検証）
+        builder = builder.with_date_format(parse::<DateFormat>(ENV_DATE_FORMAT, &value)?);
+    }
+
+    if let Some(value) = get(ENV_FORMULA_MODE) {
+        builder = builder.with_formula_mode(parse::<FormulaMode>(ENV_FORMULA_MODE, &value)?);
+    }
+
+    if let Some(value) = get(ENV_INCLUDE_HIDDEN) {
+        builder = builder.include_hidden(parse_bool(ENV_INCLUDE_HIDDEN, &value)?);
+    }
+
+    if let Some(value) = get(ENV_INCLUDE_METADATA) {
+        builder = builder.include_metadata(parse_bool(ENV_INCLUDE_METADATA, &value)?);
+    }
+
+    if let Some(value) = get(ENV_JSON_CELL_MODE) {
+        builder = builder.with_json_cell_mode(parse::<JsonCellMode>(ENV_JSON_CELL_MODE, &value)?);
+    }
+
+    if let Some(value) = get(ENV_MAX_INPUT_SIZE) {
+        let bytes = value
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| invalid_value(ENV_MAX_INPUT_SIZE, &value))?;
+        builder = builder.with_max_input_size(bytes);
+    }
+
+    Ok(builder)
+}
+
+/// 設定の列挙型を文字列名からパース
+fn parse<T: FromStr>(name: &'static str, value: &str) -> Result<T, XlsxToMdError> {
+    value.parse().map_err(|_| invalid_value(name, value))
+}
+
+/// 真偽値をパース
+fn parse_bool(name: &'static str, value: &str) -> Result<bool, XlsxToMdError> {
+    match normalize_name(value).as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(invalid_value(name, value)),
+    }
+}
+
+/// 不正な値のエラーを生成
+fn invalid_value(name: &'static str, value: &str) -> XlsxToMdError {
+    XlsxToMdError::Config(ConfigError::InvalidEnvVar {
+        name,
+        value: value.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn builder_from(vars: &[(&str, &str)]) -> Result<ConverterBuilder, XlsxToMdError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        builder_from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_builder_from_lookup_empty() {
+        let builder = builder_from(&[]).unwrap();
+        let config = builder.config();
+        assert_eq!(config.output_format, OutputFormat::Markdown);
+        assert!(!config.include_hidden);
+    }
+
+    #[test]
+    fn test_builder_from_lookup_values() {
+        let builder = builder_from(&[
+            (ENV_FORMAT, "JSON"),
+            (ENV_MERGE_STRATEGY, "html-fallback"),
+            (ENV_DATE_FORMAT, "%Y/%m/%d"),
+            (ENV_FORMULA_MODE, "formula"),
+            (ENV_INCLUDE_HIDDEN, "1"),
+            (ENV_INCLUDE_METADATA, "yes"),
+            (ENV_JSON_CELL_MODE, "typed"),
+            (ENV_MAX_INPUT_SIZE, "1048576"),
+        ])
+        .unwrap();
+        let config = builder.config();
+        assert_eq!(config.output_format, OutputFormat::Json);
+        assert_eq!(config.merge_strategy, MergeStrategy::HtmlFallback);
+        assert_eq!(
+            config.date_format,
+            DateFormat::Custom("%Y/%m/%d".to_string())
+        );
+        assert_eq!(config.formula_mode, FormulaMode::Formula);
+        assert!(config.include_hidden);
+        assert!(config.include_metadata);
+        assert_eq!(config.json_cell_mode, JsonCellMode::Typed);
+        assert_eq!(config.security.max_input_file_size, 1_048_576);
+
+        let builder = builder_from(&[(ENV_FORMAT, "xml")]).unwrap();
+        assert_eq!(builder.config().output_format, OutputFormat::Xml);
+    }
+
+    #[test]
+    fn test_builder_from_lookup_preset_layering() {
+        // プリセットを基準に個別の環境変数で上書き
+        let builder =
+            builder_from(&[(ENV_PRESET, "machine-readable"), (ENV_FORMAT, "csv")]).unwrap();
+        let config = builder.config();
+        assert_eq!(config.output_format, OutputFormat::Csv);
+        assert!(config.include_metadata);
+
+        // プログラムでの設定は環境変数より優先
+        let builder = builder.with_output_format(OutputFormat::Html);
+        assert_eq!(builder.config().output_format, OutputFormat::Html);
+    }
+
+    #[test]
+    fn test_builder_from_lookup_invalid() {
+        let result = builder_from(&[(ENV_FORMAT, "yaml")]);
+        match result {
+            Err(XlsxToMdError::Config(ConfigError::InvalidEnvVar { name, value })) => {
+                assert_eq!(name, ENV_FORMAT);
+                assert_eq!(value, "yaml");
+            }
+            _ => panic!("Expected Config error"),
+        }
+
+        assert!(builder_from(&[(ENV_INCLUDE_HIDDEN, "maybe")]).is_err());
+        assert!(builder_from(&[(ENV_MAX_INPUT_SIZE, "10MB")]).is_err());
+        assert!(builder_from(&[(ENV_PRESET, "fast")]).is_err());
+    }
+
+    #[test]
+    fn test_builder_from_lookup_ignores_empty_values() {
+        let builder = builder_from(&[(ENV_FORMAT, ""), (ENV_MAX_INPUT_SIZE, "  ")]).unwrap();
+        assert_eq!(builder.config().output_format, OutputFormat::Markdown);
+    }
+}
@@ -15,7 +15,16 @@ use thiserror::Error;
 /// - `Io`: I/O操作中に発生したエラー（ファイル読み込み失敗など）
 /// - `Parse`: Excelファイルの解析中に発生したエラー（calamine由来）
 /// - `Config`: 設定の検証に失敗したエラー（無効な範囲指定など）
+/// - `Zip`・`Xml`: XLSXファイルの構造（ZIPアーカイブ、XMLパーツ）が不正なエラー
+/// - `SheetNotFound`・`SheetIndexOutOfRange`: 変換対象のシートが見つからないエラー
+/// - `Format`: セルの値のフォーマットに失敗したエラー
 /// - `UnsupportedFeature`: サポートされていない機能が検出されたエラー
+/// - `Write`・`InvalidWriteInput`: XLSXファイルの書き出しに失敗したエラー（`write`フィーチャー）
+/// - `Validation`: 列の値の検証に違反したエラー（`ValidationPolicy::Fail`の場合）
+///
+/// 今後のバージョンでバリアントが追加される可能性があるため、`#[non_exhaustive]`です。
+/// `match`では`_`アームを用意するか、`kind()`・`sheet()`・`cell()`などのアクセサで
+/// エラーの情報を取得してください。
 ///
 /// # 使用例
 ///
@@ -30,6 +39,7 @@ use thiserror::Error;
 /// }
 /// ```
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum XlsxToMdError {
     /// I/O操作中に発生したエラー
     ///
@@ -59,8 +69,25 @@ pub enum XlsxToMdError {
     /// ZIPアーカイブの解析エラー
     ///
     /// XLSXファイル（ZIPアーカイブ）の解析中に発生したエラーです。
-    #[error("ZIP archive error: {0}")]
-    Zip(String),
+    #[error("ZIP archive error{}: {message}", part_suffix(.part))]
+    Zip {
+        /// エラーが発生したパーツ名（例: `xl/styles.xml`）。アーカイブ全体のエラーの場合は`None`
+        part: Option<String>,
+        /// エラーの詳細メッセージ
+        message: String,
+    },
+
+    /// XMLパーツの解析エラー
+    ///
+    /// XLSXファイル内のXMLパーツ（`xl/styles.xml`、`xl/worksheets/sheet1.xml`など）が
+    /// 不正な場合に発生します。
+    #[error("XML error in '{part}': {message}")]
+    Xml {
+        /// エラーが発生したパーツ名
+        part: String,
+        /// エラーの詳細メッセージ
+        message: String,
+    },
 
     /// 数値の解析エラー
     ///
@@ -72,26 +99,133 @@ pub enum XlsxToMdError {
     ///
     /// `ConverterBuilder::build()`時に設定を検証し、無効な設定が検出された
     /// 場合に発生します。例えば、セル範囲の開始座標が終了座標より大きい場合や、
-    /// カスタム日付形式が不正な場合などです。
+    /// カスタム日付形式が不正な場合などです。内容は`ConfigError`で分類されます。
     ///
     /// # 例
     ///
     /// ```rust,no_run
-    /// use xlsxzero::{ConverterBuilder, XlsxToMdError};
+    /// use xlsxzero::{ConfigError, ConverterBuilder, XlsxToMdError};
     ///
     /// let result = ConverterBuilder::new()
     ///     .with_range((10, 0), (0, 0))  // 無効な範囲
     ///     .build();
     ///
     /// match result {
-    ///     Err(XlsxToMdError::Config(msg)) => {
-    ///         println!("設定エラー: {}", msg);
+    ///     Err(XlsxToMdError::Config(ConfigError::InvalidValue { option, reason, .. })) => {
+    ///         println!("設定エラー（{}）: {}", option, reason);
     ///     }
     ///     _ => {}
     /// }
     /// ```
     #[error("Configuration error: {0}")]
-    Config(String),
+    Config(#[from] ConfigError),
+
+    /// 指定した名前のシートが見つからないエラー
+    ///
+    /// `SheetSelector::Name`・`SheetSelector::Names`で存在しないシート名を指定した場合に
+    /// 発生します。
+    #[error("Sheet '{sheet}' not found")]
+    SheetNotFound {
+        /// 指定されたシート名
+        sheet: String,
+    },
+
+    /// シートのインデックスが範囲外のエラー
+    ///
+    /// `SheetSelector::Index`・`SheetSelector::Indices`でシート数以上のインデックスを
+    /// 指定した場合に発生します。
+    #[error("Sheet index {index} is out of range (total sheets: {count})")]
+    SheetIndexOutOfRange {
+        /// 指定されたインデックス（0始まり）
+        index: usize,
+        /// ワークブックのシート数
+        count: usize,
+    },
+
+    /// XLSX以外のファイル形式のエラー
+    ///
+    /// XLSB、ODSなど、XLSX・XLS以外の形式のファイルが入力された場合に発生します。
+    #[error("Unsupported file format: {format} (only XLSX and XLS are supported)")]
+    UnsupportedFormat {
+        /// 検出されたファイル形式（例: `ODS`）
+        format: String,
+    },
+
+    /// セルの値のフォーマットに失敗したエラー
+    ///
+    /// 日付のシリアル値が範囲外の場合など、セルの値を文字列に変換できない場合に発生します。
+    #[error("Failed to format cell{}: {message}", location_suffix(.sheet, .cell))]
+    Format {
+        /// エラーが発生したシート名
+        sheet: Option<String>,
+        /// エラーが発生したセルの座標（A1記法）
+        cell: Option<String>,
+        /// エラーの詳細メッセージ
+        message: String,
+    },
+
+    /// テキスト変換の結果の件数が入力と一致しないエラー
+    ///
+    /// `ConverterBuilder::with_text_transformer`で登録した変換処理が、
+    /// 入力と異なる件数のテキストを返した場合に発生します。
+    ///
+    /// 変換処理は翻訳サービスなど外部の処理であることが多く、入力ファイルに起因しないため、
+    /// `kind()`は`ErrorKind::Internal`（再試行の対象）を返します。
+    #[error("Text transformer returned {actual} texts for {expected} inputs")]
+    TextTransformer {
+        /// 入力したテキストの件数
+        expected: usize,
+        /// 変換処理が返したテキストの件数
+        actual: usize,
+    },
+
+    /// XLSXファイルの書き出しに失敗したエラー
+    ///
+    /// `write`フィーチャーの`writer`モジュールで、XLSXファイル（ZIPアーカイブ）の生成や
+    /// 書き込みに失敗した場合に発生します。入力の内容に起因するエラーは`InvalidWriteInput`です。
+    #[error("Failed to write XLSX: {0}")]
+    Write(String),
+
+    /// XLSXファイルとして書き出せない入力のエラー
+    ///
+    /// `write`フィーチャーの`writer`モジュールで、Excelのシート名として使用できない名前
+    /// （31文字を超える、`[]:*?/\`を含むなど）や、Excelの上限を超える行数・列数・文字列、
+    /// 不正なJSONが入力された場合に発生します。
+    #[error("Cannot write XLSX{}: {message}", location_suffix(.sheet, &None))]
+    InvalidWriteInput {
+        /// エラーが発生したシート名（シート名が指定されていない場合は`None`）
+        sheet: Option<String>,
+        /// エラーの詳細メッセージ
+        message: String,
+    },
+
+    /// リモートのワークブックのダウンロードに失敗したエラー
+    ///
+    /// `fetch`フィーチャーの`Converter::convert_url()`で、接続やTLSの失敗、
+    /// 成功以外のHTTPステータスが返された場合に発生します。
+    /// サイズの上限を超えた場合は`SecurityViolation`になります。
+    #[error("Failed to fetch '{url}': {message}")]
+    Fetch {
+        /// ダウンロードしようとしたURL
+        url: String,
+        /// HTTPステータスコード（応答を受信できなかった場合は`None`）
+        status: Option<u16>,
+        /// エラーの内容
+        message: String,
+    },
+
+    /// 列の値の検証に違反したエラー
+    ///
+    /// `ConverterBuilder::with_validations()`で指定した検証に違反したシートがあり、
+    /// `ValidationPolicy::Fail`が指定されている場合に発生します。
+    /// メッセージには、シートのすべての違反（`; `区切り）が含まれます。
+    #[error("Validation failed at sheet '{sheet}': {message}")]
+    Validation {
+        /// 違反が検出されたシート名
+        sheet: String,
+        /// 違反の内容
+        message: String,
+    },
 
     /// サポートされていない機能が検出されたエラー
     ///
@@ -126,19 +260,492 @@ pub enum XlsxToMdError {
     /// セキュリティ制限に違反したエラー
     ///
     /// ZIP bomb攻撃、パストラバーサル攻撃、ファイルサイズ制限などの
-    /// セキュリティ制限に違反した場合に発生します。
+    /// セキュリティ制限に違反した場合に発生します。違反した制限と観測した値は
+    /// `SecurityLimit`で表されます。
     ///
     /// # 例
     ///
     /// ```rust,no_run
-    /// use xlsxzero::XlsxToMdError;
+    /// use xlsxzero::{SecurityLimit, XlsxToMdError};
     ///
-    /// let error = XlsxToMdError::SecurityViolation(
-    ///     "File size exceeds maximum allowed size".to_string()
-    /// );
+    /// let error = XlsxToMdError::SecurityViolation(SecurityLimit::InputSize {
+    ///     size: 200 * 1024 * 1024,
+    ///     max: 100 * 1024 * 1024,
+    /// });
     /// ```
     #[error("Security violation: {0}")]
-    SecurityViolation(String),
+    SecurityViolation(#[from] SecurityLimit),
+
+    /// 変換処理中に発生した内部エラー
+    ///
+    /// `Converter::try_convert`などの`try_`系メソッドが、変換処理中のパニックを
+    /// 捕捉した場合に使用されます。メッセージにはパニックの内容が含まれます。
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+/// 設定の検証に失敗したエラーの内容
+///
+/// `XlsxToMdError::Config`に含まれ、どのオプションのどの値が不正かを
+/// エラーメッセージの文字列照合なしに判別するために使用します。
+/// `option`・`method`には、対応する`ConverterBuilder`のメソッド名（例: `with_range`）が入ります。
+///
+/// 今後のバージョンでバリアントが追加される可能性があるため、`#[non_exhaustive]`です。
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// オプションの値が不正
+    #[error("Invalid value {value:?} for {option}: {reason}")]
+    InvalidValue {
+        /// オプションを設定する`ConverterBuilder`のメソッド名
+        option: &'static str,
+        /// 指定された値
+        value: String,
+        /// 値が不正な理由
+        reason: String,
+    },
+
+    /// 名前で指定する値（列挙型の名前、出力フォーマット名など）が選択肢にない
+    #[error("Invalid {kind} '{value}' (expected one of: {})", .expected.join(", "))]
+    UnknownName {
+        /// 値の種類（例: `OutputFormat`）
+        kind: &'static str,
+        /// 指定された名前
+        value: String,
+        /// 指定できる名前の一覧
+        expected: Vec<String>,
+    },
+
+    /// A1形式として解析できないセル参照
+    #[error("Invalid A1 reference '{reference}' (expected e.g. 'B2', 'B2:F100', 'A:C' or '1:5')")]
+    InvalidReference {
+        /// 指定された参照
+        reference: String,
+    },
+
+    /// 正規表現・計算列の式の構文が不正
+    #[error("Cannot parse {text:?} for {option}: {message}")]
+    InvalidSyntax {
+        /// 式を設定する`ConverterBuilder`のメソッド名
+        option: &'static str,
+        /// 指定された式
+        text: String,
+        /// 構文エラーの内容
+        message: String,
+    },
+
+    /// 名前付き範囲が見つからない、または単一のセル範囲を参照していない
+    #[error("Named range '{name}' not found or does not refer to a single cell range")]
+    NamedRangeNotFound {
+        /// 指定された名前
+        name: String,
+    },
+
+    /// 環境変数の値が不正
+    #[error("Invalid value for environment variable {name}: '{value}'")]
+    InvalidEnvVar {
+        /// 環境変数名
+        name: &'static str,
+        /// 環境変数の値
+        value: String,
+    },
+
+    /// 登録しようとした出力フォーマットの名前が組み込み・登録済みのフォーマットと重複
+    #[error("Format '{name}' is already defined")]
+    DuplicateFormat {
+        /// 登録しようとしたフォーマットの名前
+        name: String,
+    },
+}
+
+/// 違反したセキュリティ制限
+///
+/// `XlsxToMdError::SecurityViolation`に含まれ、違反した制限（`SecurityLimits`の項目など）と
+/// 観測した値を表します。展開・受信を上限を超えた時点で打ち切る場合、観測した値は
+/// 実際のサイズより小さいことがあります（上限に1を加えた値など）。
+///
+/// 今後のバージョンでバリアントが追加される可能性があるため、`#[non_exhaustive]`です。
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SecurityLimit {
+    /// 入力ファイルのサイズが`max_input_file_size`を超えた
+    #[error("Input file size exceeds maximum: {size} bytes (max: {max} bytes)")]
+    InputSize {
+        /// 入力ファイルのサイズ（バイト）
+        size: u64,
+        /// 上限（バイト）
+        max: u64,
+    },
+
+    /// ダウンロードするファイルのサイズが`max_input_file_size`を超えた（`fetch`フィーチャー）
+    #[error("Remote file size exceeds maximum: {size} bytes (max: {max} bytes)")]
+    RemoteSize {
+        /// 申告された、または受信したサイズ（バイト）
+        size: u64,
+        /// 上限（バイト）
+        max: u64,
+    },
+
+    /// ZIPアーカイブのファイル数が`max_file_count`を超えた
+    #[error("ZIP archive contains too many files: {count} (max: {max})")]
+    FileCount {
+        /// ファイル数
+        count: usize,
+        /// 上限
+        max: usize,
+    },
+
+    /// ZIPアーカイブのファイルパスが不正（パストラバーサル、絶対パスなど）
+    #[error("Invalid ZIP path '{path}': {reason}")]
+    InvalidPath {
+        /// ファイルパス
+        path: String,
+        /// 不正な理由
+        reason: &'static str,
+    },
+
+    /// 単一ファイルの展開後のサイズが`max_file_size`を超えた
+    #[error("File '{part}' exceeds maximum size: {size} bytes (max: {max} bytes)")]
+    FileSize {
+        /// ファイル名
+        part: String,
+        /// 宣言された、または展開したサイズ（バイト）
+        size: u64,
+        /// 上限（バイト）
+        max: u64,
+    },
+
+    /// ファイルの圧縮率が`max_compression_ratio`を超えた
+    #[error(
+        "File '{part}' exceeds maximum compression ratio: {size} bytes inflated from {compressed_size} bytes (max ratio: {max_ratio})"
+    )]
+    CompressionRatio {
+        /// ファイル名
+        part: String,
+        /// 宣言された、または展開したサイズ（バイト）
+        size: u64,
+        /// 圧縮後のサイズ（バイト）
+        compressed_size: u64,
+        /// 圧縮率の上限
+        max_ratio: u64,
+    },
+
+    /// 展開後の合計サイズが`max_decompressed_size`を超えた
+    #[error("Total decompressed size exceeds maximum: {size} bytes (max: {max} bytes)")]
+    DecompressedSize {
+        /// 展開後の合計サイズ（バイト）
+        size: u64,
+        /// 上限（バイト）
+        max: u64,
+    },
+
+    /// ファイルが宣言されたサイズを超えて展開された
+    #[error("File '{part}' inflates beyond its declared size: {declared_size} bytes")]
+    DeclaredSize {
+        /// ファイル名
+        part: String,
+        /// 宣言されたサイズ（バイト）
+        declared_size: u64,
+    },
+
+    /// 出力のサイズが`ConverterBuilder::with_max_output_size`の上限を超えた
+    #[error("Output size exceeds maximum: {size} bytes (max: {max} bytes)")]
+    OutputSize {
+        /// 出力のサイズ（バイト）
+        size: u64,
+        /// 上限（バイト）
+        max: u64,
+    },
+
+    /// XMLパーツのイベント数（開始・終了タグ、テキストなど）が上限（1億）を超えた
+    #[error("XML part '{part}' exceeds maximum event count (max: {max})")]
+    XmlEvents {
+        /// パーツ名
+        part: String,
+        /// 上限
+        max: u64,
+    },
+
+    /// XMLパーツの要素の入れ子の深さが上限（256）を超えた
+    #[error("XML part '{part}' exceeds maximum element nesting depth: {depth} (max: {max})")]
+    XmlDepth {
+        /// パーツ名
+        part: String,
+        /// 入れ子の深さ
+        depth: usize,
+        /// 上限
+        max: usize,
+    },
+
+    /// XMLパーツの1つの要素の属性数が上限（512）を超えた
+    #[error(
+        "XML part '{part}' has an element exceeding maximum attribute count: {count} (max: {max})"
+    )]
+    XmlAttributes {
+        /// パーツ名
+        part: String,
+        /// 属性数
+        count: usize,
+        /// 上限
+        max: usize,
+    },
+
+    /// XMLパーツにDOCTYPE宣言（DTD・エンティティ）が含まれる
+    #[error(
+        "XML part '{part}' contains a DOCTYPE declaration (DTDs and entities are not allowed)"
+    )]
+    XmlDoctype {
+        /// パーツ名
+        part: String,
+    },
+}
+
+/// エラーの分類
+///
+/// `XlsxToMdError::kind()`で取得し、ジョブキューなどで再試行・デッドレター・アラートを
+/// エラーメッセージの文字列照合なしに自動で判断するために使用します。
+/// 同じ入力と設定に対しては、常に同じ分類が返されます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// 入力ファイルまたは設定が不正
+    ///
+    /// 破損したファイル、XLSX以外の形式、不正なXML、無効な設定値などです。
+    /// 同じ入力で再試行しても成功しないため、デッドレターとして扱うことを推奨します。
+    InvalidInput,
+
+    /// リソース制限・セキュリティ制限に違反
+    ///
+    /// 入力サイズ、展開後サイズ、ファイル数などの上限超過や、
+    /// パストラバーサルなどのセキュリティ制限違反です。
+    /// 制限値を見直さない限り再試行しても成功しません。
+    ResourceLimit,
+
+    /// 内部エラー
+    ///
+    /// 出力先への書き込み失敗など、入力ファイル以外に起因するI/Oエラー、
+    /// テキスト変換（`with_text_transformer`）の失敗、
+    /// `try_`系メソッドが捕捉した変換処理中のパニックです。
+    /// 一時的な障害の可能性があるため、再試行またはアラートの対象です。
+    Internal,
+
+    /// サポートされていない機能
+    ///
+    /// 入力自体は正常ですが、現在のバージョンでは変換できない機能を含みます。
+    Unsupported,
+}
+
+impl XlsxToMdError {
+    /// エラーの分類を取得
+    ///
+    /// # 戻り値
+    ///
+    /// エラーの分類（`ErrorKind`）
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::{ConverterBuilder, ErrorKind};
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// let input = File::open("example.xlsx")?;
+    ///
+    /// match converter.convert_to_string(input) {
+    ///     Ok(markdown) => println!("{}", markdown),
+    ///     Err(e) => match e.kind() {
+    ///         ErrorKind::Internal => { /* 再試行 */ }
+    ///         ErrorKind::InvalidInput | ErrorKind::ResourceLimit => { /* デッドレター */ }
+    ///         _ => { /* アラート */ }
+    ///     },
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            XlsxToMdError::Io(e) => match e.kind() {
+                // 入力データの破損・途中切れによるI/Oエラー
+                std::io::ErrorKind::InvalidData | std::io::ErrorKind::UnexpectedEof => {
+                    ErrorKind::InvalidInput
+                }
+                _ => ErrorKind::Internal,
+            },
+            XlsxToMdError::Parse(_)
+            | XlsxToMdError::Utf8(_)
+            | XlsxToMdError::Zip { .. }
+            | XlsxToMdError::Xml { .. }
+            | XlsxToMdError::ParseInt(_)
+            | XlsxToMdError::Config(_)
+            | XlsxToMdError::SheetNotFound { .. }
+            | XlsxToMdError::SheetIndexOutOfRange { .. }
+            | XlsxToMdError::Format { .. }
+            | XlsxToMdError::InvalidWriteInput { .. }
+            | XlsxToMdError::Validation { .. } => ErrorKind::InvalidInput,
+            // 外部の変換処理や出力先の障害は、再試行で成功しうる
+            XlsxToMdError::TextTransformer { .. } | XlsxToMdError::Write(_) => ErrorKind::Internal,
+            // クライアントエラー（404など）は再試行しても成功しない
+            // ただし、タイムアウト（408）とレート制限（429）は再試行で成功しうる
+            XlsxToMdError::Fetch {
+                status: Some(408 | 429),
+                ..
+            } => ErrorKind::Internal,
+            XlsxToMdError::Fetch {
+                status: Some(400..=499),
+                ..
+            } => ErrorKind::InvalidInput,
+            XlsxToMdError::Fetch { .. } => ErrorKind::Internal,
+            XlsxToMdError::UnsupportedFeature { .. } | XlsxToMdError::UnsupportedFormat { .. } => {
+                ErrorKind::Unsupported
+            }
+            XlsxToMdError::SecurityViolation(_) => ErrorKind::ResourceLimit,
+            XlsxToMdError::Internal(_) => ErrorKind::Internal,
+        }
+    }
+
+    /// エラーが発生したシート名を取得
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - シートに関連するエラーの場合、シート名
+    /// * `None` - シートに関連しないエラー、またはシートが特定できない場合
+    pub fn sheet(&self) -> Option<&str> {
+        match self {
+            XlsxToMdError::UnsupportedFeature { sheet, .. }
+            | XlsxToMdError::SheetNotFound { sheet }
+            | XlsxToMdError::Validation { sheet, .. } => Some(sheet),
+            XlsxToMdError::Format { sheet, .. }
+            | XlsxToMdError::InvalidWriteInput { sheet, .. } => sheet.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// エラーが発生したセルの座標（A1記法）を取得
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - セルに関連するエラーの場合、セルの座標（例: `B5`）
+    /// * `None` - セルに関連しないエラー、またはセルが特定できない場合
+    ///
+    /// # 使用例
+    ///
+    /// ```rust,no_run
+    /// use xlsxzero::ConverterBuilder;
+    /// use std::fs::File;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let converter = ConverterBuilder::new().build()?;
+    /// if let Err(e) = converter.convert_to_string(File::open("example.xlsx")?) {
+    ///     if let (Some(sheet), Some(cell)) = (e.sheet(), e.cell()) {
+    ///         eprintln!("{}!{}: {}", sheet, cell, e);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cell(&self) -> Option<&str> {
+        match self {
+            XlsxToMdError::UnsupportedFeature { cell, .. } => Some(cell),
+            XlsxToMdError::Format { cell, .. } => cell.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// エラーが発生したXLSXファイル内のパーツ名を取得
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - パーツが特定できる場合、パーツ名（例: `xl/styles.xml`）
+    /// * `None` - パーツに関連しないエラーの場合
+    pub fn part(&self) -> Option<&str> {
+        match self {
+            XlsxToMdError::Zip { part, .. } => part.as_deref(),
+            XlsxToMdError::Xml { part, .. } => Some(part),
+            _ => None,
+        }
+    }
+
+    /// ZIPアーカイブのエラーを生成（内部ヘルパー）
+    pub(crate) fn zip(error: impl std::fmt::Display) -> Self {
+        XlsxToMdError::Zip {
+            part: None,
+            message: error.to_string(),
+        }
+    }
+
+    /// パーツ名を指定してZIPアーカイブのエラーを生成（内部ヘルパー）
+    pub(crate) fn zip_part(part: &str, error: impl std::fmt::Display) -> Self {
+        XlsxToMdError::Zip {
+            part: Some(part.to_string()),
+            message: error.to_string(),
+        }
+    }
+
+    /// XMLパーツの解析エラーを生成（内部ヘルパー）
+    pub(crate) fn xml(part: &str, error: impl std::fmt::Display) -> Self {
+        XlsxToMdError::Xml {
+            part: part.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    /// JSONの出力エラーを変換（内部ヘルパー）
+    ///
+    /// 出力先への書き込みの失敗は`Io`、それ以外（シリアライズできない値など）は
+    /// 入力に起因しないため`Internal`になります。
+    pub(crate) fn json(error: serde_json::Error) -> Self {
+        if error.is_io() {
+            XlsxToMdError::Io(error.into())
+        } else {
+            XlsxToMdError::Internal(format!("JSON error: {}", error))
+        }
+    }
+
+    /// 位置が未確定のフォーマットエラーを生成（内部ヘルパー）
+    pub(crate) fn format(message: impl Into<String>) -> Self {
+        XlsxToMdError::Format {
+            sheet: None,
+            cell: None,
+            message: message.into(),
+        }
+    }
+
+    /// フォーマットエラーにシート名とセルの座標を設定（内部ヘルパー）
+    ///
+    /// 他のエラー、または位置が設定済みのフォーマットエラーはそのまま返します。
+    pub(crate) fn at_cell(self, sheet_name: &str, cell_ref: impl FnOnce() -> String) -> Self {
+        match self {
+            XlsxToMdError::Format {
+                sheet: None,
+                cell: None,
+                message,
+            } => XlsxToMdError::Format {
+                sheet: Some(sheet_name.to_string()),
+                cell: Some(cell_ref()),
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+/// パーツ名をエラーメッセージに付加する文字列（内部ヘルパー）
+fn part_suffix(part: &Option<String>) -> String {
+    match part {
+        Some(part) => format!(" in '{}'", part),
+        None => String::new(),
+    }
+}
+
+/// シート名とセルの座標をエラーメッセージに付加する文字列（内部ヘルパー）
+fn location_suffix(sheet: &Option<String>, cell: &Option<String>) -> String {
+    match (sheet, cell) {
+        (Some(sheet), Some(cell)) => format!(" at sheet '{}', cell {}", sheet, cell),
+        (Some(sheet), None) => format!(" at sheet '{}'", sheet),
+        (None, Some(cell)) => format!(" at cell {}", cell),
+        (None, None) => String::new(),
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +753,115 @@ mod tests {
     use super::*;
     use std::io;
 
+    // ErrorKindのテスト
+    #[test]
+    fn test_error_kind() {
+        let error: XlsxToMdError = io::Error::new(io::ErrorKind::BrokenPipe, "pipe").into();
+        assert_eq!(error.kind(), ErrorKind::Internal);
+
+        let error: XlsxToMdError = io::Error::new(io::ErrorKind::UnexpectedEof, "eof").into();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error: XlsxToMdError = calamine::Error::Msg("Corrupted file").into();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error = XlsxToMdError::zip("invalid archive");
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error = XlsxToMdError::xml("xl/styles.xml", "unexpected EOF");
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error = XlsxToMdError::SheetNotFound {
+            sheet: "Missing".to_string(),
+        };
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error = XlsxToMdError::UnsupportedFormat {
+            format: "ODS".to_string(),
+        };
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+
+        let error = XlsxToMdError::Config(ConfigError::InvalidReference {
+            reference: "B2:".to_string(),
+        });
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+        let error = XlsxToMdError::SecurityViolation(SecurityLimit::InputSize { size: 2, max: 1 });
+        assert_eq!(error.kind(), ErrorKind::ResourceLimit);
+
+        let fetch = |status| XlsxToMdError::Fetch {
+            url: "https://example.com/book.xlsx".to_string(),
+            status,
+            message: "failed".to_string(),
+        };
+        assert_eq!(fetch(Some(404)).kind(), ErrorKind::InvalidInput);
+        assert_eq!(fetch(Some(408)).kind(), ErrorKind::Internal);
+        assert_eq!(fetch(Some(429)).kind(), ErrorKind::Internal);
+        assert_eq!(fetch(Some(503)).kind(), ErrorKind::Internal);
+        assert_eq!(fetch(None).kind(), ErrorKind::Internal);
+
+        let error = XlsxToMdError::Write("failed to write the archive".to_string());
+        assert_eq!(error.kind(), ErrorKind::Internal);
+
+        let error = XlsxToMdError::InvalidWriteInput {
+            sheet: Some("a/b".to_string()),
+            message: "invalid sheet name".to_string(),
+        };
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(error.sheet(), Some("a/b"));
+        assert_eq!(
+            error.to_string(),
+            "Cannot write XLSX at sheet 'a/b': invalid sheet name"
+        );
+
+        let error = XlsxToMdError::TextTransformer {
+            expected: 3,
+            actual: 2,
+        };
+        assert_eq!(error.kind(), ErrorKind::Internal);
+
+        let error = XlsxToMdError::Validation {
+            sheet: "Orders".to_string(),
+            message: "column 'ID' has duplicate values".to_string(),
+        };
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert_eq!(error.sheet(), Some("Orders"));
+
+        let error = XlsxToMdError::UnsupportedFeature {
+            sheet: "Sheet1".to_string(),
+            cell: "A1".to_string(),
+            message: "Pivot table".to_string(),
+        };
+        assert_eq!(error.kind(), ErrorKind::Unsupported);
+
+        let error = XlsxToMdError::Internal("conversion panicked".to_string());
+        assert_eq!(error.kind(), ErrorKind::Internal);
+
+        // JSONの出力エラーは、出力先の失敗をI/Oエラーのまま保持する
+        let error = serde_json::to_writer(FailingWriter, &[1]).unwrap_err();
+        let error = XlsxToMdError::json(error);
+        assert!(matches!(&error, XlsxToMdError::Io(e) if e.kind() == io::ErrorKind::BrokenPipe));
+        assert_eq!(error.kind(), ErrorKind::Internal);
+        let error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+        assert!(matches!(
+            XlsxToMdError::json(error),
+            XlsxToMdError::Internal(_)
+        ));
+    }
+
+    /// 常に書き込みに失敗する出力先
+    struct FailingWriter;
+
+    impl io::Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "pipe"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
     // Ioエラーのテスト
     #[test]
     fn test_io_error() {
@@ -201,11 +917,14 @@ mod tests {
     // Configエラーのテスト
     #[test]
     fn test_config_error() {
-        let error = XlsxToMdError::Config("Invalid range: start > end".to_string());
+        let error: XlsxToMdError = ConfigError::DuplicateFormat {
+            name: "markdown".to_string(),
+        }
+        .into();
 
         match error {
-            XlsxToMdError::Config(msg) => {
-                assert_eq!(msg, "Invalid range: start > end");
+            XlsxToMdError::Config(ConfigError::DuplicateFormat { name }) => {
+                assert_eq!(name, "markdown");
             }
             _ => panic!("Expected Config error"),
         }
@@ -213,11 +932,51 @@ mod tests {
 
     #[test]
     fn test_config_error_display() {
-        let error = XlsxToMdError::Config("Invalid date format: 'xyz'".to_string());
-        let error_msg = error.to_string();
+        let error = XlsxToMdError::Config(ConfigError::InvalidValue {
+            option: "with_date_format",
+            value: "xyz".to_string(),
+            reason: "produces an empty string".to_string(),
+        });
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: Invalid value \"xyz\" for with_date_format: produces an empty string"
+        );
+
+        let error = XlsxToMdError::Config(ConfigError::UnknownName {
+            kind: "OutputFormat",
+            value: "yaml".to_string(),
+            expected: vec!["markdown".to_string(), "csv".to_string()],
+        });
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: Invalid OutputFormat 'yaml' (expected one of: markdown, csv)"
+        );
+    }
+
+    // SecurityViolationエラーのテスト
+    #[test]
+    fn test_security_violation_display() {
+        let error: XlsxToMdError = SecurityLimit::CompressionRatio {
+            part: "xl/worksheets/sheet1.xml".to_string(),
+            size: 2_000_001,
+            compressed_size: 1_000,
+            max_ratio: 100,
+        }
+        .into();
+        assert_eq!(
+            error.to_string(),
+            "Security violation: File 'xl/worksheets/sheet1.xml' exceeds maximum compression ratio: \
+             2000001 bytes inflated from 1000 bytes (max ratio: 100)"
+        );
 
-        assert!(error_msg.contains("Configuration error"));
-        assert!(error_msg.contains("Invalid date format: 'xyz'"));
+        let error = XlsxToMdError::SecurityViolation(SecurityLimit::InvalidPath {
+            path: "../etc/passwd".to_string(),
+            reason: "path traversal",
+        });
+        assert_eq!(
+            error.to_string(),
+            "Security violation: Invalid ZIP path '../etc/passwd': path traversal"
+        );
     }
 
     // UnsupportedFeatureエラーのテスト
@@ -258,6 +1017,58 @@ mod tests {
         assert!(error_msg.contains("Complex formula not supported in Phase I"));
     }
 
+    // アクセサのテスト
+    #[test]
+    fn test_error_accessors() {
+        let error = XlsxToMdError::UnsupportedFeature {
+            sheet: "Sheet1".to_string(),
+            cell: "A1".to_string(),
+            message: "Pivot table".to_string(),
+        };
+        assert_eq!(error.sheet(), Some("Sheet1"));
+        assert_eq!(error.cell(), Some("A1"));
+        assert_eq!(error.part(), None);
+
+        let error = XlsxToMdError::format("Invalid epoch date").at_cell("Data", || "C3".into());
+        assert_eq!(error.sheet(), Some("Data"));
+        assert_eq!(error.cell(), Some("C3"));
+        assert_eq!(
+            error.to_string(),
+            "Failed to format cell at sheet 'Data', cell C3: Invalid epoch date"
+        );
+        // 設定済みの位置は上書きしない
+        let error = error.at_cell("Other", || "A1".into());
+        assert_eq!(error.cell(), Some("C3"));
+
+        let error = XlsxToMdError::SheetNotFound {
+            sheet: "Missing".to_string(),
+        };
+        assert_eq!(error.sheet(), Some("Missing"));
+        assert_eq!(error.cell(), None);
+
+        let error = XlsxToMdError::zip_part("xl/styles.xml", "invalid checksum");
+        assert_eq!(error.part(), Some("xl/styles.xml"));
+        assert_eq!(
+            error.to_string(),
+            "ZIP archive error in 'xl/styles.xml': invalid checksum"
+        );
+        assert_eq!(
+            XlsxToMdError::zip("invalid Zip archive").to_string(),
+            "ZIP archive error: invalid Zip archive"
+        );
+
+        let error = XlsxToMdError::xml("xl/workbook.xml", "unexpected EOF");
+        assert_eq!(error.part(), Some("xl/workbook.xml"));
+        assert_eq!(error.sheet(), None);
+
+        let error = XlsxToMdError::Config(ConfigError::NamedRangeNotFound {
+            name: "Sales".to_string(),
+        });
+        assert_eq!(error.sheet(), None);
+        assert_eq!(error.cell(), None);
+        assert_eq!(error.part(), None);
+    }
+
     // エラー変換のテスト（?演算子の動作確認）
     #[test]
     fn test_error_conversion_with_question_mark() {
@@ -301,7 +1112,10 @@ mod tests {
             .starts_with("Failed to parse Excel file"));
 
         // Config
-        let config_err = XlsxToMdError::Config("test config".to_string());
+        let config_err = XlsxToMdError::Config(ConfigError::InvalidEnvVar {
+            name: "XLSXZERO_FORMAT",
+            value: "yaml".to_string(),
+        });
         assert!(config_err.to_string().starts_with("Configuration error"));
 
         // UnsupportedFeature
@@ -0,0 +1,622 @@
+//! Expr Module
+//!
+//! 計算列（`ConverterBuilder::with_computed_column()`）の式を解析・評価するモジュール。
+//!
+//! 式は既存の列（先頭行の見出し名で参照）に対する四則演算・文字列連結・関数呼び出しからなり、
+//! 出力前のグリッドの各行で評価されます。
+//!
+//! # 構文
+//!
+//! * 数値: `1`, `0.08`
+//! * 文字列: `'text'`、`"text"`
+//! * 列の参照: `amount`（英数字・`_`からなる見出し名）、`[Unit Price]`（任意の見出し名）
+//! * 演算子: `+` `-` `*` `/`（数値）、`&`（文字列連結）、単項の`-`、括弧
+//! * 関数: `concat(a, b, ...)`、`upper(s)`、`lower(s)`、`trim(s)`、`round(x[, digits])`、`abs(x)`
+
+use crate::error::{ConfigError, XlsxToMdError};
+use crate::formatter::CellFormatter;
+use crate::grid::{Cell, LogicalGrid};
+use crate::types::{CellCoord, CellError, CellValue};
+
+/// 二項演算子
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Concat,
+}
+
+/// 関数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Concat,
+    Upper,
+    Lower,
+    Trim,
+    Round,
+    Abs,
+}
+
+impl Func {
+    /// 関数名（大文字小文字を区別しない）から関数を取得
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "concat" => Some(Func::Concat),
+            "upper" => Some(Func::Upper),
+            "lower" => Some(Func::Lower),
+            "trim" => Some(Func::Trim),
+            "round" => Some(Func::Round),
+            "abs" => Some(Func::Abs),
+            _ => None,
+        }
+    }
+
+    /// 引数の数の範囲（最小, 最大）
+    fn arity(self) -> (usize, usize) {
+        match self {
+            Func::Concat => (1, usize::MAX),
+            Func::Round => (1, 2),
+            Func::Upper | Func::Lower | Func::Trim | Func::Abs => (1, 1),
+        }
+    }
+}
+
+/// 構文木
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Text(String),
+    /// 列の参照（`ComputedColumn::columns`の添字）
+    Column(usize),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+}
+
+/// 評価中の値
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+impl Value {
+    /// 数値として取得（空文字列は0、数値として解釈できない文字列は`#VALUE!`）
+    fn to_number(&self) -> Result<f64, CellError> {
+        match self {
+            Value::Number(n) => Ok(*n),
+            Value::Text(s) if s.trim().is_empty() => Ok(0.0),
+            Value::Text(s) => s.trim().parse().map_err(|_| CellError::Value),
+        }
+    }
+
+    /// 文字列として取得
+    fn into_text(self) -> String {
+        match self {
+            Value::Number(n) => round_significant(n).to_string(),
+            Value::Text(s) => s,
+        }
+    }
+}
+
+/// 字句
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Ident(String),
+    /// `[...]`で囲んだ列名
+    Quoted(String),
+    Op(char),
+}
+
+/// コンパイル済みの計算列
+#[derive(Debug, Clone)]
+pub(crate) struct ComputedColumn {
+    /// 列名（見出し）
+    name: String,
+
+    /// 式
+    expr: Expr,
+
+    /// 式が参照する列名（出現順、重複なし）
+    columns: Vec<String>,
+}
+
+impl ComputedColumn {
+    /// 計算列の式を解析
+    ///
+    /// # 引数
+    ///
+    /// * `name` - 列名（見出し）
+    /// * `source` - 式
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(ComputedColumn)` - 解析に成功した場合
+    /// * `Err(XlsxToMdError::Config)` - 列名が空、または式が不正な場合
+    pub fn compile(name: &str, source: &str) -> Result<Self, XlsxToMdError> {
+        if name.trim().is_empty() {
+            return Err(XlsxToMdError::Config(ConfigError::InvalidValue {
+                option: "with_computed_column",
+                value: name.to_string(),
+                reason: format!("column name for expression '{}' must be non-empty", source),
+            }));
+        }
+        let error = |message: String| {
+            XlsxToMdError::Config(ConfigError::InvalidSyntax {
+                option: "with_computed_column",
+                text: source.to_string(),
+                message: format!("{} (column '{}')", message, name),
+            })
+        };
+
+        let tokens = tokenize(source).map_err(error)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            columns: Vec::new(),
+        };
+        let expr = parser.parse_expr().map_err(error)?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(error(format!("unexpected {:?}", token)));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            expr,
+            columns: parser.columns,
+        })
+    }
+
+    /// 列名（見出し）を取得
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// グリッドの末尾に計算列を追加
+    ///
+    /// 先頭行を見出しとして列を参照し、見出しに列名を、2行目以降に各行の評価結果を出力します。
+    /// 見出しは前後の空白を除いて比較し、完全に一致する列がない場合は大文字小文字を区別せずに
+    /// 比較します。数値として扱えない値の演算は`#VALUE!`、0による除算は`#DIV/0!`になり、
+    /// 参照したセルのエラー値はそのまま結果になります。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 計算列を追加した場合（グリッドが空の場合は何もしない）
+    /// * `Err(String)` - 見出しに見つからない列名（計算列は追加しない）
+    pub fn apply(&self, grid: &mut LogicalGrid, formatter: &CellFormatter) -> Result<(), String> {
+        if grid.get_rows() == 0 {
+            return Ok(());
+        }
+
+        // 参照する列を見出しから解決
+        let indices = self
+            .columns
+            .iter()
+            .map(|name| grid.find_header(name).ok_or_else(|| name.clone()))
+            .collect::<Result<Vec<usize>, String>>()?;
+
+        let mut cells = Vec::with_capacity(grid.get_rows());
+        let mut header = Cell::new(formatter.format_text(&self.name));
+        header.value = CellValue::String(self.name.clone());
+        cells.push(header);
+        for row in 1..grid.get_rows() as u32 {
+            let lookup =
+                |idx: usize| cell_value(grid.get_cell(CellCoord::new(row, indices[idx] as u32)));
+            cells.push(match eval(&self.expr, &lookup) {
+                Ok(Value::Number(n)) if !n.is_finite() => error_cell(CellError::Num),
+                Ok(Value::Number(n)) => {
+                    let n = round_significant(n);
+                    let mut cell = Cell::new(n.to_string());
+                    cell.value = CellValue::Number(n);
+                    cell
+                }
+                Ok(Value::Text(s)) => {
+                    let mut cell = Cell::new(formatter.format_text(&s));
+                    cell.value = CellValue::String(s);
+                    cell
+                }
+                Err(e) => error_cell(e),
+            });
+        }
+        grid.push_column(cells);
+        Ok(())
+    }
+}
+
+/// セルの値を評価中の値に変換（内部ヘルパー）
+///
+/// 数値は数値、エラー値はエラーとし、それ以外は文字列として扱います。
+fn cell_value(cell: Option<&Cell>) -> Result<Value, CellError> {
+    let Some(cell) = cell else {
+        return Ok(Value::Text(String::new()));
+    };
+    match &cell.value {
+        CellValue::Number(n) => Ok(Value::Number(*n)),
+        CellValue::String(s) => Ok(Value::Text(s.clone())),
+        CellValue::Error(e) => Err(*e),
+        _ => Ok(Value::Text(cell.content().to_string())),
+    }
+}
+
+/// エラー値のセルを生成（内部ヘルパー）
+fn error_cell(error: CellError) -> Cell {
+    let mut cell = Cell::new(error.as_excel_str().to_string());
+    cell.value = CellValue::Error(error);
+    cell
+}
+
+/// 浮動小数点数の演算誤差を有効数字15桁に丸める（内部ヘルパー）
+fn round_significant(n: f64) -> f64 {
+    if n == 0.0 || !n.is_finite() {
+        return n;
+    }
+    format!("{:.14e}", n).parse().unwrap_or(n)
+}
+
+/// 式を評価（内部ヘルパー）
+fn eval<F>(expr: &Expr, lookup: &F) -> Result<Value, CellError>
+where
+    F: Fn(usize) -> Result<Value, CellError>,
+{
+    Ok(match expr {
+        Expr::Number(n) => Value::Number(*n),
+        Expr::Text(s) => Value::Text(s.clone()),
+        Expr::Column(idx) => lookup(*idx)?,
+        Expr::Neg(inner) => Value::Number(-eval(inner, lookup)?.to_number()?),
+        Expr::Binary(BinaryOp::Concat, left, right) => {
+            let mut text = eval(left, lookup)?.into_text();
+            text.push_str(&eval(right, lookup)?.into_text());
+            Value::Text(text)
+        }
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, lookup)?.to_number()?;
+            let right = eval(right, lookup)?.to_number()?;
+            Value::Number(match op {
+                BinaryOp::Add => left + right,
+                BinaryOp::Sub => left - right,
+                BinaryOp::Mul => left * right,
+                BinaryOp::Div if right == 0.0 => return Err(CellError::Div0),
+                BinaryOp::Div => left / right,
+                BinaryOp::Concat => unreachable!("handled above"),
+            })
+        }
+        Expr::Call(func, args) => {
+            let mut values = args
+                .iter()
+                .map(|arg| eval(arg, lookup))
+                .collect::<Result<Vec<Value>, CellError>>()?
+                .into_iter();
+            let mut first = || values.next().unwrap_or(Value::Text(String::new()));
+            match func {
+                Func::Concat => {
+                    let mut text = first().into_text();
+                    text.extend(values.map(Value::into_text));
+                    Value::Text(text)
+                }
+                Func::Upper => Value::Text(first().into_text().to_uppercase()),
+                Func::Lower => Value::Text(first().into_text().to_lowercase()),
+                Func::Trim => Value::Text(first().into_text().trim().to_string()),
+                Func::Abs => Value::Number(first().to_number()?.abs()),
+                Func::Round => {
+                    let n = first().to_number()?;
+                    let digits = match values.next() {
+                        Some(digits) => digits.to_number()?.trunc().clamp(-15.0, 15.0) as i32,
+                        None => 0,
+                    };
+                    let scale = 10f64.powi(digits);
+                    Value::Number((round_significant(n * scale)).round() / scale)
+                }
+            }
+        }
+    })
+}
+
+/// 式を字句に分割（内部ヘルパー）
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '+' | '-' | '*' | '/' | '&' | '(' | ')' | ',' => tokens.push(Token::Op(c)),
+            '\'' | '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, ch)) if ch == c => break,
+                        Some((_, ch)) => text.push(ch),
+                        None => return Err(format!("unterminated string at {}", start)),
+                    }
+                }
+                tokens.push(Token::Text(text));
+            }
+            '[' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, ']')) => break,
+                        Some((_, ch)) => name.push(ch),
+                        None => return Err(format!("unterminated column name at {}", start)),
+                    }
+                }
+                tokens.push(Token::Quoted(name));
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(idx, ch)) = chars.peek() {
+                    if !(ch.is_ascii_digit() || ch == '.') {
+                        break;
+                    }
+                    end = idx + ch.len_utf8();
+                    chars.next();
+                }
+                let literal = &source[start..end];
+                let n = literal
+                    .parse()
+                    .map_err(|_| format!("invalid number '{}'", literal))?;
+                tokens.push(Token::Number(n));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some(&(_, ch)) = chars.peek() {
+                    if !(ch.is_alphanumeric() || ch == '_') {
+                        break;
+                    }
+                    ident.push(ch);
+                    chars.next();
+                }
+                tokens.push(Token::Ident(ident));
+            }
+            c => return Err(format!("unexpected character '{}' at {}", c, start)),
+        }
+    }
+    Ok(tokens)
+}
+
+/// 再帰下降パーサー（内部ヘルパー）
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    columns: Vec<String>,
+}
+
+impl Parser {
+    /// 次の字句が指定した演算子であれば読み進める
+    fn eat(&mut self, op: char) -> bool {
+        if self.tokens.get(self.pos) == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// expr := additive ('&' additive)*
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_additive()?;
+        while self.eat('&') {
+            let right = self.parse_additive()?;
+            expr = Expr::Binary(BinaryOp::Concat, Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    /// additive := term (('+' | '-') term)*
+    fn parse_additive(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_term()?;
+        loop {
+            let op = if self.eat('+') {
+                BinaryOp::Add
+            } else if self.eat('-') {
+                BinaryOp::Sub
+            } else {
+                return Ok(expr);
+            };
+            let right = self.parse_term()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            let op = if self.eat('*') {
+                BinaryOp::Mul
+            } else if self.eat('/') {
+                BinaryOp::Div
+            } else {
+                return Ok(expr);
+            };
+            let right = self.parse_unary()?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+    }
+
+    /// unary := '-' unary | primary
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.eat('-') {
+            return Ok(Expr::Neg(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    /// primary := number | string | column | function '(' args ')' | '(' expr ')'
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        match token {
+            Token::Number(n) => Ok(Expr::Number(n)),
+            Token::Text(s) => Ok(Expr::Text(s)),
+            Token::Quoted(name) => Ok(self.column(name)),
+            Token::Ident(name) if self.eat('(') => {
+                let func =
+                    Func::from_name(&name).ok_or_else(|| format!("unknown function '{}'", name))?;
+                let mut args = Vec::new();
+                if !self.eat(')') {
+                    loop {
+                        args.push(self.parse_expr()?);
+                        if self.eat(')') {
+                            break;
+                        }
+                        if !self.eat(',') {
+                            return Err(format!("expected ',' or ')' in call to '{}'", name));
+                        }
+                    }
+                }
+                let (min, max) = func.arity();
+                if args.len() < min || args.len() > max {
+                    return Err(format!(
+                        "wrong number of arguments to '{}': {}",
+                        name,
+                        args.len()
+                    ));
+                }
+                Ok(Expr::Call(func, args))
+            }
+            Token::Ident(name) => Ok(self.column(name)),
+            Token::Op('(') => {
+                let expr = self.parse_expr()?;
+                if !self.eat(')') {
+                    return Err("expected ')'".to_string());
+                }
+                Ok(expr)
+            }
+            Token::Op(op) => Err(format!("unexpected '{}'", op)),
+        }
+    }
+
+    /// 列の参照を登録（内部ヘルパー）
+    fn column(&mut self, name: String) -> Expr {
+        let idx = match self.columns.iter().position(|c| *c == name) {
+            Some(idx) => idx,
+            None => {
+                self.columns.push(name);
+                self.columns.len() - 1
+            }
+        };
+        Expr::Column(idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MergeStrategy;
+    use crate::types::SheetMetadata;
+
+    fn create_grid(rows: &[&[CellValue]]) -> LogicalGrid {
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+        let mut grid =
+            LogicalGrid::build(vec![], vec![], &metadata, MergeStrategy::DataDuplication).unwrap();
+        for (row, values) in rows.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                let mut cell = Cell::new(match value {
+                    CellValue::Number(n) => n.to_string(),
+                    CellValue::String(s) => s.clone(),
+                    _ => String::new(),
+                });
+                cell.value = value.clone();
+                grid.set_cell(CellCoord::new(row as u32, col as u32), cell);
+            }
+        }
+        grid
+    }
+
+    fn column(grid: &LogicalGrid, col: u32) -> Vec<String> {
+        (0..grid.get_rows() as u32)
+            .map(|row| {
+                grid.get_cell(CellCoord::new(row, col))
+                    .unwrap()
+                    .content()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    fn text(s: &str) -> CellValue {
+        CellValue::String(s.to_string())
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert!(ComputedColumn::compile("total", "amount *").is_err());
+        assert!(ComputedColumn::compile("total", "(amount").is_err());
+        assert!(ComputedColumn::compile("total", "'open").is_err());
+        assert!(ComputedColumn::compile("total", "sum(amount)").is_err());
+        assert!(ComputedColumn::compile("total", "upper(a, b)").is_err());
+        assert!(ComputedColumn::compile("total", "amount $ 2").is_err());
+        assert!(ComputedColumn::compile(" ", "1").is_err());
+    }
+
+    #[test]
+    fn test_apply_arithmetic_and_text() {
+        let mut grid = create_grid(&[
+            &[
+                text("amount"),
+                text("Rate"),
+                text("first"),
+                text("last name"),
+            ],
+            &[
+                CellValue::Number(100.0),
+                CellValue::Number(0.1),
+                text("Ada"),
+                text("Lovelace"),
+            ],
+            &[
+                text("n/a"),
+                CellValue::Number(3.0),
+                text("Alan"),
+                text("Turing"),
+            ],
+        ]);
+        let formatter = CellFormatter::new();
+        ComputedColumn::compile("total", "amount * rate + 1")
+            .unwrap()
+            .apply(&mut grid, &formatter)
+            .unwrap();
+        ComputedColumn::compile("name", "upper(concat(first, ' ', [last name]))")
+            .unwrap()
+            .apply(&mut grid, &formatter)
+            .unwrap();
+        ComputedColumn::compile("ratio", "round(rate / (rate - 3), 2) & '!'")
+            .unwrap()
+            .apply(&mut grid, &formatter)
+            .unwrap();
+
+        assert_eq!(column(&grid, 4), ["total", "11", "#VALUE!"]);
+        assert_eq!(column(&grid, 5), ["name", "ADA LOVELACE", "ALAN TURING"]);
+        assert_eq!(column(&grid, 6), ["ratio", "-0.03!", "#DIV/0!"]);
+    }
+
+    #[test]
+    fn test_apply_missing_column() {
+        let mut grid = create_grid(&[&[text("amount")], &[CellValue::Number(1.0)]]);
+        let result = ComputedColumn::compile("total", "amount * price")
+            .unwrap()
+            .apply(&mut grid, &CellFormatter::new());
+        assert_eq!(result, Err("price".to_string()));
+        assert_eq!(grid.get_cols(), 1);
+    }
+}
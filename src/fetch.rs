@@ -0,0 +1,212 @@
+//! Fetch Module
+//!
+//! リモートのワークブックをHTTP(S)でダウンロードするモジュール（`fetch`フィーチャー）。
+//!
+//! `Converter::convert_url()`と`Converter::convert_url_async()`で使用します。TLSにはrustlsを使用し、
+//! 入力ファイルのサイズの上限（`ConverterBuilder::with_max_input_size()`）をダウンロード中に適用します。
+
+use std::future::Future;
+use std::io::Read;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+use crate::error::{SecurityLimit, XlsxToMdError};
+
+/// 接続のタイムアウト
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 受信が途絶えた場合のタイムアウト
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// URLからワークブックをダウンロード
+///
+/// `Content-Length`が上限を超える場合は本文を受信せずに、本文が上限を超えた場合は
+/// 上限を1バイト超えた時点で受信を打ち切り、`XlsxToMdError::SecurityViolation`を返します。
+///
+/// # 引数
+///
+/// * `url` - ダウンロードするURL（`http`または`https`）
+/// * `max_size` - 受信する本文の最大サイズ（バイト）
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - 受信した本文
+/// * `Err(XlsxToMdError)` - ダウンロードに失敗した場合、またはサイズの上限を超えた場合
+pub(crate) fn download(url: &str, max_size: u64) -> Result<Vec<u8>, XlsxToMdError> {
+    let fetch_error = |status: Option<u16>, message: String| XlsxToMdError::Fetch {
+        url: url.to_string(),
+        status,
+        message,
+    };
+
+    let agent = ureq::AgentBuilder::new()
+        .timeout_connect(CONNECT_TIMEOUT)
+        .timeout_read(READ_TIMEOUT)
+        .build();
+    let response = agent.get(url).call().map_err(|e| match e {
+        ureq::Error::Status(status, response) => {
+            fetch_error(Some(status), format!("HTTP {}", response.status_text()))
+        }
+        ureq::Error::Transport(transport) => fetch_error(None, transport.to_string()),
+    })?;
+
+    // 本文を受信する前に、申告されたサイズで判定
+    let declared = response
+        .header("Content-Length")
+        .and_then(|length| length.trim().parse::<u64>().ok());
+    if let Some(length) = declared.filter(|&length| length > max_size) {
+        return Err(SecurityLimit::RemoteSize {
+            size: length,
+            max: max_size,
+        }
+        .into());
+    }
+
+    // 上限を1バイト超えた時点で受信を打ち切る
+    let mut buffer = Vec::with_capacity(declared.unwrap_or(0) as usize);
+    let bytes_read = response
+        .into_reader()
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut buffer)
+        .map_err(|e| fetch_error(None, e.to_string()))?;
+    if bytes_read as u64 > max_size {
+        return Err(SecurityLimit::RemoteSize {
+            size: bytes_read as u64,
+            max: max_size,
+        }
+        .into());
+    }
+    Ok(buffer)
+}
+
+/// バックグラウンドのスレッドで実行するダウンロード
+///
+/// 非同期ランタイムに依存しないよう、ダウンロードとダウンロード後の処理（変換など）は
+/// 専用のスレッドで実行し、完了時に待機中のタスクを起こします。
+pub(crate) struct Download<T> {
+    state: Arc<Mutex<DownloadState<T>>>,
+}
+
+/// ダウンロードの状態（スレッド間で共有）
+struct DownloadState<T> {
+    /// ダウンロード後の処理の結果（完了前はNone）
+    result: Option<Result<T, XlsxToMdError>>,
+
+    /// 完了時に起こすタスク
+    waker: Option<Waker>,
+}
+
+impl<T: Send + 'static> Download<T> {
+    /// ダウンロードを開始
+    ///
+    /// # 引数
+    ///
+    /// * `url` - ダウンロードするURL
+    /// * `max_size` - 受信する本文の最大サイズ（バイト）
+    /// * `then` - 受信した本文を処理する関数（同じスレッドで実行）
+    pub fn spawn<F>(url: &str, max_size: u64, then: F) -> Self
+    where
+        F: FnOnce(Vec<u8>) -> Result<T, XlsxToMdError> + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(DownloadState {
+            result: None,
+            waker: None,
+        }));
+        let shared = Arc::clone(&state);
+        let url = url.to_string();
+        let spawned = std::thread::Builder::new()
+            .name("xlsxzero-fetch".to_string())
+            .spawn(move || {
+                let result = download(&url, max_size).and_then(then);
+                let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        if let Err(e) = spawned {
+            state.lock().unwrap_or_else(PoisonError::into_inner).result = Some(Err(e.into()));
+        }
+        Self { state }
+    }
+}
+
+impl<T> Future for Download<T> {
+    type Output = Result<T, XlsxToMdError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    /// 1回だけ応答するHTTPサーバーを起動し、URLを返す
+    fn serve_once(response: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let _ = stream.write_all(&response);
+        });
+        format!("http://{}/book.xlsx", addr)
+    }
+
+    fn response(status: &str, content_length: Option<usize>, body: &[u8]) -> Vec<u8> {
+        let mut response = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+        if let Some(length) = content_length {
+            response.push_str(&format!("Content-Length: {}\r\n", length));
+        }
+        response.push_str("\r\n");
+        let mut response = response.into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    #[test]
+    fn test_download() {
+        let url = serve_once(response("200 OK", Some(5), b"hello"));
+        assert_eq!(download(&url, 5).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_download_size_limit() {
+        // 申告されたサイズが上限を超える
+        let url = serve_once(response("200 OK", Some(10), b"0123456789"));
+        assert!(matches!(
+            download(&url, 5),
+            Err(XlsxToMdError::SecurityViolation(_))
+        ));
+
+        // 申告のない本文が上限を超える
+        let url = serve_once(response("200 OK", None, b"0123456789"));
+        assert!(matches!(
+            download(&url, 5),
+            Err(XlsxToMdError::SecurityViolation(_))
+        ));
+    }
+
+    #[test]
+    fn test_download_status() {
+        let url = serve_once(response("404 Not Found", Some(0), b""));
+        match download(&url, 100) {
+            Err(XlsxToMdError::Fetch { status, .. }) => assert_eq!(status, Some(404)),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
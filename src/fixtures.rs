@@ -0,0 +1,263 @@
+//! Fixtures Module
+//!
+//! テストやファジングで使用するExcelファイルを生成するモジュール（`fixtures`フィーチャー）。
+//!
+//! xlsxzero自身の統合テストと同じ入力を、下流のクレートのテストやファザーのシードとして
+//! 再利用できます。各関数はXLSXファイルの内容をメモリ上に生成し、同じ関数は常に同じ
+//! セル構成のワークブックを返します。
+//!
+//! # 使用例
+//!
+//! ```toml
+//! [dev-dependencies]
+//! xlsxzero = { version = "0.1", features = ["fixtures"] }
+//! ```
+//!
+//! ```rust
+//! use std::io::Cursor;
+//! use xlsxzero::{fixtures, ConverterBuilder};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let converter = ConverterBuilder::new().build()?;
+//! let markdown = converter.convert_to_string(Cursor::new(fixtures::simple_table()?))?;
+//! assert!(markdown.contains("| Header1 | Header2 |"));
+//! # Ok(())
+//! # }
+//! ```
+
+use rust_xlsxwriter::{Format, Workbook};
+
+pub use rust_xlsxwriter::XlsxError;
+
+/// `formats_matrix()`で使用する表示形式と値の組（表示形式, 値）
+const FORMAT_MATRIX: &[(&str, f64)] = &[
+    ("General", 1234.5678),
+    ("0", 1234.5678),
+    ("0.00", 1234.5678),
+    ("#,##0", 1234.5678),
+    ("#,##0.00", 1234.5678),
+    ("0%", 0.1234),
+    ("0.00%", 0.1234),
+    ("0.00E+00", 1234.5678),
+    ("yyyy-mm-dd", 45658.0),
+    ("hh:mm:ss", 0.75),
+    ("yyyy-mm-dd hh:mm", 45658.75),
+    ("@", 1234.5678),
+];
+
+/// 見出し行と1行のデータからなる2列の表
+///
+/// | Header1 | Header2 |
+/// |---------|---------|
+/// | Data1   | Data2   |
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn simple_table() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Header1")?;
+    worksheet.write_string(0, 1, "Header2")?;
+    worksheet.write_string(1, 0, "Data1")?;
+    worksheet.write_string(1, 1, "Data2")?;
+
+    workbook.save_to_buffer()
+}
+
+/// 1セルずつ値を持つ3シート（`Sheet1`〜`Sheet3`、A1は`Sheet1_Data`など）のワークブック
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn multi_sheets() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+
+    for name in ["Sheet1", "Sheet2", "Sheet3"] {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(name)?;
+        worksheet.write_string(0, 0, format!("{}_Data", name))?;
+    }
+
+    workbook.save_to_buffer()
+}
+
+/// 横方向に結合した見出し（A1:C1の`Header`）と、3列のデータ行からなる表
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn merged_cells() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.merge_range(0, 0, 0, 2, "", &Format::new())?;
+    // 結合の後に親セルの値を書き込む
+    worksheet.write_string(0, 0, "Header")?;
+    worksheet.write_string(1, 0, "Data1")?;
+    worksheet.write_string(1, 1, "Data2")?;
+    worksheet.write_string(1, 2, "Data3")?;
+
+    workbook.save_to_buffer()
+}
+
+/// すべてのセルが`R{行}C{列}`（1始まり、例: `R1C1`）の文字列である`rows` × `cols`の表
+///
+/// 範囲の指定やサイズの上限、性能の検証に使用します。大きな値を指定すると、
+/// 生成に時間とメモリを要します（100万セルで数百MB程度）。
+///
+/// # 引数
+///
+/// * `rows` - 行数
+/// * `cols` - 列数
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合（Excelの行数・列数の上限を超える場合など）
+pub fn large_table(rows: u32, cols: u16) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    for row in 0..rows {
+        for col in 0..cols {
+            worksheet.write_string(row, col, format!("R{}C{}", row + 1, col + 1))?;
+        }
+    }
+
+    workbook.save_to_buffer()
+}
+
+/// 3行目（`HiddenData`の行）とB列が非表示の、4行 × 3列の表
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn hidden_elements() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let rows = [
+        ["Header1", "Header2", "Header3"],
+        ["VisibleData1", "VisibleData2", "VisibleData3"],
+        ["HiddenData", "HiddenData2", "HiddenData3"],
+        ["VisibleData4", "VisibleData5", "VisibleData6"],
+    ];
+    for (row_idx, row) in (0u32..).zip(rows) {
+        for (col_idx, value) in (0u16..).zip(row) {
+            worksheet.write_string(row_idx, col_idx, value)?;
+        }
+    }
+    worksheet.set_row_hidden(2)?;
+    worksheet.set_column_hidden(1)?;
+
+    workbook.save_to_buffer()
+}
+
+/// A列にハイパーリンク（URLのみのセルと、表示文字列が`Rust`のセル）を持つ表
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn hyperlinks() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Link")?;
+    worksheet.write_string(0, 1, "Description")?;
+    worksheet.write_url(1, 0, "https://example.com")?;
+    worksheet.write_string(1, 1, "Example website")?;
+    worksheet.write_url_with_text(2, 0, "https://rust-lang.org", "Rust")?;
+    worksheet.write_string(2, 1, "Rust programming language")?;
+    worksheet.write_url(3, 0, "https://github.com")?;
+    worksheet.write_string(3, 1, "GitHub")?;
+
+    workbook.save_to_buffer()
+}
+
+/// 太字・斜体の書式を設定したセルを持つ表（A列が本文、B列が書式の名前）
+///
+/// 書式はセル単位で設定されます（1つのセル内で書式が混在するリッチテキストではありません）。
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn rich_text() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let bold = Format::new().set_bold();
+    let italic = Format::new().set_italic();
+    let bold_italic = Format::new().set_bold().set_italic();
+
+    worksheet.write_string(0, 0, "Text")?;
+    worksheet.write_string(0, 1, "Format")?;
+    worksheet.write_string(1, 0, "Plain text")?;
+    worksheet.write_string(1, 1, "None")?;
+    worksheet.write_string_with_format(2, 0, "Bold text", &bold)?;
+    worksheet.write_string(2, 1, "Bold")?;
+    worksheet.write_string_with_format(3, 0, "Italic text", &italic)?;
+    worksheet.write_string(3, 1, "Italic")?;
+    worksheet.write_string_with_format(4, 0, "Bold and italic", &bold_italic)?;
+    worksheet.write_string(4, 1, "Bold + Italic")?;
+
+    workbook.save_to_buffer()
+}
+
+/// 主要な表示形式（数値・桁区切り・パーセント・指数・日付・時刻・文字列）ごとに、
+/// 1行ずつ値を持つ表
+///
+/// A列が表示形式の文字列（例: `#,##0.00`）、B列がその表示形式を設定した数値です。
+/// 表示形式の変換の回帰テストに使用します。
+///
+/// | Format     | Value     |
+/// |------------|-----------|
+/// | General    | 1234.5678 |
+/// | #,##0.00   | 1234.5678 |
+/// | 0.00%      | 0.1234    |
+/// | yyyy-mm-dd | 45658     |
+/// | ...        | ...       |
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxError)` - 生成に失敗した場合
+pub fn formats_matrix() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    worksheet.write_string(0, 0, "Format")?;
+    worksheet.write_string(0, 1, "Value")?;
+    for (row, (num_format, value)) in (1u32..).zip(FORMAT_MATRIX) {
+        worksheet.write_string(row, 0, *num_format)?;
+        let format = Format::new().set_num_format(*num_format);
+        worksheet.write_number_with_format(row, 1, *value, &format)?;
+    }
+
+    workbook.save_to_buffer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConverterBuilder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_large_table() {
+        let converter = ConverterBuilder::new().build().unwrap();
+        let markdown = converter
+            .convert_to_string(Cursor::new(large_table(3, 2).unwrap()))
+            .unwrap();
+        assert!(markdown.contains("| R3C1 | R3C2 |"));
+        assert!(!markdown.contains("R4C1"));
+        assert!(!markdown.contains("R1C3"));
+    }
+}
@@ -285,7 +285,9 @@ impl FormatParser {
     /// * `Err(XlsxToMdError)` - エラーが発生した場合
     pub fn format_number(&self, value: f64) -> Result<String, XlsxToMdError> {
         // 1. セクションの選択
-        let section = self.select_section(value);
+        let Some(section) = self.select_section(value) else {
+            return Ok(value.to_string());
+        };
 
         // 2. トークンに基づいてフォーマット
         if section.is_datetime() {
@@ -306,23 +308,19 @@ impl FormatParser {
     ///
     /// # 戻り値
     ///
-    /// 選択されたセクション
-    fn select_section(&self, value: f64) -> &FormatSection {
+    /// 選択されたセクション（該当するセクションがない場合は先頭のセクション、
+    /// セクションが1つもない場合は`None`）
+    fn select_section(&self, value: f64) -> Option<&FormatSection> {
         // Phase II制限: 条件付き書式はサポート外
         // 値に基づいてセクションを選択
-        if value > 0.0 {
-            self.sections
-                .first()
-                .unwrap_or_else(|| self.sections.first().unwrap())
+        let index = if value > 0.0 {
+            0
         } else if value < 0.0 {
-            self.sections
-                .get(1)
-                .unwrap_or_else(|| self.sections.first().unwrap())
+            1
         } else {
-            self.sections
-                .get(2)
-                .unwrap_or_else(|| self.sections.first().unwrap())
-        }
+            2
+        };
+        self.sections.get(index).or_else(|| self.sections.first())
     }
 
     /// 日付・時刻をフォーマット
@@ -349,12 +347,12 @@ impl FormatParser {
 
         // Excelシリアル値をNaiveDateTimeに変換（1900年エポック固定）
         let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)
-            .ok_or_else(|| XlsxToMdError::Config("Invalid epoch date".to_string()))?;
+            .ok_or_else(|| XlsxToMdError::format("Invalid epoch date"))?;
         let days = value.floor() as i64;
-        let date = epoch
-            .checked_add_signed(Duration::days(days + 1))
+        let date = Duration::try_days(days.saturating_add(1))
+            .and_then(|offset| epoch.checked_add_signed(offset))
             .ok_or_else(|| {
-                XlsxToMdError::Config(format!("Date calculation overflow: serial_value={}", value))
+                XlsxToMdError::format(format!("Date calculation overflow: serial_value={}", value))
             })?;
 
         let time_part = value.fract();
@@ -366,7 +364,7 @@ impl FormatParser {
         let datetime = NaiveDateTime::new(
             date,
             chrono::NaiveTime::from_hms_opt(hours, minutes, seconds)
-                .unwrap_or_else(|| chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap()),
+                .unwrap_or(chrono::NaiveTime::MIN),
         );
 
         let mut result = String::new();
@@ -482,12 +480,12 @@ impl FormatParser {
         let display_value = if has_percent { value * 100.0 } else { value };
         let abs_value = display_value.abs();
 
-        // 小数部の桁数に応じて四捨五入
+        // 小数部の桁数に応じて四捨五入（小数部がない書式は整数に四捨五入）
         let rounded_value = if has_decimal_point && total_decimal_zeros > 0 {
             let multiplier = 10f64.powi(total_decimal_zeros as i32);
             (abs_value * multiplier).round() / multiplier
         } else {
-            abs_value
+            abs_value.round()
         };
 
         // 整数部と小数部を計算
@@ -562,17 +560,13 @@ impl FormatParser {
                         }
                     }
                 }
-                FormatToken::IntegerHash => {
-                    if int_pos < int_chars.len() {
-                        let ch = int_chars[int_pos];
-                        result.push(ch);
-                        int_pos += 1;
-                    }
+                FormatToken::IntegerHash if int_pos < int_chars.len() => {
+                    let ch = int_chars[int_pos];
+                    result.push(ch);
+                    int_pos += 1;
                 }
-                FormatToken::DecimalPoint => {
-                    if has_decimal_point {
-                        result.push('.');
-                    }
+                FormatToken::DecimalPoint if has_decimal_point => {
+                    result.push('.');
                 }
                 FormatToken::DecimalZero(count) => {
                     // 小数部から必要な桁数を取得
@@ -627,7 +621,7 @@ impl FormatParser {
     /// # 戻り値
     ///
     /// 千の位区切りが追加された文字列
-    fn add_thousand_separators(s: &str) -> String {
+    pub(crate) fn add_thousand_separators(s: &str) -> String {
         let mut result = String::new();
         let chars: Vec<char> = s.chars().collect();
         let len = chars.len();
@@ -688,6 +682,8 @@ mod tests {
         assert_eq!(parser.format_number(123.0).unwrap(), "123");
         assert_eq!(parser.format_number(0.0).unwrap(), "0");
         assert_eq!(parser.format_number(999.0).unwrap(), "999");
+        // 小数部は表示せず四捨五入する
+        assert_eq!(parser.format_number(1234.5678).unwrap(), "1235");
     }
 
     #[test]
@@ -748,6 +744,9 @@ mod tests {
         assert!(result.contains("2025"));
         assert!(result.contains("01") || result.contains("1"));
         assert!(result.contains("02") || result.contains("2"));
+
+        // 範囲外のシリアル値はパニックせずにエラーを返す
+        assert!(parser.format_number(1e300).is_err());
     }
 
     #[test]
@@ -3,17 +3,18 @@
 //! セル値のフォーマット処理を提供するモジュール。
 //! Phase Iでは簡易実装として、Number Format Stringなしで動作します。
 
-use chrono::{Duration, NaiveDate};
+use chrono::{Duration, NaiveDate, NaiveTime};
 
-use crate::api::DateFormat;
+use crate::api::{DateFormat, HyperlinkMode, NullFormulaPolicy, NumberOutput, OutputFormat};
 use crate::builder::ConversionConfig;
 use crate::error::XlsxToMdError;
-use crate::types::{CellValue, RawCellData};
+use crate::output::escape_xml;
+use crate::types::{CellValue, DateKind, Hyperlink, RawCellData, RichTextFormat};
 
 /// セルフォーマッター
 ///
 /// セル値のフォーマット処理のファサードとして機能します。
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct CellFormatter {
     /// 日付フォーマッター
     date_formatter: DateFormatter,
@@ -64,6 +65,9 @@ impl CellFormatter {
                 // 日付判定
                 if self.is_date_value(*n, &raw_cell.format_id, &raw_cell.format_string) {
                     self.date_formatter.format(*n, config, is_1904)?
+                } else if config.number_output() == NumberOutput::Raw {
+                    // 生の数値（桁区切りなどの書式を適用しない）
+                    n.to_string()
                 } else {
                     self.number_formatter.format(*n, &raw_cell.format_string)?
                 }
@@ -80,32 +84,193 @@ impl CellFormatter {
 
             CellValue::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
 
-            CellValue::Error(e) => e.clone(),
+            CellValue::Error(e) => e.as_excel_str().to_string(),
 
-            CellValue::Empty => String::new(),
+            CellValue::DateTime { serial, kind } => self
+                .date_formatter
+                .format_kind(*serial, *kind, config, is_1904)?,
+
+            CellValue::Empty => match &config.null_formula_policy {
+                // 値のない数式セルはマーカーを出力
+                NullFormulaPolicy::Marker(marker) if raw_cell.is_formula_without_value() => {
+                    self.escape_markdown(marker)
+                }
+                _ => String::new(),
+            },
+        };
+
+        // 3. ハイパーリンクの表示テキスト（display属性、ヒント）がある場合はセルの値より優先
+        let formatted_value = match raw_cell.hyperlink.as_ref().and_then(Hyperlink::text) {
+            Some(text) => self.escape_markdown(text),
+            None => formatted_value,
+        };
+
+        // 4. セル単位のフォントの書式（Markdown形式のみ、リッチテキストは実行ごとの書式を優先）
+        let formatted_value = match &raw_cell.font {
+            Some(font)
+                if config.output_format == OutputFormat::Markdown
+                    && raw_cell.rich_text.is_none()
+                    && !formatted_value.trim().is_empty() =>
+            {
+                apply_font(formatted_value, font)
+            }
+            _ => formatted_value,
         };
 
-        // 3. ハイパーリンクがある場合はMarkdownリンク構文に変換
-        if let Some(ref url) = raw_cell.hyperlink {
+        // 5. ハイパーリンクがある場合は出力フォーマットに応じたリンクに変換
+        let formatted_value = self.apply_hyperlink(raw_cell, formatted_value, config);
+
+        // 6. 結果値と数式を併せて出力する場合はテンプレートで組み合わせる
+        match (&raw_cell.formula, config.formula_mode) {
+            (Some(formula), FormulaMode::Both) => Ok(apply_formula_template(
+                &config.formula_template,
+                &formatted_value,
+                &self.escape_markdown(formula.strip_prefix('=').unwrap_or(formula)),
+            )),
+            _ => Ok(formatted_value),
+        }
+    }
+
+    /// `format_cell`が生の数値を出力するセルの数値を取得
+    ///
+    /// 数値（日付を除く）を`NumberOutput::Raw`で出力し、ハイパーリンク、
+    /// フォントの書式、数式による変更を受けないセルのみ`Some`を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `raw_cell` - パーサーから抽出された生のセルデータ
+    /// * `config` - 変換設定
+    pub fn raw_number(&self, raw_cell: &RawCellData, config: &ConversionConfig) -> Option<f64> {
+        use crate::api::FormulaMode;
+
+        let CellValue::Number(n) = raw_cell.value else {
+            return None;
+        };
+        let formula_applied =
+            raw_cell.formula.is_some() && config.formula_mode != FormulaMode::CachedValue;
+        let font_applied =
+            raw_cell.font.is_some() && config.output_format == OutputFormat::Markdown;
+        if config.number_output() != NumberOutput::Raw
+            || formula_applied
+            || font_applied
+            || raw_cell.hyperlink.is_some()
+            || self.is_date_value(n, &raw_cell.format_id, &raw_cell.format_string)
+        {
+            return None;
+        }
+        Some(n)
+    }
+
+    /// 変換済みのテキストからセルの表示文字列を生成
+    ///
+    /// `TextTransformer`で変換されたテキストに、通常のフォーマットと同じ
+    /// エスケープとハイパーリンクの処理を適用します。
+    /// 変換後のテキストに`|`や改行が含まれても、表の構造は崩れません。
+    ///
+    /// # 引数
+    ///
+    /// * `raw_cell` - パーサーから抽出された生のセルデータ
+    /// * `text` - 変換済みのテキスト（エスケープ前）
+    /// * `config` - 変換設定
+    ///
+    /// # 戻り値
+    ///
+    /// 表示文字列
+    pub fn format_transformed_text(
+        &self,
+        raw_cell: &RawCellData,
+        text: &str,
+        config: &ConversionConfig,
+    ) -> String {
+        self.apply_hyperlink(raw_cell, self.escape_markdown(text), config)
+    }
+
+    /// プレーンテキストからセルの表示文字列を生成
+    ///
+    /// 文字列のセル値と同じエスケープを適用します（ハイパーリンクの処理は行いません）。
+    ///
+    /// # 引数
+    ///
+    /// * `text` - エスケープ前のテキスト
+    ///
+    /// # 戻り値
+    ///
+    /// 表示文字列
+    pub fn format_text(&self, text: &str) -> String {
+        self.escape_markdown(text)
+    }
+
+    /// ハイパーリンクがある場合は出力フォーマットに応じたリンクに変換（内部ヘルパー）
+    ///
+    /// Markdown形式ではMarkdownリンク構文、HTML形式では`<a>`要素に変換します。
+    /// JSON・XML形式はURLを別の項目として出力するため、CSV形式と同様に表示テキストのみを返します。
+    /// 脚注として出力する設定の場合、URLは後段で脚注として付加するため表示テキストのみを返します。
+    /// 表示テキストがURL自体の場合は、設定に応じて短縮したURLを表示テキストとします。
+    fn apply_hyperlink(
+        &self,
+        raw_cell: &RawCellData,
+        formatted_value: String,
+        config: &ConversionConfig,
+    ) -> String {
+        if let Some(Hyperlink { ref url, .. }) = raw_cell.hyperlink {
+            if config.hyperlink_mode == HyperlinkMode::UrlOnly {
+                return self.escape_markdown(url);
+            }
             // 表示テキストが空の場合はURLを使用
-            let display_text = if formatted_value.is_empty() {
-                url.clone()
-            } else {
-                formatted_value
+            let is_url_text =
+                formatted_value.is_empty() || formatted_value == self.escape_markdown(url);
+            let display_text = match self.shortened_url(url, config) {
+                Some(short) if is_url_text => short,
+                _ if formatted_value.is_empty() => url.clone(),
+                _ => formatted_value,
             };
-            Ok(format!("[{}]({})", display_text, url))
+            if config.hyperlink_footnotes() || config.hyperlink_mode == HyperlinkMode::TextOnly {
+                return display_text;
+            }
+            match config.output_format {
+                OutputFormat::Markdown => format!("[{}]({})", display_text, url),
+                OutputFormat::Html => {
+                    format!("<a href=\"{}\">{}</a>", escape_xml(url, true), display_text)
+                }
+                _ => display_text,
+            }
         } else {
-            Ok(formatted_value)
+            formatted_value
         }
     }
 
+    /// 長いURLを短縮した表示テキスト（例: `example.com/…`）を取得（内部ヘルパー）
+    ///
+    /// Markdown形式でURLの短縮を指定した場合に、上限の文字数を超えるURLをホスト名と省略記号に
+    /// 置き換えます。URLを出力しない`HyperlinkMode::TextOnly`や、ホスト名の後にパスなどがない
+    /// URLは短縮しません。
+    fn shortened_url(&self, url: &str, config: &ConversionConfig) -> Option<String> {
+        let max_chars = config.url_shortening?;
+        if config.output_format != OutputFormat::Markdown
+            || config.hyperlink_mode == HyperlinkMode::TextOnly
+            || url.chars().count() <= max_chars
+        {
+            return None;
+        }
+        let (_, rest) = url.split_once("://")?;
+        let end = rest.find(['/', '?', '#'])?;
+        // ユーザー情報（user@host）は表示しない
+        let host = rest[..end]
+            .rsplit_once('@')
+            .map_or(&rest[..end], |(_, host)| host);
+        if host.is_empty() {
+            return None;
+        }
+        Some(format!("{}/…", self.escape_markdown(host)))
+    }
+
     /// 数値が日付値かどうかを判定（ヒューリスティック）
     ///
     /// # 引数
     ///
     /// * `value` - 数値
-    /// * `format_id` - 数値書式ID（Phase Iでは常にNone）
-    /// * `format_string` - カスタム書式文字列（Phase Iでは常にNone）
+    /// * `format_id` - 数値書式ID（セルのスタイルから取得、スタイルがない場合はNone）
+    /// * `format_string` - 数値書式文字列（セルのスタイルから取得、スタイルがない場合はNone）
     ///
     /// # 戻り値
     ///
@@ -171,27 +336,59 @@ impl CellFormatter {
     ///
     /// # 戻り値
     ///
-    /// Markdown形式の文字列（太字: `**text**`, 斜体: `*text*`）
+    /// Markdown形式の文字列（太字: `**text**`, 斜体: `*text*`, 取り消し線: `~~text~~`）
     fn format_rich_text(&self, segments: &[crate::types::RichTextSegment]) -> String {
         let mut result = String::new();
         for segment in segments {
-            let mut text = self.escape_markdown(&segment.text);
-
-            // 書式を適用（太字、斜体）
-            if segment.format.bold && segment.format.italic {
-                text = format!("***{}***", text);
-            } else if segment.format.bold {
-                text = format!("**{}**", text);
-            } else if segment.format.italic {
-                text = format!("*{}*", text);
-            }
-
-            result.push_str(&text);
+            let text = self.escape_markdown(&segment.text);
+            result.push_str(&apply_font(text, &segment.format));
         }
         result
     }
 }
 
+/// エスケープ済みのテキストにMarkdownの書式（太字、斜体、取り消し線）を適用（内部ヘルパー）
+fn apply_font(text: String, format: &RichTextFormat) -> String {
+    let text = if format.bold && format.italic {
+        format!("***{}***", text)
+    } else if format.bold {
+        format!("**{}**", text)
+    } else if format.italic {
+        format!("*{}*", text)
+    } else {
+        text
+    };
+    if format.strike {
+        format!("~~{}~~", text)
+    } else {
+        text
+    }
+}
+
+/// テンプレートの`{value}`と`{formula}`を置換（内部ヘルパー）
+///
+/// 置換後の文字列に含まれるプレースホルダーは再度置換しないよう、テンプレートを1回だけ走査します。
+fn apply_formula_template(template: &str, value: &str, formula: &str) -> String {
+    let mut result = String::with_capacity(template.len() + value.len() + formula.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if let Some(after) = rest.strip_prefix("{value}") {
+            result.push_str(value);
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("{formula}") {
+            result.push_str(formula);
+            rest = after;
+        } else {
+            result.push('{');
+            rest = &rest[1..];
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
 impl Default for CellFormatter {
     fn default() -> Self {
         Self::new()
@@ -202,7 +399,7 @@ impl Default for CellFormatter {
 ///
 /// Excelのシリアル日付値を文字列に変換します。
 /// Phase Iでは常に1900年エポックとして処理します。
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct DateFormatter;
 
 impl DateFormatter {
@@ -235,11 +432,83 @@ impl DateFormatter {
         config: &ConversionConfig,
         is_1904: bool,
     ) -> Result<String, XlsxToMdError> {
+        let date = self.to_naive_date(serial_value, is_1904)?;
+
+        // DateFormatに応じてフォーマット
+        let formatted = match &config.date_format {
+            DateFormat::Iso8601 => date.format("%Y-%m-%d").to_string(),
+            DateFormat::Custom(format_str) => date.format(format_str).to_string(),
+        };
+
+        Ok(formatted)
+    }
+
+    /// 日付・時刻値を種別に応じてフォーマット
+    ///
+    /// # 引数
+    ///
+    /// * `serial_value` - Excelのシリアル値
+    /// * `kind` - 日付・時刻の種別
+    /// * `config` - 変換設定
+    /// * `is_1904` - 1904年エポックを使用するかどうか
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(String)` - フォーマット済み文字列
+    ///   - 日付: `DateFormat`に従う（例: `2025-01-02`）
+    ///   - 時刻: `HH:MM:SS`（経過時間の場合、時は24以上になり得る）
+    ///   - 日付と時刻: ISO 8601の場合は`2025-01-02T18:00:00`、
+    ///     カスタム形式の場合はその形式
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn format_kind(
+        &self,
+        serial_value: f64,
+        kind: DateKind,
+        config: &ConversionConfig,
+        is_1904: bool,
+    ) -> Result<String, XlsxToMdError> {
+        match kind {
+            DateKind::Date => self.format(serial_value, config, is_1904),
+            DateKind::Time => {
+                let total_seconds = (serial_value.abs() * 86_400.0).round() as u64;
+                Ok(format!(
+                    "{:02}:{:02}:{:02}",
+                    total_seconds / 3600,
+                    total_seconds / 60 % 60,
+                    total_seconds % 60
+                ))
+            }
+            DateKind::DateTime => {
+                // 秒単位に丸め、繰り上がりは日付に反映する
+                let total_seconds = (serial_value * 86_400.0).round();
+                let days = (total_seconds / 86_400.0).floor();
+                let seconds = (total_seconds - days * 86_400.0) as u32;
+                let date = self.to_naive_date(days, is_1904)?;
+                let time =
+                    NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).ok_or_else(|| {
+                        XlsxToMdError::format(format!(
+                            "Invalid time value: serial_value={}",
+                            serial_value
+                        ))
+                    })?;
+                let datetime = date.and_time(time);
+
+                let formatted = match &config.date_format {
+                    DateFormat::Iso8601 => datetime.format("%Y-%m-%dT%H:%M:%S").to_string(),
+                    DateFormat::Custom(format_str) => datetime.format(format_str).to_string(),
+                };
+                Ok(formatted)
+            }
+        }
+    }
+
+    /// シリアル値の日付部分をNaiveDateに変換（内部ヘルパー）
+    fn to_naive_date(&self, serial_value: f64, is_1904: bool) -> Result<NaiveDate, XlsxToMdError> {
         let (epoch, days_offset) = if is_1904 {
             // 1904年システム: 1904年1月1日起算
             // シリアル値0 = 1904-01-01
             let epoch = NaiveDate::from_ymd_opt(1904, 1, 1)
-                .ok_or_else(|| XlsxToMdError::Config("Invalid epoch date".to_string()))?;
+                .ok_or_else(|| XlsxToMdError::format("Invalid epoch date"))?;
             (epoch, 0i64)
         } else {
             // 1900年システム: 1899年12月30日起算
@@ -248,29 +517,24 @@ impl DateFormatter {
             // シリアル値1 = 1900年1月1日
             // エポック1899-12-30から、シリアル値1で1900-01-01になるように調整
             let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)
-                .ok_or_else(|| XlsxToMdError::Config("Invalid epoch date".to_string()))?;
+                .ok_or_else(|| XlsxToMdError::format("Invalid epoch date"))?;
             // シリアル値に対して +1日を加算する必要がある
             (epoch, 1i64)
         };
 
         // シリアル値からNaiveDateに変換
         let days = serial_value.floor() as i64;
-        let date = epoch
-            .checked_add_signed(Duration::days(days + days_offset))
+        // 範囲外のシリアル値ではDuration::daysがパニックするため、fallibleな変換を使用する
+        let date = Duration::try_days(days.saturating_add(days_offset))
+            .and_then(|offset| epoch.checked_add_signed(offset))
             .ok_or_else(|| {
-                XlsxToMdError::Config(format!(
+                XlsxToMdError::format(format!(
                     "Date calculation overflow: serial_value={}, is_1904={}",
                     serial_value, is_1904
                 ))
             })?;
 
-        // DateFormatに応じてフォーマット
-        let formatted = match &config.date_format {
-            DateFormat::Iso8601 => date.format("%Y-%m-%d").to_string(),
-            DateFormat::Custom(format_str) => date.format(format_str).to_string(),
-        };
-
-        Ok(formatted)
+        Ok(date)
     }
 }
 
@@ -278,7 +542,7 @@ impl DateFormatter {
 ///
 /// 数値を文字列に変換します。
 /// Phase IIでNumber Format Parserを使用してNumber Format Stringを解析・適用します。
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct NumberFormatter;
 
 impl NumberFormatter {
@@ -330,7 +594,7 @@ impl NumberFormatter {
 mod tests {
     use super::*;
     use crate::api::{DateFormat, FormulaMode};
-    use crate::types::{CellCoord, CellValue, RawCellData};
+    use crate::types::{CellCoord, CellError, CellValue, RawCellData};
 
     fn create_test_config() -> ConversionConfig {
         ConversionConfig::default()
@@ -397,6 +661,46 @@ mod tests {
         assert!(!formatter.is_date_value(-1.0, &None, &None));
     }
 
+    #[test]
+    fn test_date_formatter_format_kind() {
+        let formatter = DateFormatter;
+        let config = create_test_config();
+
+        let result = formatter
+            .format_kind(45658.0, DateKind::Date, &config, false)
+            .unwrap();
+        assert_eq!(result, "2025-01-02");
+
+        let result = formatter
+            .format_kind(0.75, DateKind::Time, &config, false)
+            .unwrap();
+        assert_eq!(result, "18:00:00");
+
+        // 経過時間（24時間超）
+        let result = formatter
+            .format_kind(1.25, DateKind::Time, &config, false)
+            .unwrap();
+        assert_eq!(result, "30:00:00");
+
+        let result = formatter
+            .format_kind(45658.5, DateKind::DateTime, &config, false)
+            .unwrap();
+        assert_eq!(result, "2025-01-02T12:00:00");
+
+        // 秒の丸めによる日付の繰り上がり
+        let result = formatter
+            .format_kind(45_658.999_999_9, DateKind::DateTime, &config, false)
+            .unwrap();
+        assert_eq!(result, "2025-01-03T00:00:00");
+
+        let config =
+            create_test_config_with_date_format(DateFormat::Custom("%Y/%m/%d %H:%M".to_string()));
+        let result = formatter
+            .format_kind(45658.5, DateKind::DateTime, &config, false)
+            .unwrap();
+        assert_eq!(result, "2025/01/02 12:00");
+    }
+
     #[test]
     fn test_date_formatter_iso8601() {
         let formatter = DateFormatter;
@@ -414,6 +718,10 @@ mod tests {
         // エポック1899-12-30 + (45658+1)日 = 2025-01-02
         let result = formatter.format(45658.0, &config, false).unwrap();
         assert_eq!(result, "2025-01-02");
+
+        // 範囲外のシリアル値はパニックせずにエラーを返す
+        assert!(formatter.format(1e300, &config, false).is_err());
+        assert!(formatter.format(-1e300, &config, true).is_err());
     }
 
     #[test]
@@ -481,12 +789,134 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
         assert_eq!(result, "123.45");
     }
 
+    #[test]
+    fn test_format_cell_null_formula_policy() {
+        let formatter = CellFormatter::new();
+        let raw_cell = RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::Empty,
+            format_id: None,
+            format_string: None,
+            formula: Some("SUM(A1:A3)".to_string()),
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+
+        // デフォルトは空セル
+        let config = create_test_config();
+        assert_eq!(
+            formatter.format_cell(&raw_cell, &config, false).unwrap(),
+            ""
+        );
+
+        // マーカー指定時はマーカーを出力
+        let config = ConversionConfig {
+            null_formula_policy: NullFormulaPolicy::Marker("#NOVALUE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            formatter.format_cell(&raw_cell, &config, false).unwrap(),
+            "#NOVALUE"
+        );
+
+        // 数式を持たない空セルには影響しない
+        let empty_cell = RawCellData {
+            formula: None,
+            ..raw_cell.clone()
+        };
+        assert_eq!(
+            formatter.format_cell(&empty_cell, &config, false).unwrap(),
+            ""
+        );
+
+        // 数式モードでは数式を出力
+        let config = ConversionConfig {
+            formula_mode: FormulaMode::Formula,
+            null_formula_policy: NullFormulaPolicy::Marker("#NOVALUE".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            formatter.format_cell(&raw_cell, &config, false).unwrap(),
+            "SUM(A1:A3)"
+        );
+    }
+
+    #[test]
+    fn test_format_cell_number_output() {
+        use crate::api::OutputFormat;
+
+        let formatter = CellFormatter::new();
+        let raw_cell = RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::Number(1234.56),
+            format_id: None,
+            format_string: Some("#,##0.00".to_string()),
+            formula: None,
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+
+        // Markdown（デフォルト: Formatted）は書式を適用
+        let config = create_test_config();
+        let formatted = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(formatted, "1,234.56");
+
+        // CSV（デフォルト: Raw）は生の数値
+        let config = ConversionConfig {
+            output_format: OutputFormat::Csv,
+            ..Default::default()
+        };
+        let raw = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(raw, "1234.56");
+        assert_eq!(formatter.raw_number(&raw_cell, &config), Some(1234.56));
+
+        // 表示文字列を変更するハイパーリンク・数式のセルは生の数値として扱わない
+        let linked = RawCellData {
+            hyperlink: Some(Hyperlink {
+                url: "https://example.com/".to_string(),
+                display: Some("1234.56".to_string()),
+                tooltip: None,
+            }),
+            ..raw_cell.clone()
+        };
+        assert_eq!(formatter.raw_number(&linked, &config), None);
+        let with_formula = RawCellData {
+            formula: Some("=A1*2".to_string()),
+            ..raw_cell.clone()
+        };
+        let both = ConversionConfig {
+            formula_mode: FormulaMode::Both,
+            ..config.clone()
+        };
+        assert_eq!(formatter.raw_number(&with_formula, &config), Some(1234.56));
+        assert_eq!(formatter.raw_number(&with_formula, &both), None);
+
+        // 明示的な指定はデフォルトより優先
+        let config = ConversionConfig {
+            output_format: OutputFormat::Csv,
+            number_outputs: vec![(OutputFormat::Csv, NumberOutput::Formatted)],
+            ..Default::default()
+        };
+        let formatted = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(formatted, "1,234.56");
+        assert_eq!(formatter.raw_number(&raw_cell, &config), None);
+    }
+
     #[test]
     fn test_format_cell_string() {
         let formatter = CellFormatter::new();
@@ -500,6 +930,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
@@ -519,6 +952,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let raw_cell_false = RawCellData {
@@ -529,6 +965,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         assert_eq!(
@@ -552,12 +991,15 @@ mod tests {
 
         let raw_cell = RawCellData {
             coord: CellCoord::new(0, 0),
-            value: CellValue::Error("#DIV/0!".to_string()),
+            value: CellValue::Error(CellError::Div0),
             format_id: None,
             format_string: None,
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
@@ -577,6 +1019,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
@@ -597,6 +1042,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
@@ -616,6 +1064,9 @@ mod tests {
             formula: Some("=SUM(A1:A10)".to_string()),
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         // CachedValueモードでは数式を無視して値をフォーマット
@@ -636,6 +1087,9 @@ mod tests {
             formula: Some("=SUM(A1:A10)".to_string()),
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         // Formulaモードでは数式をそのまま返す
@@ -656,6 +1110,9 @@ mod tests {
             formula: None, // 数式がない場合
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         // 数式がない場合は通常の値としてフォーマット
@@ -663,6 +1120,144 @@ mod tests {
         assert_eq!(result, "100");
     }
 
+    #[test]
+    fn test_format_cell_formula_mode_both() {
+        let formatter = CellFormatter::new();
+        let mut config = create_test_config_with_formula_mode(FormulaMode::Both);
+
+        let mut raw_cell = RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::Number(100.0),
+            format_id: None,
+            format_string: None,
+            formula: Some("SUM(A1:D1)".to_string()),
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+
+        // Bothモードでは結果値と数式をテンプレートで組み合わせる
+        let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(result, "100 (=SUM(A1:D1))");
+
+        // 先頭の`=`は重複させず、`|`はエスケープする
+        raw_cell.formula = Some("=IF(A1=\"|\",1,2)".to_string());
+        config.formula_template = "{value} ← {formula} {other}".to_string();
+        let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(result, "100 ← IF(A1=\"\\|\",1,2) {other}");
+
+        // 数式がない場合は結果値のみ
+        raw_cell.formula = None;
+        let result = formatter.format_cell(&raw_cell, &config, false).unwrap();
+        assert_eq!(result, "100");
+    }
+
+    #[test]
+    fn test_apply_formula_template_single_pass() {
+        assert_eq!(
+            apply_formula_template("{value} (={formula})", "{formula}", "A1"),
+            "{formula} (=A1)"
+        );
+        assert_eq!(apply_formula_template("{{value}}", "1", "A1"), "{1}");
+    }
+
+    #[test]
+    fn test_format_cell_hyperlink_text() {
+        let formatter = CellFormatter::new();
+        let config = create_test_config();
+        let cell = |value: &str, display: Option<&str>, tooltip: Option<&str>| RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::String(value.to_string()),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: Some(Hyperlink {
+                url: "https://example.com/docs".to_string(),
+                display: display.map(str::to_string),
+                tooltip: tooltip.map(str::to_string),
+            }),
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+        let format = |cell: RawCellData| formatter.format_cell(&cell, &config, false).unwrap();
+
+        assert_eq!(
+            format(cell("Docs", None, None)),
+            "[Docs](https://example.com/docs)"
+        );
+        // 表示テキスト、ヒントの順にセルの値より優先
+        assert_eq!(
+            format(cell("Docs", Some("User | Guide"), Some("Open"))),
+            "[User \\| Guide](https://example.com/docs)"
+        );
+        assert_eq!(
+            format(cell("Docs", Some(" "), Some("Open the guide"))),
+            "[Open the guide](https://example.com/docs)"
+        );
+    }
+
+    #[test]
+    fn test_format_cell_url_shortening() {
+        let formatter = CellFormatter::new();
+        let url = "https://user@docs.example.com/guide/install?lang=ja";
+        let cell = |value: &str| RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::String(value.to_string()),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: Some(Hyperlink {
+                url: url.to_string(),
+                display: None,
+                tooltip: None,
+            }),
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+        let format = |cell: RawCellData, config: &ConversionConfig| {
+            formatter.format_cell(&cell, config, false).unwrap()
+        };
+
+        let config = ConversionConfig {
+            url_shortening: Some(30),
+            ..Default::default()
+        };
+        // URL自体を表示するセルは短縮し、リンク先は元のURL
+        let short = format!("[docs.example.com/…]({})", url);
+        assert_eq!(format(cell(url), &config), short);
+        assert_eq!(format(cell(""), &config), short);
+        // URL以外の表示テキストは短縮しない
+        assert_eq!(format(cell("Guide"), &config), format!("[Guide]({})", url));
+        // 上限以下のURLは短縮しない
+        let config = ConversionConfig {
+            url_shortening: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(format(cell(url), &config), format!("[{0}]({0})", url));
+        // Markdown以外・TextOnlyでは短縮しない
+        let html = ConversionConfig {
+            url_shortening: Some(30),
+            output_format: OutputFormat::Html,
+            ..Default::default()
+        };
+        assert_eq!(
+            format(cell(url), &html),
+            format!("<a href=\"{0}\">{0}</a>", url)
+        );
+        let text_only = ConversionConfig {
+            url_shortening: Some(30),
+            hyperlink_mode: HyperlinkMode::TextOnly,
+            ..Default::default()
+        };
+        assert_eq!(format(cell(url), &text_only), url);
+    }
+
     // プロパティベーステスト: TC-PBT-002
     #[allow(unused_doc_comments)]
     mod property_tests {
@@ -3,25 +3,121 @@
 //! スパースなセルデータから稠密なグリッド構造への変換を提供するモジュール。
 //! セル結合の処理戦略（DataDuplication / HtmlFallback）を実装します。
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io::Write;
 
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
+use xxhash_rust::xxh3::Xxh3;
+
+use crate::api::{
+    ChunkHeader, ContentFilter, DuplicationPlacement, MergeStrategy, SortOrder, TrimMode,
+    WrapStrategy,
+};
+use crate::error::{ConfigError, XlsxToMdError};
+use crate::output::Footnotes;
+use crate::report::FoldedColumn;
+use crate::types::{
+    CellCoord, CellRange, CellValue, MergedRegion, RawCellData, RichTextFormat, SheetMetadata,
+    TableInfo,
+};
+
+/// 使用範囲の肥大化と判定する最小セル数（行数 × 列数）
+const BLOAT_MIN_CELLS: usize = 100_000;
+
+/// 使用範囲の肥大化と判定する密度の閾値（表示内容のあるセルが1/N未満の場合）
+const BLOAT_DENSITY_RATIO: usize = 100;
+
+/// 条件付き書式の単純なルールに該当するセルに付加するマーカー（`ConditionalFormats::Marker`）
+const HIGHLIGHT_MARKER: &str = "⚠";
+
+/// フォントの書式をHTMLの`class`属性値（例: `"bold italic"`）に変換（内部ヘルパー）
+fn font_classes(font: &RichTextFormat) -> String {
+    [
+        (font.bold, "bold"),
+        (font.italic, "italic"),
+        (font.strike, "strike"),
+    ]
+    .iter()
+    .filter(|(enabled, _)| *enabled)
+    .map(|(_, class)| *class)
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// Markdownの表の列幅の上限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WidthLimit {
+    /// 列幅の上限（表示幅）
+    pub width: usize,
+
+    /// 上限を超えるセルの扱い
+    pub strategy: WrapStrategy,
+
+    /// 切り詰めたセルの末尾に付加する文字列（`WrapStrategy::Truncate`の場合のみ）
+    pub marker: String,
+}
 
-use crate::api::MergeStrategy;
-use crate::error::XlsxToMdError;
-use crate::types::{CellCoord, MergedRegion, RawCellData, SheetMetadata};
+#[cfg(test)]
+impl WidthLimit {
+    /// 省略記号（`…`）で切り詰める列幅の上限を生成
+    pub fn new(width: usize, strategy: WrapStrategy) -> Self {
+        Self {
+            width,
+            strategy,
+            marker: "…".to_string(),
+        }
+    }
+}
 
 /// フォーマット済みセル
 #[derive(Debug, Clone)]
 pub(crate) struct Cell {
-    /// 表示文字列
-    pub content: String,
+    /// 表示文字列（`number`と整合させるため、変更は`set_content`または`content_mut`を経由）
+    content: String,
+
+    /// 元のセル値（フォーマット前）
+    pub value: CellValue,
+
+    /// 表示文字列が生の数値（`NumberOutput::Raw`）の場合の数値
+    ///
+    /// フォーマッターが書式を適用せずに数値を出力したセルのみ`Some`です。
+    /// 表示文字列を変更した場合（注記やマーカーの付加など）は`None`に戻します。
+    pub number: Option<f64>,
 
     /// 結合セルの一部かどうか
     pub is_merged: bool,
 
     /// 結合セルの親座標（結合セルの場合）
     pub merge_parent: Option<CellCoord>,
+
+    /// キャッシュ値を持たない数式セルかどうか
+    pub formula_no_value: bool,
+
+    /// セルに付加する脚注の本文（Markdown出力時に参照へ置き換える）
+    pub footnotes: Vec<String>,
+
+    /// 名前付きスタイル名（例: "Heading 1"）
+    pub style: Option<String>,
+
+    /// セル単位のフォントの書式（HTML形式で`class`属性として出力）
+    pub font: Option<RichTextFormat>,
+
+    /// セルの背景色（RGBの16進数6桁、HTML形式で`style`属性として出力）
+    pub fill_color: Option<String>,
+
+    /// ハイパーリンクのURL（JSON形式で`url`、XML形式で`href`属性として出力）
+    pub hyperlink: Option<String>,
+
+    /// 該当する条件付き書式のルールの説明（`ConditionalFormats::Annotate`、型付きJSONの`Marker`の場合のみ）
+    pub highlights: Vec<String>,
+
+    /// 元のシート上の座標（0始まりの絶対座標、計算列など元のセルがない場合はNone）
+    ///
+    /// 行の並べ替えや除去でセルとともに移動するため、出力時に元のセル参照を示すために使用します。
+    pub source: Option<CellCoord>,
 }
 
 impl Cell {
@@ -29,8 +125,18 @@ impl Cell {
     pub fn new(content: String) -> Self {
         Self {
             content,
+            value: CellValue::Empty,
+            number: None,
             is_merged: false,
             merge_parent: None,
+            formula_no_value: false,
+            footnotes: Vec::new(),
+            style: None,
+            font: None,
+            fill_color: None,
+            hyperlink: None,
+            highlights: Vec::new(),
+            source: None,
         }
     }
 
@@ -38,22 +144,108 @@ impl Cell {
     pub fn new_merged(content: String, parent: CellCoord) -> Self {
         Self {
             content,
+            value: CellValue::Empty,
+            number: None,
             is_merged: true,
             merge_parent: Some(parent),
+            formula_no_value: false,
+            footnotes: Vec::new(),
+            style: None,
+            font: None,
+            fill_color: None,
+            hyperlink: None,
+            highlights: Vec::new(),
+            source: None,
         }
     }
 
+    /// 表示文字列を取得
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// 表示文字列を置き換え
+    ///
+    /// 表示文字列は生の数値ではなくなるため、`number`を解除します。
+    pub fn set_content(&mut self, content: String) {
+        self.number = None;
+        self.content = content;
+    }
+
+    /// 表示文字列を変更するための参照を取得
+    ///
+    /// 表示文字列は生の数値ではなくなるため、`number`を解除します。
+    pub fn content_mut(&mut self) -> &mut String {
+        self.number = None;
+        &mut self.content
+    }
+
     /// 空セルを生成
     pub fn empty() -> Self {
         Self {
             content: String::new(),
+            value: CellValue::Empty,
+            number: None,
             is_merged: false,
             merge_parent: None,
+            formula_no_value: false,
+            footnotes: Vec::new(),
+            style: None,
+            font: None,
+            fill_color: None,
+            hyperlink: None,
+            highlights: Vec::new(),
+            source: None,
+        }
+    }
+}
+
+/// コンパイル済みの行の絞り込み条件
+///
+/// `ContentFilter`の正規表現をコンパイルしたもので、`LogicalGrid::filter_rows`で使用します。
+#[derive(Debug, Clone)]
+pub(crate) struct RowFilter {
+    /// 出力する行の条件
+    include: Option<Regex>,
+
+    /// 除去する行の条件
+    exclude: Option<Regex>,
+}
+
+impl RowFilter {
+    /// `ContentFilter`の正規表現をコンパイル
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Some(RowFilter))` - 条件が指定されている場合
+    /// * `Ok(None)` - 条件が1つも指定されていない場合
+    /// * `Err(XlsxToMdError::Config)` - 正規表現が不正な場合
+    pub fn compile(filter: &ContentFilter) -> Result<Option<Self>, XlsxToMdError> {
+        let compile = |pattern: &Option<String>| {
+            pattern
+                .as_deref()
+                .map(|pattern| {
+                    Regex::new(pattern).map_err(|e| {
+                        XlsxToMdError::Config(ConfigError::InvalidSyntax {
+                            option: "with_content_filter",
+                            text: pattern.to_string(),
+                            message: e.to_string(),
+                        })
+                    })
+                })
+                .transpose()
+        };
+        let include = compile(&filter.include_rows_matching)?;
+        let exclude = compile(&filter.exclude_rows_matching)?;
+        if include.is_none() && exclude.is_none() {
+            return Ok(None);
         }
+        Ok(Some(Self { include, exclude }))
     }
 }
 
 /// 論理的なグリッド構造
+#[derive(Debug, Clone)]
 pub(crate) struct LogicalGrid {
     /// グリッドデータ（行 × 列）
     cells: Vec<Vec<Cell>>,
@@ -63,6 +255,9 @@ pub(crate) struct LogicalGrid {
 
     /// 列数
     cols: usize,
+
+    /// セル結合の処理戦略
+    merge_strategy: MergeStrategy,
 }
 
 impl LogicalGrid {
@@ -86,23 +281,33 @@ impl LogicalGrid {
         merge_strategy: MergeStrategy,
     ) -> Result<Self, XlsxToMdError> {
         // 1. グリッドサイズの決定
-        let (rows, cols) = Self::determine_grid_size(&cells);
+        let (rows, cols) = Self::grid_size(&cells);
 
         // 2. 空のグリッドを初期化
         let mut grid_cells = vec![vec![Cell::empty(); cols]; rows];
 
-        // 3. フォーマット済みセルデータを配置
+        // 3. フォーマット済みセルデータと元のセル値を配置
         for (coord, content) in formatted_cells {
             if coord.row < rows as u32 && coord.col < cols as u32 {
                 grid_cells[coord.row as usize][coord.col as usize] = Cell::new(content);
             }
         }
+        for cell in cells {
+            let grid_cell = &mut grid_cells[cell.coord.row as usize][cell.coord.col as usize];
+            grid_cell.formula_no_value = cell.is_formula_without_value();
+            grid_cell.value = cell.value;
+            grid_cell.style = cell.style_name;
+            grid_cell.font = cell.font;
+            grid_cell.fill_color = cell.fill_color;
+            grid_cell.hyperlink = cell.hyperlink.map(|link| link.url);
+        }
 
         // 4. セル結合の処理
         let mut grid = LogicalGrid {
             cells: grid_cells,
             rows,
             cols,
+            merge_strategy,
         };
 
         match merge_strategy {
@@ -110,18 +315,80 @@ impl LogicalGrid {
                 grid.apply_data_duplication(&metadata.merged_regions)?;
             }
             MergeStrategy::HtmlFallback => {
-                // HTMLフォールバックの場合、グリッド処理はスキップ
+                // HTMLフォールバックの場合、子セルを結合セルとしてマークするのみ
                 // 後段のMarkdown Writerで直接HTML出力
+                grid.mark_merged_children(&metadata.merged_regions);
+            }
+        }
+
+        // 5. 元のシート上の座標を記録（結合セルの複製後に設定）
+        for (row_idx, row) in grid.cells.iter_mut().enumerate() {
+            for (col_idx, cell) in row.iter_mut().enumerate() {
+                cell.source = Some(CellCoord::new(
+                    metadata.first_row + row_idx as u32,
+                    metadata.first_col + col_idx as u32,
+                ));
             }
         }
 
         Ok(grid)
     }
 
-    /// グリッドサイズを決定（内部ヘルパー）
+    /// 生の数値として出力したセルの数値を設定
+    ///
+    /// 結合セルの子セルは親セルの数値を引き継ぎます。表示文字列が空のセル
+    /// （HTMLフォールバックの子セルなど）には設定しません。
+    ///
+    /// # 引数
+    ///
+    /// * `numbers` - フォーマッターが生の数値を出力したセルの座標と数値
+    pub(crate) fn set_raw_numbers(&mut self, numbers: &HashMap<CellCoord, f64>) {
+        if numbers.is_empty() {
+            return;
+        }
+        for (row_idx, row) in self.cells.iter_mut().enumerate() {
+            for (col_idx, cell) in row.iter_mut().enumerate() {
+                if cell.content.is_empty() {
+                    continue;
+                }
+                let coord = cell
+                    .merge_parent
+                    .unwrap_or_else(|| CellCoord::new(row_idx as u32, col_idx as u32));
+                cell.number = numbers.get(&coord).copied();
+            }
+        }
+    }
+
+    /// 表示文字列の行からグリッドを構築
+    ///
+    /// シートのセルに由来しない表（グラフのデータなど）の出力に使用します。
+    /// 列数は最も長い行に合わせ、不足するセルは空のセルで埋めます。
+    ///
+    /// # 引数
+    ///
+    /// * `rows` - 各行のセルの表示文字列（出力フォーマットに応じてエスケープ済み）
+    pub(crate) fn from_rows(rows: Vec<Vec<String>>) -> Self {
+        let cols = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let cells: Vec<Vec<Cell>> = rows
+            .into_iter()
+            .map(|row| {
+                let mut cells: Vec<Cell> = row.into_iter().map(Cell::new).collect();
+                cells.resize(cols, Cell::empty());
+                cells
+            })
+            .collect();
+        LogicalGrid {
+            rows: cells.len(),
+            cols,
+            cells,
+            merge_strategy: MergeStrategy::DataDuplication,
+        }
+    }
+
+    /// グリッドサイズを決定
     ///
     /// すべてのセル座標から最大行・列を算出します。
-    fn determine_grid_size(cells: &[RawCellData]) -> (usize, usize) {
+    pub fn grid_size(cells: &[RawCellData]) -> (usize, usize) {
         let mut max_row = 0;
         let mut max_col = 0;
 
@@ -136,26 +403,39 @@ impl LogicalGrid {
     /// データ重複フィル戦略を適用（内部メソッド）
     ///
     /// 結合セル範囲内のすべてのセルに親セルの値を複製します。
+    /// グリッドの外側にはみ出した部分は処理しません。
     fn apply_data_duplication(
         &mut self,
         merged_regions: &[MergedRegion],
     ) -> Result<(), XlsxToMdError> {
         for region in merged_regions {
             // 親セルの内容を取得
-            let parent_content = self.cells[region.parent.row as usize][region.parent.col as usize]
-                .content
-                .clone();
+            let Some(parent) = self
+                .cells
+                .get(region.parent.row as usize)
+                .and_then(|row| row.get(region.parent.col as usize))
+            else {
+                continue;
+            };
+            let parent_content = parent.content.clone();
+            let parent_value = parent.value.clone();
+            let parent_number = parent.number;
+            let parent_style = parent.style.clone();
 
             // 結合範囲内のすべてのセルに複製
-            for row in region.range.start.row..=region.range.end.row {
-                for col in region.range.start.col..=region.range.end.col {
+            let (rows, cols) = self.clamped_range(region);
+            for row in rows {
+                for col in cols.clone() {
                     if row == region.parent.row && col == region.parent.col {
                         // 親セルはスキップ
                         continue;
                     }
 
-                    self.cells[row as usize][col as usize] =
-                        Cell::new_merged(parent_content.clone(), region.parent);
+                    let mut cell = Cell::new_merged(parent_content.clone(), region.parent);
+                    cell.value = parent_value.clone();
+                    cell.number = parent_number;
+                    cell.style = parent_style.clone();
+                    self.cells[row as usize][col as usize] = cell;
                 }
             }
         }
@@ -163,54 +443,175 @@ impl LogicalGrid {
         Ok(())
     }
 
+    /// 結合セル範囲のうちグリッドに収まる行・列の範囲（内部ヘルパー）
+    fn clamped_range(
+        &self,
+        region: &MergedRegion,
+    ) -> (std::ops::RangeInclusive<u32>, std::ops::RangeInclusive<u32>) {
+        let clamp = |start: u32, end: u32, size: usize| {
+            if start as usize >= size {
+                // グリッドの外側にある場合は空の範囲
+                std::ops::RangeInclusive::new(1, 0)
+            } else {
+                start..=end.min(size as u32 - 1)
+            }
+        };
+        (
+            clamp(region.range.start.row, region.range.end.row, self.rows),
+            clamp(region.range.start.col, region.range.end.col, self.cols),
+        )
+    }
+
+    /// 縦方向に結合されたセルの値を、指定した行のみに残す
+    ///
+    /// `MergeStrategy::DataDuplication`で複製した後に呼び出します。値を残す行以外のセルは
+    /// 空になります。中央の行に残す場合は、親セル（脚注・条件付き書式の注記を含む）を
+    /// 中央の行に移動し、元の位置を空の結合セルとします。
+    ///
+    /// # 引数
+    ///
+    /// * `merged_regions` - 結合セル範囲のリスト
+    /// * `placement` - 値を配置する行
+    pub fn apply_duplication_placement(
+        &mut self,
+        merged_regions: &[MergedRegion],
+        placement: DuplicationPlacement,
+    ) {
+        for region in merged_regions {
+            let (start, end) = (region.range.start.row, region.range.end.row);
+            if start == end || end as usize >= self.rows {
+                continue;
+            }
+            let target = match placement {
+                DuplicationPlacement::FirstOnly => start,
+                DuplicationPlacement::Middle => start + (end - start) / 2,
+                _ => continue,
+            };
+
+            // 親セルを値を残す行に移動
+            let parent_col = region.parent.col as usize;
+            if target != region.parent.row && parent_col < self.cols {
+                let moved = CellCoord::new(target, region.parent.col);
+                let (upper, lower) = self.cells.split_at_mut(target as usize);
+                let (from, to) = (
+                    &mut upper[region.parent.row as usize][parent_col],
+                    &mut lower[0][parent_col],
+                );
+                std::mem::swap(from, to);
+                // 元のシート上の座標は位置に固定する
+                std::mem::swap(&mut from.source, &mut to.source);
+                for row in start..=end {
+                    for col in region.range.start.col..=region.range.end.col {
+                        if let Some(cell) = self.cells[row as usize].get_mut(col as usize) {
+                            if cell.merge_parent.is_some() {
+                                cell.merge_parent = Some(moved);
+                            }
+                        }
+                    }
+                }
+            }
+
+            for row in (start..=end).filter(|&row| row != target) {
+                for col in region.range.start.col..=region.range.end.col {
+                    if let Some(cell) = self.cells[row as usize].get_mut(col as usize) {
+                        cell.content_mut().clear();
+                        cell.value = CellValue::Empty;
+                        cell.style = None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 結合セル範囲内の子セルをマーク（内部メソッド）
+    ///
+    /// 親セル以外のセルを空の結合セルに置き換えます。
+    /// HTML出力時、マークされたセルは`<td>`を出力せずスキップされます。
+    pub(crate) fn mark_merged_children(&mut self, merged_regions: &[MergedRegion]) {
+        for region in merged_regions {
+            let (rows, cols) = self.clamped_range(region);
+            for row in rows {
+                for col in cols.clone() {
+                    if row == region.parent.row && col == region.parent.col {
+                        continue;
+                    }
+
+                    self.cells[row as usize][col as usize] =
+                        Cell::new_merged(String::new(), region.parent);
+                }
+            }
+        }
+    }
+
     /// Markdownテーブルとして出力
     ///
+    /// 列幅の上限を指定した場合は、表示幅が上限を超えるセルを、`WrapStrategy::Wrap`では`<br>`で折り返し、
+    /// `WrapStrategy::Truncate`では省略記号（`…`などの指定した文字列）で切り詰めます。
+    /// 上限を適用する場合の列幅は、セル内容の`<br>`で区切った各行の表示幅から計算するため、
+    /// 1つの長いセルのために他のすべての行が空白で埋められることはありません。
+    ///
     /// # 引数
     ///
     /// * `writer` - 出力先のライター
+    /// * `width_limit` - 列幅の上限（Noneの場合は上限なし）
     ///
     /// # 戻り値
     ///
     /// * `Ok(())` - 出力に成功した場合
     /// * `Err(XlsxToMdError)` - エラーが発生した場合
-    pub fn render_markdown<W: Write>(&self, writer: &mut W) -> Result<(), XlsxToMdError> {
+    pub fn render_markdown<W: Write>(
+        &self,
+        writer: &mut W,
+        width_limit: Option<&WidthLimit>,
+    ) -> Result<(), XlsxToMdError> {
         if self.rows == 0 || self.cols == 0 {
             return Ok(());
         }
 
-        // 1. 列幅の計算
-        let col_widths = self.calculate_column_widths();
+        // 1. 列幅の計算（上限を適用する場合は、適用後のセル内容の各行の表示幅から計算）
+        let limited: Option<Vec<Vec<String>>> = width_limit.map(|limit| {
+            self.cells
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|cell| limit_cell_width(cell.content.trim(), limit))
+                        .collect()
+                })
+                .collect()
+        });
+        let col_widths = match &limited {
+            Some(contents) => {
+                let mut widths = vec![3; self.cols];
+                for row in contents {
+                    for (col_idx, content) in row.iter().enumerate() {
+                        let line_width = content.split("<br>").map(|line| line.width()).max();
+                        widths[col_idx] = widths[col_idx].max(line_width.unwrap_or(0));
+                    }
+                }
+                widths
+            }
+            None => self.calculate_column_widths(),
+        };
 
         // 2. ヘッダー区切り行
         let separator = self.generate_separator(&col_widths);
 
         // 3. 各行の出力
         for (row_idx, row) in self.cells.iter().enumerate() {
-            write!(writer, "|")?;
-
-            for (col_idx, cell) in row.iter().enumerate() {
-                let width = col_widths[col_idx];
-                // セル内容をtrimしてからフォーマット
-                let trimmed_content = cell.content.trim();
-                // 表示幅を計算（全角文字は2、半角文字は1）
-                let content_width = trimmed_content.width();
-
-                // セルの前にスペースを1つ入れる
-                write!(writer, " ")?;
-                // 左揃えでセル内容を出力
-                write!(writer, "{}", trimmed_content)?;
-                // 残りのスペースを埋める（表示幅に基づく）
-                if content_width < width {
-                    for _ in content_width..width {
-                        write!(writer, " ")?;
-                    }
-                }
-                // セルの後にスペースを1つ入れる
-                write!(writer, " |")?;
+            // セル内容をtrimしてからフォーマット（上限を適用する場合は適用後の内容）
+            match &limited {
+                Some(contents) => write_markdown_row(
+                    writer,
+                    contents[row_idx].iter().map(String::as_str),
+                    &col_widths,
+                )?,
+                None => write_markdown_row(
+                    writer,
+                    row.iter().map(|cell| cell.content.trim()),
+                    &col_widths,
+                )?,
             }
 
-            writeln!(writer)?;
-
             // 最初の行の後に区切り行を挿入
             if row_idx == 0 {
                 writeln!(writer, "{}", separator)?;
@@ -223,6 +624,163 @@ impl LogicalGrid {
         Ok(())
     }
 
+    /// 指定した行数ごとに分割した表としてMarkdownを出力
+    ///
+    /// データ行（先頭行を除く行）を`rows`行ずつに分け、それぞれを空行で区切った表として出力します。
+    /// `ChunkHeader::Repeat`の場合は各表の見出し行に先頭行を繰り返し、`ChunkHeader::FirstOnly`の場合は
+    /// 2つ目以降の表の見出し行を空にします。列幅は表ごとに計算します。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    /// * `width_limit` - 各表に適用する列幅の上限（Noneの場合は上限なし）
+    /// * `chunk_rows` - 1つの表のデータ行数の上限と見出し行の扱い（Noneの場合は分割しない）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn render_markdown_chunks<W: Write>(
+        &self,
+        writer: &mut W,
+        width_limit: Option<&WidthLimit>,
+        chunk_rows: Option<(usize, ChunkHeader)>,
+    ) -> Result<(), XlsxToMdError> {
+        let (rows, header) = match chunk_rows {
+            Some((rows, header)) if rows > 0 && self.rows > rows + 1 => (rows, header),
+            _ => return self.render_markdown(writer, width_limit),
+        };
+
+        for (idx, chunk) in self.cells[1..].chunks(rows).enumerate() {
+            if idx > 0 {
+                writeln!(writer)?;
+            }
+            let head = match header {
+                ChunkHeader::FirstOnly if idx > 0 => vec![Cell::empty(); self.cols],
+                _ => self.cells[0].clone(),
+            };
+            let mut cells = Vec::with_capacity(chunk.len() + 1);
+            cells.push(head);
+            cells.extend_from_slice(chunk);
+            let table = LogicalGrid {
+                cells,
+                rows: chunk.len() + 1,
+                cols: self.cols,
+                merge_strategy: self.merge_strategy,
+            };
+            table.render_markdown(writer, width_limit)?;
+        }
+        Ok(())
+    }
+
+    /// 見出しスタイルの行で区切ったMarkdownとして出力
+    ///
+    /// 表示内容のあるセルがすべて見出しスタイル（"Title"、"Heading 1"〜"Heading 4"）の行を
+    /// Markdownの見出しとして出力し、その前後の行をそれぞれ別の表として出力します。
+    /// シート名の見出し（`#`）の下位となるよう、"Title"と"Heading 1"は`##`、
+    /// "Heading 2"は`###`のように1段下げた見出しレベルを使用します。
+    /// 見出しの後の最初の行は、その表の見出し行になります。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    /// * `width_limit` - 各表に適用する列幅の上限（Noneの場合は上限なし）
+    /// * `chunk_rows` - 各表を分割する行数（`render_markdown_chunks()`を参照）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn render_markdown_sections<W: Write>(
+        &self,
+        writer: &mut W,
+        width_limit: Option<&WidthLimit>,
+        chunk_rows: Option<(usize, ChunkHeader)>,
+    ) -> Result<(), XlsxToMdError> {
+        let mut written = false;
+        let mut start = 0;
+        for row_idx in 0..=self.rows {
+            let level = self
+                .cells
+                .get(row_idx)
+                .and_then(|row| Self::heading_level(row));
+            if row_idx < self.rows && level.is_none() {
+                continue;
+            }
+
+            // 直前の見出しまでの行を1つの表として出力
+            if start < row_idx {
+                if written {
+                    writeln!(writer)?;
+                }
+                let section = LogicalGrid {
+                    cells: self.cells[start..row_idx].to_vec(),
+                    rows: row_idx - start,
+                    cols: self.cols,
+                    merge_strategy: self.merge_strategy,
+                };
+                section.render_markdown_chunks(writer, width_limit, chunk_rows)?;
+                written = true;
+            }
+            if let (Some(level), Some(row)) = (level, self.cells.get(row_idx)) {
+                if written {
+                    writeln!(writer)?;
+                }
+                let text: Vec<&str> = row
+                    .iter()
+                    .filter(|cell| cell.merge_parent.is_none())
+                    .map(|cell| cell.content.trim())
+                    .filter(|content| !content.is_empty())
+                    .collect();
+                writeln!(writer, "{} {}", "#".repeat(level), text.join(" "))?;
+                written = true;
+            }
+            start = row_idx + 1;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// 見出しスタイルの行の見出しレベルを取得（内部ヘルパー）
+    ///
+    /// 表示内容のあるセル（結合セルの子を除く）が1つ以上あり、
+    /// そのすべてが見出しスタイルの場合に、最も上位の見出しレベルを返します。
+    fn heading_level(row: &[Cell]) -> Option<usize> {
+        let mut level: Option<usize> = None;
+        for cell in row {
+            if cell.merge_parent.is_some() || cell.content.trim().is_empty() {
+                continue;
+            }
+            let cell_level = match cell.style.as_deref()? {
+                "Title" | "Heading 1" => 2,
+                "Heading 2" => 3,
+                "Heading 3" => 4,
+                "Heading 4" => 5,
+                _ => return None,
+            };
+            level = Some(level.map_or(cell_level, |l: usize| l.min(cell_level)));
+        }
+        level
+    }
+
+    /// Markdownの表で列幅の上限を超えて切り詰められるセルの数を取得
+    ///
+    /// `render_markdown()`と同じく、前後の空白を除いたセル内容の表示幅で判定します。
+    ///
+    /// # 引数
+    ///
+    /// * `width` - 列幅の上限（表示幅）
+    /// * `heading_sections` - 見出しスタイルの行を表から除くか（`render_markdown_sections()`の場合）
+    pub fn count_truncated_cells(&self, width: usize, heading_sections: bool) -> usize {
+        self.cells
+            .iter()
+            .filter(|row| !heading_sections || Self::heading_level(row).is_none())
+            .flatten()
+            .filter(|cell| cell.content.trim().width() > width)
+            .count()
+    }
+
     /// 列幅を計算（内部ヘルパー）
     ///
     /// 各列について、すべての行のセル内容の表示幅を計算し、列ごとの最大幅を返します。
@@ -232,30 +790,16 @@ impl LogicalGrid {
         let mut widths = vec![3; self.cols]; // 最小幅は3文字（区切り行の最小幅）
 
         for row in &self.cells {
-            for (col_idx, cell) in row.iter().enumerate() {
-                // trimしてから表示幅を計算（全角文字は2、半角文字は1）
-                let trimmed_width = cell.content.trim().width();
-                widths[col_idx] = widths[col_idx].max(trimmed_width);
-            }
+            // trimしてから表示幅を計算
+            update_markdown_widths(&mut widths, row.iter().map(|cell| cell.content.trim()));
         }
 
         widths
     }
 
-    /// ヘッダー区切り行を生成（内部ヘルパー）
-    ///
-    /// 各列幅に応じて "---" を生成し、"|" で連結します。
-    /// vscode-markdown-tableの実装に準拠し、セルの前後のスペース（各1文字）を考慮します。
+    /// ヘッダー区切り行を生成（内部ヘルパー、`markdown_separator()`を参照）
     fn generate_separator(&self, col_widths: &[usize]) -> String {
-        let mut parts = vec!["|".to_string()];
-
-        for &width in col_widths {
-            // セルの前後のスペース（各1文字）+ セル幅分のハイフン
-            parts.push("-".repeat(width + 2));
-            parts.push("|".to_string());
-        }
-
-        parts.join("")
+        markdown_separator(col_widths)
     }
 
     /// HTMLテーブルとして出力
@@ -293,15 +837,17 @@ impl LogicalGrid {
                 // rowspan/colspan計算
                 let (rowspan, colspan) = self.calculate_span(&coord, merged_regions);
 
+                write!(writer, "    <td")?;
                 if rowspan > 1 || colspan > 1 {
-                    write!(
-                        writer,
-                        "    <td rowspan=\"{}\" colspan=\"{}\">",
-                        rowspan, colspan
-                    )?;
-                } else {
-                    write!(writer, "    <td>")?;
+                    write!(writer, " rowspan=\"{}\" colspan=\"{}\"", rowspan, colspan)?;
+                }
+                if let Some(font) = &cell.font {
+                    write!(writer, " class=\"{}\"", font_classes(font))?;
                 }
+                if let Some(color) = &cell.fill_color {
+                    write!(writer, " style=\"background-color:#{}\"", color)?;
+                }
+                write!(writer, ">")?;
 
                 writeln!(writer, "{}</td>", cell.content)?;
             }
@@ -336,6 +882,154 @@ impl LogicalGrid {
         self.cols
     }
 
+    /// 結合セルの子を除いた、すべてのセルの表示文字列を取得
+    pub(crate) fn texts(&self) -> impl Iterator<Item = &str> {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| !cell.is_merged)
+            .map(|cell| cell.content.as_str())
+    }
+
+    /// セル内容の安定したハッシュ値を計算
+    ///
+    /// 空でないセルを行優先順に走査し、座標と元のセル値からなる正規化済みの
+    /// ストリームをxxHash（XXH3 64bit）でハッシュ化します。
+    /// 書式や出力フォーマットに依存しないため、複数ファイル間で同一内容のシートを
+    /// 重複排除する用途に使用できます。
+    pub(crate) fn content_hash(&self) -> u64 {
+        let mut hasher = Xxh3::new();
+
+        for (row_idx, row) in self.cells.iter().enumerate() {
+            for (col_idx, cell) in row.iter().enumerate() {
+                if cell.value.is_empty() {
+                    continue;
+                }
+
+                hasher.update(&(row_idx as u64).to_le_bytes());
+                hasher.update(&(col_idx as u64).to_le_bytes());
+                hash_cell_value(&mut hasher, &cell.value);
+            }
+        }
+
+        hasher.digest()
+    }
+
+    /// 空行で区切られた表ごとの範囲とハッシュ値を計算
+    ///
+    /// すべてのセルが空の行を表の区切りとし、連続する空でない行を1つの表とします。
+    /// ハッシュ値は`content_hash()`と同じく元のセル値から計算しますが、座標は表の
+    /// 左上からの相対位置を使用するため、同じ内容の表はシート上の位置によらず一致します。
+    pub(crate) fn table_hashes(&self) -> Vec<TableInfo> {
+        let mut tables = Vec::new();
+        let mut start = None;
+        for row_idx in 0..=self.rows {
+            let blank = self
+                .cells
+                .get(row_idx)
+                .is_none_or(|row| row.iter().all(|cell| cell.value.is_empty()));
+            match (blank, start) {
+                (false, None) => start = Some(row_idx),
+                (true, Some(first)) => {
+                    tables.push(self.table_info(first, &self.cells[first..row_idx]));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        tables
+    }
+
+    /// 表の範囲とハッシュ値を計算（内部ヘルパー）
+    ///
+    /// # 引数
+    ///
+    /// * `first_row` - 表の先頭行のグリッド上のインデックス
+    /// * `rows` - 表の行（空行を含まない）
+    fn table_info(&self, first_row: usize, rows: &[Vec<Cell>]) -> TableInfo {
+        let first_col = rows
+            .iter()
+            .filter_map(|row| row.iter().position(|cell| !cell.value.is_empty()))
+            .min()
+            .unwrap_or(0);
+
+        let mut hasher = Xxh3::new();
+        let mut range: Option<CellRange> = None;
+        for (row_offset, row) in rows.iter().enumerate() {
+            for (col_idx, cell) in row.iter().enumerate().skip(first_col) {
+                if cell.value.is_empty() {
+                    continue;
+                }
+
+                hasher.update(&(row_offset as u64).to_le_bytes());
+                hasher.update(&((col_idx - first_col) as u64).to_le_bytes());
+                hash_cell_value(&mut hasher, &cell.value);
+
+                // 並べ替えた行にも対応するよう、元の座標の最小・最大で範囲を求める
+                let source = cell.source.unwrap_or_else(|| {
+                    CellCoord::new((first_row + row_offset) as u32, col_idx as u32)
+                });
+                range = Some(match range {
+                    Some(range) => CellRange::new(
+                        CellCoord::new(
+                            range.start.row.min(source.row),
+                            range.start.col.min(source.col),
+                        ),
+                        CellCoord::new(
+                            range.end.row.max(source.row),
+                            range.end.col.max(source.col),
+                        ),
+                    ),
+                    None => CellRange::new(source, source),
+                });
+            }
+        }
+
+        let origin = CellCoord::new(first_row as u32, first_col as u32);
+        TableInfo {
+            range: range.unwrap_or(CellRange::new(origin, origin)),
+            content_hash: hasher.digest(),
+        }
+    }
+
+    /// 重複シートの検出に使用する、行の内容のシグネチャを計算
+    ///
+    /// 空でない行ごとに、列の位置と元のセル値（文字列は前後の空白を除去）を
+    /// xxHash（XXH3 64bit）でハッシュ化し、昇順に並べて返します。
+    /// 行の位置を含めないため、行の挿入や並べ替えがあっても一致する行は同じ値になります。
+    pub(crate) fn row_signature(&self) -> Vec<u64> {
+        let mut signature: Vec<u64> = self
+            .cells
+            .iter()
+            .filter_map(|row| {
+                let mut hasher = Xxh3::new();
+                let mut empty = true;
+                for (col_idx, cell) in row.iter().enumerate() {
+                    match &cell.value {
+                        CellValue::String(s) if !s.trim().is_empty() => {
+                            hasher.update(&(col_idx as u64).to_le_bytes());
+                            hash_text(&mut hasher, s.trim());
+                        }
+                        CellValue::String(_) | CellValue::Empty => continue,
+                        value => {
+                            hasher.update(&(col_idx as u64).to_le_bytes());
+                            hash_cell_value(&mut hasher, value);
+                        }
+                    }
+                    empty = false;
+                }
+                (!empty).then(|| hasher.digest())
+            })
+            .collect();
+        signature.sort_unstable();
+        signature
+    }
+
+    /// セル結合の処理戦略を取得
+    pub(crate) fn merge_strategy(&self) -> MergeStrategy {
+        self.merge_strategy
+    }
+
     /// 指定された行を取得
     pub(crate) fn get_row(&self, row_idx: usize) -> &[Cell] {
         if row_idx < self.rows {
@@ -344,28 +1038,1453 @@ impl LogicalGrid {
             &[]
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::{CellRange, CellValue};
+
+    /// 指定された座標のセルを取得
+    ///
+    /// グリッド外の座標の場合は`None`を返します。
+    pub(crate) fn get_cell(&self, coord: CellCoord) -> Option<&Cell> {
+        self.cells
+            .get(coord.row as usize)
+            .and_then(|row| row.get(coord.col as usize))
+    }
+
+    /// 指定された座標のセルを変更可能な参照として取得
+    pub(crate) fn get_cell_mut(&mut self, coord: CellCoord) -> Option<&mut Cell> {
+        self.cells
+            .get_mut(coord.row as usize)
+            .and_then(|row| row.get_mut(coord.col as usize))
+    }
+
+    /// 指定された座標のセルを置き換える
+    ///
+    /// グリッド外の座標の場合は、その座標を含むように空のセルでグリッドを拡張します。
+    /// 新しいセルが元のシート上の座標を持たない場合は、置き換える前のセルの座標を引き継ぎます。
+    ///
+    /// # 引数
+    ///
+    /// * `coord` - 対象セルの座標
+    /// * `cell` - 新しいセル
+    pub(crate) fn set_cell(&mut self, coord: CellCoord, mut cell: Cell) {
+        self.grow_to(coord.row as usize + 1, coord.col as usize + 1);
+        let target = &mut self.cells[coord.row as usize][coord.col as usize];
+        if cell.source.is_none() {
+            cell.source = target.source;
+        }
+        *target = cell;
+    }
+
+    /// 末尾に列を追加
+    ///
+    /// `cells`の各要素を先頭行から順に配置し、不足する行は空のセルで埋めます。
+    /// 行数より多い場合は、空のセルからなる行でグリッドを拡張します。
+    ///
+    /// # 引数
+    ///
+    /// * `cells` - 追加する列のセル（先頭行から順）
+    pub(crate) fn push_column(&mut self, cells: Vec<Cell>) {
+        let col = self.cols;
+        self.grow_to(self.rows.max(cells.len()), col + 1);
+        for (row_idx, cell) in cells.into_iter().enumerate() {
+            self.cells[row_idx][col] = cell;
+        }
+    }
+
+    /// 元のシート上のセル参照（列名の行と行番号の列）を付加したグリッドを生成
+    ///
+    /// 先頭に各列の列名（例: `A`）からなる行を、左端に各行の行番号（1始まり）からなる列を
+    /// 追加します。列名・行番号は各列・各行で最初に見つかった元の座標から決め、
+    /// 元のセルがない列・行（計算列など）は空とします。結合セルの親座標は、
+    /// 追加した行・列の分だけ移動します（結合範囲は`MergedRegion::offset(1, 1)`で移動します）。
+    pub(crate) fn with_reference_headers(&self) -> Self {
+        if self.rows == 0 || self.cols == 0 {
+            return self.clone();
+        }
+
+        let mut header = Vec::with_capacity(self.cols + 1);
+        header.push(Cell::empty());
+        header.extend((0..self.cols).map(|col| {
+            let letter = self
+                .cells
+                .iter()
+                .find_map(|row| row.get(col).and_then(|cell| cell.source))
+                .map(|source| CellCoord::col_index_to_letter(source.col));
+            Cell::new(letter.unwrap_or_default())
+        }));
+
+        let mut cells = Vec::with_capacity(self.rows + 1);
+        cells.push(header);
+        for row in &self.cells {
+            let number = row
+                .iter()
+                .find_map(|cell| cell.source)
+                .map(|source| (source.row + 1).to_string());
+            let mut annotated = Vec::with_capacity(self.cols + 1);
+            annotated.push(Cell::new(number.unwrap_or_default()));
+            annotated.extend(row.iter().cloned().map(|mut cell| {
+                if let Some(parent) = &mut cell.merge_parent {
+                    *parent = CellCoord::new(parent.row + 1, parent.col + 1);
+                }
+                cell
+            }));
+            cells.push(annotated);
+        }
+
+        Self {
+            cells,
+            rows: self.rows + 1,
+            cols: self.cols + 1,
+            merge_strategy: self.merge_strategy,
+        }
+    }
+
+    /// グリッドを指定した行数・列数以上に拡張（内部ヘルパー）
+    fn grow_to(&mut self, rows: usize, cols: usize) {
+        let cols = self.cols.max(cols);
+        for row in &mut self.cells {
+            row.resize_with(cols, Cell::empty);
+        }
+        if rows > self.rows {
+            self.cells.resize_with(rows, || vec![Cell::empty(); cols]);
+        }
+        self.rows = self.rows.max(rows);
+        self.cols = cols;
+    }
+
+    /// すべてのセルを座標とともに変更可能な参照として取得（行優先順）
+    pub(crate) fn cells_mut(&mut self) -> impl Iterator<Item = (CellCoord, &mut Cell)> {
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(row_idx, row)| {
+                row.iter_mut().enumerate().map(move |(col_idx, cell)| {
+                    (CellCoord::new(row_idx as u32, col_idx as u32), cell)
+                })
+            })
+    }
+
+    /// セルに脚注を付加
+    ///
+    /// グリッド外の座標は無視します。
+    ///
+    /// # 引数
+    ///
+    /// * `coord` - 対象セルの座標
+    /// * `text` - 脚注の本文
+    pub(crate) fn add_footnote(&mut self, coord: CellCoord, text: String) {
+        if let Some(cell) = self
+            .cells
+            .get_mut(coord.row as usize)
+            .and_then(|row| row.get_mut(coord.col as usize))
+        {
+            cell.footnotes.push(text);
+        }
+    }
+
+    /// セルの表示文字列の末尾にコメントを付加（`CommentMode::Inline`）
+    ///
+    /// 表示文字列が空のセルはコメントのみとします。グリッド外の座標は無視します。
+    ///
+    /// # 引数
+    ///
+    /// * `coord` - 対象セルの座標
+    /// * `comment` - 付加するコメント（エスケープ済み）
+    pub(crate) fn append_comment(&mut self, coord: CellCoord, comment: &str) {
+        if let Some(cell) = self
+            .cells
+            .get_mut(coord.row as usize)
+            .and_then(|row| row.get_mut(coord.col as usize))
+        {
+            let content = cell.content_mut();
+            if !content.is_empty() {
+                content.push(' ');
+            }
+            content.push_str(comment);
+        }
+    }
+
+    /// セルの表示文字列の末尾に条件付き書式の強調表示のマーカー（`⚠`）を付加（`ConditionalFormats::Marker`）
+    ///
+    /// 表示文字列が空のセルはマーカーのみとします。グリッド外の座標は無視します。
+    ///
+    /// # 引数
+    ///
+    /// * `coord` - 対象セルの座標
+    pub(crate) fn mark_highlight(&mut self, coord: CellCoord) {
+        if let Some(cell) = self
+            .cells
+            .get_mut(coord.row as usize)
+            .and_then(|row| row.get_mut(coord.col as usize))
+        {
+            let content = cell.content_mut();
+            if !content.is_empty() {
+                content.push(' ');
+            }
+            content.push_str(HIGHLIGHT_MARKER);
+        }
+    }
+
+    /// セルに条件付き書式の注記を付加
+    ///
+    /// グリッド外の座標は無視します。
+    ///
+    /// # 引数
+    ///
+    /// * `coord` - 対象セルの座標
+    /// * `description` - 該当するルールの説明
+    pub(crate) fn add_highlight(&mut self, coord: CellCoord, description: String) {
+        if let Some(cell) = self
+            .cells
+            .get_mut(coord.row as usize)
+            .and_then(|row| row.get_mut(coord.col as usize))
+        {
+            cell.highlights.push(description);
+        }
+    }
+
+    /// セルに付加された脚注を採番し、表示文字列の末尾に参照（例: `[^1]`）を追加
+    ///
+    /// セルを行優先順に走査して`footnotes`に追加するため、番号は入力に対して安定します。
+    /// 条件付き書式の注記も、脚注の後に`Conditional format: `で始まる脚注として追加します。
+    /// 結合セルの子の脚注は、参照の重複を避けるため採番しません。
+    ///
+    /// # 引数
+    ///
+    /// * `footnotes` - 脚注の追加先
+    pub(crate) fn resolve_footnotes(&mut self, footnotes: &mut Footnotes) {
+        for cell in self.cells.iter_mut().flatten() {
+            let texts = std::mem::take(&mut cell.footnotes);
+            if cell.is_merged && cell.merge_parent.is_some() {
+                continue;
+            }
+            let highlights = cell
+                .highlights
+                .iter()
+                .map(|description| format!("Conditional format: {}", description))
+                .collect::<Vec<_>>();
+            for text in texts.into_iter().chain(highlights) {
+                let reference = footnotes.push(text);
+                cell.content_mut().push_str(&reference);
+            }
+        }
+    }
+
+    /// 表示内容のあるセル数を取得（空白のみのセルを除く）
+    pub(crate) fn non_blank_count(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|cell| !cell.content.trim().is_empty())
+            .count()
+    }
+
+    /// 使用範囲が肥大化しているかを判定
+    ///
+    /// グリッドのセル数が10万以上で、表示内容のあるセルが1%未満の場合に肥大化と判定します。
+    /// 空白文字のみのセルや、離れた位置に誤って入力されたセルによって
+    /// 使用範囲が広がったシートを検出するために使用します。
+    pub(crate) fn is_bloated(&self) -> bool {
+        is_bloated_size(self.rows, self.cols, self.non_blank_count())
+    }
+
+    /// 連続する同一内容の行を1行に集約し、最終列のセルに行数（`×N`）を付記
+    ///
+    /// 先頭行（見出し）と結合セルを含む行は集約しません。
+    /// HTMLフォールバックでは結合セルの座標を維持する必要があるため、
+    /// 結合セルを含むグリッドでは何もしません。
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した行数
+    pub(crate) fn dedupe_consecutive_rows(&mut self) -> usize {
+        if self.rows < 3 || self.cols == 0 {
+            return 0;
+        }
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return 0;
+        }
+
+        // 先頭行（見出し）は集約の対象外
+        let mut rows = std::mem::take(&mut self.cells).into_iter();
+        let header = rows.next();
+        let mut deduped: Vec<(Vec<Cell>, usize)> = Vec::with_capacity(self.rows);
+        for row in rows {
+            match deduped.last_mut() {
+                Some((last, count)) if Self::is_same_row(last, &row) => *count += 1,
+                _ => deduped.push((row, 1)),
+            }
+        }
+
+        let removed = self.rows - 1 - deduped.len();
+        self.cells = header
+            .into_iter()
+            .chain(deduped.into_iter().map(|(mut row, count)| {
+                if count > 1 {
+                    if let Some(cell) = row.last_mut() {
+                        let content = cell.content_mut();
+                        if !content.is_empty() {
+                            content.push(' ');
+                        }
+                        content.push_str(&format!("×{}", count));
+                    }
+                }
+                row
+            }))
+            .collect();
+        self.rows = self.cells.len();
+        removed
+    }
+
+    /// 行の内容による絞り込み
+    ///
+    /// 各行のセルの表示文字列をタブ区切りで連結した文字列を判定します。
+    /// 除去条件に一致する行はすべて除去し、出力条件に一致しない行は
+    /// 残った先頭行（見出し）を除いて除去します。
+    /// HTMLフォールバックで結合セルを含む場合は、結合構造を保持するため何もしません。
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した行数
+    pub(crate) fn filter_rows(&mut self, filter: &RowFilter) -> usize {
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return 0;
+        }
+
+        let mut text = String::new();
+        let mut header_kept = false;
+        self.cells.retain(|row| {
+            text.clear();
+            for (idx, cell) in row.iter().enumerate() {
+                if idx > 0 {
+                    text.push('\t');
+                }
+                text.push_str(&cell.content);
+            }
+
+            if filter.exclude.as_ref().is_some_and(|re| re.is_match(&text)) {
+                return false;
+            }
+            if !header_kept {
+                header_kept = true;
+                return true;
+            }
+            filter.include.as_ref().is_none_or(|re| re.is_match(&text))
+        });
+
+        let removed = self.rows - self.cells.len();
+        self.rows = self.cells.len();
+        if self.rows == 0 {
+            self.cols = 0;
+        }
+        removed
+    }
+
+    /// すべてのセルが空（空白のみを含む）の行を除去
+    ///
+    /// 結合セルの一部を含む行は除去せず、除去した行より下の結合範囲と結合セルの親座標を
+    /// 除去した行数だけ上に移動します。HTMLフォールバックで結合セルを含む場合は、
+    /// 結合構造を保持するため何もしません。
+    ///
+    /// # 引数
+    ///
+    /// * `merged_regions` - 結合セル範囲のリスト（行の除去に合わせて移動）
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した行の、除去前のグリッドでの行番号（0始まり、昇順）
+    pub(crate) fn remove_blank_rows(&mut self, merged_regions: &mut [MergedRegion]) -> Vec<u32> {
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return Vec::new();
+        }
+
+        let mut removed = Vec::new();
+        let mut row_idx = 0;
+        self.cells.retain(|row| {
+            let blank = row
+                .iter()
+                .all(|cell| !cell.is_merged && cell.content.trim().is_empty());
+            if blank {
+                removed.push(row_idx);
+            }
+            row_idx += 1;
+            !blank
+        });
+        if removed.is_empty() {
+            return removed;
+        }
+
+        // 除去した行より下の座標を上に移動
+        let shift = |row: &mut u32| *row -= removed.partition_point(|&r| r < *row) as u32;
+        for region in merged_regions.iter_mut() {
+            shift(&mut region.range.start.row);
+            shift(&mut region.range.end.row);
+            shift(&mut region.parent.row);
+        }
+        for cell in self.cells.iter_mut().flatten() {
+            if let Some(parent) = &mut cell.merge_parent {
+                shift(&mut parent.row);
+            }
+        }
+
+        self.rows = self.cells.len();
+        if self.rows == 0 {
+            self.cols = 0;
+        }
+        removed
+    }
+
+    /// 先頭行（見出し）から列を検索
+    ///
+    /// 見出しは前後の空白を除いて比較し、完全に一致する列がない場合は
+    /// 大文字小文字を区別せずに比較します。見出しが文字列のセルは、エスケープ前の文字列で比較します。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(usize)` - 最初に一致した列番号
+    /// * `None` - 一致する列がない場合、またはグリッドが空の場合
+    pub(crate) fn find_header(&self, name: &str) -> Option<usize> {
+        let header = self.cells.first()?;
+        let texts: Vec<&str> = header
+            .iter()
+            .map(|cell| match &cell.value {
+                CellValue::String(s) => s.trim(),
+                _ => cell.content.trim(),
+            })
+            .collect();
+        let name = name.trim();
+        texts.iter().position(|text| *text == name).or_else(|| {
+            let name = name.to_lowercase();
+            texts.iter().position(|text| text.to_lowercase() == name)
+        })
+    }
+
+    /// 先頭行（見出し）を除く行で、指定した列の空のセルを直前の空でないセルの値で埋める
+    ///
+    /// 表示文字列が空白のみのセルも空として扱います。すべてのセルが空の行は埋めません。
+    /// 列の結合セルの子は結合を解除し、JSONなどで親セルと同様に値を出力します。
+    /// 列がグリッド外の場合や、HTMLフォールバックで結合セルを含む場合は何もしません。
+    ///
+    /// # 引数
+    ///
+    /// * `col` - 埋める列番号
+    ///
+    /// # 戻り値
+    ///
+    /// 値を埋めたセルの数
+    pub(crate) fn forward_fill(&mut self, col: usize) -> usize {
+        if col >= self.cols || self.rows < 2 {
+            return 0;
+        }
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return 0;
+        }
+
+        let mut filled = 0;
+        let mut last: Option<(String, CellValue, Option<f64>)> = None;
+        for row in &mut self.cells[1..] {
+            if row.iter().all(|cell| cell.content.trim().is_empty()) {
+                continue;
+            }
+            let cell = &mut row[col];
+            cell.is_merged = false;
+            cell.merge_parent = None;
+            if !cell.content.trim().is_empty() {
+                last = Some((cell.content.clone(), cell.value.clone(), cell.number));
+            } else if let Some((content, value, number)) = &last {
+                // 上のセルの値をそのまま複製するため、生の数値も引き継ぐ
+                cell.set_content(content.clone());
+                cell.value = value.clone();
+                cell.number = *number;
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    /// 先頭行（見出し）を除く行を指定した列の値で並べ替え
+    ///
+    /// 安定ソートのため、値が等しい行は元の順序を保ちます。値が空の行は順序によらず末尾に配置します。
+    /// `numeric_aware`の場合は、数値・日付のセルを値で比較して文字列より前に配置し、
+    /// 文字列は含まれる数字の並びを数値として比較します（`item2` < `item10`）。
+    /// それ以外の場合は表示文字列を比較します。
+    /// 列がグリッド外の場合や、HTMLフォールバックで結合セルを含む場合は何もしません。
+    ///
+    /// # 引数
+    ///
+    /// * `col` - 並べ替えに使用する列番号
+    /// * `order` - 並べ替えの順序
+    /// * `numeric_aware` - 数値を考慮して比較するか
+    ///
+    /// # 戻り値
+    ///
+    /// 並べ替えを行った場合は`true`
+    pub(crate) fn sort_rows(&mut self, col: usize, order: SortOrder, numeric_aware: bool) -> bool {
+        if col >= self.cols || self.rows < 2 {
+            return false;
+        }
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return false;
+        }
+
+        self.cells[1..].sort_by(|a, b| {
+            let (a, b) = (&a[col], &b[col]);
+            let (a_empty, b_empty) = (a.content.trim().is_empty(), b.content.trim().is_empty());
+            if a_empty || b_empty {
+                return a_empty.cmp(&b_empty);
+            }
+            let ordering = if numeric_aware {
+                match (sort_number(a), sort_number(b)) {
+                    (Some(x), Some(y)) => x.total_cmp(&y),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => natural_cmp(&a.content, &b.content),
+                }
+            } else {
+                a.content.cmp(&b.content)
+            };
+            match order {
+                SortOrder::Descending => ordering.reverse(),
+                _ => ordering,
+            }
+        });
+        true
+    }
+
+    /// 先頭行（見出し）の末尾の括弧に記載された単位を検出
+    ///
+    /// 結合セルの子は単位を持たないものとして扱います。
+    ///
+    /// # 引数
+    ///
+    /// * `strip` - 見出しの表示文字列から単位表記を除去するか
+    ///
+    /// # 戻り値
+    ///
+    /// 列ごとの単位（単位のない列は`None`、グリッドが空の場合は空のベクター）
+    pub(crate) fn detect_header_units(&mut self, strip: bool) -> Vec<Option<String>> {
+        let Some(header) = self.cells.first_mut() else {
+            return Vec::new();
+        };
+        header
+            .iter_mut()
+            .map(|cell| {
+                if cell.merge_parent.is_some() {
+                    return None;
+                }
+                let (rest, unit) = crate::output::split_header_unit(&cell.content)?;
+                let (rest, unit) = (rest.to_string(), unit.to_string());
+                if strip {
+                    *cell.content_mut() = rest;
+                }
+                Some(unit)
+            })
+            .collect()
+    }
+
+    /// 先頭行（見出し）の末尾に注記を付加
+    ///
+    /// 注記は見出しのセルの元のシート上の座標から求めます。結合セルの子には付加しません。
+    ///
+    /// # 引数
+    ///
+    /// * `annotation` - 見出しの元の座標から付加する注記（エスケープ済み）を返す関数
+    pub(crate) fn annotate_header<F>(&mut self, annotation: F)
+    where
+        F: Fn(CellCoord) -> Option<String>,
+    {
+        let Some(header) = self.cells.first_mut() else {
+            return;
+        };
+        for cell in header.iter_mut() {
+            if cell.merge_parent.is_some() {
+                continue;
+            }
+            if let Some(text) = cell.source.and_then(&annotation) {
+                let content = cell.content_mut();
+                if !content.is_empty() {
+                    content.push(' ');
+                }
+                content.push_str(&text);
+            }
+        }
+    }
+
+    /// 見出し以外のすべての行が同じ値を持つ列（定数列）を表から除去
+    ///
+    /// 見出し行と2行以上のデータ行がある場合のみ対象とします。
+    /// 値が空の列、結合セルや脚注を含む列は除去しません。
+    /// すべての列が定数列の場合や、HTMLフォールバックで結合セルを含む場合は何もしません。
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した列（元の列順）
+    pub(crate) fn fold_constant_columns(&mut self) -> Vec<FoldedColumn> {
+        if self.rows < 3 || self.cols < 2 {
+            return Vec::new();
+        }
+        if self.merge_strategy == MergeStrategy::HtmlFallback
+            && self.cells.iter().flatten().any(|cell| cell.is_merged)
+        {
+            return Vec::new();
+        }
+
+        let constant: Vec<usize> = (0..self.cols)
+            .filter(|&col| self.is_constant_column(col))
+            .collect();
+        if constant.is_empty() || constant.len() == self.cols {
+            return Vec::new();
+        }
+
+        let folded = constant
+            .iter()
+            .map(|&col| {
+                let column = CellCoord::col_index_to_letter(col as u32);
+                let header = &self.cells[0][col].content;
+                FoldedColumn {
+                    header: if header.trim().is_empty() {
+                        column.clone()
+                    } else {
+                        header.clone()
+                    },
+                    value: self.cells[1][col].content.clone(),
+                    column,
+                }
+            })
+            .collect();
+
+        for row in &mut self.cells {
+            let mut col = 0;
+            row.retain(|_| {
+                let keep = !constant.contains(&col);
+                col += 1;
+                keep
+            });
+        }
+        self.cols -= constant.len();
+        folded
+    }
+
+    /// 列が定数列かを判定（内部ヘルパー）
+    fn is_constant_column(&self, col: usize) -> bool {
+        let header = &self.cells[0][col];
+        let first = &self.cells[1][col];
+        !header.is_merged
+            && header.footnotes.is_empty()
+            && header.highlights.is_empty()
+            && !first.content.trim().is_empty()
+            && self.cells[1..].iter().all(|row| {
+                let cell = &row[col];
+                !cell.is_merged
+                    && cell.footnotes.is_empty()
+                    && cell.highlights.is_empty()
+                    && cell.content == first.content
+            })
+    }
+
+    /// 2つの行が集約可能な同一内容の行かを判定（内部ヘルパー）
+    ///
+    /// 表示文字列・脚注・条件付き書式の注記が一致し、結合セルを含まない場合に`true`を返します。
+    fn is_same_row(a: &[Cell], b: &[Cell]) -> bool {
+        a.len() == b.len()
+            && a.iter().zip(b).all(|(x, y)| {
+                !x.is_merged
+                    && !y.is_merged
+                    && x.content == y.content
+                    && x.footnotes == y.footnotes
+                    && x.highlights == y.highlights
+            })
+    }
+
+    /// 末尾の空白行・列を除去し、表示内容のある範囲に縮小
+    ///
+    /// 結合セルの座標を維持するため、先頭の行・列は除去しません。
+    /// 結合セルの子は表示内容のあるセルとして扱います（`rowspan`/`colspan`の整合性を保つため）。
+    pub(crate) fn shrink_to_content(&mut self) {
+        let mut rows = 0;
+        let mut cols = 0;
+        for (row_idx, row) in self.cells.iter().enumerate() {
+            for (col_idx, cell) in row.iter().enumerate() {
+                if cell.is_merged || !cell.content.trim().is_empty() {
+                    rows = row_idx + 1;
+                    cols = cols.max(col_idx + 1);
+                }
+            }
+        }
+
+        self.cells.truncate(rows);
+        for row in &mut self.cells {
+            row.truncate(cols);
+        }
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    /// 空の行・列を刈り込む
+    ///
+    /// `TrimMode::All`では先頭の空行・空列を除去し、結合範囲と結合セルの親座標を
+    /// 除去した行数・列数だけ移動した上で、表の途中の空行も除去します。
+    /// 末尾の空行・空列はいずれのモードでも`shrink_to_content()`と同様に除去します。
+    ///
+    /// # 引数
+    ///
+    /// * `mode` - 刈り込み方式
+    /// * `merged_regions` - 結合セル範囲のリスト（行・列の除去に合わせて移動）
+    pub(crate) fn trim_empty(&mut self, mode: TrimMode, merged_regions: &mut [MergedRegion]) {
+        let is_blank = |cell: &Cell| !cell.is_merged && cell.content.trim().is_empty();
+        match mode {
+            TrimMode::None => return,
+            TrimMode::Trailing => {}
+            TrimMode::All => {
+                let top = self
+                    .cells
+                    .iter()
+                    .take_while(|row| row.iter().all(is_blank))
+                    .count();
+                let left = self
+                    .cells
+                    .iter()
+                    .map(|row| row.iter().take_while(|cell| is_blank(cell)).count())
+                    .min()
+                    .unwrap_or(0);
+                if top > 0 || left > 0 {
+                    self.cells.drain(..top);
+                    for row in &mut self.cells {
+                        row.drain(..left.min(row.len()));
+                    }
+                    let shift = |coord: &mut CellCoord| {
+                        coord.row = coord.row.saturating_sub(top as u32);
+                        coord.col = coord.col.saturating_sub(left as u32);
+                    };
+                    for region in merged_regions.iter_mut() {
+                        shift(&mut region.range.start);
+                        shift(&mut region.range.end);
+                        shift(&mut region.parent);
+                    }
+                    for cell in self.cells.iter_mut().flatten() {
+                        if let Some(parent) = &mut cell.merge_parent {
+                            shift(parent);
+                        }
+                    }
+                    self.rows = self.cells.len();
+                    self.cols = self.cols.saturating_sub(left);
+                }
+            }
+        }
+
+        self.shrink_to_content();
+        if mode == TrimMode::All {
+            self.remove_blank_rows(merged_regions);
+        }
+    }
+
+    /// 先頭から`rows`行を残し、以降の行を除去
+    ///
+    /// 結合範囲の調整は呼び出し元で行います。
+    ///
+    /// # 戻り値
+    ///
+    /// 除去した行数
+    pub(crate) fn truncate_rows(&mut self, rows: usize) -> usize {
+        if rows >= self.rows {
+            return 0;
+        }
+        let removed = self.rows - rows;
+        self.cells.truncate(rows);
+        self.rows = rows;
+        if self.rows == 0 {
+            self.cols = 0;
+        }
+        removed
+    }
+
+    /// 表示内容のあるセル（空白のみのセルを除く）の数が`max_cells`以下に収まる、
+    /// 先頭からの行数を取得
+    pub(crate) fn rows_within_cells(&self, max_cells: usize) -> usize {
+        let mut count = 0usize;
+        for (row_idx, row) in self.cells.iter().enumerate() {
+            count += row
+                .iter()
+                .filter(|cell| !cell.content.trim().is_empty())
+                .count();
+            if count > max_cells {
+                return row_idx;
+            }
+        }
+        self.rows
+    }
+}
+
+/// セル値を型の識別子とともにハッシュ化（内部ヘルパー）
+fn hash_cell_value(hasher: &mut Xxh3, value: &CellValue) {
+    match value {
+        CellValue::Number(n) => {
+            hasher.update(b"n");
+            hasher.update(&n.to_bits().to_le_bytes());
+        }
+        CellValue::DateTime { serial, .. } => {
+            hasher.update(b"d");
+            hasher.update(&serial.to_bits().to_le_bytes());
+        }
+        CellValue::String(s) => hash_text(hasher, s),
+        CellValue::Bool(b) => {
+            hasher.update(b"b");
+            hasher.update(&[*b as u8]);
+        }
+        CellValue::Error(e) => {
+            let e = e.as_excel_str();
+            hasher.update(b"e");
+            hasher.update(&(e.len() as u64).to_le_bytes());
+            hasher.update(e.as_bytes());
+        }
+        CellValue::Empty => {}
+    }
+}
+
+/// 文字列のセル値をハッシュ化（内部ヘルパー）
+fn hash_text(hasher: &mut Xxh3, s: &str) {
+    hasher.update(b"s");
+    hasher.update(&(s.len() as u64).to_le_bytes());
+    hasher.update(s.as_bytes());
+}
+
+/// 並べ替えに使用するセルの数値（数値・日付のセル、または数値として解釈できる表示文字列）
+fn sort_number(cell: &Cell) -> Option<f64> {
+    match cell.value {
+        CellValue::Number(n) => Some(n),
+        CellValue::DateTime { serial, .. } => Some(serial),
+        _ => cell.content.trim().parse().ok(),
+    }
+}
+
+/// 数字の並びを数値として比較する文字列の比較（内部ヘルパー）
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let take_digits = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(c);
+                    }
+                    digits.trim_start_matches('0').to_string()
+                };
+                let (x, y) = (take_digits(&mut a), take_digits(&mut b));
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(&y));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+/// Markdownの表の列幅を1行分のセル内容で更新
+///
+/// 各列の幅は、セル内容の表示幅（全角文字は2、半角文字は1）の最大値です。
+/// セル内容はtrim済みのものを渡します。
+pub(crate) fn update_markdown_widths<'a>(
+    widths: &mut [usize],
+    contents: impl IntoIterator<Item = &'a str>,
+) {
+    for (width, content) in widths.iter_mut().zip(contents) {
+        *width = (*width).max(content.width());
+    }
+}
+
+/// Markdownの表のヘッダー区切り行を生成
+///
+/// 各列幅に応じて "---" を生成し、"|" で連結します。
+/// vscode-markdown-tableの実装に準拠し、セルの前後のスペース（各1文字）を考慮します。
+pub(crate) fn markdown_separator(col_widths: &[usize]) -> String {
+    let mut parts = vec!["|".to_string()];
+
+    for &width in col_widths {
+        // セルの前後のスペース（各1文字）+ セル幅分のハイフン
+        parts.push("-".repeat(width + 2));
+        parts.push("|".to_string());
+    }
+
+    parts.join("")
+}
+
+/// Markdownの表の1行を出力
+///
+/// セル内容（trim済み）を左揃えで出力し、列幅まで表示幅に基づいてスペースで埋めます。
+pub(crate) fn write_markdown_row<'a, W: Write>(
+    writer: &mut W,
+    contents: impl IntoIterator<Item = &'a str>,
+    col_widths: &[usize],
+) -> Result<(), XlsxToMdError> {
+    write!(writer, "|")?;
+
+    for (content, &width) in contents.into_iter().zip(col_widths) {
+        // 表示幅を計算（全角文字は2、半角文字は1）
+        let content_width = content.width();
+
+        // セルの前にスペースを1つ入れる
+        write!(writer, " ")?;
+        // 左揃えでセル内容を出力
+        write!(writer, "{}", content)?;
+        // 残りのスペースを埋める（表示幅に基づく）
+        if content_width < width {
+            for _ in content_width..width {
+                write!(writer, " ")?;
+            }
+        }
+        // セルの後にスペースを1つ入れる
+        write!(writer, " |")?;
+    }
+
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// 使用範囲が肥大化しているかを、グリッドの大きさと表示内容のあるセルの数から判定
+///
+/// `LogicalGrid::is_bloated()`と同じ規則です。
+pub(crate) fn is_bloated_size(rows: usize, cols: usize, non_blank: usize) -> bool {
+    let area = rows.saturating_mul(cols);
+    area >= BLOAT_MIN_CELLS && non_blank.saturating_mul(BLOAT_DENSITY_RATIO) < area
+}
+
+/// 列幅の上限を超えるセル内容を折り返し、または切り詰める（内部ヘルパー）
+///
+/// 表示幅は全角文字を2として計算します。エスケープ（`\|`など）・改行（`<br>`）・
+/// 脚注の参照（`[^1]`）・書記素クラスタ（結合文字や絵文字のシーケンス）の途中では分割しません。
+fn limit_cell_width(content: &str, limit: &WidthLimit) -> String {
+    if limit.strategy == WrapStrategy::Truncate {
+        return truncate_cell(content, limit.width, &limit.marker);
+    }
+    content
+        .split("<br>")
+        .map(|line| wrap_line(line, limit.width))
+        .collect::<Vec<_>>()
+        .join("<br>")
+}
+/// 1行を空白の位置で折り返す（上限より長い語は上限の幅ごとに分割）（内部ヘルパー）
+fn wrap_line(line: &str, limit: usize) -> String {
+    if line.width() <= limit {
+        return line.to_string();
+    }
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for word in line.split(' ').filter(|word| !word.is_empty()) {
+        let word_width = word.width();
+        if current_width > 0 && current_width + 1 + word_width <= limit {
+            current.push(' ');
+            current.push_str(word);
+            current_width += 1 + word_width;
+            continue;
+        }
+        if current_width > 0 {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        for unit in markdown_units(word) {
+            let unit_width = unit.width();
+            if current_width > 0 && current_width + unit_width > limit {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            current.push_str(unit);
+            current_width += unit_width;
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines.join("<br>")
+}
+
+/// 上限の幅で切り詰め、省略記号を付加（末尾の脚注の参照は残す）（内部ヘルパー）
+///
+/// 切り詰めた内容と省略記号の表示幅の合計は上限を超えません。
+/// 省略記号だけで上限を超える場合は、省略記号も書記素クラスタ単位で切り詰めます。
+fn truncate_cell(content: &str, limit: usize, marker: &str) -> String {
+    if content.width() <= limit {
+        return content.to_string();
+    }
+    let units = markdown_units(content);
+    let is_reference = |unit: &&str| unit.len() > 3 && unit.starts_with("[^");
+    let body_len = units
+        .iter()
+        .rposition(|unit| !is_reference(unit))
+        .map_or(0, |idx| idx + 1);
+    let suffix = units[body_len..].concat();
+    let available = limit.saturating_sub(suffix.width());
+    let marker = take_width(marker, available);
+    let budget = available - marker.width();
+
+    let mut body = String::new();
+    let mut width = 0;
+    for unit in &units[..body_len] {
+        width += unit.width();
+        if width > budget {
+            break;
+        }
+        body.push_str(unit);
+    }
+    let mut body = body.as_str();
+    while let Some(rest) = body.trim_end().strip_suffix("<br>") {
+        body = rest;
+    }
+    format!("{}{}{}", body.trim_end(), marker, suffix)
+}
+
+/// 表示幅が上限に収まるまでの先頭部分を書記素クラスタ単位で取得（内部ヘルパー）
+fn take_width(s: &str, limit: usize) -> &str {
+    let mut width = 0;
+    let mut end = 0;
+    for grapheme in s.graphemes(true) {
+        width += grapheme.width();
+        if width > limit {
+            break;
+        }
+        end += grapheme.len();
+    }
+    &s[..end]
+}
+
+/// Markdownのセル内容を分割できない単位に分割（内部ヘルパー）
+///
+/// エスケープ（`\|`）、改行（`<br>`）、脚注の参照（`[^1]`）はそれぞれ1単位、
+/// それ以外は書記素クラスタ（結合文字や絵文字のシーケンスを含む1文字）ずつです。
+fn markdown_units(s: &str) -> Vec<&str> {
+    let first_grapheme_len = |s: &str| s.graphemes(true).next().map_or(0, str::len);
+    let mut units = Vec::new();
+    let mut rest = s;
+    while !rest.is_empty() {
+        let len = if let Some(escaped) = rest.strip_prefix('\\') {
+            1 + first_grapheme_len(escaped)
+        } else if rest.starts_with("<br>") {
+            4
+        } else if rest.starts_with("[^") {
+            rest.find(']')
+                .filter(|&end| end > 2 && rest[2..end].bytes().all(|b| b.is_ascii_digit()))
+                .map_or(1, |end| end + 1)
+        } else {
+            first_grapheme_len(rest)
+        };
+        units.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CellRange, CellValue};
+
+    #[test]
+    fn test_cell_new() {
+        let cell = Cell::new("Hello".to_string());
+        assert_eq!(cell.content, "Hello");
+        assert!(!cell.is_merged);
+        assert!(cell.merge_parent.is_none());
+    }
+
+    #[test]
+    fn test_cell_new_merged() {
+        let parent = CellCoord::new(0, 0);
+        let cell = Cell::new_merged("Merged".to_string(), parent);
+        assert_eq!(cell.content, "Merged");
+        assert!(cell.is_merged);
+        assert_eq!(cell.merge_parent, Some(parent));
+    }
+
+    #[test]
+    fn test_cell_set_content_clears_number() {
+        let mut cell = Cell::new("1,234".to_string());
+        cell.number = Some(1234.0);
+        cell.set_content("1,234 JPY".to_string());
+        assert_eq!(cell.content(), "1,234 JPY");
+        assert_eq!(cell.number, None);
+
+        cell.number = Some(1234.0);
+        cell.content_mut().push_str("[^1]");
+        assert_eq!(cell.content(), "1,234 JPY[^1]");
+        assert_eq!(cell.number, None);
+    }
+
+    #[test]
+    fn test_used_range_bloat() {
+        // A1:B3にデータ、A200000に空白文字のみのセル
+        let mut cells = vec![vec![Cell::empty(); 2]; 200_000];
+        for (row, row_cells) in cells.iter_mut().enumerate().take(3) {
+            row_cells[0] = Cell::new(format!("A{}", row + 1));
+            row_cells[1] = Cell::new(format!("B{}", row + 1));
+        }
+        cells[199_999][0] = Cell::new(" ".to_string());
+
+        let mut grid = LogicalGrid {
+            cells,
+            rows: 200_000,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        assert_eq!(grid.non_blank_count(), 6);
+        assert!(grid.is_bloated());
+
+        grid.shrink_to_content();
+        assert_eq!(grid.get_rows(), 3);
+        assert_eq!(grid.get_cols(), 2);
+        assert!(!grid.is_bloated());
+    }
+
+    #[test]
+    fn test_dedupe_consecutive_rows() {
+        let row = |a: &str, b: &str| vec![Cell::new(a.to_string()), Cell::new(b.to_string())];
+        let contents = |grid: &LogicalGrid| -> Vec<Vec<String>> {
+            (0..grid.get_rows())
+                .map(|r| grid.get_row(r).iter().map(|c| c.content.clone()).collect())
+                .collect()
+        };
+
+        let mut grid = LogicalGrid {
+            cells: vec![
+                row("Level", "Message"),
+                row("Level", "Message"),
+                row("WARN", "retrying"),
+                row("WARN", "retrying"),
+                row("WARN", "retrying"),
+                row("INFO", "done"),
+                row("WARN", ""),
+                row("WARN", ""),
+            ],
+            rows: 8,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        assert_eq!(grid.dedupe_consecutive_rows(), 3);
+        assert_eq!(
+            contents(&grid),
+            vec![
+                vec!["Level", "Message"],
+                // 先頭行（見出し）とは集約しない
+                vec!["Level", "Message"],
+                vec!["WARN", "retrying ×3"],
+                vec!["INFO", "done"],
+                vec!["WARN", "×2"],
+            ]
+        );
+
+        // HTMLフォールバックで結合セルを含む場合は集約しない
+        let mut cells = vec![row("H", "H"), row("x", "y"), row("x", "y")];
+        cells[0][1] = Cell::new_merged("H".to_string(), CellCoord::new(0, 0));
+        let mut grid = LogicalGrid {
+            cells,
+            rows: 3,
+            cols: 2,
+            merge_strategy: MergeStrategy::HtmlFallback,
+        };
+        assert_eq!(grid.dedupe_consecutive_rows(), 0);
+        assert_eq!(grid.get_rows(), 3);
+    }
+
+    #[test]
+    fn test_filter_rows() {
+        let row = |a: &str, b: &str| vec![Cell::new(a.to_string()), Cell::new(b.to_string())];
+        let filter = |include: Option<&str>, exclude: Option<&str>| {
+            RowFilter::compile(&ContentFilter {
+                include_rows_matching: include.map(str::to_string),
+                exclude_rows_matching: exclude.map(str::to_string),
+            })
+            .unwrap()
+            .unwrap()
+        };
+        let grid = || LogicalGrid {
+            cells: vec![
+                row("Confidential", ""),
+                row("Region", "Sales"),
+                row("EMEA", "100"),
+                row("APAC", "200"),
+                row("Confidential", ""),
+            ],
+            rows: 5,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        // 除去条件は先頭行にも適用し、残った先頭行を見出しとして扱う
+        let mut g = grid();
+        assert_eq!(
+            g.filter_rows(&filter(Some("^EMEA\t"), Some("^Confidential"))),
+            3
+        );
+        assert_eq!(g.get_rows(), 2);
+        assert_eq!(g.get_row(0)[0].content, "Region");
+        assert_eq!(g.get_row(1)[0].content, "EMEA");
+
+        // セルはタブ区切りで連結して判定する
+        let mut g = grid();
+        assert_eq!(g.filter_rows(&filter(None, Some("APAC\t200"))), 1);
+        assert_eq!(g.get_rows(), 4);
+
+        // 条件が指定されていない場合はコンパイル結果がない
+        assert!(RowFilter::compile(&ContentFilter::default())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_remove_blank_rows() {
+        let row = |a: &str, b: &str| vec![Cell::new(a.to_string()), Cell::new(b.to_string())];
+        let mut cells = vec![
+            row("Name", "Price"),
+            row("", ""),
+            row("Apple", "100"),
+            row(" ", ""),
+            row("", ""),
+            row("Pear", "120"),
+            row("", ""),
+        ];
+        // 結合セルの一部を含む行は除去しない
+        cells[4][0] = Cell::new_merged(String::new(), CellCoord::new(2, 0));
+        let mut grid = LogicalGrid {
+            cells,
+            rows: 7,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        let mut regions = vec![MergedRegion::new(CellRange::new(
+            CellCoord::new(2, 0),
+            CellCoord::new(4, 0),
+        ))];
+        assert_eq!(grid.remove_blank_rows(&mut regions), vec![1, 3, 6]);
+        assert_eq!(grid.get_rows(), 4);
+        assert_eq!(grid.get_row(1)[0].content, "Apple");
+        assert!(grid.get_row(2)[0].is_merged);
+
+        // 除去した行より下の結合範囲と親座標を移動する
+        assert_eq!(regions[0].range.start, CellCoord::new(1, 0));
+        assert_eq!(regions[0].range.end, CellCoord::new(2, 0));
+        assert_eq!(grid.get_row(2)[0].merge_parent, Some(CellCoord::new(1, 0)));
+
+        // HTMLフォールバックで結合セルを含む場合は除去しない
+        grid.merge_strategy = MergeStrategy::HtmlFallback;
+        grid.cells.push(row("", ""));
+        grid.rows += 1;
+        assert!(grid.remove_blank_rows(&mut regions).is_empty());
+        assert_eq!(grid.get_rows(), 5);
+    }
+
+    #[test]
+    fn test_trim_empty() {
+        let row = |cells: [&str; 4]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let build = || {
+            let mut cells = vec![
+                row(["", "", "", ""]),
+                row(["", "Name", "Price", ""]),
+                row(["", "", " ", ""]),
+                row(["", "Apple", "", ""]),
+                row(["", "", "", ""]),
+            ];
+            // 結合セルの一部（子セル）は内容のあるセルとして扱う
+            cells[3][2] = Cell::new_merged(String::new(), CellCoord::new(3, 1));
+            let grid = LogicalGrid {
+                cells,
+                rows: 5,
+                cols: 4,
+                merge_strategy: MergeStrategy::DataDuplication,
+            };
+            let regions = vec![MergedRegion::new(CellRange::new(
+                CellCoord::new(3, 1),
+                CellCoord::new(3, 2),
+            ))];
+            (grid, regions)
+        };
+
+        let (mut grid, mut regions) = build();
+        grid.trim_empty(TrimMode::None, &mut regions);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (5, 4));
+
+        // 末尾のみ除去（左上の位置は変わらない）
+        grid.trim_empty(TrimMode::Trailing, &mut regions);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (4, 3));
+        assert_eq!(grid.get_row(1)[1].content, "Name");
+
+        // 先頭・末尾と表の途中の空行を除去し、結合範囲を移動
+        let (mut grid, mut regions) = build();
+        grid.trim_empty(TrimMode::All, &mut regions);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (2, 2));
+        assert_eq!(grid.get_row(0)[0].content, "Name");
+        assert_eq!(grid.get_row(1)[0].content, "Apple");
+        assert_eq!(grid.get_row(1)[1].merge_parent, Some(CellCoord::new(1, 0)));
+        assert_eq!(regions[0].range.start, CellCoord::new(1, 0));
+        assert_eq!(regions[0].range.end, CellCoord::new(1, 1));
+
+        // すべて空のグリッド
+        let mut grid = LogicalGrid {
+            cells: vec![row(["", " ", "", ""]); 3],
+            rows: 3,
+            cols: 4,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        grid.trim_empty(TrimMode::All, &mut []);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (0, 0));
+    }
+
+    #[test]
+    fn test_render_markdown_sections() {
+        let cell = |content: &str, style: Option<&str>| {
+            let mut cell = Cell::new(content.to_string());
+            cell.style = style.map(str::to_string);
+            cell
+        };
+        let grid = LogicalGrid {
+            cells: vec![
+                vec![cell("Report", Some("Title")), cell("", None)],
+                vec![cell("Revenue", Some("Heading 2")), cell("", None)],
+                vec![cell("Region", None), cell("Sales", None)],
+                vec![cell("EMEA", None), cell("100", None)],
+                // 見出しスタイル以外のセルを含む行は見出しにしない
+                vec![cell("Costs", Some("Heading 2")), cell("note", Some("Note"))],
+                vec![cell("Item", None), cell("Cost", None)],
+            ],
+            rows: 6,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        let mut output = Vec::new();
+        grid.render_markdown_sections(&mut output, None, None)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "## Report\n\n### Revenue\n\n\
+             | Region | Sales |\n\
+             |--------|-------|\n\
+             | EMEA   | 100   |\n\
+             | Costs  | note  |\n\
+             | Item   | Cost  |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_markdown_chunks() {
+        let row = |cells: &[&str]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let grid = LogicalGrid {
+            cells: vec![
+                row(&["Name", "Score"]),
+                row(&["Alice", "90"]),
+                row(&["Bob", "85"]),
+                row(&["Charlotte", "7"]),
+            ],
+            rows: 4,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        let render = |chunk_rows| {
+            let mut output = Vec::new();
+            grid.render_markdown_chunks(&mut output, None, chunk_rows)
+                .unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        // 列幅は表ごとに計算し、各表に見出し行を繰り返す
+        assert_eq!(
+            render(Some((2, ChunkHeader::Repeat))),
+            "| Name  | Score |\n\
+             |-------|-------|\n\
+             | Alice | 90    |\n\
+             | Bob   | 85    |\n\
+             \n\
+             | Name      | Score |\n\
+             |-----------|-------|\n\
+             | Charlotte | 7     |\n"
+        );
+        assert_eq!(
+            render(Some((2, ChunkHeader::FirstOnly))),
+            "| Name  | Score |\n\
+             |-------|-------|\n\
+             | Alice | 90    |\n\
+             | Bob   | 85    |\n\
+             \n\
+             |           |     |\n\
+             |-----------|-----|\n\
+             | Charlotte | 7   |\n"
+        );
+        // データ行数が上限以下の場合は分割しない
+        assert_eq!(render(Some((3, ChunkHeader::Repeat))), render(None));
+    }
 
     #[test]
-    fn test_cell_new() {
-        let cell = Cell::new("Hello".to_string());
-        assert_eq!(cell.content, "Hello");
-        assert!(!cell.is_merged);
-        assert!(cell.merge_parent.is_none());
+    fn test_fold_constant_columns() {
+        let row = |cells: &[&str]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let mut grid = LogicalGrid {
+            cells: vec![
+                row(&["Region", "Product", "", "Note"]),
+                row(&["EMEA", "Apple", "x", ""]),
+                row(&["EMEA", "Orange", "x", ""]),
+            ],
+            rows: 3,
+            cols: 4,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        let folded = grid.fold_constant_columns();
+        // 値が空の列（D）は除去しない。見出しが空の列は列名を見出しとする
+        assert_eq!(
+            folded
+                .iter()
+                .map(|c| (c.column.as_str(), c.header.as_str(), c.value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("A", "Region", "EMEA"), ("C", "C", "x")]
+        );
+        assert_eq!(grid.get_cols(), 2);
+        assert_eq!(grid.get_row(0)[0].content, "Product");
+        assert_eq!(grid.get_row(2)[0].content, "Orange");
+
+        // データ行が1行のみの場合は対象外
+        let mut grid = LogicalGrid {
+            cells: vec![row(&["Region", "Product"]), row(&["EMEA", "Apple"])],
+            rows: 2,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        assert!(grid.fold_constant_columns().is_empty());
     }
 
     #[test]
-    fn test_cell_new_merged() {
-        let parent = CellCoord::new(0, 0);
-        let cell = Cell::new_merged("Merged".to_string(), parent);
-        assert_eq!(cell.content, "Merged");
-        assert!(cell.is_merged);
-        assert_eq!(cell.merge_parent, Some(parent));
+    fn test_used_range_not_bloated() {
+        // 小さなシートは密度にかかわらず肥大化と判定しない
+        let mut cells = vec![vec![Cell::empty(); 10]; 100];
+        cells[0][0] = Cell::new("Header".to_string());
+        let grid = LogicalGrid {
+            cells,
+            rows: 100,
+            cols: 10,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        assert!(!grid.is_bloated());
     }
 
     #[test]
@@ -377,7 +2496,7 @@ mod tests {
     }
 
     #[test]
-    fn test_determine_grid_size() {
+    fn test_grid_size() {
         let cells = vec![
             RawCellData {
                 coord: CellCoord::new(0, 0),
@@ -387,6 +2506,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(2, 3),
@@ -396,10 +2518,13 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
-        let (rows, cols) = LogicalGrid::determine_grid_size(&cells);
+        let (rows, cols) = LogicalGrid::grid_size(&cells);
         assert_eq!(rows, 3);
         assert_eq!(cols, 4);
     }
@@ -416,6 +2541,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let result = LogicalGrid::build(
@@ -438,6 +2567,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -447,6 +2579,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -463,6 +2598,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let result = LogicalGrid::build(
@@ -489,6 +2628,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -498,6 +2640,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 2),
@@ -507,6 +2652,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -523,6 +2671,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let result = LogicalGrid::build(
@@ -538,6 +2690,144 @@ mod tests {
         // 注意: 内部実装の詳細に依存するため、render_markdown()の出力で確認する方が良い
     }
 
+    #[test]
+    fn test_build_with_merged_region_beyond_grid() {
+        let cells = vec![
+            RawCellData {
+                coord: CellCoord::new(0, 0),
+                value: CellValue::String("Header".to_string()),
+                format_id: None,
+                format_string: None,
+                formula: None,
+                hyperlink: None,
+                rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
+            },
+            RawCellData {
+                coord: CellCoord::new(1, 1),
+                value: CellValue::String("B2".to_string()),
+                format_id: None,
+                format_string: None,
+                formula: None,
+                hyperlink: None,
+                rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
+            },
+        ];
+        let formatted_cells = vec![
+            (CellCoord::new(0, 0), "Header".to_string()),
+            (CellCoord::new(1, 1), "B2".to_string()),
+        ];
+
+        // A1:XFD1048576（グリッドの外側にはみ出す結合範囲）と、グリッドの外側の結合範囲
+        let whole_sheet = MergedRegion::new(CellRange::new(
+            CellCoord::new(0, 0),
+            CellCoord::new(1_048_575, 16_383),
+        ));
+        let outside = MergedRegion::new(CellRange::new(CellCoord::new(5, 5), CellCoord::new(6, 6)));
+        assert_eq!(whole_sheet.cell_count_within(2, 2), 4);
+        assert_eq!(outside.cell_count_within(2, 2), 0);
+
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![whole_sheet, outside],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+
+        for strategy in [MergeStrategy::DataDuplication, MergeStrategy::HtmlFallback] {
+            let grid =
+                LogicalGrid::build(cells.clone(), formatted_cells.clone(), &metadata, strategy)
+                    .unwrap();
+            assert_eq!((grid.rows, grid.cols), (2, 2));
+            assert!(grid.cells[1][1].is_merged);
+            let expected = if strategy == MergeStrategy::DataDuplication {
+                "Header"
+            } else {
+                ""
+            };
+            assert_eq!(grid.cells[1][1].content, expected);
+        }
+    }
+
+    #[test]
+    fn test_reference_headers() {
+        let texts = [(0, 0, "Name"), (1, 0, "Bob"), (1, 1, "25")];
+        let cells: Vec<RawCellData> = texts
+            .iter()
+            .map(|&(row, col, text)| RawCellData {
+                coord: CellCoord::new(row, col),
+                value: CellValue::String(text.to_string()),
+                format_id: None,
+                format_string: None,
+                formula: None,
+                hyperlink: None,
+                rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
+            })
+            .collect();
+        let formatted_cells = texts
+            .iter()
+            .map(|&(row, col, text)| (CellCoord::new(row, col), text.to_string()))
+            .collect();
+        // 値の範囲がC3から始まるシート
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 2,
+            first_col: 2,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+        let mut grid = LogicalGrid::build(
+            cells,
+            formatted_cells,
+            &metadata,
+            MergeStrategy::DataDuplication,
+        )
+        .unwrap();
+        assert_eq!(grid.cells[1][1].source, Some(CellCoord::new(3, 3)));
+
+        // 置き換えたセルは元の座標を引き継ぎ、追加した列は元の座標を持たない
+        grid.set_cell(CellCoord::new(1, 1), Cell::new("26".to_string()));
+        grid.push_column(vec![Cell::new("Total".to_string())]);
+        assert_eq!(grid.cells[1][1].source, Some(CellCoord::new(3, 3)));
+
+        let annotated = grid.with_reference_headers();
+        assert_eq!((annotated.rows, annotated.cols), (3, 4));
+        let texts: Vec<Vec<&str>> = annotated
+            .cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.content.as_str()).collect())
+            .collect();
+        assert_eq!(
+            texts,
+            vec![
+                vec!["", "C", "D", ""],
+                vec!["3", "Name", "", "Total"],
+                vec!["4", "Bob", "26", ""],
+            ]
+        );
+    }
+
     #[test]
     fn test_render_markdown() {
         let cells = vec![
@@ -549,6 +2839,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -558,6 +2851,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -574,6 +2870,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let grid = LogicalGrid::build(
@@ -585,11 +2885,11 @@ mod tests {
         .unwrap();
 
         let mut output = Vec::new();
-        let result = grid.render_markdown(&mut output);
+        let result = grid.render_markdown(&mut output, None);
         assert!(result.is_ok());
 
         let markdown = String::from_utf8(output).unwrap();
-        
+
         // フォーマットが正しいことを確認
         // 実際の出力形式: | A1 | B1 | または | A1   | B1   | (列幅に応じて)
         assert!(markdown.contains("A1"));
@@ -613,6 +2913,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -622,6 +2925,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(1, 0),
@@ -631,6 +2937,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(1, 1),
@@ -640,6 +2949,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -658,6 +2970,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let grid = LogicalGrid::build(
@@ -669,21 +2985,43 @@ mod tests {
         .unwrap();
 
         let mut output = Vec::new();
-        let result = grid.render_markdown(&mut output);
+        let result = grid.render_markdown(&mut output, None);
         assert!(result.is_ok());
 
         let markdown = String::from_utf8(output).unwrap();
-        
+
         // trim処理が正しく動作することを確認
         // 前後のスペースが削除され、列幅が適切に計算されている
         // セル内容がtrimされていることを確認
-        assert!(markdown.contains("Header1"), "Markdown should contain 'Header1'. Got: {}", markdown);
-        assert!(markdown.contains("Header2"), "Markdown should contain 'Header2'. Got: {}", markdown);
-        assert!(markdown.contains("Data1"), "Markdown should contain 'Data1'. Got: {}", markdown);
-        assert!(markdown.contains("Data2"), "Markdown should contain 'Data2'. Got: {}", markdown);
+        assert!(
+            markdown.contains("Header1"),
+            "Markdown should contain 'Header1'. Got: {}",
+            markdown
+        );
+        assert!(
+            markdown.contains("Header2"),
+            "Markdown should contain 'Header2'. Got: {}",
+            markdown
+        );
+        assert!(
+            markdown.contains("Data1"),
+            "Markdown should contain 'Data1'. Got: {}",
+            markdown
+        );
+        assert!(
+            markdown.contains("Data2"),
+            "Markdown should contain 'Data2'. Got: {}",
+            markdown
+        );
         // 前後のスペースが削除されていることを確認
-        assert!(!markdown.contains("  Header1  "), "Markdown should not contain spaces around 'Header1'");
-        assert!(!markdown.contains("  Data1  "), "Markdown should not contain spaces around 'Data1'");
+        assert!(
+            !markdown.contains("  Header1  "),
+            "Markdown should not contain spaces around 'Header1'"
+        );
+        assert!(
+            !markdown.contains("  Data1  "),
+            "Markdown should not contain spaces around 'Data1'"
+        );
         // 列幅が統一されていることを確認（区切り行の長さが一致）
         let lines: Vec<&str> = markdown.lines().collect();
         assert!(lines.len() >= 2);
@@ -722,6 +3060,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -731,6 +3072,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -747,6 +3091,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let grid = LogicalGrid::build(
@@ -784,6 +3132,7 @@ mod tests {
             cells: grid_cells,
             rows: 2,
             cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
         };
 
         let widths = grid.calculate_column_widths();
@@ -797,6 +3146,7 @@ mod tests {
             cells: vec![],
             rows: 0,
             cols: 0,
+            merge_strategy: MergeStrategy::DataDuplication,
         };
 
         let col_widths = vec![3, 5, 2];
@@ -814,6 +3164,7 @@ mod tests {
             cells: vec![],
             rows: 0,
             cols: 0,
+            merge_strategy: MergeStrategy::DataDuplication,
         };
 
         let (rowspan, colspan) =
@@ -836,7 +3187,7 @@ mod tests {
                 Cell::new("店舗名".to_string()),         // 3文字 × 2 = 6
             ],
             vec![
-                Cell::new("01100".to_string()), // 5文字 × 1 = 5
+                Cell::new("01100".to_string()),  // 5文字 × 1 = 5
                 Cell::new("札幌店".to_string()), // 3文字 × 2 = 6
             ],
         ];
@@ -845,6 +3196,7 @@ mod tests {
             cells: grid_cells,
             rows: 2,
             cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
         };
 
         let widths = grid.calculate_column_widths();
@@ -864,6 +3216,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(0, 1),
@@ -873,6 +3228,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(1, 0),
@@ -882,6 +3240,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
             RawCellData {
                 coord: CellCoord::new(1, 1),
@@ -891,6 +3252,9 @@ mod tests {
                 formula: None,
                 hyperlink: None,
                 rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
             },
         ];
 
@@ -909,6 +3273,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         let grid = LogicalGrid::build(
@@ -920,7 +3288,7 @@ mod tests {
         .unwrap();
 
         let mut output = Vec::new();
-        let result = grid.render_markdown(&mut output);
+        let result = grid.render_markdown(&mut output, None);
         assert!(result.is_ok());
 
         let markdown = String::from_utf8(output).unwrap();
@@ -959,4 +3327,576 @@ mod tests {
         assert_eq!("市区町村コード".width(), 14); // 7文字 × 2 = 14
         assert_eq!("01100".width(), 5); // 5文字 × 1 = 5
     }
+
+    #[test]
+    fn test_resolve_footnotes() {
+        let raw_cell = |row, col, text: &str| RawCellData {
+            coord: CellCoord::new(row, col),
+            value: CellValue::String(text.to_string()),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        };
+        let cells = vec![raw_cell(0, 0, "A"), raw_cell(1, 1, "B")];
+        let formatted_cells = vec![
+            (CellCoord::new(0, 0), "A".to_string()),
+            (CellCoord::new(1, 1), "B".to_string()),
+        ];
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+        let mut grid = LogicalGrid::build(
+            cells,
+            formatted_cells,
+            &metadata,
+            MergeStrategy::DataDuplication,
+        )
+        .unwrap();
+
+        // 追加順ではなく行優先順で採番される
+        grid.add_footnote(CellCoord::new(1, 1), "second".to_string());
+        grid.add_footnote(CellCoord::new(0, 0), "first".to_string());
+        // グリッド外の座標は無視される
+        grid.add_footnote(CellCoord::new(5, 5), "ignored".to_string());
+
+        let mut footnotes = Footnotes::new(0);
+        grid.resolve_footnotes(&mut footnotes);
+        assert_eq!(grid.get_row(0)[0].content, "A[^1]");
+        assert_eq!(grid.get_row(1)[1].content, "B[^2]");
+
+        let mut output = Vec::new();
+        footnotes.write_markdown(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\n[^1]: first\n[^2]: second\n"
+        );
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let build = |value: CellValue, content: &str| {
+            let cells = vec![RawCellData {
+                coord: CellCoord::new(1, 1),
+                value,
+                format_id: None,
+                format_string: None,
+                formula: None,
+                hyperlink: None,
+                rich_text: None,
+                style_name: None,
+                font: None,
+                fill_color: None,
+            }];
+            let formatted_cells = vec![(CellCoord::new(1, 1), content.to_string())];
+            let metadata = SheetMetadata {
+                name: "Sheet1".to_string(),
+                index: 0,
+                hidden: false,
+                merged_regions: vec![],
+                hidden_rows: vec![],
+                hidden_cols: vec![],
+                is_1904: false,
+                first_row: 0,
+                first_col: 0,
+                zero_size_rows: vec![],
+                zero_size_cols: vec![],
+            };
+            LogicalGrid::build(
+                cells,
+                formatted_cells,
+                &metadata,
+                MergeStrategy::DataDuplication,
+            )
+            .unwrap()
+        };
+
+        // 同じ値なら表示文字列（書式）が異なっても同じハッシュ
+        let a = build(CellValue::Number(1234.5), "1234.5");
+        let b = build(CellValue::Number(1234.5), "1,234.50");
+        assert_eq!(a.content_hash(), b.content_hash());
+
+        // 値や型が異なれば異なるハッシュ
+        let c = build(CellValue::Number(1234.0), "1234");
+        let d = build(CellValue::String("1234.5".to_string()), "1234.5");
+        assert_ne!(a.content_hash(), c.content_hash());
+        assert_ne!(a.content_hash(), d.content_hash());
+    }
+
+    #[test]
+    fn test_row_signature() {
+        let build = |rows: &[&[&str]]| {
+            let mut cells = Vec::new();
+            let mut formatted_cells = Vec::new();
+            for (r, row) in rows.iter().enumerate() {
+                for (c, text) in row.iter().enumerate() {
+                    let coord = CellCoord::new(r as u32, c as u32);
+                    cells.push(RawCellData {
+                        coord,
+                        value: CellValue::String(text.to_string()),
+                        format_id: None,
+                        format_string: None,
+                        formula: None,
+                        hyperlink: None,
+                        rich_text: None,
+                        style_name: None,
+                        font: None,
+                        fill_color: None,
+                    });
+                    formatted_cells.push((coord, text.to_string()));
+                }
+            }
+            let metadata = SheetMetadata {
+                name: "Sheet1".to_string(),
+                index: 0,
+                hidden: false,
+                merged_regions: vec![],
+                hidden_rows: vec![],
+                hidden_cols: vec![],
+                is_1904: false,
+                first_row: 0,
+                first_col: 0,
+                zero_size_rows: vec![],
+                zero_size_cols: vec![],
+            };
+            LogicalGrid::build(
+                cells,
+                formatted_cells,
+                &metadata,
+                MergeStrategy::DataDuplication,
+            )
+            .unwrap()
+        };
+
+        // 行の順序と文字列の前後の空白に依存しない
+        let a = build(&[&["Name", "Qty"], &["apple", "1"], &["pear", "2"]]);
+        let b = build(&[&["Name", "Qty"], &["pear ", "2"], &["apple", "1"]]);
+        assert_eq!(a.row_signature().len(), 3);
+        assert_eq!(a.row_signature(), b.row_signature());
+
+        // 列の位置が異なれば異なる行
+        let c = build(&[&["Name", "Qty"], &["apple", "1"], &["2", "pear"]]);
+        assert_ne!(a.row_signature(), c.row_signature());
+
+        // 空の行は含めない
+        let d = build(&[&["", " "]]);
+        assert!(d.row_signature().is_empty());
+    }
+
+    #[test]
+    fn test_table_hashes() {
+        let build = |rows: &[&[&str]]| {
+            let mut cells = Vec::new();
+            let mut formatted_cells = Vec::new();
+            for (r, row) in rows.iter().enumerate() {
+                for (c, text) in row.iter().enumerate().filter(|(_, text)| !text.is_empty()) {
+                    let coord = CellCoord::new(r as u32, c as u32);
+                    cells.push(RawCellData {
+                        coord,
+                        value: CellValue::String(text.to_string()),
+                        format_id: None,
+                        format_string: None,
+                        formula: None,
+                        hyperlink: None,
+                        rich_text: None,
+                        style_name: None,
+                        font: None,
+                        fill_color: None,
+                    });
+                    formatted_cells.push((coord, text.to_string()));
+                }
+            }
+            let metadata = SheetMetadata {
+                name: "Sheet1".to_string(),
+                index: 0,
+                hidden: false,
+                merged_regions: vec![],
+                hidden_rows: vec![],
+                hidden_cols: vec![],
+                is_1904: false,
+                first_row: 2,
+                first_col: 0,
+                zero_size_rows: vec![],
+                zero_size_cols: vec![],
+            };
+            LogicalGrid::build(
+                cells,
+                formatted_cells,
+                &metadata,
+                MergeStrategy::DataDuplication,
+            )
+            .unwrap()
+        };
+
+        // 空行で区切られた2つの表（2つ目は1列右にずれた同じ内容）と、内容の異なる表
+        let grid = build(&[
+            &["Name", "Qty", ""],
+            &["apple", "1", ""],
+            &["", "", ""],
+            &["", "Name", "Qty"],
+            &["", "apple", "1"],
+            &["", "", ""],
+            &["Name", "Qty", ""],
+            &["pear", "2", ""],
+        ]);
+        let tables = grid.table_hashes();
+        let ranges: Vec<String> = tables.iter().map(|t| t.range.to_string()).collect();
+        assert_eq!(ranges, vec!["A3:B4", "B6:C7", "A9:B10"]);
+        assert_eq!(tables[0].content_hash, tables[1].content_hash);
+        assert_ne!(tables[0].content_hash, tables[2].content_hash);
+        assert!(tables[0].content_hash_string().starts_with("xxh3:"));
+
+        // 空のシートには表がない
+        assert!(build(&[]).table_hashes().is_empty());
+    }
+
+    #[test]
+    fn test_set_cell_and_push_column() {
+        let cells = vec![RawCellData {
+            coord: CellCoord::new(0, 0),
+            value: CellValue::String("A".to_string()),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        }];
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+        let mut grid = LogicalGrid::build(
+            cells,
+            vec![(CellCoord::new(0, 0), "A".to_string())],
+            &metadata,
+            MergeStrategy::DataDuplication,
+        )
+        .unwrap();
+
+        // グリッド外のセルを設定するとグリッドが拡張される
+        grid.set_cell(CellCoord::new(1, 1), Cell::new("B".to_string()));
+        assert_eq!((grid.get_rows(), grid.get_cols()), (2, 2));
+        assert_eq!(grid.get_cell(CellCoord::new(1, 1)).unwrap().content, "B");
+        assert_eq!(grid.get_cell(CellCoord::new(1, 0)).unwrap().content, "");
+        assert!(grid.get_cell(CellCoord::new(2, 0)).is_none());
+
+        // 列は末尾に追加され、行数より多いセルは行を追加する
+        grid.push_column(vec![
+            Cell::new("x".to_string()),
+            Cell::new("y".to_string()),
+            Cell::new("z".to_string()),
+        ]);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (3, 3));
+        assert_eq!(grid.get_row(2)[2].content, "z");
+        assert_eq!(grid.get_row(2).len(), 3);
+    }
+
+    #[test]
+    fn test_forward_fill() {
+        let row = |cells: &[&str]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let mut grid = LogicalGrid {
+            cells: vec![
+                row(&["Region", "City"]),
+                row(&["", "Orphan"]),
+                row(&["East", "Boston"]),
+                row(&[" ", "New York"]),
+                row(&["", ""]),
+                row(&["", "Philadelphia"]),
+                row(&["West", "Seattle"]),
+            ],
+            rows: 7,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        grid.cells[2][0].number = Some(1.0);
+
+        assert_eq!(grid.forward_fill(0), 2);
+        // 複製したセルは生の数値も引き継ぐ
+        assert_eq!(grid.cells[3][0].number, Some(1.0));
+        let regions: Vec<&str> = grid.cells.iter().map(|r| r[0].content.as_str()).collect();
+        // 最初の値より前のセルと、すべてのセルが空の行は埋めない
+        assert_eq!(regions, ["Region", "", "East", "East", "", "East", "West"]);
+        assert_eq!(grid.forward_fill(2), 0);
+    }
+
+    #[test]
+    fn test_truncate_rows() {
+        let row = |cells: &[&str]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let mut grid = LogicalGrid {
+            cells: vec![
+                row(&["Name", "Score"]),
+                row(&["Alice", "90"]),
+                row(&["", " "]),
+                row(&["Bob", "85"]),
+            ],
+            rows: 4,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        // 空白のみのセルは数えない
+        assert_eq!(grid.rows_within_cells(4), 3);
+        assert_eq!(grid.rows_within_cells(6), 4);
+        assert_eq!(grid.rows_within_cells(1), 0);
+
+        assert_eq!(grid.truncate_rows(5), 0);
+        assert_eq!(grid.truncate_rows(2), 2);
+        assert_eq!(grid.get_rows(), 2);
+        assert_eq!(grid.get_row(1)[0].content, "Alice");
+        assert_eq!(grid.truncate_rows(0), 2);
+        assert_eq!((grid.get_rows(), grid.get_cols()), (0, 0));
+    }
+
+    #[test]
+    fn test_sort_rows() {
+        let cell = |content: &str, value: CellValue| {
+            let mut cell = Cell::new(content.to_string());
+            cell.value = value;
+            cell
+        };
+        let text = |s: &str| cell(s, CellValue::String(s.to_string()));
+        let number = |n: f64| cell(&n.to_string(), CellValue::Number(n));
+        let mut grid = LogicalGrid {
+            cells: vec![
+                vec![text("Name"), text("Qty")],
+                vec![text("item10"), number(10.0)],
+                vec![text("item2"), number(9.0)],
+                vec![text(""), text("n/a")],
+                vec![text("item1"), number(100.0)],
+            ],
+            rows: 5,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+        let column = |grid: &LogicalGrid, col: usize| -> Vec<String> {
+            (0..grid.get_rows())
+                .map(|row| grid.get_row(row)[col].content.clone())
+                .collect()
+        };
+
+        assert_eq!(grid.find_header(" qty "), Some(1));
+        assert_eq!(grid.find_header("Price"), None);
+
+        // 文字列として比較（空の値は末尾）
+        assert!(grid.sort_rows(0, SortOrder::Ascending, false));
+        assert_eq!(column(&grid, 0), ["Name", "item1", "item10", "item2", ""]);
+
+        // 数字の並びを数値として比較
+        grid.sort_rows(0, SortOrder::Descending, true);
+        assert_eq!(column(&grid, 0), ["Name", "item10", "item2", "item1", ""]);
+
+        // 数値は値で比較し、文字列より前に配置
+        grid.sort_rows(1, SortOrder::Ascending, true);
+        assert_eq!(column(&grid, 1), ["Qty", "9", "10", "100", "n/a"]);
+        grid.sort_rows(1, SortOrder::Ascending, false);
+        assert_eq!(column(&grid, 1), ["Qty", "10", "100", "9", "n/a"]);
+
+        assert!(!grid.sort_rows(2, SortOrder::Ascending, false));
+    }
+
+    #[test]
+    fn test_render_markdown_with_width_limit() {
+        let row = |cells: &[&str]| -> Vec<Cell> {
+            cells.iter().map(|c| Cell::new(c.to_string())).collect()
+        };
+        let grid = LogicalGrid {
+            cells: vec![
+                row(&["ID", "Description"]),
+                row(&["1", "short"]),
+                row(&["2", "a long description text[^1]"]),
+            ],
+            rows: 3,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        let mut output = Vec::new();
+        grid.render_markdown(&mut output, Some(&WidthLimit::new(12, WrapStrategy::Wrap)))
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "| ID  | Description |\n\
+             |-----|-------------|\n\
+             | 1   | short       |\n\
+             | 2   | a long<br>description<br>text[^1] |\n"
+        );
+
+        let mut output = Vec::new();
+        grid.render_markdown(
+            &mut output,
+            Some(&WidthLimit::new(12, WrapStrategy::Truncate)),
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "| ID  | Description |\n\
+             |-----|-------------|\n\
+             | 1   | short       |\n\
+             | 2   | a long…[^1] |\n"
+        );
+    }
+
+    #[test]
+    fn test_limit_cell_width() {
+        // 上限より長い語は上限の幅ごとに分割し、エスケープは分割しない
+        assert_eq!(
+            limit_cell_width("abcdefgh\\|ij", &WidthLimit::new(4, WrapStrategy::Wrap)),
+            "abcd<br>efgh<br>\\|ij"
+        );
+        // 全角文字は表示幅2
+        assert_eq!(
+            limit_cell_width("日本語の説明", &WidthLimit::new(5, WrapStrategy::Wrap)),
+            "日本<br>語の<br>説明"
+        );
+        // 既存の改行ごとに折り返す
+        assert_eq!(
+            limit_cell_width(
+                "one two<br>three four",
+                &WidthLimit::new(5, WrapStrategy::Wrap)
+            ),
+            "one<br>two<br>three<br>four"
+        );
+        assert_eq!(
+            limit_cell_width(
+                "line one<br>line two",
+                &WidthLimit::new(10, WrapStrategy::Truncate)
+            ),
+            "line one…"
+        );
+        assert_eq!(
+            limit_cell_width("fits", &WidthLimit::new(4, WrapStrategy::Truncate)),
+            "fits"
+        );
+    }
+
+    #[test]
+    fn test_truncate_cell_marker() {
+        // 指定した文字列を付加し、付加後の表示幅は上限以内
+        assert_eq!(
+            truncate_cell("a long description", 10, "[...]"),
+            "a lon[...]"
+        );
+        assert_eq!(truncate_cell("長い説明の文章", 8, "（略）"), "長（略）");
+        assert_eq!(truncate_cell("a long text[^1]", 8, "..."), "a...[^1]");
+        // 省略記号だけで上限を超える場合は省略記号も切り詰める
+        assert_eq!(truncate_cell("description", 4, "[more]"), "[mor");
+        // 書記素クラスタ（結合文字・絵文字のシーケンス）の途中では分割しない
+        assert_eq!(
+            truncate_cell("e\u{301}e\u{301}e\u{301}e\u{301}", 3, "…"),
+            "e\u{301}e\u{301}…"
+        );
+        assert_eq!(truncate_cell("👍🏽👍🏽👍🏽", 5, "…"), "👍🏽👍🏽…");
+        for (content, limit, marker) in [
+            ("a long description", 10, "…"),
+            ("日本語の長い説明", 7, "…"),
+            ("abcdef", 3, "（略）"),
+        ] {
+            assert!(truncate_cell(content, limit, marker).width() <= limit);
+        }
+    }
+
+    #[test]
+    fn test_count_truncated_cells() {
+        let mut heading = Cell::new("Heading text".to_string());
+        heading.style = Some("Heading 1".to_string());
+        let grid = LogicalGrid {
+            cells: vec![
+                vec![heading, Cell::new(String::new())],
+                vec![
+                    Cell::new("a long description".to_string()),
+                    Cell::new(" short ".to_string()),
+                ],
+            ],
+            rows: 2,
+            cols: 2,
+            merge_strategy: MergeStrategy::DataDuplication,
+        };
+
+        assert_eq!(grid.count_truncated_cells(5, false), 2);
+        assert_eq!(grid.count_truncated_cells(5, true), 1);
+        assert_eq!(grid.count_truncated_cells(20, false), 0);
+    }
+
+    #[test]
+    fn test_apply_duplication_placement() {
+        // A1:A4を縦に結合したラベルと、各行の値
+        let regions = [MergedRegion::new(CellRange::new(
+            CellCoord::new(0, 0),
+            CellCoord::new(3, 0),
+        ))];
+        let duplicated = || {
+            let mut cells = vec![vec![
+                Cell::new("Fruit".to_string()),
+                Cell::new("1".to_string()),
+            ]];
+            for value in ["2", "3", "4"] {
+                cells.push(vec![
+                    Cell::new_merged("Fruit".to_string(), regions[0].parent),
+                    Cell::new(value.to_string()),
+                ]);
+            }
+            cells[0][0]
+                .footnotes
+                .push("https://example.com".to_string());
+            LogicalGrid {
+                cells,
+                rows: 4,
+                cols: 2,
+                merge_strategy: MergeStrategy::DataDuplication,
+            }
+        };
+        let labels = |grid: &LogicalGrid| -> Vec<String> {
+            grid.cells
+                .iter()
+                .map(|row| row[0].content.clone())
+                .collect()
+        };
+
+        let mut grid = duplicated();
+        grid.apply_duplication_placement(&regions, DuplicationPlacement::All);
+        assert_eq!(labels(&grid), ["Fruit", "Fruit", "Fruit", "Fruit"]);
+
+        let mut grid = duplicated();
+        grid.apply_duplication_placement(&regions, DuplicationPlacement::FirstOnly);
+        assert_eq!(labels(&grid), ["Fruit", "", "", ""]);
+
+        // 中央の行（偶数行は上側）に親セルごと移動
+        let mut grid = duplicated();
+        grid.apply_duplication_placement(&regions, DuplicationPlacement::Middle);
+        assert_eq!(labels(&grid), ["", "Fruit", "", ""]);
+        assert!(grid.cells[1][0].merge_parent.is_none());
+        assert_eq!(grid.cells[1][0].footnotes, ["https://example.com"]);
+        assert_eq!(grid.cells[0][0].merge_parent, Some(CellCoord::new(1, 0)));
+        assert_eq!(grid.cells[3][1].content, "4");
+    }
 }
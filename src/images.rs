@@ -0,0 +1,168 @@
+//! Images Module
+//!
+//! ワークシートに図形として配置された画像の抽出先（`ConverterBuilder::extract_images_to()`、
+//! `ConverterBuilder::with_image_handler()`）と、抽出先に渡される画像を提供するモジュール。
+
+use crate::api::OutputFormat;
+use crate::error::XlsxToMdError;
+use crate::output::escape_xml;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// 抽出した画像
+///
+/// `ConverterBuilder::with_image_handler()`で設定したコールバックに渡されます。
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ExtractedImage<'a> {
+    /// シート名
+    pub sheet: &'a str,
+
+    /// 画像の配置位置の左上のセル（A1形式、例: `B3`）
+    pub cell: &'a str,
+
+    /// 画像のファイル名（例: `sheet1_img1.png`）
+    pub file_name: &'a str,
+
+    /// 出力に記載した画像のパス（例: `images/sheet1_img1.png`）
+    pub path: &'a str,
+
+    /// ワークブック内の画像ファイルのパーツ名（例: `xl/media/image1.png`）
+    pub source: &'a str,
+
+    /// 代替テキスト（設定されていない場合は`None`）
+    pub description: Option<&'a str>,
+
+    /// 画像ファイルの内容
+    pub data: &'a [u8],
+}
+
+/// 抽出した画像を受け取るコールバック
+type ImageHandler = Arc<dyn Fn(&ExtractedImage<'_>) -> Result<(), XlsxToMdError> + Send + Sync>;
+
+/// 変換設定に保持する画像の抽出先
+///
+/// `ConversionConfig`に保持するため、`Debug`と`Clone`を実装します。
+#[derive(Clone)]
+pub(crate) enum ImageSink {
+    /// 指定したディレクトリに画像ファイルを書き出す
+    Directory(PathBuf),
+
+    /// コールバックに画像を渡す
+    Handler(ImageHandler),
+}
+
+/// コールバックに画像を渡す場合に、出力に記載する画像のディレクトリ
+const HANDLER_IMAGE_DIR: &str = "images";
+
+impl ImageSink {
+    /// 出力に記載する画像のパスを取得（例: `images/sheet1_img1.png`）
+    ///
+    /// ディレクトリに書き出す場合は、指定したディレクトリのパス（区切りは`/`）を使用します。
+    pub fn image_path(&self, file_name: &str) -> String {
+        match self {
+            Self::Directory(dir) => {
+                let dir = dir.to_string_lossy().replace('\\', "/");
+                match dir.trim_end_matches('/') {
+                    "" | "." => file_name.to_string(),
+                    dir => format!("{}/{}", dir, file_name),
+                }
+            }
+            Self::Handler(_) => format!("{}/{}", HANDLER_IMAGE_DIR, file_name),
+        }
+    }
+
+    /// 画像を抽出先に渡す
+    ///
+    /// ディレクトリに書き出す場合、ディレクトリが存在しない場合は作成します。
+    pub fn write(&self, image: &ExtractedImage<'_>) -> Result<(), XlsxToMdError> {
+        match self {
+            Self::Directory(dir) => {
+                std::fs::create_dir_all(dir)?;
+                std::fs::write(dir.join(image.file_name), image.data)?;
+                Ok(())
+            }
+            Self::Handler(handler) => handler(image),
+        }
+    }
+}
+
+impl fmt::Debug for ImageSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Directory(dir) => f.debug_tuple("Directory").field(dir).finish(),
+            Self::Handler(_) => f.write_str("Handler"),
+        }
+    }
+}
+
+/// 画像のファイル名を生成（例: `sheet1_img1.png`）
+///
+/// 拡張子は画像ファイルのパーツ名から取得します（ない場合は`bin`）。
+///
+/// # 引数
+///
+/// * `sheet_number` - ワークブック内のシートの順序（1始まり）
+/// * `image_number` - シート内の画像の順序（1始まり）
+/// * `source` - 画像ファイルのパーツ名
+pub(crate) fn image_file_name(sheet_number: usize, image_number: usize, source: &str) -> String {
+    let extension = source
+        .rsplit_once('/')
+        .map_or(source, |(_, file)| file)
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .filter(|extension| {
+            !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+        .unwrap_or_else(|| "bin".to_string());
+    format!("sheet{}_img{}.{}", sheet_number, image_number, extension)
+}
+
+/// 出力フォーマットに応じた画像の参照を生成
+///
+/// Markdownでは画像の構文（`![代替テキスト](パス)`）、HTMLでは`<img>`要素、
+/// それ以外の出力フォーマットではパスのみを返します。
+///
+/// # 引数
+///
+/// * `format` - 出力フォーマット
+/// * `path` - 画像のパス
+/// * `description` - エスケープ済みの代替テキスト（Markdown・HTMLのみ使用）
+pub(crate) fn image_reference(format: OutputFormat, path: &str, description: &str) -> String {
+    match format {
+        OutputFormat::Markdown => format!("![{}]({})", description, path),
+        OutputFormat::Html => format!(
+            "<img src=\"{}\" alt=\"{}\">",
+            escape_xml(path, true),
+            escape_xml(description, true)
+        ),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_file_name_and_path() {
+        assert_eq!(
+            image_file_name(1, 2, "xl/media/image1.PNG"),
+            "sheet1_img2.png"
+        );
+        assert_eq!(image_file_name(3, 1, "xl/media/image"), "sheet3_img1.bin");
+
+        let sink = ImageSink::Directory(PathBuf::from("out\\images/"));
+        assert_eq!(sink.image_path("a.png"), "out/images/a.png");
+        let sink = ImageSink::Directory(PathBuf::from("."));
+        assert_eq!(sink.image_path("a.png"), "a.png");
+        let sink = ImageSink::Handler(Arc::new(|_| Ok(())));
+        assert_eq!(sink.image_path("a.png"), "images/a.png");
+
+        assert_eq!(
+            image_reference(OutputFormat::Html, "images/a.png", "Front & back"),
+            "<img src=\"images/a.png\" alt=\"Front &amp; back\">"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Inspect Module
+//!
+//! ワークブックを変換せずに、文書管理の画面などで使用する情報を取得するモジュール。
+
+/// ワークブックの情報
+///
+/// `Converter::inspect()`の戻り値として返されます。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::ConverterBuilder;
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let converter = ConverterBuilder::new().build()?;
+/// let info = converter.inspect(File::open("example.xlsx")?)?;
+///
+/// println!("sheets: {:?}", info.sheet_names);
+/// if let Some(thumbnail) = &info.thumbnail {
+///     println!("<img src=\"{}\">", thumbnail.to_data_uri());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WorkbookInfo {
+    /// すべてのシート名（ワークブックでの順序、非表示のシートを含む）
+    pub sheet_names: Vec<String>,
+
+    /// ワークブックに埋め込まれたサムネイル画像（ない場合はNone）
+    pub thumbnail: Option<Thumbnail>,
+}
+
+/// ワークブックに埋め込まれたサムネイル画像
+///
+/// Excelで「縮小版を保存する」を指定して保存したワークブックなどは、最初のシートの
+/// プレビュー画像（通常は`docProps/thumbnail.jpeg`）を含みます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Thumbnail {
+    /// 画像ファイルの内容
+    pub bytes: Vec<u8>,
+
+    /// 画像の形式（例: `image/jpeg`、`image/png`、`image/x-wmf`）
+    ///
+    /// 画像ファイルの先頭のバイト列、判定できない場合は拡張子から決定します。
+    /// どちらでも判定できない場合は`application/octet-stream`です。
+    pub mime: String,
+}
+
+impl Thumbnail {
+    /// Base64で埋め込んだデータURI（例: `data:image/jpeg;base64,/9j/4AAQ...`）を取得
+    ///
+    /// HTMLの`<img>`要素の`src`属性やJSONの値として、画像ファイルを別に保存せずに
+    /// プレビューを表示する場合に使用します。
+    pub fn to_data_uri(&self) -> String {
+        format!("data:{};base64,{}", self.mime, base64_encode(&self.bytes))
+    }
+}
+
+/// Base64（RFC 4648、パディングあり）でエンコード（内部ヘルパー）
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (idx, &b)| n | u32::from(b) << (16 - idx * 8));
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                encoded.push(char::from(ALPHABET[(n >> (18 - idx * 6)) as usize & 0x3F]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_encode(&[0xFF, 0xD8, 0xFF]), "/9j/");
+    }
+
+    #[test]
+    fn test_to_data_uri() {
+        let thumbnail = Thumbnail {
+            bytes: vec![0x89, b'P', b'N', b'G'],
+            mime: "image/png".to_string(),
+        };
+        assert_eq!(thumbnail.to_data_uri(), "data:image/png;base64,iVBORw==");
+    }
+}
@@ -1,4 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+// 不正な入力でパニックしないことを保証するため、ライブラリ本体ではunwrap/expect/panicを禁止する
+#![cfg_attr(
+    not(test),
+    deny(clippy::unwrap_used, clippy::expect_used, clippy::panic)
+)]
 
 //! xlsxzero - Pure-Rust Excel parser and Markdown converter for RAG systems
 //!
@@ -84,20 +89,71 @@
 //! ```
 
 mod api;
+mod batch;
 mod builder;
+mod convert;
+mod document;
+mod duplicates;
+mod env;
 mod error;
+mod expr;
+#[cfg(feature = "fetch")]
+mod fetch;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 mod format;
 mod formatter;
 mod grid;
+mod images;
+mod inspect;
+mod model;
+mod ordered;
 mod output;
 mod parser;
+mod pipeline;
+mod plugin;
+mod progress;
+mod report;
+mod script;
 mod security;
+mod sink;
 mod types;
+mod validation;
+#[cfg(feature = "write")]
+pub mod writer;
 
 // 公開API
-pub use api::{DateFormat, FormulaMode, MergeStrategy, OutputFormat, SheetSelector};
+pub use api::{
+    BlankRows, CellImageMode, ChartMode, ChunkHeader, ColumnSelector, ColumnType, CommentMode,
+    ConditionalFormats, ContentFilter, DataValidations, DateFormat, DuplicateSheets,
+    DuplicationPlacement, FormulaMode, HeaderUnits, HyperlinkMode, JsonCellMode, JsonDocumentMode,
+    JsonKeyMode, JsonStyle, MergeStrategy, NullFormulaPolicy, NumberOutput, OutputFormat,
+    OutputOverflow, PivotMode, Preset, ProtectedSheets, RowDedupe, SheetSelector, SortOrder,
+    TrimMode, Validation, ValidationPolicy, WrapStrategy,
+};
+pub use batch::{BatchConverter, BatchInput, BatchItemReport};
 pub use builder::{Converter, ConverterBuilder};
-pub use error::XlsxToMdError;
+pub use document::GridDocument;
+pub use error::{ConfigError, ErrorKind, SecurityLimit, XlsxToMdError};
+pub use images::ExtractedImage;
+pub use inspect::{Thumbnail, WorkbookInfo};
+pub use model::{CellModel, ColumnModel, RowModel, SheetModel, TableModel, WorkbookModel};
+pub use pipeline::{PipelineStage, Stage, StageCell, StageContext, TextTransformer};
+#[cfg(feature = "plugins")]
+pub use plugin::FORMAT_PLUGINS;
+pub use plugin::{register_format, registered_formats, FormatCapabilities, FormatPlugin};
+pub use progress::ProgressEvent;
+pub use report::{
+    CellChange, ConversionReport, ConversionWarning, DuplicateSheet, FoldedColumn, PhaseTimings,
+    Revision, SheetReport, SheetTimings, WarningKind,
+};
+pub use security::SecurityLimits;
+pub use sink::{DirectorySink, OutputSink, SheetDocument, SheetOutput};
+pub use types::{CellCoord, CellError, CellRange, CellValue, DateKind};
+
+/// `FORMAT_PLUGINS`への登録に使用するlinkme（`plugins`フィーチャー）
+#[cfg(feature = "plugins")]
+pub use linkme;
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,303 @@
+//! Model Module
+//!
+//! 変換結果をシート → 行 → 型付きセルの構造で表す文書モデル（`WorkbookModel`）を提供するモジュール。
+//!
+//! JSON形式の出力（NDJSONを含む）は、シートごとにこのモデルを構築してから出力します。
+//! Rustのプログラムからは`Converter::convert_to_model()`で、JSONの出力と解析を経由せずに
+//! 同じ内容を取得できます。
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::JsonKeyMode;
+use crate::grid::{Cell, LogicalGrid};
+use crate::output::{col_to_letter, header_keys};
+use crate::types::{CellCoord, CellValue, MergedRegion, SheetInfo};
+
+/// ワークブック全体の文書モデル
+///
+/// `Converter::convert_to_model()`の戻り値として返されます。serdeでシリアライズ・デシリアライズできます。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::{CellValue, ConverterBuilder};
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let converter = ConverterBuilder::new().build()?;
+/// let model = converter.convert_to_model(File::open("example.xlsx")?)?;
+///
+/// for sheet in &model.sheets {
+///     for row in &sheet.rows {
+///         for cell in &row.cells {
+///             if let CellValue::Number(n) = cell.value {
+///                 println!("{}!{}: {}", sheet.name, cell.column, n);
+///             }
+///         }
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct WorkbookModel {
+    /// シートのモデル（出力順）
+    pub sheets: Vec<SheetModel>,
+}
+
+/// 1シートの文書モデル
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SheetModel {
+    /// シート名
+    pub name: String,
+
+    /// シート内テキストの主要な文字体系（`latin`、`cjk`、`cyrillic`、`other`）
+    ///
+    /// `ConverterBuilder::include_metadata(true)`の場合のみ判定します（判定できない場合もNone）。
+    #[serde(default)]
+    pub script: Option<String>,
+
+    /// シート内容のハッシュ値（例: `xxh3:0123456789abcdef`）
+    ///
+    /// `ConverterBuilder::include_metadata(true)`の場合のみ計算します。
+    #[serde(default)]
+    pub content_hash: Option<String>,
+
+    /// 空行で区切られた表ごとの範囲とハッシュ値（上から順）
+    ///
+    /// `ConverterBuilder::include_metadata(true)`の場合のみ計算します。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<TableModel>,
+
+    /// 保護されたシートとして識別するか（`ProtectedSheets::Flag`の場合のみ）
+    #[serde(default)]
+    pub protected: bool,
+
+    /// 除去した空行のシート上の行番号（1始まり、`BlankRows::RemoveWithMarker`の場合のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_blank_rows: Vec<u32>,
+
+    /// 列の情報（列順）
+    pub columns: Vec<ColumnModel>,
+
+    /// 行（上から順、見出しをキーとする`JsonKeyMode`の場合は先頭行を除く）
+    pub rows: Vec<RowModel>,
+}
+
+/// シート内の表の情報
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TableModel {
+    /// シート上の表の範囲（例: `A1:C5`）
+    pub range: String,
+
+    /// 表の内容のハッシュ値（例: `xxh3:0123456789abcdef`、表の位置によらず内容が同じなら一致）
+    pub content_hash: String,
+}
+
+/// 列の情報
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ColumnModel {
+    /// セルのキー（`JsonKeyMode`に従った見出し、または列名）
+    pub key: String,
+
+    /// 先頭行の見出し
+    pub header: String,
+
+    /// 列名（例: `A`）
+    pub column: String,
+
+    /// 見出しから検出した単位（`ConverterBuilder::with_header_units()`で検出した場合のみ）
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+/// 1行の文書モデル
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RowModel {
+    /// セル（列順、結合セルは左上のセルのみ）
+    ///
+    /// `FormatCapabilities::supports_merged_cells`が`false`の独自フォーマットでは、
+    /// 結合範囲のすべてのセルを含みます。
+    pub cells: Vec<CellModel>,
+}
+
+/// 型付きセルの文書モデル
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CellModel {
+    /// セルのキー（`ColumnModel::key`と同じ）
+    pub key: String,
+
+    /// 列名（例: `A`）
+    pub column: String,
+
+    /// セルの値（フォーマット前）
+    pub value: CellValue,
+
+    /// 出力フォーマット適用後の表示文字列
+    pub formatted: String,
+
+    /// 表示文字列が生の数値（`NumberOutput::Raw`）の場合の数値
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<f64>,
+
+    /// キャッシュ値を持たない数式セルか
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub formula_no_value: bool,
+
+    /// 名前付きスタイル名（例: `Heading 1`、スタイル名を解析した場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+
+    /// 該当する条件付き書式のルールの説明（`ConditionalFormats::Annotate`、型付きJSONの`Marker`の場合のみ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<String>,
+
+    /// 元のシート上のセル参照（例: `B3`、計算列など元のセルがない場合はNone）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+
+    /// ハイパーリンクのURL（ハイパーリンクを解析した場合のみ）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+
+    /// 結合セルの行数（結合していない場合は1）
+    #[serde(default = "default_span", skip_serializing_if = "is_single_span")]
+    pub row_span: u32,
+
+    /// 結合セルの列数（結合していない場合は1）
+    #[serde(default = "default_span", skip_serializing_if = "is_single_span")]
+    pub col_span: u32,
+}
+
+/// 結合していないセルの行数・列数（serdeの既定値）
+fn default_span() -> u32 {
+    1
+}
+
+/// 結合していないセルか（serdeの出力で省略するか）
+fn is_single_span(span: &u32) -> bool {
+    *span == 1
+}
+
+impl CellModel {
+    /// 生の数値として出力されたセルの数値を取得
+    ///
+    /// `NumberOutput::Raw`で書式を適用せずに出力したセルの場合に`Some`を返します。
+    /// 日付として出力されたセルや、書式・注記・リンクなどで表示文字列を変更したセルは`None`になります。
+    pub fn raw_number(&self) -> Option<f64> {
+        self.number
+    }
+
+    /// グリッドのセルからモデルを生成（内部ヘルパー）
+    fn from_cell(cell: &Cell, column: &ColumnModel, (row_span, col_span): (u32, u32)) -> Self {
+        Self {
+            key: column.key.clone(),
+            column: column.column.clone(),
+            value: cell.value.clone(),
+            formatted: cell.content().to_string(),
+            number: cell.number,
+            formula_no_value: cell.formula_no_value,
+            style: cell.style.clone(),
+            highlights: cell.highlights.clone(),
+            reference: cell.source.map(|source| source.to_a1_notation()),
+            url: cell.hyperlink.clone(),
+            row_span,
+            col_span,
+        }
+    }
+}
+
+impl SheetModel {
+    /// 出力前のグリッドからシートのモデルを構築
+    ///
+    /// # 引数
+    ///
+    /// * `grid` - 出力するグリッド
+    /// * `info` - シート単位の情報
+    /// * `key_mode` - セルのキーの決定方式
+    /// * `merged_regions` - 結合セル範囲のリスト（Noneの場合は結合セルの子も含める）
+    pub(crate) fn from_grid(
+        grid: &LogicalGrid,
+        info: &SheetInfo,
+        key_mode: JsonKeyMode,
+        merged_regions: Option<&[MergedRegion]>,
+    ) -> Self {
+        let cols = grid.get_cols();
+        let rows = if cols == 0 { 0 } else { grid.get_rows() };
+
+        // 列名（A, B, C, ...）と先頭行の見出しから列の情報を作成
+        let column_names: Vec<String> = (0..cols).map(|col| col_to_letter(col as u32)).collect();
+        let headers: Vec<&str> = if rows > 0 {
+            grid.get_row(0).iter().map(|cell| cell.content()).collect()
+        } else {
+            vec![""; cols]
+        };
+        let use_header = key_mode != JsonKeyMode::ColumnLetter && rows > 0;
+        let keys = if use_header {
+            header_keys(&headers, &column_names, key_mode)
+        } else {
+            column_names.clone()
+        };
+        let columns: Vec<ColumnModel> = (0..cols)
+            .map(|col_idx| ColumnModel {
+                key: keys[col_idx].clone(),
+                header: headers[col_idx].to_string(),
+                column: column_names[col_idx].clone(),
+                unit: info
+                    .column_units
+                    .as_ref()
+                    .and_then(|units| units.get(col_idx).cloned().flatten()),
+            })
+            .collect();
+
+        // 各行のセル（結合範囲がある場合は結合セルの子をスキップし、親セルに行数・列数を設定）
+        let rows = (usize::from(use_header)..rows)
+            .map(|row_idx| RowModel {
+                cells: grid
+                    .get_row(row_idx)
+                    .iter()
+                    .zip(&columns)
+                    .enumerate()
+                    .filter_map(|(col_idx, (cell, column))| {
+                        let Some(regions) = merged_regions else {
+                            return Some(CellModel::from_cell(cell, column, (1, 1)));
+                        };
+                        if cell.is_merged && cell.merge_parent.is_some() {
+                            return None;
+                        }
+                        let coord = CellCoord::new(row_idx as u32, col_idx as u32);
+                        let span = regions
+                            .iter()
+                            .find(|region| region.parent == coord)
+                            .map_or((1, 1), |region| (region.row_span(), region.col_span()));
+                        Some(CellModel::from_cell(cell, column, span))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            name: info.name.clone(),
+            script: info.script.map(|script| script.as_str().to_string()),
+            content_hash: info.content_hash_string(),
+            tables: info
+                .tables
+                .iter()
+                .map(|table| TableModel {
+                    range: table.range.to_string(),
+                    content_hash: table.content_hash_string(),
+                })
+                .collect(),
+            protected: info.protected,
+            removed_blank_rows: info.removed_blank_rows.clone(),
+            columns,
+            rows,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Ordered Module
+//!
+//! 複数の要素を並列に処理し、結果を要素の順に受け取るためのモジュール。
+//!
+//! シートの変換は並列に行いますが、出力はワークブックの順である必要があります。
+//! すべての結果を集めてから並べ替えるのではなく、処理が完了した結果を順に受け取り、
+//! 先行する要素の結果がそろった時点で出力します。出力済みの結果は保持しないため、
+//! 同時に保持する結果は順序待ちのものに限られます。
+//!
+//! `parallel`フィーチャー（既定で有効）が無効な場合、およびrayonのワーカースレッド上から
+//! 呼び出された場合（ネストした並列処理）は、要素の順に1つずつ処理します。
+//! どの場合も結果を受け取る順序は同じです。
+
+/// 要素を処理し、結果を要素の順に`emit`に渡す
+///
+/// `work`は要素ごとに（並列に）呼び出され、`emit`は呼び出し元のスレッドで要素の順に
+/// 呼び出されます。`work`がエラーを返した場合は、それより後の未着手の要素の処理を中止し、
+/// エラーになった要素のうち最も前の要素のエラーを返します（逐次処理と同じ結果）。
+/// `emit`がエラーを返した場合は、未着手の要素の処理を中止してそのエラーを返します。
+///
+/// # 引数
+///
+/// * `items` - 処理する要素（出力順）
+/// * `work` - 要素を処理する関数（要素のインデックスと要素を受け取る）
+/// * `emit` - 処理結果を受け取る関数（要素のインデックスと結果を受け取る）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - すべての要素の結果を`emit`に渡した場合
+/// * `Err(E)` - `work`または`emit`がエラーを返した場合
+pub(crate) fn for_each_ordered<T, U, E, F, G>(items: &[T], work: F, mut emit: G) -> Result<(), E>
+where
+    T: Sync,
+    U: Send,
+    E: Send,
+    F: Fn(usize, &T) -> Result<U, E> + Sync,
+    G: FnMut(usize, U) -> Result<(), E>,
+{
+    #[cfg(feature = "parallel")]
+    if items.len() > 1
+        && rayon::current_num_threads() > 1
+        && rayon::current_thread_index().is_none()
+    {
+        return parallel::for_each_ordered(items, work, emit);
+    }
+
+    for (idx, item) in items.iter().enumerate() {
+        emit(idx, work(idx, item)?)?;
+    }
+    Ok(())
+}
+
+/// 同時に処理できる要素の数（`parallel`フィーチャーが無効な場合は1）
+pub(crate) fn concurrency() -> usize {
+    #[cfg(feature = "parallel")]
+    {
+        rayon::current_num_threads()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        1
+    }
+}
+
+#[cfg(feature = "parallel")]
+mod parallel {
+    use rayon::prelude::*;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::mpsc;
+
+    /// rayonで要素を並列に処理し、完了した結果を順序待ちのバッファ経由で要素の順に渡す
+    pub(super) fn for_each_ordered<T, U, E, F, G>(
+        items: &[T],
+        work: F,
+        mut emit: G,
+    ) -> Result<(), E>
+    where
+        T: Sync,
+        U: Send,
+        E: Send,
+        F: Fn(usize, &T) -> Result<U, E> + Sync,
+        G: FnMut(usize, U) -> Result<(), E>,
+    {
+        // エラーになった要素のうち最も前のインデックス（これより後の要素は処理しない）
+        let first_error = AtomicUsize::new(usize::MAX);
+        let (sender, receiver) = mpsc::channel::<(usize, Result<U, E>)>();
+
+        std::thread::scope(|scope| {
+            // 処理はrayonのスレッドプールで行い、呼び出し元のスレッドは結果の受け取りに専念する
+            let (work, first_error) = (&work, &first_error);
+            scope.spawn(move || {
+                items
+                    .par_iter()
+                    .enumerate()
+                    .for_each_with(sender, |sender, (idx, item)| {
+                        if idx > first_error.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let result = work(idx, item);
+                        if result.is_err() {
+                            first_error.fetch_min(idx, Ordering::Relaxed);
+                        }
+                        // 受け取り側が終了している場合は結果を破棄
+                        let _ = sender.send((idx, result));
+                    });
+            });
+
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            // 送信側がすべて終了するとイテレーターが終了する
+            for (idx, result) in receiver.iter() {
+                pending.insert(idx, result);
+                while let Some(result) = pending.remove(&next) {
+                    let emitted = result.and_then(|value| emit(next, value));
+                    if let Err(e) = emitted {
+                        first_error.store(0, Ordering::Relaxed);
+                        return Err(e);
+                    }
+                    next += 1;
+                }
+            }
+
+            // エラーより前の要素はすべて処理されるため、通常はここで残る結果はない
+            match pending.into_values().find_map(Result::err) {
+                Some(e) => Err(e),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 逐次処理（`parallel`が`false`）または並列処理の実装で要素を処理
+    ///
+    /// 並列処理の実装は、スレッド数に関係なく（1スレッドの環境でも）使用する
+    fn run<F, G>(parallel: bool, items: &[usize], work: F, emit: G) -> Result<(), String>
+    where
+        F: Fn(usize, &usize) -> Result<usize, String> + Sync,
+        G: FnMut(usize, usize) -> Result<(), String>,
+    {
+        #[cfg(feature = "parallel")]
+        if parallel {
+            return super::parallel::for_each_ordered(items, work, emit);
+        }
+        let _ = parallel;
+        for_each_ordered(items, work, emit)
+    }
+
+    #[test]
+    fn test_for_each_ordered() {
+        let items: Vec<usize> = (0..200).collect();
+        let expected: Vec<(usize, usize)> = items.iter().map(|&i| (i, i * 2)).collect();
+
+        for parallel in [false, true] {
+            let mut emitted = Vec::new();
+            run(
+                parallel,
+                &items,
+                |idx, item| {
+                    // 後の要素ほど早く完了するようにして、完了順と出力順を変える
+                    std::thread::sleep(std::time::Duration::from_micros((200 - idx as u64) * 10));
+                    Ok(item * 2)
+                },
+                |idx, value| {
+                    emitted.push((idx, value));
+                    Ok(())
+                },
+            )
+            .unwrap();
+            assert_eq!(emitted, expected);
+        }
+    }
+
+    #[test]
+    fn test_for_each_ordered_errors() {
+        let items: Vec<usize> = (0..50).collect();
+
+        for parallel in [false, true] {
+            // 処理のエラーより前の要素はすべて渡され、最も前の要素のエラーを返す
+            let mut emitted = Vec::new();
+            let result = run(
+                parallel,
+                &items,
+                |_, &item| {
+                    if item == 10 || item == 40 {
+                        Err(format!("item {}", item))
+                    } else {
+                        Ok(item)
+                    }
+                },
+                |_, value| {
+                    emitted.push(value);
+                    Ok(())
+                },
+            );
+            assert_eq!(result, Err("item 10".to_string()));
+            assert_eq!(emitted, (0..10).collect::<Vec<_>>());
+
+            // 受け取り側のエラー
+            let result = run(
+                parallel,
+                &items,
+                |_, &item| Ok(item),
+                |idx, _| {
+                    if idx == 3 {
+                        Err("emit".to_string())
+                    } else {
+                        Ok(())
+                    }
+                },
+            );
+            assert_eq!(result, Err("emit".to_string()));
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Change History Module
+//!
+//! 共有ブックの変更履歴の付録（Change history）を提供するモジュール。
+//!
+//! `ConverterBuilder::include_change_history(true)`の場合に、すべてのシートの後に出力されます。
+//! 変更されたセルごとに1行（セルの変更がない履歴は1行）として、日時・ユーザー・シート・セル・
+//! 変更前後の値を出力します。
+
+use super::{escape_html, escape_xml};
+use crate::api::OutputFormat;
+use crate::error::XlsxToMdError;
+use crate::report::{CellChange, Revision};
+use serde_json::{json, Value};
+use std::io::Write;
+
+/// 表の列見出し
+const HEADERS: [&str; 6] = ["Date", "User", "Sheet", "Cell", "Old value", "New value"];
+
+/// 変更履歴をセルの変更ごとの行に展開（内部ヘルパー）
+fn rows(revisions: &[Revision]) -> impl Iterator<Item = (&Revision, Option<&CellChange>)> {
+    revisions.iter().flat_map(|revision| {
+        let changes: Vec<Option<&CellChange>> = if revision.changes.is_empty() {
+            vec![None]
+        } else {
+            revision.changes.iter().map(Some).collect()
+        };
+        changes.into_iter().map(move |change| (revision, change))
+    })
+}
+
+/// 1行分のセル（日時、ユーザー、シート、セル、変更前の値、変更後の値）を取得（内部ヘルパー）
+fn row_cells<'a>(revision: &'a Revision, change: Option<&'a CellChange>) -> [&'a str; 6] {
+    [
+        revision.date_time.as_str(),
+        revision.user.as_str(),
+        change.map_or("", |c| c.sheet.as_str()),
+        change.map_or("", |c| c.cell.as_str()),
+        change.and_then(|c| c.old_value.as_deref()).unwrap_or(""),
+        change.and_then(|c| c.new_value.as_deref()).unwrap_or(""),
+    ]
+}
+
+/// 変更履歴をJSONの配列に変換
+///
+/// 各要素は`date`、`user`、`sheet`、`cell`、`old`、`new`からなるオブジェクトです。
+/// セルの変更がない履歴の`sheet`・`cell`、空のセルの値は`null`になります。
+pub(crate) fn change_history_json(revisions: &[Revision]) -> Value {
+    rows(revisions)
+        .map(|(revision, change)| {
+            json!({
+                "date": revision.date_time,
+                "user": revision.user,
+                "sheet": change.map(|c| &c.sheet),
+                "cell": change.map(|c| &c.cell),
+                "old": change.and_then(|c| c.old_value.as_ref()),
+                "new": change.and_then(|c| c.new_value.as_ref()),
+            })
+        })
+        .collect()
+}
+
+/// 変更履歴の表を出力
+///
+/// Markdownでは表、HTMLでは`<table>`、CSVでは`#`で始まるコメント行として出力します。
+/// XML形式では`<changes>`要素、JSON形式では`changes`配列を持つオブジェクトを出力します。
+/// 見出しやシートとの区切りは出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - 出力フォーマット
+/// * `revisions` - 変更履歴
+/// * `single_line` - JSONを1行で出力するか（`JsonStyle::Minified`またはNDJSONの場合）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_change_history<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    revisions: &[Revision],
+    single_line: bool,
+) -> Result<(), XlsxToMdError> {
+    match format {
+        OutputFormat::Markdown => {
+            writeln!(writer, "| {} |", HEADERS.join(" | "))?;
+            writeln!(writer, "|{}", "---|".repeat(HEADERS.len()))?;
+            for (revision, change) in rows(revisions) {
+                let cells = row_cells(revision, change).map(escape_markdown);
+                writeln!(writer, "| {} |", cells.join(" | "))?;
+            }
+        }
+        OutputFormat::Html => {
+            writeln!(writer, "<table>\n  <tr>")?;
+            for header in HEADERS {
+                writeln!(writer, "    <th>{}</th>", header)?;
+            }
+            writeln!(writer, "  </tr>")?;
+            for (revision, change) in rows(revisions) {
+                writeln!(writer, "  <tr>")?;
+                for cell in row_cells(revision, change) {
+                    writeln!(writer, "    <td>{}</td>", escape_html(cell))?;
+                }
+                writeln!(writer, "  </tr>")?;
+            }
+            writeln!(writer, "</table>")?;
+        }
+        OutputFormat::Csv => {
+            for (revision, change) in rows(revisions) {
+                write!(writer, "# {} {}", revision.date_time, revision.user)?;
+                if let Some(change) = change {
+                    write!(
+                        writer,
+                        ": {}!{} {:?} -> {:?}",
+                        change.sheet,
+                        change.cell,
+                        change.old_value.as_deref().unwrap_or(""),
+                        change.new_value.as_deref().unwrap_or("")
+                    )?;
+                }
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Xml => {
+            writeln!(writer, "<changes>")?;
+            for revision in revisions {
+                let attrs = format!(
+                    "date=\"{}\" user=\"{}\"",
+                    escape_xml(&revision.date_time, true),
+                    escape_xml(&revision.user, true)
+                );
+                if revision.changes.is_empty() {
+                    writeln!(writer, "  <change {}/>", attrs)?;
+                }
+                for change in &revision.changes {
+                    writeln!(
+                        writer,
+                        "  <change {} sheet=\"{}\" cell=\"{}\">",
+                        attrs,
+                        escape_xml(&change.sheet, true),
+                        escape_xml(&change.cell, true)
+                    )?;
+                    if let Some(old) = &change.old_value {
+                        writeln!(writer, "    <old>{}</old>", escape_xml(old, false))?;
+                    }
+                    if let Some(new) = &change.new_value {
+                        writeln!(writer, "    <new>{}</new>", escape_xml(new, false))?;
+                    }
+                    writeln!(writer, "  </change>")?;
+                }
+            }
+            writeln!(writer, "</changes>")?;
+        }
+        _ => {
+            let value = json!({ "changes": change_history_json(revisions) });
+            if single_line {
+                serde_json::to_writer(&mut *writer, &value)
+            } else {
+                serde_json::to_writer_pretty(&mut *writer, &value)
+            }
+            .map_err(XlsxToMdError::json)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// Markdownの表のセルとしてエスケープ（内部ヘルパー）
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revisions() -> Vec<Revision> {
+        vec![
+            Revision {
+                user: "Ada".to_string(),
+                date_time: "2024-05-01T09:30:00Z".to_string(),
+                changes: vec![CellChange {
+                    sheet: "Budget".to_string(),
+                    cell: "B2".to_string(),
+                    old_value: Some("120".to_string()),
+                    new_value: Some("a|b".to_string()),
+                }],
+            },
+            Revision {
+                user: "Alan".to_string(),
+                date_time: "2024-05-02T10:00:00Z".to_string(),
+                changes: Vec::new(),
+            },
+        ]
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let mut output = Vec::new();
+        write_change_history(&mut output, format, &revisions(), false).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_write_change_history() {
+        assert_eq!(
+            render(OutputFormat::Markdown),
+            "| Date | User | Sheet | Cell | Old value | New value |\n\
+             |---|---|---|---|---|---|\n\
+             | 2024-05-01T09:30:00Z | Ada | Budget | B2 | 120 | a\\|b |\n\
+             | 2024-05-02T10:00:00Z | Alan |  |  |  |  |\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Csv),
+            "# 2024-05-01T09:30:00Z Ada: Budget!B2 \"120\" -> \"a|b\"\n\
+             # 2024-05-02T10:00:00Z Alan\n"
+        );
+        assert_eq!(
+            render(OutputFormat::Xml),
+            "<changes>\n  <change date=\"2024-05-01T09:30:00Z\" user=\"Ada\" sheet=\"Budget\" cell=\"B2\">\n\
+             \x20   <old>120</old>\n    <new>a|b</new>\n  </change>\n\
+             \x20 <change date=\"2024-05-02T10:00:00Z\" user=\"Alan\"/>\n</changes>\n"
+        );
+
+        let value: Value = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+        assert_eq!(value["changes"][0]["new"], "a|b");
+        assert_eq!(value["changes"][1]["user"], "Alan");
+        assert!(value["changes"][1]["cell"].is_null());
+    }
+}
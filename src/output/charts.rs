@@ -0,0 +1,91 @@
+//! Charts Module
+//!
+//! シートに配置されたグラフの注記（`ChartMode::Placeholder`）と、
+//! キャッシュされた系列のデータの表（`ChartMode::Data`）の出力を提供するモジュール。
+
+use super::escape_html;
+use crate::api::{ChartMode, OutputFormat};
+use crate::error::XlsxToMdError;
+use crate::grid::LogicalGrid;
+use crate::parser::Chart;
+use std::io::Write;
+
+/// シートに配置されたグラフを出力
+///
+/// 各グラフの概要（例: `Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec`）を、
+/// Markdownでは引用、HTMLでは段落、CSVでは`#`で始まるコメント行、XMLではコメントとして出力します。
+/// `ChartMode::Data`の場合、MarkdownとHTMLでは概要に続けて、項目を行・系列を列とする表を出力します。
+/// JSON形式とグラフがない場合は何も出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `mode` - グラフの扱い
+/// * `charts` - 出力するグラフ（配置位置の順）
+/// * `format_text` - 表のセルの文字列をエスケープする関数
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_charts<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    mode: ChartMode,
+    charts: &[Chart],
+    format_text: impl Fn(&str) -> String,
+) -> Result<(), XlsxToMdError> {
+    if mode == ChartMode::Skip {
+        return Ok(());
+    }
+
+    for chart in charts {
+        let summary = chart.summary().replace(['\r', '\n'], " ");
+        match format {
+            OutputFormat::Markdown => writeln!(writer, "\n> {}", summary)?,
+            OutputFormat::Html => writeln!(writer, "<p>{}</p>", escape_html(&summary))?,
+            OutputFormat::Csv => writeln!(writer, "# {}", summary)?,
+            OutputFormat::Xml => writeln!(writer, "<!-- {} -->", summary.replace("--", "- -"))?,
+            _ => return Ok(()),
+        }
+
+        if mode != ChartMode::Data || chart.series.is_empty() {
+            continue;
+        }
+        let grid = data_grid(chart, &format_text);
+        match format {
+            OutputFormat::Markdown => {
+                writeln!(writer)?;
+                grid.render_markdown(writer, None)?;
+            }
+            OutputFormat::Html => grid.render_html(writer, &[])?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// グラフの系列のデータから表のグリッドを構築（内部ヘルパー）
+///
+/// 先頭行は空のセルと系列名、以降の各行は項目名と各系列の値です。
+fn data_grid(chart: &Chart, format_text: &impl Fn(&str) -> String) -> LogicalGrid {
+    let mut rows = Vec::with_capacity(chart.data_rows() + 1);
+    rows.push(
+        std::iter::once(String::new())
+            .chain(chart.series_names().iter().map(|name| format_text(name)))
+            .collect(),
+    );
+    for row in 0..chart.data_rows() {
+        rows.push(
+            std::iter::once(format_text(&chart.category(row)))
+                .chain(
+                    chart.series.iter().map(|series| {
+                        format_text(series.values.get(row).map_or("", String::as_str))
+                    }),
+                )
+                .collect(),
+        );
+    }
+    LogicalGrid::from_rows(rows)
+}
@@ -0,0 +1,147 @@
+//! Footnotes Module
+//!
+//! Markdownの脚注（`[^n]`）の採番と脚注セクションの出力を提供するモジュール。
+//!
+//! セルのコメント、ハイパーリンク（`HyperlinkMode::Footnote`）、切り詰めたセルの全文など、
+//! 表の中に置くと列幅を広げてしまう内容をシート末尾に移すために使用します。
+
+use crate::error::XlsxToMdError;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// シート単位の脚注の集合
+///
+/// 脚注はレンダリング時に追加された順（行優先でのセルの走査順）に1から採番されるため、
+/// 同じ入力からは常に同じ番号が得られます。
+/// 同じ本文の脚注（同じURLのハイパーリンクなど）は1つにまとめ、同じ参照を返します。
+/// 複数シートを1つの文書に出力した場合にラベルが重複しないよう、
+/// 2番目以降のシートのラベルにはシートの出力順を付加します（例: `[^2-1]`）。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Footnotes {
+    /// ラベルの接頭辞（先頭のシートは空文字列）
+    label_prefix: String,
+
+    /// 脚注の本文（追加順）
+    entries: Vec<String>,
+
+    /// 本文から脚注番号への対応（同じ本文の脚注をまとめるため）
+    numbers: HashMap<String, usize>,
+}
+
+impl Footnotes {
+    /// シートの出力順を指定して生成
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_index` - シートの出力順（0始まり）
+    pub fn new(sheet_index: usize) -> Self {
+        let label_prefix = if sheet_index == 0 {
+            String::new()
+        } else {
+            format!("{}-", sheet_index + 1)
+        };
+        Self {
+            label_prefix,
+            entries: Vec::new(),
+            numbers: HashMap::new(),
+        }
+    }
+
+    /// 脚注を追加し、本文に挿入する参照（例: `[^1]`）を返す
+    ///
+    /// 同じ本文の脚注が追加済みの場合は、新たに採番せずに既存の参照を返します。
+    ///
+    /// # 引数
+    ///
+    /// * `text` - 脚注の本文
+    pub fn push(&mut self, text: impl Into<String>) -> String {
+        let text = text.into();
+        let number = match self.numbers.get(&text) {
+            Some(&number) => number,
+            None => {
+                self.entries.push(text.clone());
+                self.numbers.insert(text, self.entries.len());
+                self.entries.len()
+            }
+        };
+        format!("[^{}]", self.label(number))
+    }
+
+    /// 脚注が1つもないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 脚注セクションをMarkdownとして出力
+    ///
+    /// 脚注がない場合は何も出力しません。
+    /// 本文の2行目以降は、脚注の続きとして解釈されるようにインデントします。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write_markdown<W: Write>(&self, writer: &mut W) -> Result<(), XlsxToMdError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(writer)?;
+        for (idx, text) in self.entries.iter().enumerate() {
+            let mut lines = text.lines();
+            writeln!(
+                writer,
+                "[^{}]: {}",
+                self.label(idx + 1),
+                lines.next().unwrap_or("")
+            )?;
+            for line in lines {
+                writeln!(writer, "    {}", line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 脚注番号からラベルを生成（内部ヘルパー）
+    fn label(&self, number: usize) -> String {
+        format!("{}{}", self.label_prefix, number)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(footnotes: &Footnotes) -> String {
+        let mut output = Vec::new();
+        footnotes.write_markdown(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_footnotes_numbering() {
+        let mut footnotes = Footnotes::new(0);
+        assert!(footnotes.is_empty());
+        assert_eq!(render(&footnotes), "");
+
+        assert_eq!(footnotes.push("https://example.com"), "[^1]");
+        assert_eq!(footnotes.push("first line\nsecond line"), "[^2]");
+        // 同じ本文の脚注は1つにまとめる
+        assert_eq!(footnotes.push("https://example.com"), "[^1]");
+        assert_eq!(
+            render(&footnotes),
+            "\n[^1]: https://example.com\n[^2]: first line\n    second line\n"
+        );
+    }
+
+    #[test]
+    fn test_footnotes_label_prefix() {
+        let mut footnotes = Footnotes::new(2);
+        assert_eq!(footnotes.push("note"), "[^3-1]");
+        assert_eq!(render(&footnotes), "\n[^3-1]: note\n");
+    }
+}
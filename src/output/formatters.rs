@@ -2,22 +2,43 @@
 //!
 //! 各出力フォーマットの実装を提供するモジュール。
 
+use crate::api::{ChunkHeader, JsonCellMode, JsonKeyMode, MergeStrategy, NumberOutput};
 use crate::error::XlsxToMdError;
-use crate::grid::LogicalGrid;
+use crate::grid::{Cell, LogicalGrid, WidthLimit};
+use crate::model::{CellModel, SheetModel};
 use crate::types::MergedRegion;
+use crate::types::{CellValue, SheetInfo};
 use std::io::Write;
 
 /// Markdown形式のフォーマッター
-pub struct MarkdownFormatter;
+#[derive(Debug, Clone)]
+pub struct MarkdownFormatter {
+    /// 見出しスタイルの行で表を区切り、Markdownの見出しとして出力するか
+    pub heading_sections: bool,
+
+    /// 列幅の上限
+    pub column_width_limit: Option<WidthLimit>,
+
+    /// 表を分割する行数（データ行数の上限, 見出し行の扱い）
+    pub chunk_rows: Option<(usize, ChunkHeader)>,
+}
 
 impl MarkdownFormatter {
     pub fn render<W: Write>(
         &self,
         grid: &LogicalGrid,
         writer: &mut W,
-        _merged_regions: &[MergedRegion],
+        merged_regions: &[MergedRegion],
     ) -> Result<(), XlsxToMdError> {
-        grid.render_markdown(writer)
+        // HTMLフォールバック戦略で結合セルが存在する場合は、HTMLテーブルを埋め込む
+        if grid.merge_strategy() == MergeStrategy::HtmlFallback && !merged_regions.is_empty() {
+            return grid.render_html(writer, merged_regions);
+        }
+        let width_limit = self.column_width_limit.as_ref();
+        if self.heading_sections {
+            return grid.render_markdown_sections(writer, width_limit, self.chunk_rows);
+        }
+        grid.render_markdown_chunks(writer, width_limit, self.chunk_rows)
     }
 }
 
@@ -36,67 +57,256 @@ impl HtmlFormatter {
 }
 
 /// JSON形式のフォーマッター
-pub struct JsonFormatter;
+#[derive(Debug, Clone, Copy)]
+pub struct JsonFormatter {
+    /// 数値出力方式（`Raw`の場合、数値セルをJSONの数値として出力）
+    pub number_output: NumberOutput,
+
+    /// セルの表現方式
+    pub cell_mode: JsonCellMode,
+
+    /// 行オブジェクトのキー
+    pub key_mode: JsonKeyMode,
+
+    /// シート単位のメタデータを`metadata`オブジェクトとして含めるか
+    pub include_metadata: bool,
+
+    /// 値のない数式セルに`formula_no_value`フラグを付与するか（型付きセルのみ）
+    pub flag_formula_no_value: bool,
+
+    /// セルの名前付きスタイル名を`style`として含めるか（型付きセルのみ）
+    pub include_styles: bool,
+
+    /// 改行を含まない1行のJSONとして出力するか（`JsonStyle::Minified`またはNDJSON）
+    pub single_line: bool,
+
+    /// 元のシート上のセル参照を`ref`として含めるか
+    pub cell_references: bool,
+
+    /// ハイパーリンクのURLを`url`として含めるか（`HyperlinkMode::TextOnly`以外）
+    pub hyperlinks: bool,
+
+    /// シートのオブジェクトで囲まず、行オブジェクトの配列のみを出力するか
+    /// （`JsonDocumentMode::Records`）
+    pub records: bool,
+
+    /// `fingerprint`オブジェクトとして出力する設定のハッシュ値（Noneの場合は出力しない）
+    pub config_hash: Option<u64>,
+}
 
 impl JsonFormatter {
     pub fn render<W: Write>(
         &self,
         grid: &LogicalGrid,
         writer: &mut W,
-        _merged_regions: &[MergedRegion],
+        merged_regions: &[MergedRegion],
+        info: &SheetInfo,
     ) -> Result<(), XlsxToMdError> {
         use serde_json::json;
 
-        // グリッドの行と列の情報を取得
-        let rows = grid.get_rows();
-        let cols = grid.get_cols();
-
-        if rows == 0 || cols == 0 {
+        if (grid.get_rows() == 0 || grid.get_cols() == 0)
+            && !self.records
+            && !self.include_metadata
+            && self.config_hash.is_none()
+            && info.links.is_none()
+            && info.removed_blank_rows.is_empty()
+        {
             // 空のグリッドの場合は空のJSONオブジェクトを出力
             writeln!(writer, "{{}}")?;
             return Ok(());
         }
 
-        // 列名を生成（A, B, C, ...）
-        let column_names: Vec<String> = (0..cols)
-            .map(|col| {
-                let col_letter = col_to_letter(col as u32);
-                col_letter
-            })
-            .collect();
+        // 文書モデルを構築し、行オブジェクトのキーと値に変換
+        let model = SheetModel::from_grid(grid, info, self.key_mode, Some(merged_regions));
+        let has_rows = grid.get_rows() > 0 && grid.get_cols() > 0;
+        let use_header = self.key_mode != JsonKeyMode::ColumnLetter && has_rows;
 
-        // 各行をオブジェクトとして構築
-        let json_rows: Vec<serde_json::Value> = (0..rows)
-            .map(|row_idx| {
-                let row = grid.get_row(row_idx);
-                let mut row_obj = serde_json::Map::new();
-
-                for (col_idx, cell) in row.iter().enumerate() {
-                    let col_name = &column_names[col_idx];
-                    // 結合セルの子はスキップ（親セルのみ含める）
-                    if !cell.is_merged || cell.merge_parent.is_none() {
-                        row_obj.insert(col_name.clone(), json!(cell.content));
-                    }
-                }
+        // キーと見出しの対応（見出しをキーとする場合、または単位の検出時のみ）
+        let columns: Option<Vec<serde_json::Value>> =
+            (use_header || (has_rows && info.column_units.is_some())).then(|| {
+                model
+                    .columns
+                    .iter()
+                    .map(|column| {
+                        let mut obj = json!({
+                            "key": column.key,
+                            "header": column.header,
+                            "column": column.column,
+                        });
+                        if info.column_units.is_some() {
+                            obj["unit"] = json!(column.unit);
+                        }
+                        obj
+                    })
+                    .collect()
+            });
 
+        // 各行をオブジェクトとして構築（空のグリッドの場合は空配列）
+        let json_rows: Vec<serde_json::Value> = model
+            .rows
+            .iter()
+            .map(|row| {
+                let row_obj: serde_json::Map<String, serde_json::Value> = row
+                    .cells
+                    .iter()
+                    .map(|cell| (cell.key.clone(), self.cell_to_json(cell)))
+                    .collect();
                 json!(row_obj)
             })
             .collect();
 
-        // JSONオブジェクトを構築
-        let json_output = json!({
-            "rows": json_rows
-        });
+        // レコード形式では行オブジェクトの配列のみを出力
+        if self.records {
+            return self.write_json(writer, &json!(json_rows));
+        }
 
-        // JSONを出力
-        serde_json::to_writer_pretty(&mut *writer, &json_output).map_err(|e| {
-            XlsxToMdError::Config(format!("JSON serialization error: {}", e))
-        })?;
+        // JSONオブジェクトを構築（複数シートの文書で識別できるよう、シート名を常に含める）
+        let mut json_output = serde_json::Map::new();
+        json_output.insert("name".to_string(), json!(model.name));
+        if self.include_metadata {
+            let mut metadata = json!({
+                "sheet_name": model.name,
+                "script": model.script,
+                "content_hash": model.content_hash,
+            });
+            if !model.tables.is_empty() {
+                metadata["tables"] = json!(model.tables);
+            }
+            if model.protected {
+                metadata["protected"] = json!(true);
+            }
+            if !info.visuals.sparklines.is_empty() {
+                metadata["sparklines"] = info.visuals.sparklines_json();
+            }
+            if !info.visuals.data_bars.is_empty() {
+                metadata["data_bars"] = info.visuals.data_bars_json();
+            }
+            json_output.insert("metadata".to_string(), metadata);
+        }
+        if let Some(hash) = self.config_hash {
+            json_output.insert(
+                "fingerprint".to_string(),
+                json!({
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "config_hash": crate::output::config_hash_string(hash),
+                }),
+            );
+        }
+        if let Some(links) = &info.links {
+            let links: Vec<serde_json::Value> = links
+                .entries()
+                .map(|(url, cells)| json!({ "url": url, "cells": cells }))
+                .collect();
+            json_output.insert("links".to_string(), json!(links));
+        }
+        if !info.conditional_formats.is_empty() {
+            let formats: Vec<serde_json::Value> = info
+                .conditional_formats
+                .iter()
+                .map(|format| format.to_json())
+                .collect();
+            json_output.insert("conditional_formats".to_string(), json!(formats));
+        }
+        if !info.data_validations.is_empty() {
+            let validations: Vec<serde_json::Value> = info
+                .data_validations
+                .iter()
+                .map(|validation| validation.to_json())
+                .collect();
+            json_output.insert("data_validations".to_string(), json!(validations));
+        }
+        if !model.removed_blank_rows.is_empty() {
+            json_output.insert(
+                "removed_blank_rows".to_string(),
+                json!(model.removed_blank_rows),
+            );
+        }
+        if let Some(columns) = columns {
+            json_output.insert("columns".to_string(), json!(columns));
+        }
+        json_output.insert("rows".to_string(), json!(json_rows));
+        self.write_json(writer, &serde_json::Value::Object(json_output))
+    }
+
+    /// JSON値を書式の設定に応じて出力（内部ヘルパー）
+    fn write_json<W: Write>(
+        &self,
+        writer: &mut W,
+        value: &serde_json::Value,
+    ) -> Result<(), XlsxToMdError> {
+        if self.single_line {
+            serde_json::to_writer(&mut *writer, value)
+        } else {
+            serde_json::to_writer_pretty(&mut *writer, value)
+        }
+        .map_err(XlsxToMdError::json)?;
         writeln!(writer)?;
         writer.flush()?;
 
         Ok(())
     }
+
+    /// セルをJSON値に変換（内部ヘルパー）
+    fn cell_to_json(&self, cell: &CellModel) -> serde_json::Value {
+        use serde_json::json;
+
+        match self.cell_mode {
+            JsonCellMode::Typed => {
+                let (cell_type, raw) = match &cell.value {
+                    CellValue::Number(n) => ("number", json_number(*n)),
+                    CellValue::DateTime { serial, kind } => (kind.as_str(), json_number(*serial)),
+                    CellValue::String(s) => ("string", json!(s)),
+                    CellValue::Bool(b) => ("bool", json!(b)),
+                    CellValue::Error(e) => ("error", json!(e.as_excel_str())),
+                    CellValue::Empty => ("empty", serde_json::Value::Null),
+                };
+                let mut obj = json!({
+                    "type": cell_type,
+                    "raw": raw,
+                    "formatted": cell.formatted,
+                });
+                if let CellValue::Error(e) = &cell.value {
+                    obj["error"] = json!(e.as_str());
+                }
+                if self.flag_formula_no_value && cell.formula_no_value {
+                    obj["formula_no_value"] = json!(true);
+                }
+                if let (true, Some(style)) = (self.include_styles, &cell.style) {
+                    obj["style"] = json!(style);
+                }
+                if !cell.highlights.is_empty() {
+                    obj["highlight"] = json!(true);
+                    obj["highlights"] = json!(cell.highlights);
+                }
+                if let (true, Some(reference)) = (self.cell_references, &cell.reference) {
+                    obj["ref"] = json!(reference);
+                }
+                if let (true, Some(url)) = (self.hyperlinks, &cell.url) {
+                    obj["url"] = json!(url);
+                }
+                obj
+            }
+            JsonCellMode::Plain => {
+                let value = match cell.raw_number() {
+                    Some(n) if self.number_output == NumberOutput::Raw => json_number(n),
+                    _ => json!(cell.formatted),
+                };
+                let url = cell.url.as_ref().filter(|_| self.hyperlinks);
+                if !self.cell_references && url.is_none() {
+                    return value;
+                }
+                // セル参照・ハイパーリンクを含める場合は、値と参照・URLを持つオブジェクトとして出力
+                let mut obj = json!({ "value": value });
+                if let (true, Some(reference)) = (self.cell_references, &cell.reference) {
+                    obj["ref"] = json!(reference);
+                }
+                if let Some(url) = url {
+                    obj["url"] = json!(url);
+                }
+                obj
+            }
+        }
+    }
 }
 
 /// CSV形式のフォーマッター
@@ -118,35 +328,163 @@ impl CsvFormatter {
 
         // 各行をCSV形式で出力
         for row_idx in 0..rows {
-            let row = grid.get_row(row_idx);
-            let mut first = true;
+            // 結合セルの子はスキップ（親セルのみ含める）
+            let contents = grid
+                .get_row(row_idx)
+                .iter()
+                .filter(|cell| !(cell.is_merged && cell.merge_parent.is_some()))
+                .map(Cell::content);
+            write_csv_row(writer, contents)?;
+        }
 
-            for (_col_idx, cell) in row.iter().enumerate() {
-                // 結合セルの子はスキップ（親セルのみ含める）
-                if cell.is_merged && cell.merge_parent.is_some() {
-                    continue;
-                }
+        writer.flush()?;
+        Ok(())
+    }
+}
 
-                if !first {
-                    write!(writer, ",")?;
-                }
-                first = false;
+/// CSVの1行を出力
+///
+/// 各セルの内容をエスケープし、カンマで区切って出力します。
+pub(crate) fn write_csv_row<'a, W: Write>(
+    writer: &mut W,
+    contents: impl IntoIterator<Item = &'a str>,
+) -> Result<(), XlsxToMdError> {
+    for (col_idx, content) in contents.into_iter().enumerate() {
+        if col_idx > 0 {
+            write!(writer, ",")?;
+        }
+        // CSVエスケープ処理
+        write!(writer, "{}", escape_csv(content))?;
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// XML形式のフォーマッター
+#[derive(Debug, Clone, Copy)]
+pub struct XmlFormatter {
+    /// シート単位のメタデータを`<sheet>`要素の属性として含めるか
+    pub include_metadata: bool,
+
+    /// ハイパーリンクのURLを`<cell>`要素の`href`属性として含めるか（`HyperlinkMode::TextOnly`以外）
+    pub hyperlinks: bool,
+}
 
-                // CSVエスケープ処理
-                let escaped = escape_csv(&cell.content);
-                write!(writer, "{}", escaped)?;
+impl XmlFormatter {
+    pub fn render<W: Write>(
+        &self,
+        grid: &LogicalGrid,
+        writer: &mut W,
+        _merged_regions: &[MergedRegion],
+        info: &SheetInfo,
+    ) -> Result<(), XlsxToMdError> {
+        write!(writer, "<sheet name=\"{}\"", escape_xml(&info.name, true))?;
+        if self.include_metadata {
+            if let Some(script) = info.script {
+                write!(writer, " script=\"{}\"", script.as_str())?;
+            }
+            if let Some(hash) = info.content_hash_string() {
+                write!(writer, " content_hash=\"{}\"", hash)?;
             }
+            if info.protected {
+                write!(writer, " protected=\"true\"")?;
+            }
+        }
 
-            writeln!(writer)?;
+        let rows: Vec<(usize, &[Cell])> = (0..grid.get_rows())
+            .map(|row_idx| (row_idx, grid.get_row(row_idx)))
+            .filter(|(_, row)| row.iter().any(is_xml_cell))
+            .collect();
+        let links = info.links.as_ref().filter(|links| !links.is_empty());
+        let visuals = Some(&info.visuals).filter(|v| self.include_metadata && !v.is_empty());
+        if rows.is_empty() && links.is_none() && visuals.is_none() {
+            writeln!(writer, "/>")?;
+            return Ok(());
+        }
+        writeln!(writer, ">")?;
+
+        // ハイパーリンクの一覧（参照元のセルは空白区切り）
+        if let Some(links) = links {
+            writeln!(writer, "  <links>")?;
+            for (url, cells) in links.entries() {
+                writeln!(
+                    writer,
+                    "    <link url=\"{}\" cells=\"{}\"/>",
+                    escape_xml(url, true),
+                    cells.join(" ")
+                )?;
+            }
+            writeln!(writer, "  </links>")?;
+        }
+
+        // スパークラインとデータバー（描画位置と参照元の範囲）
+        if let Some(visuals) = visuals {
+            writeln!(writer, "  <visuals>")?;
+            for sparkline in &visuals.sparklines {
+                writeln!(
+                    writer,
+                    "    <sparkline range=\"{}\" source=\"{}\" type=\"{}\"/>",
+                    escape_xml(&sparkline.range, true),
+                    escape_xml(&sparkline.source, true),
+                    escape_xml(&sparkline.kind, true)
+                )?;
+            }
+            for range in &visuals.data_bars {
+                let range = escape_xml(range, true);
+                writeln!(
+                    writer,
+                    "    <data_bar range=\"{}\" source=\"{}\"/>",
+                    range, range
+                )?;
+            }
+            writeln!(writer, "  </visuals>")?;
+        }
+
+        // 各行を出力（空のセルと結合セルの子は出力しない）
+        for (row_idx, row) in rows {
+            writeln!(writer, "  <row r=\"{}\">", row_idx + 1)?;
+            for (col_idx, cell) in row.iter().enumerate() {
+                if !is_xml_cell(cell) {
+                    continue;
+                }
+                let cell_type = match cell.value {
+                    CellValue::Number(_) => "n",
+                    CellValue::DateTime { .. } => "d",
+                    CellValue::Bool(_) => "b",
+                    CellValue::Error(_) => "e",
+                    CellValue::String(_) | CellValue::Empty => "s",
+                };
+                write!(
+                    writer,
+                    "    <cell r=\"{}{}\" t=\"{}\"",
+                    col_to_letter(col_idx as u32),
+                    row_idx + 1,
+                    cell_type
+                )?;
+                if let (true, Some(url)) = (self.hyperlinks, &cell.hyperlink) {
+                    write!(writer, " href=\"{}\"", escape_xml(url, true))?;
+                }
+                writeln!(writer, ">{}</cell>", escape_xml(cell.content(), false))?;
+            }
+            writeln!(writer, "  </row>")?;
         }
 
+        writeln!(writer, "</sheet>")?;
         writer.flush()?;
         Ok(())
     }
 }
 
+/// XML形式で`<cell>`要素として出力するセルかどうか（内部ヘルパー）
+///
+/// 表示文字列が空のセルと、結合セルの子（親セルのみ出力する）は出力しません。
+fn is_xml_cell(cell: &Cell) -> bool {
+    let is_merged_child = cell.is_merged && cell.merge_parent.is_some();
+    !cell.content().is_empty() && !is_merged_child
+}
+
 /// 列インデックスをExcel列名（A, B, C, ...）に変換
-fn col_to_letter(mut col: u32) -> String {
+pub(crate) fn col_to_letter(mut col: u32) -> String {
     let mut result = String::new();
     loop {
         result.push((b'A' + (col % 26) as u8) as char);
@@ -158,6 +496,43 @@ fn col_to_letter(mut col: u32) -> String {
     result.chars().rev().collect()
 }
 
+/// 数値をJSONの数値に変換
+///
+/// 整数値（安全に表現できる範囲内）は`1234.0`ではなく`1234`として出力します。
+fn json_number(n: f64) -> serde_json::Value {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if n.fract() == 0.0 && n.abs() <= MAX_SAFE_INTEGER {
+        serde_json::Value::from(n as i64)
+    } else {
+        serde_json::Value::from(n)
+    }
+}
+
+/// XMLの特殊文字をエスケープ
+///
+/// `&`、`<`、`>`、`"`、`'`を文字参照に置き換えます。復帰（CR）は改行の正規化で失われないよう
+/// `&#13;`に、属性値（`attribute`が`true`）ではタブと改行も文字参照に置き換えます。
+/// XML 1.0で使用できない制御文字は`U+FFFD`に置き換えます。
+pub(crate) fn escape_xml(s: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\n' if attribute => escaped.push_str("&#10;"),
+            '\t' if attribute => escaped.push_str("&#9;"),
+            '\n' | '\t' => escaped.push(c),
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => escaped.push('\u{fffd}'),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 /// CSV文字列をエスケープ
 ///
 /// ダブルクォート、改行、カンマを含む場合はダブルクォートで囲み、
@@ -169,4 +544,3 @@ fn escape_csv(s: &str) -> String {
         s.to_string()
     }
 }
-
@@ -0,0 +1,191 @@
+//! Front Matter Module
+//!
+//! Markdown文書の先頭に出力するYAMLフロントマターの生成を提供するモジュール。
+
+use crate::error::XlsxToMdError;
+use crate::types::SheetInfo;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// 追加の項目とシート単位の情報をYAMLフロントマターとして出力
+///
+/// 追加の項目はキーの順に先頭へ出力します。`sheets`が空の場合は`sheets`の項目を出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `extra` - 追加の項目（キー, 値）
+/// * `sheets` - 出力対象シートの情報（出力順）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_front_matter<W: Write>(
+    writer: &mut W,
+    extra: &BTreeMap<String, String>,
+    sheets: &[SheetInfo],
+) -> Result<(), XlsxToMdError> {
+    writeln!(writer, "---")?;
+    for (key, value) in extra {
+        writeln!(writer, "{}: {}", yaml_key(key), yaml_quote(value))?;
+    }
+    if !sheets.is_empty() {
+        writeln!(writer, "sheets:")?;
+    }
+    for sheet in sheets {
+        writeln!(writer, "  - name: {}", yaml_quote(&sheet.name))?;
+        match sheet.script {
+            Some(script) => writeln!(writer, "    script: {}", script.as_str())?,
+            None => writeln!(writer, "    script: null")?,
+        }
+        if let Some(hash) = sheet.content_hash_string() {
+            writeln!(writer, "    content_hash: {}", yaml_quote(&hash))?;
+        }
+        if !sheet.tables.is_empty() {
+            writeln!(writer, "    tables:")?;
+            for table in &sheet.tables {
+                writeln!(
+                    writer,
+                    "      - range: {}",
+                    yaml_quote(&table.range.to_string())
+                )?;
+                writeln!(
+                    writer,
+                    "        content_hash: {}",
+                    yaml_quote(&table.content_hash_string())
+                )?;
+            }
+        }
+        if sheet.protected {
+            writeln!(writer, "    protected: true")?;
+        }
+        if !sheet.visuals.sparklines.is_empty() {
+            writeln!(writer, "    sparklines:")?;
+            for sparkline in &sheet.visuals.sparklines {
+                writeln!(writer, "      - range: {}", yaml_quote(&sparkline.range))?;
+                writeln!(writer, "        source: {}", yaml_quote(&sparkline.source))?;
+                writeln!(writer, "        type: {}", yaml_quote(&sparkline.kind))?;
+            }
+        }
+        if !sheet.visuals.data_bars.is_empty() {
+            writeln!(writer, "    data_bars:")?;
+            for range in &sheet.visuals.data_bars {
+                writeln!(writer, "      - range: {}", yaml_quote(range))?;
+                writeln!(writer, "        source: {}", yaml_quote(range))?;
+            }
+        }
+    }
+    writeln!(writer, "---")?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// YAMLのキーとして出力（英数字・`_`・`-`以外を含む場合はダブルクォート文字列）
+fn yaml_key(key: &str) -> String {
+    let plain = key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && !key.starts_with('-');
+    if plain {
+        key.to_string()
+    } else {
+        yaml_quote(key)
+    }
+}
+
+/// 文字列をYAMLのダブルクォート文字列としてエスケープ
+fn yaml_quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::SheetVisuals;
+    use crate::script::Script;
+    use crate::types::{CellCoord, CellRange, SheetVisibility, TableInfo};
+
+    #[test]
+    fn test_write_front_matter() {
+        let sheets = vec![
+            SheetInfo {
+                name: "売上".to_string(),
+                script: Some(Script::Cjk),
+                content_hash: Some(0xff),
+                tables: vec![TableInfo {
+                    range: CellRange::new(CellCoord::new(0, 0), CellCoord::new(1, 2)),
+                    content_hash: 0xab,
+                }],
+                links: None,
+                column_units: None,
+                protected: false,
+                conditional_formats: Vec::new(),
+                data_validations: Vec::new(),
+                visuals: SheetVisuals::default(),
+                visibility: SheetVisibility::Visible,
+                removed_blank_rows: Vec::new(),
+            },
+            SheetInfo {
+                name: "Numbers".to_string(),
+                script: None,
+                content_hash: None,
+                tables: Vec::new(),
+                links: None,
+                column_units: None,
+                protected: true,
+                conditional_formats: Vec::new(),
+                data_validations: Vec::new(),
+                visuals: SheetVisuals::default(),
+                visibility: SheetVisibility::Visible,
+                removed_blank_rows: Vec::new(),
+            },
+        ];
+
+        let mut output = Vec::new();
+        write_front_matter(&mut output, &BTreeMap::new(), &sheets).unwrap();
+
+        let yaml = String::from_utf8(output).unwrap();
+        assert_eq!(
+            yaml,
+            "---\nsheets:\n  - name: \"売上\"\n    script: cjk\n    content_hash: \"xxh3:00000000000000ff\"\n    tables:\n      - range: \"A1:C2\"\n        content_hash: \"xxh3:00000000000000ab\"\n  - name: \"Numbers\"\n    script: null\n    protected: true\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_write_front_matter_extra() {
+        let extra = BTreeMap::from([
+            ("tenant_id".to_string(), "acme".to_string()),
+            ("source system".to_string(), "ERP \"v2\"".to_string()),
+        ]);
+
+        let mut output = Vec::new();
+        write_front_matter(&mut output, &extra, &[]).unwrap();
+
+        let yaml = String::from_utf8(output).unwrap();
+        assert_eq!(
+            yaml,
+            "---\n\"source system\": \"ERP \\\"v2\\\"\"\ntenant_id: \"acme\"\n---\n\n"
+        );
+    }
+
+    #[test]
+    fn test_yaml_quote() {
+        assert_eq!(yaml_quote("Sheet1"), "\"Sheet1\"");
+        assert_eq!(yaml_quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(yaml_quote("line\nbreak"), "\"line\\nbreak\"");
+    }
+}
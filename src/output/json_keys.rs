@@ -0,0 +1,189 @@
+//! JSON Keys Module
+//!
+//! JSON出力における行オブジェクトのキー（`JsonKeyMode`）の決定を提供するモジュール。
+//!
+//! 先頭行の見出しからキーを作成し、必要に応じて正規化（単位表記の除去、小文字化、
+//! 空白・記号の`_`への置き換え）を行います。
+
+use crate::api::JsonKeyMode;
+use std::collections::HashSet;
+
+/// 見出しから行オブジェクトのキーを作成
+///
+/// 見出しが空（正規化後に空）の列は列名をキーとし、重複するキーには`_2`、`_3`...を付加します。
+///
+/// # 引数
+///
+/// * `headers` - 先頭行の見出し（列順）
+/// * `column_names` - 列名（A, B, C, ...）
+/// * `mode` - キーの決定方式
+///
+/// # 戻り値
+///
+/// 列順のキー（`JsonKeyMode::ColumnLetter`の場合は列名）
+pub(crate) fn header_keys(
+    headers: &[&str],
+    column_names: &[String],
+    mode: JsonKeyMode,
+) -> Vec<String> {
+    let mut used: HashSet<String> = HashSet::new();
+    headers
+        .iter()
+        .zip(column_names)
+        .map(|(header, column)| {
+            let key = match mode {
+                JsonKeyMode::Header => header.trim().to_string(),
+                JsonKeyMode::NormalizedHeader => normalize_header(header),
+                _ => return column.clone(),
+            };
+            let key = if key.is_empty() {
+                match mode {
+                    JsonKeyMode::NormalizedHeader => column.to_lowercase(),
+                    _ => column.clone(),
+                }
+            } else {
+                key
+            };
+
+            let mut unique = key.clone();
+            let mut suffix = 2;
+            while !used.insert(unique.clone()) {
+                unique = format!("{}_{}", key, suffix);
+                suffix += 1;
+            }
+            unique
+        })
+        .collect()
+}
+
+/// 見出しを正規化したキーに変換
+///
+/// 末尾の単位表記（括弧で囲まれた部分）を除去して小文字化し、
+/// 英数字（日本語などを含む）以外の文字の連続を`_`にまとめます。
+/// 見出し全体が括弧で囲まれている場合は、括弧内の文字列をキーとして使用します。
+///
+/// # 使用例
+///
+/// * `" Unit  Price (USD) "` → `unit_price`
+/// * `"Weight [kg]"` → `weight`
+/// * `"売上（円）"` → `売上`
+pub(crate) fn normalize_header(header: &str) -> String {
+    let mut text = header.trim();
+    while let Some(stripped) = strip_unit_suffix(text) {
+        text = stripped;
+    }
+
+    let mut key = String::with_capacity(text.len());
+    let mut pending_separator = false;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            if pending_separator && !key.is_empty() {
+                key.push('_');
+            }
+            pending_separator = false;
+            key.push(c);
+        } else {
+            pending_separator = true;
+        }
+    }
+    key
+}
+
+/// 見出しを単位表記の前の部分と単位に分割
+///
+/// 末尾の括弧（`()`、`[]`、`（）`、`［］`）内を単位として扱います。
+/// 括弧内が空の場合や16文字を超える場合、見出し全体が括弧の場合は単位として扱いません。
+///
+/// # 戻り値
+///
+/// * `Some((見出し, 単位))` - 単位を検出した場合（見出しは前後の空白を除去）
+/// * `None` - 単位が見つからない場合
+///
+/// # 使用例
+///
+/// * `"Weight (kg)"` → `Some(("Weight", "kg"))`
+/// * `"金額（円）"` → `Some(("金額", "円"))`
+pub(crate) fn split_header_unit(header: &str) -> Option<(&str, &str)> {
+    const MAX_UNIT_CHARS: usize = 16;
+
+    let text = header.trim();
+    let (rest, unit) = split_bracket_suffix(text)?;
+    let unit = unit.trim();
+    if unit.is_empty() || unit.chars().count() > MAX_UNIT_CHARS {
+        return None;
+    }
+    Some((rest, unit))
+}
+
+/// 末尾の括弧で囲まれた単位表記を除去（内部ヘルパー）
+///
+/// 除去した場合は残りの文字列（末尾の空白を除去）を、
+/// 単位表記がない場合や見出し全体が括弧の場合は`None`を返します。
+fn strip_unit_suffix(text: &str) -> Option<&str> {
+    split_bracket_suffix(text).map(|(rest, _)| rest)
+}
+
+/// 末尾の括弧の前の部分と括弧内に分割（内部ヘルパー）
+///
+/// 括弧の前が空の場合は`None`を返します。
+fn split_bracket_suffix(text: &str) -> Option<(&str, &str)> {
+    let (open, close) = match text.chars().last()? {
+        ')' => ('(', ')'),
+        '）' => ('（', '）'),
+        ']' => ('[', ']'),
+        '］' => ('［', '］'),
+        _ => return None,
+    };
+    let start = text.rfind(open)?;
+    let rest = text[..start].trim_end();
+    let inner = &text[start + open.len_utf8()..text.len() - close.len_utf8()];
+    (!rest.is_empty()).then_some((rest, inner))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_header() {
+        assert_eq!(normalize_header(" Unit  Price (USD) "), "unit_price");
+        assert_eq!(normalize_header("Weight [kg]"), "weight");
+        assert_eq!(normalize_header("Weight(kg)"), "weight");
+        assert_eq!(normalize_header("売上（円）"), "売上");
+        assert_eq!(normalize_header("E-mail / Phone"), "e_mail_phone");
+        assert_eq!(normalize_header("Total (net) (USD)"), "total");
+        // 見出し全体が括弧の場合は括弧内を使用
+        assert_eq!(normalize_header("(kg)"), "kg");
+        assert_eq!(normalize_header("  "), "");
+    }
+
+    #[test]
+    fn test_split_header_unit() {
+        assert_eq!(split_header_unit("Weight (kg)"), Some(("Weight", "kg")));
+        assert_eq!(split_header_unit(" Price [USD] "), Some(("Price", "USD")));
+        assert_eq!(split_header_unit("金額（円）"), Some(("金額", "円")));
+        assert_eq!(split_header_unit("Name"), None);
+        assert_eq!(split_header_unit("(kg)"), None);
+        assert_eq!(split_header_unit("Note ()"), None);
+        assert_eq!(split_header_unit("Comment (this is a long remark)"), None);
+    }
+
+    #[test]
+    fn test_header_keys() {
+        let columns: Vec<String> = ["A", "B", "C", "D"].map(String::from).to_vec();
+        let headers = ["Name", " name ", "", "Weight (kg)"];
+
+        assert_eq!(
+            header_keys(&headers, &columns, JsonKeyMode::NormalizedHeader),
+            vec!["name", "name_2", "c", "weight"]
+        );
+        assert_eq!(
+            header_keys(&headers, &columns, JsonKeyMode::Header),
+            vec!["Name", "name", "C", "Weight (kg)"]
+        );
+        assert_eq!(
+            header_keys(&headers, &columns, JsonKeyMode::ColumnLetter),
+            vec!["A", "B", "C", "D"]
+        );
+    }
+}
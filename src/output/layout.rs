@@ -0,0 +1,695 @@
+//! Document Layout Module
+//!
+//! 変換したシートを1つの文書にまとめる際のレイアウト（シート間の区切り、シートの見出し、
+//! 文書全体の前後に付加する内容）を提供するモジュール。
+//!
+//! `Converter::convert()`、`Converter::convert_to_chunks()`など、シートを連結して
+//! 1つの文書を出力するメソッドで共通に使用します。
+
+use super::change_history::{change_history_json, write_change_history};
+use super::escape_html;
+use crate::api::{JsonDocumentMode, OutputFormat};
+use crate::builder::ConversionConfig;
+use crate::error::XlsxToMdError;
+use crate::report::Revision;
+use std::collections::HashSet;
+use std::io::Write;
+
+/// シートを1つの文書にまとめる際のレイアウト
+///
+/// すべてのシートがJSON形式の場合は、文書全体が正しいJSON（`JsonDocumentMode::Sheets`・
+/// `JsonDocumentMode::Records`）またはNDJSON（`JsonDocumentMode::Lines`）になるように出力します。
+/// 複数のシートがすべてHTML形式の場合は、シートごとの`<section>`からなる1つのHTML文書を、
+/// すべてXML形式の場合は、シートごとの`<sheet>`を`<workbook>`で囲んだ1つのXML文書を出力します。
+/// それ以外の場合は、シートの出力フォーマットに応じた区切りと見出しをシートの前に出力します。
+///
+/// 変更履歴がある場合は、すべてのシートの後に変更履歴の付録を出力します。JSON・XML形式では
+/// シートが1つでも文書全体を`{"sheets": [...], "changes": [...]}`・`<workbook>`で囲みます。
+#[derive(Debug, Clone)]
+pub(crate) struct DocumentLayout {
+    /// シート間の区切り（`None`の場合はフォーマットごとの既定の区切り）
+    separator: Option<String>,
+
+    /// JSON文書としての出力方式
+    json: JsonWrapping,
+
+    /// JSON文書を空白と改行を含めずに1行で出力するか
+    json_minified: bool,
+
+    /// レコード形式のJSON文書として出力する場合、シートごとのキー（シート名）
+    record_keys: Vec<String>,
+
+    /// HTML文書として出力する場合、シートごとの（`id`属性, シート名）
+    html_sections: Option<Vec<(String, String)>>,
+
+    /// HTML文書の先頭にシートへのナビゲーションを出力するか
+    html_navigation: bool,
+
+    /// シートを`<workbook>`要素で囲んだXML文書として出力するか
+    xml_workbook: bool,
+
+    /// 文書の末尾に付録として出力する変更履歴
+    change_history: Vec<Revision>,
+
+    /// 変更履歴の出力フォーマット（すべてのシートが同じフォーマットの場合はそのフォーマット）
+    change_history_format: OutputFormat,
+
+    /// HTML文書として出力する場合、変更履歴の`<section>`の`id`属性の値
+    change_history_id: String,
+}
+
+/// JSON文書としての出力方式（内部用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonWrapping {
+    /// JSON文書として扱わない（シートが1つのJSON、またはJSON以外のフォーマットを含む）
+    None,
+
+    /// 各シートのオブジェクトを`{"sheets": [...]}`で囲む
+    Sheets,
+
+    /// 1シート1行のJSONを区切りなしで連結する
+    Lines,
+
+    /// 各シートの行の配列をシート名をキーとする1つのオブジェクトで囲む
+    Records,
+}
+
+impl DocumentLayout {
+    /// 変換設定と文書に含めるシートからレイアウトを生成
+    ///
+    /// # 引数
+    ///
+    /// * `config` - 変換設定（シート間の区切り、JSON文書の構造、HTMLのナビゲーション）
+    /// * `sheets` - 文書に含めるシートの名前と出力フォーマット（出力順）
+    /// * `revisions` - 付録として出力する変更履歴（空の場合は出力しない）
+    pub fn from_config(
+        config: &ConversionConfig,
+        sheets: &[(&str, OutputFormat)],
+        revisions: &[Revision],
+    ) -> Self {
+        let all_of = |target: OutputFormat| {
+            !sheets.is_empty() && sheets.iter().all(|(_, format)| *format == target)
+        };
+        // 変更履歴を付加する場合は、シートが1つでも文書全体を囲む
+        let wrap = sheets.len() > 1 || !revisions.is_empty();
+        // レコード形式のJSON文書には変更履歴を含めない
+        let records =
+            config.json_document_mode == JsonDocumentMode::Records && all_of(OutputFormat::Json);
+        let json = match config.json_document_mode {
+            _ if !all_of(OutputFormat::Json) => JsonWrapping::None,
+            JsonDocumentMode::Lines => JsonWrapping::Lines,
+            JsonDocumentMode::Records if sheets.len() > 1 => JsonWrapping::Records,
+            JsonDocumentMode::Records => JsonWrapping::None,
+            _ if wrap => JsonWrapping::Sheets,
+            _ => JsonWrapping::None,
+        };
+        let record_keys = if json == JsonWrapping::Records {
+            sheets.iter().map(|(name, _)| name.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+        let mut used = HashSet::new();
+        let html_sections = (sheets.len() > 1 && all_of(OutputFormat::Html)).then(|| {
+            sheets
+                .iter()
+                .map(|(name, _)| (section_id(name, &mut used), name.to_string()))
+                .collect()
+        });
+        let change_history_format = match sheets.first() {
+            Some((_, format)) if all_of(*format) => *format,
+            _ => config.output_format,
+        };
+        Self {
+            separator: config.sheet_separator.clone(),
+            json,
+            json_minified: config.json_minified(),
+            record_keys,
+            html_sections,
+            html_navigation: config.include_html_navigation,
+            xml_workbook: wrap && all_of(OutputFormat::Xml),
+            change_history: if records {
+                Vec::new()
+            } else {
+                revisions.to_vec()
+            },
+            change_history_format,
+            change_history_id: section_id(CHANGE_HISTORY_TITLE, &mut used),
+        }
+    }
+
+    /// 文書の先頭に付加する内容を出力
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write_prologue<W: Write>(&self, writer: &mut W) -> Result<(), XlsxToMdError> {
+        if self.json == JsonWrapping::Sheets {
+            if self.json_minified {
+                write!(writer, "{{\"sheets\":[")?;
+            } else {
+                writeln!(writer, "{{\n  \"sheets\": [")?;
+            }
+        }
+        if self.json == JsonWrapping::Records {
+            if self.json_minified {
+                write!(writer, "{{")?;
+            } else {
+                writeln!(writer, "{{")?;
+            }
+        }
+        if let Some(sections) = &self.html_sections {
+            let title: Vec<String> = sections.iter().map(|(_, name)| escape_html(name)).collect();
+            writeln!(writer, "<!DOCTYPE html>\n<html>\n<head>")?;
+            writeln!(writer, "<meta charset=\"utf-8\">")?;
+            writeln!(writer, "<title>{}</title>", title.join(", "))?;
+            writeln!(writer, "</head>\n<body>")?;
+            if self.html_navigation {
+                writeln!(writer, "<nav>\n<ul>")?;
+                for (id, name) in sections {
+                    writeln!(
+                        writer,
+                        "<li><a href=\"#{}\">{}</a></li>",
+                        escape_html(id),
+                        escape_html(name)
+                    )?;
+                }
+                if !self.change_history.is_empty() {
+                    writeln!(
+                        writer,
+                        "<li><a href=\"#{}\">{}</a></li>",
+                        escape_html(&self.change_history_id),
+                        CHANGE_HISTORY_TITLE
+                    )?;
+                }
+                writeln!(writer, "</ul>\n</nav>")?;
+            }
+        }
+        if self.xml_workbook {
+            writeln!(writer, "<workbook>")?;
+        }
+        Ok(())
+    }
+
+    /// 1シート分の出力（シート間の区切り、見出し、本文）を出力
+    ///
+    /// 見出しはMarkdownでは`# シート名`、CSVでは`# Sheet: シート名`のコメント行、
+    /// HTML・XMLでは`<!-- Sheet: シート名 -->`のコメントです。JSONではシートのオブジェクトに
+    /// シート名が含まれるため、見出しは出力しません。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    /// * `sheet_idx` - シートの出力順（0始まり）
+    /// * `name` - 見出しに使用するシート名（非表示のシートは表示状態を付記したもの）
+    /// * `format` - シートの出力フォーマット
+    /// * `body` - シートの変換結果
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write_sheet<W: Write>(
+        &self,
+        writer: &mut W,
+        sheet_idx: usize,
+        name: &str,
+        format: OutputFormat,
+        body: &str,
+    ) -> Result<(), XlsxToMdError> {
+        match self.json {
+            JsonWrapping::Sheets if self.json_minified => {
+                // `sheets`配列の要素として、カンマで区切る
+                if sheet_idx > 0 {
+                    write!(writer, ",")?;
+                }
+                write!(writer, "{}", body.trim_end_matches('\n'))?;
+                return Ok(());
+            }
+            JsonWrapping::Sheets => {
+                // `sheets`配列の要素として、字下げしてカンマで区切る
+                if sheet_idx > 0 {
+                    writeln!(writer, ",")?;
+                }
+                for (line_idx, line) in body.trim_end_matches('\n').lines().enumerate() {
+                    if line_idx > 0 {
+                        writeln!(writer)?;
+                    }
+                    write!(writer, "    {}", line)?;
+                }
+                return Ok(());
+            }
+            JsonWrapping::Lines => {
+                write!(writer, "{}", body)?;
+                return Ok(());
+            }
+            JsonWrapping::Records => {
+                // シート名をキーとするメンバーとして、カンマで区切る
+                let key = self.record_keys.get(sheet_idx).map_or(name, String::as_str);
+                let key = serde_json::to_string(key).map_err(XlsxToMdError::json)?;
+                if self.json_minified {
+                    if sheet_idx > 0 {
+                        write!(writer, ",")?;
+                    }
+                    write!(writer, "{}:{}", key, body.trim_end_matches('\n'))?;
+                    return Ok(());
+                }
+                if sheet_idx > 0 {
+                    writeln!(writer, ",")?;
+                }
+                write!(writer, "  {}: ", key)?;
+                for (line_idx, line) in body.trim_end_matches('\n').lines().enumerate() {
+                    if line_idx > 0 {
+                        write!(writer, "\n  ")?;
+                    }
+                    write!(writer, "{}", line)?;
+                }
+                return Ok(());
+            }
+            JsonWrapping::None => {}
+        }
+        if let Some(sections) = &self.html_sections {
+            let id = sections.get(sheet_idx).map_or("", |(id, _)| id.as_str());
+            writeln!(writer, "<section id=\"{}\">", escape_html(id))?;
+            writeln!(writer, "<h1>{}</h1>", escape_html(name))?;
+            write!(writer, "{}", body)?;
+            writeln!(writer, "</section>")?;
+            return Ok(());
+        }
+        if self.xml_workbook {
+            // シートの`<sheet>`要素にシート名が含まれるため、見出しは出力しない
+            write!(writer, "{}", body)?;
+            return Ok(());
+        }
+
+        self.write_sheet_heading(writer, sheet_idx, name, format)?;
+        write!(writer, "{}", body)?;
+        Ok(())
+    }
+
+    /// シートの本文の前に出力する内容（シート間の区切り、見出し）のみを出力
+    ///
+    /// JSON・HTML・XMLの文書にまとめない場合（`has_plain_sheets()`が`true`の場合）に、
+    /// 本文を後から直接書き出すために使用します。`write_sheet()`は本文の前にこの内容を出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    /// * `sheet_idx` - シートの出力順（0始まり）
+    /// * `name` - 見出しに使用するシート名（非表示のシートは表示状態を付記したもの）
+    /// * `format` - シートの出力フォーマット
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write_sheet_heading<W: Write>(
+        &self,
+        writer: &mut W,
+        sheet_idx: usize,
+        name: &str,
+        format: OutputFormat,
+    ) -> Result<(), XlsxToMdError> {
+        // シート間の区切り
+        if sheet_idx > 0 {
+            match (&self.separator, format) {
+                (Some(separator), _) => write!(writer, "{}", separator)?,
+                (None, OutputFormat::Markdown) => writeln!(writer, "\n---\n")?,
+                (None, _) => writeln!(writer)?,
+            }
+        }
+
+        // シートの見出し
+        match format {
+            OutputFormat::Markdown => writeln!(writer, "# {}\n", name)?,
+            OutputFormat::Csv => writeln!(writer, "# Sheet: {}\n", name)?,
+            OutputFormat::Html | OutputFormat::Xml => {
+                writeln!(writer, "<!-- Sheet: {} -->\n", name)?
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// シートの本文をそのまま（区切りと見出しの後に）出力する文書か
+    ///
+    /// JSON文書・HTML文書・XML文書にシートをまとめる場合は`false`を返します。
+    pub fn has_plain_sheets(&self) -> bool {
+        self.json == JsonWrapping::None && self.html_sections.is_none() && !self.xml_workbook
+    }
+
+    /// 文書の末尾に付加する内容（変更履歴の付録、文書全体の終了）を出力
+    ///
+    /// 変更履歴の付録は、JSON文書では`changes`配列、NDJSONでは`changes`配列を持つ最後の行、
+    /// HTML文書では`<section>`、XML文書では`<workbook>`内の`<changes>`要素です。それ以外の場合は、
+    /// シートと同じ区切りの後に`Change history`の見出しと変更履歴の表を出力します。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write_epilogue<W: Write>(&self, writer: &mut W) -> Result<(), XlsxToMdError> {
+        let history = !self.change_history.is_empty();
+        match self.json {
+            JsonWrapping::Sheets if self.json_minified => {
+                write!(writer, "]")?;
+                if history {
+                    let changes = serde_json::to_string(&change_history_json(&self.change_history))
+                        .map_err(XlsxToMdError::json)?;
+                    write!(writer, ",\"changes\":{}", changes)?;
+                }
+                writeln!(writer, "}}")?;
+                return Ok(());
+            }
+            JsonWrapping::Sheets => {
+                write!(writer, "\n  ]")?;
+                if history {
+                    let changes =
+                        serde_json::to_string_pretty(&change_history_json(&self.change_history))
+                            .map_err(XlsxToMdError::json)?;
+                    write!(
+                        writer,
+                        ",\n  \"changes\": {}",
+                        changes.replace('\n', "\n  ")
+                    )?;
+                }
+                writeln!(writer, "\n}}")?;
+                return Ok(());
+            }
+            JsonWrapping::Lines => {
+                if history {
+                    self.write_change_history(writer, OutputFormat::Json)?;
+                }
+                return Ok(());
+            }
+            JsonWrapping::Records if self.json_minified => {
+                writeln!(writer, "}}")?;
+                return Ok(());
+            }
+            JsonWrapping::Records => {
+                writeln!(writer, "\n}}")?;
+                return Ok(());
+            }
+            JsonWrapping::None => {}
+        }
+        if self.html_sections.is_some() {
+            if history {
+                writeln!(
+                    writer,
+                    "<section id=\"{}\">",
+                    escape_html(&self.change_history_id)
+                )?;
+                writeln!(writer, "<h1>{}</h1>", CHANGE_HISTORY_TITLE)?;
+                self.write_change_history(writer, OutputFormat::Html)?;
+                writeln!(writer, "</section>")?;
+            }
+            writeln!(writer, "</body>\n</html>")?;
+            return Ok(());
+        }
+        if self.xml_workbook {
+            if history {
+                self.write_change_history(writer, OutputFormat::Xml)?;
+            }
+            writeln!(writer, "</workbook>")?;
+            return Ok(());
+        }
+        if history {
+            let format = self.change_history_format;
+            match (&self.separator, format) {
+                (Some(separator), _) => write!(writer, "{}", separator)?,
+                (None, OutputFormat::Markdown) => writeln!(writer, "\n---\n")?,
+                (None, _) => writeln!(writer)?,
+            }
+            match format {
+                OutputFormat::Markdown => writeln!(writer, "# {}\n", CHANGE_HISTORY_TITLE)?,
+                OutputFormat::Csv => writeln!(writer, "# {}\n", CHANGE_HISTORY_TITLE)?,
+                OutputFormat::Html | OutputFormat::Xml => {
+                    writeln!(writer, "<!-- {} -->\n", CHANGE_HISTORY_TITLE)?
+                }
+                _ => {}
+            }
+            self.write_change_history(writer, format)?;
+        }
+        Ok(())
+    }
+
+    /// 変更履歴の表を出力（内部ヘルパー）
+    fn write_change_history<W: Write>(
+        &self,
+        writer: &mut W,
+        format: OutputFormat,
+    ) -> Result<(), XlsxToMdError> {
+        write_change_history(writer, format, &self.change_history, self.json_minified)
+    }
+}
+
+/// 変更履歴の付録の見出し
+const CHANGE_HISTORY_TITLE: &str = "Change history";
+
+/// シート名から`<section>`の`id`属性の値を生成（内部ヘルパー）
+///
+/// 空白を`-`に置き換えて小文字化し、既に使用した値と重複する場合は`-2`、`-3`...を付加します。
+fn section_id(name: &str, used: &mut HashSet<String>) -> String {
+    let base: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .flat_map(char::to_lowercase)
+        .collect();
+    let base = if base.is_empty() {
+        "sheet".to_string()
+    } else {
+        base
+    };
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while !used.insert(id.clone()) {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::JsonStyle;
+
+    /// レイアウトを生成し、シートを連結
+    fn render(config: &ConversionConfig, sheets: &[(&str, OutputFormat, &str)]) -> String {
+        let formats: Vec<(&str, OutputFormat)> = sheets
+            .iter()
+            .map(|(name, format, _)| (*name, *format))
+            .collect();
+        let layout = DocumentLayout::from_config(config, &formats, &[]);
+
+        let mut output = Vec::new();
+        layout.write_prologue(&mut output).unwrap();
+        for (idx, (name, format, body)) in sheets.iter().enumerate() {
+            layout
+                .write_sheet(&mut output, idx, name, *format, body)
+                .unwrap();
+        }
+        layout.write_epilogue(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_default_layout() {
+        let config = ConversionConfig::default();
+        let sheets = [
+            ("A", OutputFormat::Markdown, "| x |\n"),
+            ("B", OutputFormat::Markdown, "| y |\n"),
+        ];
+        assert_eq!(
+            render(&config, &sheets),
+            "# A\n\n| x |\n\n---\n\n# B\n\n| y |\n"
+        );
+
+        let sheets = [
+            ("A", OutputFormat::Csv, "x\n"),
+            ("B", OutputFormat::Html, "<table></table>\n"),
+        ];
+        assert_eq!(
+            render(&config, &sheets),
+            "# Sheet: A\n\nx\n\n<!-- Sheet: B -->\n\n<table></table>\n"
+        );
+
+        // シートが1つのHTMLは断片のまま
+        let sheets = [("A", OutputFormat::Html, "<table></table>\n")];
+        assert_eq!(
+            render(&config, &sheets),
+            "<!-- Sheet: A -->\n\n<table></table>\n"
+        );
+    }
+
+    #[test]
+    fn test_custom_separator() {
+        let config = ConversionConfig {
+            sheet_separator: Some("\n<!-- page -->\n\n".to_string()),
+            ..Default::default()
+        };
+        let sheets = [
+            ("A", OutputFormat::Markdown, "x\n"),
+            ("B", OutputFormat::Markdown, "y\n"),
+        ];
+        assert_eq!(
+            render(&config, &sheets),
+            "# A\n\nx\n\n<!-- page -->\n\n# B\n\ny\n"
+        );
+    }
+
+    #[test]
+    fn test_json_document() {
+        // 区切りを指定しても、JSON文書の構造は変わらない
+        let config = ConversionConfig {
+            sheet_separator: Some("---".to_string()),
+            ..Default::default()
+        };
+        let sheets = [
+            ("A", OutputFormat::Json, "{\n  \"name\": \"A\"\n}\n"),
+            ("B", OutputFormat::Json, "{\n  \"name\": \"B\"\n}\n"),
+        ];
+        let output = render(&config, &sheets);
+        assert_eq!(
+            output,
+            "{\n  \"sheets\": [\n    {\n      \"name\": \"A\"\n    },\n    {\n      \"name\": \"B\"\n    }\n  ]\n}\n"
+        );
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["sheets"][1]["name"], "B");
+
+        // シートが1つの場合はオブジェクトのみ
+        assert_eq!(render(&config, &sheets[..1]), sheets[0].2);
+
+        // 1行のJSON文書
+        let config = ConversionConfig {
+            json_style: JsonStyle::Minified,
+            ..Default::default()
+        };
+        let minified = [
+            ("A", OutputFormat::Json, "{\"name\":\"A\"}\n"),
+            ("B", OutputFormat::Json, "{\"name\":\"B\"}\n"),
+        ];
+        assert_eq!(
+            render(&config, &minified),
+            "{\"sheets\":[{\"name\":\"A\"},{\"name\":\"B\"}]}\n"
+        );
+
+        // NDJSONでは見出しも区切りも付加しない
+        let config = ConversionConfig {
+            json_document_mode: JsonDocumentMode::Lines,
+            ..Default::default()
+        };
+        let sheets = [
+            ("A", OutputFormat::Json, "{\"name\":\"A\"}\n"),
+            ("B", OutputFormat::Json, "{\"name\":\"B\"}\n"),
+        ];
+        assert_eq!(
+            render(&config, &sheets),
+            "{\"name\":\"A\"}\n{\"name\":\"B\"}\n"
+        );
+
+        // レコード形式ではシート名をキーとするオブジェクトで囲む
+        let config = ConversionConfig {
+            json_document_mode: JsonDocumentMode::Records,
+            ..Default::default()
+        };
+        let sheets = [
+            ("A", OutputFormat::Json, "[\n  {\n    \"x\": 1\n  }\n]\n"),
+            ("B \"2\"", OutputFormat::Json, "[]\n"),
+        ];
+        let output = render(&config, &sheets);
+        assert_eq!(
+            output,
+            "{\n  \"A\": [\n    {\n      \"x\": 1\n    }\n  ],\n  \"B \\\"2\\\"\": []\n}\n"
+        );
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["A"][0]["x"], 1);
+        assert_eq!(render(&config, &sheets[..1]), sheets[0].2);
+    }
+
+    #[test]
+    fn test_html_document() {
+        let config = ConversionConfig {
+            include_html_navigation: true,
+            ..Default::default()
+        };
+        let sheets = [
+            ("Q1 Sales", OutputFormat::Html, "<table></table>\n"),
+            ("R&D", OutputFormat::Html, "<table></table>\n"),
+        ];
+        assert_eq!(
+            render(&config, &sheets),
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>Q1 Sales, R&amp;D</title>\n</head>\n<body>\n\
+             <nav>\n<ul>\n<li><a href=\"#q1-sales\">Q1 Sales</a></li>\n\
+             <li><a href=\"#r&amp;d\">R&amp;D</a></li>\n</ul>\n</nav>\n\
+             <section id=\"q1-sales\">\n<h1>Q1 Sales</h1>\n<table></table>\n</section>\n\
+             <section id=\"r&amp;d\">\n<h1>R&amp;D</h1>\n<table></table>\n</section>\n\
+             </body>\n</html>\n"
+        );
+    }
+
+    #[test]
+    fn test_change_history() {
+        let config = ConversionConfig::default();
+        let revisions = [Revision {
+            user: "Ada".to_string(),
+            date_time: "2024-05-01T09:30:00Z".to_string(),
+            changes: Vec::new(),
+        }];
+        let render = |sheets: &[(&str, OutputFormat, &str)]| {
+            let formats: Vec<(&str, OutputFormat)> = sheets
+                .iter()
+                .map(|(name, format, _)| (*name, *format))
+                .collect();
+            let layout = DocumentLayout::from_config(&config, &formats, &revisions);
+            let mut output = Vec::new();
+            layout.write_prologue(&mut output).unwrap();
+            for (idx, (name, format, body)) in sheets.iter().enumerate() {
+                layout
+                    .write_sheet(&mut output, idx, name, *format, body)
+                    .unwrap();
+            }
+            layout.write_epilogue(&mut output).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        // シートが1つのJSONも、変更履歴とともにオブジェクトで囲む
+        let output = render(&[("A", OutputFormat::Json, "{\n  \"name\": \"A\"\n}\n")]);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(value["sheets"][0]["name"], "A");
+        assert_eq!(value["changes"][0]["user"], "Ada");
+
+        let output = render(&[
+            ("A", OutputFormat::Html, "<table></table>\n"),
+            ("Change history", OutputFormat::Html, "<table></table>\n"),
+        ]);
+        assert!(output.contains("<section id=\"change-history-2\">\n<h1>Change history</h1>\n"));
+        assert!(output.ends_with("</table>\n</section>\n</body>\n</html>\n"));
+
+        let output = render(&[("A", OutputFormat::Csv, "x\n")]);
+        assert_eq!(
+            output,
+            "# Sheet: A\n\nx\n\n# Change history\n\n# 2024-05-01T09:30:00Z Ada\n"
+        );
+    }
+
+    #[test]
+    fn test_section_id() {
+        let mut used = HashSet::new();
+        assert_eq!(section_id("Q1 Sales", &mut used), "q1-sales");
+        assert_eq!(section_id("q1  sales", &mut used), "q1--sales");
+        assert_eq!(section_id("Q1-Sales", &mut used), "q1-sales-2");
+        assert_eq!(section_id(" ", &mut used), "sheet");
+        assert_eq!(section_id("売上", &mut used), "売上");
+    }
+}
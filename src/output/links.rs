@@ -0,0 +1,175 @@
+//! Links Module
+//!
+//! シート内のハイパーリンクの一覧（リンク一覧セクション）を提供するモジュール。
+//!
+//! 同じURLを参照するセルをまとめ、URLごとに参照元のセルを列挙します。
+//! `ConverterBuilder::include_link_inventory(true)`の場合に、表の後に出力されます。
+
+use super::escape_html;
+use crate::api::OutputFormat;
+use crate::error::XlsxToMdError;
+use crate::types::RawCellData;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// シート内のハイパーリンクの一覧
+///
+/// URLは最初に出現したセルの順（行優先）に並び、同じURLは1つにまとめられます。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct LinkInventory {
+    /// URLと、そのURLを参照するセル（A1記法）
+    entries: Vec<(String, Vec<String>)>,
+}
+
+impl LinkInventory {
+    /// セルデータからハイパーリンクの一覧を作成
+    ///
+    /// # 引数
+    ///
+    /// * `cells` - シートのセルデータ
+    pub fn collect(cells: &[RawCellData]) -> Self {
+        let mut linked: Vec<&RawCellData> =
+            cells.iter().filter(|c| c.hyperlink.is_some()).collect();
+        linked.sort_by_key(|cell| (cell.coord.row, cell.coord.col));
+
+        let mut index_of: HashMap<&str, usize> = HashMap::new();
+        let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+        for cell in linked {
+            let Some(url) = cell.hyperlink.as_ref().map(|link| link.url.as_str()) else {
+                continue;
+            };
+            let idx = *index_of.entry(url).or_insert_with(|| {
+                entries.push((url.to_string(), Vec::new()));
+                entries.len() - 1
+            });
+            entries[idx].1.push(cell.coord.to_a1_notation());
+        }
+        Self { entries }
+    }
+
+    /// ハイパーリンクが1つもないかどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// URLと参照元のセルを出現順に取得
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.entries
+            .iter()
+            .map(|(url, cells)| (url.as_str(), cells.as_slice()))
+    }
+
+    /// リンク一覧セクションを出力
+    ///
+    /// Markdownでは見出しと箇条書き、HTMLでは見出しとリスト、CSVでは`#`で始まるコメント行として
+    /// 出力します。JSON形式ではシートのオブジェクトに含めるため、ここでは何も出力しません。
+    /// ハイパーリンクがない場合も何も出力しません。
+    ///
+    /// # 引数
+    ///
+    /// * `writer` - 出力先のライター
+    /// * `format` - シートの出力フォーマット
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn write<W: Write>(
+        &self,
+        writer: &mut W,
+        format: OutputFormat,
+    ) -> Result<(), XlsxToMdError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        match format {
+            OutputFormat::Markdown => {
+                writeln!(writer, "\n## Links\n")?;
+                for (url, cells) in self.entries() {
+                    writeln!(writer, "- {}: {}", url, cells.join(", "))?;
+                }
+            }
+            OutputFormat::Html => {
+                writeln!(writer, "<h2>Links</h2>\n<ul>")?;
+                for (url, cells) in self.entries() {
+                    let url = escape_html(url);
+                    writeln!(
+                        writer,
+                        "<li><a href=\"{}\">{}</a>: {}</li>",
+                        url,
+                        url,
+                        cells.join(", ")
+                    )?;
+                }
+                writeln!(writer, "</ul>")?;
+            }
+            OutputFormat::Csv => {
+                writeln!(writer, "\n# Links")?;
+                for (url, cells) in self.entries() {
+                    writeln!(writer, "# {}: {}", url, cells.join(", "))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{CellCoord, CellValue, Hyperlink};
+
+    fn link(row: u32, col: u32, url: &str) -> RawCellData {
+        RawCellData {
+            coord: CellCoord::new(row, col),
+            value: CellValue::String("link".to_string()),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: Some(Hyperlink {
+                url: url.to_string(),
+                display: None,
+                tooltip: None,
+            }),
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        }
+    }
+
+    fn render(inventory: &LinkInventory, format: OutputFormat) -> String {
+        let mut output = Vec::new();
+        inventory.write(&mut output, format).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_link_inventory() {
+        let inventory = LinkInventory::collect(&[
+            link(3, 0, "https://example.com/b"),
+            link(1, 1, "https://example.com/a"),
+            link(4, 2, "https://example.com/a"),
+        ]);
+        // 最初に出現したセルの順に並び、同じURLは1つにまとめる
+        assert_eq!(
+            render(&inventory, OutputFormat::Markdown),
+            "\n## Links\n\n- https://example.com/a: B2, C5\n- https://example.com/b: A4\n"
+        );
+        assert_eq!(
+            render(&inventory, OutputFormat::Csv),
+            "\n# Links\n# https://example.com/a: B2, C5\n# https://example.com/b: A4\n"
+        );
+        assert_eq!(render(&inventory, OutputFormat::Json), "");
+
+        let inventory = LinkInventory::collect(&[link(0, 0, "https://example.com/?a=1&b=2")]);
+        assert_eq!(
+            render(&inventory, OutputFormat::Html),
+            "<h2>Links</h2>\n<ul>\n<li><a href=\"https://example.com/?a=1&amp;b=2\">https://example.com/?a=1&amp;b=2</a>: A1</li>\n</ul>\n"
+        );
+
+        assert!(LinkInventory::collect(&[]).is_empty());
+    }
+}
@@ -2,34 +2,82 @@
 //!
 //! Strategy Patternによる出力フォーマットの抽象化を提供するモジュール。
 
+mod change_history;
+mod charts;
+mod footnotes;
 mod formatters;
+mod front_matter;
+mod json_keys;
+mod layout;
+mod links;
+#[cfg(feature = "msgpack")]
+pub(crate) mod msgpack;
+mod pivots;
 
+use crate::builder::ConversionConfig;
 use crate::error::XlsxToMdError;
 use crate::grid::LogicalGrid;
-use crate::types::MergedRegion;
+use crate::report::{DuplicateSheet, FoldedColumn};
+use crate::types::{MergedRegion, SheetInfo};
 use std::io::Write;
 
+pub(crate) use charts::write_charts;
+pub(crate) use footnotes::Footnotes;
 pub use formatters::*;
+pub(crate) use front_matter::write_front_matter;
+pub(crate) use json_keys::{header_keys, split_header_unit};
+pub(crate) use layout::DocumentLayout;
+pub(crate) use links::LinkInventory;
+pub(crate) use pivots::write_pivot_tables;
 
 /// 出力フォーマッター（Strategy Pattern）
 ///
-/// 各出力フォーマット（Markdown, HTML, JSON, CSV）をenumとして表現します。
-#[derive(Debug, Clone, Copy)]
+/// 各出力フォーマット（Markdown, HTML, JSON, CSV, XML）をenumとして表現します。
+#[derive(Debug, Clone)]
 pub enum OutputFormatter {
-    Markdown,
+    Markdown(MarkdownFormatter),
     Html,
-    Json,
+    Json(JsonFormatter),
     Csv,
+    Xml(XmlFormatter),
 }
 
 impl OutputFormatter {
-    /// 出力フォーマットからフォーマッターを生成
-    pub fn from_format(format: crate::api::OutputFormat) -> Self {
-        match format {
-            crate::api::OutputFormat::Markdown => OutputFormatter::Markdown,
+    /// 変換設定からフォーマッターを生成
+    pub(crate) fn from_config(config: &ConversionConfig) -> Self {
+        match config.output_format {
+            crate::api::OutputFormat::Markdown => OutputFormatter::Markdown(MarkdownFormatter {
+                heading_sections: config.split_on_heading_styles,
+                column_width_limit: config.markdown_width_limit(),
+                chunk_rows: config.table_chunk_rows,
+            }),
             crate::api::OutputFormat::Html => OutputFormatter::Html,
-            crate::api::OutputFormat::Json => OutputFormatter::Json,
+            crate::api::OutputFormat::Json => OutputFormatter::Json(JsonFormatter {
+                number_output: config.number_output(),
+                cell_mode: config.json_cell_mode,
+                key_mode: config.json_key_mode,
+                include_metadata: config.include_metadata,
+                flag_formula_no_value: config.null_formula_policy
+                    != crate::api::NullFormulaPolicy::Empty,
+                include_styles: config.include_cell_styles,
+                single_line: config.json_minified(),
+                cell_references: config.cell_references,
+                hyperlinks: config.hyperlink_mode != crate::api::HyperlinkMode::TextOnly,
+                records: config.json_document_mode == crate::api::JsonDocumentMode::Records,
+                config_hash: None,
+            }),
             crate::api::OutputFormat::Csv => OutputFormatter::Csv,
+            crate::api::OutputFormat::Xml => OutputFormatter::Xml(XmlFormatter {
+                include_metadata: config.include_metadata,
+                hyperlinks: config.hyperlink_mode != crate::api::HyperlinkMode::TextOnly,
+            }),
+        }
+    }
+
+    /// 出力に含める設定のハッシュ値を指定（JSON形式の`fingerprint`オブジェクトで使用）
+    pub(crate) fn set_config_hash(&mut self, hash: u64) {
+        if let OutputFormatter::Json(formatter) = self {
+            formatter.config_hash = Some(hash);
         }
     }
 
@@ -40,6 +88,7 @@ impl OutputFormatter {
     /// * `grid` - 出力するグリッド
     /// * `writer` - 出力先のライター
     /// * `merged_regions` - 結合セル範囲のリスト（HTML形式で使用）
+    /// * `info` - シート単位の情報（JSON/XML形式のメタデータで使用）
     ///
     /// # 戻り値
     ///
@@ -50,21 +99,212 @@ impl OutputFormatter {
         grid: &LogicalGrid,
         writer: &mut W,
         merged_regions: &[MergedRegion],
+        info: &SheetInfo,
     ) -> Result<(), XlsxToMdError> {
         match self {
-            OutputFormatter::Markdown => {
-                MarkdownFormatter.render(grid, writer, merged_regions)
+            OutputFormatter::Markdown(formatter) => formatter.render(grid, writer, merged_regions),
+            OutputFormatter::Html => HtmlFormatter.render(grid, writer, merged_regions),
+            OutputFormatter::Json(formatter) => {
+                formatter.render(grid, writer, merged_regions, info)
             }
-            OutputFormatter::Html => {
-                HtmlFormatter.render(grid, writer, merged_regions)
-            }
-            OutputFormatter::Json => {
-                JsonFormatter.render(grid, writer, merged_regions)
+            OutputFormatter::Csv => CsvFormatter.render(grid, writer, merged_regions),
+            OutputFormatter::Xml(formatter) => formatter.render(grid, writer, merged_regions, info),
+        }
+    }
+}
+
+/// 表から除去した定数列を「見出し: 値」の形式で出力
+///
+/// Markdownでは箇条書き、HTMLでは段落、CSVでは`#`で始まるコメント行として出力し、
+/// 表との間に空行を挿入します。定数列がない場合は何も出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `folded_columns` - 除去した定数列
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_folded_columns<W: Write>(
+    writer: &mut W,
+    format: crate::api::OutputFormat,
+    folded_columns: &[FoldedColumn],
+) -> Result<(), XlsxToMdError> {
+    use crate::api::OutputFormat;
+
+    if folded_columns.is_empty() {
+        return Ok(());
+    }
+
+    for column in folded_columns {
+        match format {
+            OutputFormat::Markdown => writeln!(writer, "- {}: {}", column.header, column.value)?,
+            OutputFormat::Html => writeln!(writer, "<p>{}: {}</p>", column.header, column.value)?,
+            OutputFormat::Csv => writeln!(writer, "# {}: {}", column.header, column.value)?,
+            _ => return Ok(()),
+        }
+    }
+    writeln!(writer)?;
+    Ok(())
+}
+
+/// 重複シートの内容の代わりに、重複元のシートを示す注記を出力
+///
+/// Markdownでは引用、HTMLでは段落、CSVでは`#`で始まるコメント行として出力します。
+/// JSON形式では、シート名・重複元のシート名・類似度からなるオブジェクトを、
+/// XML形式では、それらを属性とする空の`<sheet>`要素を出力します。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `duplicate` - 検出した重複シート
+/// * `single_line` - JSONを1行で出力するか（`JsonStyle::Minified`またはNDJSONの場合）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_duplicate_note<W: Write>(
+    writer: &mut W,
+    format: crate::api::OutputFormat,
+    duplicate: &DuplicateSheet,
+    single_line: bool,
+) -> Result<(), XlsxToMdError> {
+    use crate::api::OutputFormat;
+
+    let note = format!(
+        "Near-duplicate of sheet \"{}\" (similarity: {}%); content omitted.",
+        duplicate.duplicate_of,
+        (duplicate.similarity * 100.0).floor()
+    );
+    match format {
+        OutputFormat::Markdown => writeln!(writer, "> {}", note)?,
+        OutputFormat::Html => writeln!(writer, "<p>{}</p>", escape_html(&note))?,
+        OutputFormat::Csv => writeln!(writer, "# {}", note)?,
+        OutputFormat::Xml => writeln!(
+            writer,
+            "<sheet name=\"{}\" duplicate_of=\"{}\" similarity=\"{}\"/>",
+            escape_xml(&duplicate.sheet, true),
+            escape_xml(&duplicate.duplicate_of, true),
+            duplicate.similarity
+        )?,
+        _ => {
+            let value = serde_json::json!({
+                "name": duplicate.sheet,
+                "duplicate_of": duplicate.duplicate_of,
+                "similarity": duplicate.similarity,
+            });
+            if single_line {
+                serde_json::to_writer(&mut *writer, &value)
+            } else {
+                serde_json::to_writer_pretty(&mut *writer, &value)
             }
-            OutputFormatter::Csv => {
-                CsvFormatter.render(grid, writer, merged_regions)
+            .map_err(XlsxToMdError::json)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// 出力を打ち切ったことを示す注記を出力
+///
+/// Markdownでは引用、HTMLでは段落、CSVでは`#`で始まるコメント行、XMLではコメントとして出力します。
+/// JSON形式では何も出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `note` - 注記の本文（例: `Output truncated: exceeded the maximum output size of 1024 bytes.`）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_truncation_marker<W: Write>(
+    writer: &mut W,
+    format: crate::api::OutputFormat,
+    note: &str,
+) -> Result<(), XlsxToMdError> {
+    use crate::api::OutputFormat;
+
+    match format {
+        OutputFormat::Markdown => writeln!(writer, "\n> {}", note)?,
+        OutputFormat::Html => writeln!(writer, "<p>{}</p>", note)?,
+        OutputFormat::Csv => writeln!(writer, "# {}", note)?,
+        OutputFormat::Xml => writeln!(writer, "<!-- {} -->", note)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 除去した空行の行番号を示す注記を出力
+///
+/// 連続する行番号は範囲にまとめます（例: `removed blank rows: 3, 6-7`）。
+/// Markdown・HTML・XMLではコメント、CSVでは`#`で始まるコメント行として出力します。
+/// JSON形式ではシートのオブジェクトに含めるため、何も出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `rows` - 除去した空行のシート上の行番号（1始まり、昇順）
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_removed_rows_marker<W: Write>(
+    writer: &mut W,
+    format: crate::api::OutputFormat,
+    rows: &[u32],
+) -> Result<(), XlsxToMdError> {
+    use crate::api::OutputFormat;
+
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    // 連続する行番号を範囲にまとめる
+    let mut ranges: Vec<String> = Vec::new();
+    let mut start = rows[0];
+    for (idx, &row) in rows.iter().enumerate() {
+        let end = rows.get(idx + 1).is_none_or(|&next| next != row + 1);
+        if end {
+            ranges.push(if start == row {
+                row.to_string()
+            } else {
+                format!("{}-{}", start, row)
+            });
+            if let Some(&next) = rows.get(idx + 1) {
+                start = next;
             }
         }
     }
+    let note = format!("removed blank rows: {}", ranges.join(", "));
+
+    match format {
+        OutputFormat::Markdown => writeln!(writer, "\n<!-- {} -->", note)?,
+        OutputFormat::Html | OutputFormat::Xml => writeln!(writer, "<!-- {} -->", note)?,
+        OutputFormat::Csv => writeln!(writer, "# {}", note)?,
+        _ => {}
+    }
+    Ok(())
 }
 
+/// 設定のハッシュ値を出力用の文字列（例: `xxh3:0123456789abcdef`）に変換
+pub(crate) fn config_hash_string(hash: u64) -> String {
+    format!("xxh3:{:016x}", hash)
+}
+
+/// HTMLの特殊文字をエスケープ
+pub(crate) fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,186 @@
+//! MessagePack Module
+//!
+//! JSON値をMessagePack形式にエンコードするモジュール（`msgpack`フィーチャー）。
+//!
+//! `Converter::convert_to_msgpack()`で使用します。整数は値を表現できる最小の型で、
+//! 整数でない数値は64bit浮動小数点数としてエンコードします。
+
+use crate::error::XlsxToMdError;
+use serde_json::Value;
+use std::io::Write;
+
+/// JSON値をMessagePack形式で出力
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `value` - 出力する値
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - 文字列・配列・オブジェクトの長さが上限（2^32 - 1）を超える場合、
+///   または書き込みに失敗した場合
+pub(crate) fn write_value<W: Write>(writer: &mut W, value: &Value) -> Result<(), XlsxToMdError> {
+    match value {
+        Value::Null => writer.write_all(&[0xc0])?,
+        Value::Bool(b) => writer.write_all(&[if *b { 0xc3 } else { 0xc2 }])?,
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                write_uint(writer, n)?;
+            } else if let Some(n) = n.as_i64() {
+                write_int(writer, n)?;
+            } else {
+                writer.write_all(&[0xcb])?;
+                writer.write_all(&n.as_f64().unwrap_or(f64::NAN).to_be_bytes())?;
+            }
+        }
+        Value::String(s) => write_str(writer, s)?,
+        Value::Array(items) => {
+            write_len(
+                writer,
+                items.len(),
+                (0x90, 16),
+                [None, Some(0xdc), Some(0xdd)],
+            )?;
+            for item in items {
+                write_value(writer, item)?;
+            }
+        }
+        Value::Object(map) => {
+            write_len(
+                writer,
+                map.len(),
+                (0x80, 16),
+                [None, Some(0xde), Some(0xdf)],
+            )?;
+            for (key, item) in map {
+                write_str(writer, key)?;
+                write_value(writer, item)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 文字列を出力（内部ヘルパー）
+fn write_str<W: Write>(writer: &mut W, s: &str) -> Result<(), XlsxToMdError> {
+    write_len(
+        writer,
+        s.len(),
+        (0xa0, 32),
+        [Some(0xd9), Some(0xda), Some(0xdb)],
+    )?;
+    writer.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+/// 0以上の整数を出力（内部ヘルパー）
+fn write_uint<W: Write>(writer: &mut W, n: u64) -> Result<(), XlsxToMdError> {
+    if n < 0x80 {
+        writer.write_all(&[n as u8])?;
+    } else if n <= u8::MAX as u64 {
+        writer.write_all(&[0xcc, n as u8])?;
+    } else if n <= u16::MAX as u64 {
+        writer.write_all(&[0xcd])?;
+        writer.write_all(&(n as u16).to_be_bytes())?;
+    } else if n <= u32::MAX as u64 {
+        writer.write_all(&[0xce])?;
+        writer.write_all(&(n as u32).to_be_bytes())?;
+    } else {
+        writer.write_all(&[0xcf])?;
+        writer.write_all(&n.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// 負の整数を出力（内部ヘルパー）
+fn write_int<W: Write>(writer: &mut W, n: i64) -> Result<(), XlsxToMdError> {
+    if n >= -32 {
+        writer.write_all(&[n as i8 as u8])?;
+    } else if n >= i8::MIN as i64 {
+        writer.write_all(&[0xd0, n as i8 as u8])?;
+    } else if n >= i16::MIN as i64 {
+        writer.write_all(&[0xd1])?;
+        writer.write_all(&(n as i16).to_be_bytes())?;
+    } else if n >= i32::MIN as i64 {
+        writer.write_all(&[0xd2])?;
+        writer.write_all(&(n as i32).to_be_bytes())?;
+    } else {
+        writer.write_all(&[0xd3])?;
+        writer.write_all(&n.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// 文字列・配列・マップの型と長さを出力（内部ヘルパー）
+///
+/// # 引数
+///
+/// * `fixed` - 長さを型に含める形式の（型の先頭バイト, 長さの上限）
+/// * `markers` - 長さが8bit・16bit・32bitの型（配列・マップには8bitの型がない）
+fn write_len<W: Write>(
+    writer: &mut W,
+    len: usize,
+    fixed: (u8, usize),
+    markers: [Option<u8>; 3],
+) -> Result<(), XlsxToMdError> {
+    match markers {
+        _ if len < fixed.1 => writer.write_all(&[fixed.0 | len as u8])?,
+        [Some(marker), _, _] if len <= u8::MAX as usize => {
+            writer.write_all(&[marker, len as u8])?
+        }
+        [_, Some(marker), _] if len <= u16::MAX as usize => {
+            writer.write_all(&[marker])?;
+            writer.write_all(&(len as u16).to_be_bytes())?;
+        }
+        [_, _, marker] => {
+            let len = u32::try_from(len).map_err(|_| {
+                XlsxToMdError::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("MessagePack length exceeds the maximum: {}", len),
+                ))
+            })?;
+            writer.write_all(&[marker.unwrap_or(0xdb)])?;
+            writer.write_all(&len.to_be_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn encode(value: Value) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_value(&mut buffer, &value).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_write_scalars() {
+        assert_eq!(encode(json!(null)), [0xc0]);
+        assert_eq!(encode(json!(true)), [0xc3]);
+        assert_eq!(encode(json!(5)), [0x05]);
+        assert_eq!(encode(json!(200)), [0xcc, 200]);
+        assert_eq!(encode(json!(1000)), [0xcd, 0x03, 0xe8]);
+        assert_eq!(encode(json!(-1)), [0xff]);
+        assert_eq!(encode(json!(-100)), [0xd0, (-100i8) as u8]);
+        assert_eq!(encode(json!(-1000)), [0xd1, 0xfc, 0x18]);
+        let mut float = vec![0xcb];
+        float.extend_from_slice(&1.5f64.to_be_bytes());
+        assert_eq!(encode(json!(1.5)), float);
+    }
+
+    #[test]
+    fn test_write_containers() {
+        assert_eq!(encode(json!("ab")), [0xa2, b'a', b'b']);
+        let long = "x".repeat(40);
+        assert_eq!(encode(json!(long))[..2], [0xd9, 40]);
+        assert_eq!(encode(json!([1, "a"])), [0x92, 0x01, 0xa1, b'a']);
+        assert_eq!(encode(json!(vec![0; 20]))[..3], [0xdc, 0x00, 20]);
+        assert_eq!(encode(json!({"k": false})), [0x81, 0xa1, b'k', 0xc2]);
+    }
+}
@@ -0,0 +1,66 @@
+//! Pivots Module
+//!
+//! ピボットテーブルの元データ（`PivotMode::SourceData`）の出力を提供するモジュール。
+
+use super::{escape_html, CsvFormatter};
+use crate::api::{OutputFormat, PivotMode};
+use crate::error::XlsxToMdError;
+use crate::grid::LogicalGrid;
+use crate::parser::PivotTable;
+use std::io::Write;
+
+/// ピボットテーブルの元データを出力
+///
+/// 各ピボットテーブルについて、`Pivot: <名前>`の見出しと、フィールド名を先頭行とする元データの表を
+/// 出力します。見出しはMarkdownでは`##`、HTMLでは`<h2>`、CSVでは`#`で始まるコメント行です。
+/// JSON・XML形式と`PivotMode::Cells`の場合は何も出力しません。
+///
+/// # 引数
+///
+/// * `writer` - 出力先のライター
+/// * `format` - シートの出力フォーマット
+/// * `mode` - ピボットテーブルの扱い
+/// * `pivots` - 出力するピボットテーブル（配置範囲の順）
+/// * `format_text` - 見出しと表のセルの文字列をエスケープする関数
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 出力に成功した場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn write_pivot_tables<W: Write>(
+    writer: &mut W,
+    format: OutputFormat,
+    mode: PivotMode,
+    pivots: &[PivotTable],
+    format_text: impl Fn(&str) -> String,
+) -> Result<(), XlsxToMdError> {
+    if mode == PivotMode::Cells {
+        return Ok(());
+    }
+
+    for pivot in pivots {
+        let heading = format!("Pivot: {}", pivot.name);
+        let grid = LogicalGrid::from_rows(
+            std::iter::once(&pivot.fields)
+                .chain(&pivot.records)
+                .map(|row| row.iter().map(|value| format_text(value)).collect())
+                .collect(),
+        );
+        match format {
+            OutputFormat::Markdown => {
+                writeln!(writer, "\n## {}\n", format_text(&heading))?;
+                grid.render_markdown(writer, None)?;
+            }
+            OutputFormat::Html => {
+                writeln!(writer, "<h2>{}</h2>", escape_html(&heading))?;
+                grid.render_html(writer, &[])?;
+            }
+            OutputFormat::Csv => {
+                writeln!(writer, "\n# {}", heading)?;
+                CsvFormatter.render(&grid, writer, &[])?;
+            }
+            _ => return Ok(()),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,357 @@
+//! Charts Module
+//!
+//! ワークシートに配置されたグラフを解析するモジュール。
+//!
+//! グラフはワークシートのリレーションシップから参照される次のパーツに記録されます。
+//!
+//! 1. xl/drawings/drawing*.xml: グラフの配置（`<xdr:graphicFrame>`の`<c:chart r:id="rId1">`）
+//! 2. xl/drawings/_rels/drawing*.xml.rels: `r:id`のIDからグラフXMLへの対応
+//! 3. xl/charts/chart*.xml: グラフのタイトルと系列（`<c:ser>`）
+//!
+//! 系列の名前・項目・値は、参照先のセル範囲の値のキャッシュ（`<c:strCache>`・`<c:numCache>`）から取得します。
+
+use super::conditional_formats::str_attr;
+use super::drawings::{drawing_parts, parse_drawing};
+use super::metadata::XlsxMetadataParser;
+use super::rich_data::{parse_rel_targets, read_part, resolve_target};
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use quick_xml::events::Event;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// グラフの系列（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct ChartSeries {
+    /// 系列名（`<c:tx>`、設定されていない場合は`None`）
+    pub name: Option<String>,
+
+    /// 値（`<c:val>`または`<c:yVal>`のキャッシュ、項目の順）
+    pub values: Vec<String>,
+}
+
+/// シートに配置されたグラフ（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Chart {
+    /// 配置位置の左上のセルの行インデックス（シートの絶対座標、0始まり）
+    pub row: u32,
+
+    /// 配置位置の左上のセルの列インデックス（シートの絶対座標、0始まり）
+    pub col: u32,
+
+    /// グラフのタイトル（設定されていない場合は`None`）
+    pub title: Option<String>,
+
+    /// 項目（先頭の系列の`<c:cat>`または`<c:xVal>`のキャッシュ）
+    pub categories: Vec<String>,
+
+    /// 系列（記録順）
+    pub series: Vec<ChartSeries>,
+}
+
+impl Chart {
+    /// 系列の表示名を取得
+    ///
+    /// 系列名が設定されていない系列は、Excelと同様に`Series1`のような名前とします。
+    pub fn series_names(&self) -> Vec<String> {
+        self.series
+            .iter()
+            .enumerate()
+            .map(|(idx, series)| {
+                series
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| format!("Series{}", idx + 1))
+            })
+            .collect()
+    }
+
+    /// グラフの概要を1行の文字列として取得
+    ///
+    /// 例: `Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec`
+    pub fn summary(&self) -> String {
+        let mut summary = match &self.title {
+            Some(title) => format!("Chart: '{}'", title),
+            None => "Chart".to_string(),
+        };
+        if !self.series.is_empty() {
+            summary.push_str(" — series: ");
+            summary.push_str(&self.series_names().join(", "));
+        }
+        match self.categories.as_slice() {
+            [] => {}
+            [only] => summary.push_str(&format!(" over {}", only)),
+            [first, .., last] => summary.push_str(&format!(" over {}..{}", first, last)),
+        }
+        summary
+    }
+
+    /// 表として出力する行数（項目数と系列の値の数の最大値）
+    pub fn data_rows(&self) -> usize {
+        self.series
+            .iter()
+            .map(|series| series.values.len())
+            .chain(std::iter::once(self.categories.len()))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 表の行の項目名を取得（項目がない場合は1始まりの番号）
+    pub fn category(&self, row: usize) -> String {
+        self.categories
+            .get(row)
+            .cloned()
+            .unwrap_or_else(|| (row + 1).to_string())
+    }
+}
+
+/// シートごとのグラフを解析
+///
+/// グラフのないシートは含みません。グラフは配置位置の行・列の順に並べます。
+/// グラフXMLが存在しないグラフは含みません。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `worksheet_parts` - (ワークシートパーツ, シート名)の一覧
+///
+/// # 戻り値
+///
+/// * `Ok(HashMap<..>)` - シート名 -> グラフ（配置位置の順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_charts<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    worksheet_parts: &[(String, String)],
+) -> Result<HashMap<String, Vec<Chart>>, XlsxToMdError> {
+    let mut charts = HashMap::new();
+
+    for (file_name, sheet_name) in worksheet_parts {
+        let mut sheet_charts = Vec::new();
+        for part in drawing_parts(archive, file_name)? {
+            let Some(xml) = read_part(archive, &part)? else {
+                continue;
+            };
+            let anchors = parse_drawing(&part, &xml)?.charts;
+            if anchors.is_empty() {
+                continue;
+            }
+
+            let drawing_rels_part = XlsxMetadataParser::rels_path_for_part(&part);
+            let targets = match read_part(archive, &drawing_rels_part)? {
+                Some(xml) => parse_rel_targets(&drawing_rels_part, &xml)?,
+                None => HashMap::new(),
+            };
+            let drawing_base = part.rsplit_once('/').map_or("", |(dir, _)| dir);
+            for anchor in anchors {
+                let Some(target) = targets.get(&anchor.id) else {
+                    continue;
+                };
+                let chart_part = resolve_target(drawing_base, target);
+                let Some(xml) = read_part(archive, &chart_part)? else {
+                    continue;
+                };
+                let mut chart = parse_chart(&chart_part, &xml)?;
+                (chart.row, chart.col) = (anchor.row, anchor.col);
+                sheet_charts.push(chart);
+            }
+        }
+
+        if !sheet_charts.is_empty() {
+            sheet_charts.sort_by_key(|chart| (chart.row, chart.col));
+            charts.insert(sheet_name.clone(), sheet_charts);
+        }
+    }
+    Ok(charts)
+}
+
+/// 解析中の系列の要素（内部用）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SeriesPart {
+    /// 系列名（`<c:tx>`）
+    Name,
+
+    /// 項目（`<c:cat>`・`<c:xVal>`）
+    Categories,
+
+    /// 値（`<c:val>`・`<c:yVal>`）
+    Values,
+}
+
+/// グラフXMLからタイトルと系列を解析（内部ヘルパー）
+///
+/// タイトルは`<c:chart>`直下の`<c:title>`のみを対象とし、軸のタイトルは含めません。
+/// 複数階層の項目（`<c:multiLvlStrCache>`）は、先頭の階層（最も内側の項目）を使用します。
+fn parse_chart(part: &str, xml: &[u8]) -> Result<Chart, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    // リッチテキストのランの境界の空白を保持する
+    reader.trim_text(false);
+
+    let mut chart = Chart::default();
+    // 解析中の要素のローカル名（ルート要素から順に）
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    // タイトルの段落（`<a:p>`ごとのテキスト）
+    let mut title: Vec<String> = Vec::new();
+    // 解析中の系列と、系列の項目（インデックス -> 値）
+    let mut series: Option<ChartSeries> = None;
+    let mut categories: BTreeMap<usize, String> = BTreeMap::new();
+    let mut values: BTreeMap<usize, String> = BTreeMap::new();
+    // 解析中の系列の要素と、解析中の`<c:pt>`のインデックス
+    let mut series_part: Option<SeriesPart> = None;
+    let mut point: Option<usize> = None;
+    // 複数階層の項目で、先頭の階層を解析済みか
+    let mut level_done = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = e.local_name().as_ref().to_vec();
+                let in_series = series.is_some();
+                match name.as_slice() {
+                    b"ser" => {
+                        series = Some(ChartSeries::default());
+                        categories.clear();
+                        values.clear();
+                        level_done = false;
+                    }
+                    b"tx" if in_series && path.last().is_some_and(|p| p == b"ser") => {
+                        series_part = Some(SeriesPart::Name);
+                    }
+                    b"cat" | b"xVal" if in_series => series_part = Some(SeriesPart::Categories),
+                    b"val" | b"yVal" if in_series => series_part = Some(SeriesPart::Values),
+                    b"pt" if series_part.is_some() => {
+                        point = str_attr(part, &e, b"idx")?.and_then(|idx| idx.parse().ok());
+                    }
+                    b"p" if is_chart_title(&path) => title.push(String::new()),
+                    _ => {}
+                }
+                path.push(name);
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                let leaf = path.last().map(Vec::as_slice);
+                if is_chart_title(&path) && matches!(leaf, Some(b"t") | Some(b"v")) {
+                    match title.last_mut() {
+                        Some(paragraph) => paragraph.push_str(&text),
+                        None => title.push(text.into_owned()),
+                    }
+                } else if leaf == Some(b"v") {
+                    let (Some(series), Some(series_part)) = (series.as_mut(), series_part) else {
+                        continue;
+                    };
+                    match (series_part, point) {
+                        (SeriesPart::Name, _) => {
+                            series.name.get_or_insert_with(String::new).push_str(&text);
+                        }
+                        (SeriesPart::Categories, Some(idx)) if !level_done => {
+                            categories.insert(idx, text.into_owned());
+                        }
+                        (SeriesPart::Values, Some(idx)) => {
+                            values.insert(idx, text.into_owned());
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Ok(Event::End(e)) => {
+                path.pop();
+                match e.local_name().as_ref() {
+                    b"ser" => {
+                        if let Some(mut series) = series.take() {
+                            series.name = series
+                                .name
+                                .map(|name| name.trim().to_string())
+                                .filter(|name| !name.is_empty());
+                            series.values = densify(&values);
+                            if chart.categories.is_empty() {
+                                chart.categories = densify(&categories);
+                            }
+                            chart.series.push(series);
+                        }
+                        series_part = None;
+                    }
+                    b"tx" | b"cat" | b"xVal" | b"val" | b"yVal"
+                        if path.last().is_some_and(|p| p == b"ser") =>
+                    {
+                        series_part = None;
+                    }
+                    b"pt" => point = None,
+                    b"lvl" => level_done = true,
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+
+    let title = title
+        .iter()
+        .map(|paragraph| paragraph.trim())
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    chart.title = (!title.is_empty()).then_some(title);
+    Ok(chart)
+}
+
+/// 解析中の要素がグラフのタイトル（`<c:chart>`直下の`<c:title>`）の中かどうか（内部ヘルパー）
+fn is_chart_title(path: &[Vec<u8>]) -> bool {
+    path.windows(2)
+        .position(|pair| pair[0] == b"chart" && pair[1] == b"title")
+        .is_some_and(|idx| idx == 1)
+}
+
+/// インデックス付きの値を、欠けたインデックスを空文字列とした一覧に変換（内部ヘルパー）
+fn densify(points: &BTreeMap<usize, String>) -> Vec<String> {
+    let len = points.keys().next_back().map_or(0, |&idx| idx + 1);
+    let mut values = vec![String::new(); len];
+    for (&idx, value) in points {
+        values[idx] = value.clone();
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chart() {
+        let xml = br#"<c:chartSpace xmlns:c="c" xmlns:a="a"><c:chart><c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>Revenue by </a:t></a:r><a:r><a:t>Quarter</a:t></a:r></a:p></c:rich></c:tx></c:title><c:plotArea><c:barChart><c:ser><c:idx val="0"/><c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>2023</c:v></c:pt></c:strCache></c:strRef></c:tx><c:cat><c:strRef><c:f>Sheet1!$A$2:$A$4</c:f><c:strCache><c:ptCount val="3"/><c:pt idx="0"><c:v>Jan</c:v></c:pt><c:pt idx="1"><c:v>Feb</c:v></c:pt><c:pt idx="2"><c:v>Dec</c:v></c:pt></c:strCache></c:strRef></c:cat><c:val><c:numRef><c:f>Sheet1!$B$2:$B$4</c:f><c:numCache><c:formatCode>General</c:formatCode><c:ptCount val="3"/><c:pt idx="0"><c:v>120</c:v></c:pt><c:pt idx="2"><c:v>140</c:v></c:pt></c:numCache></c:numRef></c:val></c:ser><c:ser><c:idx val="1"/><c:val><c:numRef><c:numCache><c:pt idx="0"><c:v>150</c:v></c:pt></c:numCache></c:numRef></c:val></c:ser></c:barChart><c:valAx><c:title><c:tx><c:rich><a:p><a:r><a:t>USD</a:t></a:r></a:p></c:rich></c:tx></c:title></c:valAx></c:plotArea></c:chart></c:chartSpace>"#;
+        let chart = parse_chart("xl/charts/chart1.xml", xml).unwrap();
+
+        // 軸のタイトルは含めず、欠けた値は空文字列とする
+        assert_eq!(chart.title.as_deref(), Some("Revenue by Quarter"));
+        assert_eq!(chart.categories, vec!["Jan", "Feb", "Dec"]);
+        assert_eq!(
+            chart.series,
+            vec![
+                ChartSeries {
+                    name: Some("2023".to_string()),
+                    values: vec!["120".to_string(), String::new(), "140".to_string()],
+                },
+                ChartSeries {
+                    name: None,
+                    values: vec!["150".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            chart.summary(),
+            "Chart: 'Revenue by Quarter' — series: 2023, Series2 over Jan..Dec"
+        );
+        assert_eq!(chart.data_rows(), 3);
+    }
+
+    #[test]
+    fn test_parse_chart_frame() {
+        let xml = br#"<xdr:wsDr xmlns:xdr="x" xmlns:a="a" xmlns:c="c" xmlns:r="r"><xdr:twoCellAnchor><xdr:from><xdr:col>4</xdr:col><xdr:row>2</xdr:row></xdr:from><xdr:graphicFrame><xdr:nvGraphicFramePr><xdr:cNvPr id="2" name="Chart 1"/></xdr:nvGraphicFramePr><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart r:id="rId3"/></a:graphicData></a:graphic></xdr:graphicFrame><xdr:clientData/></xdr:twoCellAnchor></xdr:wsDr>"#;
+        let anchors = parse_drawing("xl/drawings/drawing1.xml", xml).unwrap();
+
+        assert_eq!(anchors.charts.len(), 1);
+        assert_eq!((anchors.charts[0].row, anchors.charts[0].col), (2, 4));
+        assert_eq!(anchors.charts[0].id, "rId3");
+    }
+}
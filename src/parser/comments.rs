@@ -0,0 +1,436 @@
+//! Comments Module
+//!
+//! セルのコメント（メモ）とスレッド形式のコメントを解析するモジュール。
+//!
+//! コメントはワークシートのリレーションシップ（例: xl/worksheets/_rels/sheet1.xml.rels）から
+//! 参照される次のパーツに記録されます。
+//!
+//! 1. xl/comments*.xml: メモ（従来のコメント）。`<authors>`の作成者と`<comment ref="B2" authorId="0">`の本文
+//! 2. xl/threadedComments/threadedComment*.xml: スレッド形式のコメント（返信は記録順に親のコメントの後に続く）
+//! 3. xl/persons/person.xml: スレッド形式のコメントの作成者（`personId` → 表示名、ワークブックのリレーションシップから参照）
+//!
+//! スレッド形式のコメントには、互換性のために同じセルにメモ（`[Threaded comment]`で始まる本文）も
+//! 作成されるため、スレッド形式のコメントがあるセルではメモを使用しません。
+//! メモの吹き出し（xl/drawings/vmlDrawing*.vml）は表示位置と大きさのみで本文を含まないため、解析しません。
+
+use super::metadata::{parse_cell_ref_bytes, XlsxMetadataParser};
+use super::rich_data::{read_part, resolve_target};
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// メモのリレーションシップの種類（末尾）
+const COMMENTS_REL: &str = "/comments";
+
+/// スレッド形式のコメントのリレーションシップの種類（末尾）
+const THREADED_COMMENTS_REL: &str = "/threadedComment";
+
+/// スレッド形式のコメントの作成者一覧のリレーションシップの種類（末尾）
+const PERSONS_REL: &str = "/person";
+
+/// セルのコメント（内部用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CellComment {
+    /// 作成者（不明な場合は`None`）
+    pub author: Option<String>,
+
+    /// 本文（前後の空白を除く）
+    pub text: String,
+}
+
+impl CellComment {
+    /// 作成者を付加した本文を取得（例: `Alice: Please check`）
+    pub fn to_text(&self) -> String {
+        match &self.author {
+            Some(author) => format!("{}: {}", author, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// シートごとのセルのコメントを解析
+///
+/// コメントのないシートは含みません。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `worksheet_parts` - (ワークシートパーツ, シート名)の一覧
+///
+/// # 戻り値
+///
+/// * `Ok(HashMap<..>)` - シート名 -> セル座標（シートの絶対座標） -> コメント（スレッドの場合は返信を含む記録順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+#[allow(clippy::type_complexity)]
+pub(crate) fn parse_comments<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    worksheet_parts: &[(String, String)],
+) -> Result<HashMap<String, HashMap<(u32, u32), Vec<CellComment>>>, XlsxToMdError> {
+    let mut comments = HashMap::new();
+    // スレッド形式のコメントの作成者（最初に必要になった時点で解析）
+    let mut persons: Option<HashMap<String, String>> = None;
+
+    for (file_name, sheet_name) in worksheet_parts {
+        let rels_part = XlsxMetadataParser::rels_path_for_part(file_name);
+        let Some(xml) = read_part(archive, &rels_part)? else {
+            continue;
+        };
+        let rels = parse_rel_types(&rels_part, &xml)?;
+        let base = file_name.rsplit_once('/').map_or("", |(dir, _)| dir);
+
+        let mut sheet_comments: HashMap<(u32, u32), Vec<CellComment>> = HashMap::new();
+        for (_, target) in rels.iter().filter(|(t, _)| t.ends_with(COMMENTS_REL)) {
+            let part = resolve_target(base, target);
+            if let Some(xml) = read_part(archive, &part)? {
+                sheet_comments.extend(parse_legacy_comments(&part, &xml)?);
+            }
+        }
+        for (_, target) in rels
+            .iter()
+            .filter(|(t, _)| t.ends_with(THREADED_COMMENTS_REL))
+        {
+            let part = resolve_target(base, target);
+            let Some(xml) = read_part(archive, &part)? else {
+                continue;
+            };
+            if persons.is_none() {
+                persons = Some(parse_workbook_persons(archive)?);
+            }
+            let names = persons.get_or_insert_with(HashMap::new);
+            // スレッド形式のコメントがあるセルは、互換用のメモを置き換える
+            sheet_comments.extend(parse_threaded_comments(&part, &xml, names)?);
+        }
+
+        if !sheet_comments.is_empty() {
+            comments.insert(sheet_name.clone(), sheet_comments);
+        }
+    }
+    Ok(comments)
+}
+
+/// 属性値を文字列として取得（内部ヘルパー）
+fn str_attr(
+    part: &str,
+    element: &BytesStart<'_>,
+    key: &[u8],
+) -> Result<Option<String>, XlsxToMdError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+        if attr.key.local_name().as_ref() == key {
+            let raw = std::str::from_utf8(&attr.value)?;
+            let value =
+                quick_xml::escape::unescape(raw).map_err(|e| XlsxToMdError::xml(part, e))?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// リレーションシップファイルから、種類とターゲットの一覧を解析（内部ヘルパー）
+pub(super) fn parse_rel_types(
+    part: &str,
+    xml: &[u8],
+) -> Result<Vec<(String, String)>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut rels = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.local_name().as_ref() == b"Relationship" =>
+            {
+                let rel_type = str_attr(part, &e, b"Type")?;
+                let target = str_attr(part, &e, b"Target")?;
+                if let (Some(rel_type), Some(target)) = (rel_type, target) {
+                    rels.push((rel_type, target));
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(rels)
+}
+
+/// ワークブックのリレーションシップから作成者一覧のパーツを解決し、`personId`から表示名への対応を解析（内部ヘルパー）
+fn parse_workbook_persons<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<HashMap<String, String>, XlsxToMdError> {
+    let rels_part = "xl/_rels/workbook.xml.rels";
+    let persons_part = match read_part(archive, rels_part)? {
+        Some(xml) => parse_rel_types(rels_part, &xml)?
+            .into_iter()
+            .find(|(rel_type, _)| rel_type.ends_with(PERSONS_REL))
+            .map(|(_, target)| resolve_target("xl", &target)),
+        None => None,
+    };
+    let Some(part) = persons_part else {
+        return Ok(HashMap::new());
+    };
+    match read_part(archive, &part)? {
+        Some(xml) => parse_persons(&part, &xml),
+        None => Ok(HashMap::new()),
+    }
+}
+
+/// xl/persons/person.xml から、`personId`から表示名への対応を解析（内部ヘルパー）
+fn parse_persons(part: &str, xml: &[u8]) -> Result<HashMap<String, String>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut persons = HashMap::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"person" => {
+                let id = str_attr(part, &e, b"id")?;
+                let name = str_attr(part, &e, b"displayName")?;
+                if let (Some(id), Some(name)) = (id, name) {
+                    persons.insert(id, name);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(persons)
+}
+
+/// 本文の要素（`<text>`）内のテキストを連結（内部ヘルパー）
+///
+/// メモの本文はリッチテキスト（`<r>`・`<t>`要素）、スレッド形式のコメントの本文は
+/// `<text>`要素の直下のテキストです（`plain`で指定）。
+/// ふりがな（`<rPh>`）のテキストは含めません。`<text>`の終了タグの直後まで読み進めます。
+fn read_text_element(
+    part: &str,
+    reader: &mut XmlReader,
+    plain: bool,
+) -> Result<String, XlsxToMdError> {
+    let mut text = String::new();
+    let mut in_text = plain;
+    let mut phonetic_depth = 0usize;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"rPh" => phonetic_depth += 1,
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"rPh" => phonetic_depth = phonetic_depth.saturating_sub(1),
+                b"t" => in_text = false,
+                b"text" => break,
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_text && phonetic_depth == 0 => {
+                text.push_str(&e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(text)
+}
+
+/// xl/comments*.xml から、セル座標からメモへの対応を解析（内部ヘルパー）
+///
+/// Excelはメモの本文の先頭に作成者名（`Alice:`と改行）を太字で挿入するため、
+/// 作成者名と同じ場合は取り除きます。
+fn parse_legacy_comments(
+    part: &str,
+    xml: &[u8],
+) -> Result<HashMap<(u32, u32), Vec<CellComment>>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+
+    let mut authors: Vec<String> = Vec::new();
+    let mut comments = HashMap::new();
+    // 読み込み中の<comment>要素の(セル座標, 作成者のインデックス)
+    let mut current: Option<((u32, u32), Option<usize>)> = None;
+    let mut in_author = false;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"author" => {
+                    in_author = true;
+                    authors.push(String::new());
+                }
+                b"comment" => {
+                    let coord = str_attr(part, &e, b"ref")?
+                        .and_then(|r| parse_cell_ref_bytes(r.as_bytes()));
+                    let author = str_attr(part, &e, b"authorId")?.and_then(|id| id.parse().ok());
+                    current = coord.map(|coord| (coord, author));
+                }
+                b"text" => {
+                    let text = read_text_element(part, &mut reader, false)?;
+                    let Some((coord, author_id)) = current.take() else {
+                        continue;
+                    };
+                    let author = author_id
+                        .and_then(|id: usize| authors.get(id))
+                        .filter(|author| !author.is_empty())
+                        .cloned();
+                    let text = match author
+                        .as_deref()
+                        .and_then(|a| text.strip_prefix(a))
+                        .and_then(|rest| rest.strip_prefix(':'))
+                    {
+                        Some(rest) => rest.to_string(),
+                        None => text,
+                    };
+                    let text = text.trim();
+                    if !text.is_empty() {
+                        comments.insert(
+                            coord,
+                            vec![CellComment {
+                                author,
+                                text: text.to_string(),
+                            }],
+                        );
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) if in_author => {
+                if let Some(author) = authors.last_mut() {
+                    author.push_str(&e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?);
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"author" => in_author = false,
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"author" => {
+                authors.push(String::new());
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(comments)
+}
+
+/// xl/threadedComments/threadedComment*.xml から、セル座標からコメントのスレッドへの対応を解析（内部ヘルパー）
+fn parse_threaded_comments(
+    part: &str,
+    xml: &[u8],
+    persons: &HashMap<String, String>,
+) -> Result<HashMap<(u32, u32), Vec<CellComment>>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+
+    let mut threads: HashMap<(u32, u32), Vec<CellComment>> = HashMap::new();
+    // 読み込み中の<threadedComment>要素の(セル座標, 作成者)
+    let mut current: Option<((u32, u32), Option<String>)> = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"threadedComment" => {
+                    let coord = str_attr(part, &e, b"ref")?
+                        .and_then(|r| parse_cell_ref_bytes(r.as_bytes()));
+                    let author = str_attr(part, &e, b"personId")?
+                        .and_then(|id| persons.get(&id))
+                        .cloned();
+                    current = coord.map(|coord| (coord, author));
+                }
+                b"text" => {
+                    let text = read_text_element(part, &mut reader, true)?;
+                    let Some((coord, author)) = current.take() else {
+                        continue;
+                    };
+                    let text = text.trim();
+                    if !text.is_empty() {
+                        threads.entry(coord).or_default().push(CellComment {
+                            author,
+                            text: text.to_string(),
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(threads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn archive(parts: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+    }
+
+    fn comment(author: Option<&str>, text: &str) -> CellComment {
+        CellComment {
+            author: author.map(str::to_string),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let mut archive = archive(&[
+            (
+                "xl/_rels/workbook.xml.rels",
+                r#"<Relationships><Relationship Id="rId9" Type="http://schemas.microsoft.com/office/2017/10/relationships/person" Target="persons/person.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/persons/person.xml",
+                r#"<personList><person displayName="Bob" id="{P1}"/><person displayName="Carol" id="{P2}"/></personList>"#,
+            ),
+            (
+                "xl/worksheets/_rels/sheet1.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/vmlDrawing" Target="../drawings/vmlDrawing1.vml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments" Target="../comments1.xml"/><Relationship Id="rId3" Type="http://schemas.microsoft.com/office/2017/10/relationships/threadedComment" Target="../threadedComments/threadedComment1.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/comments1.xml",
+                r#"<comments><authors><author>Alice</author><author>tc={T1}</author></authors><commentList><comment ref="B2" authorId="0"><text><r><rPr><b/></rPr><t>Alice:</t></r><r><t xml:space="preserve">
+Check &amp; confirm</t></r><rPh sb="0" eb="1"><t>ふりがな</t></rPh></text></comment><comment ref="C3" authorId="1"><text><t>[Threaded comment] Your version of Excel...</t></text></comment></commentList></comments>"#,
+            ),
+            (
+                "xl/threadedComments/threadedComment1.xml",
+                r#"<ThreadedComments><threadedComment ref="C3" personId="{P1}" id="{T1}"><text>Is this final?</text></threadedComment><threadedComment ref="C3" personId="{P2}" id="{T2}" parentId="{T1}"><text>Yes</text></threadedComment></ThreadedComments>"#,
+            ),
+        ]);
+        let parts = vec![("xl/worksheets/sheet1.xml".to_string(), "Data".to_string())];
+
+        let comments = parse_comments(&mut archive, &parts).unwrap();
+        let sheet = &comments["Data"];
+        assert_eq!(sheet.len(), 2);
+        // メモの本文の先頭の作成者名とふりがなは含めない
+        assert_eq!(sheet[&(1, 1)], [comment(Some("Alice"), "Check & confirm")]);
+        // スレッド形式のコメントは互換用のメモを置き換え、返信を記録順に含める
+        assert_eq!(
+            sheet[&(2, 2)],
+            [
+                comment(Some("Bob"), "Is this final?"),
+                comment(Some("Carol"), "Yes")
+            ]
+        );
+        assert_eq!(sheet[&(2, 2)][0].to_text(), "Bob: Is this final?");
+    }
+
+    #[test]
+    fn test_parse_comments_without_comments() {
+        let mut archive = archive(&[(
+            "xl/worksheets/_rels/sheet1.xml.rels",
+            r#"<Relationships><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com" TargetMode="External"/></Relationships>"#,
+        )]);
+        let parts = vec![("xl/worksheets/sheet1.xml".to_string(), "Data".to_string())];
+        assert!(parse_comments(&mut archive, &parts).unwrap().is_empty());
+    }
+}
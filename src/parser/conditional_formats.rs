@@ -0,0 +1,565 @@
+//! Conditional Formats Module
+//!
+//! ワークシートXMLの条件付き書式（`<conditionalFormatting>`）のルールを解析し、
+//! 単純なルールに該当するセルを判定するモジュール。
+//!
+//! 条件付き書式はシートデータ（`<sheetData>`）の後、Excel 2010以降の拡張ルールは
+//! `<extLst>`内の`<x14:conditionalFormatting>`（適用範囲は`<xm:sqref>`要素）に記録されます。
+
+use super::metadata::parse_cell_ref_bytes;
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use crate::types::{CellCoord, CellRange, CellValue, RawCellData};
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// 条件付き書式の1つのルール（内部用）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ConditionalFormat {
+    /// 適用範囲（A1記法、複数の範囲は空白区切り）
+    pub sqref: String,
+
+    /// 適用範囲（`sqref`を解析したもの）
+    pub ranges: Vec<CellRange>,
+
+    /// ルールの種類（例: `cellIs`、`duplicateValues`、`top10`、`colorScale`）
+    pub rule_type: String,
+
+    /// 優先順位（小さいほど優先）
+    pub priority: Option<u32>,
+
+    /// 比較演算子（`cellIs`の`greaterThan`など、文字列のルールの`containsText`など）
+    pub operator: Option<String>,
+
+    /// 数式（`cellIs`の比較値、`expression`の条件式など）
+    pub formulas: Vec<String>,
+
+    /// 上位・下位の件数または割合（`top10`）
+    pub rank: Option<u32>,
+
+    /// `rank`が割合か（`top10`）
+    pub percent: bool,
+
+    /// 下位か（`top10`）
+    pub bottom: bool,
+
+    /// 平均より上か（`aboveAverage`、既定値は真）
+    pub above_average: bool,
+
+    /// 平均と等しい値を含むか（`aboveAverage`）
+    pub equal_average: bool,
+
+    /// 標準偏差の倍数（`aboveAverage`で標準偏差を基準とする場合）
+    pub std_dev: Option<u32>,
+
+    /// 比較する文字列（`containsText`、`beginsWith`、`endsWith`など）
+    pub text: Option<String>,
+
+    /// しきい値（カラースケール・データバー・アイコンセットの`<cfvo>`の種類と値）
+    pub thresholds: Vec<(String, Option<String>)>,
+}
+
+impl ConditionalFormat {
+    /// ルールをJSONのオブジェクトに変換
+    ///
+    /// ルールの種類に関係のない項目（値のない項目）は含みません。
+    pub fn to_json(&self) -> Value {
+        let mut obj = json!({
+            "range": self.sqref,
+            "type": self.rule_type,
+        });
+        if let Some(priority) = self.priority {
+            obj["priority"] = json!(priority);
+        }
+        if let Some(operator) = &self.operator {
+            obj["operator"] = json!(operator);
+        }
+        if !self.formulas.is_empty() {
+            obj["formulas"] = json!(self.formulas);
+        }
+        if let Some(rank) = self.rank {
+            obj["rank"] = json!(rank);
+            obj["percent"] = json!(self.percent);
+            obj["bottom"] = json!(self.bottom);
+        }
+        if self.rule_type == "aboveAverage" {
+            obj["above_average"] = json!(self.above_average);
+            obj["equal_average"] = json!(self.equal_average);
+            if let Some(std_dev) = self.std_dev {
+                obj["std_dev"] = json!(std_dev);
+            }
+        }
+        if let Some(text) = &self.text {
+            obj["text"] = json!(text);
+        }
+        if !self.thresholds.is_empty() {
+            let thresholds: Vec<Value> = self
+                .thresholds
+                .iter()
+                .map(|(kind, value)| match value {
+                    Some(value) => json!({ "type": kind, "value": value }),
+                    None => json!({ "type": kind }),
+                })
+                .collect();
+            obj["thresholds"] = json!(thresholds);
+        }
+        obj
+    }
+
+    /// 注記の本文を取得（注記の対象外のルールは`None`）
+    ///
+    /// 例: `duplicate value in A2:A10`、`top 10% in B2:B20`、`value > 100 in C2:C9`
+    pub fn description(&self) -> Option<String> {
+        let condition = match self.rule_type.as_str() {
+            "duplicateValues" => "duplicate value".to_string(),
+            "uniqueValues" => "unique value".to_string(),
+            "top10" => format!(
+                "{} {}{}",
+                if self.bottom { "bottom" } else { "top" },
+                self.rank?,
+                if self.percent { "%" } else { "" }
+            ),
+            "aboveAverage" if self.std_dev.is_none() => {
+                let side = if self.above_average { "above" } else { "below" };
+                if self.equal_average {
+                    format!("equal to or {} average", side)
+                } else {
+                    format!("{} average", side)
+                }
+            }
+            "cellIs" => {
+                let (low, high) = self.numeric_operands()?;
+                match (self.operator.as_deref()?, high) {
+                    ("between", Some(high)) => format!("value between {} and {}", low, high),
+                    ("notBetween", Some(high)) => {
+                        format!("value not between {} and {}", low, high)
+                    }
+                    (operator, _) => format!("value {} {}", operator_symbol(operator)?, low),
+                }
+            }
+            "containsText" | "notContainsText" | "beginsWith" | "endsWith" => {
+                let verb = match self.rule_type.as_str() {
+                    "containsText" => "contains",
+                    "notContainsText" => "does not contain",
+                    "beginsWith" => "begins with",
+                    _ => "ends with",
+                };
+                format!("{} \"{}\"", verb, self.text.as_deref()?)
+            }
+            _ => return None,
+        };
+        Some(format!("{} in {}", condition, self.sqref))
+    }
+
+    /// ルールに該当するセルの座標を取得
+    ///
+    /// 注記の対象外のルール（`description()`が`None`）では空を返します。
+    /// 値の比較はExcelと同様に、文字列は大文字小文字を区別せずに行います。
+    ///
+    /// # 引数
+    ///
+    /// * `cells` - シートのセルデータ
+    pub fn matching_cells(&self, cells: &[RawCellData]) -> Vec<CellCoord> {
+        if self.description().is_none() {
+            return Vec::new();
+        }
+        let in_range: Vec<&RawCellData> = cells
+            .iter()
+            .filter(|cell| !cell.value.is_empty())
+            .filter(|cell| self.ranges.iter().any(|range| range.contains(cell.coord)))
+            .collect();
+        let numbers: Vec<f64> = in_range.iter().filter_map(|c| number(&c.value)).collect();
+
+        let matches: Box<dyn Fn(&RawCellData) -> bool> = match self.rule_type.as_str() {
+            "duplicateValues" | "uniqueValues" => {
+                let mut counts: HashMap<String, usize> = HashMap::new();
+                for cell in &in_range {
+                    *counts.entry(compare_key(&cell.value)).or_default() += 1;
+                }
+                let duplicate = self.rule_type == "duplicateValues";
+                Box::new(move |cell| (counts[&compare_key(&cell.value)] > 1) == duplicate)
+            }
+            "top10" => {
+                let Some(threshold) = self.rank_threshold(&numbers) else {
+                    return Vec::new();
+                };
+                let bottom = self.bottom;
+                Box::new(move |cell| {
+                    number(&cell.value).is_some_and(|n| {
+                        if bottom {
+                            n <= threshold
+                        } else {
+                            n >= threshold
+                        }
+                    })
+                })
+            }
+            "aboveAverage" => {
+                if numbers.is_empty() {
+                    return Vec::new();
+                }
+                let average = numbers.iter().sum::<f64>() / numbers.len() as f64;
+                let (above, equal) = (self.above_average, self.equal_average);
+                Box::new(move |cell| {
+                    number(&cell.value).is_some_and(|n| {
+                        (equal && n == average) || if above { n > average } else { n < average }
+                    })
+                })
+            }
+            "cellIs" => {
+                let Some((low, high)) = self.numeric_operands() else {
+                    return Vec::new();
+                };
+                let operator = self.operator.clone().unwrap_or_default();
+                Box::new(move |cell| {
+                    number(&cell.value).is_some_and(|n| {
+                        let high = high.unwrap_or(low);
+                        match operator.as_str() {
+                            "between" => n >= low.min(high) && n <= low.max(high),
+                            "notBetween" => n < low.min(high) || n > low.max(high),
+                            "equal" => n == low,
+                            "notEqual" => n != low,
+                            "greaterThan" => n > low,
+                            "greaterThanOrEqual" => n >= low,
+                            "lessThan" => n < low,
+                            "lessThanOrEqual" => n <= low,
+                            _ => false,
+                        }
+                    })
+                })
+            }
+            _ => {
+                let text = self.text.clone().unwrap_or_default().to_lowercase();
+                let rule_type = self.rule_type.clone();
+                Box::new(move |cell| {
+                    let value = compare_key(&cell.value);
+                    match rule_type.as_str() {
+                        "containsText" => value.contains(&text),
+                        "notContainsText" => !value.contains(&text),
+                        "beginsWith" => value.starts_with(&text),
+                        _ => value.ends_with(&text),
+                    }
+                })
+            }
+        };
+
+        in_range
+            .into_iter()
+            .filter(|cell| matches(cell))
+            .map(|cell| cell.coord)
+            .collect()
+    }
+
+    /// `cellIs`の比較値を数値として取得（内部ヘルパー）
+    ///
+    /// 比較値が数値の定数でない場合（セル参照や数式）は`None`を返します。
+    fn numeric_operands(&self) -> Option<(f64, Option<f64>)> {
+        let mut values = self.formulas.iter().map(|f| f.trim().parse::<f64>().ok());
+        let low = values.next()??;
+        let high = match values.next() {
+            Some(value) => Some(value?),
+            None => None,
+        };
+        Some((low, high))
+    }
+
+    /// 上位・下位の境界値を取得（内部ヘルパー）
+    ///
+    /// 割合の場合は件数に対する割合（切り捨て、最低1件）を件数とします。
+    fn rank_threshold(&self, numbers: &[f64]) -> Option<f64> {
+        let rank = self.rank? as usize;
+        if numbers.is_empty() || rank == 0 {
+            return None;
+        }
+        let count = if self.percent {
+            (numbers.len() * rank.min(100) / 100).max(1)
+        } else {
+            rank.min(numbers.len())
+        };
+        let mut sorted = numbers.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        if !self.bottom {
+            sorted.reverse();
+        }
+        sorted.get(count - 1).copied()
+    }
+}
+
+/// 比較演算子の記号を取得（内部ヘルパー）
+fn operator_symbol(operator: &str) -> Option<&'static str> {
+    Some(match operator {
+        "equal" => "=",
+        "notEqual" => "<>",
+        "greaterThan" => ">",
+        "greaterThanOrEqual" => ">=",
+        "lessThan" => "<",
+        "lessThanOrEqual" => "<=",
+        _ => return None,
+    })
+}
+
+/// セルの数値を取得（日付はシリアル値）（内部ヘルパー）
+fn number(value: &CellValue) -> Option<f64> {
+    match value {
+        CellValue::Number(n) => Some(*n),
+        CellValue::DateTime { serial, .. } => Some(*serial),
+        _ => None,
+    }
+}
+
+/// 値の比較に使用する文字列を取得（文字列は小文字化）（内部ヘルパー）
+fn compare_key(value: &CellValue) -> String {
+    match value {
+        CellValue::String(s) => s.to_lowercase(),
+        CellValue::Bool(b) => b.to_string(),
+        CellValue::Error(e) => e.as_excel_str().to_string(),
+        value => number(value).map(|n| n.to_string()).unwrap_or_default(),
+    }
+}
+
+/// 適用範囲（例: `A1:B5 D2`）を解析（内部ヘルパー）
+pub(super) fn parse_sqref(sqref: &str) -> Vec<CellRange> {
+    sqref
+        .split_whitespace()
+        .filter_map(|reference| {
+            let mut ends = reference.splitn(2, ':');
+            let (row, col) = parse_cell_ref_bytes(ends.next()?.as_bytes())?;
+            let start = CellCoord::new(row, col);
+            let end = match ends.next() {
+                Some(end) => {
+                    let (row, col) = parse_cell_ref_bytes(end.as_bytes())?;
+                    CellCoord::new(row, col)
+                }
+                None => start,
+            };
+            Some(CellRange::new(start, end))
+        })
+        .collect()
+}
+
+/// 属性値を文字列として取得（内部ヘルパー）
+pub(super) fn str_attr(
+    part: &str,
+    element: &BytesStart<'_>,
+    key: &[u8],
+) -> Result<Option<String>, XlsxToMdError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+        if attr.key.local_name().as_ref() == key {
+            let raw = std::str::from_utf8(&attr.value)?;
+            let value =
+                quick_xml::escape::unescape(raw).map_err(|e| XlsxToMdError::xml(part, e))?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// `<cfRule>`要素の属性からルールを生成（内部ヘルパー）
+fn parse_rule(part: &str, element: &BytesStart<'_>) -> Result<ConditionalFormat, XlsxToMdError> {
+    let flag = |value: Option<String>| value.is_some_and(|v| v == "1" || v == "true");
+    Ok(ConditionalFormat {
+        sqref: String::new(),
+        ranges: Vec::new(),
+        rule_type: str_attr(part, element, b"type")?.unwrap_or_default(),
+        priority: str_attr(part, element, b"priority")?.and_then(|v| v.parse().ok()),
+        operator: str_attr(part, element, b"operator")?,
+        formulas: Vec::new(),
+        rank: str_attr(part, element, b"rank")?.and_then(|v| v.parse().ok()),
+        percent: flag(str_attr(part, element, b"percent")?),
+        bottom: flag(str_attr(part, element, b"bottom")?),
+        above_average: str_attr(part, element, b"aboveAverage")?
+            .is_none_or(|v| v == "1" || v == "true"),
+        equal_average: flag(str_attr(part, element, b"equalAverage")?),
+        std_dev: str_attr(part, element, b"stdDev")?.and_then(|v| v.parse().ok()),
+        text: str_attr(part, element, b"text")?,
+        thresholds: Vec::new(),
+    })
+}
+
+/// ワークシートXMLから条件付き書式のルールを解析
+///
+/// `xml_content`は最初の条件付き書式の要素の開始位置からのスライスです。
+/// ルールは記録順（`<conditionalFormatting>`の順、要素内のルールの順）に返します。
+///
+/// # 引数
+///
+/// * `part` - ワークシートXMLのパーツ名（エラーメッセージ用）
+/// * `xml_content` - 条件付き書式の要素の開始位置からのワークシートXML
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<ConditionalFormat>)` - 条件付き書式のルール
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_conditional_formats(
+    part: &str,
+    xml_content: &[u8],
+) -> Result<Vec<ConditionalFormat>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml_content);
+    reader.trim_text(true);
+    // スライスの途中から読むため、対応する開始タグのない終了タグを許容する
+    reader.check_end_names(false);
+
+    let mut formats = Vec::new();
+    // 解析中の<conditionalFormatting>の適用範囲と、その要素内のルール
+    let mut sqref: Option<String> = None;
+    let mut rules: Vec<ConditionalFormat> = Vec::new();
+    let mut in_formatting = false;
+    // 解析中のテキスト要素（`<formula>`・`<xm:f>`は`true`、`<xm:sqref>`は`false`）
+    let mut text_target: Option<bool> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"conditionalFormatting" => {
+                    in_formatting = true;
+                    sqref = str_attr(part, &e, b"sqref")?;
+                    rules.clear();
+                }
+                b"cfRule" if in_formatting => rules.push(parse_rule(part, &e)?),
+                b"formula" | b"f" if in_formatting => text_target = Some(true),
+                b"sqref" if in_formatting => text_target = Some(false),
+                b"cfvo" if in_formatting => {
+                    if let Some(rule) = rules.last_mut() {
+                        let kind = str_attr(part, &e, b"type")?.unwrap_or_default();
+                        rule.thresholds.push((kind, str_attr(part, &e, b"val")?));
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                match text_target {
+                    Some(true) => {
+                        if let Some(rule) = rules.last_mut() {
+                            rule.formulas.push(text.into_owned());
+                        }
+                    }
+                    Some(false) => sqref = Some(text.into_owned()),
+                    None => {}
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"formula" | b"f" | b"sqref" => text_target = None,
+                b"conditionalFormatting" if in_formatting => {
+                    in_formatting = false;
+                    let sqref = sqref.take().unwrap_or_default();
+                    let ranges = parse_sqref(&sqref);
+                    for rule in rules.drain(..) {
+                        formats.push(ConditionalFormat {
+                            sqref: sqref.clone(),
+                            ranges: ranges.clone(),
+                            ..rule
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(formats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number_cell(row: u32, col: u32, n: f64) -> RawCellData {
+        RawCellData {
+            coord: CellCoord::new(row, col),
+            value: CellValue::Number(n),
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_conditional_formats() {
+        let xml = br#"<conditionalFormatting sqref="A2:A10 C1"><cfRule type="duplicateValues" dxfId="0" priority="2"/><cfRule type="cellIs" dxfId="1" priority="1" operator="between"><formula>10</formula><formula>20</formula></cfRule></conditionalFormatting><conditionalFormatting sqref="B2:B9"><cfRule type="colorScale" priority="3"><colorScale><cfvo type="min"/><cfvo type="percentile" val="50"/><color rgb="FFF8696B"/></colorScale></cfRule></conditionalFormatting></worksheet>"#;
+        let formats = parse_conditional_formats("xl/worksheets/sheet1.xml", xml).unwrap();
+        assert_eq!(formats.len(), 3);
+        assert_eq!(formats[0].rule_type, "duplicateValues");
+        assert_eq!(formats[0].sqref, "A2:A10 C1");
+        assert_eq!(formats[0].ranges.len(), 2);
+        assert_eq!(formats[1].formulas, vec!["10", "20"]);
+        assert_eq!(
+            formats[1].description().as_deref(),
+            Some("value between 10 and 20 in A2:A10 C1")
+        );
+        assert_eq!(
+            formats[2].to_json(),
+            json!({
+                "range": "B2:B9",
+                "type": "colorScale",
+                "priority": 3,
+                "thresholds": [{"type": "min"}, {"type": "percentile", "value": "50"}],
+            })
+        );
+        assert_eq!(formats[2].description(), None);
+
+        // Excel 2010以降の拡張ルール（適用範囲は<xm:sqref>）
+        let xml = br#"<x14:conditionalFormatting xmlns:xm="m"><x14:cfRule type="containsText" operator="containsText" text="late" id="{1}"><xm:f>NOT(ISERROR(SEARCH("late",D2)))</xm:f></x14:cfRule><xm:sqref>D2:D9</xm:sqref></x14:conditionalFormatting></ext></extLst></worksheet>"#;
+        let formats = parse_conditional_formats("xl/worksheets/sheet1.xml", xml).unwrap();
+        assert_eq!(formats[0].sqref, "D2:D9");
+        assert_eq!(
+            formats[0].description().as_deref(),
+            Some("contains \"late\" in D2:D9")
+        );
+    }
+
+    #[test]
+    fn test_matching_cells() {
+        let cells: Vec<RawCellData> = [5.0, 9.0, 5.0, 1.0, 7.0]
+            .iter()
+            .enumerate()
+            .map(|(row, n)| number_cell(row as u32 + 1, 0, *n))
+            .collect();
+        let rule = |xml: &str| {
+            let xml = format!(
+                "<conditionalFormatting sqref=\"A2:A6\">{}</conditionalFormatting>",
+                xml
+            );
+            parse_conditional_formats("sheet", xml.as_bytes())
+                .unwrap()
+                .remove(0)
+        };
+        let rows = |rule: ConditionalFormat| -> Vec<u32> {
+            rule.matching_cells(&cells)
+                .iter()
+                .map(|coord| coord.row)
+                .collect()
+        };
+
+        assert_eq!(rows(rule(r#"<cfRule type="duplicateValues"/>"#)), [1, 3]);
+        assert_eq!(rows(rule(r#"<cfRule type="top10" rank="2"/>"#)), [2, 5]);
+        assert_eq!(
+            rows(rule(
+                r#"<cfRule type="top10" rank="20" percent="1" bottom="1"/>"#
+            )),
+            [4]
+        );
+        // 平均は5.4
+        assert_eq!(rows(rule(r#"<cfRule type="aboveAverage"/>"#)), [2, 5]);
+        assert_eq!(
+            rows(rule(
+                r#"<cfRule type="cellIs" operator="lessThanOrEqual"><formula>5</formula></cfRule>"#
+            )),
+            [1, 3, 4]
+        );
+        // 比較値がセル参照の場合は注記しない
+        assert!(rows(rule(
+            r#"<cfRule type="cellIs" operator="equal"><formula>$B$1</formula></cfRule>"#
+        ))
+        .is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+//! Data Validations Module
+//!
+//! ワークシートXMLの入力規則（`<dataValidations>`）を解析するモジュール。
+//!
+//! 入力規則はシートデータ（`<sheetData>`）の後に記録されます。別のシートの範囲を参照するリストなど、
+//! Excel 2010以降の拡張の入力規則は`<extLst>`内の`<x14:dataValidation>`（数式は`<xm:f>`、
+//! 適用範囲は`<xm:sqref>`要素）に記録されます。
+
+use super::conditional_formats::{parse_sqref, str_attr};
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use crate::types::{split_sheet_prefix, CellRange};
+use quick_xml::events::{BytesStart, Event};
+use serde_json::{json, Value};
+
+/// 入力規則の1つのルール（内部用）
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DataValidation {
+    /// 適用範囲（A1記法、複数の範囲は空白区切り）
+    pub sqref: String,
+
+    /// 適用範囲（`sqref`を解析したもの）
+    pub ranges: Vec<CellRange>,
+
+    /// 入力規則の種類（例: `list`、`whole`、`decimal`、`date`、`textLength`、`custom`）
+    pub validation_type: String,
+
+    /// 比較演算子（`whole`の`between`など、省略時は`between`）
+    pub operator: Option<String>,
+
+    /// 数式（`formula1`・`formula2`、リストの場合は値の一覧または参照先）
+    pub formulas: Vec<String>,
+
+    /// 入力可能な値の一覧（リストの場合、値の一覧または参照先のセルの値に解決できた場合のみ）
+    pub allowed: Option<Vec<String>>,
+}
+
+impl DataValidation {
+    /// リストの入力規則かどうか
+    pub fn is_list(&self) -> bool {
+        self.validation_type == "list"
+    }
+
+    /// リストの値の一覧（`"Open,Closed,Pending"`のように数式に直接記載されている場合のみ）を取得
+    pub fn literal_values(&self) -> Option<Vec<String>> {
+        if !self.is_list() {
+            return None;
+        }
+        let list = self
+            .formulas
+            .first()?
+            .strip_prefix('"')?
+            .strip_suffix('"')?;
+        Some(
+            list.replace("\"\"", "\"")
+                .split(',')
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .collect(),
+        )
+    }
+
+    /// リストの参照先（シート名で修飾されている場合はシート名、セル範囲または名前）を取得
+    ///
+    /// 値の一覧が数式に直接記載されている場合、関数（`INDIRECT`など）を含む場合は`None`を返します。
+    pub fn list_reference(&self) -> Option<(Option<String>, &str)> {
+        if !self.is_list() {
+            return None;
+        }
+        let formula = self.formulas.first()?.trim();
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        if formula.is_empty() || formula.starts_with('"') || formula.contains('(') {
+            return None;
+        }
+        Some(match split_sheet_prefix(formula) {
+            Some((sheet, reference)) => (Some(sheet), reference),
+            None => (None, formula),
+        })
+    }
+
+    /// ルールをJSONのオブジェクトに変換
+    ///
+    /// ルールの種類に関係のない項目（値のない項目）は含みません。
+    pub fn to_json(&self) -> Value {
+        let mut obj = json!({
+            "range": self.sqref,
+            "type": self.validation_type,
+        });
+        if let Some(operator) = &self.operator {
+            obj["operator"] = json!(operator);
+        }
+        if !self.formulas.is_empty() {
+            obj["formulas"] = json!(self.formulas);
+        }
+        if let Some(allowed) = &self.allowed {
+            obj["allowed"] = json!(allowed);
+        }
+        obj
+    }
+}
+
+/// `<dataValidation>`要素の属性から入力規則を生成（内部ヘルパー）
+fn parse_validation(part: &str, element: &BytesStart<'_>) -> Result<DataValidation, XlsxToMdError> {
+    Ok(DataValidation {
+        sqref: str_attr(part, element, b"sqref")?.unwrap_or_default(),
+        ranges: Vec::new(),
+        validation_type: str_attr(part, element, b"type")?.unwrap_or_else(|| "none".to_string()),
+        operator: str_attr(part, element, b"operator")?,
+        formulas: Vec::new(),
+        allowed: None,
+    })
+}
+
+/// 解析した入力規則を追加（内部ヘルパー）
+///
+/// 種類が`none`（任意の値）のルールは追加しません。
+fn push_validation(validations: &mut Vec<DataValidation>, mut validation: DataValidation) {
+    if validation.validation_type == "none" {
+        return;
+    }
+    validation.ranges = parse_sqref(&validation.sqref);
+    validation.allowed = validation.literal_values();
+    validations.push(validation);
+}
+
+/// ワークシートXMLから入力規則を解析
+///
+/// `xml_content`は最初の入力規則の要素の開始位置からのスライスです。
+/// 種類が`none`（任意の値）のルールは含みません。リストの値の一覧が数式に直接記載されている場合は、
+/// `allowed`に値の一覧を設定します。
+///
+/// # 引数
+///
+/// * `part` - ワークシートXMLのパーツ名（エラーメッセージ用）
+/// * `xml_content` - 入力規則の要素の開始位置からのワークシートXML
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<DataValidation>)` - 入力規則（記録順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_data_validations(
+    part: &str,
+    xml_content: &[u8],
+) -> Result<Vec<DataValidation>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml_content);
+    reader.trim_text(true);
+    // スライスの途中から読むため、対応する開始タグのない終了タグを許容する
+    reader.check_end_names(false);
+
+    let mut validations = Vec::new();
+    // 解析中の<dataValidation>
+    let mut current: Option<DataValidation> = None;
+    // 解析中のテキスト要素（`<formula1>`・`<formula2>`・`<xm:f>`は`true`、`<xm:sqref>`は`false`）
+    let mut text_target: Option<bool> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"dataValidation" => current = Some(parse_validation(part, &e)?),
+                // x14の入力規則では<x14:formula1>の中の<xm:f>に数式がある
+                b"formula1" | b"formula2" | b"f" if current.is_some() => text_target = Some(true),
+                b"sqref" if current.is_some() => text_target = Some(false),
+                _ => {}
+            },
+            // 数式のない入力規則（適用範囲のみ）
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"dataValidation" => {
+                push_validation(&mut validations, parse_validation(part, &e)?);
+            }
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                match (text_target, current.as_mut()) {
+                    (Some(true), Some(validation)) => validation.formulas.push(text.into_owned()),
+                    (Some(false), Some(validation)) => validation.sqref = text.into_owned(),
+                    _ => {}
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"formula1" | b"formula2" | b"f" | b"sqref" => text_target = None,
+                b"dataValidation" => {
+                    if let Some(validation) = current.take() {
+                        push_validation(&mut validations, validation);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(validations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data_validations() {
+        let xml = br#"<dataValidations count="3"><dataValidation type="list" allowBlank="1" sqref="C2:C100"><formula1>"Open, Closed,Pending"</formula1></dataValidation><dataValidation type="whole" operator="greaterThan" sqref="D2:D100 F2"><formula1>0</formula1></dataValidation><dataValidation type="list" sqref="E2:E100"><formula1>$H$1:$H$3</formula1></dataValidation></dataValidations><extLst><ext><x14:dataValidations xmlns:xm="m"><x14:dataValidation type="list"><x14:formula1><xm:f>'Lists ''24'!$A$1:$A$5</xm:f></x14:formula1><xm:sqref>G2:G9</xm:sqref></x14:dataValidation></x14:dataValidations></ext></extLst></worksheet>"#;
+        let validations = parse_data_validations("xl/worksheets/sheet1.xml", xml).unwrap();
+        assert_eq!(validations.len(), 4);
+
+        assert!(validations[0].is_list());
+        assert_eq!(
+            validations[0].allowed.as_deref(),
+            Some(["Open", "Closed", "Pending"].map(String::from).as_slice())
+        );
+        assert_eq!(validations[0].list_reference(), None);
+        assert_eq!(
+            validations[1].to_json(),
+            json!({
+                "range": "D2:D100 F2",
+                "type": "whole",
+                "operator": "greaterThan",
+                "formulas": ["0"],
+            })
+        );
+        assert_eq!(validations[1].ranges.len(), 2);
+        assert_eq!(validations[2].allowed, None);
+        assert_eq!(validations[2].list_reference(), Some((None, "$H$1:$H$3")));
+        // Excel 2010以降の拡張の入力規則（別のシートの範囲の参照）
+        assert_eq!(validations[3].sqref, "G2:G9");
+        assert_eq!(
+            validations[3].list_reference(),
+            Some((Some("Lists '24".to_string()), "$A$1:$A$5"))
+        );
+    }
+}
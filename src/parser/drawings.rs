@@ -0,0 +1,290 @@
+//! Drawings Module
+//!
+//! ワークシートに図形として配置された画像（「セルの上に配置」した画像）を解析するモジュール。
+//!
+//! 画像はワークシートのリレーションシップ（例: xl/worksheets/_rels/sheet1.xml.rels）から
+//! 参照される次のパーツに記録されます。
+//!
+//! 1. xl/drawings/drawing*.xml: 図形の配置（`<xdr:twoCellAnchor>`などの`<xdr:from>`のセル位置）と
+//!    画像（`<xdr:pic>`の`<a:blip r:embed="rId1">`）
+//! 2. xl/drawings/_rels/drawing*.xml.rels: `r:embed`のIDから画像ファイルへの対応
+//! 3. xl/media/*: 画像ファイル
+//!
+//! セル内の画像（「セルに配置」した画像）は`rich_data`モジュールで扱います。
+//! 図形XMLに配置されたグラフ（`<xdr:graphicFrame>`の`<c:chart r:id="rId2">`）の位置も解析し、
+//! グラフの内容は`charts`モジュールで扱います。
+
+use super::comments::parse_rel_types;
+use super::conditional_formats::str_attr;
+use super::metadata::XlsxMetadataParser;
+use super::rich_data::{parse_rel_targets, read_part, resolve_target};
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use quick_xml::events::Event;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::sync::Arc;
+use zip::ZipArchive;
+
+/// 図形のリレーションシップの種類（末尾）
+const DRAWING_REL: &str = "/drawing";
+
+/// 図形として配置された画像（内部用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DrawingImage {
+    /// 配置位置の左上のセルの行インデックス（シートの絶対座標、0始まり）
+    pub row: u32,
+
+    /// 配置位置の左上のセルの列インデックス（シートの絶対座標、0始まり）
+    pub col: u32,
+
+    /// 画像ファイルのパーツ名（例: `xl/media/image1.png`）
+    pub part: String,
+
+    /// 代替テキスト（`<xdr:cNvPr>`の`descr`属性、空の場合は`None`）
+    pub description: Option<String>,
+
+    /// 画像ファイルの内容（同じ画像ファイルを参照する画像で共有）
+    pub data: Arc<[u8]>,
+}
+
+/// 図形XMLから解析した画像の配置（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct PictureAnchor {
+    row: u32,
+    col: u32,
+    embed: String,
+    description: Option<String>,
+}
+
+/// 図形XMLから解析したグラフの配置（内部用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ChartAnchor {
+    /// 配置位置の左上のセルの行インデックス（シートの絶対座標、0始まり）
+    pub row: u32,
+
+    /// 配置位置の左上のセルの列インデックス（シートの絶対座標、0始まり）
+    pub col: u32,
+
+    /// グラフXMLを参照するリレーションシップのID（`<c:chart r:id="rId1">`）
+    pub id: String,
+}
+
+/// 図形XMLから解析した画像とグラフの配置（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct DrawingAnchors {
+    /// 画像の配置（記録順）
+    pictures: Vec<PictureAnchor>,
+
+    /// グラフの配置（記録順）
+    pub charts: Vec<ChartAnchor>,
+}
+
+/// シートごとの図形として配置された画像を解析
+///
+/// 画像のないシートは含みません。画像は配置位置の行・列の順に並べます。
+/// 画像ファイルが存在しない画像（外部ファイルへのリンクなど）は含みません。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `worksheet_parts` - (ワークシートパーツ, シート名)の一覧
+///
+/// # 戻り値
+///
+/// * `Ok(HashMap<..>)` - シート名 -> 画像（配置位置の順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_drawing_images<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    worksheet_parts: &[(String, String)],
+) -> Result<HashMap<String, Vec<DrawingImage>>, XlsxToMdError> {
+    let mut images = HashMap::new();
+    // 画像ファイルの内容（パーツ名 -> 内容、複数のシートで同じ画像を参照する場合に共有）
+    let mut media: HashMap<String, Arc<[u8]>> = HashMap::new();
+
+    for (file_name, sheet_name) in worksheet_parts {
+        let mut sheet_images = Vec::new();
+        for part in drawing_parts(archive, file_name)? {
+            let Some(xml) = read_part(archive, &part)? else {
+                continue;
+            };
+            let anchors = parse_drawing(&part, &xml)?.pictures;
+            if anchors.is_empty() {
+                continue;
+            }
+
+            let drawing_rels_part = XlsxMetadataParser::rels_path_for_part(&part);
+            let targets = match read_part(archive, &drawing_rels_part)? {
+                Some(xml) => parse_rel_targets(&drawing_rels_part, &xml)?,
+                None => HashMap::new(),
+            };
+            let drawing_base = part.rsplit_once('/').map_or("", |(dir, _)| dir);
+            for anchor in anchors {
+                let Some(target) = targets.get(&anchor.embed) else {
+                    continue;
+                };
+                let media_part = resolve_target(drawing_base, target);
+                let data = match media.get(&media_part) {
+                    Some(data) => Arc::clone(data),
+                    None => match read_part(archive, &media_part)? {
+                        Some(data) => {
+                            let data: Arc<[u8]> = data.into();
+                            media.insert(media_part.clone(), Arc::clone(&data));
+                            data
+                        }
+                        None => continue,
+                    },
+                };
+                sheet_images.push(DrawingImage {
+                    row: anchor.row,
+                    col: anchor.col,
+                    part: media_part,
+                    description: anchor.description,
+                    data,
+                });
+            }
+        }
+
+        if !sheet_images.is_empty() {
+            sheet_images.sort_by_key(|image| (image.row, image.col));
+            images.insert(sheet_name.clone(), sheet_images);
+        }
+    }
+    Ok(images)
+}
+
+/// ワークシートから参照される図形XMLのパーツ名を取得
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `file_name` - ワークシートパーツ（例: `xl/worksheets/sheet1.xml`）
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<String>)` - 図形XMLのパーツ名（リレーションシップの記録順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(super) fn drawing_parts<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    file_name: &str,
+) -> Result<Vec<String>, XlsxToMdError> {
+    let rels_part = XlsxMetadataParser::rels_path_for_part(file_name);
+    let Some(xml) = read_part(archive, &rels_part)? else {
+        return Ok(Vec::new());
+    };
+    let base = file_name.rsplit_once('/').map_or("", |(dir, _)| dir);
+    Ok(parse_rel_types(&rels_part, &xml)?
+        .iter()
+        .filter(|(t, _)| t.ends_with(DRAWING_REL))
+        .map(|(_, target)| resolve_target(base, target))
+        .collect())
+}
+
+/// 図形XMLから画像とグラフの配置を解析（内部ヘルパー）
+///
+/// グループ化された画像・グラフは、グループの配置位置を使用します。
+/// 位置をセルで指定しない配置（`<xdr:absoluteAnchor>`）は、先頭のセル（A1）に配置したものとします。
+pub(super) fn parse_drawing(part: &str, xml: &[u8]) -> Result<DrawingAnchors, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut anchors = DrawingAnchors::default();
+    // 解析中の配置位置（行, 列）
+    let mut position = (0, 0);
+    // 解析中の<xdr:from>内の要素（`b"row"`または`b"col"`）
+    let mut in_from = false;
+    let mut from_target: Option<&'static [u8]> = None;
+    // 解析中の<xdr:pic>
+    let mut picture: Option<PictureAnchor> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"twoCellAnchor" | b"oneCellAnchor" | b"absoluteAnchor" => position = (0, 0),
+                b"from" => in_from = true,
+                b"row" if in_from => from_target = Some(b"row"),
+                b"col" if in_from => from_target = Some(b"col"),
+                b"pic" => picture = Some(PictureAnchor::default()),
+                _ => {}
+            },
+            Ok(Event::Empty(e)) => match (e.local_name().as_ref(), picture.as_mut()) {
+                (b"cNvPr", Some(picture)) => {
+                    picture.description = str_attr(part, &e, b"descr")?
+                        .map(|descr| descr.trim().to_string())
+                        .filter(|descr| !descr.is_empty());
+                }
+                (b"blip", Some(picture)) => {
+                    picture.embed = str_attr(part, &e, b"embed")?.unwrap_or_default();
+                }
+                (b"chart", None) => {
+                    if let Some(id) = str_attr(part, &e, b"id")?.filter(|id| !id.is_empty()) {
+                        anchors.charts.push(ChartAnchor {
+                            row: position.0,
+                            col: position.1,
+                            id,
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) => {
+                if let Some(target) = from_target {
+                    let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                    let value = text.trim().parse().unwrap_or(0);
+                    if target == b"row" {
+                        position.0 = value;
+                    } else {
+                        position.1 = value;
+                    }
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"from" => in_from = false,
+                b"row" | b"col" => from_target = None,
+                b"pic" => {
+                    if let Some(mut picture) = picture.take().filter(|p| !p.embed.is_empty()) {
+                        (picture.row, picture.col) = position;
+                        anchors.pictures.push(picture);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(anchors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_drawing() {
+        let xml = br#"<xdr:wsDr xmlns:xdr="x" xmlns:a="a" xmlns:r="r"><xdr:twoCellAnchor editAs="oneCell"><xdr:from><xdr:col>3</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>1</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:from><xdr:to><xdr:col>5</xdr:col><xdr:colOff>0</xdr:colOff><xdr:row>8</xdr:row><xdr:rowOff>0</xdr:rowOff></xdr:to><xdr:pic><xdr:nvPicPr><xdr:cNvPr id="2" name="Picture 1" descr="Front view"/><xdr:cNvPicPr/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="rId1"/></xdr:blipFill></xdr:pic><xdr:clientData/></xdr:twoCellAnchor><xdr:twoCellAnchor><xdr:from><xdr:col>0</xdr:col><xdr:row>9</xdr:row></xdr:from><xdr:sp><xdr:nvSpPr><xdr:cNvPr id="3" name="Rectangle 2"/></xdr:nvSpPr></xdr:sp><xdr:clientData/></xdr:twoCellAnchor><xdr:absoluteAnchor><xdr:pos x="0" y="0"/><xdr:pic><xdr:nvPicPr><xdr:cNvPr id="4" name="Picture 3" descr=" "/></xdr:nvPicPr><xdr:blipFill><a:blip r:embed="rId2"/></xdr:blipFill></xdr:pic></xdr:absoluteAnchor></xdr:wsDr>"#;
+        let pictures = parse_drawing("xl/drawings/drawing1.xml", xml)
+            .unwrap()
+            .pictures;
+
+        // 画像以外の図形（<xdr:sp>）は含めない
+        assert_eq!(
+            pictures,
+            vec![
+                PictureAnchor {
+                    row: 1,
+                    col: 3,
+                    embed: "rId1".to_string(),
+                    description: Some("Front view".to_string()),
+                },
+                PictureAnchor {
+                    row: 0,
+                    col: 0,
+                    embed: "rId2".to_string(),
+                    description: None,
+                },
+            ]
+        );
+    }
+}
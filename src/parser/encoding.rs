@@ -0,0 +1,198 @@
+//! Encoding Module
+//!
+//! XMLパーツに含まれる不正なUTF-8の修復を提供するモジュール。
+//!
+//! 一部のサードパーティ製ツールは、共有文字列などにWindows-1252（Latin-1）のバイト列を
+//! そのまま書き込むことがあり、UTF-8として解析できずに変換全体が失敗します。
+//! `ConverterBuilder::with_encoding_fallback(true)`の場合は、不正なバイト列を置換文字
+//! （U+FFFD）に置き換えたZIPアーカイブを再構築してから解析します。
+
+use crate::error::{SecurityLimit, XlsxToMdError};
+use crate::security::{inflate_entry, validate_zip_path, SecurityLimits};
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// 不正なUTF-8を置換したワークブック
+#[derive(Debug)]
+pub(crate) struct RepairedWorkbook {
+    /// 再構築したZIPアーカイブ
+    pub buffer: Vec<u8>,
+
+    /// 置換を行ったパーツ名（アーカイブ内の順）
+    pub parts: Vec<String>,
+}
+
+/// XMLパーツの不正なUTF-8を置換文字（U+FFFD）に置き換える
+///
+/// すべてのXMLパーツ（`.xml`、`.rels`）を展開してUTF-8として検証し、
+/// 不正なバイト列を含むパーツのみを置き換えたZIPアーカイブを再構築します。
+/// 他のパーツは再圧縮せずにそのままコピーします。
+/// UTF-16のBOMで始まるパーツは検証の対象外です。
+///
+/// # 引数
+///
+/// * `buffer` - XLSXファイル（ZIPアーカイブ）の内容
+/// * `security_config` - 展開時に適用するセキュリティ設定
+///
+/// # 戻り値
+///
+/// * `Ok(Some(RepairedWorkbook))` - 置換を行った場合、再構築したアーカイブ
+/// * `Ok(None)` - すべてのXMLパーツが正しいUTF-8の場合
+/// * `Err(XlsxToMdError)` - エラーが発生した場合
+pub(crate) fn repair_invalid_utf8(
+    buffer: &[u8],
+    security_config: &SecurityLimits,
+) -> Result<Option<RepairedWorkbook>, XlsxToMdError> {
+    let mut archive = ZipArchive::new(Cursor::new(buffer)).map_err(XlsxToMdError::zip)?;
+
+    // セキュリティチェック: ファイル数の上限
+    if archive.len() > security_config.max_file_count {
+        return Err(SecurityLimit::FileCount {
+            count: archive.len(),
+            max: security_config.max_file_count,
+        }
+        .into());
+    }
+
+    // 1. XMLパーツを検証し、不正なUTF-8を含むパーツの置換結果を保持
+    let mut repaired: Vec<(usize, String, Vec<u8>)> = Vec::new();
+    let mut total_decompressed_size = 0u64;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(XlsxToMdError::zip)?;
+        let file_name = file.name().to_string();
+        if !(file_name.ends_with(".xml") || file_name.ends_with(".rels")) {
+            continue;
+        }
+
+        // パストラバーサル対策
+        validate_zip_path(&file_name)?;
+
+        // 宣言サイズに依存せず、サイズか圧縮率の上限を超えた時点で展開を打ち切る
+        let mut content = Vec::new();
+        let compressed_size = file.compressed_size();
+        inflate_entry(
+            &file_name,
+            compressed_size,
+            &mut file,
+            security_config,
+            &mut content,
+        )?;
+        total_decompressed_size = total_decompressed_size.saturating_add(content.len() as u64);
+        if total_decompressed_size > security_config.max_decompressed_size {
+            return Err(SecurityLimit::DecompressedSize {
+                size: total_decompressed_size,
+                max: security_config.max_decompressed_size,
+            }
+            .into());
+        }
+
+        if is_utf16(&content) || std::str::from_utf8(&content).is_ok() {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&content).into_owned();
+        repaired.push((i, file_name, text.into_bytes()));
+    }
+
+    if repaired.is_empty() {
+        return Ok(None);
+    }
+
+    // 2. 置換したパーツのみを書き直してアーカイブを再構築
+    let mut writer = ZipWriter::new(Cursor::new(Vec::with_capacity(buffer.len())));
+    let mut repaired_iter = repaired.iter().peekable();
+    for i in 0..archive.len() {
+        match repaired_iter.peek() {
+            Some((index, file_name, content)) if *index == i => {
+                writer
+                    .start_file(file_name.as_str(), FileOptions::default())
+                    .map_err(|e| XlsxToMdError::zip_part(file_name, e))?;
+                writer.write_all(content)?;
+                repaired_iter.next();
+            }
+            _ => {
+                let file = archive.by_index_raw(i).map_err(XlsxToMdError::zip)?;
+                writer.raw_copy_file(file).map_err(XlsxToMdError::zip)?;
+            }
+        }
+    }
+    let buffer = writer.finish().map_err(XlsxToMdError::zip)?.into_inner();
+
+    Ok(Some(RepairedWorkbook {
+        buffer,
+        parts: repaired
+            .into_iter()
+            .map(|(_, file_name, _)| file_name)
+            .collect(),
+    }))
+}
+
+/// UTF-16のBOMで始まるかどうか（内部ヘルパー）
+fn is_utf16(content: &[u8]) -> bool {
+    content.starts_with(&[0xFF, 0xFE]) || content.starts_with(&[0xFE, 0xFF])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// 指定したパーツからなるZIPアーカイブを生成
+    fn archive(parts: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn read_part(buffer: &[u8], name: &str) -> Vec<u8> {
+        let mut archive = ZipArchive::new(Cursor::new(buffer)).unwrap();
+        let mut content = Vec::new();
+        archive
+            .by_name(name)
+            .unwrap()
+            .read_to_end(&mut content)
+            .unwrap();
+        content
+    }
+
+    #[test]
+    fn test_repair_invalid_utf8() {
+        let buffer = archive(&[
+            ("xl/workbook.xml", b"<workbook/>"),
+            ("xl/sharedStrings.xml", b"<t>Caf\xE9</t>"),
+            ("xl/media/image1.png", b"\x89PNG\xFF"),
+        ]);
+
+        let repaired = repair_invalid_utf8(&buffer, &SecurityLimits::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(repaired.parts, vec!["xl/sharedStrings.xml"]);
+        assert_eq!(
+            read_part(&repaired.buffer, "xl/sharedStrings.xml"),
+            "<t>Caf\u{FFFD}</t>".as_bytes()
+        );
+        // 他のパーツはそのまま
+        assert_eq!(
+            read_part(&repaired.buffer, "xl/workbook.xml"),
+            b"<workbook/>"
+        );
+        assert_eq!(
+            read_part(&repaired.buffer, "xl/media/image1.png"),
+            b"\x89PNG\xFF"
+        );
+    }
+
+    #[test]
+    fn test_repair_invalid_utf8_valid_input() {
+        let buffer = archive(&[
+            ("xl/sharedStrings.xml", "<t>Café</t>".as_bytes()),
+            ("xl/styles.xml", b"\xFF\xFE<\x00s\x00/\x00>\x00"),
+        ]);
+        assert!(repair_invalid_utf8(&buffer, &SecurityLimits::default())
+            .unwrap()
+            .is_none());
+    }
+}
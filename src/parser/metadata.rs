@@ -7,55 +7,414 @@ use std::collections::{HashMap, HashSet};
 use std::io::{Read, Seek};
 use zip::ZipArchive;
 
+use super::charts::{parse_charts, Chart};
+use super::comments::{parse_comments, CellComment};
+use super::conditional_formats::{parse_conditional_formats, ConditionalFormat};
+use super::data_validations::{parse_data_validations, DataValidation};
+use super::drawings::{parse_drawing_images, DrawingImage};
+use super::pivots::{parse_pivot_tables, PivotTable};
+use super::visuals::{data_bar_ranges, parse_sparklines, SheetVisuals};
 use crate::error::XlsxToMdError;
-use crate::security::{validate_zip_path, SecurityConfig};
-use crate::types::{RichTextFormat, RichTextSegment};
+use crate::report::Revision;
+use crate::security::{check_archive, validate_zip_path, SecurityLimits, XmlReader};
+use crate::types::Hyperlink;
+use crate::types::{
+    split_sheet_prefix, CellCoord, CellRange, RichTextFormat, RichTextSegment, MAX_COL_INDEX,
+};
 
 /// セルスタイル情報（cellXfs要素）
 #[derive(Debug, Clone)]
 pub(crate) struct CellXf {
     pub num_fmt_id: u32,
-    #[allow(dead_code)]
+    /// フォント（fontsのインデックス）
     pub font_id: Option<u32>,
-    #[allow(dead_code)]
+    /// 塗りつぶし（fillsのインデックス）
     pub fill_id: Option<u32>,
     #[allow(dead_code)]
     pub border_id: Option<u32>,
+    /// 名前付きスタイル（cellStyleXfs）のインデックス
+    pub xf_id: Option<u32>,
 }
 
-/// ハイパーリンク情報
-#[derive(Debug, Clone)]
-pub(crate) struct Hyperlink {
-    /// URL
-    pub url: String,
-    /// 表示テキスト（セルの値、またはURLと同じ）
+/// 解析するメタデータの構成要素
+///
+/// `XlsxMetadataParser::with_parts()`で、必要な要素のみを解析するために使用します。
+/// 要素は`|`で組み合わせられます（例: `MetadataParts::SHARED_STRINGS | MetadataParts::STYLES`）。
+/// 1904年エポックの判定（xl/workbook.xml）は小さいため、常に解析します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MetadataParts(u32);
+
+impl MetadataParts {
+    /// 共有文字列のリッチテキスト（xl/sharedStrings.xml と各セルの共有文字列インデックス）
+    pub const SHARED_STRINGS: Self = Self(1 << 0);
+
+    /// セルスタイルと数値書式（xl/styles.xml とワークシートXMLのセルの`s`属性）
+    pub const STYLES: Self = Self(1 << 1);
+
+    /// ハイパーリンク（ワークシートXMLとリレーションシップ）
+    pub const HYPERLINKS: Self = Self(1 << 2);
+
+    /// 非表示行・列（ワークシートXML）
+    pub const HIDDEN: Self = Self(1 << 3);
+
+    /// セルの名前付きスタイル（xl/styles.xml の`<cellStyles>`とワークシートXML）
+    pub const CELL_STYLES: Self = Self(1 << 4);
+
+    /// セル内の画像（ワークシートXMLの`vm`属性とxl/richData/*.xml）
+    pub const CELL_IMAGES: Self = Self(1 << 5);
+
+    /// 動的配列数式の溢れた範囲（ワークシートXMLの`<f t="array" ref="...">`）
+    pub const SPILL_RANGES: Self = Self(1 << 6);
+
+    /// シートの保護（ワークシートXMLの`<sheetProtection sheet="1">`）
+    pub const PROTECTION: Self = Self(1 << 7);
+
+    /// 共有ブックの変更履歴（xl/revisions/*.xml）
+    pub const REVISIONS: Self = Self(1 << 8);
+
+    /// 条件付き書式のルール（ワークシートXMLの`<conditionalFormatting>`）
+    pub const CONDITIONAL_FORMATS: Self = Self(1 << 9);
+
+    /// スパークラインとデータバー（ワークシートXMLの`<x14:sparklineGroups>`と`<conditionalFormatting>`）
+    pub const VISUALS: Self = Self(1 << 10);
+
+    /// セルのコメント（xl/comments*.xml、xl/threadedComments/*.xml、xl/persons/person.xml）
+    pub const COMMENTS: Self = Self(1 << 11);
+
+    /// セル単位のフォントの書式（xl/styles.xmlの`<fonts>`とワークシートXMLのスタイル属性）
+    pub const FONT_STYLES: Self = Self(1 << 13);
+
+    /// セルの背景色（xl/styles.xmlの`<fills>`とワークシートXMLのスタイル属性）
+    pub const FILL_COLORS: Self = Self(1 << 14);
+
+    /// 図形として配置された画像（xl/drawings/*.xmlとxl/media/*）
+    pub const DRAWINGS: Self = Self(1 << 15);
+
+    /// グラフ（xl/drawings/*.xmlから参照されるxl/charts/chart*.xml）
+    pub const CHARTS: Self = Self(1 << 16);
+
+    /// ピボットテーブル（xl/pivotTables/*.xmlとxl/pivotCache/*）
+    pub const PIVOT_TABLES: Self = Self(1 << 17);
+
+    /// 入力規則（ワークシートXMLの`<dataValidations>`と`<x14:dataValidations>`）
+    pub const DATA_VALIDATIONS: Self = Self(1 << 12);
+
+    /// すべての要素
+    pub const ALL: Self = Self(
+        Self::SHARED_STRINGS.0
+            | Self::STYLES.0
+            | Self::HYPERLINKS.0
+            | Self::HIDDEN.0
+            | Self::CELL_STYLES.0
+            | Self::CELL_IMAGES.0
+            | Self::SPILL_RANGES.0
+            | Self::PROTECTION.0
+            | Self::REVISIONS.0
+            | Self::CONDITIONAL_FORMATS.0
+            | Self::VISUALS.0
+            | Self::COMMENTS.0
+            | Self::DATA_VALIDATIONS.0
+            | Self::FONT_STYLES.0
+            | Self::FILL_COLORS.0
+            | Self::DRAWINGS.0
+            | Self::CHARTS.0
+            | Self::PIVOT_TABLES.0,
+    );
+
+    /// 指定した要素をすべて含むかどうか
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// 指定した要素を除いた集合を取得
+    pub fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl std::ops::BitOr for MetadataParts {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// 名前付き範囲（xl/workbook.xml の`<definedName>`要素）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DefinedName {
+    /// 名前（例: "SalesTable"）
+    pub name: String,
+    /// シートレベルの名前の場合、シートのインデックス（`localSheetId`属性、ワークブック内の順序）
+    pub local_sheet_id: Option<usize>,
+    /// 参照先（例: "Sheet1!$B$2:$D$10"）
+    pub refers_to: String,
+}
+
+/// xl/workbook.xml の解析結果（内部用）
+#[derive(Debug, Default)]
+struct WorkbookXml {
+    /// 1904年エポックを使用するか
+    is_1904: bool,
+    /// シート名（ワークブック内の順序）
+    sheet_names: Vec<String>,
+    /// (ワークシートパーツ, シート名)（ワークブック内の順序、パーツに解決できたシートのみ）
+    sheet_parts: Vec<(String, String)>,
+    /// 名前付き範囲（定義順）
+    defined_names: Vec<DefinedName>,
+}
+
+/// xl/styles.xml の解析結果（内部用）
+#[derive(Debug, Default)]
+struct StylesXml {
+    /// カスタム書式ID（164以上）-> Number Format String
+    num_formats: HashMap<u32, String>,
+    /// スタイルID（cellXfsのインデックス）-> スタイル
+    cell_xfs: Vec<CellXf>,
+    /// 名前付きスタイルのxfId -> スタイル名（既定のスタイルを除く）
+    cell_style_names: HashMap<u32, String>,
+    /// フォントの書式（fontsの順序）
+    fonts: Vec<RichTextFormat>,
+    /// 塗りつぶしの色（fillsの順序、RGBの16進数6桁、色のない塗りつぶしは`None`）
+    fills: Vec<Option<String>>,
+}
+
+/// 書式付きの共有文字列（内部用）
+///
+/// 共有文字列インデックスの昇順に並べた連続した配列で、太字・斜体などの書式を含む文字列のみを
+/// 保持します（通常のテキストはcalamineが読み込んだ値をそのまま使用するため保持しない）。
+/// 巨大な共有文字列テーブルでも、インデックスごとのハッシュテーブルの項目を確保しません。
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RichSharedStrings {
+    /// 共有文字列インデックス（昇順）
+    indices: Vec<u32>,
+    /// 各文字列のセグメント（`indices`と同じ順序）
+    segments: Vec<Box<[RichTextSegment]>>,
+}
+
+impl RichSharedStrings {
+    /// 書式付きの文字列を追加（インデックスは昇順に追加する）
+    fn push(&mut self, index: u32, segments: Vec<RichTextSegment>) {
+        debug_assert!(self.indices.last().is_none_or(|&last| last < index));
+        self.indices.push(index);
+        self.segments.push(segments.into_boxed_slice());
+    }
+
+    /// 共有文字列インデックスから、文字列の位置（`get()`の引数）を取得
+    ///
+    /// 書式のない文字列（保持していない文字列）の場合は`None`を返します。
+    fn position(&self, index: u32) -> Option<u32> {
+        self.indices
+            .binary_search(&index)
+            .ok()
+            .and_then(|pos| u32::try_from(pos).ok())
+    }
+
+    /// 位置から文字列のセグメントを取得
+    fn get(&self, position: u32) -> Option<&[RichTextSegment]> {
+        self.segments
+            .get(position as usize)
+            .map(|segments| &**segments)
+    }
+
+    /// 保持している文字列の数
     #[allow(dead_code)]
-    pub display: Option<String>,
+    pub(crate) fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// 書式付きの文字列がないか
+    pub(crate) fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// 書式付きの共有文字列を参照するセル（セル座標, `RichSharedStrings`内の位置）のリスト（内部用）
+type RichTextCells = Vec<((u32, u32), u32)>;
+
+/// ワークシートXMLの解析結果（内部用）
+#[derive(Debug, Default)]
+struct WorksheetScan {
+    /// 非表示行インデックスのセット
+    hidden_rows: HashSet<u32>,
+    /// 非表示列インデックスのセット
+    hidden_cols: HashSet<u32>,
+    /// 高さが0の行インデックスのセット（非表示の行を除く）
+    zero_size_rows: HashSet<u32>,
+    /// 幅が0の列インデックスのセット（非表示の列を除く）
+    zero_size_cols: HashSet<u32>,
+    /// 書式付きの共有文字列を参照するセル（セル座標, `RichSharedStrings`内の位置）
+    rich_text_cells: RichTextCells,
+    /// セル座標 -> スタイルID（名前付きスタイルまたは数値書式を持つセルのみ）
+    cell_styles: HashMap<(u32, u32), u32>,
+    /// セル座標 -> 値メタデータのインデックス（セル内の画像のみ）
+    cell_images: HashMap<(u32, u32), u32>,
+    /// 動的配列数式の溢れた範囲（起点のセルを含む）
+    spill_ranges: Vec<CellRange>,
+    /// シートが保護されているか
+    protected: bool,
+    /// 条件付き書式のルール（記録順）
+    conditional_formats: Vec<ConditionalFormat>,
+    /// スパークラインとデータバー
+    visuals: SheetVisuals,
+    /// 入力規則（記録順）
+    data_validations: Vec<DataValidation>,
+    /// セル座標 -> ハイパーリンク
+    hyperlinks: HashMap<(u32, u32), Hyperlink>,
+}
+
+/// 全ワークシートの解析結果（シート名ごと、内部用）
+///
+/// 該当する情報がないシートは含みません。
+#[derive(Debug, Default)]
+struct WorksheetScans {
+    hidden_rows: HashMap<String, HashSet<u32>>,
+    hidden_cols: HashMap<String, HashSet<u32>>,
+    zero_size_rows: HashMap<String, HashSet<u32>>,
+    zero_size_cols: HashMap<String, HashSet<u32>>,
+    rich_text_cells: HashMap<String, RichTextCells>,
+    cell_styles: HashMap<String, HashMap<(u32, u32), u32>>,
+    cell_images: HashMap<String, HashMap<(u32, u32), u32>>,
+    spill_ranges: HashMap<String, Vec<CellRange>>,
+    protected_sheets: HashSet<String>,
+    conditional_formats: HashMap<String, Vec<ConditionalFormat>>,
+    visuals: HashMap<String, SheetVisuals>,
+    data_validations: HashMap<String, Vec<DataValidation>>,
+    hyperlinks: HashMap<String, HashMap<(u32, u32), Hyperlink>>,
+}
+
+impl WorksheetScans {
+    /// シートの解析結果を追加（内部ヘルパー）
+    fn insert(&mut self, sheet_name: String, scan: WorksheetScan) {
+        if !scan.hidden_rows.is_empty() {
+            self.hidden_rows
+                .insert(sheet_name.clone(), scan.hidden_rows);
+        }
+        if !scan.hidden_cols.is_empty() {
+            self.hidden_cols
+                .insert(sheet_name.clone(), scan.hidden_cols);
+        }
+        if !scan.zero_size_rows.is_empty() {
+            self.zero_size_rows
+                .insert(sheet_name.clone(), scan.zero_size_rows);
+        }
+        if !scan.zero_size_cols.is_empty() {
+            self.zero_size_cols
+                .insert(sheet_name.clone(), scan.zero_size_cols);
+        }
+        if !scan.rich_text_cells.is_empty() {
+            // セル座標の順に並べ、二分探索で参照する（同じセルが重複する場合は後の記録を優先）
+            let mut cells = scan.rich_text_cells;
+            cells.sort_by_key(|&(coord, _)| coord);
+            cells.dedup_by(|later, earlier| {
+                let duplicate = later.0 == earlier.0;
+                if duplicate {
+                    earlier.1 = later.1;
+                }
+                duplicate
+            });
+            self.rich_text_cells.insert(sheet_name.clone(), cells);
+        }
+        if !scan.cell_styles.is_empty() {
+            self.cell_styles
+                .insert(sheet_name.clone(), scan.cell_styles);
+        }
+        if !scan.cell_images.is_empty() {
+            self.cell_images
+                .insert(sheet_name.clone(), scan.cell_images);
+        }
+        if !scan.spill_ranges.is_empty() {
+            self.spill_ranges
+                .insert(sheet_name.clone(), scan.spill_ranges);
+        }
+        if !scan.conditional_formats.is_empty() {
+            self.conditional_formats
+                .insert(sheet_name.clone(), scan.conditional_formats);
+        }
+        if !scan.visuals.is_empty() {
+            self.visuals.insert(sheet_name.clone(), scan.visuals);
+        }
+        if !scan.data_validations.is_empty() {
+            self.data_validations
+                .insert(sheet_name.clone(), scan.data_validations);
+        }
+        if !scan.hyperlinks.is_empty() {
+            self.hyperlinks.insert(sheet_name.clone(), scan.hyperlinks);
+        }
+        if scan.protected {
+            self.protected_sheets.insert(sheet_name);
+        }
+    }
 }
 
 /// XLSXメタデータパーサー
 ///
 /// XLSXファイル（ZIPアーカイブ）からXMLを直接解析し、
 /// calamineで取得できない情報を抽出します。
-#[derive(Clone)]
+/// 旧形式（XLS）のワークブックでは、空のメタデータ（`Default`）を使用します。
+#[derive(Clone, Default)]
 pub(crate) struct XlsxMetadataParser {
     /// numFmtId -> formatCode のマッピング
     num_formats: HashMap<u32, String>,
     /// styleId -> CellXf のマッピング
     cell_xfs: Vec<CellXf>,
+    /// フォントの書式（fontsの順序、`FONT_STYLES`を解析しなかった場合は空）
+    fonts: Vec<RichTextFormat>,
+    /// 塗りつぶしの色（fillsの順序、`FILL_COLORS`を解析しなかった場合は空）
+    fills: Vec<Option<String>>,
     /// シート名 -> 非表示行インデックスのセット
     pub(crate) hidden_rows: HashMap<String, HashSet<u32>>,
     /// シート名 -> 非表示列インデックスのセット
     pub(crate) hidden_cols: HashMap<String, HashSet<u32>>,
+    /// シート名 -> 高さが0の行インデックスのセット（非表示の行を除く）
+    pub(crate) zero_size_rows: HashMap<String, HashSet<u32>>,
+    /// シート名 -> 幅が0の列インデックスのセット（非表示の列を除く）
+    pub(crate) zero_size_cols: HashMap<String, HashSet<u32>>,
     /// シート名 -> セル座標 -> ハイパーリンク情報のマッピング
     pub(crate) hyperlinks: HashMap<String, HashMap<(u32, u32), Hyperlink>>,
     /// 1904年エポックを使用するかどうか
     is_1904: bool,
-    /// 共有文字列インデックス -> リッチテキストセグメントのマッピング
-    /// 通常のテキストの場合は、1つのプレーンテキストセグメントを含む
-    pub(crate) shared_strings: HashMap<u32, Vec<RichTextSegment>>,
-    /// シート名 -> セル座標 -> 共有文字列インデックスのマッピング
-    pub(crate) cell_string_indices: HashMap<String, HashMap<(u32, u32), u32>>,
+    /// 書式付きの共有文字列
+    /// 太字・斜体の書式を含む文字列のみを保持する（通常のテキストはcalamineの値を使用する）
+    pub(crate) shared_strings: RichSharedStrings,
+    /// シート名 -> 書式付きの共有文字列を参照するセル（セル座標の昇順、`shared_strings`内の位置）
+    /// ワークシートの走査時に共有文字列インデックスを解決し、該当するセルのみを保持する
+    rich_text_cells: HashMap<String, RichTextCells>,
+    /// xfId（cellStyleXfsのインデックス） -> 名前付きスタイル名のマッピング
+    /// 既定のスタイル（"Normal"）は含まない
+    cell_style_names: HashMap<u32, String>,
+    /// シート名 -> セル座標 -> スタイルIDのマッピング
+    /// 名前付きスタイルまたは数値書式（General以外）を持つセルのみを保持する
+    cell_styles: HashMap<String, HashMap<(u32, u32), u32>>,
+    /// シート名 -> セル座標 -> 値メタデータのインデックスのマッピング
+    /// セル内の画像（`vm`属性を持つセル）のみを保持する
+    cell_images: HashMap<String, HashMap<(u32, u32), u32>>,
+    /// 値メタデータのインデックス -> 画像ファイルのパーツ名のマッピング
+    cell_image_targets: HashMap<u32, String>,
+    /// シート名 -> 動的配列数式の溢れた範囲（起点のセルを含む）のマッピング
+    spill_ranges: HashMap<String, Vec<CellRange>>,
+    /// 保護されたシート名のセット
+    protected_sheets: HashSet<String>,
+    /// シート名 -> 条件付き書式のルール（記録順）のマッピング
+    conditional_formats: HashMap<String, Vec<ConditionalFormat>>,
+    /// シート名 -> スパークラインとデータバーのマッピング
+    visuals: HashMap<String, SheetVisuals>,
+    /// シート名 -> 入力規則（記録順）のマッピング
+    data_validations: HashMap<String, Vec<DataValidation>>,
+    /// シート名 -> セル座標 -> コメント（スレッドの場合は返信を含む記録順）のマッピング
+    comments: HashMap<String, HashMap<(u32, u32), Vec<CellComment>>>,
+    /// シート名 -> 図形として配置された画像（配置位置の順）のマッピング
+    drawing_images: HashMap<String, Vec<DrawingImage>>,
+    /// シート名 -> グラフ（配置位置の順）のマッピング
+    charts: HashMap<String, Vec<Chart>>,
+    /// シート名 -> ピボットテーブル（配置範囲の順）のマッピング
+    pivot_tables: HashMap<String, Vec<PivotTable>>,
+    /// 共有ブックの変更履歴（記録順）
+    revisions: Vec<Revision>,
+    /// シート名（ワークブック内の順序、xl/workbook.xml の`<sheets>`）
+    sheet_names: Vec<String>,
+    /// 名前付き範囲（定義順）
+    defined_names: Vec<DefinedName>,
 }
 
 impl XlsxMetadataParser {
@@ -69,85 +428,255 @@ impl XlsxMetadataParser {
     ///
     /// * `Ok(XlsxMetadataParser)` - メタデータの解析に成功した場合
     /// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+    #[allow(dead_code)]
     pub fn new<R: Read + Seek>(xlsx_reader: R) -> Result<Self, XlsxToMdError> {
-        let security_config = SecurityConfig::default();
-
-        let mut archive =
-            ZipArchive::new(xlsx_reader).map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?;
-
-        // セキュリティチェック: ファイル数の上限
-        if archive.len() > security_config.max_file_count {
-            return Err(XlsxToMdError::SecurityViolation(format!(
-                "ZIP archive contains too many files: {} (max: {})",
-                archive.len(),
-                security_config.max_file_count
-            )));
-        }
+        Self::with_parts(xlsx_reader, MetadataParts::ALL, &SecurityLimits::default())
+    }
 
-        // セキュリティチェック: 各ファイルのパス検証とサイズチェック
-        let mut total_decompressed_size = 0u64;
-        for i in 0..archive.len() {
-            let file = archive
-                .by_index(i)
-                .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?;
-
-            // パストラバーサル対策
-            let file_name = file.name();
-            validate_zip_path(file_name).map_err(|e| {
-                XlsxToMdError::SecurityViolation(format!("Invalid ZIP path: {}", e))
-            })?;
-
-            // ファイルサイズチェック
-            let file_size = file.size();
-            if file_size > security_config.max_file_size {
-                return Err(XlsxToMdError::SecurityViolation(format!(
-                    "File '{}' exceeds maximum size: {} bytes (max: {} bytes)",
-                    file_name, file_size, security_config.max_file_size
-                )));
-            }
+    /// XLSXファイル（ZIPアーカイブ）から指定した要素のメタデータのみを解析
+    ///
+    /// 解析しなかった要素は空として扱われます（例: `HIDDEN`を含めない場合、すべての行・列が表示扱い）。
+    /// 巨大なxl/sharedStrings.xmlなど、変換設定で不要な要素の解析時間を省略するために使用します。
+    ///
+    /// # 引数
+    ///
+    /// * `xlsx_reader` - XLSXファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `parts` - 解析する要素
+    /// * `security_config` - ZIPアーカイブに適用するセキュリティ制限
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(XlsxMetadataParser)` - メタデータの解析に成功した場合
+    /// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+    pub fn with_parts<R: Read + Seek>(
+        xlsx_reader: R,
+        parts: MetadataParts,
+        security_config: &SecurityLimits,
+    ) -> Result<Self, XlsxToMdError> {
+        let mut archive = ZipArchive::new(xlsx_reader).map_err(XlsxToMdError::zip)?;
 
-            // 展開後のサイズ累計をチェック
-            total_decompressed_size =
-                total_decompressed_size
-                    .checked_add(file_size)
-                    .ok_or_else(|| {
-                        XlsxToMdError::SecurityViolation(
-                            "Total decompressed size calculation overflow".to_string(),
-                        )
-                    })?;
-
-            if total_decompressed_size > security_config.max_decompressed_size {
-                return Err(XlsxToMdError::SecurityViolation(format!(
-                    "Total decompressed size exceeds maximum: {} bytes (max: {} bytes)",
-                    total_decompressed_size, security_config.max_decompressed_size
-                )));
-            }
+        // セキュリティチェック: ファイル数・パス・実際に展開したサイズと圧縮率
+        check_archive(&mut archive, security_config)?;
+
+        Self::from_checked_archive(archive, parts)
+    }
+
+    /// セキュリティ制限の検証済みのZIPアーカイブから指定した要素のメタデータのみを解析
+    ///
+    /// `with_parts()`と同じ解析を行いますが、アーカイブの検証（`check_archive()`）は呼び出し元で
+    /// 済ませているものとして省略します。展開を伴う検証を重複して行わないために使用します。
+    ///
+    /// # 引数
+    ///
+    /// * `archive` - 検証済みのZIPアーカイブ
+    /// * `parts` - 解析する要素
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(XlsxMetadataParser)` - メタデータの解析に成功した場合
+    /// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+    pub fn from_checked_archive<R: Read + Seek>(
+        mut archive: ZipArchive<R>,
+        parts: MetadataParts,
+    ) -> Result<Self, XlsxToMdError> {
+        // 1. xl/workbook.xml を解析（1904年エポック、シート名とワークシートパーツの対応）
+        let WorkbookXml {
+            is_1904,
+            sheet_names,
+            sheet_parts,
+            defined_names,
+        } = Self::parse_workbook(&mut archive)?;
+
+        // 2. xl/styles.xml を解析
+        let StylesXml {
+            num_formats,
+            cell_xfs,
+            cell_style_names,
+            fonts,
+            fills,
+        } = if parts.contains(MetadataParts::STYLES)
+            || parts.contains(MetadataParts::CELL_STYLES)
+            || parts.contains(MetadataParts::FONT_STYLES)
+            || parts.contains(MetadataParts::FILL_COLORS)
+        {
+            Self::parse_styles(&mut archive)?
+        } else {
+            StylesXml::default()
+        };
+        // フォントの書式は、セル単位の書式を出力する場合のみ保持する
+        let fonts = if parts.contains(MetadataParts::FONT_STYLES) {
+            fonts
+        } else {
+            Vec::new()
+        };
+        // 塗りつぶしの色は、セルの背景色を出力する場合のみ保持する
+        let fills = if parts.contains(MetadataParts::FILL_COLORS) {
+            fills
+        } else {
+            Vec::new()
+        };
+
+        // 3. xl/sharedStrings.xml を解析
+        let shared_strings = if parts.contains(MetadataParts::SHARED_STRINGS) {
+            Self::parse_shared_strings(&mut archive)?
+        } else {
+            RichSharedStrings::default()
+        };
+
+        // 4. xl/worksheets/*.xml を解析（非表示行・列、共有文字列インデックス、セルのスタイル、セル内の画像、
+        //    ハイパーリンクなど、ワークシートXMLから取得する情報はシートごとに1回の読み込みでまとめて収集する）
+        // 書式付きの共有文字列がない場合は、セルの共有文字列インデックスを収集しない
+        let mut worksheet_parts = if shared_strings.is_empty() {
+            parts.without(MetadataParts::SHARED_STRINGS)
+        } else {
+            parts
+        };
+        // 名前付きスタイルを参照するスタイルID、数値書式（General以外）を持つスタイルID、
+        // 書式（太字・斜体・取り消し線）のあるフォント、または色のある塗りつぶしを参照するスタイルIDのみを
+        // 収集対象とする
+        let styled_xfs: HashSet<u32> = cell_xfs
+            .iter()
+            .enumerate()
+            .filter(|(_, xf)| {
+                (parts.contains(MetadataParts::CELL_STYLES)
+                    && xf
+                        .xf_id
+                        .is_some_and(|xf_id| cell_style_names.contains_key(&xf_id)))
+                    || (parts.contains(MetadataParts::STYLES) && xf.num_fmt_id != 0)
+                    || xf
+                        .font_id
+                        .and_then(|font_id| fonts.get(font_id as usize))
+                        .is_some_and(|font| !font.is_plain())
+                    || xf
+                        .fill_id
+                        .and_then(|fill_id| fills.get(fill_id as usize))
+                        .is_some_and(Option::is_some)
+            })
+            .filter_map(|(idx, _)| u32::try_from(idx).ok())
+            .collect();
+        if styled_xfs.is_empty() {
+            worksheet_parts = worksheet_parts.without(
+                MetadataParts::CELL_STYLES
+                    | MetadataParts::STYLES
+                    | MetadataParts::FONT_STYLES
+                    | MetadataParts::FILL_COLORS,
+            );
         }
+        let scans = if worksheet_parts.contains(MetadataParts::HIDDEN)
+            || worksheet_parts.contains(MetadataParts::SHARED_STRINGS)
+            || worksheet_parts.contains(MetadataParts::STYLES)
+            || worksheet_parts.contains(MetadataParts::CELL_STYLES)
+            || worksheet_parts.contains(MetadataParts::FONT_STYLES)
+            || worksheet_parts.contains(MetadataParts::FILL_COLORS)
+            || worksheet_parts.contains(MetadataParts::CELL_IMAGES)
+            || worksheet_parts.contains(MetadataParts::SPILL_RANGES)
+            || worksheet_parts.contains(MetadataParts::PROTECTION)
+            || worksheet_parts.contains(MetadataParts::CONDITIONAL_FORMATS)
+            || worksheet_parts.contains(MetadataParts::VISUALS)
+            || worksheet_parts.contains(MetadataParts::DATA_VALIDATIONS)
+            || worksheet_parts.contains(MetadataParts::HYPERLINKS)
+        {
+            Self::parse_worksheets(
+                &mut archive,
+                &sheet_parts,
+                worksheet_parts,
+                &shared_strings,
+                &styled_xfs,
+            )?
+        } else {
+            WorksheetScans::default()
+        };
+        let WorksheetScans {
+            hidden_rows,
+            hidden_cols,
+            zero_size_rows,
+            zero_size_cols,
+            rich_text_cells,
+            cell_styles,
+            cell_images,
+            spill_ranges,
+            protected_sheets,
+            conditional_formats,
+            visuals,
+            data_validations,
+            hyperlinks,
+        } = scans;
 
-        // 1. xl/styles.xml を解析
-        let (num_formats, cell_xfs) = Self::parse_styles(&mut archive)?;
+        // セル内の画像がある場合のみ、xl/richData/*.xml から画像ファイルを解決する
+        let cell_image_targets = if cell_images.is_empty() {
+            HashMap::new()
+        } else {
+            super::rich_data::parse_cell_image_targets(&mut archive)?
+        };
 
-        // 2. xl/sharedStrings.xml を解析
-        let shared_strings = Self::parse_shared_strings(&mut archive)?;
+        // 5. xl/revisions/*.xml を解析
+        let revisions = if parts.contains(MetadataParts::REVISIONS) {
+            super::revisions::parse_revisions(&mut archive)?
+        } else {
+            Vec::new()
+        };
 
-        // 3. xl/worksheets/*.xml を解析
-        let (hidden_rows, hidden_cols, cell_string_indices) = Self::parse_worksheets(&mut archive)?;
+        // 6. セルのコメントを解析
+        let comments = if parts.contains(MetadataParts::COMMENTS) {
+            let worksheets = Self::worksheet_parts(&mut archive, &sheet_parts)?;
+            parse_comments(&mut archive, &worksheets)?
+        } else {
+            HashMap::new()
+        };
 
-        // 4. ハイパーリンク情報を解析
-        let hyperlinks = Self::parse_hyperlinks(&mut archive)?;
+        // 7. 図形として配置された画像を解析
+        let drawing_images = if parts.contains(MetadataParts::DRAWINGS) {
+            let worksheets = Self::worksheet_parts(&mut archive, &sheet_parts)?;
+            parse_drawing_images(&mut archive, &worksheets)?
+        } else {
+            HashMap::new()
+        };
+
+        // 8. グラフを解析
+        let charts = if parts.contains(MetadataParts::CHARTS) {
+            let worksheets = Self::worksheet_parts(&mut archive, &sheet_parts)?;
+            parse_charts(&mut archive, &worksheets)?
+        } else {
+            HashMap::new()
+        };
 
-        // 5. xl/workbook.xml を解析
-        let is_1904 = Self::parse_workbook(&mut archive)?;
+        // 9. ピボットテーブルを解析
+        let pivot_tables = if parts.contains(MetadataParts::PIVOT_TABLES) {
+            let worksheets = Self::worksheet_parts(&mut archive, &sheet_parts)?;
+            parse_pivot_tables(&mut archive, &worksheets)?
+        } else {
+            HashMap::new()
+        };
 
         Ok(Self {
             num_formats,
             cell_xfs,
+            fonts,
+            fills,
             hidden_rows,
             hidden_cols,
+            zero_size_rows,
+            zero_size_cols,
             hyperlinks,
             is_1904,
             shared_strings,
-            cell_string_indices,
+            rich_text_cells,
+            cell_style_names,
+            cell_styles,
+            cell_images,
+            cell_image_targets,
+            spill_ranges,
+            protected_sheets,
+            conditional_formats,
+            visuals,
+            data_validations,
+            comments,
+            drawing_images,
+            charts,
+            pivot_tables,
+            revisions,
+            sheet_names,
+            defined_names,
         })
     }
 
@@ -173,6 +702,314 @@ impl XlsxMetadataParser {
         })
     }
 
+    /// styleIdから数値書式ID（numFmtId）を取得
+    ///
+    /// # 引数
+    ///
+    /// * `style_id` - スタイルID（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(u16)` - 数値書式ID（組み込み書式は0-163、カスタム書式は164以上）
+    /// * `None` - スタイルIDが範囲外、または数値書式IDが`u16`の範囲外の場合
+    pub fn get_num_fmt_id(&self, style_id: u32) -> Option<u16> {
+        self.cell_xfs
+            .get(style_id as usize)
+            .and_then(|xf| u16::try_from(xf.num_fmt_id).ok())
+    }
+
+    /// シートが保護されているかを判定
+    ///
+    /// シートの保護（`<sheetProtection>`）は編集を制限するだけで、データは暗号化されません。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// シートが保護されている場合は`true`（`PROTECTION`を解析しなかった場合は常に`false`）
+    pub fn is_sheet_protected(&self, sheet_name: &str) -> bool {
+        self.protected_sheets.contains(sheet_name)
+    }
+
+    /// シートの条件付き書式のルールを取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// 条件付き書式のルール（記録順、`CONDITIONAL_FORMATS`を解析しなかった場合は空）
+    pub fn conditional_formats(&self, sheet_name: &str) -> &[ConditionalFormat] {
+        self.conditional_formats
+            .get(sheet_name)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// シートのスパークラインとデータバーを取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// スパークラインとデータバー（ない場合、`VISUALS`を解析しなかった場合は`None`）
+    pub fn visuals(&self, sheet_name: &str) -> Option<&SheetVisuals> {
+        self.visuals.get(sheet_name)
+    }
+
+    /// シートの入力規則を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// 入力規則（記録順、`DATA_VALIDATIONS`を解析しなかった場合は空）
+    pub fn data_validations(&self, sheet_name: &str) -> &[DataValidation] {
+        self.data_validations
+            .get(sheet_name)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// シートのセルのコメントを取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// セル座標（シートの絶対座標）からコメントへの対応（ない場合、`COMMENTS`を解析しなかった場合は`None`）
+    pub fn comments(&self, sheet_name: &str) -> Option<&HashMap<(u32, u32), Vec<CellComment>>> {
+        self.comments.get(sheet_name)
+    }
+
+    /// シートに図形として配置された画像を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// 画像（配置位置の行・列の順、ない場合、`DRAWINGS`を解析しなかった場合は空）
+    pub fn drawing_images(&self, sheet_name: &str) -> &[DrawingImage] {
+        self.drawing_images
+            .get(sheet_name)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// シートに配置されたグラフを取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// グラフ（配置位置の行・列の順、ない場合、`CHARTS`を解析しなかった場合は空）
+    pub fn charts(&self, sheet_name: &str) -> &[Chart] {
+        self.charts.get(sheet_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// シートに配置されたピボットテーブルを取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// ピボットテーブル（配置範囲の行・列の順、ない場合、`PIVOT_TABLES`を解析しなかった場合は空）
+    pub fn pivot_tables(&self, sheet_name: &str) -> &[PivotTable] {
+        self.pivot_tables.get(sheet_name).map_or(&[], Vec::as_slice)
+    }
+
+    /// 共有ブックの変更履歴を取得
+    ///
+    /// # 戻り値
+    ///
+    /// 変更履歴（記録順、`REVISIONS`を解析しなかった場合は空）
+    pub fn revisions(&self) -> &[Revision] {
+        &self.revisions
+    }
+
+    /// 名前付き範囲の参照先のシート名とセル範囲を取得
+    ///
+    /// 名前は大文字・小文字を区別せずに照合し、ブックレベルの名前をシートレベルの名前より優先します。
+    /// シートレベルの名前は`Sheet1!SalesTable`のようにシート名で修飾して指定することもできます。
+    ///
+    /// # 引数
+    ///
+    /// * `name` - 名前（例: "SalesTable"）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some((&str, CellRange))` - 参照先のシート名と範囲（シートの絶対座標）
+    /// * `None` - 名前が見つからない場合、または参照先が単一のセル範囲でない場合
+    ///   （複数の範囲、数式、定数、`#REF!`など）
+    pub fn defined_name_range(&self, name: &str) -> Option<(&str, CellRange)> {
+        let (scope, name) = match split_sheet_prefix(name) {
+            Some((sheet, name)) => (
+                Some(self.sheet_names.iter().position(|s| *s == sheet)?),
+                name,
+            ),
+            None => (None, name),
+        };
+        let defined = self
+            .defined_names
+            .iter()
+            .filter(|defined| defined.name.eq_ignore_ascii_case(name))
+            .filter(|defined| scope.is_none() || defined.local_sheet_id == scope)
+            .min_by_key(|defined| defined.local_sheet_id.is_some())?;
+
+        let (sheet, area) = split_sheet_prefix(&defined.refers_to)?;
+        let sheet = self.sheet_names.iter().find(|s| **s == sheet)?;
+        Some((sheet.as_str(), area.parse().ok()?))
+    }
+
+    /// セルの名前付きスタイル名を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - セルに名前付きスタイル（例: "Heading 1"）が適用されている場合
+    /// * `None` - 既定のスタイル（"Normal"）の場合、または情報が取得できない場合
+    pub fn get_cell_style_name(&self, sheet_name: &str, row: u32, col: u32) -> Option<&str> {
+        let style_id = self.cell_styles.get(sheet_name)?.get(&(row, col))?;
+        let xf_id = self.cell_xfs.get(*style_id as usize)?.xf_id?;
+        self.cell_style_names.get(&xf_id).map(|s| s.as_str())
+    }
+
+    /// セル単位のフォントの書式を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&RichTextFormat)` - セルのフォントに太字・斜体・取り消し線のいずれかが設定されている場合
+    /// * `None` - 書式のないフォントの場合、または`FONT_STYLES`を解析しなかった場合
+    pub fn get_cell_font(&self, sheet_name: &str, row: u32, col: u32) -> Option<&RichTextFormat> {
+        let style_id = self.get_cell_style_id(sheet_name, row, col)?;
+        let font_id = self.cell_xfs.get(style_id as usize)?.font_id?;
+        self.fonts
+            .get(font_id as usize)
+            .filter(|font| !font.is_plain())
+    }
+
+    /// セルの背景色（塗りつぶしの色）を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&str)` - RGBの16進数6桁（例: `"FFFF00"`）
+    /// * `None` - 塗りつぶしのない場合、色がRGBで指定されていない場合（テーマの色など）、
+    ///   または`FILL_COLORS`を解析しなかった場合
+    pub fn get_cell_fill_color(&self, sheet_name: &str, row: u32, col: u32) -> Option<&str> {
+        let style_id = self.get_cell_style_id(sheet_name, row, col)?;
+        let fill_id = self.cell_xfs.get(style_id as usize)?.fill_id?;
+        self.fills.get(fill_id as usize)?.as_deref()
+    }
+
+    /// セルのリッチテキスト（書式付きの共有文字列）を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&[RichTextSegment])` - セルが書式付きの共有文字列を参照している場合
+    /// * `None` - 通常のテキストの場合、または`SHARED_STRINGS`を解析しなかった場合
+    pub fn get_cell_rich_text(
+        &self,
+        sheet_name: &str,
+        row: u32,
+        col: u32,
+    ) -> Option<&[RichTextSegment]> {
+        let cells = self.rich_text_cells.get(sheet_name)?;
+        let pos = cells
+            .binary_search_by_key(&(row, col), |&(coord, _)| coord)
+            .ok()?;
+        self.shared_strings.get(cells[pos].1)
+    }
+
+    /// セルのスタイルID（cellXfsのインデックス）を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(u32)` - セルに名前付きスタイルまたは数値書式（General以外）が適用されている場合
+    /// * `None` - 既定のスタイルの場合、または情報が取得できない場合
+    pub fn get_cell_style_id(&self, sheet_name: &str, row: u32, col: u32) -> Option<u32> {
+        self.cell_styles.get(sheet_name)?.get(&(row, col)).copied()
+    }
+
+    /// セル内の画像を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(Some(&str))` - セル内の画像で、画像ファイル（例: `xl/media/image1.png`）に解決できた場合
+    /// * `Some(None)` - セル内の画像だが、画像ファイルに解決できない場合（Web上の画像など）
+    /// * `None` - セル内の画像でない場合
+    pub fn get_cell_image(&self, sheet_name: &str, row: u32, col: u32) -> Option<Option<&str>> {
+        let vm = self.cell_images.get(sheet_name)?.get(&(row, col))?;
+        Some(self.cell_image_targets.get(vm).map(String::as_str))
+    }
+
+    /// 動的配列数式の溢れた範囲にあるセルの起点を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `row` - 行インデックス（0始まり）
+    /// * `col` - 列インデックス（0始まり）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some((row, col))` - セルが溢れた範囲にある場合、数式を持つ起点のセルの座標
+    /// * `None` - 溢れた範囲にない場合、またはセルが起点の場合
+    pub fn get_spill_anchor(&self, sheet_name: &str, row: u32, col: u32) -> Option<(u32, u32)> {
+        let coord = CellCoord::new(row, col);
+        self.spill_ranges
+            .get(sheet_name)?
+            .iter()
+            .find(|range| range.start != coord && range.contains(coord))
+            .map(|range| (range.start.row, range.start.col))
+    }
+
     /// 行が非表示かどうかを判定
     ///
     /// # 引数
@@ -224,13 +1061,17 @@ impl XlsxMetadataParser {
     /// xl/sharedStrings.xml の解析（プライベート）
     ///
     /// `<sst>` 要素を解析し、リッチテキスト情報を抽出します。
+    /// 通常のテキストはcalamineが読み込んだ値と同じため、太字・斜体の書式を含む
+    /// 文字列のみをマッピングに追加します。`<r>`要素（書式付きの実行）が1つもない場合は
+    /// XMLイベントの解析自体を省略します。
     fn parse_shared_strings<R: Read + Seek>(
         archive: &mut ZipArchive<R>,
-    ) -> Result<HashMap<u32, Vec<RichTextSegment>>, XlsxToMdError> {
-        let mut shared_strings = HashMap::new();
+    ) -> Result<RichSharedStrings, XlsxToMdError> {
+        let mut shared_strings = RichSharedStrings::default();
 
         // xl/sharedStrings.xml を開く
-        let mut shared_strings_file = match archive.by_name("xl/sharedStrings.xml") {
+        let part = "xl/sharedStrings.xml";
+        let mut shared_strings_file = match archive.by_name(part) {
             Ok(file) => file,
             Err(_) => {
                 // sharedStrings.xmlが存在しない場合は空の結果を返す
@@ -243,13 +1084,17 @@ impl XlsxMetadataParser {
         let mut xml_content = Vec::new();
         shared_strings_file.read_to_end(&mut xml_content)?;
 
+        // 事前走査: 書式付きの実行がなければ、すべて通常のテキスト
+        if memchr::memmem::find(&xml_content, b"<r>").is_none() {
+            return Ok(shared_strings);
+        }
+
         use quick_xml::events::Event;
-        use quick_xml::Reader;
 
-        let mut reader = Reader::from_reader(xml_content.as_slice());
-        reader.trim_text(true);
+        // 実行の境界の空白（例: `<t xml:space="preserve">normal </t>`）を保持するため、
+        // テキストはトリムしない（<t>要素外の空白は読み飛ばされる）
+        let mut reader = XmlReader::new(part, xml_content.as_slice());
 
-        let mut buf = Vec::new();
         let mut in_si = false;
         let mut in_r = false;
         let mut in_t = false;
@@ -260,7 +1105,7 @@ impl XlsxMetadataParser {
         let mut has_r_element = false;
 
         loop {
-            match reader.read_event_into(&mut buf) {
+            match reader.read_event() {
                 Ok(Event::Start(e)) => {
                     match e.name().as_ref() {
                         b"si" => {
@@ -290,6 +1135,10 @@ impl XlsxMetadataParser {
                             // <i/> 要素（斜体）
                             current_format.italic = true;
                         }
+                        b"strike" if in_r => {
+                            // <strike/> 要素（取り消し線）
+                            current_format.strike = true;
+                        }
                         b"t" if in_si => {
                             // <t> 要素の開始（テキスト）
                             in_t = true;
@@ -297,14 +1146,24 @@ impl XlsxMetadataParser {
                         _ => {}
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_t {
-                        let text = e
-                            .unescape()
-                            .map_err(|e| XlsxToMdError::Config(format!("XML text error: {}", e)))?;
-                        current_segment_text.push_str(&text);
+                Ok(Event::Empty(e)) if in_r => {
+                    // <b/>、<i/>、<strike/> は通常は自己終了タグ（val="0"の場合は書式なし）
+                    let enabled = e
+                        .try_get_attribute("val")
+                        .ok()
+                        .flatten()
+                        .is_none_or(|attr| is_true_attr(&attr.value));
+                    match e.name().as_ref() {
+                        b"b" => current_format.bold = enabled,
+                        b"i" => current_format.italic = enabled,
+                        b"strike" => current_format.strike = enabled,
+                        _ => {}
                     }
                 }
+                Ok(Event::Text(e)) if in_t => {
+                    let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                    current_segment_text.push_str(&text);
+                }
                 Ok(Event::End(e)) => {
                     match e.name().as_ref() {
                         b"si" => {
@@ -324,8 +1183,13 @@ impl XlsxMetadataParser {
                                         .push(RichTextSegment::plain(current_segment_text.clone()));
                                 }
                             }
-                            if !current_segments.is_empty() {
-                                shared_strings.insert(current_index, current_segments.clone());
+                            // 書式を含む文字列のみを保持
+                            if current_segments
+                                .iter()
+                                .any(|segment| !segment.format.is_plain())
+                            {
+                                shared_strings
+                                    .push(current_index, std::mem::take(&mut current_segments));
                             }
                             current_index += 1;
                             in_si = false;
@@ -354,7 +1218,7 @@ impl XlsxMetadataParser {
                     }
                 }
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
@@ -365,18 +1229,21 @@ impl XlsxMetadataParser {
     /// xl/styles.xml の解析（プライベート）
     ///
     /// `<numFmts>` と `<cellXfs>` を解析し、Number Format Stringのマッピングを構築します。
+    /// あわせて `<cellStyles>` から名前付きスタイル（例: "Heading 1"）の名前を、
+    /// `<fonts>` からフォントの書式（太字・斜体・取り消し線）を、`<fills>` から塗りつぶしの色を取得します。
+    /// 既定のスタイル（"Normal"、`builtinId="0"`）は含めません。
     fn parse_styles<R: Read + Seek>(
         archive: &mut ZipArchive<R>,
-    ) -> Result<(HashMap<u32, String>, Vec<CellXf>), XlsxToMdError> {
-        let mut num_formats = HashMap::new();
-        let mut cell_xfs = Vec::new();
+    ) -> Result<StylesXml, XlsxToMdError> {
+        let mut styles = StylesXml::default();
 
         // xl/styles.xml を開く（パストラバーサル対策済み）
-        let mut styles_file = match archive.by_name("xl/styles.xml") {
+        let part = "xl/styles.xml";
+        let mut styles_file = match archive.by_name(part) {
             Ok(file) => file,
             Err(_) => {
                 // styles.xmlが存在しない場合は空の結果を返す
-                return Ok((num_formats, cell_xfs));
+                return Ok(styles);
             }
         };
 
@@ -386,326 +1253,524 @@ impl XlsxMetadataParser {
         styles_file.read_to_end(&mut xml_content)?;
 
         use quick_xml::events::Event;
-        use quick_xml::Reader;
 
-        let mut reader = Reader::from_reader(xml_content.as_slice());
+        let mut reader = XmlReader::new(part, xml_content.as_slice());
         reader.trim_text(true);
 
-        let mut buf = Vec::new();
         let mut in_num_fmts = false;
         let mut in_cell_xfs = false;
-        let mut current_num_fmt_id: Option<u32> = None;
-        let mut current_num_fmt_code: Option<String> = None;
-        let mut current_xf: Option<CellXf> = None;
+        let mut in_cell_styles = false;
+        let mut in_fonts = false;
+        let mut in_fills = false;
+        // 解析中の<patternFill>に模様（`patternType`が`none`以外）があるか
+        let mut has_pattern = false;
 
         loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
+            match reader.read_event() {
+                // 要素の情報はすべて属性にあるため、開始タグと自己終了タグを同様に扱う
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     match e.name().as_ref() {
                         b"numFmts" => {
                             in_num_fmts = true;
                         }
                         b"numFmt" if in_num_fmts => {
                             // <numFmt numFmtId="165" formatCode="0.000"/>
-                            current_num_fmt_id = None;
-                            current_num_fmt_code = None;
+                            let mut num_fmt_id: Option<u32> = None;
+                            let mut num_fmt_code: Option<String> = None;
                             for attr in e.attributes() {
-                                let attr = attr.map_err(|e| {
-                                    XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                                })?;
+                                let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
                                 match attr.key.as_ref() {
                                     b"numFmtId" => {
                                         let id_str = std::str::from_utf8(&attr.value)?;
-                                        current_num_fmt_id = Some(id_str.parse()?);
+                                        num_fmt_id = Some(id_str.parse()?);
                                     }
                                     b"formatCode" => {
-                                        current_num_fmt_code =
+                                        num_fmt_code =
                                             Some(std::str::from_utf8(&attr.value)?.to_string());
                                     }
                                     _ => {}
                                 }
                             }
+                            // カスタム書式ID（>= 164）のみ保存
+                            if let (Some(id), Some(code)) = (num_fmt_id, num_fmt_code) {
+                                if id >= 164 {
+                                    styles.num_formats.insert(id, code);
+                                }
+                            }
                         }
                         b"cellXfs" => {
                             in_cell_xfs = true;
                         }
                         b"xf" if in_cell_xfs => {
-                            // <xf numFmtId="165" fontId="0" fillId="0" borderId="0"/>
-                            let mut num_fmt_id = 0u32;
-                            let mut font_id = None;
-                            let mut fill_id = None;
-                            let mut border_id = None;
-
+                            // <xf numFmtId="165" fontId="0" fillId="0" borderId="0" xfId="0"/>
+                            let mut xf = CellXf {
+                                num_fmt_id: 0,
+                                font_id: None,
+                                fill_id: None,
+                                border_id: None,
+                                xf_id: None,
+                            };
                             for attr in e.attributes() {
-                                let attr = attr.map_err(|e| {
-                                    XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                                })?;
+                                let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                                let value = || -> Result<u32, XlsxToMdError> {
+                                    Ok(std::str::from_utf8(&attr.value)?.parse()?)
+                                };
                                 match attr.key.as_ref() {
-                                    b"numFmtId" => {
-                                        let id_str = std::str::from_utf8(&attr.value)?;
-                                        num_fmt_id = id_str.parse()?;
-                                    }
-                                    b"fontId" => {
-                                        let id_str = std::str::from_utf8(&attr.value)?;
-                                        font_id = Some(id_str.parse()?);
+                                    b"numFmtId" => xf.num_fmt_id = value()?,
+                                    b"fontId" => xf.font_id = Some(value()?),
+                                    b"fillId" => xf.fill_id = Some(value()?),
+                                    b"borderId" => xf.border_id = Some(value()?),
+                                    b"xfId" => xf.xf_id = Some(value()?),
+                                    _ => {}
+                                }
+                            }
+                            styles.cell_xfs.push(xf);
+                        }
+                        b"cellStyles" => {
+                            in_cell_styles = true;
+                        }
+                        b"cellStyle" if in_cell_styles => {
+                            // <cellStyle name="Heading 1" xfId="1" builtinId="16"/>
+                            let mut name: Option<String> = None;
+                            let mut xf_id: Option<u32> = None;
+                            let mut is_normal = false;
+                            for attr in e.attributes() {
+                                let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                                match attr.key.as_ref() {
+                                    b"name" => {
+                                        let raw = std::str::from_utf8(&attr.value)?;
+                                        name = Some(
+                                            quick_xml::escape::unescape(raw)
+                                                .map_err(|e| XlsxToMdError::xml(part, e))?
+                                                .into_owned(),
+                                        );
                                     }
-                                    b"fillId" => {
-                                        let id_str = std::str::from_utf8(&attr.value)?;
-                                        fill_id = Some(id_str.parse()?);
+                                    b"xfId" => {
+                                        xf_id = std::str::from_utf8(&attr.value)?.parse().ok();
                                     }
-                                    b"borderId" => {
-                                        let id_str = std::str::from_utf8(&attr.value)?;
-                                        border_id = Some(id_str.parse()?);
+                                    b"builtinId" => {
+                                        is_normal = attr.value.as_ref() == b"0";
                                     }
                                     _ => {}
                                 }
                             }
-
-                            current_xf = Some(CellXf {
-                                num_fmt_id,
-                                font_id,
-                                fill_id,
-                                border_id,
-                            });
+                            if let (Some(name), Some(xf_id), false) = (name, xf_id, is_normal) {
+                                styles.cell_style_names.insert(xf_id, name);
+                            }
                         }
-                        _ => {}
-                    }
-                }
-                Ok(Event::End(e)) => {
-                    match e.name().as_ref() {
-                        b"numFmts" => {
-                            in_num_fmts = false;
+                        b"fonts" => {
+                            in_fonts = true;
                         }
-                        b"numFmt" if in_num_fmts => {
-                            if let (Some(id), Some(code)) =
-                                (current_num_fmt_id, current_num_fmt_code.take())
-                            {
-                                // カスタム書式ID（>= 164）のみ保存
-                                if id >= 164 {
-                                    num_formats.insert(id, code);
+                        b"font" if in_fonts => {
+                            styles.fonts.push(RichTextFormat::new());
+                        }
+                        b"b" | b"i" | b"strike" if in_fonts => {
+                            // <b/>、<i/>、<strike/>（val="0"の場合は書式なし）
+                            let enabled = e
+                                .try_get_attribute("val")
+                                .ok()
+                                .flatten()
+                                .is_none_or(|attr| is_true_attr(&attr.value));
+                            if let Some(font) = styles.fonts.last_mut() {
+                                match e.name().as_ref() {
+                                    b"b" => font.bold = enabled,
+                                    b"i" => font.italic = enabled,
+                                    _ => font.strike = enabled,
                                 }
                             }
                         }
-                        b"cellXfs" => {
-                            in_cell_xfs = false;
+                        b"fills" => {
+                            in_fills = true;
+                        }
+                        b"fill" if in_fills => {
+                            styles.fills.push(None);
+                        }
+                        b"patternFill" if in_fills => {
+                            // <patternFill patternType="solid">（patternType省略時は塗りつぶしなし）
+                            has_pattern = e
+                                .try_get_attribute("patternType")
+                                .ok()
+                                .flatten()
+                                .is_some_and(|attr| attr.value.as_ref() != b"none");
                         }
-                        b"xf" if in_cell_xfs => {
-                            if let Some(xf) = current_xf.take() {
-                                cell_xfs.push(xf);
+                        b"fgColor" if in_fills && has_pattern => {
+                            // <fgColor rgb="FFFFFF00"/>（テーマの色・インデックスの色は扱わない）
+                            let rgb = match e.try_get_attribute("rgb") {
+                                Ok(Some(attr)) => argb_to_rgb(std::str::from_utf8(&attr.value)?),
+                                _ => None,
+                            };
+                            if let (Some(rgb), Some(fill)) = (rgb, styles.fills.last_mut()) {
+                                *fill = Some(rgb);
                             }
                         }
                         _ => {}
                     }
                 }
+                Ok(Event::End(e)) => match e.name().as_ref() {
+                    b"numFmts" => {
+                        in_num_fmts = false;
+                    }
+                    b"cellXfs" => {
+                        in_cell_xfs = false;
+                    }
+                    b"cellStyles" => {
+                        in_cell_styles = false;
+                    }
+                    b"fonts" => {
+                        in_fonts = false;
+                    }
+                    b"fills" => {
+                        in_fills = false;
+                    }
+                    b"patternFill" => {
+                        has_pattern = false;
+                    }
+                    _ => {}
+                },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
 
-        Ok((num_formats, cell_xfs))
+        Ok(styles)
     }
 
     /// xl/worksheets/*.xml の解析（プライベート）
     ///
-    /// すべてのワークシートXMLファイルを解析し、非表示行・列の情報を収集します。
-    #[allow(clippy::type_complexity)]
+    /// `sheet_parts`のすべてのワークシートXMLファイルを解析し、非表示行・列の情報を収集します。
+    /// `parts`に含まれない要素（`HIDDEN`、`SHARED_STRINGS`、`CELL_STYLES`、`CELL_IMAGES`、
+    /// `SPILL_RANGES`、`PROTECTION`、`CONDITIONAL_FORMATS`、`DATA_VALIDATIONS`、`HYPERLINKS`）は
+    /// 収集しません。各ワークシートXMLは1回だけ展開し、すべての要素を同じ内容から解析します。
+    /// 共有文字列インデックスは、`shared_strings`に含まれる文字列を参照するセルのみ、
+    /// スタイルIDは、`styled_xfs`に含まれるスタイルのセルのみ収集します。
     fn parse_worksheets<R: Read + Seek>(
         archive: &mut ZipArchive<R>,
-    ) -> Result<
-        (
-            HashMap<String, HashSet<u32>>,
-            HashMap<String, HashSet<u32>>,
-            HashMap<String, HashMap<(u32, u32), u32>>,
-        ),
-        XlsxToMdError,
-    > {
-        let mut hidden_rows: HashMap<String, HashSet<u32>> = HashMap::new();
-        let mut hidden_cols: HashMap<String, HashSet<u32>> = HashMap::new();
-        let mut cell_string_indices: HashMap<String, HashMap<(u32, u32), u32>> = HashMap::new();
-
-        // すべてのワークシートXMLファイルを検索
+        sheet_parts: &[(String, String)],
+        parts: MetadataParts,
+        shared_strings: &RichSharedStrings,
+        styled_xfs: &HashSet<u32>,
+    ) -> Result<WorksheetScans, XlsxToMdError> {
+        let mut scans = WorksheetScans::default();
+
+        for (file_name, sheet_name) in Self::worksheet_parts(archive, sheet_parts)? {
+            // 非表示行・列と共有文字列インデックスを解析
+            let mut xml_content = Vec::new();
+            archive
+                .by_name(&file_name)
+                .map_err(|e| XlsxToMdError::zip_part(&file_name, e))?
+                .read_to_end(&mut xml_content)?;
+
+            // ハイパーリンクのあるシートのみ、リレーションシップを取得
+            // （存在しない、または解析できない場合は外部リンクなし）
+            let relationships = if parts.contains(MetadataParts::HYPERLINKS)
+                && memchr::memmem::find(&xml_content, b"<hyperlinks").is_some()
+            {
+                archive
+                    .by_name(&Self::rels_path_for_part(&file_name))
+                    .ok()
+                    .and_then(|mut rels_file| Self::parse_relationships(&mut rels_file).ok())
+                    .filter(|rels| !rels.is_empty())
+            } else {
+                None
+            };
+
+            let scan = Self::parse_worksheet_xml(
+                &file_name,
+                &xml_content,
+                parts,
+                shared_strings,
+                styled_xfs,
+                relationships.as_ref(),
+            )?;
+            scans.insert(sheet_name, scan);
+        }
+
+        Ok(scans)
+    }
+
+    /// 解析するワークシートXMLファイルとシート名の一覧を取得（プライベート）
+    ///
+    /// xl/workbook.xml から解決した`sheet_parts`のうち、アーカイブに存在するパーツを返します。
+    /// xl/workbook.xml にシートの一覧がない場合は、xl/worksheets/sheet*.xml を検索し、
+    /// シート名をファイル名から推測します（例: "xl/worksheets/sheet1.xml" -> "Sheet1"）。
+    fn worksheet_parts<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+        sheet_parts: &[(String, String)],
+    ) -> Result<Vec<(String, String)>, XlsxToMdError> {
+        let mut worksheets = Vec::new();
+
+        if !sheet_parts.is_empty() {
+            for (file_name, sheet_name) in sheet_parts {
+                // セキュリティ: パストラバーサル対策（ターゲットはリレーションシップから解決したパス）
+                validate_zip_path(file_name)?;
+                if archive.file_names().any(|name| name == file_name) {
+                    worksheets.push((file_name.clone(), sheet_name.clone()));
+                }
+            }
+            return Ok(worksheets);
+        }
+
         for i in 0..archive.len() {
             let file_name = archive
                 .by_index(i)
-                .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?
+                .map_err(XlsxToMdError::zip)?
                 .name()
                 .to_string();
 
             // セキュリティ: パストラバーサル対策（既にnew()で検証済みだが、念のため再確認）
-            validate_zip_path(&file_name).map_err(|e| {
-                XlsxToMdError::SecurityViolation(format!("Invalid ZIP path: {}", e))
-            })?;
+            validate_zip_path(&file_name)?;
 
             if file_name.starts_with("xl/worksheets/sheet") && file_name.ends_with(".xml") {
-                // シート名を抽出（例: "xl/worksheets/sheet1.xml" -> "Sheet1"）
-                // 実際のシート名はworkbook.xmlから取得する必要があるが、
-                // ここではファイル名から推測する（簡易実装）
                 let sheet_name = Self::extract_sheet_name_from_path(&file_name);
-
-                // 非表示行・列と共有文字列インデックスを解析
-                let mut file = archive
-                    .by_name(&file_name)
-                    .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?;
-                let (rows, cols, string_indices) = Self::parse_worksheet_xml(&mut file)?;
-                if !rows.is_empty() {
-                    hidden_rows.insert(sheet_name.clone(), rows);
-                }
-                if !cols.is_empty() {
-                    hidden_cols.insert(sheet_name.clone(), cols);
-                }
-                if !string_indices.is_empty() {
-                    cell_string_indices.insert(sheet_name, string_indices);
-                }
+                worksheets.push((file_name, sheet_name));
             }
         }
 
-        Ok((hidden_rows, hidden_cols, cell_string_indices))
+        Ok(worksheets)
     }
 
-    /// ワークシートXMLファイルから非表示行・列と共有文字列インデックスを解析
-    #[allow(clippy::type_complexity)]
+    /// ワークシートXMLファイルから非表示行・列、共有文字列インデックス、セルのスタイル、セル内の画像、
+    /// 動的配列数式の溢れた範囲、シートの保護、条件付き書式、スパークラインとデータバー、入力規則、
+    /// ハイパーリンクを解析
+    ///
+    /// ハイパーリンクのURLは`relationships`（シートのリレーションシップ）から解決します。
+    ///
+    /// 巨大なシートでは走査が変換時間の大半を占めるため、次の方法で処理を軽減します。
+    /// * memchrによる事前走査で`hidden=`属性や共有文字列セル（`t="s"`）、スタイル属性（`s=`）、
+    ///   値メタデータ属性（`vm=`）、配列数式（`t="array"`）の
+    ///   有無を確認し、必要な情報がないシートはXMLイベントの解析自体を省略する
+    /// * スライスから借用したイベントを読み、セルごとの文字列の確保を行わない
     fn parse_worksheet_xml(
-        reader: &mut zip::read::ZipFile<'_>,
-    ) -> Result<(HashSet<u32>, HashSet<u32>, HashMap<(u32, u32), u32>), XlsxToMdError> {
+        part: &str,
+        xml_content: &[u8],
+        parts: MetadataParts,
+        shared_strings: &RichSharedStrings,
+        styled_xfs: &HashSet<u32>,
+        relationships: Option<&HashMap<String, String>>,
+    ) -> Result<WorksheetScan, XlsxToMdError> {
+        use memchr::memmem;
         use quick_xml::events::Event;
-        use quick_xml::Reader;
-        use std::io::Read;
 
-        // ZIPファイルの内容を一度メモリに読み込む
-        let mut xml_content = Vec::new();
-        reader.read_to_end(&mut xml_content)?;
+        let mut scan = WorksheetScan::default();
+
+        // ハイパーリンク: <hyperlinks>要素は<sheetData>より後にあるため、要素の位置から解析する
+        if parts.contains(MetadataParts::HYPERLINKS) {
+            if let Some(pos) = memmem::find(xml_content, b"<hyperlinks") {
+                scan.hyperlinks =
+                    Self::parse_worksheet_hyperlinks(part, &xml_content[pos..], relationships)?;
+            }
+        }
+
+        // シートの保護: <sheetProtection>要素はシートの末尾付近にあるため、要素の位置から解析する
+        if parts.contains(MetadataParts::PROTECTION) {
+            if let Some(pos) = memmem::find(xml_content, b"<sheetProtection") {
+                scan.protected = Self::parse_sheet_protection(part, &xml_content[pos..])?;
+            }
+        }
+
+        // 条件付き書式: シートデータの後（拡張ルールは<extLst>内）にあるため、最初の要素の位置から解析する
+        let formats_start = memmem::find(xml_content, b"conditionalFormatting")
+            .map(|pos| memchr::memrchr(b'<', &xml_content[..pos]).unwrap_or(pos));
+        if parts.contains(MetadataParts::CONDITIONAL_FORMATS) {
+            if let Some(start) = formats_start {
+                scan.conditional_formats = parse_conditional_formats(part, &xml_content[start..])?;
+            }
+        }
+
+        // スパークラインとデータバー: データバーは条件付き書式のルールから取得する
+        if parts.contains(MetadataParts::VISUALS) {
+            if let Some(pos) = memmem::find(xml_content, b"sparklineGroups") {
+                let start = memchr::memrchr(b'<', &xml_content[..pos]).unwrap_or(pos);
+                scan.visuals.sparklines = parse_sparklines(part, &xml_content[start..])?;
+            }
+            if let Some(start) = formats_start {
+                if memmem::find(&xml_content[start..], b"dataBar").is_some() {
+                    scan.visuals.data_bars = if parts.contains(MetadataParts::CONDITIONAL_FORMATS) {
+                        data_bar_ranges(&scan.conditional_formats)
+                    } else {
+                        data_bar_ranges(&parse_conditional_formats(part, &xml_content[start..])?)
+                    };
+                }
+            }
+        }
+
+        // 入力規則: 条件付き書式の後（拡張の入力規則は<extLst>内）にあるため、最初の要素の位置から解析する
+        if parts.contains(MetadataParts::DATA_VALIDATIONS) {
+            if let Some(pos) = memmem::find(xml_content, b"dataValidation") {
+                let start = memchr::memrchr(b'<', &xml_content[..pos]).unwrap_or(pos);
+                scan.data_validations = parse_data_validations(part, &xml_content[start..])?;
+            }
+        }
+
+        // 事前走査: 対象の属性が存在しない場合は解析しない
+        // 高さ・幅が0の行・列（`ht="0"`・`width="0"`）も非表示の情報として収集する
+        let collect_hidden = parts.contains(MetadataParts::HIDDEN)
+            && [
+                b"hidden=".as_slice(),
+                b" ht=\"0",
+                b" ht='0",
+                b" width=\"0",
+                b" width='0",
+            ]
+            .iter()
+            .any(|pattern| memmem::find(xml_content, pattern).is_some());
+        let collect_strings = parts.contains(MetadataParts::SHARED_STRINGS)
+            && (memmem::find(xml_content, b"t=\"s\"").is_some()
+                || memmem::find(xml_content, b"t='s'").is_some());
+        let collect_styles = (parts.contains(MetadataParts::STYLES)
+            || parts.contains(MetadataParts::CELL_STYLES)
+            || parts.contains(MetadataParts::FONT_STYLES)
+            || parts.contains(MetadataParts::FILL_COLORS))
+            && !styled_xfs.is_empty()
+            && memmem::find(xml_content, b" s=").is_some();
+        let collect_images = parts.contains(MetadataParts::CELL_IMAGES)
+            && memmem::find(xml_content, b" vm=").is_some();
+        let collect_spills = parts.contains(MetadataParts::SPILL_RANGES)
+            && (memmem::find(xml_content, b"t=\"array\"").is_some()
+                || memmem::find(xml_content, b"t='array'").is_some());
+        if !collect_hidden
+            && !collect_strings
+            && !collect_styles
+            && !collect_images
+            && !collect_spills
+        {
+            return Ok(scan);
+        }
 
-        let mut xml_reader = Reader::from_reader(xml_content.as_slice());
+        let mut xml_reader = XmlReader::new(part, xml_content);
         xml_reader.trim_text(true);
 
-        let mut buf = Vec::new();
-        let mut hidden_rows = HashSet::new();
-        let mut hidden_cols = HashSet::new();
-        let mut cell_string_indices = HashMap::new();
         let mut in_cols = false;
         let mut in_row = false;
         let mut in_cell = false;
+        let mut in_value = false;
         let mut current_row_num: Option<u32> = None;
         let mut current_col_num: Option<u32> = None;
-        let mut current_cell_type: Option<String> = None;
-        let mut current_cell_value: Option<String> = None;
+        let mut is_shared_string = false;
+        let mut current_string_index: Option<u32> = None;
+        let mut current_style: Option<u32> = None;
+        let mut current_image: Option<u32> = None;
+        let mut is_dynamic_array = false;
 
         loop {
-            match xml_reader.read_event_into(&mut buf) {
+            match xml_reader.read_event() {
                 Ok(Event::Start(e)) => {
                     match e.name().as_ref() {
                         b"cols" => {
                             in_cols = true;
                         }
-                        b"col" if in_cols => {
+                        b"col" if in_cols && collect_hidden => {
                             // <col min="3" max="3" hidden="1"/>
-                            let mut current_col_min: Option<u32> = None;
-                            let mut current_col_max: Option<u32> = None;
-                            let mut is_hidden = false;
-
-                            for attr in e.attributes() {
-                                let attr = attr.map_err(|e| {
-                                    XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                                })?;
-                                match attr.key.as_ref() {
-                                    b"min" => {
-                                        let min_str = std::str::from_utf8(&attr.value)?;
-                                        // Excelの列番号は1始まりなので、0始まりに変換
-                                        current_col_min = Some(min_str.parse::<u32>()? - 1);
-                                    }
-                                    b"max" => {
-                                        let max_str = std::str::from_utf8(&attr.value)?;
-                                        current_col_max = Some(max_str.parse::<u32>()? - 1);
-                                    }
-                                    b"hidden" => {
-                                        let hidden_str = std::str::from_utf8(&attr.value)?;
-                                        is_hidden = hidden_str == "1" || hidden_str == "true";
-                                    }
-                                    _ => {}
-                                }
-                            }
-
-                            if is_hidden {
-                                if let (Some(min), Some(max)) = (current_col_min, current_col_max) {
-                                    for col in min..=max {
-                                        hidden_cols.insert(col);
-                                    }
-                                }
-                            }
+                            Self::collect_hidden_cols(part, &e, &mut scan)?;
                         }
                         b"row" => {
-                            // <row r="15" hidden="1">
+                            // <row r="15" hidden="1">、<row r="16" ht="0" customHeight="1">
                             in_row = true;
                             current_row_num = None;
                             let mut is_hidden = false;
+                            let mut is_zero_size = false;
 
                             for attr in e.attributes() {
-                                let attr = attr.map_err(|e| {
-                                    XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                                })?;
+                                let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
                                 match attr.key.as_ref() {
                                     b"r" => {
                                         let r_str = std::str::from_utf8(&attr.value)?;
                                         // Excelの行番号は1始まりなので、0始まりに変換
-                                        current_row_num = Some(r_str.parse::<u32>()? - 1);
+                                        // 不正な行番号（0）は無視する
+                                        current_row_num = r_str.parse::<u32>()?.checked_sub(1);
                                     }
                                     b"hidden" => {
-                                        let hidden_str = std::str::from_utf8(&attr.value)?;
-                                        is_hidden = hidden_str == "1" || hidden_str == "true";
+                                        is_hidden = is_true_attr(&attr.value);
+                                    }
+                                    b"ht" => {
+                                        is_zero_size = is_zero_attr(&attr.value);
                                     }
                                     _ => {}
                                 }
                             }
 
-                            if is_hidden {
+                            if collect_hidden {
                                 if let Some(row) = current_row_num {
-                                    hidden_rows.insert(row);
+                                    if is_hidden {
+                                        scan.hidden_rows.insert(row);
+                                    } else if is_zero_size {
+                                        scan.zero_size_rows.insert(row);
+                                    }
                                 }
                             }
                         }
-                        b"c" if in_row => {
-                            // <c r="A1" t="s">
+                        b"c" if in_row
+                            && (collect_strings
+                                || collect_styles
+                                || collect_images
+                                || collect_spills) =>
+                        {
+                            // <c r="A1" s="3" t="s">
                             in_cell = true;
                             current_col_num = None;
-                            current_cell_type = None;
-                            current_cell_value = None;
+                            is_shared_string = false;
+                            current_string_index = None;
+                            current_style = None;
+                            current_image = None;
+                            is_dynamic_array = false;
 
                             for attr in e.attributes() {
-                                let attr = attr.map_err(|e| {
-                                    XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                                })?;
+                                let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
                                 match attr.key.as_ref() {
                                     b"r" => {
-                                        let ref_str = std::str::from_utf8(&attr.value)?;
                                         // セル参照から行・列を抽出（例: "A1" -> (0, 0)）
-                                        if let Some((row, col)) = Self::parse_cell_ref(ref_str) {
+                                        if let Some((row, col)) = parse_cell_ref_bytes(&attr.value)
+                                        {
                                             current_row_num = Some(row);
                                             current_col_num = Some(col);
                                         }
                                     }
                                     b"t" => {
-                                        let t_str = std::str::from_utf8(&attr.value)?;
-                                        current_cell_type = Some(t_str.to_string());
+                                        is_shared_string =
+                                            collect_strings && attr.value.as_ref() == b"s";
+                                    }
+                                    b"s" if collect_styles => {
+                                        current_style = std::str::from_utf8(&attr.value)
+                                            .ok()
+                                            .and_then(|s| s.parse::<u32>().ok())
+                                            .filter(|s| styled_xfs.contains(s));
+                                    }
+                                    b"vm" if collect_images => {
+                                        current_image = std::str::from_utf8(&attr.value)
+                                            .ok()
+                                            .and_then(|vm| vm.parse::<u32>().ok());
+                                    }
+                                    b"cm" if collect_spills => {
+                                        // 動的配列数式のセルはセルメタデータ（cm）を持つ
+                                        // （cmのないtype="array"は従来の配列数式）
+                                        is_dynamic_array = true;
                                     }
                                     _ => {}
                                 }
                             }
                         }
+                        b"f" if in_cell && is_dynamic_array => {
+                            // <f t="array" ref="B2:B5">_xlfn.SEQUENCE(4)</f>
+                            if let Some(range) = Self::parse_spill_range(part, &e)? {
+                                scan.spill_ranges.push(range);
+                            }
+                        }
                         b"v" if in_cell => {
                             // <v>0</v> - 共有文字列インデックス
-                            // テキストを読み込む準備
+                            in_value = is_shared_string;
                         }
                         _ => {}
                     }
                 }
-                Ok(Event::Text(e)) => {
-                    if in_cell {
-                        let text = e
-                            .unescape()
-                            .map_err(|e| XlsxToMdError::Config(format!("XML text error: {}", e)))?;
-                        current_cell_value = Some(text.to_string());
-                    }
+                Ok(Event::Empty(e)) if in_cols && collect_hidden && e.name().as_ref() == b"col" => {
+                    Self::collect_hidden_cols(part, &e, &mut scan)?;
+                }
+                Ok(Event::Text(e)) if in_value => {
+                    // 共有文字列インデックスは数字のみのため、アンエスケープせずに解析する
+                    current_string_index = std::str::from_utf8(&e)
+                        .ok()
+                        .and_then(|text| text.trim().parse::<u32>().ok());
                 }
                 Ok(Event::End(e)) => {
                     match e.name().as_ref() {
@@ -716,126 +1781,140 @@ impl XlsxMetadataParser {
                             in_row = false;
                             current_row_num = None;
                         }
+                        b"v" => {
+                            in_value = false;
+                        }
                         b"c" if in_cell => {
-                            // セルが終了したら、共有文字列インデックスを保存
-                            if let (Some(row), Some(col), Some(cell_type), Some(cell_value)) = (
-                                current_row_num,
-                                current_col_num,
-                                current_cell_type,
-                                current_cell_value.take(),
-                            ) {
-                                if cell_type == "s" {
-                                    // セルタイプが"s"（shared string）の場合
-                                    if let Ok(index) = cell_value.parse::<u32>() {
-                                        cell_string_indices.insert((row, col), index);
+                            // セルが終了したら、共有文字列インデックスとスタイルIDを保存
+                            if let (Some(row), Some(col)) = (current_row_num, current_col_num) {
+                                if let Some(index) = current_string_index.take() {
+                                    if let Some(position) = shared_strings.position(index) {
+                                        scan.rich_text_cells.push(((row, col), position));
                                     }
                                 }
+                                if let Some(style) = current_style.take() {
+                                    scan.cell_styles.insert((row, col), style);
+                                }
+                                if let Some(vm) = current_image.take() {
+                                    scan.cell_images.insert((row, col), vm);
+                                }
                             }
                             in_cell = false;
                             current_col_num = None;
-                            current_cell_type = None;
+                            is_shared_string = false;
                         }
                         _ => {}
                     }
                 }
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
 
-        Ok((hidden_rows, hidden_cols, cell_string_indices))
+        Ok(scan)
     }
 
-    /// ハイパーリンク情報を解析
+    /// `<sheetProtection>`要素からシートが保護されているかを取得（内部ヘルパー）
     ///
-    /// ワークシートXMLとリレーションシップファイルからハイパーリンク情報を取得します。
-    #[allow(clippy::type_complexity)]
-    fn parse_hyperlinks<R: Read + Seek>(
-        archive: &mut ZipArchive<R>,
-    ) -> Result<HashMap<String, HashMap<(u32, u32), Hyperlink>>, XlsxToMdError> {
-        let mut hyperlinks: HashMap<String, HashMap<(u32, u32), Hyperlink>> = HashMap::new();
-
-        // 1回のループでリレーションシップファイルとワークシートXMLの両方を処理
-        // リレーションシップファイルを先に収集してから、ワークシートXMLを処理
-        let mut rels_map: HashMap<String, HashMap<String, String>> = HashMap::new();
-        let mut worksheet_files: Vec<(String, String)> = Vec::new(); // (file_name, sheet_name)
-
-        for i in 0..archive.len() {
-            let file_name = archive
-                .by_index(i)
-                .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?
-                .name()
-                .to_string();
+    /// `sheet`属性が真の場合のみ保護されているものとします（属性の既定値は偽）。
+    /// `xml_content`は要素の開始位置からのスライスです。
+    fn parse_sheet_protection(part: &str, xml_content: &[u8]) -> Result<bool, XlsxToMdError> {
+        use quick_xml::events::Event;
 
-            // セキュリティ: パストラバーサル対策（既にnew()で検証済みだが、念のため再確認）
-            validate_zip_path(&file_name).map_err(|e| {
-                XlsxToMdError::SecurityViolation(format!("Invalid ZIP path: {}", e))
-            })?;
-
-            // リレーションシップファイルの処理
-            if file_name.contains("_rels") && file_name.ends_with(".xml.rels") {
-                // ワークシートのリレーションシップファイルのみを処理
-                if file_name.contains("worksheets/_rels/sheet") {
-                    let sheet_name = Self::extract_sheet_name_from_rels_path(&file_name);
-                    let mut file = archive
-                        .by_name(&file_name)
-                        .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?;
-                    let rels = Self::parse_relationships(&mut file)?;
-                    if !rels.is_empty() {
-                        rels_map.insert(sheet_name, rels);
+        let mut xml_reader = XmlReader::new(part, xml_content);
+        match xml_reader.read_event() {
+            Ok(Event::Empty(e)) | Ok(Event::Start(e)) => {
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                    if attr.key.as_ref() == b"sheet" {
+                        return Ok(is_true_attr(&attr.value));
                     }
                 }
+                Ok(false)
             }
-            // ワークシートXMLファイルの収集
-            else if file_name.starts_with("xl/worksheets/sheet") && file_name.ends_with(".xml") {
-                let sheet_name = Self::extract_sheet_name_from_path(&file_name);
-                worksheet_files.push((file_name, sheet_name));
-            }
+            Ok(_) => Ok(false),
+            Err(e) => Err(e),
         }
+    }
 
-        // 2. ワークシートXMLからハイパーリンク要素を解析
-        for (file_name, sheet_name) in worksheet_files {
-            // 対応するリレーションシップファイルを探す
-            // ファイル名から番号を抽出（例: "sheet1.xml" -> 1）
-            let sheet_num = if let Some(name) = file_name.strip_prefix("xl/worksheets/sheet") {
-                if let Some(num_str) = name.strip_suffix(".xml") {
-                    num_str.parse::<usize>().ok()
-                } else {
-                    None
-                }
-            } else {
-                None
-            };
-
-            // リレーションシップファイル名を構築（例: "xl/worksheets/_rels/sheet1.xml.rels"）
-            let rels_file_name = if let Some(num) = sheet_num {
-                format!("xl/worksheets/_rels/sheet{}.xml.rels", num)
-            } else {
-                continue;
-            };
-
-            // リレーションシップを取得（先に収集したマップから取得、なければ直接読み込む）
-            let rels_for_sheet = if let Some(rels) = rels_map.get(&sheet_name) {
-                Some(rels.clone())
-            } else if let Ok(mut rels_file) = archive.by_name(&rels_file_name) {
-                Self::parse_relationships(&mut rels_file).ok()
-            } else {
-                None
-            };
+    /// 動的配列数式の`<f>`要素から溢れた範囲を取得（内部ヘルパー）
+    ///
+    /// 配列数式でない場合、または範囲が起点のセルのみの場合は`None`を返します。
+    fn parse_spill_range(
+        part: &str,
+        element: &quick_xml::events::BytesStart<'_>,
+    ) -> Result<Option<CellRange>, XlsxToMdError> {
+        let mut is_array = false;
+        let mut range_ref: Option<Vec<u8>> = None;
+
+        for attr in element.attributes() {
+            let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+            match attr.key.as_ref() {
+                b"t" => is_array = attr.value.as_ref() == b"array",
+                b"ref" => range_ref = Some(attr.value.into_owned()),
+                _ => {}
+            }
+        }
 
-            let mut file = archive
-                .by_name(&file_name)
-                .map_err(|e| XlsxToMdError::Zip(format!("{}", e)))?;
-            let sheet_hyperlinks =
-                Self::parse_worksheet_hyperlinks(&mut file, &rels_for_sheet.as_ref())?;
+        let Some(range_ref) = range_ref.filter(|_| is_array) else {
+            return Ok(None);
+        };
+        let mut ends = range_ref.splitn(2, |&b| b == b':');
+        let start = ends.next().and_then(parse_cell_ref_bytes);
+        let end = ends.next().and_then(parse_cell_ref_bytes);
+        Ok(match (start, end) {
+            (Some(start), Some(end)) if start != end => Some(CellRange::new(
+                CellCoord::new(start.0, start.1),
+                CellCoord::new(end.0, end.1),
+            )),
+            _ => None,
+        })
+    }
 
-            if !sheet_hyperlinks.is_empty() {
-                hyperlinks.insert(sheet_name, sheet_hyperlinks);
+    /// `<col>`要素から非表示列と幅が0の列を収集（内部ヘルパー）
+    fn collect_hidden_cols(
+        part: &str,
+        element: &quick_xml::events::BytesStart<'_>,
+        scan: &mut WorksheetScan,
+    ) -> Result<(), XlsxToMdError> {
+        let mut current_col_min: Option<u32> = None;
+        let mut current_col_max: Option<u32> = None;
+        let mut is_hidden = false;
+        let mut is_zero_size = false;
+
+        for attr in element.attributes() {
+            let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+            match attr.key.as_ref() {
+                b"min" => {
+                    let min_str = std::str::from_utf8(&attr.value)?;
+                    // Excelの列番号は1始まりなので、0始まりに変換
+                    current_col_min = Some(min_str.parse::<u32>()?.saturating_sub(1));
+                }
+                b"max" => {
+                    let max_str = std::str::from_utf8(&attr.value)?;
+                    // Excelの最終列（XFD）を超える範囲は切り詰める
+                    current_col_max =
+                        Some(max_str.parse::<u32>()?.saturating_sub(1).min(MAX_COL_INDEX));
+                }
+                b"hidden" => {
+                    is_hidden = is_true_attr(&attr.value);
+                }
+                b"width" => {
+                    is_zero_size = is_zero_attr(&attr.value);
+                }
+                _ => {}
             }
         }
 
-        Ok(hyperlinks)
+        if let (Some(min), Some(max)) = (current_col_min, current_col_max) {
+            if is_hidden {
+                scan.hidden_cols.extend(min..=max);
+            } else if is_zero_size {
+                scan.zero_size_cols.extend(min..=max);
+            }
+        }
+        Ok(())
     }
 
     /// リレーションシップファイルを解析
@@ -843,20 +1922,20 @@ impl XlsxMetadataParser {
         reader: &mut zip::read::ZipFile<'_>,
     ) -> Result<HashMap<String, String>, XlsxToMdError> {
         use quick_xml::events::Event;
-        use quick_xml::Reader;
         use std::io::Read;
 
+        let part = reader.name().to_string();
+        let part = part.as_str();
         let mut xml_content = Vec::new();
         reader.read_to_end(&mut xml_content)?;
 
-        let mut xml_reader = Reader::from_reader(xml_content.as_slice());
+        let mut xml_reader = XmlReader::new(part, xml_content.as_slice());
         xml_reader.trim_text(true);
 
-        let mut buf = Vec::new();
         let mut relationships = HashMap::new();
 
         loop {
-            match xml_reader.read_event_into(&mut buf) {
+            match xml_reader.read_event() {
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     // Event::Emptyは自己終了タグの場合に発生
                     let name = e.name();
@@ -865,15 +1944,16 @@ impl XlsxMetadataParser {
                         let mut target = None;
 
                         for attr in e.attributes() {
-                            let attr = attr.map_err(|e| {
-                                XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                            })?;
+                            let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
                             match attr.key.as_ref() {
                                 b"Id" => {
                                     id = Some(std::str::from_utf8(&attr.value)?.to_string());
                                 }
                                 b"Target" => {
-                                    target = Some(std::str::from_utf8(&attr.value)?.to_string());
+                                    let raw = std::str::from_utf8(&attr.value)?;
+                                    let value = quick_xml::escape::unescape(raw)
+                                        .map_err(|e| XlsxToMdError::xml(part, e))?;
+                                    target = Some(value.into_owned());
                                 }
                                 _ => {}
                             }
@@ -890,7 +1970,7 @@ impl XlsxMetadataParser {
                     }
                 }
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
@@ -899,26 +1979,26 @@ impl XlsxMetadataParser {
     }
 
     /// ワークシートXMLからハイパーリンク要素を解析
+    ///
+    /// `xml_content`は`<hyperlinks>`要素の開始位置からのスライスです。
     fn parse_worksheet_hyperlinks(
-        reader: &mut zip::read::ZipFile<'_>,
-        relationships: &Option<&HashMap<String, String>>,
+        part: &str,
+        xml_content: &[u8],
+        relationships: Option<&HashMap<String, String>>,
     ) -> Result<HashMap<(u32, u32), Hyperlink>, XlsxToMdError> {
         use quick_xml::events::Event;
-        use quick_xml::Reader;
-        use std::io::Read;
 
-        let mut xml_content = Vec::new();
-        reader.read_to_end(&mut xml_content)?;
+        let mut hyperlinks = HashMap::new();
 
-        let mut xml_reader = Reader::from_reader(xml_content.as_slice());
+        let mut xml_reader = XmlReader::new(part, xml_content);
         xml_reader.trim_text(true);
+        // 途中から解析するため、対応する開始タグのない終了タグ（</worksheet>）を許容する
+        xml_reader.check_end_names(false);
 
-        let mut buf = Vec::new();
-        let mut hyperlinks = HashMap::new();
         let mut in_hyperlinks = false; // <hyperlinks>要素内にいるかどうか
 
         loop {
-            match xml_reader.read_event_into(&mut buf) {
+            match xml_reader.read_event() {
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     // Event::Emptyは自己終了タグ（<hyperlink ... />）の場合に発生
                     let name = e.name();
@@ -935,11 +2015,11 @@ impl XlsxMetadataParser {
                     if in_hyperlinks && name_bytes == b"hyperlink" {
                         let mut ref_attr = None;
                         let mut relationship_id = None;
+                        let mut display = None;
+                        let mut tooltip = None;
 
                         for attr_result in e.attributes() {
-                            let attr = attr_result.map_err(|e| {
-                                XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                            })?;
+                            let attr = attr_result.map_err(|e| XlsxToMdError::xml(part, e))?;
                             let key_bytes = attr.key.as_ref();
 
                             if key_bytes == b"ref" {
@@ -949,6 +2029,17 @@ impl XlsxMetadataParser {
                                 // リレーションシップID（"r:id"）
                                 relationship_id =
                                     Some(std::str::from_utf8(&attr.value)?.to_string());
+                            } else if key_bytes == b"display" || key_bytes == b"tooltip" {
+                                // 表示テキストとヒント（セルの値より優先するリンクテキスト）
+                                let raw = std::str::from_utf8(&attr.value)?;
+                                let text = quick_xml::escape::unescape(raw)
+                                    .map_err(|e| XlsxToMdError::xml(part, e))?
+                                    .into_owned();
+                                if key_bytes == b"display" {
+                                    display = Some(text);
+                                } else {
+                                    tooltip = Some(text);
+                                }
                             }
                         }
 
@@ -972,7 +2063,8 @@ impl XlsxMetadataParser {
                                         coord,
                                         Hyperlink {
                                             url,
-                                            display: None, // 表示テキストはセルの値から取得
+                                            display,
+                                            tooltip,
                                         },
                                     );
                                 }
@@ -980,14 +2072,12 @@ impl XlsxMetadataParser {
                         }
                     }
                 }
-                Ok(Event::End(e)) => {
-                    // <hyperlinks>要素の終了を検出
-                    if e.name().as_ref() == b"hyperlinks" {
-                        in_hyperlinks = false;
-                    }
+                // <hyperlinks>要素の終了を検出
+                Ok(Event::End(e)) if e.name().as_ref() == b"hyperlinks" => {
+                    in_hyperlinks = false;
                 }
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
@@ -997,57 +2087,21 @@ impl XlsxMetadataParser {
 
     /// セル参照文字列を座標に変換（例: "A1" -> (0, 0)）
     fn parse_cell_ref(ref_str: &str) -> Option<(u32, u32)> {
-        // 簡単な実装: "A1"形式を想定
-        let mut col_str = String::new();
-        let mut row_str = String::new();
-
-        for ch in ref_str.chars() {
-            if ch.is_ascii_alphabetic() {
-                col_str.push(ch);
-            } else if ch.is_ascii_digit() {
-                row_str.push(ch);
-            }
-        }
-
-        if col_str.is_empty() || row_str.is_empty() {
-            return None;
-        }
-
-        // 列を数値に変換（A=0, B=1, ..., Z=25, AA=26, ...）
-        let col = col_str
-            .chars()
-            .rev()
-            .enumerate()
-            .map(|(i, ch)| {
-                let val = (ch as u32) - ('A' as u32) + 1;
-                val * 26_u32.pow(i as u32)
-            })
-            .sum::<u32>()
-            - 1;
-
-        // 行を数値に変換（1始まりなので0始まりに変換）
-        let row = row_str.parse::<u32>().ok()? - 1;
-
-        Some((row, col))
+        parse_cell_ref_bytes(ref_str.as_bytes())
     }
 
-    /// リレーションシップファイルパスからシート名を抽出
-    fn extract_sheet_name_from_rels_path(path: &str) -> String {
-        // "xl/worksheets/_rels/sheet1.xml.rels" -> "Sheet1"
-        if let Some(name) = path.strip_prefix("xl/worksheets/_rels/sheet") {
-            if let Some(num_str) = name.strip_suffix(".xml.rels") {
-                if let Ok(num) = num_str.parse::<usize>() {
-                    return format!("Sheet{}", num);
-                }
-            }
+    /// パーツに対応するリレーションシップファイルのパスを取得
+    /// （例: "xl/worksheets/sheet1.xml" -> "xl/worksheets/_rels/sheet1.xml.rels"）
+    pub(super) fn rels_path_for_part(part: &str) -> String {
+        match part.rsplit_once('/') {
+            Some((dir, file)) => format!("{}/_rels/{}.rels", dir, file),
+            None => format!("_rels/{}.rels", part),
         }
-        path.to_string()
     }
 
-    /// ファイルパスからシート名を抽出（簡易実装）
+    /// ファイルパスからシート名を推測
     ///
-    /// 実際の実装では、workbook.xmlからシート名とファイル名のマッピングを取得すべきですが、
-    /// ここでは簡易的にファイル名から推測します。
+    /// xl/workbook.xml にシートの一覧がない場合のフォールバックとして使用します。
     fn extract_sheet_name_from_path(path: &str) -> String {
         // "xl/worksheets/sheet1.xml" -> "Sheet1"
         if let Some(name) = path.strip_prefix("xl/worksheets/sheet") {
@@ -1064,53 +2118,194 @@ impl XlsxMetadataParser {
     /// xl/workbook.xml の解析（プライベート）
     ///
     /// `<workbookPr date1904="true"/>` を解析し、1904年エポックフラグを取得します。
-    fn parse_workbook<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<bool, XlsxToMdError> {
-        let mut workbook_file = match archive.by_name("xl/workbook.xml") {
-            Ok(file) => file,
-            Err(_) => {
-                // workbook.xmlが存在しない場合はデフォルト（false）を返す
-                return Ok(false);
-            }
-        };
+    /// また、`<sheets>`の`<sheet name="..." r:id="..."/>`と xl/_rels/workbook.xml.rels を照合し、
+    /// ワークシートパーツとシート名の対応（ワークブック内の順序）を取得します。
+    /// `<definedNames>`の名前付き範囲も取得します。
+    fn parse_workbook<R: Read + Seek>(
+        archive: &mut ZipArchive<R>,
+    ) -> Result<WorkbookXml, XlsxToMdError> {
+        use super::rich_data::{parse_rel_targets, read_part, resolve_target};
 
-        // ZIPファイルの内容を一度メモリに読み込む
-        use std::io::Read;
-        let mut xml_content = Vec::new();
-        workbook_file.read_to_end(&mut xml_content)?;
+        let part = "xl/workbook.xml";
+        let Some(xml_content) = read_part(archive, part)? else {
+            // workbook.xmlが存在しない場合はデフォルト（false、シートの一覧・名前付き範囲なし）を返す
+            return Ok(WorkbookXml::default());
+        };
 
         use quick_xml::events::Event;
-        use quick_xml::Reader;
 
-        let mut reader = Reader::from_reader(xml_content.as_slice());
+        let mut reader = XmlReader::new(part, xml_content.as_slice());
         reader.trim_text(true);
 
-        let mut buf = Vec::new();
         let mut is_1904 = false;
+        // (リレーションシップID, シート名)
+        let mut sheets: Vec<(String, String)> = Vec::new();
+        let mut defined_names = Vec::new();
+        // 解析中の<definedName>要素
+        let mut current_name: Option<DefinedName> = None;
 
         loop {
-            match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(e)) => {
-                    if e.name().as_ref() == b"workbookPr" {
-                        // <workbookPr date1904="true"/>
-                        for attr in e.attributes() {
-                            let attr = attr.map_err(|e| {
-                                XlsxToMdError::Config(format!("XML attribute error: {}", e))
-                            })?;
-                            if attr.key.as_ref() == b"date1904" {
-                                let value_str = std::str::from_utf8(&attr.value)?;
-                                is_1904 = value_str == "1" || value_str == "true";
+            match reader.read_event() {
+                // <workbookPr date1904="true"/>
+                Ok(Event::Start(e)) if e.name().as_ref() == b"workbookPr" => {
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                        if attr.key.as_ref() == b"date1904" {
+                            let value_str = std::str::from_utf8(&attr.value)?;
+                            is_1904 = value_str == "1" || value_str == "true";
+                        }
+                    }
+                }
+                // <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
+                Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                    if e.local_name().as_ref() == b"sheet" =>
+                {
+                    let (mut name, mut rel_id) = (None, None);
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                        match attr.key.local_name().as_ref() {
+                            b"name" => {
+                                let raw = std::str::from_utf8(&attr.value)?;
+                                let value = quick_xml::escape::unescape(raw)
+                                    .map_err(|e| XlsxToMdError::xml(part, e))?;
+                                name = Some(value.into_owned());
+                            }
+                            b"id" => rel_id = Some(std::str::from_utf8(&attr.value)?.to_string()),
+                            _ => {}
+                        }
+                    }
+                    if let (Some(name), Some(rel_id)) = (name, rel_id) {
+                        sheets.push((rel_id, name));
+                    }
+                }
+                // <definedName name="SalesTable" localSheetId="0">Sheet1!$B$2:$D$10</definedName>
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"definedName" => {
+                    let mut defined = DefinedName {
+                        name: String::new(),
+                        local_sheet_id: None,
+                        refers_to: String::new(),
+                    };
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                        match attr.key.as_ref() {
+                            b"name" => {
+                                let raw = std::str::from_utf8(&attr.value)?;
+                                let value = quick_xml::escape::unescape(raw)
+                                    .map_err(|e| XlsxToMdError::xml(part, e))?;
+                                defined.name = value.into_owned();
+                            }
+                            b"localSheetId" => {
+                                defined.local_sheet_id =
+                                    std::str::from_utf8(&attr.value)?.parse().ok();
                             }
+                            _ => {}
                         }
                     }
+                    current_name = Some(defined);
+                }
+                Ok(Event::Text(e)) if current_name.is_some() => {
+                    let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                    if let Some(defined) = current_name.as_mut() {
+                        defined.refers_to.push_str(&text);
+                    }
+                }
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"definedName" => {
+                    if let Some(defined) = current_name.take() {
+                        defined_names.push(defined);
+                    }
                 }
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(XlsxToMdError::Config(format!("XML parse error: {}", e))),
+                Err(e) => return Err(e),
                 _ => {}
             }
         }
 
-        Ok(is_1904)
+        // リレーションシップIDをワークシートパーツに解決（ターゲットはxl/からの相対パス）
+        let part = "xl/_rels/workbook.xml.rels";
+        let targets = match read_part(archive, part)? {
+            Some(xml) => parse_rel_targets(part, &xml)?,
+            None => HashMap::new(),
+        };
+        let sheet_parts = sheets
+            .iter()
+            .filter_map(|(rel_id, name)| {
+                targets
+                    .get(rel_id)
+                    .map(|target| (resolve_target("xl", target), name.clone()))
+            })
+            .collect();
+
+        Ok(WorkbookXml {
+            is_1904,
+            sheet_names: sheets.into_iter().map(|(_, name)| name).collect(),
+            sheet_parts,
+            defined_names,
+        })
+    }
+}
+
+/// 真偽値の属性値が真かどうか（`"1"`または`"true"`）
+fn is_true_attr(value: &[u8]) -> bool {
+    value == b"1" || value == b"true"
+}
+
+/// ARGBの色の属性値をRGBの16進数6桁に変換（例: `"FFFFFF00"` -> `"FFFF00"`）
+///
+/// アルファ値は無視します。16進数でない値は`None`を返します。
+fn argb_to_rgb(value: &str) -> Option<String> {
+    if !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    match value.len() {
+        8 => Some(value[2..].to_ascii_uppercase()),
+        6 => Some(value.to_ascii_uppercase()),
+        _ => None,
+    }
+}
+
+/// 高さ・幅の属性値が0かどうか（`"0"`、`"0.0"`など）
+fn is_zero_attr(value: &[u8]) -> bool {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|text| text.trim().parse::<f64>().ok())
+        == Some(0.0)
+}
+
+/// セル参照のバイト列を座標に変換（例: `b"AB12"` -> (11, 27)）
+///
+/// 列は英字（大文字・小文字）、行は数字からなる`A1`形式を想定し、文字列を確保せずに変換します。
+/// 範囲指定（例: `A1:B2`）の場合は左上のセルの座標を返します。
+/// 英字・数字が欠けている場合や、値が範囲外の場合は`None`を返します。
+pub(super) fn parse_cell_ref_bytes(ref_bytes: &[u8]) -> Option<(u32, u32)> {
+    let letters = ref_bytes
+        .iter()
+        .take_while(|b| b.is_ascii_alphabetic())
+        .count();
+    let (col_bytes, row_bytes) = ref_bytes.split_at(letters);
+    if col_bytes.is_empty() {
+        return None;
+    }
+
+    // 列を数値に変換（A=0, B=1, ..., Z=25, AA=26, ...）
+    let mut col: u32 = 0;
+    for b in col_bytes {
+        let val = (b.to_ascii_uppercase() - b'A' + 1) as u32;
+        col = col.checked_mul(26)?.checked_add(val)?;
     }
+
+    // 行を数値に変換（1始まりなので0始まりに変換）
+    // 範囲指定（例: "A1:B2"）の場合は左上のセルを返す
+    let digits = row_bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    let (row_bytes, rest) = row_bytes.split_at(digits);
+    if row_bytes.is_empty() || !(rest.is_empty() || rest[0] == b':') {
+        return None;
+    }
+    let mut row: u32 = 0;
+    for b in row_bytes {
+        row = row.checked_mul(10)?.checked_add((b - b'0') as u32)?;
+    }
+
+    Some((row.checked_sub(1)?, col - 1))
 }
 
 /// ビルトイン書式ID（0-163）のマッピング
@@ -1162,6 +2357,7 @@ fn get_builtin_format(id: u32) -> Option<&'static str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::MAX_ROW_INDEX;
 
     #[test]
     fn test_get_builtin_format() {
@@ -1174,6 +2370,399 @@ mod tests {
         assert_eq!(get_builtin_format(164), None);
     }
 
+    #[test]
+    fn test_metadata_parts() {
+        let parts = MetadataParts::SHARED_STRINGS | MetadataParts::STYLES;
+        assert!(parts.contains(MetadataParts::SHARED_STRINGS));
+        assert!(parts.contains(MetadataParts::STYLES));
+        assert!(!parts.contains(MetadataParts::HYPERLINKS));
+        assert!(!parts.contains(MetadataParts::SHARED_STRINGS | MetadataParts::HIDDEN));
+        assert!(MetadataParts::ALL.contains(MetadataParts::CELL_STYLES));
+
+        let parts = MetadataParts::ALL.without(MetadataParts::HIDDEN);
+        assert!(parts.contains(MetadataParts::HYPERLINKS));
+        assert!(!parts.contains(MetadataParts::HIDDEN));
+        assert!(MetadataParts::ALL.contains(parts));
+    }
+
+    #[test]
+    fn test_with_parts() {
+        use rust_xlsxwriter::Workbook;
+
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_url(0, 0, "https://example.com").unwrap();
+        worksheet.write_string(1, 0, "hidden").unwrap();
+        worksheet.set_row_hidden(1).unwrap();
+        let buffer = workbook.save_to_buffer().unwrap();
+
+        let all = XlsxMetadataParser::new(std::io::Cursor::new(buffer.as_slice())).unwrap();
+        assert!(all.is_row_hidden("Sheet1", 1));
+        assert!(all.hyperlinks.contains_key("Sheet1"));
+
+        // 指定しなかった要素は解析しない
+        let parts = MetadataParts::ALL.without(MetadataParts::HIDDEN | MetadataParts::HYPERLINKS);
+        let partial = XlsxMetadataParser::with_parts(
+            std::io::Cursor::new(buffer.as_slice()),
+            parts,
+            &SecurityLimits::default(),
+        )
+        .unwrap();
+        assert!(!partial.is_row_hidden("Sheet1", 1));
+        assert!(partial.hyperlinks.is_empty());
+        assert_eq!(partial.shared_strings.len(), all.shared_strings.len());
+    }
+
+    #[test]
+    fn test_parse_cell_ref_bytes() {
+        assert_eq!(parse_cell_ref_bytes(b"A1"), Some((0, 0)));
+        assert_eq!(parse_cell_ref_bytes(b"ab12"), Some((11, 27)));
+        assert_eq!(parse_cell_ref_bytes(b"A1:B2"), Some((0, 0)));
+        assert_eq!(parse_cell_ref_bytes(b"1A"), None);
+        assert_eq!(parse_cell_ref_bytes(b"A"), None);
+    }
+
+    #[test]
+    fn test_parse_worksheet_xml() {
+        let xml = br#"<worksheet><cols><col min="2" max="3" hidden="1"/></cols><sheetData><row r="1"><c r="A1" s="2" t="s"><v>4</v></c><c r="B1" s="1"><v>7</v></c></row><row r="2" hidden="1"><c r="A2" t="s"><v>0</v></c><c r="B2" t="e" vm="1"><v>#VALUE!</v></c></row><row r="3"><c r="A3" cm="1"><f t="array" ref="A3:A5">_xlfn.SEQUENCE(3)</f><v>1</v></c><c r="B3"><f t="array" ref="B3:B4">A3:A4</f><v>1</v></c><c r="C3" cm="1"><f t="array" ref="C3">_xlfn.SEQUENCE(1)</f><v>1</v></c></row></sheetData><sheetProtection sheet="1" objects="1"/></worksheet>"#;
+
+        // インデックス4のみが書式付きの共有文字列
+        let mut shared_strings = RichSharedStrings::default();
+        shared_strings.push(4, vec![RichTextSegment::plain("x".to_string())]);
+        // スタイルID 2のみが名前付きスタイルを持つ
+        let styled_xfs = HashSet::from([2]);
+
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::ALL,
+            &shared_strings,
+            &styled_xfs,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scan.hidden_rows, HashSet::from([1]));
+        // 自己終了タグの<col/>も非表示列として扱う
+        assert_eq!(scan.hidden_cols, HashSet::from([1, 2]));
+        // 書式のない共有文字列を参照するセルは収集しない（値は`RichSharedStrings`内の位置）
+        assert_eq!(scan.rich_text_cells, vec![((0, 0), 0)]);
+        assert_eq!(scan.cell_styles, HashMap::from([((0, 0), 2)]));
+        assert_eq!(scan.cell_images, HashMap::from([((1, 1), 1)]));
+        // 従来の配列数式（cmなし）と起点のみの範囲は収集しない
+        assert_eq!(
+            scan.spill_ranges,
+            vec![CellRange::new(CellCoord::new(2, 0), CellCoord::new(4, 0))]
+        );
+        assert!(scan.protected);
+
+        // 非表示の情報のみを要求した場合は共有文字列インデックスとスタイルを収集しない
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::HIDDEN,
+            &shared_strings,
+            &styled_xfs,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scan.hidden_rows.len(), 1);
+        assert!(scan.rich_text_cells.is_empty());
+        assert!(scan.cell_styles.is_empty());
+        assert!(scan.cell_images.is_empty());
+        assert!(scan.spill_ranges.is_empty());
+        assert!(!scan.protected);
+
+        // sheet属性が偽の<sheetProtection>は保護されていない
+        let xml =
+            br#"<worksheet><sheetData/><sheetProtection sheet="0" password="CC3D"/></worksheet>"#;
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::PROTECTION,
+            &shared_strings,
+            &styled_xfs,
+            None,
+        )
+        .unwrap();
+        assert!(!scan.protected);
+
+        // 不正な行番号・列範囲でパニックせず、列範囲は最終列までに切り詰める
+        let xml = br#"<worksheet><cols><col min="0" max="4294967295" hidden="1"/></cols><sheetData><row r="0" hidden="1"/></sheetData></worksheet>"#;
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::HIDDEN,
+            &shared_strings,
+            &styled_xfs,
+            None,
+        )
+        .unwrap();
+        assert!(scan.hidden_rows.is_empty());
+        assert_eq!(scan.hidden_cols.len(), MAX_COL_INDEX as usize + 1);
+
+        // 高さ・幅が0の行・列は非表示の行・列と区別して収集する
+        let xml = br#"<worksheet><cols><col min="1" max="1" width="0"/><col min="2" max="2" width="0" hidden="1"/><col min="3" max="3" width="9.5"/></cols><sheetData><row r="1" ht="0.0" customHeight="1"><c r="A1"/></row><row r="2" ht="0" hidden="1"><c r="A2"/></row><row r="3" ht="15"><c r="A3"/></row></sheetData></worksheet>"#;
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::HIDDEN,
+            &shared_strings,
+            &styled_xfs,
+            None,
+        )
+        .unwrap();
+        assert_eq!(scan.zero_size_rows, HashSet::from([0]));
+        assert_eq!(scan.hidden_rows, HashSet::from([1]));
+        assert_eq!(scan.zero_size_cols, HashSet::from([0]));
+        assert_eq!(scan.hidden_cols, HashSet::from([1]));
+
+        // ハイパーリンクは同じ走査で収集し、URLはリレーションシップから解決する
+        let xml = br#"<worksheet><sheetData><row r="1" hidden="1"><c r="A1"><v>1</v></c></row></sheetData><hyperlinks><hyperlink ref="B2" r:id="rId1" display="Docs"/><hyperlink ref="C3" r:id="rId9"/></hyperlinks></worksheet>"#;
+        let relationships =
+            HashMap::from([("rId1".to_string(), "https://example.com".to_string())]);
+        let scan = XlsxMetadataParser::parse_worksheet_xml(
+            "xl/worksheets/sheet1.xml",
+            xml,
+            MetadataParts::HIDDEN | MetadataParts::HYPERLINKS,
+            &shared_strings,
+            &styled_xfs,
+            Some(&relationships),
+        )
+        .unwrap();
+        assert_eq!(scan.hidden_rows, HashSet::from([0]));
+        assert_eq!(scan.hyperlinks.len(), 1);
+        let link = &scan.hyperlinks[&(1, 1)];
+        assert_eq!(link.url, "https://example.com");
+        assert_eq!(link.display.as_deref(), Some("Docs"));
+    }
+
+    #[test]
+    fn test_parse_styles() {
+        use std::io::Write;
+
+        let styles = br#"<styleSheet><numFmts count="1"><numFmt numFmtId="164" formatCode="0.000"/></numFmts><fonts count="3"><font><sz val="11"/></font><font><b/><sz val="11"/></font><font><i val="0"/><strike/></font></fonts><fills count="4"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill><fill><patternFill patternType="solid"><fgColor rgb="FFFFFF00"/><bgColor indexed="64"/></patternFill></fill><fill><patternFill patternType="solid"><fgColor theme="4"/></patternFill></fill></fills><cellStyleXfs count="2"><xf numFmtId="0"/><xf numFmtId="0"/></cellStyleXfs><cellXfs count="3"><xf numFmtId="0" xfId="0"/><xf numFmtId="164" xfId="0"/><xf numFmtId="0" xfId="1"/></cellXfs><cellStyles count="2"><cellStyle name="Normal" xfId="0" builtinId="0"/><cellStyle name="Heading 1" xfId="1" builtinId="16"/></cellStyles></styleSheet>"#;
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file("xl/styles.xml", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(styles).unwrap();
+        let buffer = writer.finish().unwrap().into_inner();
+
+        let mut archive = ZipArchive::new(std::io::Cursor::new(buffer)).unwrap();
+        let StylesXml {
+            num_formats,
+            cell_xfs,
+            cell_style_names,
+            fonts,
+            fills,
+        } = XlsxMetadataParser::parse_styles(&mut archive).unwrap();
+        // 自己終了タグの<numFmt/>・<xf/>も解析し、cellStyleXfsの<xf/>は含めない
+        assert_eq!(num_formats.get(&164).map(|s| s.as_str()), Some("0.000"));
+        assert_eq!(cell_xfs.len(), 3);
+        assert_eq!(cell_xfs[1].num_fmt_id, 164);
+        assert_eq!(cell_xfs[2].xf_id, Some(1));
+        // 既定のスタイル（Normal）は含めない
+        assert_eq!(
+            cell_style_names,
+            HashMap::from([(1, "Heading 1".to_string())])
+        );
+        // フォントの書式（val="0"の場合は書式なし）
+        assert_eq!(fonts.len(), 3);
+        assert!(fonts[0].is_plain());
+        assert!(fonts[1].bold && !fonts[1].italic);
+        assert!(!fonts[2].italic && fonts[2].strike);
+        // 塗りつぶしの色（RGBで指定された色のみ）
+        assert_eq!(fills, vec![None, None, Some("FFFF00".to_string()), None]);
+    }
+
+    #[test]
+    fn test_parse_workbook_sheet_parts() {
+        use std::io::Write;
+
+        // シート名はファイル名と無関係で、r:idの順序もファイルの番号と一致しない
+        let parts: [(&str, &[u8]); 5] = [
+            (
+                "xl/workbook.xml",
+                br#"<workbook xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Budget" sheetId="1" r:id="rId2"/><sheet name="Q&amp;A" sheetId="2" r:id="rId1"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/_rels/workbook.xml.rels",
+                br#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Target="/xl/worksheets/sheet2.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/worksheets/sheet1.xml",
+                br#"<worksheet><sheetData><row r="2" hidden="1"><c r="A2"><v>1</v></c></row></sheetData></worksheet>"#,
+            ),
+            (
+                "xl/worksheets/sheet2.xml",
+                br#"<worksheet><sheetData><row r="1"><c r="A1"><v>1</v></c></row></sheetData><hyperlinks><hyperlink ref="A1" r:id="rId1"/></hyperlinks></worksheet>"#,
+            ),
+            (
+                "xl/worksheets/_rels/sheet2.xml.rels",
+                br#"<Relationships><Relationship Id="rId1" Target="https://example.com" TargetMode="External"/></Relationships>"#,
+            ),
+        ];
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer
+                .start_file(name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(content).unwrap();
+        }
+        let buffer = writer.finish().unwrap().into_inner();
+
+        let mut archive = ZipArchive::new(std::io::Cursor::new(buffer.clone())).unwrap();
+        let workbook = XlsxMetadataParser::parse_workbook(&mut archive).unwrap();
+        assert!(!workbook.is_1904);
+        assert_eq!(workbook.sheet_names, ["Budget", "Q&A"]);
+        assert!(workbook.defined_names.is_empty());
+        assert_eq!(
+            workbook.sheet_parts,
+            vec![
+                ("xl/worksheets/sheet2.xml".to_string(), "Budget".to_string()),
+                ("xl/worksheets/sheet1.xml".to_string(), "Q&A".to_string()),
+            ]
+        );
+
+        // 非表示行・ハイパーリンクは実際のシート名に対応付けられる
+        let metadata = XlsxMetadataParser::new(std::io::Cursor::new(buffer)).unwrap();
+        assert!(metadata.is_row_hidden("Q&A", 1));
+        assert!(!metadata.is_row_hidden("Budget", 1));
+        assert!(!metadata.hidden_rows.contains_key("Sheet1"));
+        assert_eq!(
+            metadata.hyperlinks["Budget"][&(0, 0)].url,
+            "https://example.com"
+        );
+    }
+
+    #[test]
+    fn test_defined_name_range() {
+        use std::io::Write;
+
+        let workbook = br#"<workbook><sheets><sheet name="Data" sheetId="1" r:id="rId1"/><sheet name="Q1 '24" sheetId="2" r:id="rId2"/></sheets><definedNames><definedName name="_xlnm._FilterDatabase" localSheetId="0" hidden="1">Data!$A$1:$C$9</definedName><definedName name="SalesTable">Data!$B$2:$D$10</definedName><definedName name="Area" localSheetId="1">'Q1 ''24'!$C:$A</definedName><definedName name="Area">Data!$3:$4</definedName><definedName name="Split">Data!$A$1,Data!$C$3</definedName><definedName name="Broken">#REF!</definedName></definedNames></workbook>"#;
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        writer
+            .start_file("xl/workbook.xml", zip::write::FileOptions::default())
+            .unwrap();
+        writer.write_all(workbook).unwrap();
+        let buffer = writer.finish().unwrap().into_inner();
+        let metadata = XlsxMetadataParser::new(std::io::Cursor::new(buffer)).unwrap();
+
+        let range = |start: (u32, u32), end: (u32, u32)| {
+            CellRange::new(
+                CellCoord::new(start.0, start.1),
+                CellCoord::new(end.0, end.1),
+            )
+        };
+        // 大文字・小文字を区別しない
+        assert_eq!(
+            metadata.defined_name_range("salestable"),
+            Some(("Data", range((1, 1), (9, 3))))
+        );
+        // ブックレベルの名前を優先し、シートレベルの名前はシート名で修飾して指定する
+        assert_eq!(
+            metadata.defined_name_range("Area"),
+            Some(("Data", range((2, 0), (3, MAX_COL_INDEX))))
+        );
+        assert_eq!(
+            metadata.defined_name_range("'Q1 ''24'!Area"),
+            Some(("Q1 '24", range((0, 0), (MAX_ROW_INDEX, 2))))
+        );
+        assert_eq!(
+            metadata.defined_name_range("Data!_xlnm._FilterDatabase"),
+            Some(("Data", range((0, 0), (8, 2))))
+        );
+        // 単一のセル範囲でない名前、存在しない名前
+        assert_eq!(metadata.defined_name_range("Split"), None);
+        assert_eq!(metadata.defined_name_range("Broken"), None);
+        assert_eq!(metadata.defined_name_range("Missing"), None);
+        assert_eq!(metadata.defined_name_range("Other!SalesTable"), None);
+    }
+
+    #[test]
+    fn test_rels_path_for_part() {
+        assert_eq!(
+            XlsxMetadataParser::rels_path_for_part("xl/worksheets/sheet1.xml"),
+            "xl/worksheets/_rels/sheet1.xml.rels"
+        );
+        assert_eq!(
+            XlsxMetadataParser::rels_path_for_part("xl/worksheets/data.xml"),
+            "xl/worksheets/_rels/data.xml.rels"
+        );
+    }
+
+    #[test]
+    fn test_rich_shared_strings() {
+        let mut shared_strings = RichSharedStrings::default();
+        shared_strings.push(3, vec![RichTextSegment::plain("a".to_string())]);
+        shared_strings.push(10, vec![RichTextSegment::plain("b".to_string())]);
+        assert_eq!(shared_strings.len(), 2);
+        assert_eq!(shared_strings.position(10), Some(1));
+        // 書式のない文字列は保持しない
+        assert_eq!(shared_strings.position(4), None);
+        assert_eq!(shared_strings.get(1).unwrap()[0].text, "b");
+
+        // セルは座標の順に並べ、重複するセルは後の記録を優先する
+        let mut scans = WorksheetScans::default();
+        let scan = WorksheetScan {
+            rich_text_cells: vec![((1, 0), 0), ((0, 2), 0), ((1, 0), 1)],
+            ..WorksheetScan::default()
+        };
+        scans.insert("Sheet1".to_string(), scan);
+        assert_eq!(
+            scans.rich_text_cells["Sheet1"],
+            vec![((0, 2), 0), ((1, 0), 1)]
+        );
+    }
+
+    #[test]
+    fn test_shared_strings_rich_text_only() {
+        use rust_xlsxwriter::{Format, Workbook};
+
+        let plain_only = |workbook: &mut Workbook| {
+            let worksheet = workbook.add_worksheet();
+            worksheet.write_string(0, 0, "plain").unwrap();
+            worksheet.write_string(1, 0, "other").unwrap();
+        };
+        let mut workbook = Workbook::new();
+        plain_only(&mut workbook);
+        let buffer = workbook.save_to_buffer().unwrap();
+        let metadata = XlsxMetadataParser::new(std::io::Cursor::new(buffer.as_slice())).unwrap();
+        // 書式付きの文字列がない場合はマッピングを構築しない
+        assert!(metadata.shared_strings.is_empty());
+        assert!(metadata.rich_text_cells.is_empty());
+
+        let mut workbook = Workbook::new();
+        plain_only(&mut workbook);
+        let bold = Format::new().set_bold();
+        let default = Format::default();
+        workbook
+            .worksheet_from_index(0)
+            .unwrap()
+            .write_rich_string(2, 0, &[(&default, "normal "), (&bold, "bold")])
+            .unwrap();
+        let buffer = workbook.save_to_buffer().unwrap();
+        let metadata = XlsxMetadataParser::new(std::io::Cursor::new(buffer.as_slice())).unwrap();
+        assert_eq!(metadata.shared_strings.len(), 1);
+        assert_eq!(metadata.rich_text_cells["Sheet1"].len(), 1);
+        assert_eq!(metadata.get_cell_rich_text("Sheet1", 0, 0), None);
+        let segments = metadata.get_cell_rich_text("Sheet1", 2, 0).unwrap();
+        assert_eq!(
+            segments,
+            &[
+                RichTextSegment::plain("normal ".to_string()),
+                RichTextSegment::new(
+                    "bold".to_string(),
+                    RichTextFormat {
+                        bold: true,
+                        italic: false,
+                        strike: false
+                    }
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_extract_sheet_name_from_path() {
         assert_eq!(
@@ -3,8 +3,28 @@
 //! calamineを使用したExcelファイル解析の基礎実装。
 //! ストリーミング処理により、メモリ効率的にセルデータを抽出します。
 
+mod charts;
+mod comments;
+mod conditional_formats;
+mod data_validations;
+mod drawings;
+mod encoding;
 mod metadata;
+mod pivots;
+mod revisions;
+mod rich_data;
+mod thumbnail;
+mod visuals;
 mod workbook;
 
-pub(crate) use metadata::XlsxMetadataParser;
-pub(crate) use workbook::WorkbookParser;
+pub(crate) use charts::Chart;
+pub(crate) use comments::CellComment;
+pub(crate) use conditional_formats::ConditionalFormat;
+pub(crate) use data_validations::DataValidation;
+pub(crate) use drawings::DrawingImage;
+pub(crate) use encoding::repair_invalid_utf8;
+pub(crate) use metadata::{MetadataParts, XlsxMetadataParser};
+pub(crate) use pivots::PivotTable;
+pub(crate) use thumbnail::read_thumbnail;
+pub(crate) use visuals::SheetVisuals;
+pub(crate) use workbook::{is_legacy_xls, SharedBuffer, WorkbookParser, WorkbookParserPool};
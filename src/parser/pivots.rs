@@ -0,0 +1,379 @@
+//! Pivots Module
+//!
+//! ワークシートに配置されたピボットテーブルと、そのキャッシュされた元データを解析するモジュール。
+//!
+//! ピボットテーブルはワークシートのリレーションシップから参照される次のパーツに記録されます。
+//!
+//! 1. xl/pivotTables/pivotTable*.xml: ピボットテーブルの名前と配置範囲（`<location ref="A3:C7">`）
+//! 2. xl/pivotCache/pivotCacheDefinition*.xml: 元データのフィールド名と共有アイテム（`<cacheField>`）
+//! 3. xl/pivotCache/pivotCacheRecords*.xml: 元データのレコード（`<r>`）
+//!
+//! レコードの値は、値そのもの（`<n v="120"/>`など）または共有アイテムのインデックス（`<x v="0"/>`）です。
+
+use super::comments::parse_rel_types;
+use super::conditional_formats::{parse_sqref, str_attr};
+use super::metadata::XlsxMetadataParser;
+use super::rich_data::{read_part, resolve_target};
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// ピボットテーブルのリレーションシップの種類（末尾）
+const PIVOT_TABLE_REL: &str = "/pivotTable";
+
+/// ピボットキャッシュの定義のリレーションシップの種類（末尾）
+const PIVOT_CACHE_DEFINITION_REL: &str = "/pivotCacheDefinition";
+
+/// ピボットキャッシュのレコードのリレーションシップの種類（末尾）
+const PIVOT_CACHE_RECORDS_REL: &str = "/pivotCacheRecords";
+
+/// シートに配置されたピボットテーブル（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct PivotTable {
+    /// ピボットテーブルの名前（例: `PivotTable1`）
+    pub name: String,
+
+    /// 配置範囲の左上のセルの行インデックス（シートの絶対座標、0始まり）
+    pub row: u32,
+
+    /// 配置範囲の左上のセルの列インデックス（シートの絶対座標、0始まり）
+    pub col: u32,
+
+    /// 元データのフィールド名（レコードの列の順）
+    pub fields: Vec<String>,
+
+    /// 元データのレコード（各レコードはフィールドの順の表示文字列）
+    ///
+    /// ワークブックに元データが保存されていない場合は空です。
+    pub records: Vec<Vec<String>>,
+}
+
+/// ピボットキャッシュのフィールド（内部用）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct CacheField {
+    /// フィールド名
+    name: String,
+
+    /// 共有アイテム（レコードの`<x v="n"/>`で参照される値）
+    items: Vec<String>,
+}
+
+/// シートごとのピボットテーブルを解析
+///
+/// ピボットテーブルのないシートは含みません。ピボットテーブルは配置範囲の行・列の順に並べます。
+/// 同じピボットキャッシュを参照するピボットテーブルは、キャッシュを1度だけ解析します。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `worksheet_parts` - (ワークシートパーツ, シート名)の一覧
+///
+/// # 戻り値
+///
+/// * `Ok(HashMap<..>)` - シート名 -> ピボットテーブル（配置範囲の順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_pivot_tables<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    worksheet_parts: &[(String, String)],
+) -> Result<HashMap<String, Vec<PivotTable>>, XlsxToMdError> {
+    let mut pivots = HashMap::new();
+    // ピボットキャッシュの定義のパーツ名 -> (フィールド名, レコード)
+    let mut caches: HashMap<String, (Vec<String>, Vec<Vec<String>>)> = HashMap::new();
+
+    for (file_name, sheet_name) in worksheet_parts {
+        let mut sheet_pivots = Vec::new();
+        for part in related_parts(archive, file_name, PIVOT_TABLE_REL)? {
+            let Some(xml) = read_part(archive, &part)? else {
+                continue;
+            };
+            let mut pivot = parse_pivot_table(&part, &xml)?;
+
+            if let Some(definition) = related_parts(archive, &part, PIVOT_CACHE_DEFINITION_REL)?
+                .into_iter()
+                .next()
+            {
+                if !caches.contains_key(&definition) {
+                    let cache = parse_cache(archive, &definition)?;
+                    caches.insert(definition.clone(), cache);
+                }
+                (pivot.fields, pivot.records) = caches[&definition].clone();
+            }
+            sheet_pivots.push(pivot);
+        }
+
+        if !sheet_pivots.is_empty() {
+            sheet_pivots.sort_by_key(|pivot| (pivot.row, pivot.col));
+            pivots.insert(sheet_name.clone(), sheet_pivots);
+        }
+    }
+    Ok(pivots)
+}
+
+/// パーツのリレーションシップから、指定した種類のパーツ名を取得（内部ヘルパー）
+fn related_parts<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    part: &str,
+    rel_suffix: &str,
+) -> Result<Vec<String>, XlsxToMdError> {
+    let rels_part = XlsxMetadataParser::rels_path_for_part(part);
+    let Some(xml) = read_part(archive, &rels_part)? else {
+        return Ok(Vec::new());
+    };
+    let base = part.rsplit_once('/').map_or("", |(dir, _)| dir);
+    Ok(parse_rel_types(&rels_part, &xml)?
+        .iter()
+        .filter(|(t, _)| t.ends_with(rel_suffix))
+        .map(|(_, target)| resolve_target(base, target))
+        .collect())
+}
+
+/// ピボットキャッシュの定義とレコードを解析（内部ヘルパー）
+///
+/// レコードのパーツがない場合（元データを保存しない設定）は、レコードを空とします。
+fn parse_cache<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    definition: &str,
+) -> Result<(Vec<String>, Vec<Vec<String>>), XlsxToMdError> {
+    let fields = match read_part(archive, definition)? {
+        Some(xml) => parse_cache_fields(definition, &xml)?,
+        None => Vec::new(),
+    };
+    let mut records = Vec::new();
+    if let Some(part) = related_parts(archive, definition, PIVOT_CACHE_RECORDS_REL)?
+        .into_iter()
+        .next()
+    {
+        if let Some(xml) = read_part(archive, &part)? {
+            records = parse_cache_records(&part, &xml, &fields)?;
+        }
+    }
+    let names = fields.into_iter().map(|field| field.name).collect();
+    Ok((names, records))
+}
+
+/// ピボットテーブルの定義から名前と配置範囲を解析（内部ヘルパー）
+fn parse_pivot_table(part: &str, xml: &[u8]) -> Result<PivotTable, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut pivot = PivotTable::default();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"pivotTableDefinition" => {
+                    pivot.name = str_attr(part, &e, b"name")?.unwrap_or_default();
+                }
+                b"location" => {
+                    let reference = str_attr(part, &e, b"ref")?.unwrap_or_default();
+                    if let Some(range) = parse_sqref(&reference).first() {
+                        (pivot.row, pivot.col) = (range.start.row, range.start.col);
+                    }
+                    break;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(pivot)
+}
+
+/// ピボットキャッシュの定義からフィールドを解析（内部ヘルパー）
+///
+/// 元データにない計算フィールド・グループ化フィールド（`databaseField="0"`）は、
+/// レコードに値を持たないため含めません。
+fn parse_cache_fields(part: &str, xml: &[u8]) -> Result<Vec<CacheField>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut fields = Vec::new();
+    // 解析中のフィールド（元データにないフィールドの場合は`None`）
+    let mut field: Option<CacheField> = None;
+    let mut in_shared_items = false;
+    // 子要素を持つアイテムの中か（子要素の値はアイテムとして扱わない）
+    let mut in_item = false;
+
+    loop {
+        let (e, is_start) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, true),
+            Ok(Event::Empty(e)) => (e, false),
+            Ok(Event::End(e)) => {
+                match e.local_name().as_ref() {
+                    b"cacheField" => fields.extend(field.take()),
+                    b"sharedItems" => in_shared_items = false,
+                    name if is_item(name) => in_item = false,
+                    _ => {}
+                }
+                continue;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => continue,
+        };
+        match e.local_name().as_ref() {
+            b"cacheField" => {
+                let database_field = str_attr(part, &e, b"databaseField")?;
+                let cache_field = CacheField {
+                    name: str_attr(part, &e, b"name")?.unwrap_or_default(),
+                    items: Vec::new(),
+                };
+                if database_field.is_some_and(|v| v == "0" || v == "false") {
+                    field = None;
+                } else if is_start {
+                    field = Some(cache_field);
+                } else {
+                    fields.push(cache_field);
+                }
+            }
+            b"sharedItems" => in_shared_items = is_start,
+            name if is_item(name) && in_shared_items && !in_item => {
+                if let Some(field) = field.as_mut() {
+                    field.items.push(item_value(part, &e)?);
+                }
+                in_item = is_start;
+            }
+            _ => {}
+        }
+    }
+    Ok(fields)
+}
+
+/// ピボットキャッシュのレコードを解析（内部ヘルパー）
+///
+/// 共有アイテムのインデックス（`<x v="n"/>`）は、フィールドの共有アイテムの値に置き換えます。
+fn parse_cache_records(
+    part: &str,
+    xml: &[u8],
+    fields: &[CacheField],
+) -> Result<Vec<Vec<String>>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut records = Vec::new();
+    let mut record: Option<Vec<String>> = None;
+    let mut in_item = false;
+
+    loop {
+        let (e, is_start) = match reader.read_event() {
+            Ok(Event::Start(e)) => (e, true),
+            Ok(Event::Empty(e)) => (e, false),
+            Ok(Event::End(e)) => {
+                match e.local_name().as_ref() {
+                    b"r" => records.extend(record.take()),
+                    name if is_item(name) || name == b"x" => in_item = false,
+                    _ => {}
+                }
+                continue;
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => continue,
+        };
+        match e.local_name().as_ref() {
+            b"r" if is_start => record = Some(Vec::new()),
+            b"r" => records.push(Vec::new()),
+            name if (is_item(name) || name == b"x") && !in_item => {
+                if let Some(record) = record.as_mut() {
+                    let value = if name == b"x" {
+                        let idx: Option<usize> =
+                            str_attr(part, &e, b"v")?.and_then(|v| v.parse().ok());
+                        fields
+                            .get(record.len())
+                            .zip(idx)
+                            .and_then(|(field, idx)| field.items.get(idx))
+                            .cloned()
+                            .unwrap_or_default()
+                    } else {
+                        item_value(part, &e)?
+                    };
+                    record.push(value);
+                }
+                in_item = is_start;
+            }
+            _ => {}
+        }
+    }
+    Ok(records)
+}
+
+/// 値を表すアイテムの要素かどうか（内部ヘルパー）
+fn is_item(name: &[u8]) -> bool {
+    matches!(name, b"s" | b"n" | b"b" | b"d" | b"e" | b"m")
+}
+
+/// アイテムの要素から表示文字列を取得（内部ヘルパー）
+///
+/// 真偽値は`TRUE`/`FALSE`、時刻が0時の日時は日付のみ、値なし（`<m/>`）は空文字列とします。
+fn item_value(part: &str, element: &BytesStart<'_>) -> Result<String, XlsxToMdError> {
+    let value = str_attr(part, element, b"v")?.unwrap_or_default();
+    Ok(match element.local_name().as_ref() {
+        b"b" => {
+            if value == "1" || value == "true" {
+                "TRUE".to_string()
+            } else {
+                "FALSE".to_string()
+            }
+        }
+        b"d" => value
+            .strip_suffix("T00:00:00")
+            .map_or_else(|| value.clone(), str::to_string),
+        b"m" => String::new(),
+        _ => value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pivot_cache() {
+        let definition = br#"<pivotCacheDefinition r:id="rId1"><cacheSource type="worksheet"><worksheetSource ref="A1:D4" sheet="Sales"/></cacheSource><cacheFields count="4"><cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="East"/><s v="West"/></sharedItems></cacheField><cacheField name="Amount" numFmtId="0"><sharedItems containsNumber="1" minValue="10" maxValue="30"/></cacheField><cacheField name="Date" numFmtId="14"><sharedItems containsDate="1"/></cacheField><cacheField name="Half" numFmtId="0" databaseField="0"><fieldGroup base="2"/></cacheField></cacheFields></pivotCacheDefinition>"#;
+        let fields =
+            parse_cache_fields("xl/pivotCache/pivotCacheDefinition1.xml", definition).unwrap();
+
+        // 元データにないフィールドは含めない
+        assert_eq!(
+            fields,
+            vec![
+                CacheField {
+                    name: "Region".to_string(),
+                    items: vec!["East".to_string(), "West".to_string()],
+                },
+                CacheField {
+                    name: "Amount".to_string(),
+                    items: Vec::new(),
+                },
+                CacheField {
+                    name: "Date".to_string(),
+                    items: Vec::new(),
+                },
+            ]
+        );
+
+        let records = br#"<pivotCacheRecords count="3"><r><x v="1"/><n v="10"/><d v="2024-01-05T00:00:00"/></r><r><x v="0"/><n v="20.5"/><d v="2024-02-01T09:30:00"/></r><r><x v="0"/><m/><m/></r></pivotCacheRecords>"#;
+        let records =
+            parse_cache_records("xl/pivotCache/pivotCacheRecords1.xml", records, &fields).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                vec!["West", "10", "2024-01-05"],
+                vec!["East", "20.5", "2024-02-01T09:30:00"],
+                vec!["East", "", ""],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pivot_table() {
+        let xml = br#"<pivotTableDefinition name="SalesPivot" cacheId="1" dataCaption="Values"><location ref="C3:E8" firstHeaderRow="1" firstDataRow="1" firstDataCol="1"/><pivotFields count="1"/></pivotTableDefinition>"#;
+        let pivot = parse_pivot_table("xl/pivotTables/pivotTable1.xml", xml).unwrap();
+        assert_eq!(pivot.name, "SalesPivot");
+        assert_eq!((pivot.row, pivot.col), (2, 2));
+    }
+}
@@ -0,0 +1,332 @@
+//! Revisions Module
+//!
+//! 共有ブックの変更履歴（xl/revisions/*.xml）を解析するモジュール。
+//!
+//! 変更履歴は次のパーツから構成されます。
+//!
+//! 1. xl/revisions/revisionHeaders.xml: 変更を保存した1回ごとのヘッダー（ユーザー名、日時、ログのリレーションシップID）
+//! 2. xl/revisions/_rels/revisionHeaders.xml.rels: リレーションシップID → 変更ログ
+//! 3. xl/revisions/revisionLog*.xml: セルの変更（`<rcc>`、変更前`<oc>`・変更後`<nc>`の値）
+//!
+//! 変更ログのシートはシートID（`sheetId`）で記録されるため、xl/workbook.xml からシート名に解決します。
+
+use super::rich_data::{parse_rel_targets, read_part, resolve_target};
+use crate::error::XlsxToMdError;
+use crate::report::{CellChange, Revision};
+use crate::security::XmlReader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// 変更履歴のヘッダーのパーツ
+const HEADERS_PART: &str = "xl/revisions/revisionHeaders.xml";
+
+/// 共有ブックの変更履歴を解析
+///
+/// 変更履歴のパーツがない場合は空の履歴を返します。変更ログが見つからないヘッダーは、
+/// セルの変更なしの履歴として含めます。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<Revision>)` - 変更履歴（ヘッダーの記録順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_revisions<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<Vec<Revision>, XlsxToMdError> {
+    let Some(xml) = read_part(archive, HEADERS_PART)? else {
+        return Ok(Vec::new());
+    };
+    let headers = parse_headers(HEADERS_PART, &xml)?;
+
+    let part = "xl/revisions/_rels/revisionHeaders.xml.rels";
+    let log_targets = match read_part(archive, part)? {
+        Some(xml) => parse_rel_targets(part, &xml)?,
+        None => HashMap::new(),
+    };
+    let part = "xl/workbook.xml";
+    let sheet_names = match read_part(archive, part)? {
+        Some(xml) => parse_sheet_ids(part, &xml)?,
+        None => HashMap::new(),
+    };
+
+    let mut revisions = Vec::with_capacity(headers.len());
+    for (revision, rel_id) in headers {
+        let log_part = rel_id
+            .and_then(|id| log_targets.get(&id))
+            .map(|target| resolve_target("xl/revisions", target));
+        let changes = match log_part {
+            Some(part) => match read_part(archive, &part)? {
+                Some(xml) => parse_cell_changes(&part, &xml, &sheet_names)?,
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        revisions.push(Revision {
+            changes,
+            ..revision
+        });
+    }
+    Ok(revisions)
+}
+
+/// 属性値を文字列として取得（内部ヘルパー）
+fn str_attr(
+    part: &str,
+    element: &BytesStart<'_>,
+    key: &[u8],
+) -> Result<Option<String>, XlsxToMdError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+        if attr.key.local_name().as_ref() == key {
+            let raw = std::str::from_utf8(&attr.value)?;
+            let value =
+                quick_xml::escape::unescape(raw).map_err(|e| XlsxToMdError::xml(part, e))?;
+            return Ok(Some(value.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// revisionHeaders.xml から、ヘッダーごとの履歴（セルの変更は空）と変更ログのリレーションシップIDを解析
+fn parse_headers(part: &str, xml: &[u8]) -> Result<Vec<(Revision, Option<String>)>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut headers = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"header" => {
+                let revision = Revision {
+                    user: str_attr(part, &e, b"userName")?.unwrap_or_default(),
+                    date_time: str_attr(part, &e, b"dateTime")?.unwrap_or_default(),
+                    changes: Vec::new(),
+                };
+                // r:id（名前空間付き）のみを対象とし、同名の属性と区別する
+                let mut rel_id = None;
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                    if attr.key.as_ref().ends_with(b":id") {
+                        rel_id = Some(std::str::from_utf8(&attr.value)?.to_string());
+                    }
+                }
+                headers.push((revision, rel_id));
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(headers)
+}
+
+/// xl/workbook.xml から、シートIDとシート名の対応を解析
+fn parse_sheet_ids(part: &str, xml: &[u8]) -> Result<HashMap<String, String>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut names = HashMap::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"sheet" => {
+                if let (Some(id), Some(name)) = (
+                    str_attr(part, &e, b"sheetId")?,
+                    str_attr(part, &e, b"name")?,
+                ) {
+                    names.insert(id, name);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(names)
+}
+
+/// 変更前・変更後のセルの値の解析状態（内部用）
+#[derive(Debug, Default)]
+struct ChangedCell {
+    /// セルの型（`t`属性）
+    value_type: Option<String>,
+    /// 値（`<v>`）
+    value: Option<String>,
+    /// インライン文字列（`<is>`内の`<t>`の連結）
+    inline: Option<String>,
+    /// 数式（`<f>`）
+    formula: Option<String>,
+}
+
+impl ChangedCell {
+    /// 表示する値を取得（真偽値は`TRUE`/`FALSE`、数式は`=`で始まる文字列）
+    fn into_value(self) -> Option<String> {
+        if let Some(inline) = self.inline {
+            return Some(inline);
+        }
+        match (self.value, self.value_type.as_deref()) {
+            (Some(value), Some("b")) => {
+                Some(if value == "1" { "TRUE" } else { "FALSE" }.to_string())
+            }
+            (Some(value), _) => Some(value),
+            (None, _) => self.formula.map(|formula| format!("={}", formula)),
+        }
+    }
+}
+
+/// 変更ログから、セルの値の変更（`<rcc>`）を記録順に解析
+fn parse_cell_changes(
+    part: &str,
+    xml: &[u8],
+    sheet_names: &HashMap<String, String>,
+) -> Result<Vec<CellChange>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+
+    let mut changes = Vec::new();
+    let mut current: Option<CellChange> = None;
+    // 解析中の変更前（`true`）・変更後（`false`）のセル
+    let mut cell: Option<(bool, ChangedCell)> = None;
+    // 解析中のテキスト要素（`v`、`t`、`f`）
+    let mut text_element: Option<Vec<u8>> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"rcc" => {
+                let sheet_id = str_attr(part, &e, b"sId")?.unwrap_or_default();
+                current = Some(CellChange {
+                    sheet: sheet_names.get(&sheet_id).cloned().unwrap_or(sheet_id),
+                    cell: String::new(),
+                    old_value: None,
+                    new_value: None,
+                });
+            }
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                name @ (b"oc" | b"nc") if current.is_some() => {
+                    if let (Some(change), Some(r)) = (current.as_mut(), str_attr(part, &e, b"r")?) {
+                        change.cell = r;
+                    }
+                    let changed = ChangedCell {
+                        value_type: str_attr(part, &e, b"t")?,
+                        ..Default::default()
+                    };
+                    cell = Some((name == b"oc", changed));
+                }
+                name @ (b"v" | b"t" | b"f") if cell.is_some() => {
+                    text_element = Some(name.to_vec());
+                }
+                _ => {}
+            },
+            Ok(Event::Text(e)) => {
+                if let (Some(element), Some((_, changed))) = (&text_element, cell.as_mut()) {
+                    let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                    let target = match element.as_slice() {
+                        b"v" => &mut changed.value,
+                        b"t" => &mut changed.inline,
+                        _ => &mut changed.formula,
+                    };
+                    target.get_or_insert_with(String::new).push_str(&text);
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"v" | b"t" | b"f" => text_element = None,
+                b"oc" | b"nc" => {
+                    if let (Some(change), Some((old, changed))) = (current.as_mut(), cell.take()) {
+                        let value = changed.into_value();
+                        if old {
+                            change.old_value = value;
+                        } else {
+                            change.new_value = value;
+                        }
+                    }
+                }
+                b"rcc" => changes.extend(current.take()),
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn archive(parts: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+    }
+
+    #[test]
+    fn test_parse_revisions() {
+        let mut archive = archive(&[
+            (
+                "xl/workbook.xml",
+                r#"<workbook><sheets><sheet name="Budget" sheetId="3" r:id="rId1"/></sheets></workbook>"#,
+            ),
+            (
+                "xl/revisions/revisionHeaders.xml",
+                r#"<headers xmlns:r="r" guid="{A}"><header guid="{B}" dateTime="2024-05-01T09:30:00Z" userName="Ada" r:id="rId1"/><header guid="{C}" dateTime="2024-05-02T10:00:00Z" userName="Alan &amp; co" r:id="rId2"/></headers>"#,
+            ),
+            (
+                "xl/revisions/_rels/revisionHeaders.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Target="revisionLog1.xml"/><Relationship Id="rId2" Target="revisionLog2.xml"/></Relationships>"#,
+            ),
+            (
+                "xl/revisions/revisionLog1.xml",
+                r#"<revisions><rcc rId="1" sId="3"><oc r="B2" t="n"><v>120</v></oc><nc r="B2" t="n"><v>150</v></nc></rcc><rcc rId="2" sId="3"><nc r="C2" t="inlineStr"><is><t>a &lt; b</t></is></nc></rcc><rrc rId="3" sId="3" ref="A5:XFD5" action="insertRow"/></revisions>"#,
+            ),
+            (
+                "xl/revisions/revisionLog2.xml",
+                r#"<revisions><rcc rId="4" sId="9"><oc r="D1" t="b"><v>1</v></oc><nc r="D1"><f>SUM(A1:A3)</f></nc></rcc></revisions>"#,
+            ),
+        ]);
+
+        let revisions = parse_revisions(&mut archive).unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].user, "Ada");
+        assert_eq!(revisions[0].date_time, "2024-05-01T09:30:00Z");
+        assert_eq!(
+            revisions[0].changes,
+            vec![
+                CellChange {
+                    sheet: "Budget".to_string(),
+                    cell: "B2".to_string(),
+                    old_value: Some("120".to_string()),
+                    new_value: Some("150".to_string()),
+                },
+                CellChange {
+                    sheet: "Budget".to_string(),
+                    cell: "C2".to_string(),
+                    old_value: None,
+                    new_value: Some("a < b".to_string()),
+                },
+            ]
+        );
+
+        // 存在しないシートIDはそのまま、真偽値と数式は表示用の文字列に変換
+        assert_eq!(revisions[1].user, "Alan & co");
+        let change = &revisions[1].changes[0];
+        assert_eq!(change.sheet, "9");
+        assert_eq!(change.old_value.as_deref(), Some("TRUE"));
+        assert_eq!(change.new_value.as_deref(), Some("=SUM(A1:A3)"));
+    }
+
+    #[test]
+    fn test_parse_revisions_without_revisions() {
+        let mut archive = archive(&[("xl/workbook.xml", "<workbook/>")]);
+        assert!(parse_revisions(&mut archive).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+//! Rich Data Module
+//!
+//! セル内の画像（「セルに配置」した画像、`IMAGE()`関数）の参照先を解析するモジュール。
+//!
+//! セル内の画像は、セルの`vm`属性（値メタデータのインデックス、1始まり）から
+//! 次のパーツをたどって画像ファイルに解決されます。
+//!
+//! 1. xl/metadata.xml: 値メタデータ → `XLRICHVALUE`の将来メタデータ → リッチ値のインデックス
+//! 2. xl/richData/rdrichvaluestructure.xml: リッチ値の構造（`_rvRel:LocalImageIdentifier`キーの位置）
+//! 3. xl/richData/rdrichvalue.xml: リッチ値 → 画像リレーションシップのインデックス
+//! 4. xl/richData/richValueRel.xml: 画像リレーションシップのインデックス → リレーションシップID
+//! 5. xl/richData/_rels/richValueRel.xml.rels: リレーションシップID → 画像ファイル
+
+use crate::error::XlsxToMdError;
+use crate::security::{read_entry, XmlReader};
+use quick_xml::events::Event;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// ローカル画像の構造で、画像リレーションシップのインデックスを保持するキー
+const LOCAL_IMAGE_KEY: &str = "_rvRel:LocalImageIdentifier";
+
+/// セルの値メタデータのインデックスから、画像ファイルのパーツ名への対応を解析
+///
+/// Web上の画像（`IMAGE()`関数）など、ワークブック内の画像ファイルに解決できない
+/// 値メタデータは含みません。リッチデータのパーツがない場合は空の対応を返します。
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+///
+/// # 戻り値
+///
+/// * `Ok(HashMap<u32, String>)` - 値メタデータのインデックス（1始まり）から画像ファイル
+///   （例: `xl/media/image1.png`）への対応
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_cell_image_targets<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+) -> Result<HashMap<u32, String>, XlsxToMdError> {
+    let mut targets = HashMap::new();
+
+    let Some(metadata) = read_part(archive, "xl/metadata.xml")? else {
+        return Ok(targets);
+    };
+    let rich_values = parse_value_metadata("xl/metadata.xml", &metadata)?;
+    if rich_values.is_empty() {
+        return Ok(targets);
+    }
+
+    let part = "xl/richData/rdrichvaluestructure.xml";
+    let structures = match read_part(archive, part)? {
+        Some(xml) => parse_structures(part, &xml)?,
+        None => return Ok(targets),
+    };
+    let part = "xl/richData/rdrichvalue.xml";
+    let image_rels = match read_part(archive, part)? {
+        Some(xml) => parse_rich_values(part, &xml, &structures)?,
+        None => return Ok(targets),
+    };
+    let part = "xl/richData/richValueRel.xml";
+    let rel_ids = match read_part(archive, part)? {
+        Some(xml) => parse_rel_ids(part, &xml)?,
+        None => return Ok(targets),
+    };
+    let part = "xl/richData/_rels/richValueRel.xml.rels";
+    let rel_targets = match read_part(archive, part)? {
+        Some(xml) => parse_rel_targets(part, &xml)?,
+        None => return Ok(targets),
+    };
+
+    for (vm, rich_value) in rich_values {
+        let target = image_rels
+            .get(rich_value as usize)
+            .copied()
+            .flatten()
+            .and_then(|rel| rel_ids.get(rel as usize))
+            .and_then(|id| rel_targets.get(id));
+        if let Some(target) = target {
+            targets.insert(vm, resolve_target("xl/richData", target));
+        }
+    }
+    Ok(targets)
+}
+
+/// パーツの内容を宣言されたサイズの範囲内で読み込む（存在しない場合は`None`）（内部ヘルパー）
+pub(super) fn read_part<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    part: &str,
+) -> Result<Option<Vec<u8>>, XlsxToMdError> {
+    let mut file = match archive.by_name(part) {
+        Ok(file) => file,
+        Err(_) => return Ok(None),
+    };
+    let declared_size = file.size();
+    read_entry(part, declared_size, &mut file).map(Some)
+}
+
+/// 数値の属性値を取得（内部ヘルパー）
+fn u32_attr(
+    part: &str,
+    element: &quick_xml::events::BytesStart<'_>,
+    key: &[u8],
+) -> Result<Option<u32>, XlsxToMdError> {
+    for attr in element.attributes() {
+        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+        if attr.key.local_name().as_ref() == key {
+            return Ok(std::str::from_utf8(&attr.value)?.trim().parse().ok());
+        }
+    }
+    Ok(None)
+}
+
+/// xl/metadata.xml から、値メタデータのインデックス（1始まり）とリッチ値のインデックスの対応を解析
+fn parse_value_metadata(part: &str, xml: &[u8]) -> Result<HashMap<u32, u32>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    // XLRICHVALUEの将来メタデータ（<bk>の順）→ リッチ値のインデックス
+    let mut future_rich_values: Vec<Option<u32>> = Vec::new();
+    // 値メタデータ（<bk>の順）→ 将来メタデータのインデックス
+    let mut value_metadata: Vec<Option<u32>> = Vec::new();
+    let mut in_rich_future = false;
+    let mut in_value_metadata = false;
+    // メタデータの種類（<metadataType>の順、1始まり）のうち、XLRICHVALUEの番号
+    let mut type_count = 0;
+    let mut rich_type: Option<u32> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"metadataType" => {
+                    type_count += 1;
+                    if e.attributes().flatten().any(|attr| {
+                        attr.key.as_ref() == b"name" && attr.value.as_ref() == b"XLRICHVALUE"
+                    }) {
+                        rich_type = Some(type_count);
+                    }
+                }
+                b"futureMetadata" => {
+                    in_rich_future = e.attributes().flatten().any(|attr| {
+                        attr.key.as_ref() == b"name" && attr.value.as_ref() == b"XLRICHVALUE"
+                    });
+                }
+                b"valueMetadata" => in_value_metadata = true,
+                b"bk" if in_rich_future => future_rich_values.push(None),
+                b"bk" if in_value_metadata => value_metadata.push(None),
+                b"rvb" if in_rich_future => {
+                    if let Some(last) = future_rich_values.last_mut() {
+                        *last = u32_attr(part, &e, b"i")?;
+                    }
+                }
+                // 他の種類の値メタデータは対象外
+                b"rc" if in_value_metadata && u32_attr(part, &e, b"t")? == rich_type => {
+                    if let Some(last) = value_metadata.last_mut() {
+                        *last = u32_attr(part, &e, b"v")?;
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"futureMetadata" => in_rich_future = false,
+                b"valueMetadata" => in_value_metadata = false,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+
+    Ok(value_metadata
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, future)| {
+            let rich_value = future_rich_values
+                .get(future? as usize)
+                .copied()
+                .flatten()?;
+            Some((idx as u32 + 1, rich_value))
+        })
+        .collect())
+}
+
+/// rdrichvaluestructure.xml から、構造ごとの画像リレーションシップのキーの位置を解析
+fn parse_structures(part: &str, xml: &[u8]) -> Result<Vec<Option<usize>>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut structures: Vec<Option<usize>> = Vec::new();
+    let mut key_count = 0;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"s" => {
+                    structures.push(None);
+                    key_count = 0;
+                }
+                b"k" => {
+                    let is_image_key = e.attributes().flatten().any(|attr| {
+                        attr.key.as_ref() == b"n"
+                            && attr.value.as_ref() == LOCAL_IMAGE_KEY.as_bytes()
+                    });
+                    if let Some(last) = structures.last_mut() {
+                        if is_image_key {
+                            *last = Some(key_count);
+                        }
+                    }
+                    key_count += 1;
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(structures)
+}
+
+/// rdrichvalue.xml から、リッチ値ごとの画像リレーションシップのインデックスを解析
+fn parse_rich_values(
+    part: &str,
+    xml: &[u8],
+    structures: &[Option<usize>],
+) -> Result<Vec<Option<u32>>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut rich_values: Vec<Option<u32>> = Vec::new();
+    // 現在のリッチ値で画像リレーションシップを保持する<v>の位置
+    let mut image_position: Option<usize> = None;
+    let mut value_count = 0;
+    let mut in_image_value = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"rv" => {
+                    rich_values.push(None);
+                    image_position = u32_attr(part, &e, b"s")?
+                        .and_then(|s| structures.get(s as usize).copied().flatten());
+                    value_count = 0;
+                }
+                b"v" => {
+                    in_image_value = image_position == Some(value_count);
+                    value_count += 1;
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"v" => value_count += 1,
+            Ok(Event::Text(e)) if in_image_value => {
+                if let Some(last) = rich_values.last_mut() {
+                    *last = std::str::from_utf8(&e)?.trim().parse().ok();
+                }
+            }
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"v" => in_image_value = false,
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(rich_values)
+}
+
+/// richValueRel.xml から、画像リレーションシップのIDを出現順に解析
+fn parse_rel_ids(part: &str, xml: &[u8]) -> Result<Vec<String>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut ids = Vec::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"rel" => {
+                let mut id = String::new();
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                    if attr.key.local_name().as_ref() == b"id" {
+                        id = std::str::from_utf8(&attr.value)?.to_string();
+                    }
+                }
+                ids.push(id);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(ids)
+}
+
+/// リレーションシップファイルから、IDとターゲットの対応を解析
+pub(super) fn parse_rel_targets(
+    part: &str,
+    xml: &[u8],
+) -> Result<HashMap<String, String>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml);
+    reader.trim_text(true);
+
+    let mut targets = HashMap::new();
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.local_name().as_ref() == b"Relationship" =>
+            {
+                let (mut id, mut target) = (None, None);
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                    match attr.key.as_ref() {
+                        b"Id" => id = Some(std::str::from_utf8(&attr.value)?.to_string()),
+                        b"Target" => target = Some(std::str::from_utf8(&attr.value)?.to_string()),
+                        _ => {}
+                    }
+                }
+                if let (Some(id), Some(target)) = (id, target) {
+                    targets.insert(id, target);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(targets)
+}
+
+/// リレーションシップのターゲット（`base`のディレクトリからの相対パス）をアーカイブ内のパーツ名に変換（内部ヘルパー）
+///
+/// 例: `base`が`xl/richData`の場合、`../media/image1.png` → `xl/media/image1.png`、
+/// `/xl/media/image1.png` → `xl/media/image1.png`
+pub(super) fn resolve_target(base: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+    let mut segments: Vec<&str> = base.split('/').collect();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn archive(parts: &[(&str, &str)]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cell_image_targets() {
+        let mut archive = archive(&[
+            (
+                "xl/metadata.xml",
+                r#"<metadata xmlns:xlrd="x"><metadataTypes count="1"><metadataType name="XLRICHVALUE"/></metadataTypes><futureMetadata name="XLRICHVALUE" count="2"><bk><extLst><ext><xlrd:rvb i="0"/></ext></extLst></bk><bk><extLst><ext><xlrd:rvb i="1"/></ext></extLst></bk></futureMetadata><valueMetadata count="2"><bk><rc t="1" v="1"/></bk><bk><rc t="1" v="0"/></bk></valueMetadata></metadata>"#,
+            ),
+            (
+                "xl/richData/rdrichvaluestructure.xml",
+                r#"<rvStructures><s t="_webimage"><k n="WebImageIdentifier" t="i"/></s><s t="_localImage"><k n="CalcOrigin" t="i"/><k n="_rvRel:LocalImageIdentifier" t="i"/></s></rvStructures>"#,
+            ),
+            (
+                "xl/richData/rdrichvalue.xml",
+                r#"<rvData><rv s="1"><v>5</v><v>1</v></rv><rv s="0"><v>0</v></rv></rvData>"#,
+            ),
+            (
+                "xl/richData/richValueRel.xml",
+                r#"<richValueRels xmlns:r="r"><rel r:id="rId1"/><rel r:id="rId2"/></richValueRels>"#,
+            ),
+            (
+                "xl/richData/_rels/richValueRel.xml.rels",
+                r#"<Relationships><Relationship Id="rId1" Target="../media/image1.png"/><Relationship Id="rId2" Target="../media/image2.png"/></Relationships>"#,
+            ),
+        ]);
+
+        let targets = parse_cell_image_targets(&mut archive).unwrap();
+        // vm="2" → 将来メタデータ0 → リッチ値0（ローカル画像、2番目のキー）→ rId2
+        assert_eq!(
+            targets.get(&2).map(String::as_str),
+            Some("xl/media/image2.png")
+        );
+        // vm="1" → リッチ値1（Web上の画像）は画像ファイルに解決できない
+        assert_eq!(targets.get(&1), None);
+    }
+
+    #[test]
+    fn test_parse_cell_image_targets_without_rich_data() {
+        let mut archive = archive(&[("xl/workbook.xml", "<workbook/>")]);
+        assert!(parse_cell_image_targets(&mut archive).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target("xl/richData", "../media/image1.png"),
+            "xl/media/image1.png"
+        );
+        assert_eq!(
+            resolve_target("xl/richData", "/xl/media/image1.png"),
+            "xl/media/image1.png"
+        );
+        assert_eq!(
+            resolve_target("xl/revisions", "revisionLog1.xml"),
+            "xl/revisions/revisionLog1.xml"
+        );
+    }
+}
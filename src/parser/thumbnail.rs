@@ -0,0 +1,180 @@
+//! Thumbnail Module
+//!
+//! ワークブックに埋め込まれたサムネイル画像を取得するモジュール。
+//!
+//! サムネイル画像はパッケージのリレーションシップ（`_rels/.rels`）で、種類が
+//! `.../metadata/thumbnail`のリレーションシップのターゲットとして参照されます。
+
+use super::rich_data::read_part;
+use crate::error::{SecurityLimit, XlsxToMdError};
+use crate::inspect::Thumbnail;
+use crate::security::{SecurityLimits, XmlReader};
+use quick_xml::events::Event;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// パッケージのリレーションシップのパーツ名
+const PACKAGE_RELS: &str = "_rels/.rels";
+
+/// リレーションシップがない場合に使用するサムネイル画像のパーツ名
+const DEFAULT_THUMBNAIL: &str = "docProps/thumbnail.jpeg";
+
+/// サムネイル画像のリレーションシップの種類（末尾）
+const THUMBNAIL_REL_TYPE: &str = "/metadata/thumbnail";
+
+/// ワークブックに埋め込まれたサムネイル画像を取得
+///
+/// # 引数
+///
+/// * `archive` - XLSXファイルのZIPアーカイブ
+/// * `security` - セキュリティ制限（画像ファイルのサイズの上限）
+///
+/// # 戻り値
+///
+/// * `Ok(Some(Thumbnail))` - サムネイル画像がある場合
+/// * `Ok(None)` - サムネイル画像がない場合
+/// * `Err(XlsxToMdError)` - 画像ファイルがサイズの上限を超える場合、または解析エラーが発生した場合
+pub(crate) fn read_thumbnail<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    security: &SecurityLimits,
+) -> Result<Option<Thumbnail>, XlsxToMdError> {
+    let part = match read_part(archive, PACKAGE_RELS)? {
+        Some(xml) => match thumbnail_target(&xml)? {
+            // ターゲットはパッケージのルートからのパス
+            Some(target) => target.trim_start_matches('/').to_string(),
+            None => return Ok(None),
+        },
+        None => DEFAULT_THUMBNAIL.to_string(),
+    };
+
+    let size = match archive.by_name(&part) {
+        Ok(file) => file.size(),
+        Err(_) => return Ok(None),
+    };
+    if size > security.max_file_size {
+        return Err(SecurityLimit::FileSize {
+            part,
+            size,
+            max: security.max_file_size,
+        }
+        .into());
+    }
+    let Some(bytes) = read_part(archive, &part)? else {
+        return Ok(None);
+    };
+    let mime = mime_type(&part, &bytes).to_string();
+    Ok(Some(Thumbnail { bytes, mime }))
+}
+
+/// パッケージのリレーションシップからサムネイル画像のターゲットを取得（内部ヘルパー）
+fn thumbnail_target(xml: &[u8]) -> Result<Option<String>, XlsxToMdError> {
+    let mut reader = XmlReader::new(PACKAGE_RELS, xml);
+    reader.trim_text(true);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.local_name().as_ref() == b"Relationship" =>
+            {
+                let (mut rel_type, mut target) = (None, None);
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|e| XlsxToMdError::xml(PACKAGE_RELS, e))?;
+                    match attr.key.as_ref() {
+                        b"Type" => rel_type = Some(std::str::from_utf8(&attr.value)?.to_string()),
+                        b"Target" => target = Some(std::str::from_utf8(&attr.value)?.to_string()),
+                        _ => {}
+                    }
+                }
+                if rel_type.is_some_and(|t| t.ends_with(THUMBNAIL_REL_TYPE)) {
+                    return Ok(target);
+                }
+            }
+            Ok(Event::Eof) => return Ok(None),
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+}
+
+/// 画像の形式を判定（先頭のバイト列、判定できない場合は拡張子から）（内部ヘルパー）
+fn mime_type(part: &str, bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return "image/jpeg";
+    }
+    if bytes.starts_with(b"\x89PNG") {
+        return "image/png";
+    }
+    if bytes.starts_with(b"GIF8") {
+        return "image/gif";
+    }
+    let extension = part
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("jpeg" | "jpg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("bmp") => "image/bmp",
+        Some("wmf") => "image/x-wmf",
+        Some("emf") => "image/x-emf",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
+
+    fn archive(parts: &[(&str, &[u8])]) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer.start_file(*name, FileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+    }
+
+    #[test]
+    fn test_read_thumbnail() {
+        let rels = br#"<Relationships><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail" Target="/docProps/thumbnail.wmf"/></Relationships>"#;
+        let mut with_rels = archive(&[
+            ("_rels/.rels", rels),
+            ("docProps/thumbnail.wmf", &[0xD7, 0xCD, 0xC6, 0x9A]),
+        ]);
+        let thumbnail = read_thumbnail(&mut with_rels, &SecurityLimits::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(thumbnail.bytes, [0xD7, 0xCD, 0xC6, 0x9A]);
+        assert_eq!(thumbnail.mime, "image/x-wmf");
+
+        // リレーションシップがない場合は既定のパーツ名
+        let mut without_rels = archive(&[("docProps/thumbnail.jpeg", &[0xFF, 0xD8, 0xFF, 0xE0])]);
+        let thumbnail = read_thumbnail(&mut without_rels, &SecurityLimits::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(thumbnail.mime, "image/jpeg");
+
+        // サムネイル画像のリレーションシップがない場合は、ファイルがあっても参照しない
+        let mut unreferenced = archive(&[
+            ("_rels/.rels", b"<Relationships/>"),
+            ("docProps/thumbnail.jpeg", &[0xFF, 0xD8, 0xFF, 0xE0]),
+        ]);
+        assert!(
+            read_thumbnail(&mut unreferenced, &SecurityLimits::default())
+                .unwrap()
+                .is_none()
+        );
+
+        let security = SecurityLimits {
+            max_file_size: 2,
+            ..SecurityLimits::default()
+        };
+        assert!(matches!(
+            read_thumbnail(&mut with_rels, &security),
+            Err(XlsxToMdError::SecurityViolation(_))
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+//! Visuals Module
+//!
+//! 変換で失われる視覚的な表現（スパークライン・データバー）を検出するモジュール。
+//!
+//! スパークラインはワークシートXMLの`<extLst>`内の`<x14:sparklineGroups>`に、
+//! 描画位置（`<xm:sqref>`）と参照元の範囲（`<xm:f>`）の組として記録されます。
+//! データバーは種類が`dataBar`の条件付き書式のルールです。
+
+use super::conditional_formats::ConditionalFormat;
+use crate::error::XlsxToMdError;
+use crate::security::XmlReader;
+use quick_xml::events::Event;
+use serde_json::{json, Value};
+
+/// スパークライン（内部用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Sparkline {
+    /// 描画位置のセル（A1記法）
+    pub range: String,
+
+    /// 参照元の範囲（例: `Sheet1!A2:E2`）
+    pub source: String,
+
+    /// 種類（`line`、`column`、`stacked`）
+    pub kind: String,
+}
+
+/// シートの視覚的な表現（内部用）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct SheetVisuals {
+    /// スパークライン（記録順）
+    pub sparklines: Vec<Sparkline>,
+
+    /// データバーの適用範囲（記録順、重複なし）
+    ///
+    /// データバーはセル自身の値を表すため、適用範囲が参照元の範囲でもあります。
+    pub data_bars: Vec<String>,
+}
+
+impl SheetVisuals {
+    /// スパークラインもデータバーもないか
+    pub fn is_empty(&self) -> bool {
+        self.sparklines.is_empty() && self.data_bars.is_empty()
+    }
+
+    /// スパークラインをJSONの配列に変換（各要素は`range`、`source`、`type`）
+    pub fn sparklines_json(&self) -> Value {
+        self.sparklines
+            .iter()
+            .map(|s| json!({ "range": s.range, "source": s.source, "type": s.kind }))
+            .collect()
+    }
+
+    /// データバーをJSONの配列に変換（各要素は`range`、`source`）
+    pub fn data_bars_json(&self) -> Value {
+        self.data_bars
+            .iter()
+            .map(|range| json!({ "range": range, "source": range }))
+            .collect()
+    }
+}
+
+/// 条件付き書式のルールからデータバーの適用範囲を取得
+///
+/// Excel 2010以降のデータバーは`<extLst>`内にも同じ範囲のルールが記録されるため、重複を除きます。
+pub(crate) fn data_bar_ranges(formats: &[ConditionalFormat]) -> Vec<String> {
+    let mut ranges: Vec<String> = Vec::new();
+    for format in formats.iter().filter(|f| f.rule_type == "dataBar") {
+        if !ranges.contains(&format.sqref) {
+            ranges.push(format.sqref.clone());
+        }
+    }
+    ranges
+}
+
+/// ワークシートXMLからスパークラインを解析
+///
+/// `xml_content`は`<x14:sparklineGroups>`要素の開始位置からのスライスです。
+///
+/// # 引数
+///
+/// * `part` - ワークシートXMLのパーツ名（エラーメッセージ用）
+/// * `xml_content` - スパークラインの要素の開始位置からのワークシートXML
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<Sparkline>)` - スパークライン（記録順）
+/// * `Err(XlsxToMdError)` - 解析エラーが発生した場合
+pub(crate) fn parse_sparklines(
+    part: &str,
+    xml_content: &[u8],
+) -> Result<Vec<Sparkline>, XlsxToMdError> {
+    let mut reader = XmlReader::new(part, xml_content);
+    reader.trim_text(true);
+    // スライスの途中から読むため、対応する開始タグのない終了タグを許容する
+    reader.check_end_names(false);
+
+    let mut sparklines = Vec::new();
+    // 解析中のグループの種類と、スパークラインの参照元・描画位置
+    let mut kind = String::from("line");
+    let mut source = String::new();
+    let mut range = String::new();
+    // 解析中のテキスト要素（`<xm:f>`は`true`、`<xm:sqref>`は`false`）
+    let mut text_target: Option<bool> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                b"sparklineGroup" => {
+                    kind = String::from("line");
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(|e| XlsxToMdError::xml(part, e))?;
+                        if attr.key.local_name().as_ref() == b"type" {
+                            kind = std::str::from_utf8(&attr.value)?.to_string();
+                        }
+                    }
+                }
+                b"sparkline" => {
+                    source.clear();
+                    range.clear();
+                }
+                b"f" => text_target = Some(true),
+                b"sqref" => text_target = Some(false),
+                _ => {}
+            },
+            Ok(Event::Text(e)) => {
+                let text = e.unescape().map_err(|e| XlsxToMdError::xml(part, e))?;
+                match text_target {
+                    Some(true) => source = text.into_owned(),
+                    Some(false) => range = text.into_owned(),
+                    None => {}
+                }
+            }
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"f" | b"sqref" => text_target = None,
+                b"sparkline" => sparklines.push(Sparkline {
+                    range: std::mem::take(&mut range),
+                    source: std::mem::take(&mut source),
+                    kind: kind.clone(),
+                }),
+                b"sparklineGroups" => break,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(e),
+            _ => {}
+        }
+    }
+
+    Ok(sparklines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sparklines() {
+        let xml = br#"<x14:sparklineGroups xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main">
+            <x14:sparklineGroup displayEmptyCellsAs="gap">
+                <x14:colorSeries rgb="FF376092"/>
+                <x14:sparklines>
+                    <x14:sparkline><xm:f>Sheet1!A2:E2</xm:f><xm:sqref>F2</xm:sqref></x14:sparkline>
+                    <x14:sparkline><xm:f>'Q1 &amp; Q2'!A3:E3</xm:f><xm:sqref>F3</xm:sqref></x14:sparkline>
+                </x14:sparklines>
+            </x14:sparklineGroup>
+            <x14:sparklineGroup type="column">
+                <x14:sparklines>
+                    <x14:sparkline><xm:f>Sheet1!A4:E4</xm:f><xm:sqref>F4</xm:sqref></x14:sparkline>
+                </x14:sparklines>
+            </x14:sparklineGroup>
+        </x14:sparklineGroups></ext></extLst></worksheet>"#;
+
+        let sparklines = parse_sparklines("xl/worksheets/sheet1.xml", xml).unwrap();
+        assert_eq!(
+            sparklines,
+            vec![
+                Sparkline {
+                    range: "F2".to_string(),
+                    source: "Sheet1!A2:E2".to_string(),
+                    kind: "line".to_string(),
+                },
+                Sparkline {
+                    range: "F3".to_string(),
+                    source: "'Q1 & Q2'!A3:E3".to_string(),
+                    kind: "line".to_string(),
+                },
+                Sparkline {
+                    range: "F4".to_string(),
+                    source: "Sheet1!A4:E4".to_string(),
+                    kind: "column".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -3,29 +3,74 @@
 //! calamineを使用したExcelファイル解析の基礎実装。
 //! ストリーミング処理により、メモリ効率的にセルデータを抽出します。
 
-use calamine::{open_workbook_auto_from_rs, Data, Range, Reader, Sheets, Xlsx};
+use calamine::{
+    open_workbook_auto_from_rs, CellErrorType, Data, DataRef, Range, Reader, SheetVisible, Sheets,
+    Xls, Xlsx, XlsxError,
+};
+use std::collections::{HashMap, HashSet};
 use std::io::{Cursor, Read, Seek};
+use std::sync::{Arc, Mutex};
 
-use crate::api::SheetSelector;
+use crate::api::{CellImageMode, FormulaMode, SheetSelector};
 use crate::builder::ConversionConfig;
-use crate::error::XlsxToMdError;
+use crate::error::{SecurityLimit, XlsxToMdError};
 use crate::parser::XlsxMetadataParser;
-use crate::types::{CellCoord, CellRange, CellValue, MergedRegion, RawCellData, SheetMetadata};
+use crate::progress::ProgressEvent;
+use crate::types::{
+    CellCoord, CellError, CellRange, CellValue, DateKind, MergedRegion, RawCellData, SheetMetadata,
+    SheetVisibility,
+};
+
+/// 旧形式（.xls、BIFF）のワークブックの先頭バイト（OLE複合ドキュメントのシグネチャ）
+const XLS_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// 入力データが旧形式（.xls、BIFF）のワークブックかどうかを判定
+///
+/// XMLメタデータ（非表示行・列、ハイパーリンク、リッチテキストなど）はZIP形式のXLSXのみから
+/// 取得するため、旧形式の場合は解析を省略します。
+pub(crate) fn is_legacy_xls(buffer: &[u8]) -> bool {
+    buffer.starts_with(&XLS_SIGNATURE)
+}
+
+/// 行単位で読み込むシートの範囲（`WorkbookParser::scan_sheet()`で取得）
+#[derive(Debug)]
+pub(crate) struct SheetScan {
+    /// シートのメタデータ（`first_row`・`first_col`は値の範囲の左上）
+    pub metadata: SheetMetadata,
+
+    /// 値の範囲の行数
+    height: u32,
+
+    /// 値の範囲の列数
+    width: u32,
+
+    /// 値の範囲の外側にある数式セル（値の範囲の左上を基準とした座標, 数式）（行優先の順）
+    formula_cells: Vec<(CellCoord, String)>,
+
+    /// 出力するグリッドの行数（`LogicalGrid::grid_size()`と同じ規則）
+    pub rows: usize,
+
+    /// 出力するグリッドの列数（`LogicalGrid::grid_size()`と同じ規則）
+    pub cols: usize,
+}
 
 /// ワークブックパーサー
 ///
 /// calamineのラッパーとして、ワークブックレベルの操作を提供します。
 /// Phase IIでは、XlsxMetadataParserを統合してXMLメタデータも取得します。
-pub(crate) struct WorkbookParser<R: Read + Seek + Clone> {
-    /// calamineのワークブック（XLSX形式のみサポート）
-    workbook: Xlsx<R>,
-    /// XMLメタデータパーサー（Phase II）
-    metadata: Option<XlsxMetadataParser>,
+pub(crate) struct WorkbookParser<R: Read + Seek> {
+    /// calamineのワークブック（XLSX形式と旧形式のXLS形式をサポート）
+    workbook: Sheets<R>,
+    /// XMLメタデータパーサー（Phase II、同じワークブックのパーサー間で共有する）
+    metadata: Option<Arc<XlsxMetadataParser>>,
 }
 
 impl WorkbookParser<std::io::Cursor<Vec<u8>>> {
     /// ワークブックを開き、XMLメタデータも解析する（Phase II）
     ///
+    /// 入力データは1回だけメモリに読み込み、複製せずにメタデータの解析とcalamineで共有します。
+    /// ZIPアーカイブの検証（展開を伴うセキュリティチェック）も1回のみ行います。
+    ///
     /// # 引数
     ///
     /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
@@ -34,16 +79,13 @@ impl WorkbookParser<std::io::Cursor<Vec<u8>>> {
     ///
     /// * `Ok(WorkbookParser)` - ワークブックとメタデータの読み込みに成功した場合
     /// * `Err(XlsxToMdError)` - エラーが発生した場合
-    ///
-    /// # 注意
-    ///
-    /// このメソッドは、ファイルを2回読み込む必要があるため、効率が悪い可能性があります。
-    /// 将来的には、1回の読み込みで両方を処理する最適化を検討します。
+    #[allow(dead_code)]
     pub fn open_with_metadata<R: Read + Seek>(mut reader: R) -> Result<Self, XlsxToMdError> {
-        use crate::security::SecurityConfig;
+        use crate::parser::MetadataParts;
+        use crate::security::SecurityLimits;
 
         // セキュリティチェック: 入力ファイルサイズの上限
-        let security_config = SecurityConfig::default();
+        let security_config = SecurityLimits::default();
 
         // ファイル全体をメモリに読み込む（効率化のため）
         // セキュリティ: ファイルサイズ制限を適用
@@ -51,104 +93,186 @@ impl WorkbookParser<std::io::Cursor<Vec<u8>>> {
         let bytes_read = reader.read_to_end(&mut buffer)?;
 
         if bytes_read as u64 > security_config.max_input_file_size {
-            return Err(XlsxToMdError::SecurityViolation(format!(
-                "Input file size exceeds maximum: {} bytes (max: {} bytes)",
-                bytes_read, security_config.max_input_file_size
-            )));
+            return Err(SecurityLimit::InputSize {
+                size: bytes_read as u64,
+                max: security_config.max_input_file_size,
+            }
+            .into());
         }
 
-        // calamineでワークブックを開く
-        let sheets = open_workbook_auto_from_rs(Cursor::new(buffer.clone()))
-            .map_err(XlsxToMdError::Parse)?;
-        let workbook = match sheets {
-            Sheets::Xlsx(workbook) => workbook,
-            _ => {
-                return Err(XlsxToMdError::Config(
-                    "Only XLSX format is supported".to_string(),
-                ))
-            }
-        };
+        // XMLメタデータを解析（入力データを借用し、検証と解析で同じアーカイブを使用する）
+        let metadata = Some(Arc::new(XlsxMetadataParser::with_parts(
+            Cursor::new(buffer.as_slice()),
+            MetadataParts::ALL,
+            &security_config,
+        )?));
 
-        // XMLメタデータを解析
-        let metadata = Some(XlsxMetadataParser::new(Cursor::new(buffer))?);
+        // calamineでワークブックを開く（入力データは複製せずに移動する）
+        let sheets =
+            open_workbook_auto_from_rs(Cursor::new(buffer)).map_err(XlsxToMdError::Parse)?;
+        let workbook = supported_workbook(sheets)?;
 
         Ok(WorkbookParser { workbook, metadata })
     }
+}
+
+impl<R: Read + Seek + Clone> WorkbookParser<R> {
+    /// ワークブックを開く（Phase I互換）
+    ///
+    /// # 引数
+    ///
+    /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seek + Cloneトレイトを実装）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(WorkbookParser)` - ワークブックの読み込みに成功した場合（XLSX形式・XLS形式をサポート）
+    /// * `Err(XlsxToMdError::Parse)` - ワークブックの読み込みに失敗した場合
+    /// * `Err(XlsxToMdError::UnsupportedFormat)` - XLSX形式・XLS形式でない場合
+    pub fn open(reader: R) -> Result<Self, XlsxToMdError> {
+        let sheets = open_workbook_auto_from_rs(reader).map_err(XlsxToMdError::Parse)?;
+        Ok(Self {
+            workbook: supported_workbook(sheets)?,
+            metadata: None, // Phase I: メタデータなし
+        })
+    }
 
     /// ワークブックを開き、既存のメタデータを再利用する
     ///
+    /// リーダーの内容は複製せずに読み込みます（`Cursor<SharedBuffer>`で入力データを共有できます）。
+    /// メタデータは複製せず、参照カウントで他のパーサーと共有します。
+    ///
     /// # 引数
     ///
-    /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seek + Cloneトレイトを実装）
     /// * `metadata` - 再利用するメタデータ
     ///
     /// # 戻り値
     ///
     /// * `Ok(WorkbookParser)` - ワークブックの読み込みに成功した場合
     /// * `Err(XlsxToMdError)` - エラーが発生した場合
-    pub fn open_with_existing_metadata<R: Read + Seek>(
-        mut reader: R,
-        metadata: XlsxMetadataParser,
+    pub fn open_with_existing_metadata(
+        reader: R,
+        metadata: Arc<XlsxMetadataParser>,
     ) -> Result<Self, XlsxToMdError> {
-        use crate::security::SecurityConfig;
+        Ok(Self::open(reader)?.with_metadata(metadata))
+    }
+}
 
-        // セキュリティチェック: 入力ファイルサイズの上限
-        let security_config = SecurityConfig::default();
+/// パーサー間で共有する入力データ
+///
+/// 参照カウントで共有するため、パーサーを開くたびに入力データを複製しません。
+/// `Cursor<SharedBuffer>`はRead + Seek + Cloneを実装するリーダーとして使用できます。
+#[derive(Clone)]
+pub(crate) struct SharedBuffer(Arc<Vec<u8>>);
 
-        // ファイル全体をメモリに読み込む（効率化のため）
-        // セキュリティ: ファイルサイズ制限を適用
-        let mut buffer = Vec::new();
-        let bytes_read = reader.read_to_end(&mut buffer)?;
+impl SharedBuffer {
+    /// 入力データを共有する（データは複製しない）
+    pub fn new(buffer: Vec<u8>) -> Self {
+        Self(Arc::new(buffer))
+    }
+}
 
-        if bytes_read as u64 > security_config.max_input_file_size {
-            return Err(XlsxToMdError::SecurityViolation(format!(
-                "Input file size exceeds maximum: {} bytes (max: {} bytes)",
-                bytes_read, security_config.max_input_file_size
-            )));
-        }
+impl AsRef<[u8]> for SharedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
 
-        // calamineでワークブックを開く
-        let sheets = open_workbook_auto_from_rs(Cursor::new(buffer))
-            .map_err(XlsxToMdError::Parse)?;
-        let workbook = match sheets {
-            Sheets::Xlsx(workbook) => workbook,
-            _ => {
-                return Err(XlsxToMdError::Config(
-                    "Only XLSX format is supported".to_string(),
-                ))
-            }
-        };
+/// シート処理用のワークブックのプール
+///
+/// 並列処理では、メモリ内の入力データを複製せずに共有し、ワークブックを開いたパーサーを
+/// シート間で再利用します。開くパーサーの数は同時に処理するシートの数まで
+/// （逐次処理では1つ）に限られ、シート数に比例してワークブックを開き直すことはありません。
+pub(crate) struct WorkbookParserPool {
+    /// 共有する入力データ
+    buffer: SharedBuffer,
+    /// 解析済みのメタデータ（すべてのパーサーで共有する）
+    metadata: Arc<XlsxMetadataParser>,
+    /// 使用されていないパーサー
+    idle: Mutex<Vec<WorkbookParser<Cursor<SharedBuffer>>>>,
+}
+
+impl WorkbookParserPool {
+    /// 入力データ、解析済みのメタデータ、開いたパーサーからプールを作成
+    ///
+    /// `parser`はプールの最初のパーサーとして再利用します（シートの選択などで開いたパーサー）。
+    /// 追加のパーサーは必要になった時点で開きます。
+    pub fn new(
+        buffer: SharedBuffer,
+        metadata: Arc<XlsxMetadataParser>,
+        parser: WorkbookParser<Cursor<SharedBuffer>>,
+    ) -> Self {
+        Self {
+            buffer,
+            metadata,
+            idle: Mutex::new(vec![parser]),
+        }
+    }
 
-        // 既存のメタデータを再利用
-        let metadata = Some(metadata);
+    /// 解析済みのメタデータを取得
+    pub fn metadata(&self) -> &XlsxMetadataParser {
+        &self.metadata
+    }
 
-        Ok(WorkbookParser { workbook, metadata })
+    /// プールのパーサーを1つ借りて`f`を呼び出す
+    ///
+    /// 使用されていないパーサーがない場合は新しく開きます。`f`の終了後、パーサーはプールに戻します。
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(T)` - `f`の戻り値
+    /// * `Err(XlsxToMdError)` - ワークブックを開けなかった場合、または`f`がエラーを返した場合
+    pub fn with_parser<T>(
+        &self,
+        f: impl FnOnce(&mut WorkbookParser<Cursor<SharedBuffer>>) -> Result<T, XlsxToMdError>,
+    ) -> Result<T, XlsxToMdError> {
+        // ロックが汚染されている場合（他のシートの処理中のパニック）は新しく開く
+        let idle = self.idle.lock().ok().and_then(|mut idle| idle.pop());
+        let mut parser = match idle {
+            Some(parser) => parser,
+            None => WorkbookParser::open_with_existing_metadata(
+                Cursor::new(self.buffer.clone()),
+                Arc::clone(&self.metadata),
+            )?,
+        };
+        let result = f(&mut parser);
+        if let Ok(mut idle) = self.idle.lock() {
+            idle.push(parser);
+        }
+        result
     }
 }
 
-impl<R: Read + Seek + Clone> WorkbookParser<R> {
-    /// ワークブックを開く（Phase I互換）
+impl<R: Read + Seek> WorkbookParser<R> {
+    /// ワークブックをメモリに読み込まずに開き、既存のメタデータを再利用する
+    ///
+    /// 形式の自動判定（リーダーの複製が必要）を行わず、`legacy_xls`に応じて
+    /// XLSX形式またはXLS形式として開くため、`File`などの複製できないリーダーを直接使用できます。
     ///
     /// # 引数
     ///
-    /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seek + Cloneトレイトを実装）
+    /// * `reader` - Excelファイルを読み込むためのリーダー（Read + Seekトレイトを実装）
+    /// * `legacy_xls` - 旧形式（.xls、BIFF）のワークブックかどうか
+    /// * `metadata` - 再利用するメタデータ
     ///
     /// # 戻り値
     ///
-    /// * `Ok(WorkbookParser)` - ワークブックの読み込みに成功した場合（XLSX形式のみサポート）
-    /// * `Err(XlsxToMdError::Parse)` - ワークブックの読み込みに失敗した場合、またはXLSX形式でない場合
-    #[allow(dead_code)]
-    pub fn open(reader: R) -> Result<Self, XlsxToMdError> {
-        let sheets = open_workbook_auto_from_rs(reader).map_err(XlsxToMdError::Parse)?;
-        match sheets {
-            Sheets::Xlsx(workbook) => Ok(Self {
-                workbook,
-                metadata: None, // Phase I: メタデータなし
-            }),
-            _ => Err(XlsxToMdError::Config(
-                "Only XLSX format is supported".to_string(),
-            )),
-        }
+    /// * `Ok(WorkbookParser)` - ワークブックの読み込みに成功した場合
+    /// * `Err(XlsxToMdError::Parse)` - ワークブックの読み込みに失敗した場合
+    pub fn open_unbuffered(
+        reader: R,
+        legacy_xls: bool,
+        metadata: Arc<XlsxMetadataParser>,
+    ) -> Result<Self, XlsxToMdError> {
+        let workbook = if legacy_xls {
+            Sheets::Xls(Xls::new(reader).map_err(|e| XlsxToMdError::Parse(e.into()))?)
+        } else {
+            Sheets::Xlsx(Xlsx::new(reader).map_err(|e| XlsxToMdError::Parse(e.into()))?)
+        };
+        Ok(Self {
+            workbook,
+            metadata: Some(metadata),
+        })
     }
 
     /// すべてのシート名を取得
@@ -160,14 +284,88 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
         self.workbook.sheet_names().to_vec()
     }
 
+    /// シートの表示状態を取得
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    ///
+    /// # 戻り値
+    ///
+    /// シートの表示状態（シートが見つからない場合は表示）
+    pub fn sheet_visibility(&self, sheet_name: &str) -> SheetVisibility {
+        match self
+            .workbook
+            .sheets_metadata()
+            .iter()
+            .find(|sheet| sheet.name == sheet_name)
+            .map(|sheet| sheet.visible)
+        {
+            Some(SheetVisible::Hidden) => SheetVisibility::Hidden,
+            Some(SheetVisible::VeryHidden) => SheetVisibility::VeryHidden,
+            _ => SheetVisibility::Visible,
+        }
+    }
+
+    /// セル範囲の値を文字列として取得（入力規則のリストの参照先の解決用）
+    ///
+    /// 範囲はシートの絶対座標で指定し、シートの使用範囲の外と空のセルは含みません。
+    /// 同じシートの複数の範囲を1回のシートの読み込みで取得します。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `ranges` - セル範囲
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<Vec<String>>)` - 範囲ごとのセルの値（行優先の順）
+    /// * `Err(XlsxToMdError)` - シートの読み込みに失敗した場合
+    pub fn range_values(
+        &mut self,
+        sheet_name: &str,
+        ranges: &[CellRange],
+    ) -> Result<Vec<Vec<String>>, XlsxToMdError> {
+        let sheet = self
+            .workbook
+            .worksheet_range(sheet_name)
+            .map_err(XlsxToMdError::Parse)?;
+        let Some((last_row, last_col)) = sheet.end() else {
+            return Ok(vec![Vec::new(); ranges.len()]);
+        };
+
+        Ok(ranges
+            .iter()
+            .map(|range| {
+                let mut values = Vec::new();
+                for row in range.start.row..=range.end.row.min(last_row) {
+                    for col in range.start.col..=range.end.col.min(last_col) {
+                        match sheet.get_value((row, col)) {
+                            None | Some(Data::Empty) => {}
+                            Some(value) => values.push(value.to_string()),
+                        }
+                    }
+                }
+                values
+            })
+            .collect())
+    }
+
     /// メタデータを取得（並列処理での再利用用）
     ///
     /// # 戻り値
     ///
     /// * `Some(&XlsxMetadataParser)` - メタデータが存在する場合
     /// * `None` - メタデータが存在しない場合
+    #[allow(dead_code)]
     pub fn metadata(&self) -> Option<&XlsxMetadataParser> {
-        self.metadata.as_ref()
+        self.metadata.as_deref()
+    }
+
+    /// 解析済みのメタデータを設定（メタデータは複製せずに共有する）
+    pub fn with_metadata(mut self, metadata: Arc<XlsxMetadataParser>) -> Self {
+        self.metadata = Some(metadata);
+        self
     }
 
     /// シート選択方式に基づいてシートを選択
@@ -180,7 +378,8 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
     /// # 戻り値
     ///
     /// * `Ok(Vec<String>)` - 選択されたシート名のリスト
-    /// * `Err(XlsxToMdError::Config)` - シートが見つからない、またはインデックスが範囲外の場合
+    /// * `Err(XlsxToMdError::SheetNotFound)` - 指定した名前のシートが見つからない場合
+    /// * `Err(XlsxToMdError::SheetIndexOutOfRange)` - インデックスが範囲外の場合
     pub fn select_sheets(
         &self,
         selector: &SheetSelector,
@@ -196,18 +395,19 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
 
             SheetSelector::Index(index) => {
                 if *index >= all_sheet_names.len() {
-                    return Err(XlsxToMdError::Config(format!(
-                        "Sheet index {} is out of range (total: {})",
-                        index,
-                        all_sheet_names.len()
-                    )));
+                    return Err(XlsxToMdError::SheetIndexOutOfRange {
+                        index: *index,
+                        count: all_sheet_names.len(),
+                    });
                 }
                 Ok(vec![all_sheet_names[*index].clone()])
             }
 
             SheetSelector::Name(name) => {
                 if !all_sheet_names.contains(name) {
-                    return Err(XlsxToMdError::Config(format!("Sheet '{}' not found", name)));
+                    return Err(XlsxToMdError::SheetNotFound {
+                        sheet: name.clone(),
+                    });
                 }
                 Ok(vec![name.clone()])
             }
@@ -216,11 +416,10 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
                 let mut result = Vec::new();
                 for &index in indices {
                     if index >= all_sheet_names.len() {
-                        return Err(XlsxToMdError::Config(format!(
-                            "Sheet index {} is out of range (total: {})",
+                        return Err(XlsxToMdError::SheetIndexOutOfRange {
                             index,
-                            all_sheet_names.len()
-                        )));
+                            count: all_sheet_names.len(),
+                        });
                     }
                     result.push(all_sheet_names[index].clone());
                 }
@@ -230,7 +429,9 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
             SheetSelector::Names(names) => {
                 for name in names {
                     if !all_sheet_names.contains(name) {
-                        return Err(XlsxToMdError::Config(format!("Sheet '{}' not found", name)));
+                        return Err(XlsxToMdError::SheetNotFound {
+                            sheet: name.clone(),
+                        });
                     }
                 }
                 Ok(names.clone())
@@ -258,10 +459,10 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
         let range = self
             .workbook
             .worksheet_range(sheet_name)
-            .map_err(|e| XlsxToMdError::Parse(e.into()))?;
+            .map_err(XlsxToMdError::Parse)?;
 
         // 2. メタデータの収集
-        let metadata = self.collect_metadata(sheet_name)?;
+        let mut metadata = self.sheet_metadata(sheet_name, config)?;
 
         // 3. 数式情報を事前に取得（全セルで再利用するため）
         // 注意: 各セルごとにworksheet_formula()を呼び出すと非常に遅いため、
@@ -269,40 +470,436 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
         let formula_range = self.workbook.worksheet_formula(sheet_name).ok();
 
         // 4. セルデータの抽出（ストリーミング処理）
+        // 値の範囲の左上を基準とした相対座標でセルを扱う
+        // 数式範囲は値の範囲と開始位置が異なる場合があるため、絶対座標で参照する
+        let origin = range.start().unwrap_or((0, 0));
+        metadata.first_row = origin.0;
+        metadata.first_col = origin.1;
         let mut cells = Vec::new();
 
+        let total_rows = range.height();
         for (row_idx, row) in range.rows().enumerate() {
+            // 指定した行数ごとに、解析済みの行数を通知
+            if row_idx > 0 && row_idx.is_multiple_of(config.progress_interval) {
+                config.emit_progress(ProgressEvent::RowsProcessed {
+                    sheet: sheet_name,
+                    rows: row_idx,
+                    total_rows,
+                });
+            }
             let row_idx = row_idx as u32;
 
-            // 非表示行のスキップ（Phase I: hidden_rowsは常に空リスト）
-            if !config.include_hidden && metadata.hidden_rows.contains(&row_idx) {
+            // 非表示行のスキップ（非表示行はシートの絶対座標で保持されている）
+            if !config.include_hidden && metadata.hidden_rows.contains(&(origin.0 + row_idx)) {
                 continue;
             }
 
             for (col_idx, cell) in row.iter().enumerate() {
-                let col_idx = col_idx as u32;
+                let coord = CellCoord::new(row_idx, col_idx as u32);
+                if Self::is_excluded(coord, &metadata, config) {
+                    continue;
+                }
+
+                // RawCellDataの生成
+                let formula = Self::lookup_formula(&formula_range, origin, coord);
+                let raw_cell =
+                    self.extract_cell_data_with_formula(coord, cell, sheet_name, origin, formula)?;
+                cells.push(raw_cell);
+            }
+        }
+
+        // 5. 値の範囲外にある数式セル（キャッシュ値を持たない数式セル）を追加
+        if let Some(ref formula_range) = formula_range {
+            let (height, width) = (range.height() as u32, range.width() as u32);
+            let formula_origin = formula_range.start().unwrap_or((0, 0));
+            for (row, col, formula) in formula_range.used_cells() {
+                let abs_row = formula_origin.0 + row as u32;
+                let abs_col = formula_origin.1 + col as u32;
+                if formula.is_empty() || abs_row < origin.0 || abs_col < origin.1 {
+                    continue;
+                }
 
-                // 非表示列のスキップ（Phase I: hidden_colsは常に空リスト）
-                if !config.include_hidden && metadata.hidden_cols.contains(&col_idx) {
+                let coord = CellCoord::new(abs_row - origin.0, abs_col - origin.1);
+                if (coord.row < height && coord.col < width)
+                    || (!config.include_hidden && metadata.hidden_rows.contains(&abs_row))
+                    || Self::is_excluded(coord, &metadata, config)
+                {
                     continue;
                 }
 
-                let coord = CellCoord::new(row_idx, col_idx);
+                let raw_cell = self.extract_cell_data_with_formula(
+                    coord,
+                    &Data::Empty,
+                    sheet_name,
+                    origin,
+                    Some(formula.clone()),
+                )?;
+                cells.push(raw_cell);
+            }
+        }
+
+        // 6. セル内の画像の値を置き換える
+        Self::replace_cell_images(
+            self.metadata.as_deref(),
+            sheet_name,
+            origin,
+            &mut cells,
+            &config.cell_image_mode,
+        );
+
+        // 7. 動的配列数式の溢れた範囲のセルに起点を示す（数式モードのみ）
+        if config.formula_mode == FormulaMode::Formula {
+            self.annotate_spill_cells(sheet_name, origin, &mut cells);
+        }
+
+        Ok((metadata, cells))
+    }
+
+    /// シートを行単位で読み込むための範囲を走査
+    ///
+    /// セルデータを保持せずにワークシートのXMLを読み込み、`parse_sheet()`と同じ規則で
+    /// 値の範囲（値のあるセルを囲む範囲）と、出力するグリッドの行数・列数を求めます。
+    /// 値の範囲の外側にある数式セル（キャッシュ値を持たない数式セル）の座標のみを保持します。
+    /// 行単位の読み込みには`stream_rows()`を使用します。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `config` - 変換設定
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Some(SheetScan))` - 走査したシートの範囲
+    /// * `Ok(None)` - 行単位で読み込めないシートの場合（旧形式のワークブック、グラフシートなど）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    pub fn scan_sheet(
+        &mut self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+    ) -> Result<Option<SheetScan>, XlsxToMdError> {
+        let mut metadata = self.sheet_metadata(sheet_name, config)?;
+        let Sheets::Xlsx(workbook) = &mut self.workbook else {
+            return Ok(None);
+        };
+
+        // 1. 値の範囲（`worksheet_range()`と同じく、値のあるセルを囲む範囲）
+        let mut reader = match workbook.worksheet_cells_reader(sheet_name) {
+            Ok(reader) => reader,
+            Err(XlsxError::NotAWorksheet(_)) => return Ok(None),
+            Err(e) => return Err(XlsxToMdError::Parse(e.into())),
+        };
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        while let Some(cell) = reader
+            .next_cell()
+            .map_err(|e| XlsxToMdError::Parse(e.into()))?
+        {
+            if matches!(cell.get_value(), DataRef::Empty) {
+                continue;
+            }
+            let (row, col) = cell.get_position();
+            bounds = Some(match bounds {
+                None => (row, row, col, col),
+                Some((first_row, _, first_col, last_col)) => {
+                    (first_row, row, first_col.min(col), last_col.max(col))
+                }
+            });
+        }
+        drop(reader);
+        let (origin, height, width) = match bounds {
+            Some((first_row, last_row, first_col, last_col)) => (
+                (first_row, first_col),
+                last_row.saturating_sub(first_row) + 1,
+                last_col - first_col + 1,
+            ),
+            None => ((0, 0), 0, 0),
+        };
+        metadata.first_row = origin.0;
+        metadata.first_col = origin.1;
+
+        // 2. 値の範囲外にある数式セル（読み込めない場合は`parse_sheet()`で処理する）
+        let Ok(mut reader) = workbook.worksheet_cells_reader(sheet_name) else {
+            return Ok(None);
+        };
+        let mut formula_cells = Vec::new();
+        loop {
+            let cell = match reader.next_formula() {
+                Ok(Some(cell)) => cell,
+                Ok(None) => break,
+                Err(_) => return Ok(None),
+            };
+            let (abs_row, abs_col) = cell.get_position();
+            if cell.get_value().is_empty() || abs_row < origin.0 || abs_col < origin.1 {
+                continue;
+            }
+            let coord = CellCoord::new(abs_row - origin.0, abs_col - origin.1);
+            if (coord.row < height && coord.col < width)
+                || (!config.include_hidden && metadata.hidden_rows.contains(&abs_row))
+                || Self::is_excluded(coord, &metadata, config)
+            {
+                continue;
+            }
+            formula_cells.push((coord, cell.get_value().clone()));
+        }
+        formula_cells.sort_by_key(|(coord, _)| (coord.row, coord.col));
+
+        // 3. グリッドの行数・列数（`LogicalGrid::grid_size()`と同じく、出力するセルの最大の座標）
+        let visible =
+            |row: u32| config.include_hidden || !metadata.hidden_rows.contains(&(origin.0 + row));
+        let included = |row: u32, col: u32| {
+            visible(row) && !Self::is_excluded(CellCoord::new(row, col), &metadata, config)
+        };
+        let last_row = (0..height)
+            .rev()
+            .find(|&row| (0..width).any(|col| included(row, col)));
+        let last_col = (0..width)
+            .rev()
+            .find(|&col| (0..height).any(|row| included(row, col)));
+        let rows = last_row
+            .into_iter()
+            .chain(formula_cells.iter().map(|(coord, _)| coord.row))
+            .max()
+            .unwrap_or(0);
+        let cols = last_col
+            .into_iter()
+            .chain(formula_cells.iter().map(|(coord, _)| coord.col))
+            .max()
+            .unwrap_or(0);
+
+        Ok(Some(SheetScan {
+            metadata,
+            height,
+            width,
+            formula_cells,
+            rows: rows as usize + 1,
+            cols: cols as usize + 1,
+        }))
+    }
+
+    /// 走査したシートのセルデータを1行ずつ読み込む
+    ///
+    /// `scan_sheet()`で求めたグリッドの各行について、`parse_sheet()`が抽出するセルと同じ
+    /// セルデータを行優先の順で`visit`に渡します（非表示行や範囲制限の外側の行は空の行）。
+    /// シート全体のセルデータを保持しないため、メモリ使用量は1行分のセルデータに比例します。
+    ///
+    /// 値の範囲内のセルの数式は取得しません（`FormulaMode::CachedValue`で
+    /// `NullFormulaPolicy::Empty`の場合と同じ出力になります）。
+    /// 動的配列数式の溢れた範囲の注記も行いません。
+    ///
+    /// # 引数
+    ///
+    /// * `sheet_name` - シート名
+    /// * `config` - 変換設定
+    /// * `scan` - `scan_sheet()`で走査したシートの範囲
+    /// * `visit` - 行番号（グリッドの先頭を0とする）と、その行のセルデータを受け取る関数
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - すべての行を読み込んだ場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（`visit`のエラーを含む）
+    pub fn stream_rows<F>(
+        &mut self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+        scan: &SheetScan,
+        mut visit: F,
+    ) -> Result<(), XlsxToMdError>
+    where
+        F: FnMut(usize, &[RawCellData]) -> Result<(), XlsxToMdError>,
+    {
+        let metadata = self.metadata.as_deref();
+        let Sheets::Xlsx(workbook) = &mut self.workbook else {
+            return Ok(());
+        };
+        let mut reader = workbook
+            .worksheet_cells_reader(sheet_name)
+            .map_err(|e| XlsxToMdError::Parse(e.into()))?;
+
+        let origin = (scan.metadata.first_row, scan.metadata.first_col);
+        let mut pending = reader
+            .next_cell()
+            .map_err(|e| XlsxToMdError::Parse(e.into()))?;
+        let mut formula_cells = scan.formula_cells.iter().peekable();
+        let mut values = vec![Data::Empty; scan.width as usize];
+        let mut cells = Vec::with_capacity(scan.cols);
+        for row_idx in 0..scan.rows as u32 {
+            // 値の範囲内の行のセルを読み込む（前の行、範囲外の列のセルは読み飛ばす）
+            let abs_row = origin.0 + row_idx;
+            values.fill(Data::Empty);
+            while let Some(cell) = pending.take() {
+                let (row, col) = cell.get_position();
+                if row > abs_row {
+                    pending = Some(cell);
+                    break;
+                }
+                let col = col.wrapping_sub(origin.1);
+                if row == abs_row && col < scan.width && !matches!(cell.get_value(), DataRef::Empty)
+                {
+                    values[col as usize] = Data::from(cell.get_value().clone());
+                }
+                pending = reader
+                    .next_cell()
+                    .map_err(|e| XlsxToMdError::Parse(e.into()))?;
+            }
 
-                // 範囲制限のチェック
-                if let Some(range) = &config.range {
-                    if !range.contains(coord) {
+            // 非表示行と範囲制限の外側のセルを除き、`parse_sheet()`と同じセルデータを生成
+            cells.clear();
+            let visible = config.include_hidden || !scan.metadata.hidden_rows.contains(&abs_row);
+            if visible && row_idx < scan.height {
+                for (col_idx, value) in values.iter().enumerate() {
+                    let coord = CellCoord::new(row_idx, col_idx as u32);
+                    if Self::is_excluded(coord, &scan.metadata, config) {
                         continue;
                     }
+                    cells.push(cell_data(metadata, coord, value, sheet_name, origin, None));
                 }
+            }
+            while let Some((coord, formula)) =
+                formula_cells.next_if(|(coord, _)| coord.row == row_idx)
+            {
+                cells.push(cell_data(
+                    metadata,
+                    *coord,
+                    &Data::Empty,
+                    sheet_name,
+                    origin,
+                    Some(formula.clone()),
+                ));
+            }
+            Self::replace_cell_images(
+                metadata,
+                sheet_name,
+                origin,
+                &mut cells,
+                &config.cell_image_mode,
+            );
 
-                // RawCellDataの生成
-                let raw_cell = self.extract_cell_data_with_formula(coord, cell, sheet_name, &formula_range)?;
-                cells.push(raw_cell);
+            visit(row_idx as usize, &cells)?;
+        }
+        Ok(())
+    }
+
+    /// シートのメタデータを取得（内部ヘルパーメソッド）
+    ///
+    /// 高さ・幅が0の行・列は、設定した場合のみ非表示の行・列に加えます。
+    fn sheet_metadata(
+        &mut self,
+        sheet_name: &str,
+        config: &ConversionConfig,
+    ) -> Result<SheetMetadata, XlsxToMdError> {
+        let mut metadata = self.collect_metadata(sheet_name)?;
+        if config.zero_size_as_hidden {
+            metadata
+                .hidden_rows
+                .extend(metadata.zero_size_rows.iter().copied());
+            metadata
+                .hidden_cols
+                .extend(metadata.zero_size_cols.iter().copied());
+        } else {
+            metadata.zero_size_rows.clear();
+            metadata.zero_size_cols.clear();
+        }
+        Ok(metadata)
+    }
+
+    /// セル内の画像のセルの値を、出力方式に応じた文字列に置き換える（内部ヘルパーメソッド）
+    ///
+    /// セル内の画像には値として`#VALUE!`エラーが保存されているため、
+    /// `CellImageMode::Value`以外では文字列の値に置き換えます。数式はそのまま保持します。
+    fn replace_cell_images(
+        metadata: Option<&XlsxMetadataParser>,
+        sheet_name: &str,
+        origin: (u32, u32),
+        cells: &mut [RawCellData],
+        mode: &CellImageMode,
+    ) {
+        let Some(metadata) = metadata else {
+            return;
+        };
+        for cell in cells {
+            // メタデータはシートの絶対座標で保持されている
+            let (row, col) = (origin.0 + cell.coord.row, origin.1 + cell.coord.col);
+            let Some(target) = metadata.get_cell_image(sheet_name, row, col) else {
+                continue;
+            };
+            let text = match (mode, target) {
+                (CellImageMode::Placeholder(text), _) => text.clone(),
+                (CellImageMode::Reference, Some(target)) => format!("[image: {}]", target),
+                (CellImageMode::Reference, None) => "[image]".to_string(),
+                _ => continue,
+            };
+            cell.value = CellValue::String(text);
+            cell.rich_text = None;
+        }
+    }
+
+    /// 動的配列数式の溢れた範囲にあるセルに、起点のセルを示す注記を設定する（内部ヘルパーメソッド）
+    ///
+    /// 動的配列数式（FILTER、SEQUENCEなど）の数式は起点のセルにのみ保存され、
+    /// 溢れた範囲のセルはキャッシュ値のみを持ちます。数式モードでは、これらのセルの数式として
+    /// `↳ spill of B2`のような注記を設定します。
+    fn annotate_spill_cells(
+        &self,
+        sheet_name: &str,
+        origin: (u32, u32),
+        cells: &mut [RawCellData],
+    ) {
+        let Some(metadata) = self.metadata.as_ref() else {
+            return;
+        };
+        for cell in cells.iter_mut().filter(|cell| cell.formula.is_none()) {
+            let (row, col) = (origin.0 + cell.coord.row, origin.1 + cell.coord.col);
+            if let Some((anchor_row, anchor_col)) = metadata.get_spill_anchor(sheet_name, row, col)
+            {
+                let anchor = CellCoord::new(anchor_row, anchor_col);
+                cell.formula = Some(format!("↳ spill of {}", anchor.to_a1_notation()));
             }
         }
+    }
 
-        Ok((metadata, cells))
+    /// セルを出力対象から除外するかを判定（内部ヘルパーメソッド）
+    ///
+    /// 非表示列、および範囲制限外のセルを除外します。
+    fn is_excluded(coord: CellCoord, metadata: &SheetMetadata, config: &ConversionConfig) -> bool {
+        // 非表示列のスキップ（非表示列はシートの絶対座標で保持されている）
+        if !config.include_hidden
+            && metadata
+                .hidden_cols
+                .contains(&(metadata.first_col + coord.col))
+        {
+            return true;
+        }
+
+        // 範囲制限のチェック（範囲はシートの絶対座標で指定されている）
+        if let Some(range) = &config.range {
+            let abs = CellCoord::new(
+                metadata.first_row + coord.row,
+                metadata.first_col + coord.col,
+            );
+            if !range.contains(abs) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// セルの数式を取得（内部ヘルパーメソッド）
+    ///
+    /// # 引数
+    ///
+    /// * `formula_range` - 事前に取得した数式範囲
+    /// * `origin` - 値の範囲の開始位置（絶対座標）
+    /// * `coord` - 値の範囲の開始位置を基準としたセル座標
+    fn lookup_formula(
+        formula_range: &Option<Range<String>>,
+        origin: (u32, u32),
+        coord: CellCoord,
+    ) -> Option<String> {
+        let formula_range = formula_range.as_ref()?;
+        formula_range
+            .get_value((origin.0 + coord.row, origin.1 + coord.col))
+            .filter(|f| !f.is_empty())
+            .cloned()
     }
 
     /// セルデータを抽出（内部ヘルパーメソッド）
@@ -323,17 +920,18 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
         cell: &Data,
         sheet_name: &str,
     ) -> Result<RawCellData, XlsxToMdError> {
-        self.extract_cell_data_with_formula(coord, cell, sheet_name, &None)
+        self.extract_cell_data_with_formula(coord, cell, sheet_name, (0, 0), None)
     }
 
-    /// セルデータを抽出（数式範囲を事前に取得したバージョン）
+    /// セルデータを抽出（数式を事前に取得したバージョン）
     ///
     /// # 引数
     ///
     /// * `coord` - セル座標
     /// * `cell` - calamineのセルデータ
     /// * `sheet_name` - シート名（数式取得用）
-    /// * `formula_range` - 事前に取得した数式範囲（Noneの場合は取得を試みる）
+    /// * `origin` - 値の範囲の左上のシート上の絶対座標（メタデータの参照用）
+    /// * `formula` - 事前に取得した数式文字列（数式セルでない場合は`None`）
     ///
     /// # 戻り値
     ///
@@ -343,88 +941,17 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
         coord: CellCoord,
         cell: &Data,
         sheet_name: &str,
-        formula_range: &Option<Range<String>>,
+        origin: (u32, u32),
+        formula: Option<String>,
     ) -> Result<RawCellData, XlsxToMdError> {
-        // 1. 値の変換
-        let value = match cell {
-            Data::Int(i) => CellValue::Number(*i as f64),
-            Data::Float(f) => CellValue::Number(*f),
-            Data::String(s) => CellValue::String(s.clone()),
-            Data::Bool(b) => CellValue::Bool(*b),
-            Data::Error(e) => CellValue::Error(format!("{:?}", e)),
-            Data::Empty => CellValue::Empty,
-            _ => CellValue::Empty,
-        };
-
-        // 2. 書式情報の取得
-        // Phase II: XlsxMetadataParserでxl/styles.xmlから取得
-        let (format_id, format_string) = if let Some(ref metadata) = self.metadata {
-            // calamineからstyle_idを取得（現在は未対応のため、None）
-            // 将来的には、calamineのAPI拡張を待つか、XMLから直接取得する必要がある
-            let style_id = None; // TODO: calamineからstyle_idを取得
-            if let Some(style_id) = style_id {
-                let fmt_str = metadata.get_format_string(style_id);
-                (Some(style_id as u16), fmt_str.map(|s| s.to_string()))
-            } else {
-                (None, None)
-            }
-        } else {
-            (None, None) // Phase I: メタデータなし
-        };
-
-        // 3. 数式情報の取得
-        // Phase I: calamine 0.26以降のworksheet_formula() APIで取得可能
-        // 事前に取得した数式範囲を使用（各セルごとに呼び出すと非常に遅い）
-        let formula = if let Some(ref formula_range) = formula_range {
-            // 座標をcalamineの形式に変換（(row, col)）
-            let calamine_coord = (coord.row as usize, coord.col as usize);
-            formula_range.get(calamine_coord).and_then(|f| {
-                if f.is_empty() {
-                    None
-                } else {
-                    Some(f.clone())
-                }
-            })
-        } else {
-            None
-        };
-
-        // 4. ハイパーリンク情報の取得
-        // Phase II: XlsxMetadataParserでxl/worksheets/*.xmlと_rels/*.xml.relsから取得
-        let hyperlink = if let Some(ref metadata) = self.metadata {
-            metadata.hyperlinks.get(sheet_name).and_then(|sheet_links| {
-                sheet_links
-                    .get(&(coord.row, coord.col))
-                    .map(|h| h.url.clone())
-            })
-        } else {
-            None
-        };
-
-        // 5. リッチテキスト情報の取得
-        // Phase II: XlsxMetadataParserでxl/sharedStrings.xmlとxl/worksheets/*.xmlから取得
-        let rich_text = if let Some(ref metadata) = self.metadata {
-            metadata
-                .cell_string_indices
-                .get(sheet_name)
-                .and_then(|sheet_indices| {
-                    sheet_indices
-                        .get(&(coord.row, coord.col))
-                        .and_then(|index| metadata.shared_strings.get(index).cloned())
-                })
-        } else {
-            None
-        };
-
-        Ok(RawCellData {
+        Ok(cell_data(
+            self.metadata.as_deref(),
             coord,
-            value,
-            format_id,
-            format_string,
+            cell,
+            sheet_name,
+            origin,
             formula,
-            hyperlink,
-            rich_text,
-        })
+        ))
     }
 
     /// シートのメタデータを収集
@@ -444,30 +971,40 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
             .sheet_names()
             .iter()
             .position(|name| name == sheet_name)
-            .ok_or_else(|| XlsxToMdError::Config(format!("Sheet '{}' not found", sheet_name)))?;
+            .ok_or_else(|| XlsxToMdError::SheetNotFound {
+                sheet: sheet_name.to_string(),
+            })?;
 
-        // 2. 非表示フラグの取得
-        // Phase I: calamine APIで非表示シート情報は未サポート
-        // Phase II: XlsxMetadataParserで取得予定
-        let hidden = false; // Phase I: false固定
+        // 2. 非表示フラグの取得（`hidden`・`veryHidden`のいずれも非表示）
+        let hidden = self.sheet_visibility(sheet_name) != SheetVisibility::Visible;
 
         // 3. 結合セル範囲の取得
         // Phase I: calamine 0.26以降で完全対応
-        self.workbook
-            .load_merged_regions()
-            .map_err(|e| XlsxToMdError::Parse(e.into()))?;
-        let merged_regions = match self.workbook.worksheet_merge_cells(sheet_name) {
-            Some(Ok(regions)) => regions
-                .iter()
-                .map(|dims| {
-                    let start = CellCoord::new(dims.start.0, dims.start.1);
-                    let end = CellCoord::new(dims.end.0, dims.end.1);
-                    let range = CellRange::new(start, end);
-                    MergedRegion::new(range)
-                })
-                .collect(),
-            Some(Err(_)) | None => Vec::new(),
+        let regions = match &mut self.workbook {
+            Sheets::Xlsx(workbook) => {
+                workbook
+                    .load_merged_regions()
+                    .map_err(|e| XlsxToMdError::Parse(e.into()))?;
+                match workbook.worksheet_merge_cells(sheet_name) {
+                    Some(Ok(regions)) => regions,
+                    Some(Err(_)) | None => Vec::new(),
+                }
+            }
+            // 旧形式はワークブックを開いた時点で結合セル範囲を読み込み済み
+            Sheets::Xls(workbook) => workbook
+                .worksheet_merge_cells(sheet_name)
+                .unwrap_or_default(),
+            _ => Vec::new(),
         };
+        let merged_regions = regions
+            .iter()
+            .map(|dims| {
+                let start = CellCoord::new(dims.start.0, dims.start.1);
+                let end = CellCoord::new(dims.end.0, dims.end.1);
+                let range = CellRange::new(start, end);
+                MergedRegion::new(range)
+            })
+            .collect();
 
         // 4. 非表示行・列のリスト
         // Phase II: XlsxMetadataParserでxl/worksheets/*.xmlから取得
@@ -487,6 +1024,25 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
             (Vec::new(), Vec::new()) // Phase I: 空リスト
         };
 
+        // 高さ・幅が0の行・列（非表示として扱うかは変換設定に応じて`parse_sheet()`で判定）
+        let (zero_size_rows, zero_size_cols) = match self.metadata {
+            Some(ref metadata) => {
+                let sorted = |sets: &HashMap<String, HashSet<u32>>| {
+                    let mut indices: Vec<u32> = sets
+                        .get(sheet_name)
+                        .map(|set| set.iter().copied().collect())
+                        .unwrap_or_default();
+                    indices.sort_unstable();
+                    indices
+                };
+                (
+                    sorted(&metadata.zero_size_rows),
+                    sorted(&metadata.zero_size_cols),
+                )
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
         // 5. 1904年エポックフラグ
         // Phase II: XlsxMetadataParserでxl/workbook.xmlから取得
         let is_1904 = self.metadata.as_ref().map(|m| m.is_1904()).unwrap_or(false); // Phase I: デフォルトはfalse
@@ -499,9 +1055,198 @@ impl<R: Read + Seek + Clone> WorkbookParser<R> {
             hidden_rows,
             hidden_cols,
             is_1904,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows,
+            zero_size_cols,
         })
     }
 }
 
+/// セルデータを生成（内部ヘルパー）
+///
+/// calamineのセルの値を変換し、XMLメタデータから書式、ハイパーリンク、リッチテキスト、
+/// スタイル名、フォント、背景色を取得します。
+///
+/// # 引数
+///
+/// * `metadata` - XMLメタデータ（Noneの場合は値のみ）
+/// * `coord` - 値の範囲の左上を基準としたセル座標
+/// * `cell` - calamineのセルデータ
+/// * `sheet_name` - シート名
+/// * `origin` - 値の範囲の左上のシート上の絶対座標（メタデータの参照用）
+/// * `formula` - 事前に取得した数式文字列（数式セルでない場合は`None`）
+fn cell_data(
+    metadata: Option<&XlsxMetadataParser>,
+    coord: CellCoord,
+    cell: &Data,
+    sheet_name: &str,
+    origin: (u32, u32),
+    formula: Option<String>,
+) -> RawCellData {
+    // メタデータはシートの絶対座標で保持されている
+    let (row, col) = (origin.0 + coord.row, origin.1 + coord.col);
+
+    // 1. 書式情報の取得
+    // Phase II: XlsxMetadataParserでxl/styles.xmlとxl/worksheets/*.xml（セルの`s`属性）から取得
+    let (format_id, format_string) = match metadata {
+        Some(metadata) => match metadata.get_cell_style_id(sheet_name, row, col) {
+            Some(style_id) => (
+                metadata.get_num_fmt_id(style_id),
+                metadata.get_format_string(style_id).map(|s| s.to_string()),
+            ),
+            None => (None, None),
+        },
+        None => (None, None), // Phase I: メタデータなし
+    };
+
+    // 2. 値の変換
+    // 日付・時刻書式のセルは、書式から日付・時刻の種別を判定する
+    let date_kind = DateKind::from_format(format_id, format_string.as_deref());
+    let value = match cell {
+        Data::Int(i) => number_or_date(*i as f64, date_kind),
+        Data::Float(f) => number_or_date(*f, date_kind),
+        Data::DateTime(dt) => {
+            // calamineが日付・時刻書式と判定したセル
+            // 経過時間書式（[h]:mm:ssなど）は時刻として扱う
+            let serial = dt.as_f64();
+            let kind = if dt.is_duration() {
+                DateKind::Time
+            } else {
+                date_kind.unwrap_or_else(|| DateKind::from_serial(serial))
+            };
+            CellValue::DateTime { serial, kind }
+        }
+        Data::String(s) => CellValue::String(s.clone()),
+        Data::Bool(b) => CellValue::Bool(*b),
+        Data::Error(e) => CellValue::Error(cell_error(e)),
+        Data::Empty => CellValue::Empty,
+        _ => CellValue::Empty,
+    };
+
+    // 3. ハイパーリンク情報の取得
+    // Phase II: XlsxMetadataParserでxl/worksheets/*.xmlと_rels/*.xml.relsから取得
+    let hyperlink = if let Some(metadata) = metadata {
+        metadata
+            .hyperlinks
+            .get(sheet_name)
+            .and_then(|sheet_links| sheet_links.get(&(row, col)).cloned())
+    } else {
+        None
+    };
+
+    // 4. リッチテキスト情報の取得
+    // Phase II: XlsxMetadataParserでxl/sharedStrings.xmlとxl/worksheets/*.xmlから取得
+    let rich_text = metadata
+        .and_then(|metadata| metadata.get_cell_rich_text(sheet_name, row, col))
+        .map(|segments| segments.to_vec());
+
+    // 5. 名前付きスタイル名の取得
+    // Phase II: XlsxMetadataParserでxl/styles.xmlとxl/worksheets/*.xmlから取得
+    let style_name = metadata.and_then(|metadata| {
+        metadata
+            .get_cell_style_name(sheet_name, row, col)
+            .map(|name| name.to_string())
+    });
+
+    // 6. セル単位のフォントの書式の取得
+    // Phase II: XlsxMetadataParserでxl/styles.xml（`<fonts>`）とxl/worksheets/*.xmlから取得
+    let font = metadata.and_then(|metadata| metadata.get_cell_font(sheet_name, row, col).cloned());
+
+    // 7. セルの背景色の取得
+    // Phase II: XlsxMetadataParserでxl/styles.xml（`<fills>`）とxl/worksheets/*.xmlから取得
+    let fill_color = metadata.and_then(|metadata| {
+        metadata
+            .get_cell_fill_color(sheet_name, row, col)
+            .map(|color| color.to_string())
+    });
+
+    RawCellData {
+        coord,
+        value,
+        format_id,
+        format_string,
+        formula,
+        hyperlink,
+        rich_text,
+        style_name,
+        font,
+        fill_color,
+    }
+}
+
+/// 数値セルの値を生成（日付・時刻書式の場合は日付・時刻として扱う）
+fn number_or_date(value: f64, date_kind: Option<DateKind>) -> CellValue {
+    match date_kind {
+        Some(kind) => CellValue::DateTime {
+            serial: value,
+            kind,
+        },
+        None => CellValue::Number(value),
+    }
+}
+
+/// 対応する形式（XLSX・XLS）のワークブックのみを受け付ける
+fn supported_workbook<RS>(sheets: Sheets<RS>) -> Result<Sheets<RS>, XlsxToMdError> {
+    let format = match sheets {
+        Sheets::Xlsx(_) | Sheets::Xls(_) => return Ok(sheets),
+        Sheets::Xlsb(_) => "XLSB",
+        Sheets::Ods(_) => "ODS",
+    };
+    Err(XlsxToMdError::UnsupportedFormat {
+        format: format.to_string(),
+    })
+}
+
+/// calamineのエラー値を変換
+fn cell_error(error: &CellErrorType) -> CellError {
+    match error {
+        CellErrorType::Div0 => CellError::Div0,
+        CellErrorType::NA => CellError::NA,
+        CellErrorType::Name => CellError::Name,
+        CellErrorType::Null => CellError::Null,
+        CellErrorType::Num => CellError::Num,
+        CellErrorType::Ref => CellError::Ref,
+        CellErrorType::Value => CellError::Value,
+        CellErrorType::GettingData => CellError::GettingData,
+    }
+}
+
 // テストは統合テスト（tests/）で実装します。
 // 実際のXLSXファイルが必要なため、単体テストではなく統合テストとして実装します。
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser_pool_reuses_parser() {
+        let mut workbook = rust_xlsxwriter::Workbook::new();
+        workbook.add_worksheet().write_string(0, 0, "A").unwrap();
+        workbook.add_worksheet().write_string(0, 0, "B").unwrap();
+        let buffer = workbook.save_to_buffer().unwrap();
+        let metadata = Arc::new(XlsxMetadataParser::new(Cursor::new(buffer.as_slice())).unwrap());
+        let buffer = SharedBuffer::new(buffer);
+        let parser = WorkbookParser::open_with_existing_metadata(
+            Cursor::new(buffer.clone()),
+            metadata.clone(),
+        )
+        .unwrap();
+
+        let pool = WorkbookParserPool::new(buffer, metadata, parser);
+        for sheet in ["Sheet1", "Sheet2"] {
+            let names = pool
+                .with_parser(|parser| Ok(parser.get_sheet_names()))
+                .unwrap();
+            assert!(names.iter().any(|name| name == sheet));
+        }
+        // 逐次に借りたパーサーは1つだけ開かれ、プールに戻されている
+        assert_eq!(pool.idle.lock().unwrap().len(), 1);
+
+        // エラーの場合もパーサーはプールに戻す
+        let result: Result<(), _> =
+            pool.with_parser(|_| Err(XlsxToMdError::Internal("failed".to_string())));
+        assert!(result.is_err());
+        assert_eq!(pool.idle.lock().unwrap().len(), 1);
+    }
+}
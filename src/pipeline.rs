@@ -0,0 +1,637 @@
+//! Pipeline Module
+//!
+//! シート単位の変換処理を構成するステージ（Parse → Format → GridBuild → Render）と、
+//! 各ステージの後に独自の処理を挿入するための拡張ポイントを提供するモジュール。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::error::XlsxToMdError;
+use crate::grid::LogicalGrid;
+use crate::types::{CellCoord, CellValue, RawCellData};
+
+/// 変換パイプラインのステージ
+///
+/// 各シートは以下の順に処理されます。`PipelineStage`はいずれかのステージの
+/// 完了直後に呼び出されます。
+///
+/// 1. `Parse`: ワークシートからセル値を抽出
+/// 2. `Format`: セル値を表示文字列にフォーマット
+/// 3. `GridBuild`: 稠密なグリッドを構築（セル結合の処理を含む）
+/// 4. `Render`: 出力フォーマットにレンダリング
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Stage {
+    /// セル値の抽出後
+    ///
+    /// `StageCell::value`の変更が後続の処理に反映されます（表示文字列は未生成）。
+    Parse,
+
+    /// 表示文字列のフォーマット後
+    ///
+    /// `StageCell::value`と`StageCell::text`の変更が後続の処理に反映されます。
+    Format,
+
+    /// グリッドの構築後
+    ///
+    /// 結合セルの複製を含む、すべてのグリッドセルが対象です。
+    /// `StageCell::value`と`StageCell::text`の変更が出力に反映されます。
+    GridBuild,
+
+    /// 出力フォーマットへのレンダリング後
+    ///
+    /// `StageContext::output_mut()`でシートの出力文字列を変更できます。
+    Render,
+}
+
+/// パイプラインに挿入する独自の処理
+///
+/// `ConverterBuilder::with_stage()`で登録し、指定したステージの完了後に
+/// シートごとに呼び出されます。シートは並列に処理されるため、`Send + Sync`が必要です。
+///
+/// `Fn(&mut StageContext) -> Result<(), XlsxToMdError>`を満たすクロージャも
+/// `PipelineStage`として使用できます。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::{CellValue, PipelineStage, Stage, StageContext, XlsxToMdError};
+///
+/// /// 数値をキロ単位に変換するステージ
+/// struct ToKilo;
+///
+/// impl PipelineStage for ToKilo {
+///     fn process(&self, context: &mut StageContext<'_>) -> Result<(), XlsxToMdError> {
+///         for cell in context.cells_mut() {
+///             if let CellValue::Number(n) = cell.value {
+///                 cell.value = CellValue::Number(n / 1000.0);
+///             }
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let builder = xlsxzero::ConverterBuilder::new().with_stage(Stage::Parse, ToKilo);
+/// ```
+pub trait PipelineStage: Send + Sync {
+    /// ステージの処理を実行
+    ///
+    /// # 引数
+    ///
+    /// * `context` - 処理中のシートのデータ
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 処理に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（変換全体が失敗します）
+    fn process(&self, context: &mut StageContext<'_>) -> Result<(), XlsxToMdError>;
+}
+
+impl<F> PipelineStage for F
+where
+    F: Fn(&mut StageContext<'_>) -> Result<(), XlsxToMdError> + Send + Sync,
+{
+    fn process(&self, context: &mut StageContext<'_>) -> Result<(), XlsxToMdError> {
+        self(context)
+    }
+}
+
+/// セル単位のテキスト変換（翻訳など）
+///
+/// `ConverterBuilder::with_text_transformer()`で登録し、フォーマット後の
+/// 文字列セルのテキストを一括で変換します。機械翻訳APIなどをシートごとに
+/// 1回の呼び出しで利用できるよう、シート内の文字列を重複を除いてまとめて渡します。
+///
+/// 変換対象はMarkdownなどのマークアップを含まないセル単位のプレーンテキストです。
+/// 変換結果には通常のセルと同じエスケープが適用されるため、変換後のテキストに
+/// `|`や改行が含まれても表の構造は崩れません。
+///
+/// `Fn(&[&str]) -> Result<Vec<String>, XlsxToMdError>`を満たすクロージャも
+/// `TextTransformer`として使用できます。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::{ConverterBuilder, XlsxToMdError};
+///
+/// let builder = ConverterBuilder::new().with_text_transformer(
+///     |texts: &[&str]| -> Result<Vec<String>, XlsxToMdError> {
+///         // 翻訳APIの呼び出しなど（入力と同じ数・順序で返す）
+///         Ok(texts.iter().map(|text| text.to_uppercase()).collect())
+///     },
+/// );
+/// ```
+pub trait TextTransformer: Send + Sync {
+    /// テキストを一括で変換
+    ///
+    /// # 引数
+    ///
+    /// * `texts` - 変換対象のテキスト（シート内の文字列セルの値、重複なし）
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Vec<String>)` - 変換後のテキスト（`texts`と同じ数・順序）
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（変換全体が失敗します）
+    fn transform(&self, texts: &[&str]) -> Result<Vec<String>, XlsxToMdError>;
+}
+
+impl<F> TextTransformer for F
+where
+    F: Fn(&[&str]) -> Result<Vec<String>, XlsxToMdError> + Send + Sync,
+{
+    fn transform(&self, texts: &[&str]) -> Result<Vec<String>, XlsxToMdError> {
+        self(texts)
+    }
+}
+
+/// ステージに渡されるセル
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct StageCell {
+    /// 行番号（0始まり）
+    row: u32,
+
+    /// 列番号（0始まり）
+    col: u32,
+
+    /// セルの値（フォーマット前）
+    pub value: CellValue,
+
+    /// 表示文字列（`Stage::Parse`では空文字列）
+    pub text: String,
+}
+
+impl StageCell {
+    /// 行番号（0始まり）を取得
+    pub fn row(&self) -> u32 {
+        self.row
+    }
+
+    /// 列番号（0始まり）を取得
+    pub fn col(&self) -> u32 {
+        self.col
+    }
+}
+
+/// ステージの処理対象となるシートのデータ
+pub struct StageContext<'a> {
+    /// 完了したステージ
+    stage: Stage,
+
+    /// シート名
+    sheet_name: &'a str,
+
+    /// セル（`Stage::Render`では空）
+    cells: Vec<StageCell>,
+
+    /// 出力文字列（`Stage::Render`のみ）
+    output: Option<&'a mut String>,
+}
+
+impl StageContext<'_> {
+    /// 完了したステージを取得
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// シート名を取得
+    pub fn sheet_name(&self) -> &str {
+        self.sheet_name
+    }
+
+    /// セルを取得（`Stage::Render`では空）
+    pub fn cells(&self) -> &[StageCell] {
+        &self.cells
+    }
+
+    /// セルを変更可能な参照として取得（`Stage::Render`では空）
+    pub fn cells_mut(&mut self) -> &mut [StageCell] {
+        &mut self.cells
+    }
+
+    /// シートの出力文字列を変更可能な参照として取得
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(&mut String)` - `Stage::Render`の場合
+    /// * `None` - それ以外のステージの場合
+    pub fn output_mut(&mut self) -> Option<&mut String> {
+        self.output.as_deref_mut()
+    }
+}
+
+/// 登録されたステージのリスト
+///
+/// `ConversionConfig`に保持するため、`Debug`と`Clone`を実装します。
+#[derive(Clone, Default)]
+pub(crate) struct Stages {
+    stages: Vec<(Stage, Arc<dyn PipelineStage>)>,
+
+    /// フォーマット後に適用するテキスト変換
+    text_transformer: Option<Arc<dyn TextTransformer>>,
+}
+
+impl fmt::Debug for Stages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stages")
+            .field(
+                "stages",
+                &self
+                    .stages
+                    .iter()
+                    .map(|(stage, _)| stage)
+                    .collect::<Vec<_>>(),
+            )
+            .field("text_transformer", &self.text_transformer.is_some())
+            .finish()
+    }
+}
+
+impl Stages {
+    /// 独自の処理（ステージ、テキスト変換）が登録されていないか
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty() && self.text_transformer.is_none()
+    }
+
+    /// ステージを追加
+    pub fn push(&mut self, stage: Stage, hook: Arc<dyn PipelineStage>) {
+        self.stages.push((stage, hook));
+    }
+
+    /// テキスト変換を設定
+    pub fn set_text_transformer(&mut self, transformer: Arc<dyn TextTransformer>) {
+        self.text_transformer = Some(transformer);
+    }
+
+    /// テキスト変換を文字列セルに適用
+    ///
+    /// シート内の文字列セルの値を重複を除いて一括で変換し、
+    /// `render`で生成した表示文字列で`formatted_cells`を置き換えます。
+    ///
+    /// # 引数
+    ///
+    /// * `raw_cells` - パーサーから抽出された生のセルデータ
+    /// * `formatted_cells` - フォーマット済みセル（`raw_cells`と同じ順序）
+    /// * `eligible` - 変換対象とするセルかどうかを判定する関数
+    /// * `render` - 元のセルと変換後のテキストから表示文字列を生成する関数
+    pub fn run_text_transformer<E, F>(
+        &self,
+        raw_cells: &[RawCellData],
+        formatted_cells: &mut [(CellCoord, String)],
+        eligible: E,
+        render: F,
+    ) -> Result<(), XlsxToMdError>
+    where
+        E: Fn(&RawCellData) -> bool,
+        F: Fn(&RawCellData, &str) -> String,
+    {
+        let Some(transformer) = &self.text_transformer else {
+            return Ok(());
+        };
+
+        // 変換対象のテキストを出現順に重複なく収集
+        let mut texts: Vec<&str> = Vec::new();
+        let mut index_of: HashMap<&str, usize> = HashMap::new();
+        let mut targets: Vec<(usize, usize)> = Vec::new();
+        for (cell_idx, raw) in raw_cells.iter().enumerate() {
+            if let CellValue::String(text) = &raw.value {
+                if text.trim().is_empty() || !eligible(raw) {
+                    continue;
+                }
+                let text_idx = *index_of.entry(text.as_str()).or_insert_with(|| {
+                    texts.push(text.as_str());
+                    texts.len() - 1
+                });
+                targets.push((cell_idx, text_idx));
+            }
+        }
+        if texts.is_empty() {
+            return Ok(());
+        }
+
+        let transformed = transformer.transform(&texts)?;
+        if transformed.len() != texts.len() {
+            return Err(XlsxToMdError::TextTransformer {
+                expected: texts.len(),
+                actual: transformed.len(),
+            });
+        }
+
+        for (cell_idx, text_idx) in targets {
+            formatted_cells[cell_idx].1 = render(&raw_cells[cell_idx], &transformed[text_idx]);
+        }
+        Ok(())
+    }
+
+    /// 指定したステージに登録された処理があるか
+    fn has(&self, stage: Stage) -> bool {
+        self.stages.iter().any(|(s, _)| *s == stage)
+    }
+
+    /// 指定したステージに登録された処理を登録順に実行（内部ヘルパー）
+    fn run(&self, context: &mut StageContext<'_>) -> Result<(), XlsxToMdError> {
+        for (stage, hook) in &self.stages {
+            if *stage == context.stage {
+                hook.process(context)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `Stage::Parse`の処理を実行
+    pub fn run_parse(
+        &self,
+        sheet_name: &str,
+        raw_cells: &mut [RawCellData],
+    ) -> Result<(), XlsxToMdError> {
+        if !self.has(Stage::Parse) {
+            return Ok(());
+        }
+
+        let cells = raw_cells
+            .iter()
+            .map(|raw| stage_cell(raw.coord, raw.value.clone(), String::new()))
+            .collect();
+        let mut context = StageContext::new(Stage::Parse, sheet_name, cells);
+        self.run(&mut context)?;
+
+        for (raw, cell) in raw_cells.iter_mut().zip(context.cells) {
+            raw.value = cell.value;
+        }
+        Ok(())
+    }
+
+    /// `Stage::Format`の処理を実行
+    ///
+    /// フックが値または表示文字列を変更したセルは、`raw_numbers`から除きます。
+    pub fn run_format(
+        &self,
+        sheet_name: &str,
+        raw_cells: &mut [RawCellData],
+        formatted_cells: &mut [(CellCoord, String)],
+        raw_numbers: &mut HashMap<CellCoord, f64>,
+    ) -> Result<(), XlsxToMdError> {
+        if !self.has(Stage::Format) {
+            return Ok(());
+        }
+
+        // フォーマット済みセルは元のセルと同じ順序で生成される
+        let cells = raw_cells
+            .iter()
+            .zip(formatted_cells.iter())
+            .map(|(raw, (coord, text))| stage_cell(*coord, raw.value.clone(), text.clone()))
+            .collect();
+        let mut context = StageContext::new(Stage::Format, sheet_name, cells);
+        self.run(&mut context)?;
+
+        for ((raw, (coord, text)), cell) in raw_cells
+            .iter_mut()
+            .zip(formatted_cells.iter_mut())
+            .zip(context.cells)
+        {
+            if raw.value != cell.value || *text != cell.text {
+                raw_numbers.remove(coord);
+            }
+            raw.value = cell.value;
+            *text = cell.text;
+        }
+        Ok(())
+    }
+
+    /// `Stage::GridBuild`の処理を実行
+    pub fn run_grid_build(
+        &self,
+        sheet_name: &str,
+        grid: &mut LogicalGrid,
+    ) -> Result<(), XlsxToMdError> {
+        if !self.has(Stage::GridBuild) {
+            return Ok(());
+        }
+
+        let cells = grid
+            .cells_mut()
+            .map(|(coord, cell)| stage_cell(coord, cell.value.clone(), cell.content().to_string()))
+            .collect();
+        let mut context = StageContext::new(Stage::GridBuild, sheet_name, cells);
+        self.run(&mut context)?;
+
+        for ((_, cell), stage_cell) in grid.cells_mut().zip(context.cells) {
+            if cell.content() != stage_cell.text {
+                cell.set_content(stage_cell.text);
+            } else if cell.value != stage_cell.value {
+                cell.number = None;
+            }
+            cell.value = stage_cell.value;
+        }
+        Ok(())
+    }
+
+    /// `Stage::Render`の処理を実行
+    pub fn run_render(&self, sheet_name: &str, output: &mut String) -> Result<(), XlsxToMdError> {
+        if !self.has(Stage::Render) {
+            return Ok(());
+        }
+
+        let mut context = StageContext::new(Stage::Render, sheet_name, Vec::new());
+        context.output = Some(output);
+        self.run(&mut context)
+    }
+}
+
+impl<'a> StageContext<'a> {
+    /// 新しいコンテキストを生成（内部ヘルパー）
+    fn new(stage: Stage, sheet_name: &'a str, cells: Vec<StageCell>) -> Self {
+        Self {
+            stage,
+            sheet_name,
+            cells,
+            output: None,
+        }
+    }
+}
+
+/// ステージに渡すセルを生成（内部ヘルパー）
+fn stage_cell(coord: CellCoord, value: CellValue, text: String) -> StageCell {
+    StageCell {
+        row: coord.row,
+        col: coord.col,
+        value,
+        text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw_cell(row: u32, col: u32, value: CellValue) -> RawCellData {
+        RawCellData {
+            coord: CellCoord::new(row, col),
+            value,
+            format_id: None,
+            format_string: None,
+            formula: None,
+            hyperlink: None,
+            rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
+        }
+    }
+
+    #[test]
+    fn test_run_parse() {
+        let mut stages = Stages::default();
+        stages.push(
+            Stage::Parse,
+            Arc::new(|context: &mut StageContext<'_>| {
+                assert_eq!(context.stage(), Stage::Parse);
+                assert_eq!(context.sheet_name(), "Sheet1");
+                for cell in context.cells_mut() {
+                    if let CellValue::Number(n) = cell.value {
+                        cell.value = CellValue::Number(n * 2.0);
+                    }
+                }
+                Ok(())
+            }),
+        );
+
+        let mut cells = vec![
+            raw_cell(0, 0, CellValue::String("A".to_string())),
+            raw_cell(0, 1, CellValue::Number(21.0)),
+        ];
+        stages.run_parse("Sheet1", &mut cells).unwrap();
+        assert_eq!(cells[0].value, CellValue::String("A".to_string()));
+        assert_eq!(cells[1].value, CellValue::Number(42.0));
+    }
+
+    #[test]
+    fn test_run_format_in_registration_order() {
+        let mut stages = Stages::default();
+        stages.push(
+            Stage::Format,
+            Arc::new(|context: &mut StageContext<'_>| {
+                for cell in context.cells_mut() {
+                    cell.text = cell.text.to_uppercase();
+                }
+                Ok(())
+            }),
+        );
+        stages.push(
+            Stage::Format,
+            Arc::new(|context: &mut StageContext<'_>| {
+                for cell in context.cells_mut() {
+                    cell.text.push('!');
+                }
+                Ok(())
+            }),
+        );
+
+        let mut cells = vec![raw_cell(1, 2, CellValue::String("hi".to_string()))];
+        let mut formatted = vec![(CellCoord::new(1, 2), "hi".to_string())];
+        let mut raw_numbers = HashMap::new();
+        stages
+            .run_format("Sheet1", &mut cells, &mut formatted, &mut raw_numbers)
+            .unwrap();
+        assert_eq!(formatted[0].1, "HI!");
+    }
+
+    #[test]
+    fn test_run_text_transformer_batches_unique_texts() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_in_hook = Arc::clone(&calls);
+        let mut stages = Stages::default();
+        stages.set_text_transformer(Arc::new(
+            move |texts: &[&str]| -> Result<Vec<String>, XlsxToMdError> {
+                calls_in_hook.fetch_add(1, Ordering::SeqCst);
+                // 重複は除かれ、出現順に渡される
+                assert_eq!(texts, ["りんご", "みかん"]);
+                Ok(vec!["apple".to_string(), "orange | mandarin".to_string()])
+            },
+        ));
+
+        let cells = vec![
+            raw_cell(0, 0, CellValue::String("りんご".to_string())),
+            raw_cell(0, 1, CellValue::String("みかん".to_string())),
+            raw_cell(1, 0, CellValue::String("りんご".to_string())),
+            raw_cell(1, 1, CellValue::Number(3.0)),
+        ];
+        let mut formatted: Vec<(CellCoord, String)> = cells
+            .iter()
+            .map(|c| (c.coord, c.value.as_raw_string()))
+            .collect();
+        stages
+            .run_text_transformer(
+                &cells,
+                &mut formatted,
+                |_| true,
+                |_, text| text.replace('|', "\\|"),
+            )
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(formatted[0].1, "apple");
+        assert_eq!(formatted[1].1, "orange \\| mandarin");
+        assert_eq!(formatted[2].1, "apple");
+        assert_eq!(formatted[3].1, "3");
+    }
+
+    #[test]
+    fn test_run_text_transformer_length_mismatch() {
+        let mut stages = Stages::default();
+        stages.set_text_transformer(Arc::new(
+            |_: &[&str]| -> Result<Vec<String>, XlsxToMdError> { Ok(Vec::new()) },
+        ));
+
+        let cells = vec![raw_cell(0, 0, CellValue::String("A".to_string()))];
+        let mut formatted = vec![(CellCoord::new(0, 0), "A".to_string())];
+        let result =
+            stages.run_text_transformer(&cells, &mut formatted, |_| true, |_, t| t.to_string());
+        assert!(matches!(
+            result,
+            Err(XlsxToMdError::TextTransformer {
+                expected: 1,
+                actual: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn test_run_render() {
+        let mut stages = Stages::default();
+        stages.push(
+            Stage::Render,
+            Arc::new(|context: &mut StageContext<'_>| {
+                assert!(context.cells().is_empty());
+                if let Some(output) = context.output_mut() {
+                    output.push_str("<!-- end -->\n");
+                }
+                Ok(())
+            }),
+        );
+
+        let mut output = "| A |\n".to_string();
+        stages.run_render("Sheet1", &mut output).unwrap();
+        assert_eq!(output, "| A |\n<!-- end -->\n");
+    }
+
+    #[test]
+    fn test_stage_error_propagates() {
+        let mut stages = Stages::default();
+        stages.push(
+            Stage::Render,
+            Arc::new(|_: &mut StageContext<'_>| {
+                Err(XlsxToMdError::Internal("stage failed".to_string()))
+            }),
+        );
+
+        let mut output = String::new();
+        assert!(stages.run_render("Sheet1", &mut output).is_err());
+        // 未登録のステージは何もしない
+        let mut cells = vec![raw_cell(0, 0, CellValue::Number(1.0))];
+        assert!(stages.run_parse("Sheet1", &mut cells).is_ok());
+    }
+}
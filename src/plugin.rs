@@ -0,0 +1,304 @@
+//! Plugin Module
+//!
+//! 外部のクレートが名前付きの出力フォーマットを登録するための拡張ポイント（`FormatPlugin`）と、
+//! 登録されたフォーマットのレジストリを提供するモジュール。
+//!
+//! 登録したフォーマットは`ConverterBuilder::with_format_name()`で組み込みのフォーマットと同じく
+//! 名前で選択できるため、CLIやWASMのラッパーは`--format my-org-wiki`のような指定を
+//! そのまま渡せます。フォーマットは次のいずれかの方法で登録します。
+//!
+//! * `register_format()`: 実行時に登録（すべてのターゲットで使用可能）
+//! * `FORMAT_PLUGINS`: リンク時に登録（`plugins`フィーチャー、linkmeを使用）
+
+use std::fmt;
+use std::io::Write;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::api::{normalize_name, OutputFormat};
+use crate::error::{ConfigError, XlsxToMdError};
+use crate::model::SheetModel;
+
+/// 独自の出力フォーマット
+///
+/// シートごとに構築した文書モデル（`SheetModel`）を出力します。シート間の区切り・シートの見出し・
+/// 注記は、`FormatCapabilities::layout`の組み込みフォーマットと同じ形式で出力されます。
+/// シートは並列に処理されるため、`Send + Sync`が必要です。
+///
+/// # 使用例
+///
+/// ```rust
+/// use std::io::Write;
+/// use xlsxzero::{FormatCapabilities, FormatPlugin, SheetModel, XlsxToMdError};
+///
+/// /// 社内Wikiの表形式
+/// struct WikiFormat;
+///
+/// impl FormatPlugin for WikiFormat {
+///     fn name(&self) -> &str {
+///         "my-org-wiki"
+///     }
+///
+///     fn capabilities(&self) -> FormatCapabilities {
+///         FormatCapabilities::new().with_header_row(true)
+///     }
+///
+///     fn render(&self, sheet: &SheetModel, writer: &mut dyn Write) -> Result<(), XlsxToMdError> {
+///         let headers: Vec<&str> = sheet.columns.iter().map(|c| c.header.as_str()).collect();
+///         writeln!(writer, "^ {} ^", headers.join(" ^ "))?;
+///         for row in &sheet.rows {
+///             let cells: Vec<&str> = row.cells.iter().map(|c| c.formatted.as_str()).collect();
+///             writeln!(writer, "| {} |", cells.join(" | "))?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// xlsxzero::register_format(WikiFormat)?;
+/// let converter = xlsxzero::ConverterBuilder::new()
+///     .with_format_name("my-org-wiki")
+///     .build()?;
+/// # Ok::<(), XlsxToMdError>(())
+/// ```
+pub trait FormatPlugin: Send + Sync {
+    /// フォーマット名（例: `my-org-wiki`）
+    ///
+    /// 名前は大文字・小文字と`-`・`_`を区別せずに照合します。
+    fn name(&self) -> &str;
+
+    /// フォーマットの説明（CLIのフォーマット一覧などで使用、既定は空文字列）
+    fn description(&self) -> &str {
+        ""
+    }
+
+    /// フォーマットが対応する機能（既定は`FormatCapabilities::new()`）
+    fn capabilities(&self) -> FormatCapabilities {
+        FormatCapabilities::new()
+    }
+
+    /// 1シート分の文書モデルを出力
+    ///
+    /// # 引数
+    ///
+    /// * `sheet` - シートの文書モデル（`FormatCapabilities`に従って構築したもの）
+    /// * `writer` - 出力先のライター
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 出力に成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合
+    fn render(&self, sheet: &SheetModel, writer: &mut dyn Write) -> Result<(), XlsxToMdError>;
+}
+
+/// 独自の出力フォーマットが対応する機能
+///
+/// 変換時には、この機能に合わせて`FormatPlugin::render()`に渡す文書モデルを構築します。
+/// 生成と指定は`const fn`のため、`FORMAT_PLUGINS`に登録する`static`の初期化にも使用できます。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FormatCapabilities {
+    /// 結合セルを表現できるか
+    ///
+    /// `true`の場合、結合セルは左上のセルのみを含め、`CellModel::row_span`・`col_span`で
+    /// 結合範囲を示します。`false`の場合は`MergeStrategy::DataDuplication`で変換し、
+    /// 結合範囲のすべてのセルに値を複製します。
+    pub supports_merged_cells: bool,
+
+    /// 先頭行を見出し行として扱うか
+    ///
+    /// `true`の場合、先頭行は`ColumnModel::header`・`key`となり、`SheetModel::rows`には含めません。
+    /// `false`の場合、すべての行を`SheetModel::rows`に含め、キーには列名を使用します。
+    pub needs_header_row: bool,
+
+    /// シート間の区切り・シートの見出し・注記の形式として使用する組み込みフォーマット
+    pub layout: OutputFormat,
+}
+
+impl FormatCapabilities {
+    /// 既定の機能（結合セル・見出し行なし、Markdownのレイアウト）を生成
+    pub const fn new() -> Self {
+        Self {
+            supports_merged_cells: false,
+            needs_header_row: false,
+            layout: OutputFormat::Markdown,
+        }
+    }
+
+    /// 結合セルを表現できるかを指定
+    pub const fn with_merged_cells(mut self, supported: bool) -> Self {
+        self.supports_merged_cells = supported;
+        self
+    }
+
+    /// 先頭行を見出し行として扱うかを指定
+    pub const fn with_header_row(mut self, needed: bool) -> Self {
+        self.needs_header_row = needed;
+        self
+    }
+
+    /// レイアウトに使用する組み込みフォーマットを指定
+    pub const fn with_layout(mut self, layout: OutputFormat) -> Self {
+        self.layout = layout;
+        self
+    }
+}
+
+impl Default for FormatCapabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// リンク時に登録する出力フォーマット（`plugins`フィーチャー）
+///
+/// 依存するクレートで`#[distributed_slice]`を指定した`static`を定義すると、
+/// `register_format()`を呼び出さずにフォーマットが登録されます。
+/// linkmeが対応していないターゲット（WASMなど）では`register_format()`を使用してください。
+///
+/// ```rust,ignore
+/// use xlsxzero::linkme::distributed_slice;
+/// use xlsxzero::{FormatPlugin, FORMAT_PLUGINS};
+///
+/// #[distributed_slice(FORMAT_PLUGINS)]
+/// #[linkme(crate = xlsxzero::linkme)]
+/// static WIKI: &dyn FormatPlugin = &WikiFormat;
+/// ```
+#[cfg(feature = "plugins")]
+#[linkme::distributed_slice]
+pub static FORMAT_PLUGINS: [&'static dyn FormatPlugin];
+
+/// 実行時に登録された出力フォーマット
+static REGISTRY: RwLock<Vec<Arc<dyn FormatPlugin>>> = RwLock::new(Vec::new());
+
+/// 出力フォーマットを実行時に登録
+///
+/// # 引数
+///
+/// * `plugin` - 登録するフォーマット
+///
+/// # 戻り値
+///
+/// * `Ok(())` - 登録に成功した場合
+/// * `Err(XlsxToMdError::Config)` - 名前が空の場合、または組み込みのフォーマット・
+///   登録済みのフォーマットと名前が重複する場合
+pub fn register_format<P: FormatPlugin + 'static>(plugin: P) -> Result<(), XlsxToMdError> {
+    let name = normalize_name(plugin.name());
+    if name.is_empty() {
+        return Err(XlsxToMdError::Config(ConfigError::InvalidValue {
+            option: "register_format",
+            value: plugin.name().to_string(),
+            reason: "format plugin name must not be empty".to_string(),
+        }));
+    }
+    if name.parse::<OutputFormat>().is_ok() || find_format(&name).is_some() {
+        return Err(XlsxToMdError::Config(ConfigError::DuplicateFormat {
+            name: plugin.name().to_string(),
+        }));
+    }
+
+    REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Arc::new(plugin));
+    Ok(())
+}
+
+/// 登録されたすべての出力フォーマットを取得
+///
+/// リンク時に登録したフォーマット、実行時に登録したフォーマットの順に返します。
+/// 組み込みのフォーマットは含みません（`OutputFormat::NAMES`を参照してください）。
+pub fn registered_formats() -> Vec<Arc<dyn FormatPlugin>> {
+    let mut formats: Vec<Arc<dyn FormatPlugin>> = Vec::new();
+    #[cfg(feature = "plugins")]
+    formats.extend(
+        FORMAT_PLUGINS
+            .iter()
+            .map(|&plugin| Arc::new(StaticPlugin(plugin)) as Arc<dyn FormatPlugin>),
+    );
+    formats.extend(
+        REGISTRY
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .cloned(),
+    );
+    formats
+}
+
+/// 名前で出力フォーマットを検索（内部ヘルパー）
+pub(crate) fn find_format(name: &str) -> Option<Arc<dyn FormatPlugin>> {
+    let name = normalize_name(name);
+    registered_formats()
+        .into_iter()
+        .find(|plugin| normalize_name(plugin.name()) == name)
+}
+
+/// リンク時に登録したフォーマットを`Arc`で扱うためのラッパー（内部用）
+#[cfg(feature = "plugins")]
+struct StaticPlugin(&'static dyn FormatPlugin);
+
+#[cfg(feature = "plugins")]
+impl FormatPlugin for StaticPlugin {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    fn description(&self) -> &str {
+        self.0.description()
+    }
+
+    fn capabilities(&self) -> FormatCapabilities {
+        self.0.capabilities()
+    }
+
+    fn render(&self, sheet: &SheetModel, writer: &mut dyn Write) -> Result<(), XlsxToMdError> {
+        self.0.render(sheet, writer)
+    }
+}
+
+/// 変換設定に保持する出力フォーマット
+///
+/// `ConversionConfig`に保持するため、`Debug`と`Clone`を実装します。
+#[derive(Clone)]
+pub(crate) struct FormatHandle(pub Arc<dyn FormatPlugin>);
+
+impl fmt::Debug for FormatHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FormatHandle")
+            .field(&self.0.name())
+            .field(&self.0.capabilities())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Named(&'static str);
+
+    impl FormatPlugin for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn render(
+            &self,
+            _sheet: &SheetModel,
+            _writer: &mut dyn Write,
+        ) -> Result<(), XlsxToMdError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_register_format() {
+        register_format(Named("unit-test-format")).unwrap();
+        assert!(find_format("Unit_Test_Format").is_some());
+        assert!(find_format("unit-test-other").is_none());
+
+        // 登録済み・組み込みのフォーマットと重複する名前、空の名前は登録できない
+        assert!(register_format(Named("unit_test_format")).is_err());
+        assert!(register_format(Named("md")).is_err());
+        assert!(register_format(Named(" ")).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Progress Module
+//!
+//! 変換の進捗を通知するコールバック（`ConverterBuilder::with_progress()`）と、
+//! 通知されるイベントを提供するモジュール。
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 変換の進捗イベント
+///
+/// シートは並列に処理されるため、異なるシートのイベントは交互に通知されることがあります。
+/// 同じシートのイベントは`SheetStarted` → `RowsProcessed`（0回以上） → `SheetFinished`の順に通知されます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProgressEvent<'a> {
+    /// 変換するシートが決まった（シートの処理の開始前に1回）
+    WorkbookLoaded {
+        /// 変換するシートの数
+        sheets: usize,
+    },
+
+    /// シートの処理を開始した
+    SheetStarted {
+        /// シート名
+        sheet: &'a str,
+
+        /// シートの出力順（0始まり）
+        index: usize,
+    },
+
+    /// シートの行を解析した（`ConverterBuilder::with_progress_interval()`で指定した行数ごと）
+    RowsProcessed {
+        /// シート名
+        sheet: &'a str,
+
+        /// 解析した行数（使用範囲の先頭からの行数）
+        rows: usize,
+
+        /// シートの使用範囲の行数
+        total_rows: usize,
+    },
+
+    /// シートの処理を完了した（出力フォーマットへのレンダリングを含む）
+    SheetFinished {
+        /// シート名
+        sheet: &'a str,
+
+        /// シートの出力順（0始まり）
+        index: usize,
+
+        /// 出力した表の行数
+        rows: usize,
+
+        /// シートの処理時間
+        elapsed: Duration,
+    },
+}
+
+/// 変換設定に保持する進捗の通知先
+///
+/// `ConversionConfig`に保持するため、`Debug`と`Clone`を実装します。
+#[derive(Clone)]
+pub(crate) struct ProgressHandle(pub Arc<dyn Fn(&ProgressEvent<'_>) + Send + Sync>);
+
+impl ProgressHandle {
+    /// イベントを通知
+    pub fn emit(&self, event: ProgressEvent<'_>) {
+        (self.0)(&event);
+    }
+}
+
+impl fmt::Debug for ProgressHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressHandle")
+    }
+}
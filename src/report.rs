@@ -0,0 +1,387 @@
+//! Report Module
+//!
+//! 変換処理の結果レポート（処理統計・フェーズ別の処理時間・推定メモリ使用量）を提供するモジュール。
+//! プロファイラを使用せずに、処理に時間やメモリを要するワークブックを特定するために使用します。
+
+use std::time::Duration;
+
+/// 変換処理のレポート
+///
+/// `Converter::convert_with_report()`の戻り値として返されます。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::ConverterBuilder;
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let converter = ConverterBuilder::new().build()?;
+/// let input = File::open("example.xlsx")?;
+/// let mut output = Vec::new();
+/// let report = converter.convert_with_report(input, &mut output)?;
+///
+/// println!("total: {:?}", report.timings.total);
+/// for sheet in &report.sheets {
+///     println!("{}: {} cells, parse {:?}", sheet.name, sheet.cells, sheet.timings.parse);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ConversionReport {
+    /// 変換したシートごとのレポート（出力順）
+    pub sheets: Vec<SheetReport>,
+
+    /// フェーズ別の処理時間
+    pub timings: PhaseTimings,
+
+    /// 入力データのサイズ（バイト）
+    pub input_size: u64,
+
+    /// 出力データのサイズ（バイト）
+    pub output_size: u64,
+
+    /// 推定ピークメモリ使用量（バイト）
+    ///
+    /// 入力バッファ、並列処理中のシートごとの作業領域（セルデータ・グリッド・出力バッファ）、
+    /// 書き込み待ちの出力から算出した概算値です。アロケータの実測値ではありません。
+    pub peak_memory_estimate: u64,
+
+    /// 変換中に検出された警告（ワークブック全体の警告の後に、シートの出力順）
+    ///
+    /// 変換は継続されたものの、入力や出力に注意が必要な事象を示します。
+    pub warnings: Vec<ConversionWarning>,
+
+    /// 内容がほぼ同一のシートとして検出したシート（出力順）
+    ///
+    /// `ConverterBuilder::with_duplicate_sheets()`で`DuplicateSheets::Keep`以外を
+    /// 指定した場合のみ検出します。`DuplicateSheets::Skip`で除外したシートは`sheets`に含まれません。
+    pub duplicate_sheets: Vec<DuplicateSheet>,
+
+    /// 共有ブックの変更履歴（xl/revisions/ の記録順）
+    ///
+    /// `ConverterBuilder::include_change_history(true)`を指定した場合のみ解析します。
+    pub revisions: Vec<Revision>,
+}
+
+/// 共有ブックの変更履歴の1件（ユーザーが変更を保存した1回分）
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Revision {
+    /// 変更したユーザー名
+    pub user: String,
+
+    /// 変更を保存した日時（ISO 8601形式の記録値、例: `2024-05-01T09:30:00Z`）
+    pub date_time: String,
+
+    /// 変更されたセル（記録順）
+    ///
+    /// 行・列の挿入や削除、書式の変更などのセルの値以外の変更は含みません。
+    pub changes: Vec<CellChange>,
+}
+
+/// 変更履歴に記録されたセルの値の変更
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CellChange {
+    /// シート名（ワークブックに存在しないシートの場合はシートID）
+    pub sheet: String,
+
+    /// セル（A1記法）
+    pub cell: String,
+
+    /// 変更前の値（空のセルだった場合は`None`）
+    pub old_value: Option<String>,
+
+    /// 変更後の値（セルを削除した場合は`None`、数式の場合は`=`で始まる数式）
+    pub new_value: Option<String>,
+}
+
+/// 内容がほぼ同一のシートとして検出したシート
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct DuplicateSheet {
+    /// 重複シートのシート名
+    pub sheet: String,
+
+    /// 重複元（先に出力するシート）のシート名
+    pub duplicate_of: String,
+
+    /// 行の内容の類似度（0.0〜1.0、1.0は行の内容が完全に一致）
+    pub similarity: f64,
+
+    /// 出力から除外したか（`DuplicateSheets::Skip`の場合）
+    pub skipped: bool,
+}
+
+/// 変換中に検出された警告
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConversionWarning {
+    /// 警告の対象シート名（ワークブック全体に関する警告の場合は`None`）
+    pub sheet: Option<String>,
+
+    /// 警告の種類
+    pub kind: WarningKind,
+
+    /// 警告の詳細メッセージ
+    pub message: String,
+}
+
+impl ConversionWarning {
+    /// シートに関する警告を生成
+    pub(crate) fn sheet(sheet: &str, kind: WarningKind, message: String) -> Self {
+        Self {
+            sheet: Some(sheet.to_string()),
+            kind,
+            message,
+        }
+    }
+
+    /// ワークブック全体に関する警告を生成
+    pub(crate) fn workbook(kind: WarningKind, message: String) -> Self {
+        Self {
+            sheet: None,
+            kind,
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for ConversionWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.sheet {
+            Some(sheet) => write!(f, "Sheet '{}': {}", sheet, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// 警告の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// 使用範囲の肥大化
+    ///
+    /// 表示内容のあるセルの数に対して、シートの使用範囲（行数 × 列数）が極端に大きい状態です。
+    /// 空白文字のみのセルや、離れた位置に誤って入力されたセルが原因で、
+    /// 大量の空行・空列が出力される可能性があります。
+    UsedRangeBloat,
+
+    /// 不正な文字エンコーディング
+    ///
+    /// XMLパーツにUTF-8として不正なバイト列が含まれていたため、置換文字（U+FFFD）に
+    /// 置き換えて変換を継続しました（`ConverterBuilder::with_encoding_fallback(true)`の場合のみ）。
+    /// 元の文字は復元されません。
+    InvalidEncoding,
+
+    /// 重複シート
+    ///
+    /// 先に出力するシートと行の内容がほぼ同一のシートです
+    /// （`ConverterBuilder::with_duplicate_sheets()`で検出を有効にした場合のみ）。
+    DuplicateSheet,
+
+    /// 出力の打ち切り
+    ///
+    /// 出力サイズが上限（`ConverterBuilder::with_max_output_size()`）を超えたため、
+    /// 出力を打ち切りました（`OutputOverflow::Truncate`の場合のみ）。
+    OutputTruncated,
+
+    /// 計算列の省略
+    ///
+    /// 計算列（`ConverterBuilder::with_computed_column()`）の式が参照する列が
+    /// シートの先頭行（見出し）にないため、そのシートには計算列を追加しませんでした。
+    ComputedColumnSkipped,
+
+    /// 保護されたシート
+    ///
+    /// シートの保護が設定されたシートです（`ConverterBuilder::with_protected_sheets()`で
+    /// `ProtectedSheets::Skip`または`ProtectedSheets::Flag`を指定した場合のみ）。
+    /// `Skip`の場合、シートは出力されていません。
+    ProtectedSheet,
+
+    /// 列の値の検証の違反
+    ///
+    /// `ConverterBuilder::with_validations()`で指定した検証に違反した列です。
+    /// 検証ルールごとに1件記録し、メッセージには違反した行番号（見出しを1行目とする
+    /// 出力の表の行番号、先頭の10件まで）と件数が含まれます。
+    ValidationFailed,
+
+    /// シートの打ち切り
+    ///
+    /// シート単位の上限（`ConverterBuilder::with_per_sheet_limits()`の行数・セル数・処理時間）を
+    /// 超えたため、上限を超えた行以降を出力しませんでした。メッセージには残した行数と
+    /// 超えた上限が含まれます。他のシートの変換は継続します。
+    SheetTruncated,
+
+    /// 結合範囲の展開の上限超過
+    ///
+    /// `MergeStrategy::DataDuplication`で、セル数が上限（`ConverterBuilder::with_max_merged_cells()`）を
+    /// 超える結合範囲の値を複製せず、左上のセルのみに残しました（`MergeStrategy::HtmlFallback`と同じ扱い）。
+    /// メッセージには結合範囲・セル数・上限が含まれます。
+    MergedRegionLimit,
+
+    /// 旧形式（.xls、BIFF）のワークブック
+    ///
+    /// セルの値・日付・結合セルは変換しましたが、XLSXのXMLから取得する情報
+    /// （非表示行・列、ハイパーリンク、リッチテキスト、セルのスタイル、条件付き書式など）は
+    /// 取得できないため、出力に反映されていません。
+    LegacyFormat,
+}
+
+/// ワークブック全体のフェーズ別処理時間
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct PhaseTimings {
+    /// 入力データの読み込み
+    pub read_input: Duration,
+
+    /// ZIPアーカイブの走査とワークブック構造の読み込み
+    pub zip_scan: Duration,
+
+    /// XMLメタデータ（スタイル・非表示行列・ハイパーリンクなど）の解析
+    pub metadata: Duration,
+
+    /// 全シートの処理（並列処理の経過時間）
+    ///
+    /// シートごとの内訳は`SheetReport::timings`を参照してください。
+    pub sheets: Duration,
+
+    /// 出力の書き込み
+    pub write: Duration,
+
+    /// 変換処理全体
+    pub total: Duration,
+}
+
+/// シートごとのレポート
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SheetReport {
+    /// シート名
+    pub name: String,
+
+    /// 出力グリッドの行数
+    pub rows: usize,
+
+    /// 出力グリッドの列数
+    pub cols: usize,
+
+    /// 処理したセル数（空でないセル）
+    pub cells: usize,
+
+    /// 出力サイズ（バイト）
+    pub output_size: u64,
+
+    /// シート処理の推定メモリ使用量（バイト）
+    pub memory_estimate: u64,
+
+    /// シートで検出された警告
+    pub warnings: Vec<ConversionWarning>,
+
+    /// 定数列として表から除去した列（`ConverterBuilder::with_constant_column_folding(true)`の場合）
+    pub folded_columns: Vec<FoldedColumn>,
+
+    /// 行の内容による絞り込みで除去した行数（`ConverterBuilder::with_content_filter()`の場合）
+    pub filtered_rows: usize,
+
+    /// 除去した空行の数（`ConverterBuilder::with_blank_rows()`で除去する場合）
+    pub removed_blank_rows: usize,
+
+    /// 非表示として扱った、高さが0の行数（`ConverterBuilder::with_zero_size_as_hidden(true)`の場合）
+    pub zero_size_rows: usize,
+
+    /// 非表示として扱った、幅が0の列数（`ConverterBuilder::with_zero_size_as_hidden(true)`の場合）
+    pub zero_size_cols: usize,
+
+    /// 列幅の上限で切り詰めたセルの数
+    ///
+    /// `ConverterBuilder::with_column_width_limit()`で`WrapStrategy::Truncate`を指定した
+    /// Markdown形式の表のみで数えます。
+    pub truncated_cells: usize,
+
+    /// 出力する表の行数・セル数の上限で省略した行数
+    /// （`ConverterBuilder::with_max_rows()`・`with_max_cells()`の場合）
+    pub omitted_rows: usize,
+
+    /// フェーズ別の処理時間
+    pub timings: SheetTimings,
+}
+
+/// 定数列として表から除去した列
+///
+/// 見出し以外のすべての行が同じ値を持つ列は、表の前に「見出し: 値」の形式で出力されます。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FoldedColumn {
+    /// 元の列名（例: `C`）
+    pub column: String,
+
+    /// 見出し（見出しが空の場合は列名）
+    pub header: String,
+
+    /// 列のすべての行に共通する値（出力フォーマット適用後の文字列）
+    pub value: String,
+}
+
+/// シートごとのフェーズ別処理時間
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct SheetTimings {
+    /// ワークシートの解析（セルデータの抽出）
+    pub parse: Duration,
+
+    /// セル値のフォーマット
+    pub format: Duration,
+
+    /// グリッドの構築（セル結合の処理を含む）
+    pub grid_build: Duration,
+
+    /// 出力フォーマットへのレンダリング
+    pub render: Duration,
+}
+
+impl SheetTimings {
+    /// シート処理全体の時間（各フェーズの合計）
+    pub fn total(&self) -> Duration {
+        self.parse + self.format + self.grid_build + self.render
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sheet_timings_total() {
+        let timings = SheetTimings {
+            parse: Duration::from_millis(10),
+            format: Duration::from_millis(5),
+            grid_build: Duration::from_millis(3),
+            render: Duration::from_millis(2),
+        };
+        assert_eq!(timings.total(), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_report_default() {
+        let report = ConversionReport::default();
+        assert!(report.sheets.is_empty());
+        assert_eq!(report.timings.total, Duration::ZERO);
+        assert_eq!(report.peak_memory_estimate, 0);
+        assert!(report.warnings.is_empty());
+        assert!(report.duplicate_sheets.is_empty());
+    }
+
+    #[test]
+    fn test_warning_display() {
+        let warning = ConversionWarning::sheet(
+            "Sheet1",
+            WarningKind::UsedRangeBloat,
+            "used range is bloated".to_string(),
+        );
+        assert_eq!(warning.to_string(), "Sheet 'Sheet1': used range is bloated");
+    }
+}
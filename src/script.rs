@@ -0,0 +1,157 @@
+//! Script Detection Module
+//!
+//! シート内テキストの主要な文字体系（スクリプト）を簡易的に判定するモジュール。
+//! 多言語RAGパイプラインで、埋め込みモデルの振り分けに利用することを想定しています。
+
+/// 判定対象とする最大文字数（大きなシートでも判定コストを一定に保つ）
+const MAX_SCANNED_CHARS: usize = 10_000;
+
+/// 文字体系（スクリプト）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Script {
+    /// ラテン文字（英語、欧州言語など）
+    Latin,
+
+    /// CJK（漢字、ひらがな、カタカナ、ハングル）
+    Cjk,
+
+    /// キリル文字
+    Cyrillic,
+
+    /// 上記以外の文字体系
+    Other,
+}
+
+impl Script {
+    /// メタデータ出力用の識別子を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Script::Latin => "latin",
+            Script::Cjk => "cjk",
+            Script::Cyrillic => "cyrillic",
+            Script::Other => "other",
+        }
+    }
+
+    /// 文字の文字体系を判定（内部ヘルパー）
+    ///
+    /// 数字・記号・空白など、文字体系に属さない文字の場合は`None`を返します。
+    fn of_char(ch: char) -> Option<Self> {
+        match ch as u32 {
+            // ハングル字母・CJK記号・ひらがな・カタカナ・CJK統合漢字・ハングル音節
+            0x1100..=0x11FF
+            | 0x3040..=0x30FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0xFF66..=0xFF9F
+            | 0x20000..=0x2FA1F => Some(Script::Cjk),
+            // キリル文字・キリル文字補助
+            0x0400..=0x052F => Some(Script::Cyrillic),
+            // 基本ラテン・ラテン1補助・ラテン拡張
+            _ if ch.is_ascii_alphabetic() => Some(Script::Latin),
+            0x00C0..=0x024F | 0x1E00..=0x1EFF if ch.is_alphabetic() => Some(Script::Latin),
+            // 全角英字
+            0xFF21..=0xFF3A | 0xFF41..=0xFF5A => Some(Script::Latin),
+            _ if ch.is_alphabetic() => Some(Script::Other),
+            _ => None,
+        }
+    }
+}
+
+/// テキスト群の主要な文字体系を判定
+///
+/// 先頭から最大10,000文字を走査し、文字体系ごとの出現数が最も多いものを返します。
+/// 出現数が同数の場合は、CJK、キリル文字、ラテン文字、その他の順で優先します。
+///
+/// # 引数
+///
+/// * `texts` - 判定対象のテキスト（セルの内容など）
+///
+/// # 戻り値
+///
+/// * `Some(Script)` - 主要な文字体系
+/// * `None` - 文字体系に属する文字が含まれない場合（数値のみのシートなど）
+pub(crate) fn detect_script<'a, I>(texts: I) -> Option<Script>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    // [CJK, Cyrillic, Latin, Other]
+    let mut counts = [0usize; 4];
+
+    let chars = texts
+        .into_iter()
+        .flat_map(str::chars)
+        .take(MAX_SCANNED_CHARS);
+    for ch in chars {
+        match Script::of_char(ch) {
+            Some(Script::Cjk) => counts[0] += 1,
+            Some(Script::Cyrillic) => counts[1] += 1,
+            Some(Script::Latin) => counts[2] += 1,
+            Some(Script::Other) => counts[3] += 1,
+            None => {}
+        }
+    }
+
+    let scripts = [Script::Cjk, Script::Cyrillic, Script::Latin, Script::Other];
+    let mut dominant: Option<(Script, usize)> = None;
+    for (script, count) in scripts.into_iter().zip(counts) {
+        if count > dominant.map_or(0, |(_, c)| c) {
+            dominant = Some((script, count));
+        }
+    }
+
+    dominant.map(|(script, _)| script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_script_latin() {
+        assert_eq!(
+            detect_script(["Header1", "Hello, world"]),
+            Some(Script::Latin)
+        );
+        assert_eq!(detect_script(["Café", "naïve"]), Some(Script::Latin));
+    }
+
+    #[test]
+    fn test_detect_script_cjk() {
+        assert_eq!(
+            detect_script(["市区町村コード", "店舗名"]),
+            Some(Script::Cjk)
+        );
+        assert_eq!(detect_script(["カタカナ", "ID"]), Some(Script::Cjk));
+        assert_eq!(detect_script(["안녕하세요"]), Some(Script::Cjk));
+    }
+
+    #[test]
+    fn test_detect_script_cyrillic() {
+        assert_eq!(detect_script(["Привет", "мир"]), Some(Script::Cyrillic));
+    }
+
+    #[test]
+    fn test_detect_script_dominant() {
+        // ラテン文字よりCJKが多い場合はCJK
+        assert_eq!(detect_script(["ID", "東京都千代田区"]), Some(Script::Cjk));
+        // 同数の場合はCJKを優先
+        assert_eq!(detect_script(["ab", "日本"]), Some(Script::Cjk));
+    }
+
+    #[test]
+    fn test_detect_script_none() {
+        assert_eq!(detect_script(["123", "4.56", "---"]), None);
+        assert_eq!(detect_script(Vec::<&str>::new()), None);
+    }
+
+    #[test]
+    fn test_script_as_str() {
+        assert_eq!(Script::Latin.as_str(), "latin");
+        assert_eq!(Script::Cjk.as_str(), "cjk");
+        assert_eq!(Script::Cyrillic.as_str(), "cyrillic");
+        assert_eq!(Script::Other.as_str(), "other");
+    }
+}
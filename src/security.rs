@@ -3,11 +3,39 @@
 //! セキュリティ対策を実装するモジュール。
 //! ZIP bomb攻撃、XXE攻撃、パストラバーサル攻撃などへの対策を提供します。
 
-/// セキュリティ設定
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashSet;
+use std::io::{Read, Seek, Write};
+use std::ops::{Deref, DerefMut};
+use zip::ZipArchive;
+
+use crate::error::{SecurityLimit, XlsxToMdError};
+
+/// 圧縮率の上限を適用しない展開後のサイズ（バイト）
+///
+/// 小さなXMLパーツは圧縮率が高くなりやすいため、このサイズまでは圧縮率を検証しません。
+const COMPRESSION_RATIO_GRACE_SIZE: u64 = 1_048_576; // 1MB
+
+/// セキュリティ制限
 ///
 /// ファイル処理時のセキュリティ制限を定義します。
-#[derive(Debug, Clone)]
-pub(crate) struct SecurityConfig {
+/// `ConverterBuilder::with_security_limits()`で変換ごとに指定できます。
+/// 一部の上限のみを変更する場合は、`..SecurityLimits::default()`で残りをデフォルト値にします。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::{ConverterBuilder, SecurityLimits};
+///
+/// let builder = ConverterBuilder::new().with_security_limits(SecurityLimits {
+///     max_input_file_size: 10 * 1024 * 1024,
+///     max_file_count: 1_000,
+///     ..SecurityLimits::default()
+/// });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityLimits {
     /// 展開後の最大サイズ（バイト）
     /// デフォルト: 1GB (1_073_741_824 bytes)
     pub max_decompressed_size: u64,
@@ -20,27 +48,170 @@ pub(crate) struct SecurityConfig {
     /// 入力ファイルの最大サイズ（バイト）
     /// デフォルト: 2GB (2_147_483_648 bytes)
     pub max_input_file_size: u64,
+    /// 1つの結合範囲を展開する最大セル数
+    /// デフォルト: 1,000,000
+    pub max_merged_cells: u64,
+    /// ZIPアーカイブ内の各ファイルの最大圧縮率（展開後のサイズ / 圧縮後のサイズ）
+    /// 展開後のサイズが1MB以下のファイルには適用しません
+    /// デフォルト: 100
+    pub max_compression_ratio: u64,
 }
 
-impl Default for SecurityConfig {
+impl Default for SecurityLimits {
     fn default() -> Self {
         Self {
             max_decompressed_size: 1_073_741_824, // 1GB
             max_file_count: 10_000,
             max_file_size: 104_857_600,         // 100MB
             max_input_file_size: 2_147_483_648, // 2GB
+            max_merged_cells: 1_000_000,
+            max_compression_ratio: 100,
         }
     }
 }
 
-impl SecurityConfig {
-    /// デフォルトのセキュリティ設定を作成
-    #[allow(dead_code)]
+impl SecurityLimits {
+    /// デフォルトのセキュリティ制限を作成
     pub fn new() -> Self {
         Self::default()
     }
 }
 
+/// XMLパーツの解析の制限
+///
+/// 悪意のあるXMLパーツで解析が終わらない、またはスタックやメモリを使い果たすことを防ぐため、
+/// このクレートが解析するすべてのXMLパーツに適用します。
+/// DOCTYPE宣言（内部サブセットのENTITY宣言を含む）は常に拒否します。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct XmlLimits {
+    /// 要素の最大の入れ子の深さ
+    /// デフォルト: 256
+    pub max_depth: usize,
+    /// 1つの要素の最大属性数
+    /// デフォルト: 512
+    pub max_attributes: usize,
+    /// 1つのパーツの最大イベント数（開始・終了タグ、テキストなど）
+    /// デフォルト: 100,000,000
+    pub max_events: u64,
+}
+
+impl Default for XmlLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 256,
+            max_attributes: 512,
+            max_events: 100_000_000,
+        }
+    }
+}
+
+/// 制限を検証しながらXMLパーツを読み込むリーダー
+///
+/// `quick_xml::Reader`をラップし、`read_event()`で読み込んだイベントごとに`XmlLimits`を検証します。
+/// リーダーの設定（`trim_text()`など）はラップしたリーダーのメソッドをそのまま使用します。
+pub(crate) struct XmlReader<'a> {
+    /// ラップしたリーダー
+    reader: Reader<&'a [u8]>,
+    /// パーツ名（エラーメッセージに使用）
+    part: &'a str,
+    /// 解析の制限
+    limits: XmlLimits,
+    /// 現在の要素の入れ子の深さ
+    depth: usize,
+    /// 読み込んだイベント数
+    events: u64,
+}
+
+impl<'a> XmlReader<'a> {
+    /// デフォルトの制限でXMLパーツのリーダーを作成
+    pub fn new(part: &'a str, xml: &'a [u8]) -> Self {
+        Self::with_limits(part, xml, XmlLimits::default())
+    }
+
+    /// 指定した制限でXMLパーツのリーダーを作成
+    pub fn with_limits(part: &'a str, xml: &'a [u8], limits: XmlLimits) -> Self {
+        Self {
+            reader: Reader::from_reader(xml),
+            part,
+            limits,
+            depth: 0,
+            events: 0,
+        }
+    }
+
+    /// 次のイベントを読み込み、制限を検証
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(Event)` - 読み込んだイベント
+    /// * `Err(XlsxToMdError::Xml)` - XMLの解析エラーが発生した場合
+    /// * `Err(XlsxToMdError::SecurityViolation)` - DOCTYPE宣言がある場合、または制限を超えた場合
+    pub fn read_event(&mut self) -> Result<Event<'a>, XlsxToMdError> {
+        let event = self
+            .reader
+            .read_event()
+            .map_err(|e| XlsxToMdError::xml(self.part, e))?;
+
+        self.events += 1;
+        if self.events > self.limits.max_events {
+            return Err(SecurityLimit::XmlEvents {
+                part: self.part.to_string(),
+                max: self.limits.max_events,
+            }
+            .into());
+        }
+
+        match &event {
+            Event::DocType(_) => {
+                return Err(SecurityLimit::XmlDoctype {
+                    part: self.part.to_string(),
+                }
+                .into());
+            }
+            Event::Start(e) | Event::Empty(e) => {
+                let depth = self.depth + 1;
+                if depth > self.limits.max_depth {
+                    return Err(SecurityLimit::XmlDepth {
+                        part: self.part.to_string(),
+                        depth,
+                        max: self.limits.max_depth,
+                    }
+                    .into());
+                }
+                let attributes = e.attributes().with_checks(false).count();
+                if attributes > self.limits.max_attributes {
+                    return Err(SecurityLimit::XmlAttributes {
+                        part: self.part.to_string(),
+                        count: attributes,
+                        max: self.limits.max_attributes,
+                    }
+                    .into());
+                }
+                if matches!(event, Event::Start(_)) {
+                    self.depth = depth;
+                }
+            }
+            Event::End(_) => self.depth = self.depth.saturating_sub(1),
+            _ => {}
+        }
+        Ok(event)
+    }
+}
+
+impl<'a> Deref for XmlReader<'a> {
+    type Target = Reader<&'a [u8]>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.reader
+    }
+}
+
+impl DerefMut for XmlReader<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.reader
+    }
+}
+
 /// ファイルパスの検証
 ///
 /// パストラバーサル攻撃を防ぐため、ファイルパスを検証します。
@@ -52,34 +223,610 @@ impl SecurityConfig {
 /// # 戻り値
 ///
 /// * `Ok(())` - パスが安全な場合
-/// * `Err(String)` - パスが危険な場合（`..`や絶対パスを含む）
-pub(crate) fn validate_zip_path(path: &str) -> Result<(), String> {
+/// * `Err(XlsxToMdError::SecurityViolation)` - パスが危険な場合（`..`や絶対パスを含む）
+pub(crate) fn validate_zip_path(path: &str) -> Result<(), XlsxToMdError> {
+    let invalid = |reason| {
+        Err(SecurityLimit::InvalidPath {
+            path: path.to_string(),
+            reason,
+        }
+        .into())
+    };
+
     // 空のパスは拒否
     if path.is_empty() {
-        return Err("Empty path is not allowed".to_string());
+        return invalid("empty path is not allowed");
     }
 
     // 絶対パスを拒否（Windows形式の`C:\`やUnix形式の`/`で始まるパス）
     if path.starts_with('/') || path.starts_with("C:\\") || path.starts_with("c:\\") {
-        return Err(format!("Absolute path is not allowed: {}", path));
+        return invalid("absolute path is not allowed");
     }
 
     // `..`を含むパスを拒否（ディレクトリトラバーサル攻撃）
     if path.contains("..") {
-        return Err(format!("Path traversal detected: {}", path));
+        return invalid("path traversal detected");
     }
 
     // `\`を含むパスを拒否（Windows形式のパスセパレータ）
     if path.contains('\\') {
-        return Err(format!("Backslash in path is not allowed: {}", path));
+        return invalid("backslash in path is not allowed");
+    }
+
+    Ok(())
+}
+
+/// ZIPアーカイブのすべてのファイルを検証
+///
+/// ファイル数、パス、宣言されたサイズと圧縮率を検証した後、各ファイルを実際に展開して
+/// 展開したバイト数でサイズ・圧縮率・合計サイズの上限を検証します。
+/// 宣言されたサイズを偽装したアーカイブでも、上限を超えた時点で展開を打ち切ります。
+/// 画像ファイル（`is_media_part()`）のうちワークブックのリレーションシップから参照されないものは
+/// calamineが展開しないため、事前には展開せず、読み込む時点で`read_entry()`により
+/// 宣言されたサイズを超えないことを検証します。
+///
+/// # 引数
+///
+/// * `archive` - 検証するZIPアーカイブ
+/// * `limits` - セキュリティ制限
+///
+/// # 戻り値
+///
+/// * `Ok(())` - すべてのファイルが上限の範囲内の場合
+/// * `Err(XlsxToMdError::SecurityViolation)` - 上限を超えるファイルがある場合（ファイル名を含む）
+pub(crate) fn check_archive<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    limits: &SecurityLimits,
+) -> Result<(), XlsxToMdError> {
+    // 1. ファイル数の上限
+    if archive.len() > limits.max_file_count {
+        return Err(SecurityLimit::FileCount {
+            count: archive.len(),
+            max: limits.max_file_count,
+        }
+        .into());
+    }
+
+    // 2. 各ファイルのパスと宣言されたサイズ（展開せずに拒否できるものを先に検証）
+    let mut total_declared_size = 0u64;
+    for i in 0..archive.len() {
+        let file = archive.by_index_raw(i).map_err(XlsxToMdError::zip)?;
+        let file_name = file.name();
+        validate_zip_path(file_name)?;
+
+        let file_size = file.size();
+        if file_size > limits.max_file_size {
+            return Err(SecurityLimit::FileSize {
+                part: file_name.to_string(),
+                size: file_size,
+                max: limits.max_file_size,
+            }
+            .into());
+        }
+        // 画像ファイルは展開しない場合があるため、宣言されたサイズでも圧縮率を検証
+        let ratio_limit = ratio_limit(file.compressed_size(), limits);
+        if is_media_part(file_name) && file_size > ratio_limit {
+            return Err(SecurityLimit::CompressionRatio {
+                part: file_name.to_string(),
+                size: file_size,
+                compressed_size: file.compressed_size(),
+                max_ratio: limits.max_compression_ratio,
+            }
+            .into());
+        }
+        total_declared_size = total_declared_size.saturating_add(file_size);
+        if total_declared_size > limits.max_decompressed_size {
+            return Err(SecurityLimit::DecompressedSize {
+                size: total_declared_size,
+                max: limits.max_decompressed_size,
+            }
+            .into());
+        }
     }
 
+    // 3. 実際に展開したバイト数による検証（宣言されたサイズは偽装できるため）
+    //    calamineは拡張子に関係なくリレーションシップのターゲットを上限なしで展開するため、
+    //    ワークブックから参照されない画像ファイル以外はすべて事前に検証する
+    let workbook_targets = workbook_targets(archive, limits)?;
+    let mut total_decompressed_size = 0u64;
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(XlsxToMdError::zip)?;
+        let file_name = file.name().to_string();
+        if is_media_part(&file_name) && !workbook_targets.contains(&file_name.to_ascii_lowercase())
+        {
+            continue;
+        }
+        let compressed_size = file.compressed_size();
+        let size = inflate_entry(
+            &file_name,
+            compressed_size,
+            &mut file,
+            limits,
+            &mut std::io::sink(),
+        )?;
+        total_decompressed_size = total_decompressed_size.saturating_add(size);
+        if total_decompressed_size > limits.max_decompressed_size {
+            return Err(SecurityLimit::DecompressedSize {
+                size: total_decompressed_size,
+                max: limits.max_decompressed_size,
+            }
+            .into());
+        }
+    }
     Ok(())
 }
 
+/// ZIPアーカイブのファイルを上限を検証しながら展開
+///
+/// 展開したバイト数を数え、単一ファイルの最大サイズか圧縮率の上限
+/// （`COMPRESSION_RATIO_GRACE_SIZE`を超える部分のみ）を1バイトでも超えた時点で展開を打ち切ります。
+///
+/// # 引数
+///
+/// * `name` - ファイル名（エラーメッセージに使用）
+/// * `compressed_size` - 圧縮後のサイズ（バイト）
+/// * `entry` - 展開するファイルのリーダー
+/// * `limits` - セキュリティ制限
+/// * `writer` - 展開した内容の出力先
+///
+/// # 戻り値
+///
+/// * `Ok(u64)` - 展開したバイト数
+/// * `Err(XlsxToMdError::SecurityViolation)` - 上限を超えた場合
+pub(crate) fn inflate_entry<W: Write>(
+    name: &str,
+    compressed_size: u64,
+    entry: &mut impl Read,
+    limits: &SecurityLimits,
+    writer: &mut W,
+) -> Result<u64, XlsxToMdError> {
+    let limit = limits
+        .max_file_size
+        .min(ratio_limit(compressed_size, limits));
+    let size = std::io::copy(&mut entry.take(limit.saturating_add(1)), writer)?;
+    if size <= limit {
+        return Ok(size);
+    }
+    // 上限を1バイト超えた時点で打ち切るため、観測したサイズは上限+1バイト
+    if limit == limits.max_file_size {
+        Err(SecurityLimit::FileSize {
+            part: name.to_string(),
+            size,
+            max: limits.max_file_size,
+        }
+        .into())
+    } else {
+        Err(SecurityLimit::CompressionRatio {
+            part: name.to_string(),
+            size,
+            compressed_size,
+            max_ratio: limits.max_compression_ratio,
+        }
+        .into())
+    }
+}
+
+/// 圧縮率の上限から求めた展開後のサイズの上限（内部ヘルパー）
+fn ratio_limit(compressed_size: u64, limits: &SecurityLimits) -> u64 {
+    compressed_size
+        .saturating_mul(limits.max_compression_ratio)
+        .max(COMPRESSION_RATIO_GRACE_SIZE)
+}
+
+/// ワークブックのリレーションシップ（`.xml`・`.bin`）のパーツ名
+const WORKBOOK_RELS_PARTS: [&str; 2] = ["xl/_rels/workbook.xml.rels", "xl/_rels/workbook.bin.rels"];
+
+/// `check_archive()`が事前に展開しない場合がある画像ファイルかどうか
+///
+/// `xl/media/`以下のファイルとサムネイル画像（`docProps/thumbnail.*`）が対象です（大文字と小文字を区別しない）。
+pub(crate) fn is_media_part(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.starts_with("xl/media/") || name.starts_with("docprops/thumbnail")
+}
+
+/// ワークブックのリレーションシップのターゲットをcalamineと同じ規則でパーツ名に解決（内部ヘルパー）
+///
+/// calamineはパーツ名の大文字と小文字を区別せずに開くため、小文字に変換したパーツ名を返します。
+/// リレーションシップのパーツも上限を検証しながら展開します。
+fn workbook_targets<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    limits: &SecurityLimits,
+) -> Result<HashSet<String>, XlsxToMdError> {
+    let rels_parts: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            WORKBOOK_RELS_PARTS
+                .iter()
+                .any(|part| name.eq_ignore_ascii_case(part))
+        })
+        .map(str::to_string)
+        .collect();
+
+    let mut targets = HashSet::new();
+    for part in rels_parts {
+        let mut file = archive.by_name(&part).map_err(XlsxToMdError::zip)?;
+        let compressed_size = file.compressed_size();
+        let mut xml = Vec::new();
+        inflate_entry(&part, compressed_size, &mut file, limits, &mut xml)?;
+
+        let mut reader = XmlReader::new(&part, &xml);
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                    for attr in e.attributes() {
+                        let attr = attr.map_err(|e| XlsxToMdError::xml(&part, e))?;
+                        if attr.key.local_name().as_ref() != b"Target" {
+                            continue;
+                        }
+                        let target = std::str::from_utf8(&attr.value)?;
+                        let target = target.strip_prefix('/').unwrap_or(target);
+                        let target = if target.starts_with("xl/") {
+                            target.to_string()
+                        } else {
+                            format!("xl/{}", target)
+                        };
+                        targets.insert(target.to_ascii_lowercase());
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// ZIPアーカイブのファイルを宣言されたサイズを上限として読み込む
+///
+/// `check_archive()`は一部の画像ファイルを展開せず、宣言されたサイズのみを検証するため、
+/// これらのファイルは宣言されたサイズを1バイトでも超えた時点で読み込みを打ち切ります。
+///
+/// # 引数
+///
+/// * `name` - ファイル名（エラーメッセージに使用）
+/// * `declared_size` - 宣言された展開後のサイズ（バイト）
+/// * `entry` - 展開するファイルのリーダー
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - 展開した内容
+/// * `Err(XlsxToMdError::SecurityViolation)` - 宣言されたサイズを超えた場合
+pub(crate) fn read_entry(
+    name: &str,
+    declared_size: u64,
+    entry: &mut impl Read,
+) -> Result<Vec<u8>, XlsxToMdError> {
+    let mut content = Vec::new();
+    entry
+        .take(declared_size.saturating_add(1))
+        .read_to_end(&mut content)?;
+    if content.len() as u64 > declared_size {
+        return Err(SecurityLimit::DeclaredSize {
+            part: name.to_string(),
+            declared_size,
+        }
+        .into());
+    }
+    Ok(content)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Cursor;
+    use zip::write::{FileOptions, ZipWriter};
+    use zip::CompressionMethod;
+
+    /// 指定した内容のファイルを1つ含むZIPアーカイブを生成
+    fn archive(
+        name: &str,
+        content: &[u8],
+        method: CompressionMethod,
+    ) -> ZipArchive<Cursor<Vec<u8>>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(method);
+        writer.start_file(name, options).unwrap();
+        writer.write_all(content).unwrap();
+        ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap()
+    }
+
+    #[test]
+    fn test_inflate_entry_within_limits() {
+        let content = b"<worksheet/>".repeat(100);
+        let mut archive = archive(
+            "xl/worksheets/sheet1.xml",
+            &content,
+            CompressionMethod::Deflated,
+        );
+        let mut file = archive.by_index(0).unwrap();
+        let compressed_size = file.compressed_size();
+        let mut output = Vec::new();
+        let size = inflate_entry(
+            "xl/worksheets/sheet1.xml",
+            compressed_size,
+            &mut file,
+            &SecurityLimits::default(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(size, content.len() as u64);
+        assert_eq!(output, content);
+    }
+
+    #[test]
+    fn test_inflate_entry_compression_ratio() {
+        // 2MBのゼロは数KBに圧縮され、圧縮率の上限（100倍）を超える
+        let content = vec![0u8; 2 * 1_048_576];
+        let mut archive = archive(
+            "xl/sharedStrings.xml",
+            &content,
+            CompressionMethod::Deflated,
+        );
+        let mut file = archive.by_index(0).unwrap();
+        let compressed_size = file.compressed_size();
+        let error = inflate_entry(
+            "xl/sharedStrings.xml",
+            compressed_size,
+            &mut file,
+            &SecurityLimits::default(),
+            &mut std::io::sink(),
+        )
+        .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("'xl/sharedStrings.xml'"), "{}", message);
+        assert!(message.contains("compression ratio"), "{}", message);
+
+        drop(file);
+
+        // 圧縮率の上限を引き上げれば展開できる
+        let limits = SecurityLimits {
+            max_compression_ratio: 10_000,
+            ..SecurityLimits::default()
+        };
+        let mut file = archive.by_index(0).unwrap();
+        let size = inflate_entry(
+            "xl/sharedStrings.xml",
+            compressed_size,
+            &mut file,
+            &limits,
+            &mut std::io::sink(),
+        )
+        .unwrap();
+        assert_eq!(size, content.len() as u64);
+    }
+
+    #[test]
+    fn test_check_archive_max_file_size() {
+        let content = vec![b'a'; 4096];
+        let mut archive = archive("xl/styles.xml", &content, CompressionMethod::Stored);
+        let limits = SecurityLimits {
+            max_file_size: 1024,
+            ..SecurityLimits::default()
+        };
+        let error = check_archive(&mut archive, &limits).unwrap_err();
+        assert!(error.to_string().contains("'xl/styles.xml'"), "{}", error);
+        assert!(check_archive(&mut archive, &SecurityLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_check_archive_total_decompressed_size() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for name in ["xl/a.xml", "xl/b.xml"] {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(&[b'a'; 600]).unwrap();
+        }
+        let mut archive =
+            ZipArchive::new(Cursor::new(writer.finish().unwrap().into_inner())).unwrap();
+        let limits = SecurityLimits {
+            max_decompressed_size: 1000,
+            ..SecurityLimits::default()
+        };
+        let error = check_archive(&mut archive, &limits).unwrap_err();
+        assert!(error.to_string().contains("decompressed size"), "{}", error);
+    }
+
+    #[test]
+    fn test_check_archive_media_declared_ratio() {
+        // XMLパーツ以外は展開せず、宣言されたサイズで圧縮率を検証する
+        let content = vec![0u8; 2 * 1_048_576];
+        let mut media = archive("xl/media/image1.png", &content, CompressionMethod::Deflated);
+        let error = check_archive(&mut media, &SecurityLimits::default()).unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("'xl/media/image1.png'"), "{}", message);
+        assert!(message.contains("compression ratio"), "{}", message);
+
+        let mut media = archive("xl/media/image1.png", &content, CompressionMethod::Stored);
+        assert!(check_archive(&mut media, &SecurityLimits::default()).is_ok());
+    }
+
+    #[test]
+    fn test_is_media_part() {
+        assert!(is_media_part("xl/media/image1.png"));
+        assert!(is_media_part("XL/Media/image1.PNG"));
+        assert!(is_media_part("docProps/thumbnail.jpeg"));
+        assert!(!is_media_part("xl/worksheets/sheet1.xml"));
+        assert!(!is_media_part("xl/worksheets/s1.bin"));
+        assert!(!is_media_part("xl/vbaProject.bin"));
+    }
+
+    /// ZIPアーカイブ内のファイルの宣言された展開後のサイズを書き換える
+    /// （ローカルファイルヘッダーと中央ディレクトリの両方）
+    fn forge_declared_size(zip: &mut [u8], name: &str, size: u32) {
+        let name = name.as_bytes();
+        for (signature, size_offset, name_offset) in
+            [(0x04034b50u32, 22, 30), (0x02014b50u32, 24, 46)]
+        {
+            let mut pos = 0;
+            while pos + name_offset + name.len() <= zip.len() {
+                if zip[pos..pos + 4] == signature.to_le_bytes()
+                    && &zip[pos + name_offset..pos + name_offset + name.len()] == name
+                {
+                    zip[pos + size_offset..pos + size_offset + 4]
+                        .copy_from_slice(&size.to_le_bytes());
+                }
+                pos += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_archive_forged_size_by_rels_target() {
+        // calamineは拡張子に関係なくリレーションシップのターゲットを展開するため、
+        // `.bin`のワークシートや画像ファイル名のワークシートも実際に展開して検証する
+        let bomb = vec![0u8; 2 * 1_048_576];
+        for (target, part) in [
+            ("worksheets/s1.bin", "xl/worksheets/s1.bin"),
+            ("/xl/media/sheet.png", "xl/media/sheet.png"),
+        ] {
+            let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+            let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+            writer
+                .start_file("xl/_rels/workbook.xml.rels", options)
+                .unwrap();
+            write!(
+                writer,
+                r#"<Relationships><Relationship Id="rId1" Target="{}"/></Relationships>"#,
+                target
+            )
+            .unwrap();
+            writer.start_file(part, options).unwrap();
+            writer.write_all(&bomb).unwrap();
+            let mut zip = writer.finish().unwrap().into_inner();
+            forge_declared_size(&mut zip, part, 100);
+
+            let mut archive = ZipArchive::new(Cursor::new(zip)).unwrap();
+            assert_eq!(archive.by_name(part).unwrap().size(), 100);
+            let error = check_archive(&mut archive, &SecurityLimits::default()).unwrap_err();
+            let message = error.to_string();
+            assert!(message.contains(&format!("'{}'", part)), "{}", message);
+            assert!(message.contains("compression ratio"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_check_archive_forged_size_unreferenced_media() {
+        // ワークブックから参照されない画像ファイルは事前に展開せず、読み込む時点で打ち切る
+        let bomb = vec![0u8; 2 * 1_048_576];
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file("xl/media/image1.png", FileOptions::default())
+            .unwrap();
+        writer.write_all(&bomb).unwrap();
+        let mut zip = writer.finish().unwrap().into_inner();
+        forge_declared_size(&mut zip, "xl/media/image1.png", 100);
+
+        let mut archive = ZipArchive::new(Cursor::new(zip)).unwrap();
+        assert!(check_archive(&mut archive, &SecurityLimits::default()).is_ok());
+        let mut file = archive.by_name("xl/media/image1.png").unwrap();
+        let error = read_entry("xl/media/image1.png", file.size(), &mut file).unwrap_err();
+        assert!(error.to_string().contains("declared size"), "{}", error);
+    }
+
+    #[test]
+    fn test_read_entry_declared_size() {
+        let content = b"\x89PNG....".to_vec();
+        let read = read_entry("xl/media/image1.png", 8, &mut &content[..8]).unwrap();
+        assert_eq!(read, &content[..8]);
+
+        // 宣言されたサイズを超えて展開されるファイルは読み込みを打ち切る
+        let error = read_entry("xl/media/image1.png", 4, &mut content.as_slice()).unwrap_err();
+        assert!(error.to_string().contains("declared size"), "{}", error);
+    }
+
+    #[test]
+    fn test_xml_reader_within_limits() {
+        let xml = br#"<?xml version="1.0"?><a x="1"><b><c/></b></a>"#;
+        let mut reader = XmlReader::new("xl/test.xml", xml);
+        let mut names = Vec::new();
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(e) | Event::Empty(e) => {
+                    names.push(String::from_utf8_lossy(e.name().as_ref()).into_owned())
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    /// 制限を超えるまでイベントを読み込み、違反した制限を返す
+    fn read_error(xml: &[u8], limits: XmlLimits) -> SecurityLimit {
+        let mut reader = XmlReader::with_limits("xl/test.xml", xml, limits);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Eof) => panic!("expected a limit violation"),
+                Ok(_) => {}
+                Err(XlsxToMdError::SecurityViolation(limit)) => return limit,
+                Err(e) => panic!("unexpected error: {:?}", e),
+            }
+        }
+    }
+
+    #[test]
+    fn test_xml_reader_rejects_doctype() {
+        let xml = br#"<?xml version="1.0"?>
+<!DOCTYPE a [<!ENTITY xxe SYSTEM "file:///etc/passwd">]>
+<a>&xxe;</a>"#;
+        assert_eq!(
+            read_error(xml, XmlLimits::default()),
+            SecurityLimit::XmlDoctype {
+                part: "xl/test.xml".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_xml_reader_nesting_depth() {
+        let limits = XmlLimits {
+            max_depth: 3,
+            ..XmlLimits::default()
+        };
+        // 兄弟要素は深さに含めない
+        let mut reader =
+            XmlReader::with_limits("xl/test.xml", b"<a><b><c/></b><b><c/></b></a>", limits);
+        while !matches!(reader.read_event().unwrap(), Event::Eof) {}
+
+        assert_eq!(
+            read_error(b"<a><b><c><d/></c></b></a>", limits),
+            SecurityLimit::XmlDepth {
+                part: "xl/test.xml".to_string(),
+                depth: 4,
+                max: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_xml_reader_attribute_count() {
+        let limits = XmlLimits {
+            max_attributes: 2,
+            ..XmlLimits::default()
+        };
+        assert_eq!(
+            read_error(br#"<a x="1" y="2" z="3"/>"#, limits),
+            SecurityLimit::XmlAttributes {
+                part: "xl/test.xml".to_string(),
+                count: 3,
+                max: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_xml_reader_event_count() {
+        let limits = XmlLimits {
+            max_events: 10,
+            ..XmlLimits::default()
+        };
+        assert_eq!(
+            read_error(&b"<a/>".repeat(20), limits),
+            SecurityLimit::XmlEvents {
+                part: "xl/test.xml".to_string(),
+                max: 10
+            }
+        );
+    }
 
     #[test]
     fn test_validate_zip_path_valid() {
@@ -111,6 +858,15 @@ mod tests {
         assert!(validate_zip_path("xl/../../etc/passwd").is_err());
         assert!(validate_zip_path("xl/..").is_err());
         assert!(validate_zip_path("..").is_err());
+        assert!(matches!(
+            validate_zip_path("../etc/passwd"),
+            Err(XlsxToMdError::SecurityViolation(
+                SecurityLimit::InvalidPath {
+                    reason: "path traversal detected",
+                    ..
+                }
+            ))
+        ));
     }
 
     #[test]
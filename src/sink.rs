@@ -0,0 +1,241 @@
+//! Output Sink Module
+//!
+//! シート単位の出力先（`OutputSink`）を提供するモジュール。
+//! 1回の変換で、シートごとに異なる出力フォーマットのファイルを生成するために使用します。
+
+use crate::api::OutputFormat;
+use crate::error::XlsxToMdError;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// 1シート分の変換結果
+///
+/// `OutputSink::write_sheet`に渡されます。
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct SheetDocument<'a> {
+    /// シート名
+    pub name: &'a str,
+
+    /// 変換対象シート内での出力順（0始まり）
+    pub index: usize,
+
+    /// このシートに適用された出力フォーマット
+    pub format: OutputFormat,
+
+    /// 変換結果の本文
+    pub content: &'a str,
+}
+
+/// 1シート分の変換結果（所有権を持つ形式）
+///
+/// `Converter::convert_sheets`が出力順に返します。
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SheetOutput {
+    /// シート名
+    pub name: String,
+
+    /// 変換対象シート内での出力順（0始まり）
+    pub index: usize,
+
+    /// このシートに適用された出力フォーマット
+    pub format: OutputFormat,
+
+    /// 変換結果の本文
+    pub content: String,
+
+    /// 出力したグリッドの行数
+    pub rows: usize,
+
+    /// 出力したグリッドの列数
+    pub cols: usize,
+}
+
+/// シート単位の出力先
+///
+/// `Converter::convert_to_sink`は、変換したシートを出力順に1つずつ`write_sheet`へ渡します。
+/// ファイル、オブジェクトストレージ、インデックスなど任意の出力先を実装できます。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::{OutputSink, SheetDocument, XlsxToMdError};
+///
+/// /// シート名と本文をメモリに保持する出力先
+/// #[derive(Default)]
+/// struct MemorySink(Vec<(String, String)>);
+///
+/// impl OutputSink for MemorySink {
+///     fn write_sheet(&mut self, doc: &SheetDocument<'_>) -> Result<(), XlsxToMdError> {
+///         self.0.push((doc.name.to_string(), doc.content.to_string()));
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait OutputSink {
+    /// 1シート分の変換結果を書き込む
+    ///
+    /// # 引数
+    ///
+    /// * `doc` - シートの変換結果
+    ///
+    /// # 戻り値
+    ///
+    /// * `Ok(())` - 書き込みに成功した場合
+    /// * `Err(XlsxToMdError)` - エラーが発生した場合（変換処理は中断されます）
+    fn write_sheet(&mut self, doc: &SheetDocument<'_>) -> Result<(), XlsxToMdError>;
+}
+
+/// シートごとに1ファイルを書き出す出力先
+///
+/// ファイル名は`<シート名>.<拡張子>`です。拡張子はシートに適用された出力フォーマットから
+/// 決まります（`md`、`html`、`json`、`csv`）。ファイル名に使用できない文字は`_`に置き換え、
+/// 大文字小文字を区別せずに名前が重複する場合は出力順の番号を付加します。
+///
+/// # 使用例
+///
+/// ```rust,no_run
+/// use xlsxzero::{ConverterBuilder, DirectorySink, OutputFormat};
+/// use std::fs::File;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let converter = ConverterBuilder::new()
+///     .with_sheet_override("Data", OutputFormat::Csv)
+///     .build()?;
+///
+/// let mut sink = DirectorySink::new("out");
+/// converter.convert_to_sink(File::open("example.xlsx")?, &mut sink)?;
+/// for path in sink.written() {
+///     println!("{}", path.display());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DirectorySink {
+    /// 出力先ディレクトリ
+    dir: PathBuf,
+
+    /// 書き出したファイルのパス（出力順）
+    written: Vec<PathBuf>,
+
+    /// 使用済みのファイル名（小文字化したもの）
+    used_names: HashSet<String>,
+}
+
+impl DirectorySink {
+    /// 出力先ディレクトリを指定して生成する
+    ///
+    /// ディレクトリが存在しない場合は、最初の書き込み時に作成します。
+    ///
+    /// # 引数
+    ///
+    /// * `dir` - 出力先ディレクトリ
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            written: Vec::new(),
+            used_names: HashSet::new(),
+        }
+    }
+
+    /// 出力先ディレクトリを取得する
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 書き出したファイルのパスを出力順に取得する
+    pub fn written(&self) -> &[PathBuf] {
+        &self.written
+    }
+
+    /// シートの出力ファイル名を決定（内部ヘルパー）
+    fn file_name(&mut self, doc: &SheetDocument<'_>) -> String {
+        let mut stem = sanitize_file_stem(doc.name);
+        if stem.is_empty() {
+            stem = format!("sheet{}", doc.index + 1);
+        }
+
+        let extension = doc.format.file_extension();
+        let mut file_name = format!("{}.{}", stem, extension);
+        if !self.used_names.insert(file_name.to_lowercase()) {
+            file_name = format!("{}_{}.{}", stem, doc.index + 1, extension);
+            self.used_names.insert(file_name.to_lowercase());
+        }
+        file_name
+    }
+}
+
+impl OutputSink for DirectorySink {
+    fn write_sheet(&mut self, doc: &SheetDocument<'_>) -> Result<(), XlsxToMdError> {
+        std::fs::create_dir_all(&self.dir)?;
+        let file_name = self.file_name(doc);
+        let path = self.dir.join(file_name);
+        std::fs::write(&path, doc.content)?;
+        self.written.push(path);
+        Ok(())
+    }
+}
+
+/// シート名をファイル名として使用できる文字列に変換（内部ヘルパー）
+///
+/// パス区切りや予約文字、制御文字を`_`に置き換え、前後の空白とピリオドを取り除きます。
+fn sanitize_file_stem(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect();
+    replaced
+        .trim_matches(|c: char| c.is_whitespace() || c == '.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc<'a>(name: &'a str, index: usize, format: OutputFormat) -> SheetDocument<'a> {
+        SheetDocument {
+            name,
+            index,
+            format,
+            content: "",
+        }
+    }
+
+    #[test]
+    fn test_sanitize_file_stem() {
+        assert_eq!(sanitize_file_stem("Sales"), "Sales");
+        assert_eq!(sanitize_file_stem("a/b\\c:d"), "a_b_c_d");
+        assert_eq!(sanitize_file_stem(".."), "");
+        assert_eq!(sanitize_file_stem(" 売上 "), "売上");
+    }
+
+    #[test]
+    fn test_directory_sink_file_name() {
+        let mut sink = DirectorySink::new("out");
+        assert_eq!(
+            sink.file_name(&doc("Data", 0, OutputFormat::Csv)),
+            "Data.csv"
+        );
+        assert_eq!(
+            sink.file_name(&doc("Notes", 1, OutputFormat::Markdown)),
+            "Notes.md"
+        );
+        // 大文字小文字のみ異なる名前は重複として扱う
+        assert_eq!(
+            sink.file_name(&doc("data", 2, OutputFormat::Csv)),
+            "data_3.csv"
+        );
+        // ファイル名として使えないシート名
+        assert_eq!(
+            sink.file_name(&doc("..", 3, OutputFormat::Json)),
+            "sheet4.json"
+        );
+    }
+}
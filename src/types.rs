@@ -2,9 +2,30 @@
 //!
 //! クレート全体で使用する共通データ型を定義するモジュール。
 
+use crate::error::{ConfigError, XlsxToMdError};
+use crate::output::LinkInventory;
+use crate::parser::{ConditionalFormat, DataValidation, SheetVisuals};
+use crate::script::Script;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+/// Excelの最終行（1048576）の行インデックス（0始まり）
+pub(crate) const MAX_ROW_INDEX: u32 = 1_048_575;
+
+/// Excelの最終列（XFD）の列インデックス（0始まり）
+pub(crate) const MAX_COL_INDEX: u32 = 16_383;
+
 /// セルの値を表す列挙型
-#[derive(Debug, Clone, PartialEq)]
-pub(crate) enum CellValue {
+///
+/// `PipelineStage`で、フォーマット前のセル値を参照・変更するために使用します。
+/// serdeでは`{"number": 1.5}`、`{"date_time": {"serial": 45000.0, "kind": "date"}}`、`"empty"`の
+/// ように、値の種類をキーとする形式でシリアライズします。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CellValue {
     /// 数値（f64）
     Number(f64),
 
@@ -15,7 +36,16 @@ pub(crate) enum CellValue {
     Bool(bool),
 
     /// エラー値（例: #DIV/0!）
-    Error(String),
+    Error(CellError),
+
+    /// 日付・時刻
+    DateTime {
+        /// Excelのシリアル値
+        serial: f64,
+
+        /// 日付・時刻の種別
+        kind: DateKind,
+    },
 
     /// 空セル
     Empty,
@@ -35,16 +65,227 @@ impl CellValue {
             CellValue::Number(n) => n.to_string(),
             CellValue::String(s) => s.clone(),
             CellValue::Bool(b) => b.to_string(),
-            CellValue::Error(e) => e.clone(),
+            CellValue::Error(e) => e.as_excel_str().to_string(),
+            CellValue::DateTime { serial, .. } => serial.to_string(),
             CellValue::Empty => String::new(),
         }
     }
 }
 
+/// 日付・時刻セルの種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum DateKind {
+    /// 日付のみ（例: `yyyy-mm-dd`）
+    Date,
+
+    /// 時刻のみ（例: `hh:mm:ss`、経過時間`[h]:mm`を含む）
+    Time,
+
+    /// 日付と時刻（例: `yyyy-mm-dd hh:mm`）
+    DateTime,
+}
+
+impl DateKind {
+    /// 出力用の識別子を取得
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateKind::Date => "date",
+            DateKind::Time => "time",
+            DateKind::DateTime => "datetime",
+        }
+    }
+
+    /// 数値書式から日付・時刻の種別を判定
+    ///
+    /// 書式に日付要素（年・月・日）と時刻要素（時・分・秒・AM/PM）のどちらが
+    /// 含まれるかで判定します。引用符で囲まれたリテラル、エスケープ文字、
+    /// `[$-409]`などのロケール指定は無視します。
+    ///
+    /// # 引数
+    ///
+    /// * `format_id` - 数値書式ID（組み込み書式）
+    /// * `format_string` - 数値書式文字列
+    ///
+    /// # 戻り値
+    ///
+    /// * `Some(DateKind)` - 日付・時刻書式の場合
+    /// * `None` - 日付・時刻書式でない場合、または書式が不明な場合
+    pub(crate) fn from_format(format_id: Option<u16>, format_string: Option<&str>) -> Option<Self> {
+        if let Some(format_str) = format_string {
+            return Self::from_format_string(format_str);
+        }
+
+        // Excel組み込み日付書式ID
+        match format_id? {
+            14..=17 => Some(DateKind::Date),
+            18..=21 | 45..=47 => Some(DateKind::Time),
+            22 => Some(DateKind::DateTime),
+            _ => None,
+        }
+    }
+
+    /// 数値書式文字列から日付・時刻の種別を判定（内部ヘルパー）
+    fn from_format_string(format_str: &str) -> Option<Self> {
+        // 最初のセクション（正の数の書式）のみを対象とする
+        let mut tokens = String::new();
+        let mut chars = format_str.chars();
+        let mut elapsed = false;
+        while let Some(ch) = chars.next() {
+            match ch {
+                ';' => break,
+                '"' => {
+                    for c in chars.by_ref() {
+                        if c == '"' {
+                            break;
+                        }
+                    }
+                }
+                '\\' | '_' | '*' => {
+                    chars.next();
+                }
+                '[' => {
+                    let mut inner = String::new();
+                    for c in chars.by_ref() {
+                        if c == ']' {
+                            break;
+                        }
+                        inner.push(c);
+                    }
+                    // 経過時間（[h], [mm], [ss]）は時刻要素として扱う
+                    let inner = inner.to_ascii_lowercase();
+                    if !inner.is_empty() && inner.chars().all(|c| matches!(c, 'h' | 'm' | 's')) {
+                        elapsed = true;
+                    }
+                }
+                _ => tokens.push(ch.to_ascii_lowercase()),
+            }
+        }
+
+        // AM/PM表記は時刻要素（`m`を月と誤判定しないよう先に除去）
+        let has_ampm = tokens.contains("am/pm") || tokens.contains("a/p");
+        let tokens = tokens.replace("am/pm", "").replace("a/p", "");
+
+        let has_hour_or_second = tokens.contains('h') || tokens.contains('s');
+        let has_time = elapsed || has_ampm || has_hour_or_second;
+        // `m`は時・秒と併用される場合は分、それ以外は月として扱う
+        let has_date =
+            tokens.contains('y') || tokens.contains('d') || (tokens.contains('m') && !has_time);
+
+        match (has_date, has_time) {
+            (true, true) => Some(DateKind::DateTime),
+            (true, false) => Some(DateKind::Date),
+            (false, true) => Some(DateKind::Time),
+            (false, false) => None,
+        }
+    }
+
+    /// シリアル値から日付・時刻の種別を推定
+    ///
+    /// 書式が不明な場合に使用します。整数は日付、1未満は時刻、
+    /// それ以外は日付と時刻として扱います。
+    pub(crate) fn from_serial(serial: f64) -> Self {
+        if serial.fract() == 0.0 {
+            DateKind::Date
+        } else if (0.0..1.0).contains(&serial) {
+            DateKind::Time
+        } else {
+            DateKind::DateTime
+        }
+    }
+}
+
+/// セルのエラー値
+///
+/// Excelの数式エラーを表します。出力では`#DIV/0!`などExcelと同じ表記になります。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum CellError {
+    /// 0による除算（`#DIV/0!`）
+    Div0,
+
+    /// 値が利用できない（`#N/A`）
+    #[serde(rename = "na")]
+    NA,
+
+    /// 認識できない名前（`#NAME?`）
+    Name,
+
+    /// 無効なセル参照（`#REF!`）
+    Ref,
+
+    /// 引数の型が不正（`#VALUE!`）
+    Value,
+
+    /// 数値が不正（`#NUM!`）
+    Num,
+
+    /// 範囲の共通部分が空（`#NULL!`）
+    Null,
+
+    /// 外部データの取得中（`#GETTING_DATA`）
+    GettingData,
+}
+
+impl CellError {
+    /// 出力用の識別子を取得（例: `div0`）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CellError::Div0 => "div0",
+            CellError::NA => "na",
+            CellError::Name => "name",
+            CellError::Ref => "ref",
+            CellError::Value => "value",
+            CellError::Num => "num",
+            CellError::Null => "null",
+            CellError::GettingData => "getting_data",
+        }
+    }
+
+    /// Excelでの表記を取得（例: `#DIV/0!`）
+    pub fn as_excel_str(&self) -> &'static str {
+        match self {
+            CellError::Div0 => "#DIV/0!",
+            CellError::NA => "#N/A",
+            CellError::Name => "#NAME?",
+            CellError::Ref => "#REF!",
+            CellError::Value => "#VALUE!",
+            CellError::Num => "#NUM!",
+            CellError::Null => "#NULL!",
+            CellError::GettingData => "#GETTING_DATA",
+        }
+    }
+}
+
+impl std::fmt::Display for CellError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_excel_str())
+    }
+}
+
 /// セル座標（0始まり）
+///
+/// A1形式の文字列と相互に変換できます（`"B3".parse::<CellCoord>()`、`to_string()`）。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::CellCoord;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let coord: CellCoord = "$B$3".parse()?;
+/// assert_eq!(coord, CellCoord::new(2, 1));
+/// assert_eq!(coord.to_string(), "B3");
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub(crate) struct CellCoord {
+pub struct CellCoord {
+    /// 行インデックス（0始まり）
     pub row: u32,
+    /// 列インデックス（0始まり）
     pub col: u32,
 }
 
@@ -55,15 +296,14 @@ impl CellCoord {
     }
 
     /// A1形式の文字列に変換（例: (0, 0) -> "A1"）
-    #[allow(dead_code, clippy::wrong_self_convention)]
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_a1_notation(&self) -> String {
         let col_str = Self::col_index_to_letter(self.col);
         format!("{}{}", col_str, self.row + 1)
     }
 
     /// 列インデックスを文字列に変換（0 -> "A", 25 -> "Z", 26 -> "AA"）
-    #[allow(dead_code)]
-    fn col_index_to_letter(mut col: u32) -> String {
+    pub(crate) fn col_index_to_letter(mut col: u32) -> String {
         let mut result = String::new();
         loop {
             let remainder = col % 26;
@@ -77,10 +317,50 @@ impl CellCoord {
     }
 }
 
-/// セル範囲
+impl fmt::Display for CellCoord {
+    /// A1形式で出力（例: "B3"）
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_a1_notation())
+    }
+}
+
+impl FromStr for CellCoord {
+    type Err = XlsxToMdError;
+
+    /// A1形式の文字列を座標に変換（例: "B3" -> (2, 1)）
+    ///
+    /// 絶対参照の`$`と小文字の列名も受け付けます。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_a1_end(s) {
+            Some((Some(row), Some(col))) => Ok(Self::new(row, col)),
+            _ => Err(invalid_a1(s)),
+        }
+    }
+}
+
+/// セル範囲（開始・終了のセルを含む）
+///
+/// A1形式の文字列と相互に変換できます（`"B2:F100".parse::<CellRange>()`、`to_string()`）。
+/// 単一のセル（`"B2"`）、列全体（`"A:C"`）、行全体（`"1:5"`）も受け付けます。
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::{CellCoord, CellRange};
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let range: CellRange = "B2:F100".parse()?;
+/// assert_eq!(range.start, CellCoord::new(1, 1));
+/// assert_eq!(range.end, CellCoord::new(99, 5));
+/// assert_eq!(range.to_string(), "B2:F100");
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub(crate) struct CellRange {
+pub struct CellRange {
+    /// 左上のセル
     pub start: CellCoord,
+    /// 右下のセル
     pub end: CellCoord,
 }
 
@@ -91,7 +371,6 @@ impl CellRange {
     }
 
     /// 指定された座標が範囲内にあるかを判定
-    #[allow(dead_code)]
     pub fn contains(&self, coord: CellCoord) -> bool {
         coord.row >= self.start.row
             && coord.row <= self.end.row
@@ -108,6 +387,125 @@ impl CellRange {
     }
 }
 
+impl fmt::Display for CellRange {
+    /// A1形式で出力（例: "B2:F100"、単一のセルは"B2"）
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}:{}", self.start, self.end)
+        }
+    }
+}
+
+impl FromStr for CellRange {
+    type Err = XlsxToMdError;
+
+    /// A1形式の範囲を変換（例: "B2:F100"）
+    ///
+    /// 絶対参照の`$`と小文字の列名、単一のセル（`"B2"`）、列全体（`"A:C"`）、行全体（`"1:5"`）を
+    /// 受け付けます。開始・終了が逆順の場合（`"F100:B2"`）は左上・右下に並べ替えます。
+    /// シート名付きの参照（`"Sheet2!A1:C10"`）は受け付けません。
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once(':').unwrap_or((s, s));
+        let (start_row, start_col) = parse_a1_end(start).ok_or_else(|| invalid_a1(s))?;
+        let (end_row, end_col) = parse_a1_end(end).ok_or_else(|| invalid_a1(s))?;
+        // 列全体・行全体の指定は、両端で同じ要素を省略している場合のみ有効
+        // （単一のセル以外で列・行のみの指定は、範囲の両端が必要）
+        if start_row.is_some() != end_row.is_some()
+            || start_col.is_some() != end_col.is_some()
+            || ((start_row.is_none() || start_col.is_none()) && !s.contains(':'))
+        {
+            return Err(invalid_a1(s));
+        }
+        let start = CellCoord::new(start_row.unwrap_or(0), start_col.unwrap_or(0));
+        let end = CellCoord::new(
+            end_row.unwrap_or(MAX_ROW_INDEX),
+            end_col.unwrap_or(MAX_COL_INDEX),
+        );
+        Ok(Self::new(
+            CellCoord::new(start.row.min(end.row), start.col.min(end.col)),
+            CellCoord::new(start.row.max(end.row), start.col.max(end.col)),
+        ))
+    }
+}
+
+/// A1形式の範囲の端点を行・列に変換（内部ヘルパー）
+///
+/// `"$B$3"` -> (Some(2), Some(1))、列のみ`"C"` -> (None, Some(2))、行のみ`"5"` -> (Some(4), None)。
+/// シートの範囲（XFD列、1048576行）を超える場合は`None`を返します。
+fn parse_a1_end(text: &str) -> Option<(Option<u32>, Option<u32>)> {
+    // 列・行の前の`$`（絶対参照）は読み飛ばす
+    let text = text.trim();
+    let text = text.strip_prefix('$').unwrap_or(text);
+    let letters = text.bytes().take_while(u8::is_ascii_alphabetic).count();
+    let (col_text, row_text) = text.split_at(letters);
+    let row_text = row_text.strip_prefix('$').unwrap_or(row_text);
+    if col_text.is_empty() && row_text.is_empty() {
+        return None;
+    }
+
+    // 列を数値に変換（A=0, B=1, ..., Z=25, AA=26, ...）
+    let col = if col_text.is_empty() {
+        None
+    } else {
+        let mut col: u32 = 0;
+        for b in col_text.bytes() {
+            let val = u32::from(b.to_ascii_uppercase() - b'A') + 1;
+            col = col.checked_mul(26)?.checked_add(val)?;
+        }
+        Some(col - 1).filter(|col| *col <= MAX_COL_INDEX)
+    };
+    let row = if row_text.is_empty() {
+        None
+    } else {
+        if !row_text.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        Some(row_text.parse::<u32>().ok()?.checked_sub(1)?).filter(|row| *row <= MAX_ROW_INDEX)
+    };
+    // 範囲外の列・行（例: "XFE1"）は受け付けない
+    if (!col_text.is_empty() && col.is_none()) || (!row_text.is_empty() && row.is_none()) {
+        return None;
+    }
+    Some((row, col))
+}
+
+/// A1形式として解析できない場合のエラー（内部ヘルパー）
+fn invalid_a1(s: &str) -> XlsxToMdError {
+    XlsxToMdError::Config(ConfigError::InvalidReference {
+        reference: s.to_string(),
+    })
+}
+
+/// シート名で修飾された参照をシート名と参照に分割（例: `'Q1 ''24'!$A$1` -> ("Q1 '24", "$A$1")）
+///
+/// 引用符で囲まれたシート名は、`''`を`'`に戻します。シート名がない場合は`None`を返します。
+pub(crate) fn split_sheet_prefix(reference: &str) -> Option<(String, &str)> {
+    if let Some(quoted) = reference.strip_prefix('\'') {
+        let mut sheet = String::new();
+        let mut chars = quoted.char_indices();
+        while let Some((idx, ch)) = chars.next() {
+            if ch != '\'' {
+                sheet.push(ch);
+                continue;
+            }
+            if quoted[idx + 1..].starts_with('\'') {
+                sheet.push('\'');
+                chars.next();
+                continue;
+            }
+            return quoted[idx + 1..]
+                .strip_prefix('!')
+                .map(|rest| (sheet, rest));
+        }
+        return None;
+    }
+    reference
+        .split_once('!')
+        .map(|(sheet, rest)| (sheet.to_string(), rest))
+}
+
 /// セル結合範囲の情報
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct MergedRegion {
@@ -127,6 +525,15 @@ impl MergedRegion {
         }
     }
 
+    /// 行・列を指定した数だけ移動した結合範囲を生成
+    pub fn offset(&self, rows: u32, cols: u32) -> Self {
+        let shift = |coord: CellCoord| CellCoord::new(coord.row + rows, coord.col + cols);
+        Self {
+            range: CellRange::new(shift(self.range.start), shift(self.range.end)),
+            parent: shift(self.parent),
+        }
+    }
+
     /// 指定された座標が結合範囲内にあるかを判定
     #[allow(dead_code)]
     pub fn contains(&self, coord: CellCoord) -> bool {
@@ -142,15 +549,31 @@ impl MergedRegion {
     pub fn col_span(&self) -> u32 {
         self.range.end.col - self.range.start.col + 1
     }
+
+    /// 指定した大きさのグリッドに収まる部分のセル数
+    ///
+    /// 結合範囲を展開する際に実際に処理するセル数です（グリッドの外側は処理しません）。
+    pub fn cell_count_within(&self, rows: usize, cols: usize) -> u64 {
+        let span = |start: u32, end: u32, size: usize| -> u64 {
+            if start as usize >= size {
+                return 0;
+            }
+            u64::from(end.min(size as u32 - 1) - start + 1)
+        };
+        span(self.range.start.row, self.range.end.row, rows)
+            * span(self.range.start.col, self.range.end.col, cols)
+    }
 }
 
-/// リッチテキストの書式情報
+/// リッチテキストの書式情報（セル単位のフォントの書式にも使用）
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct RichTextFormat {
     /// 太字かどうか
     pub bold: bool,
     /// 斜体かどうか
     pub italic: bool,
+    /// 取り消し線かどうか
+    pub strike: bool,
 }
 
 impl RichTextFormat {
@@ -159,8 +582,14 @@ impl RichTextFormat {
         Self {
             bold: false,
             italic: false,
+            strike: false,
         }
     }
+
+    /// 書式がないか（太字・斜体・取り消し線のいずれでもない）
+    pub fn is_plain(&self) -> bool {
+        !self.bold && !self.italic && !self.strike
+    }
 }
 
 impl Default for RichTextFormat {
@@ -193,6 +622,32 @@ impl RichTextSegment {
     }
 }
 
+/// ハイパーリンク情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Hyperlink {
+    /// URL
+    pub url: String,
+
+    /// 表示テキスト（`<hyperlink>`要素の`display`属性）
+    pub display: Option<String>,
+
+    /// ヒント（`<hyperlink>`要素の`tooltip`属性）
+    pub tooltip: Option<String>,
+}
+
+impl Hyperlink {
+    /// セルの値より優先するリンクテキスト
+    ///
+    /// 表示テキスト（`display`属性）、ヒント（`tooltip`属性）の順に、空でない最初のものを返します。
+    pub fn text(&self) -> Option<&str> {
+        [&self.display, &self.tooltip]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .find(|text| !text.trim().is_empty())
+    }
+}
+
 /// パーサーから抽出された生のセルデータ
 #[derive(Debug, Clone)]
 pub(crate) struct RawCellData {
@@ -212,11 +667,62 @@ pub(crate) struct RawCellData {
     pub formula: Option<String>,
 
     /// ハイパーリンク情報（存在する場合）
-    pub hyperlink: Option<String>,
+    pub hyperlink: Option<Hyperlink>,
 
     /// リッチテキスト情報（存在する場合）
     /// リッチテキストが存在する場合、valueはStringで通常のテキストが格納される
     pub rich_text: Option<Vec<RichTextSegment>>,
+
+    /// 名前付きスタイル名（"Normal"以外のスタイルが適用されている場合、例: "Heading 1"）
+    pub style_name: Option<String>,
+
+    /// セル単位のフォントの書式（太字・斜体・取り消し線のいずれかが設定されている場合）
+    pub font: Option<RichTextFormat>,
+
+    /// セルの背景色（RGBの16進数6桁、例: `"FFFF00"`、塗りつぶしの色がRGBで指定されている場合）
+    pub fill_color: Option<String>,
+}
+
+impl RawCellData {
+    /// キャッシュ値を持たない数式セルかどうかを判定
+    ///
+    /// 計算されずに保存されたブックの数式セルは、値が空で数式のみを持ちます。
+    pub fn is_formula_without_value(&self) -> bool {
+        self.formula.is_some() && self.value.is_empty()
+    }
+}
+
+/// シートの表示状態（内部用）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum SheetVisibility {
+    /// 表示
+    #[default]
+    Visible,
+
+    /// 非表示（Excelの「再表示」で表示できる）
+    Hidden,
+
+    /// 完全に非表示（`veryHidden`、VBAなどからのみ表示できる）
+    VeryHidden,
+}
+
+impl SheetVisibility {
+    /// 見出しに付記するラベル（表示されているシートはNone）
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            SheetVisibility::Visible => None,
+            SheetVisibility::Hidden => Some("hidden"),
+            SheetVisibility::VeryHidden => Some("very hidden"),
+        }
+    }
+
+    /// シートの見出し（非表示のシートは表示状態を付記、例: `SecretSheet (hidden)`）
+    pub fn heading(self, name: &str) -> Cow<'_, str> {
+        match self.label() {
+            Some(label) => Cow::Owned(format!("{} ({})", name, label)),
+            None => Cow::Borrowed(name),
+        }
+    }
 }
 
 /// シートのメタデータ
@@ -248,12 +754,146 @@ pub(crate) struct SheetMetadata {
     /// 1904年エポックを使用するか（ワークブック全体の設定）
     /// Phase I: 常にfalse（Phase IIで実装）
     pub is_1904: bool,
+
+    /// グリッドの先頭行に対応するシートの行（0始まり、値の範囲の開始行）
+    pub first_row: u32,
+
+    /// グリッドの先頭列に対応するシートの列（0始まり、値の範囲の開始列）
+    pub first_col: u32,
+
+    /// 非表示として扱った、高さが0の行のインデックスリスト
+    ///
+    /// `ConverterBuilder::with_zero_size_as_hidden(true)`の場合のみ設定され、`hidden_rows`にも含まれます。
+    pub zero_size_rows: Vec<u32>,
+
+    /// 非表示として扱った、幅が0の列のインデックスリスト
+    ///
+    /// `ConverterBuilder::with_zero_size_as_hidden(true)`の場合のみ設定され、`hidden_cols`にも含まれます。
+    pub zero_size_cols: Vec<u32>,
+}
+
+/// 出力に付与するシート単位の情報
+///
+/// `ConverterBuilder::include_metadata(true)`の場合に、Markdownのフロントマターや
+/// JSONの`metadata`オブジェクトとして出力されます。
+#[derive(Debug, Clone)]
+pub(crate) struct SheetInfo {
+    /// シート名
+    pub name: String,
+
+    /// シート内テキストの主要な文字体系（判定できない場合はNone）
+    pub script: Option<Script>,
+
+    /// シート内容のハッシュ値（XXH3 64bit）
+    pub content_hash: Option<u64>,
+
+    /// シート内の表ごとの範囲とハッシュ値（メタデータ出力時のみ、上から順）
+    pub tables: Vec<TableInfo>,
+
+    /// シート内のハイパーリンクの一覧（リンク一覧の出力時のみ）
+    pub links: Option<LinkInventory>,
+
+    /// 見出しから検出した列ごとの単位（単位の検出時のみ、単位のない列はNone）
+    pub column_units: Option<Vec<Option<String>>>,
+
+    /// 保護されたシートとして出力で識別するか（`ProtectedSheets::Flag`の場合のみ）
+    pub protected: bool,
+
+    /// 条件付き書式のルール（`ConditionalFormats::Ignore`以外の場合のみ）
+    pub conditional_formats: Vec<ConditionalFormat>,
+
+    /// 入力規則（`DataValidations::Ignore`以外の場合のみ）
+    pub data_validations: Vec<DataValidation>,
+
+    /// スパークラインとデータバー（メタデータ出力時のみ、変換で失われる視覚表現の識別用）
+    pub visuals: SheetVisuals,
+
+    /// シートの表示状態（非表示シートを含める設定の場合のみ、それ以外は常に表示）
+    pub visibility: SheetVisibility,
+
+    /// 除去した空行のシート上の行番号（1始まり、`BlankRows::RemoveWithMarker`の場合のみ）
+    pub removed_blank_rows: Vec<u32>,
+}
+
+impl SheetInfo {
+    /// ハッシュ値をメタデータ出力用の文字列（例: `xxh3:0123456789abcdef`）として取得
+    pub fn content_hash_string(&self) -> Option<String> {
+        self.content_hash.map(hash_string)
+    }
+
+    /// シートの見出し（非表示のシートは表示状態を付記、例: `SecretSheet (hidden)`）
+    pub fn heading(&self) -> Cow<'_, str> {
+        self.visibility.heading(&self.name)
+    }
+}
+
+/// シート内の表（空行で区切られた行のまとまり）の情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TableInfo {
+    /// シート上の表の範囲（表示内容のあるセルを囲む範囲）
+    pub range: CellRange,
+
+    /// 表の内容のハッシュ値（XXH3 64bit）
+    ///
+    /// 表の左上からの相対位置で計算するため、同じ内容の表はシート上の位置によらず同じ値になります。
+    pub content_hash: u64,
+}
+
+impl TableInfo {
+    /// ハッシュ値をメタデータ出力用の文字列（例: `xxh3:0123456789abcdef`）として取得
+    pub fn content_hash_string(&self) -> String {
+        hash_string(self.content_hash)
+    }
+}
+
+/// ハッシュ値をメタデータ出力用の文字列に変換（内部ヘルパー）
+fn hash_string(hash: u64) -> String {
+    format!("xxh3:{:016x}", hash)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // DateKind のテスト
+    #[test]
+    fn test_date_kind_from_format_string() {
+        let kind = |f: &str| DateKind::from_format(None, Some(f));
+        assert_eq!(kind("yyyy-mm-dd"), Some(DateKind::Date));
+        assert_eq!(kind("mmm yyyy"), Some(DateKind::Date));
+        assert_eq!(kind("mmmm"), Some(DateKind::Date));
+        assert_eq!(kind("hh:mm:ss"), Some(DateKind::Time));
+        assert_eq!(kind("mm:ss"), Some(DateKind::Time));
+        assert_eq!(kind("[h]:mm"), Some(DateKind::Time));
+        assert_eq!(kind("[$-409]h:mm AM/PM"), Some(DateKind::Time));
+        assert_eq!(kind("yyyy/mm/dd hh:mm"), Some(DateKind::DateTime));
+        assert_eq!(kind("m/d/yy h:mm"), Some(DateKind::DateTime));
+        assert_eq!(kind("#,##0.00"), None);
+        assert_eq!(kind("General"), None);
+        assert_eq!(kind("[Red]0.00"), None);
+        assert_eq!(kind("0 \"days\""), None);
+    }
+
+    #[test]
+    fn test_date_kind_from_format_id() {
+        assert_eq!(DateKind::from_format(Some(14), None), Some(DateKind::Date));
+        assert_eq!(DateKind::from_format(Some(20), None), Some(DateKind::Time));
+        assert_eq!(DateKind::from_format(Some(46), None), Some(DateKind::Time));
+        assert_eq!(
+            DateKind::from_format(Some(22), None),
+            Some(DateKind::DateTime)
+        );
+        assert_eq!(DateKind::from_format(Some(4), None), None);
+        assert_eq!(DateKind::from_format(None, None), None);
+    }
+
+    #[test]
+    fn test_date_kind_from_serial() {
+        assert_eq!(DateKind::from_serial(45658.0), DateKind::Date);
+        assert_eq!(DateKind::from_serial(0.5), DateKind::Time);
+        assert_eq!(DateKind::from_serial(45658.75), DateKind::DateTime);
+    }
+
     // CellValue のテスト
     #[test]
     fn test_cell_value_is_empty() {
@@ -261,7 +901,7 @@ mod tests {
         assert!(!CellValue::Number(42.0).is_empty());
         assert!(!CellValue::String("test".to_string()).is_empty());
         assert!(!CellValue::Bool(true).is_empty());
-        assert!(!CellValue::Error("#DIV/0!".to_string()).is_empty());
+        assert!(!CellValue::Error(CellError::Div0).is_empty());
     }
 
     #[test]
@@ -273,10 +913,18 @@ mod tests {
             "hello"
         );
         assert_eq!(CellValue::Bool(true).as_raw_string(), "true");
-        assert_eq!(
-            CellValue::Error("#DIV/0!".to_string()).as_raw_string(),
-            "#DIV/0!"
-        );
+        assert_eq!(CellValue::Error(CellError::Div0).as_raw_string(), "#DIV/0!");
+    }
+
+    // CellError のテスト
+    #[test]
+    fn test_cell_error_strings() {
+        assert_eq!(CellError::Div0.as_str(), "div0");
+        assert_eq!(CellError::Div0.to_string(), "#DIV/0!");
+        assert_eq!(CellError::NA.as_excel_str(), "#N/A");
+        assert_eq!(CellError::Name.as_excel_str(), "#NAME?");
+        assert_eq!(CellError::GettingData.as_str(), "getting_data");
+        assert_eq!(CellError::GettingData.as_excel_str(), "#GETTING_DATA");
     }
 
     // CellCoord のテスト
@@ -344,6 +992,76 @@ mod tests {
         assert_eq!(range3.size(), (1, 1));
     }
 
+    #[test]
+    fn test_cell_coord_from_str() {
+        assert_eq!("A1".parse::<CellCoord>().unwrap(), CellCoord::new(0, 0));
+        assert_eq!("$b$2".parse::<CellCoord>().unwrap(), CellCoord::new(1, 1));
+        assert_eq!(
+            "XFD1048576".parse::<CellCoord>().unwrap(),
+            CellCoord::new(MAX_ROW_INDEX, MAX_COL_INDEX)
+        );
+        assert_eq!(CellCoord::new(99, 27).to_string(), "AB100");
+
+        for invalid in ["", "A", "1", "A0", "1A", "XFE1", "A1048577", "A1:B2", "A-1"] {
+            assert!(
+                matches!(invalid.parse::<CellCoord>(), Err(XlsxToMdError::Config(_))),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cell_range_from_str() {
+        let range = |s: &str| s.parse::<CellRange>().unwrap();
+        let expected = CellRange::new(CellCoord::new(1, 1), CellCoord::new(99, 5));
+        assert_eq!(range("B2:F100"), expected);
+        assert_eq!(range("$B$2:$F$100"), expected);
+        assert_eq!(range("f100:b2"), expected);
+        assert_eq!(
+            range("C3"),
+            CellRange::new(CellCoord::new(2, 2), CellCoord::new(2, 2))
+        );
+        assert_eq!(
+            range("A:C"),
+            CellRange::new(CellCoord::new(0, 0), CellCoord::new(MAX_ROW_INDEX, 2))
+        );
+        assert_eq!(
+            range("$1:$5"),
+            CellRange::new(CellCoord::new(0, 0), CellCoord::new(4, MAX_COL_INDEX))
+        );
+        assert_eq!(expected.to_string(), "B2:F100");
+        assert_eq!(range("C3").to_string(), "C3");
+
+        for invalid in [
+            "",
+            "A",
+            "1",
+            "A1:",
+            "A1:B",
+            "A:1",
+            "A1:B2:C3",
+            "Sheet1!A1:B2",
+        ] {
+            assert!(
+                matches!(invalid.parse::<CellRange>(), Err(XlsxToMdError::Config(_))),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_sheet_prefix() {
+        assert_eq!(
+            split_sheet_prefix("Sheet2!A1:C10"),
+            Some(("Sheet2".to_string(), "A1:C10"))
+        );
+        assert_eq!(
+            split_sheet_prefix("'It''s Q1'!$A$1"),
+            Some(("It's Q1".to_string(), "$A$1"))
+        );
+        assert_eq!(split_sheet_prefix("A1:C10"), None);
+    }
+
     // MergedRegion のテスト
     #[test]
     fn test_merged_region_new() {
@@ -403,6 +1121,9 @@ mod tests {
             formula: None,
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         assert_eq!(cell_data.coord, coord);
@@ -424,6 +1145,9 @@ mod tests {
             formula: Some("=A1*2".to_string()),
             hyperlink: None,
             rich_text: None,
+            style_name: None,
+            font: None,
+            fill_color: None,
         };
 
         assert_eq!(cell_data.formula, Some("=A1*2".to_string()));
@@ -440,6 +1164,10 @@ mod tests {
             hidden_rows: vec![], // Phase I: 空リスト
             hidden_cols: vec![], // Phase I: 空リスト
             is_1904: false,      // Phase I: 常にfalse
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         assert_eq!(metadata.name, "Sheet1");
@@ -466,6 +1194,10 @@ mod tests {
             hidden_rows: vec![],
             hidden_cols: vec![],
             is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
         };
 
         assert_eq!(metadata.merged_regions.len(), 2);
@@ -482,9 +1214,7 @@ mod tests {
         #[allow(unused_doc_comments)]
         /// TC-PBT-001: A1 Notation Round-Trip
         ///
-        /// ランダムな座標値でA1記法に変換し、形式を検証します。
-        /// 注意: A1記法のパース関数がまだ実装されていないため、
-        /// 完全なround-tripテストではなく、形式検証のみを行います。
+        /// ランダムな座標値でA1記法に変換し、形式を検証した上で元の座標に戻ることを確認します。
         proptest! {
             #[test]
             fn test_a1_notation_round_trip(row in 0u32..10000, col in 0u32..10000) {
@@ -520,6 +1250,9 @@ mod tests {
                 let row_num: u32 = row_part.parse().unwrap();
                 prop_assert!(row_num >= 1);
                 prop_assert_eq!(row_num, row + 1);
+
+                // 6. パースすると元の座標に戻ること
+                prop_assert_eq!(a1.parse::<CellCoord>().unwrap(), coord);
             }
         }
     }
@@ -0,0 +1,224 @@
+//! Validation Module
+//!
+//! 出力する表の列の値を検証するモジュール（`ConverterBuilder::with_validations()`）。
+//!
+//! 変換した表を自動処理のパイプラインに渡す前に、列の型・空のセル・キーの重複などの
+//! 簡単なデータ品質の確認を行います。
+
+use crate::api::{ColumnSelector, ColumnType, Validation};
+use crate::grid::LogicalGrid;
+use crate::types::{CellCoord, CellValue};
+use std::collections::HashSet;
+
+/// 違反のメッセージに含める行番号の上限
+const MAX_REPORTED_ROWS: usize = 10;
+
+/// 表の見出しを除く各行を検証し、違反したルールごとのメッセージを返す
+///
+/// # 引数
+///
+/// * `grid` - 検証する表（先頭行が見出し）
+/// * `validations` - 検証ルール（指定順）
+///
+/// # 戻り値
+///
+/// 違反したルールごとのメッセージ（ルールの指定順、違反がない場合は空）
+pub(crate) fn validate(grid: &LogicalGrid, validations: &[Validation]) -> Vec<String> {
+    validations
+        .iter()
+        .filter_map(|validation| check(grid, validation))
+        .collect()
+}
+
+/// 1つのルールを検証（内部ヘルパー）
+fn check(grid: &LogicalGrid, validation: &Validation) -> Option<String> {
+    let column = match validation {
+        Validation::Type(column, _) | Validation::NotNull(column) | Validation::Unique(column) => {
+            column
+        }
+    };
+    let Some(col) = resolve_column(grid, column) else {
+        let name = match column {
+            ColumnSelector::Index(col) => CellCoord::col_index_to_letter(*col as u32),
+            ColumnSelector::Header(name) => format!("'{}'", name),
+        };
+        return Some(format!("column {} not found", name));
+    };
+
+    let mut seen = HashSet::new();
+    let mut rows = Vec::new();
+    for row_idx in 1..grid.get_rows() {
+        let Some(cell) = grid.get_row(row_idx).get(col) else {
+            continue;
+        };
+        // 結合セルの子は親セルの値を持つものとして扱う
+        if cell.merge_parent.is_some() {
+            continue;
+        }
+        let empty = cell.content().trim().is_empty();
+        let violated = match validation {
+            Validation::Type(_, column_type) => !empty && !is_type(&cell.value, *column_type),
+            Validation::NotNull(_) => empty,
+            Validation::Unique(_) => !empty && !seen.insert(cell.content()),
+        };
+        if violated {
+            rows.push(row_idx + 1);
+        }
+    }
+    if rows.is_empty() {
+        return None;
+    }
+
+    let description = match validation {
+        Validation::Type(_, column_type) => format!("values are not {}", column_type),
+        Validation::NotNull(_) => "empty cells".to_string(),
+        Validation::Unique(_) => "duplicate values".to_string(),
+    };
+    let mut listed: Vec<String> = rows
+        .iter()
+        .take(MAX_REPORTED_ROWS)
+        .map(ToString::to_string)
+        .collect();
+    if rows.len() > MAX_REPORTED_ROWS {
+        listed.push(format!("and {} more", rows.len() - MAX_REPORTED_ROWS));
+    }
+    Some(format!(
+        "column {}: {} ({} {}: {})",
+        column_label(grid, col),
+        description,
+        rows.len(),
+        if rows.len() == 1 { "row" } else { "rows" },
+        listed.join(", ")
+    ))
+}
+
+/// 列の指定を列番号に変換（表にない場合は`None`）（内部ヘルパー）
+fn resolve_column(grid: &LogicalGrid, column: &ColumnSelector) -> Option<usize> {
+    match column {
+        ColumnSelector::Index(col) => (*col < grid.get_cols()).then_some(*col),
+        ColumnSelector::Header(name) => grid.find_header(name),
+    }
+}
+
+/// 列の表示名（例: `'ID' (A)`、見出しが空の場合は`A`）（内部ヘルパー）
+fn column_label(grid: &LogicalGrid, col: usize) -> String {
+    let letter = CellCoord::col_index_to_letter(col as u32);
+    match grid.get_row(0).get(col).map(|cell| cell.content().trim()) {
+        Some(header) if !header.is_empty() => format!("'{}' ({})", header, letter),
+        _ => letter,
+    }
+}
+
+/// セルの値が指定した型か（内部ヘルパー）
+fn is_type(value: &CellValue, column_type: ColumnType) -> bool {
+    matches!(
+        (value, column_type),
+        (CellValue::Number(_), ColumnType::Number)
+            | (CellValue::DateTime { .. }, ColumnType::Date)
+            | (CellValue::String(_), ColumnType::Text)
+            | (CellValue::Bool(_), ColumnType::Bool)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MergeStrategy;
+    use crate::grid::Cell;
+    use crate::types::{DateKind, SheetMetadata};
+
+    fn empty_grid() -> LogicalGrid {
+        let metadata = SheetMetadata {
+            name: "Sheet1".to_string(),
+            index: 0,
+            hidden: false,
+            merged_regions: vec![],
+            hidden_rows: vec![],
+            hidden_cols: vec![],
+            is_1904: false,
+            first_row: 0,
+            first_col: 0,
+            zero_size_rows: vec![],
+            zero_size_cols: vec![],
+        };
+        LogicalGrid::build(vec![], vec![], &metadata, MergeStrategy::DataDuplication).unwrap()
+    }
+
+    fn cell(content: &str, value: CellValue) -> Cell {
+        let mut cell = Cell::new(content.to_string());
+        cell.value = value;
+        cell
+    }
+
+    fn text(content: &str) -> Cell {
+        cell(content, CellValue::String(content.to_string()))
+    }
+
+    fn grid() -> LogicalGrid {
+        let mut grid = empty_grid();
+        let rows = vec![
+            vec![text("ID"), text("Amount"), text("Date")],
+            vec![
+                text("A1"),
+                cell("10", CellValue::Number(10.0)),
+                cell(
+                    "2025-01-01",
+                    CellValue::DateTime {
+                        serial: 45658.0,
+                        kind: DateKind::Date,
+                    },
+                ),
+            ],
+            vec![text("A2"), text("n/a"), Cell::empty()],
+            vec![
+                text("A1"),
+                cell("#N/A", CellValue::Error(crate::CellError::NA)),
+                Cell::empty(),
+            ],
+        ];
+        for (row_idx, row) in (0u32..).zip(rows) {
+            for (col_idx, cell) in (0u32..).zip(row) {
+                grid.set_cell(CellCoord::new(row_idx, col_idx), cell);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_validate() {
+        let grid = grid();
+        let messages = validate(
+            &grid,
+            &[
+                Validation::Unique(ColumnSelector::Header("id".to_string())),
+                Validation::Type(ColumnSelector::Index(1), ColumnType::Number),
+                Validation::Type(ColumnSelector::Index(2), ColumnType::Date),
+                Validation::NotNull(ColumnSelector::Header("Date".to_string())),
+                Validation::NotNull(ColumnSelector::Header("Missing".to_string())),
+                Validation::NotNull(ColumnSelector::Index(0)),
+            ],
+        );
+        assert_eq!(
+            messages,
+            vec![
+                "column 'ID' (A): duplicate values (1 row: 4)",
+                "column 'Amount' (B): values are not number (2 rows: 3, 4)",
+                "column 'Date' (C): empty cells (2 rows: 3, 4)",
+                "column 'Missing' not found",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_limits_reported_rows() {
+        let mut grid = empty_grid();
+        grid.set_cell(CellCoord::new(0, 0), text("Name"));
+        grid.set_cell(CellCoord::new(15, 0), text("last"));
+
+        let messages = validate(&grid, &[Validation::NotNull(ColumnSelector::Index(0))]);
+        assert_eq!(
+            messages,
+            vec!["column 'Name' (A): empty cells (14 rows: 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, and 4 more)"]
+        );
+    }
+}
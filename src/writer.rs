@@ -0,0 +1,428 @@
+//! Writer Module
+//!
+//! 変換結果（グリッド、JSON）からXLSXファイルを書き出すモジュール（`write`フィーチャー）。
+//!
+//! `Converter::convert_to_grids()`で取得して編集したグリッドや、JSON形式の出力を
+//! XLSXファイルに戻し、クリーニング → 変換 → 再出力を1つのクレートで行えるようにします。
+//! 書き出すのはセルの値と結合セルのみで、書式（フォント、罫線、列幅など）は含みません。
+//! 日付・時刻のセルには、種別に応じた表示形式（`yyyy-mm-dd`、`hh:mm:ss`、
+//! `yyyy-mm-dd hh:mm:ss`）を設定します。
+//!
+//! # 使用例
+//!
+//! ```toml
+//! [dependencies]
+//! xlsxzero = { version = "0.1", features = ["write"] }
+//! ```
+//!
+//! ```rust,no_run
+//! use xlsxzero::{writer, ConverterBuilder};
+//! use std::fs::File;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let converter = ConverterBuilder::new().build()?;
+//! let mut grids = converter.convert_to_grids(File::open("input.xlsx")?)?;
+//! for grid in &mut grids {
+//!     grid.set_cell(0, 0, "ID");
+//! }
+//!
+//! std::fs::write("cleaned.xlsx", writer::grids_to_xlsx(&grids)?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::document::GridDocument;
+use crate::error::XlsxToMdError;
+use crate::types::{CellCoord, CellValue, DateKind};
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// 日付のセルに設定する表示形式
+const DATE_FORMAT: &str = "yyyy-mm-dd";
+
+/// 時刻のセルに設定する表示形式
+const TIME_FORMAT: &str = "hh:mm:ss";
+
+/// 日付と時刻のセルに設定する表示形式
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+/// グリッドをXLSXファイルに書き出す
+///
+/// 各グリッドを1シートとし、グリッドの順にシート名（`GridDocument::name()`）で追加します。
+/// グリッドの左上のセルをA1に配置します。
+///
+/// セルはフォーマット前の値（`GridDocument::cell_value()`）で書き出すため、数値・日付・論理値は
+/// Excelで計算や並べ替えができる値になります。エラー値は文字列（例: `#DIV/0!`）になります。
+/// 結合セル（`MergeStrategy::DataDuplication`で複製された値を含む）は、結合範囲が長方形として
+/// 残っている場合に結合セルとして書き出し、行の並べ替えなどで分断された場合は各セルの値を
+/// 書き出します。
+///
+/// # 引数
+///
+/// * `grids` - 書き出すグリッド（シートの順）
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxToMdError)` - シート名が重複する・Excelで使用できない場合など、
+///   書き出せない入力の場合（`XlsxToMdError::InvalidWriteInput`）、
+///   またはXLSXファイルの生成に失敗した場合（`XlsxToMdError::Write`）
+pub fn grids_to_xlsx(grids: &[GridDocument]) -> Result<Vec<u8>, XlsxToMdError> {
+    let mut workbook = Workbook::new();
+    let formats = DateFormats::new();
+
+    for document in grids {
+        let sheet = Some(document.name());
+        let worksheet = workbook.add_worksheet();
+        worksheet
+            .set_name(document.name())
+            .map_err(|e| write_error(sheet, e))?;
+
+        let grid = document.grid();
+        let merges = merge_ranges(document);
+        for (parent, end) in &merges {
+            worksheet
+                .merge_range(
+                    parent.row,
+                    to_col(sheet, parent.col)?,
+                    end.row,
+                    to_col(sheet, end.col)?,
+                    "",
+                    &Format::new(),
+                )
+                .map_err(|e| write_error(sheet, e))?;
+        }
+
+        for (row_idx, row) in (0u32..).zip((0..grid.get_rows()).map(|r| grid.get_row(r))) {
+            for (col_idx, cell) in (0u32..).zip(row) {
+                // 結合範囲に含まれるセルは親セルの値のみを書き出す
+                if let Some(parent) = cell.merge_parent {
+                    if merges.contains_key(&parent) {
+                        continue;
+                    }
+                }
+                let value = match (&cell.value, cell.content().is_empty()) {
+                    // 値を持たない表示文字列（数式の結果など）は文字列として書き出す
+                    (CellValue::Empty, false) => CellValue::String(cell.content().to_string()),
+                    (value, _) => value.clone(),
+                };
+                write_value(worksheet, sheet, row_idx, col_idx, &value, &formats)?;
+            }
+        }
+    }
+
+    workbook.save_to_buffer().map_err(|e| write_error(None, e))
+}
+
+/// JSON形式の出力をXLSXファイルに書き出す
+///
+/// `OutputFormat::Json`の出力（`JsonDocumentMode::Sheets`の1つのJSON、
+/// `JsonDocumentMode::Lines`のNDJSONのどちらも可）を読み込み、`rows`の各行を書き出します。
+///
+/// - `JsonCellMode::Typed`のセル（`type`と`raw`を持つオブジェクト）は値の種類を復元し、
+///   日付・時刻はシリアル値に表示形式を設定して書き出します
+/// - `JsonCellMode::Plain`のセル（文字列・数値・論理値）はそのまま書き出します
+///   （セル参照を含む`value`と`ref`を持つオブジェクトの場合は`value`を書き出します）
+/// - `JsonKeyMode::Header`の出力は、`columns`の見出しを1行目に、各行を2行目以降に
+///   書き出します（キーに対応する列は`columns`の`column`）
+///
+/// メタデータ（`metadata`）やリンク一覧などのその他のフィールドは無視します。
+///
+/// # 引数
+///
+/// * `json` - JSON形式の出力
+///
+/// # 戻り値
+///
+/// * `Ok(Vec<u8>)` - XLSXファイルの内容
+/// * `Err(XlsxToMdError)` - JSONが不正な場合、またはXLSXファイルの書き出しに失敗した場合
+///   （`XlsxToMdError::Write`）
+///
+/// # 使用例
+///
+/// ```rust
+/// use xlsxzero::writer;
+///
+/// # fn main() -> Result<(), xlsxzero::XlsxToMdError> {
+/// let json = r#"{"name": "Sheet1", "rows": [
+///     {"A": {"type": "string", "raw": "Total", "formatted": "Total"},
+///      "B": {"type": "number", "raw": 42, "formatted": "42"}}
+/// ]}"#;
+/// let xlsx = writer::json_to_xlsx(json)?;
+/// assert!(xlsx.starts_with(b"PK"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn json_to_xlsx(json: &str) -> Result<Vec<u8>, XlsxToMdError> {
+    let sheets = match serde_json::from_str::<Value>(json) {
+        Ok(Value::Object(mut document)) => match document.remove("sheets") {
+            Some(Value::Array(sheets)) => sheets,
+            _ => vec![Value::Object(document)],
+        },
+        Ok(_) => return Err(invalid_input(None, "JSON must be an object")),
+        // NDJSON（1行に1シート）
+        Err(_) => json
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()
+            .map_err(|e| invalid_input(None, format!("invalid JSON: {}", e)))?,
+    };
+
+    let mut workbook = Workbook::new();
+    let formats = DateFormats::new();
+
+    for sheet in &sheets {
+        let Value::Object(sheet) = sheet else {
+            return Err(invalid_input(None, "sheet must be an object"));
+        };
+        let name = sheet.get("name").and_then(Value::as_str);
+        let worksheet = workbook.add_worksheet();
+        if let Some(name) = name {
+            worksheet
+                .set_name(name)
+                .map_err(|e| write_error(Some(name), e))?;
+        }
+
+        // JsonKeyMode::Headerの場合は、見出しを1行目に書き出してキーを列に対応付ける
+        let mut key_columns: HashMap<&str, u32> = HashMap::new();
+        if let Some(Value::Array(columns)) = sheet.get("columns") {
+            for column in columns {
+                let (Some(key), Some(col)) = (
+                    column.get("key").and_then(Value::as_str),
+                    column
+                        .get("column")
+                        .and_then(Value::as_str)
+                        .and_then(column_index),
+                ) else {
+                    continue;
+                };
+                if let Some(header) = column.get("header").and_then(Value::as_str) {
+                    worksheet
+                        .write_string(0, to_col(name, col)?, header)
+                        .map_err(|e| write_error(name, e))?;
+                }
+                key_columns.insert(key, col);
+            }
+        }
+        let first_row = u32::from(!key_columns.is_empty());
+
+        let rows = sheet.get("rows").and_then(Value::as_array);
+        for (row_idx, row) in (first_row..).zip(rows.into_iter().flatten()) {
+            let Value::Object(row) = row else {
+                continue;
+            };
+            for (key, cell) in row {
+                let col = match key_columns.get(key.as_str()) {
+                    Some(col) => *col,
+                    None => column_index(key).ok_or_else(|| {
+                        invalid_input(name, format!("unknown column key '{}'", key))
+                    })?,
+                };
+                write_value(
+                    worksheet,
+                    name,
+                    row_idx,
+                    col,
+                    &json_cell_value(cell),
+                    &formats,
+                )?;
+            }
+        }
+    }
+
+    workbook.save_to_buffer().map_err(|e| write_error(None, e))
+}
+
+/// 日付・時刻の種別ごとの表示形式（内部用）
+struct DateFormats {
+    date: Format,
+    time: Format,
+    datetime: Format,
+}
+
+impl DateFormats {
+    fn new() -> Self {
+        Self {
+            date: Format::new().set_num_format(DATE_FORMAT),
+            time: Format::new().set_num_format(TIME_FORMAT),
+            datetime: Format::new().set_num_format(DATETIME_FORMAT),
+        }
+    }
+
+    fn get(&self, kind: DateKind) -> &Format {
+        match kind {
+            DateKind::Date => &self.date,
+            DateKind::Time => &self.time,
+            DateKind::DateTime => &self.datetime,
+        }
+    }
+}
+
+/// セルの値を書き出す（空のセルは書き出さない）（内部ヘルパー）
+fn write_value(
+    worksheet: &mut Worksheet,
+    sheet: Option<&str>,
+    row: u32,
+    col: u32,
+    value: &CellValue,
+    formats: &DateFormats,
+) -> Result<(), XlsxToMdError> {
+    let col = to_col(sheet, col)?;
+    let result = match value {
+        CellValue::Number(n) => worksheet.write_number(row, col, *n),
+        CellValue::DateTime { serial, kind } => {
+            worksheet.write_number_with_format(row, col, *serial, formats.get(*kind))
+        }
+        CellValue::String(s) => worksheet.write_string(row, col, s),
+        CellValue::Bool(b) => worksheet.write_boolean(row, col, *b),
+        CellValue::Error(e) => worksheet.write_string(row, col, e.as_excel_str()),
+        CellValue::Empty => return Ok(()),
+    };
+    result.map(|_| ()).map_err(|e| write_error(sheet, e))
+}
+
+/// 結合範囲（親セルの座標 → 右下のセルの座標）を取得（内部ヘルパー）
+///
+/// 親セルと同じ親を持つセルを囲む長方形に、他のセルが含まれない範囲のみを返します。
+/// 行の削除などで親セルが残っていない範囲は返しません。
+fn merge_ranges(document: &GridDocument) -> HashMap<CellCoord, CellCoord> {
+    let grid = document.grid();
+    let mut ranges: HashMap<CellCoord, (CellCoord, usize)> = HashMap::new();
+    for (row_idx, row) in (0u32..).zip((0..grid.get_rows()).map(|r| grid.get_row(r))) {
+        for (col_idx, cell) in (0u32..).zip(row) {
+            if let Some(parent) = cell.merge_parent {
+                let (end, count) = ranges.entry(parent).or_insert((parent, 1));
+                end.row = end.row.max(row_idx);
+                end.col = end.col.max(col_idx);
+                *count += 1;
+            }
+        }
+    }
+
+    ranges
+        .into_iter()
+        .filter(|(parent, (end, count))| {
+            grid.get_cell(*parent)
+                .is_some_and(|cell| cell.merge_parent.is_none())
+                && end.row >= parent.row
+                && end.col >= parent.col
+                && (end.row - parent.row + 1) as usize * (end.col - parent.col + 1) as usize
+                    == *count
+        })
+        .map(|(parent, (end, _))| (parent, end))
+        .collect()
+}
+
+/// JSONのセルを値に変換（内部ヘルパー）
+fn json_cell_value(cell: &Value) -> CellValue {
+    match cell {
+        Value::String(s) => CellValue::String(s.clone()),
+        Value::Number(n) => n.as_f64().map_or(CellValue::Empty, CellValue::Number),
+        Value::Bool(b) => CellValue::Bool(*b),
+        // セル参照を含むJsonCellMode::Plain（`value`と`ref`を持つオブジェクト）
+        Value::Object(cell) if !cell.contains_key("type") => {
+            json_cell_value(cell.get("value").unwrap_or(&Value::Null))
+        }
+        // JsonCellMode::Typed
+        Value::Object(cell) => typed_cell_value(cell),
+        _ => CellValue::Empty,
+    }
+}
+
+/// `JsonCellMode::Typed`のセルを値に変換（内部ヘルパー）
+fn typed_cell_value(cell: &Map<String, Value>) -> CellValue {
+    let raw = cell.get("raw").unwrap_or(&Value::Null);
+    let kind = match cell.get("type").and_then(Value::as_str) {
+        Some("date") => Some(DateKind::Date),
+        Some("time") => Some(DateKind::Time),
+        Some("datetime") => Some(DateKind::DateTime),
+        Some("empty") => return CellValue::Empty,
+        _ => None,
+    };
+    match (kind, raw.as_f64()) {
+        (Some(kind), Some(serial)) => CellValue::DateTime { serial, kind },
+        _ => json_cell_value(raw),
+    }
+}
+
+/// 列の文字列（例: `AB`）を列番号（0始まり）に変換（内部ヘルパー）
+fn column_index(letters: &str) -> Option<u32> {
+    if letters.is_empty() || !letters.bytes().all(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    letters
+        .bytes()
+        .try_fold(0u32, |col, b| {
+            col.checked_mul(26)?.checked_add(u32::from(b - b'A') + 1)
+        })
+        .map(|col| col - 1)
+}
+
+/// 列番号をExcelの列番号の型に変換（内部ヘルパー）
+fn to_col(sheet: Option<&str>, col: u32) -> Result<u16, XlsxToMdError> {
+    u16::try_from(col)
+        .map_err(|_| invalid_input(sheet, format!("column {} is out of range", col + 1)))
+}
+
+/// 書き出せない入力のエラーを生成（内部ヘルパー）
+fn invalid_input(sheet: Option<&str>, message: impl Into<String>) -> XlsxToMdError {
+    XlsxToMdError::InvalidWriteInput {
+        sheet: sheet.map(str::to_string),
+        message: message.into(),
+    }
+}
+
+/// rust_xlsxwriterのエラーを変換（内部ヘルパー）
+///
+/// ZIPアーカイブの生成・書き込みの失敗は`Write`、シート名や上限など入力に起因するエラーは
+/// `InvalidWriteInput`に分類します。
+fn write_error(sheet: Option<&str>, error: XlsxError) -> XlsxToMdError {
+    match error {
+        XlsxError::IoError(_) | XlsxError::ZipError(_) => XlsxToMdError::Write(error.to_string()),
+        _ => invalid_input(sheet, error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_index() {
+        assert_eq!(column_index("A"), Some(0));
+        assert_eq!(column_index("Z"), Some(25));
+        assert_eq!(column_index("AA"), Some(26));
+        assert_eq!(column_index("XFD"), Some(16383));
+        assert_eq!(column_index(""), None);
+        assert_eq!(column_index("a"), None);
+        assert_eq!(column_index("A1"), None);
+    }
+
+    #[test]
+    fn test_typed_cell_value() {
+        let cell = |json: &str| json_cell_value(&serde_json::from_str(json).unwrap());
+
+        assert_eq!(
+            cell(r#"{"type": "date", "raw": 45658, "formatted": "2025-01-01"}"#),
+            CellValue::DateTime {
+                serial: 45658.0,
+                kind: DateKind::Date
+            }
+        );
+        assert_eq!(
+            cell(r#"{"type": "number", "raw": 1.5, "formatted": "1.5"}"#),
+            CellValue::Number(1.5)
+        );
+        assert_eq!(
+            cell(r##"{"type": "error", "raw": "#N/A", "formatted": "#N/A", "error": "na"}"##),
+            CellValue::String("#N/A".to_string())
+        );
+        assert_eq!(
+            cell(r#"{"type": "empty", "raw": null, "formatted": ""}"#),
+            CellValue::Empty
+        );
+        assert_eq!(cell("true"), CellValue::Bool(true));
+    }
+}
@@ -5,7 +5,9 @@
 
 use rust_xlsxwriter::*;
 use std::io::Cursor;
-use xlsxzero::{ConverterBuilder, DateFormat};
+use xlsxzero::{
+    ConverterBuilder, DateFormat, ErrorKind, MergeStrategy, OutputFormat, Stage, XlsxToMdError,
+};
 
 // Helper module for generating boundary test fixtures
 mod fixtures {
@@ -24,7 +26,7 @@ mod fixtures {
         // For a truly empty workbook test, we'll need to create a minimal
         // workbook file manually or use a different approach.
         // For now, we'll create a workbook with one empty sheet.
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a workbook with an empty sheet (no cells)
@@ -34,7 +36,7 @@ mod fixtures {
         let worksheet = workbook.add_worksheet();
         worksheet.set_name("EmptySheet")?;
         // No cells written - completely empty sheet
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a workbook with maximum rows (1,048,576)
@@ -54,15 +56,15 @@ mod fixtures {
 
         // Write a few more rows in between for verification
         for i in 1..=10 {
-            worksheet.write_string(i, 0, &format!("Row{}", i))?;
+            worksheet.write_string(i, 0, format!("Row{}", i))?;
         }
 
         // Write to near the end
         for i in (MAX_ROWS - 10)..(MAX_ROWS - 1) {
-            worksheet.write_string(i, 0, &format!("Row{}", i))?;
+            worksheet.write_string(i, 0, format!("Row{}", i))?;
         }
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a workbook with maximum columns (16,384 = XFD)
@@ -83,15 +85,15 @@ mod fixtures {
 
         // Write a few more columns in between
         for i in 1..=10 {
-            worksheet.write_string(0, i, &format!("Col{}", i))?;
+            worksheet.write_string(0, i, format!("Col{}", i))?;
         }
 
         // Write to near the end
         for i in (MAX_COLS - 10)..(MAX_COLS - 1) {
-            worksheet.write_string(0, i, &format!("Col{}", i))?;
+            worksheet.write_string(0, i, format!("Col{}", i))?;
         }
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a workbook with a very long cell content (32,767 characters)
@@ -112,7 +114,7 @@ mod fixtures {
         // Also add a shorter cell for comparison
         worksheet.write_string(1, 0, "ShortCell")?;
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a workbook with dates at epoch boundaries
@@ -146,7 +148,7 @@ mod fixtures {
         worksheet.write_number(3, 0, 73050.0)?;
         worksheet.write_string(3, 1, "Serial73050.0")?;
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
     /// Generate a corrupted/invalid Excel file
@@ -167,6 +169,102 @@ mod fixtures {
         data.extend_from_slice(b"INVALID_CONTENT");
         data
     }
+
+    /// Rewrite one part of a generated workbook (for inputs rust_xlsxwriter cannot produce)
+    fn rewrite_part(buffer: &[u8], part: &str, rewrite: impl Fn(&str) -> String) -> Vec<u8> {
+        use std::io::{Read, Write};
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let name = file.name().to_string();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            if name == part {
+                content = rewrite(&String::from_utf8(content).unwrap()).into_bytes();
+            }
+            writer
+                .start_file(name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// Generate a corpus of malformed inputs derived from a valid workbook
+    /// Edge case: Fuzz corpus (no panic guarantee)
+    ///
+    /// Contains truncated archives, byte-flipped archives, and archives whose
+    /// worksheet XML holds out-of-range values and references.
+    pub fn generate_fuzz_corpus() -> Result<Vec<Vec<u8>>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let date_format = Format::new().set_num_format("yyyy-mm-dd hh:mm");
+        let empty_sections = Format::new().set_num_format(";;;");
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Name")?;
+        worksheet.write_string(0, 1, "Date")?;
+        worksheet.write_string(0, 2, "Hidden")?;
+        worksheet.write_string(1, 0, "Alpha")?;
+        worksheet.write_number_with_format(1, 1, 45000.5, &date_format)?;
+        worksheet.write_number_with_format(1, 2, 12.0, &empty_sections)?;
+        worksheet.merge_range(2, 0, 2, 1, "Merged", &Format::new())?;
+        worksheet.write_url_with_text(3, 0, "https://example.com", "Link")?;
+        worksheet.write_formula(3, 1, "=B2+1")?;
+        worksheet.set_column_hidden(2)?;
+        let valid = workbook.save_to_buffer()?;
+
+        let mut corpus = vec![valid.clone()];
+
+        // Truncated archives
+        for len in (0..valid.len()).step_by(97) {
+            corpus.push(valid[..len].to_vec());
+        }
+
+        // Byte-flipped archives
+        for pos in (0..valid.len()).step_by(61) {
+            let mut flipped = valid.clone();
+            flipped[pos] ^= 0xFF;
+            corpus.push(flipped);
+        }
+
+        // Out-of-range values and references in the worksheet XML
+        let sheet = "xl/worksheets/sheet1.xml";
+        for value in [
+            "1e300",
+            "-1e300",
+            "NaN",
+            "inf",
+            "-0",
+            "99999999999999999999",
+        ] {
+            corpus.push(rewrite_part(&valid, sheet, |xml| {
+                xml.replace("<v>45000.5</v>", &format!("<v>{}</v>", value))
+                    .replace("<v>12</v>", &format!("<v>{}</v>", value))
+            }));
+        }
+        corpus.push(rewrite_part(&valid, sheet, |xml| {
+            xml.replace("r=\"B2\"", "r=\"XFE1048577\"")
+                .replace("r=\"A2\"", "r=\"A0\"")
+        }));
+        corpus.push(rewrite_part(&valid, sheet, |xml| {
+            xml.replace("ref=\"A3:B3\"", "ref=\"B3:A1\"")
+        }));
+        corpus.push(rewrite_part(&valid, sheet, |xml| {
+            xml.replace("min=\"3\" max=\"3\"", "min=\"0\" max=\"4294967295\"")
+        }));
+        corpus.push(rewrite_part(&valid, sheet, |xml| {
+            xml.replace("t=\"s\"", "t=\"e\"")
+        }));
+        corpus.push(rewrite_part(&valid, "xl/sharedStrings.xml", |xml| {
+            xml.replace("<t>", "<t><").replace("count=", "x=")
+        }));
+        corpus.push(rewrite_part(&valid, "xl/styles.xml", |xml| {
+            xml.replace("yyyy-mm-dd hh:mm", "[h]:mm;\"\";@;@;@")
+        }));
+
+        Ok(corpus)
+    }
 }
 
 // TC-B-001: Empty Workbook
@@ -377,3 +475,67 @@ fn test_invalid_structure() {
         }
     }
 }
+
+// Edge case: Fuzz corpus (no panic guarantee)
+#[test]
+fn test_no_panic_on_fuzz_corpus() {
+    let corpus = fixtures::generate_fuzz_corpus().unwrap();
+    let converters = [
+        ConverterBuilder::new().build().unwrap(),
+        ConverterBuilder::new()
+            .with_merge_strategy(MergeStrategy::HtmlFallback)
+            .include_hidden(true)
+            .include_metadata(true)
+            .with_encoding_fallback(true)
+            .build()
+            .unwrap(),
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .build()
+            .unwrap(),
+    ];
+
+    // try_ variants must return (Ok or Err) for every input without unwinding
+    // into the caller, even when a dependency panics on malformed XML
+    for (idx, input) in corpus.iter().enumerate() {
+        for converter in &converters {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                let _ = converter.try_convert_to_string(Cursor::new(input.as_slice()));
+            }));
+            assert!(result.is_ok(), "Corpus entry {} caused a panic", idx);
+        }
+    }
+
+    // The unmodified workbook converts successfully
+    assert!(converters[0]
+        .try_convert_to_string(Cursor::new(corpus[0].as_slice()))
+        .is_ok());
+}
+
+// Edge case: try_ variants turn panics into errors
+#[test]
+fn test_try_convert_catches_panic() {
+    let buffer = fixtures::generate_empty_sheet().unwrap();
+    let converter = ConverterBuilder::new()
+        .with_stage(
+            Stage::Parse,
+            |_: &mut xlsxzero::StageContext<'_>| -> Result<(), XlsxToMdError> {
+                panic!("stage failure")
+            },
+        )
+        .build()
+        .unwrap();
+
+    let error = converter
+        .try_convert_to_string(Cursor::new(buffer.clone()))
+        .unwrap_err();
+    assert!(matches!(error, XlsxToMdError::Internal(_)));
+    assert_eq!(error.kind(), ErrorKind::Internal);
+    assert!(error.to_string().contains("stage failure"));
+
+    let mut output = Vec::new();
+    let error = converter
+        .try_convert(Cursor::new(buffer), &mut output)
+        .unwrap_err();
+    assert!(matches!(error, XlsxToMdError::Internal(_)));
+}
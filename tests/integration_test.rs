@@ -5,196 +5,987 @@
 
 use rust_xlsxwriter::*;
 use std::io::Cursor;
-use xlsxzero::{ConverterBuilder, FormulaMode, MergeStrategy, OutputFormat, SheetSelector};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use xlsxzero::{
+    BatchConverter, BatchInput, BlankRows, CellError, CellImageMode, CellValue, ChartMode,
+    ColumnSelector, ColumnType, CommentMode, ConditionalFormats, ContentFilter, ConverterBuilder,
+    DataValidations, DirectorySink, DuplicateSheets, DuplicationPlacement, FormulaMode,
+    HeaderUnits, HyperlinkMode, JsonCellMode, JsonDocumentMode, JsonKeyMode, JsonStyle,
+    MergeStrategy, NullFormulaPolicy, NumberOutput, OutputFormat, OutputOverflow, OutputSink,
+    PivotMode, ProgressEvent, ProtectedSheets, Revision, RowDedupe, SheetDocument, SheetSelector,
+    SortOrder, Stage, StageContext, TrimMode, Validation, ValidationPolicy, WarningKind,
+    WrapStrategy, XlsxToMdError,
+};
 
 // Helper module for generating test fixtures
 mod fixtures {
     use super::*;
 
-    /// Generate a simple 2x2 table Excel file
-    pub fn generate_simple_table() -> Result<Vec<u8>, XlsxError> {
+    pub use xlsxzero::fixtures::{
+        formats_matrix, hidden_elements, hyperlinks, large_table, merged_cells, multi_sheets,
+        rich_text, simple_table,
+    };
+
+    /// Generate a table with formula cells
+    pub fn generate_formulas() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
 
-        // Header row
-        worksheet.write_string(0, 0, "Header1")?;
-        worksheet.write_string(0, 1, "Header2")?;
+        // Data cells
+        worksheet.write_number(0, 0, 10.0)?;
+        worksheet.write_number(0, 1, 20.0)?;
+        worksheet.write_number(0, 2, 30.0)?;
+        worksheet.write_number(0, 3, 40.0)?;
+
+        // Formula cell: SUM(A1:D1) - should evaluate to 100
+        worksheet.write_formula(1, 0, "=SUM(A1:D1)")?;
 
-        // Data row
-        worksheet.write_string(1, 0, "Data1")?;
-        worksheet.write_string(1, 1, "Data2")?;
+        // Another formula: AVERAGE(A1:D1) - should evaluate to 25
+        worksheet.write_formula(1, 1, "=AVERAGE(A1:D1)")?;
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
-    /// Generate a workbook with 3 sheets
-    pub fn generate_multi_sheets() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a workbook with numbers formatted with thousands separators
+    pub fn generate_numeric_table() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let number_format = Format::new().set_num_format("#,##0.00");
+
+        worksheet.write_string(0, 0, "Item")?;
+        worksheet.write_string(0, 1, "Amount")?;
+        worksheet.write_string(1, 0, "Sales")?;
+        worksheet.write_number_with_format(1, 1, 1234.56, &number_format)?;
+        worksheet.write_string(2, 0, "Count")?;
+        worksheet.write_number(2, 1, 42)?;
+
+        workbook.save_to_buffer()
+    }
 
-        // Sheet1
-        let sheet1 = workbook.add_worksheet();
-        sheet1.set_name("Sheet1")?;
-        sheet1.write_string(0, 0, "Sheet1_Data")?;
+    /// Generate a workbook with formula cells saved without cached values
+    ///
+    /// rust_xlsxwriter always writes a cached result, so the `<v>` elements
+    /// of formula cells are stripped to emulate an uncalculated workbook.
+    pub fn generate_uncalculated_formulas() -> Result<Vec<u8>, XlsxError> {
+        use std::io::{Read, Write};
 
-        // Sheet2
-        let sheet2 = workbook.add_worksheet();
-        sheet2.set_name("Sheet2")?;
-        sheet2.write_string(0, 0, "Sheet2_Data")?;
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
 
-        // Sheet3
-        let sheet3 = workbook.add_worksheet();
-        sheet3.set_name("Sheet3")?;
-        sheet3.write_string(0, 0, "Sheet3_Data")?;
+        worksheet.write_string(0, 0, "Base")?;
+        worksheet.write_string(0, 1, "Total")?;
+        worksheet.write_number(1, 0, 10)?;
+        worksheet.write_formula(1, 1, "=A2*2")?;
+        worksheet.write_formula(2, 1, "=SUM(B2)")?;
+        let buffer = workbook.save_to_buffer()?;
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let name = file.name().to_string();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            if name == "xl/worksheets/sheet1.xml" {
+                content = String::from_utf8(content)
+                    .unwrap()
+                    .replace("</f><v>0</v>", "</f>")
+                    .into_bytes();
+            }
+            writer
+                .start_file(name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(&content).unwrap();
+        }
 
-        Ok(workbook.save_to_buffer()?)
+        Ok(writer.finish().unwrap().into_inner())
     }
 
-    /// Generate a table with merged cells
-    pub fn generate_merged_cells() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a workbook whose used range is bloated by a stray blank cell
+    pub fn generate_bloated_used_range() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
 
-        // Merged header cell (A1:C1)
-        worksheet.write_string(0, 0, "Header")?;
-        worksheet.merge_range(0, 0, 0, 2, "", &Format::new())?;
-        // Re-write the value after merging
-        worksheet.write_string(0, 0, "Header")?;
+        worksheet.write_string(0, 0, "Name")?;
+        worksheet.write_string(0, 1, "Value")?;
+        worksheet.write_string(1, 0, "Alpha")?;
+        worksheet.write_number(1, 1, 1)?;
+        // Whitespace-only cell far below the data
+        worksheet.write_string(199_999, 0, " ")?;
+
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with date, time, datetime and duration cells
+    pub fn generate_date_time_table() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
 
-        // Data rows
-        worksheet.write_string(1, 0, "Data1")?;
-        worksheet.write_string(1, 1, "Data2")?;
-        worksheet.write_string(1, 2, "Data3")?;
+        worksheet.write_string(0, 0, "Date")?;
+        worksheet.write_string(0, 1, "Time")?;
+        worksheet.write_string(0, 2, "DateTime")?;
+        worksheet.write_string(0, 3, "Duration")?;
+        worksheet.write_number_with_format(
+            1,
+            0,
+            45658.0,
+            &Format::new().set_num_format("yyyy-mm-dd"),
+        )?;
+        worksheet.write_number_with_format(1, 1, 0.5, &Format::new().set_num_format("hh:mm"))?;
+        worksheet.write_number_with_format(
+            1,
+            2,
+            45658.75,
+            &Format::new().set_num_format("yyyy-mm-dd hh:mm"),
+        )?;
+        worksheet.write_number_with_format(
+            1,
+            3,
+            1.5,
+            &Format::new().set_num_format("[h]:mm:ss"),
+        )?;
+
+        workbook.save_to_buffer()
+    }
 
-        Ok(workbook.save_to_buffer()?)
+    /// Rewrite one part of a generated workbook (for inputs rust_xlsxwriter cannot produce)
+    fn rewrite_part(buffer: Vec<u8>, part: &str, rewrite: impl Fn(Vec<u8>) -> Vec<u8>) -> Vec<u8> {
+        use std::io::{Read, Write};
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let name = file.name().to_string();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            if name == part {
+                content = rewrite(content);
+            }
+            writer
+                .start_file(name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
     }
 
-    /// Generate a table with formula cells
-    pub fn generate_formulas() -> Result<Vec<u8>, XlsxError> {
+    /// Append extra parts to a generated workbook (for inputs rust_xlsxwriter cannot produce)
+    fn add_parts(buffer: Vec<u8>, parts: &[(&str, &str)]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new_append(Cursor::new(buffer)).unwrap();
+        for (name, content) in parts {
+            writer
+                .start_file(*name, zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    /// Generate a shared workbook with two revisions of its budget sheet
+    pub fn generate_revision_history() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
+        worksheet.set_name("Budget")?;
+        worksheet.write_string(0, 0, "Item")?;
+        worksheet.write_string(0, 1, "Amount")?;
+        worksheet.write_string(1, 0, "Rent")?;
+        worksheet.write_number(1, 1, 150)?;
+        let buffer = workbook.save_to_buffer()?;
+
+        Ok(add_parts(
+            buffer,
+            &[
+                (
+                    "xl/revisions/revisionHeaders.xml",
+                    r#"<headers xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" guid="{00000000-0000-0000-0000-000000000000}"><header guid="{00000000-0000-0000-0000-000000000001}" dateTime="2024-05-01T09:30:00Z" maxSheetId="2" userName="Ada" r:id="rId1"><sheetIdMap count="1"><sheetId val="1"/></sheetIdMap></header><header guid="{00000000-0000-0000-0000-000000000002}" dateTime="2024-05-02T10:00:00Z" maxSheetId="2" userName="Alan" r:id="rId2"><sheetIdMap count="1"><sheetId val="1"/></sheetIdMap></header></headers>"#,
+                ),
+                (
+                    "xl/revisions/_rels/revisionHeaders.xml.rels",
+                    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/revisionLog" Target="revisionLog1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/revisionLog" Target="revisionLog2.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/revisions/revisionLog1.xml",
+                    r#"<revisions><rcc rId="1" sId="1"><oc r="B2" t="n"><v>120</v></oc><nc r="B2" t="n"><v>150</v></nc></rcc></revisions>"#,
+                ),
+                (
+                    "xl/revisions/revisionLog2.xml",
+                    r#"<revisions><rcc rId="2" sId="1"><nc r="A3" t="inlineStr"><is><t>Power</t></is></nc></rcc><rcc rId="3" sId="1"><oc r="A3" t="inlineStr"><is><t>Power</t></is></oc></rcc></revisions>"#,
+                ),
+            ],
+        ))
+    }
 
-        // Data cells
-        worksheet.write_number(0, 0, 10.0)?;
-        worksheet.write_number(0, 1, 20.0)?;
-        worksheet.write_number(0, 2, 30.0)?;
-        worksheet.write_number(0, 3, 40.0)?;
+    /// Generate a workbook whose shared strings contain invalid UTF-8
+    ///
+    /// Mimics third-party generators that write Latin-1 / Windows-1252 bytes:
+    /// "Café" is stored with a bare 0xE9 byte instead of its UTF-8 encoding.
+    pub fn generate_invalid_utf8_shared_strings() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Name")?;
+        worksheet.write_string(1, 0, "Café")?;
+        let buffer = workbook.save_to_buffer()?;
+
+        Ok(rewrite_part(buffer, "xl/sharedStrings.xml", |content| {
+            let text = String::from_utf8(content).unwrap();
+            text.replace("é", "\u{1}")
+                .into_bytes()
+                .into_iter()
+                .map(|byte| if byte == 1 { 0xE9 } else { byte })
+                .collect()
+        }))
+    }
 
-        // Formula cell: SUM(A1:D1) - should evaluate to 100
-        worksheet.write_formula(1, 0, "=SUM(A1:D1)")?;
+    /// Generate a workbook whose cells use the "Heading 1" and "Input" named styles
+    ///
+    /// rust_xlsxwriter only writes the "Normal" cell style, so styles.xml is replaced:
+    /// the cells written with `heading` get style 1 ("Heading 1"), `input` gets style 2 ("Input").
+    pub fn generate_named_styles() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let heading = Format::new().set_bold();
+        let input = Format::new().set_italic();
+
+        worksheet.write_string_with_format(0, 0, "Quarterly Report", &heading)?;
+        worksheet.write_string(1, 0, "Region")?;
+        worksheet.write_string(1, 1, "Sales")?;
+        worksheet.write_string(2, 0, "EMEA")?;
+        worksheet.write_number_with_format(2, 1, 100, &input)?;
+        worksheet.write_string_with_format(3, 0, "Costs", &heading)?;
+        worksheet.write_string(4, 0, "Item")?;
+        worksheet.write_string(4, 1, "Cost")?;
+        worksheet.write_string(5, 0, "Rent")?;
+        worksheet.write_number(5, 1, 50)?;
+        let buffer = workbook.save_to_buffer()?;
+
+        Ok(rewrite_part(buffer, "xl/styles.xml", |_| {
+            br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><fonts count="1"><font><sz val="11"/><name val="Calibri"/></font></fonts><fills count="2"><fill><patternFill patternType="none"/></fill><fill><patternFill patternType="gray125"/></fill></fills><borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders><cellStyleXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs><cellXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="1"/><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="2"/></cellXfs><cellStyles count="3"><cellStyle name="Normal" xfId="0" builtinId="0"/><cellStyle name="Heading 1" xfId="1" builtinId="16"/><cellStyle name="Input" xfId="2" builtinId="20"/></cellStyles></styleSheet>"#
+                .to_vec()
+        }))
+    }
 
-        // Another formula: AVERAGE(A1:D1) - should evaluate to 25
-        worksheet.write_formula(1, 1, "=AVERAGE(A1:D1)")?;
+    /// Generate a workbook with formula error values (#DIV/0!, #N/A)
+    pub fn generate_error_cells() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Ratio")?;
+        worksheet.write_formula(1, 0, Formula::new("=1/0").set_result("#DIV/0!"))?;
+        worksheet.write_formula(2, 0, Formula::new("=NA()").set_result("#N/A"))?;
+        let buffer = workbook.save_to_buffer()?;
+
+        // rust_xlsxwriterは文字列の結果として書き込むため、エラー値（t="e"）に置き換える
+        Ok(rewrite_part(
+            buffer,
+            "xl/worksheets/sheet1.xml",
+            |content| {
+                String::from_utf8(content)
+                    .unwrap()
+                    .replace(r#"t="str""#, r#"t="e""#)
+                    .into_bytes()
+            },
+        ))
+    }
+
+    /// 1x1ピクセルのPNG画像
+    pub const PNG: &[u8] = &[
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44,
+        0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00, 0x00, 0x1F,
+        0x15, 0xC4, 0x89, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x00,
+        0x01, 0x00, 0x00, 0x05, 0x00, 0x01, 0x0D, 0x0A, 0x2D, 0xB4, 0x00, 0x00, 0x00, 0x00, 0x49,
+        0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+
+    /// Generate a workbook with an image placed in a cell ("Place in Cell")
+    pub fn generate_cell_image() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Product")?;
+        worksheet.write_string(0, 1, "Photo")?;
+        worksheet.write_string(1, 0, "Apple")?;
+        worksheet.embed_image(1, 1, &Image::new_from_buffer(PNG)?)?;
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with a dynamic array formula (SEQUENCE) and its cached spill values
+    pub fn generate_spill_range() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Seq")?;
+        worksheet.write_string(0, 1, "Label")?;
+        worksheet.write_dynamic_array_formula(
+            1,
+            0,
+            4,
+            0,
+            Formula::new("=SEQUENCE(4)").set_result("1"),
+        )?;
+        // Excelは溢れた範囲の計算結果を通常の値として保存する
+        for row in 2..5u32 {
+            worksheet.write_number(row, 0, row as f64)?;
+        }
+        for row in 1..5u32 {
+            worksheet.write_string(row, 1, format!("L{}", row))?;
+        }
+        workbook.save_to_buffer()
+    }
 
-        Ok(workbook.save_to_buffer()?)
+    /// Generate a workbook with a copied sheet ("Sheet1 (2)") and an unrelated sheet
+    pub fn generate_duplicate_sheets() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        for name in ["Sheet1", "Other", "Sheet1 (2)"] {
+            let worksheet = workbook.add_worksheet();
+            worksheet.set_name(name)?;
+            worksheet.write_string(0, 0, "Item")?;
+            worksheet.write_string(0, 1, "Qty")?;
+            for row in 1..20u32 {
+                let item = if name == "Other" {
+                    format!("other{}", row)
+                } else {
+                    format!("item{}", row)
+                };
+                worksheet.write_string(row, 0, item)?;
+                worksheet.write_number(row, 1, row as f64)?;
+            }
+        }
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with typed cells, XML special characters and a second sheet
+    pub fn generate_xml_table() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name("R&D")?;
+        worksheet.write_string(0, 0, "Item")?;
+        worksheet.write_string(0, 1, "Amount")?;
+        worksheet.write_string(0, 2, "Done")?;
+        worksheet.write_string(1, 0, "<Tom & \"Jerry\">")?;
+        worksheet.write_number_with_format(
+            1,
+            1,
+            1234.5,
+            &Format::new().set_num_format("#,##0.00"),
+        )?;
+        worksheet.write_boolean(1, 2, true)?;
+        // 3行目は空行、4行目は途中の列のみ
+        worksheet.write_number(3, 1, 42)?;
+
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name("Notes")?;
+        worksheet.write_string(0, 0, "line1\nline2")?;
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook whose second sheet is protected
+    pub fn generate_protected_sheet() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Open")?;
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Locked")?;
+        worksheet.protect();
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with duplicate-highlighting and top-1 conditional formats
+    pub fn generate_conditional_formats() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "Invoice")?;
+        worksheet.write_string(0, 1, "Amount")?;
+        for (row, (invoice, amount)) in
+            [("INV-1", 120), ("INV-2", 80), ("INV-1", 300), ("INV-3", 50)]
+                .into_iter()
+                .enumerate()
+        {
+            worksheet.write_string(row as u32 + 1, 0, invoice)?;
+            worksheet.write_number(row as u32 + 1, 1, amount)?;
+        }
+        worksheet.add_conditional_format(1, 0, 4, 0, &ConditionalFormatDuplicate::new())?;
+        worksheet.add_conditional_format(
+            1,
+            1,
+            4,
+            1,
+            &ConditionalFormatTop::new().set_rule(ConditionalFormatTopRule::Top(1)),
+        )?;
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with a column sparkline per row and a data bar over the totals
+    pub fn generate_visuals() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        for (col, header) in ["Q1", "Q2", "Q3", "Total", "Trend"].into_iter().enumerate() {
+            worksheet.write_string(0, col as u16, header)?;
+        }
+        for row in 1..=2u32 {
+            for col in 0..3u16 {
+                worksheet.write_number(row, col, f64::from(row * 10 + u32::from(col)))?;
+            }
+            worksheet.write_formula(row, 3, format!("=SUM(A{0}:C{0})", row + 1).as_str())?;
+            worksheet.add_sparkline(
+                row,
+                4,
+                &Sparkline::new()
+                    .set_range(("Sheet1", row, 0, row, 2))
+                    .set_type(SparklineType::Column),
+            )?;
+        }
+        worksheet.add_conditional_format(1, 3, 2, 3, &ConditionalFormatDataBar::new())?;
+        workbook.save_to_buffer()
     }
 
-    /// Generate a large table for range restriction tests
-    pub fn generate_large_table() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a workbook with a verbose description column
+    pub fn generate_long_descriptions() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
+        worksheet.write_string(0, 0, "SKU")?;
+        worksheet.write_string(0, 1, "Description")?;
+        worksheet.write_string(1, 0, "A-1")?;
+        worksheet.write_string(1, 1, "Stainless steel water bottle with a leak-proof lid")?;
+        worksheet.write_string(2, 0, "B-2")?;
+        worksheet.write_string(2, 1, "Mug")?;
+        workbook.save_to_buffer()
+    }
 
-        // Generate a 10x10 table
-        for row in 0..10 {
-            for col in 0..10 {
-                worksheet.write_string(row, col, &format!("R{}C{}", row + 1, col + 1))?;
+    /// Generate a workbook whose package relationships reference a JPEG thumbnail
+    pub fn generate_with_thumbnail() -> Result<Vec<u8>, XlsxError> {
+        use std::io::{Read, Write};
+
+        let mut workbook = Workbook::new();
+        workbook.add_worksheet().set_name("Summary")?;
+        workbook.add_worksheet().set_name("Data")?;
+        let buffer = workbook.save_to_buffer()?;
+
+        // _rels/.rels は置き換えるため、アーカイブを作り直す
+        let mut archive = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            if file.name() == "_rels/.rels" {
+                content = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail" Target="docProps/thumbnail.jpeg"/></Relationships>"#
+                    .to_vec();
             }
+            writer
+                .start_file(file.name(), zip::write::FileOptions::default())
+                .unwrap();
+            writer.write_all(&content).unwrap();
+        }
+        writer
+            .start_file(
+                "docProps/thumbnail.jpeg",
+                zip::write::FileOptions::default(),
+            )
+            .unwrap();
+        writer
+            .write_all(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F'])
+            .unwrap();
+        Ok(writer.finish().unwrap().into_inner())
+    }
+
+    /// Generate a workbook with a visible, a hidden and a very hidden sheet
+    pub fn generate_hidden_sheets() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        workbook
+            .add_worksheet()
+            .set_name("Public")?
+            .write_string(0, 0, "Visible")?;
+        workbook
+            .add_worksheet()
+            .set_name("Archive")?
+            .set_hidden(true)
+            .write_string(0, 0, "Hidden")?;
+        workbook
+            .add_worksheet()
+            .set_name("SecretSheet")?
+            .set_hidden(true)
+            .write_string(0, 0, "Very hidden")?;
+        let buffer = workbook.save_to_buffer()?;
+
+        // rust_xlsxwriterは`veryHidden`を出力できないため、ワークブックXMLを書き換える
+        Ok(rewrite_part(buffer, "xl/workbook.xml", |content| {
+            String::from_utf8(content)
+                .unwrap()
+                .replace(
+                    r#"<sheet name="SecretSheet" sheetId="3" state="hidden""#,
+                    r#"<sheet name="SecretSheet" sheetId="3" state="veryHidden""#,
+                )
+                .into_bytes()
+        }))
+    }
+
+    /// Generate a table whose category label is merged vertically over three rows
+    pub fn generate_vertical_merge() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        worksheet.write_string(0, 0, "Category")?;
+        worksheet.write_string(0, 1, "Item")?;
+        worksheet.merge_range(1, 0, 3, 0, "Fruit", &Format::new())?;
+        for (row, item) in [(1, "Apple"), (2, "Banana"), (3, "Cherry")] {
+            worksheet.write_string(row, 1, item)?;
         }
+        worksheet.write_string(4, 0, "Vegetable")?;
+        worksheet.write_string(4, 1, "Carrot")?;
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
-    /// Generate a table with hidden rows and columns
-    pub fn generate_hidden_elements() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a `People` sheet with a Name/Age header row and an `Empty` sheet
+    pub fn generate_people() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
+        worksheet.set_name("People")?;
+        worksheet.write_string(0, 0, "Name")?;
+        worksheet.write_string(0, 1, "Age")?;
+        for (row, (name, age)) in [("Alice", 30.0), ("Bob", 25.0)].iter().enumerate() {
+            worksheet.write_string(row as u32 + 1, 0, *name)?;
+            worksheet.write_number(row as u32 + 1, 1, *age)?;
+        }
+        workbook.add_worksheet().set_name("Empty")?;
 
-        // Header row (row 0)
-        worksheet.write_string(0, 0, "Header1")?;
-        worksheet.write_string(0, 1, "Header2")?;
-        worksheet.write_string(0, 2, "Header3")?;
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a Name/Age table starting at B2 whose fourth sheet row is hidden
+    pub fn generate_offset_table() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_string(1, 1, "Name")?;
+        worksheet.write_string(1, 2, "Age")?;
+        for (row, name, age) in [(2, "Alice", 30.0), (3, "Hidden", 99.0), (4, "Bob", 25.0)] {
+            worksheet.write_string(row, 1, name)?;
+            worksheet.write_number(row, 2, age)?;
+        }
+        worksheet.set_row_hidden(3)?;
 
-        // Visible data row (row 1)
-        worksheet.write_string(1, 0, "VisibleData1")?;
-        worksheet.write_string(1, 1, "VisibleData2")?;
-        worksheet.write_string(1, 2, "VisibleData3")?;
+        workbook.save_to_buffer()
+    }
 
-        // Hidden row (row 2) - will be hidden
-        worksheet.write_string(2, 0, "HiddenData")?;
-        worksheet.write_string(2, 1, "HiddenData2")?;
-        worksheet.write_string(2, 2, "HiddenData3")?;
+    /// Generate a workbook whose sheet names differ from the default `Sheet1`, `Sheet2`, ...
+    /// (the second sheet has a hidden row, the first a hyperlink)
+    pub fn generate_renamed_sheets() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
 
-        // Visible data row (row 3)
-        worksheet.write_string(3, 0, "VisibleData4")?;
-        worksheet.write_string(3, 1, "VisibleData5")?;
-        worksheet.write_string(3, 2, "VisibleData6")?;
+        let worksheet = workbook.add_worksheet().set_name("Links")?;
+        worksheet.write_string(0, 0, "Site")?;
+        worksheet.write_url_with_text(1, 0, "https://docs.rs", "docs")?;
 
-        // Hide row 2 (0-indexed, so row 3 in Excel)
+        let worksheet = workbook.add_worksheet().set_name("Budget")?;
+        worksheet.write_string(0, 0, "Item")?;
+        worksheet.write_string(1, 0, "Visible")?;
+        worksheet.write_string(2, 0, "Secret")?;
         worksheet.set_row_hidden(2)?;
 
-        // Hide column B (index 1)
-        worksheet.set_column_hidden(1)?;
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a table whose group label is written only on the first row of each group
+    pub fn generate_grouped_labels() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+
+        worksheet.write_string(0, 0, "Region")?;
+        worksheet.write_string(0, 1, "City")?;
+        worksheet.write_string(1, 0, "East")?;
+        worksheet.merge_range(3, 0, 4, 0, "West", &Format::new())?;
+        for (row, city) in [
+            (1, "Boston"),
+            (2, "New York"),
+            (3, "Seattle"),
+            (4, "Portland"),
+        ] {
+            worksheet.write_string(row, 1, city)?;
+        }
+
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a workbook with `count` sheets (`S000`, `S001`, ...) whose sizes decrease,
+    /// so that later sheets finish converting before earlier ones
+    pub fn generate_many_sheets(count: u32) -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+
+        for sheet in 0..count {
+            let worksheet = workbook.add_worksheet();
+            worksheet.set_name(format!("S{:03}", sheet))?;
+            for row in 0..(count - sheet) * 2 {
+                worksheet.write_string(row, 0, format!("S{:03}R{}", sheet, row))?;
+                worksheet.write_number(row, 1, f64::from(row))?;
+            }
+        }
 
-        Ok(workbook.save_to_buffer()?)
+        workbook.save_to_buffer()
     }
 
-    /// Generate a table with hyperlinks
-    pub fn generate_hyperlinks() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a workbook with an order sheet and a sheet without the order columns
+    pub fn generate_order_lines() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
+        worksheet.set_name("Orders")?;
+        for (col, header) in ["first", "last", "Amount", "Tax Rate"].iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header)?;
+        }
+        worksheet.write_string(1, 0, "Ada")?;
+        worksheet.write_string(1, 1, "Lovelace")?;
+        worksheet.write_number(1, 2, 120)?;
+        worksheet.write_number(1, 3, 0.1)?;
+        worksheet.write_string(2, 0, "Alan")?;
+        worksheet.write_string(2, 1, "Turing")?;
+        worksheet.write_string(2, 2, "TBD")?;
+        worksheet.write_number(2, 3, 0.08)?;
 
-        // Header row
-        worksheet.write_string(0, 0, "Link")?;
-        worksheet.write_string(0, 1, "Description")?;
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name("Memo")?;
+        worksheet.write_string(0, 0, "note")?;
+        worksheet.write_string(1, 0, "no orders")?;
+        workbook.save_to_buffer()
+    }
 
-        // Hyperlink cell with text
-        worksheet.write_url(1, 0, "https://example.com")?;
-        worksheet.write_string(1, 1, "Example website")?;
+    /// Generate a legacy .xls (BIFF8) workbook (rust_xlsxwriter cannot write this format)
+    ///
+    /// One sheet "Legacy" with a header row, two dates, and "Fruit" merged over A2:A3.
+    pub fn generate_legacy_xls() -> Vec<u8> {
+        fn record(stream: &mut Vec<u8>, typ: u16, data: &[u8]) {
+            stream.extend_from_slice(&typ.to_le_bytes());
+            stream.extend_from_slice(&(data.len() as u16).to_le_bytes());
+            stream.extend_from_slice(data);
+        }
+        fn bof(stream: &mut Vec<u8>, dt: u16) {
+            let mut data = vec![0u8; 16];
+            data[..2].copy_from_slice(&0x0600u16.to_le_bytes());
+            data[2..4].copy_from_slice(&dt.to_le_bytes());
+            record(stream, 0x0809, &data);
+        }
+        fn cell(row: u16, col: u16, xf: u16) -> Vec<u8> {
+            [row, col, xf]
+                .iter()
+                .flat_map(|v| v.to_le_bytes())
+                .collect()
+        }
+        fn label(stream: &mut Vec<u8>, row: u16, col: u16, text: &str) {
+            let mut data = cell(row, col, 0);
+            data.extend_from_slice(&(text.len() as u16).to_le_bytes());
+            data.push(0); // 1バイト文字
+            data.extend_from_slice(text.as_bytes());
+            record(stream, 0x0204, &data);
+        }
+        fn number(stream: &mut Vec<u8>, row: u16, col: u16, xf: u16, value: f64) {
+            let mut data = cell(row, col, xf);
+            data.extend_from_slice(&value.to_le_bytes());
+            record(stream, 0x0203, &data);
+        }
 
-        // Hyperlink cell with custom text
-        worksheet.write_url_with_text(2, 0, "https://rust-lang.org", "Rust")?;
-        worksheet.write_string(2, 1, "Rust programming language")?;
+        // Workbookストリーム: グローバル（XF 0: 標準、XF 1: 日付）とシート
+        let mut globals = Vec::new();
+        bof(&mut globals, 0x0005);
+        for format_id in [0u16, 14] {
+            let mut data = vec![0u8; 20];
+            data[2..4].copy_from_slice(&format_id.to_le_bytes());
+            record(&mut globals, 0x00E0, &data);
+        }
+        let boundsheet_at = globals.len();
+        let mut boundsheet = vec![0u8; 6];
+        boundsheet.extend_from_slice(&[6, 0]);
+        boundsheet.extend_from_slice(b"Legacy");
+        record(&mut globals, 0x0085, &boundsheet);
+        record(&mut globals, 0x000A, &[]);
+
+        let mut sheet = Vec::new();
+        bof(&mut sheet, 0x0010);
+        for (col, header) in ["Item", "Date", "Qty"].into_iter().enumerate() {
+            label(&mut sheet, 0, col as u16, header);
+        }
+        label(&mut sheet, 1, 0, "Fruit");
+        number(&mut sheet, 1, 1, 1, 45292.0);
+        number(&mut sheet, 1, 2, 0, 3.0);
+        number(&mut sheet, 2, 1, 1, 45293.0);
+        number(&mut sheet, 2, 2, 0, 7.0);
+        let merged: Vec<u8> = [1u16, 1, 2, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        record(&mut sheet, 0x00E5, &merged);
+        record(&mut sheet, 0x000A, &[]);
+
+        // BoundSheet8のシートの開始位置を設定
+        let sheet_pos = globals.len() as u32;
+        globals[boundsheet_at + 4..boundsheet_at + 8].copy_from_slice(&sheet_pos.to_le_bytes());
+        let mut stream = globals;
+        stream.extend_from_slice(&sheet);
+        let stream_len = stream.len().max(4096); // ミニストリームを使用しない大きさにする
+        stream.resize(stream_len.div_ceil(512) * 512, 0);
+
+        // 複合ドキュメント（セクター0: FAT、1..=n: Workbook、n+1: ディレクトリ）
+        const END_OF_CHAIN: u32 = 0xFFFF_FFFE;
+        const FREE: u32 = 0xFFFF_FFFF;
+        let sectors = (stream.len() / 512) as u32;
+        let mut header = vec![0u8; 512];
+        header[..8].copy_from_slice(&[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1]);
+        for (offset, value) in [(24, 0x003Eu16), (26, 3), (28, 0xFFFE), (30, 9), (32, 6)] {
+            header[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+        }
+        for (offset, value) in [
+            (44, 1u32),
+            (48, sectors + 1),
+            (56, 4096),
+            (60, END_OF_CHAIN),
+            (68, END_OF_CHAIN),
+        ] {
+            header[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        for (idx, chunk) in header[76..].chunks_mut(4).enumerate() {
+            chunk.copy_from_slice(&if idx == 0 { 0 } else { FREE }.to_le_bytes());
+        }
 
-        // Another hyperlink
-        worksheet.write_url(3, 0, "https://github.com")?;
-        worksheet.write_string(3, 1, "GitHub")?;
+        let mut fat = vec![0xFFFF_FFFDu32];
+        fat.extend(2..=sectors);
+        fat.push(END_OF_CHAIN);
+        fat.push(END_OF_CHAIN);
+        fat.resize(128, FREE);
+
+        let entry = |name: &str, typ: u8, start: u32, len: u32| {
+            let mut entry = vec![0u8; 128];
+            let utf16: Vec<u8> = name.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+            entry[..utf16.len()].copy_from_slice(&utf16);
+            entry[64..66].copy_from_slice(&(utf16.len() as u16 + 2).to_le_bytes());
+            entry[66] = typ;
+            entry[116..120].copy_from_slice(&start.to_le_bytes());
+            entry[120..124].copy_from_slice(&len.to_le_bytes());
+            entry
+        };
+        let mut directory = entry("Root Entry", 5, END_OF_CHAIN, 0);
+        directory.extend(entry("Workbook", 2, 1, stream.len() as u32));
+        directory.resize(512, 0);
+
+        let mut buffer = header;
+        buffer.extend(fat.iter().flat_map(|v| v.to_le_bytes()));
+        buffer.extend_from_slice(&stream);
+        buffer.extend_from_slice(&directory);
+        buffer
+    }
 
-        Ok(workbook.save_to_buffer()?)
+    /// Generate a table with a zero-height row and a zero-width column (not marked hidden)
+    pub fn generate_zero_size() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        worksheet.write_row(0, 0, ["Name", "Price", "Cost"])?;
+        worksheet.write_row(1, 0, ["Apple", "100", "60"])?;
+        worksheet.write_row(2, 0, ["Draft", "0", "0"])?;
+        worksheet.write_row(3, 0, ["Pear", "120", "80"])?;
+        worksheet.set_row_height(2, 33)?;
+        worksheet.set_column_width(2, 20)?;
+        let buffer = workbook.save_to_buffer()?;
+
+        // rust_xlsxwriterは高さ・幅が0の行・列を非表示（hidden="1"）として書き込むため、
+        // 高さ・幅のみを0に置き換える
+        Ok(rewrite_part(
+            buffer,
+            "xl/worksheets/sheet1.xml",
+            |content| {
+                let xml = String::from_utf8(content).unwrap();
+                let (start, end) = (xml.find("<cols>").unwrap(), xml.find("</cols>").unwrap());
+                format!(
+                    "{}<cols><col min=\"3\" max=\"3\" width=\"0\" customWidth=\"1\"/>{}",
+                    &xml[..start],
+                    &xml[end..]
+                )
+                .replace(r#"ht="33""#, r#"ht="0""#)
+                .into_bytes()
+            },
+        ))
     }
 
-    /// Generate a table with rich text formatting
-    /// Note: rust_xlsxwriter doesn't directly support rich text in cells,
-    /// but we can create cells with different formats to test basic functionality.
-    /// For true rich text (mixed formatting in one cell), we'd need to manipulate XML directly.
-    pub fn generate_rich_text() -> Result<Vec<u8>, XlsxError> {
+    /// Generate a table with cell notes (one of them on a hidden row)
+    pub fn generate_comments() -> Result<Vec<u8>, XlsxError> {
         let mut workbook = Workbook::new();
         let worksheet = workbook.add_worksheet();
 
-        // Create formats for bold and italic
-        let bold_format = Format::new().set_bold();
-        let italic_format = Format::new().set_italic();
-        let bold_italic_format = Format::new().set_bold().set_italic();
+        worksheet.write_string(0, 0, "Item")?;
+        worksheet.write_string(0, 1, "Amount")?;
+        worksheet.write_string(1, 0, "Rent")?;
+        worksheet.write_number(1, 1, 1200)?;
+        worksheet.write_string(2, 0, "Power")?;
+        worksheet.write_number(2, 1, 80)?;
+        worksheet.write_string(3, 0, "Draft")?;
+        worksheet.write_number(3, 1, 5)?;
+        worksheet.set_row_hidden(3)?;
+
+        worksheet.set_default_note_author("Alice");
+        worksheet.insert_note(1, 1, &Note::new("Includes tax"))?;
+        worksheet.insert_note(
+            2,
+            0,
+            &Note::new("Estimate | to be confirmed").set_author("Bob"),
+        )?;
+        worksheet.insert_note(3, 1, &Note::new("Hidden note"))?;
+
+        workbook.save_to_buffer()
+    }
+
+    /// Generate a task list with data validations (dropdown lists and a number rule)
+    pub fn generate_data_validations() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("Tasks")?;
+
+        for (col, header) in ["Task", "Status", "Owner", "Hours"].iter().enumerate() {
+            worksheet.write_string(0, col as u16, *header)?;
+        }
+        worksheet.write_string(1, 0, "Audit")?;
+        worksheet.write_string(1, 1, "Open")?;
+        worksheet.write_string(1, 2, "Bob")?;
+        worksheet.write_number(1, 3, 4)?;
+
+        let status = DataValidation::new().allow_list_strings(&["Open", "Closed", "Pending"])?;
+        worksheet.add_data_validation(1, 1, 20, 1, &status)?;
+        let owner = DataValidation::new().allow_list_formula(Formula::new("=Lists!$A$1:$A$5"));
+        worksheet.add_data_validation(1, 2, 20, 2, &owner)?;
+        let hours = DataValidation::new().allow_whole_number(DataValidationRule::GreaterThan(0));
+        worksheet.add_data_validation(1, 3, 20, 3, &hours)?;
+
+        // リストの値の一覧（非表示のシート）
+        let lists = workbook.add_worksheet().set_name("Lists")?;
+        lists.write_string(0, 0, "Alice")?;
+        lists.write_string(1, 0, "Bob|Team")?;
+        lists.write_string(2, 0, "Carol")?;
+        lists.set_hidden(true);
+
+        workbook.save_to_buffer()
+    }
 
-        // Header row
-        worksheet.write_string(0, 0, "Text")?;
-        worksheet.write_string(0, 1, "Format")?;
+    /// Generate a table with cell-level font styles (bold headers, italic and struck-out cells)
+    pub fn generate_font_styles() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let bold = Format::new().set_bold();
+        let italic = Format::new().set_italic();
+        let strike = Format::new().set_font_strikethrough();
+
+        worksheet.write_string_with_format(0, 0, "Item", &bold)?;
+        worksheet.write_string_with_format(0, 1, "Amount", &bold)?;
+        worksheet.write_string_with_format(1, 0, "Rent", &strike)?;
+        worksheet.write_number(1, 1, 1200)?;
+        worksheet.write_string_with_format(2, 0, "Total", &italic)?;
+        worksheet.write_number_with_format(2, 1, 1200, &bold)?;
+
+        workbook.save_to_buffer()
+    }
 
-        // Plain text
-        worksheet.write_string(1, 0, "Plain text")?;
-        worksheet.write_string(1, 1, "None")?;
+    /// Generate a product catalog with pictures placed over cells ("Place over Cells")
+    /// (one inside the used range with alt text, one to the right of it)
+    pub fn generate_drawing_images() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("Catalog")?;
+        worksheet.write_string(0, 0, "Product")?;
+        worksheet.write_string(0, 1, "Photo")?;
+        worksheet.write_string(1, 0, "Chair")?;
+        worksheet.write_string(2, 0, "Desk")?;
+        let photo = Image::new_from_buffer(PNG)?.set_alt_text("Front view");
+        worksheet.insert_image(1, 1, &photo)?;
+        worksheet.insert_image(2, 3, &Image::new_from_buffer(PNG)?)?;
+
+        workbook.save_to_buffer()
+    }
 
-        // Bold text
-        worksheet.write_string_with_format(2, 0, "Bold text", &bold_format)?;
-        worksheet.write_string(2, 1, "Bold")?;
+    /// Generate monthly revenue with a column chart of two series placed next to the table
+    pub fn generate_chart() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("Revenue")?;
+        worksheet.write_row(0, 0, ["Month", "2023", "2024"])?;
+        worksheet.write_row(1, 0, ["Jan"])?;
+        worksheet.write_row(1, 1, [120, 150])?;
+        worksheet.write_row(2, 0, ["Dec"])?;
+        worksheet.write_row(2, 1, [130, 170])?;
+
+        let mut chart = Chart::new(ChartType::Column);
+        chart.title().set_name("Revenue by Quarter");
+        for col in [1, 2] {
+            chart
+                .add_series()
+                .set_name(("Revenue", 0, col))
+                .set_categories(("Revenue", 1, 0, 2, 0))
+                .set_values(("Revenue", 1, col, 2, col));
+        }
+        worksheet.insert_chart(4, 4, &chart)?;
 
-        // Italic text
-        worksheet.write_string_with_format(3, 0, "Italic text", &italic_format)?;
-        worksheet.write_string(3, 1, "Italic")?;
+        workbook.save_to_buffer()
+    }
 
-        // Bold and italic text
-        worksheet.write_string_with_format(4, 0, "Bold and italic", &bold_italic_format)?;
-        worksheet.write_string(4, 1, "Bold + Italic")?;
+    /// Generate a pivot table summarizing sales by region, with its cached source records
+    /// (rust_xlsxwriter cannot write pivot tables, so the pivot parts are appended)
+    pub fn generate_pivot_table() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet().set_name("Summary")?;
+        worksheet.write_string(2, 0, "Row Labels")?;
+        worksheet.write_string(2, 1, "Sum of Amount")?;
+        worksheet.write_string(3, 0, "East")?;
+        worksheet.write_number(3, 1, 150)?;
+        worksheet.write_string(4, 0, "West")?;
+        worksheet.write_number(4, 1, 80)?;
+        let buffer = workbook.save_to_buffer()?;
+
+        Ok(add_parts(
+            buffer,
+            &[
+                (
+                    "xl/worksheets/_rels/sheet1.xml.rels",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable" Target="../pivotTables/pivotTable1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/pivotTables/pivotTable1.xml",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotTableDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="SalesPivot" cacheId="1" dataCaption="Values"><location ref="A3:B5" firstHeaderRow="1" firstDataRow="1" firstDataCol="1"/></pivotTableDefinition>"#,
+                ),
+                (
+                    "xl/pivotTables/_rels/pivotTable1.xml.rels",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheDefinition" Target="../pivotCache/pivotCacheDefinition1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/pivotCache/pivotCacheDefinition1.xml",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1" recordCount="3"><cacheSource type="worksheet"><worksheetSource ref="A1:B4" sheet="Sales"/></cacheSource><cacheFields count="2"><cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="East"/><s v="West"/></sharedItems></cacheField><cacheField name="Amount" numFmtId="0"><sharedItems containsNumber="1" minValue="30" maxValue="120"/></cacheField></cacheFields></pivotCacheDefinition>"#,
+                ),
+                (
+                    "xl/pivotCache/_rels/pivotCacheDefinition1.xml.rels",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheRecords" Target="pivotCacheRecords1.xml"/></Relationships>"#,
+                ),
+                (
+                    "xl/pivotCache/pivotCacheRecords1.xml",
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="3"><r><x v="0"/><n v="120"/></r><r><x v="1"/><n v="80"/></r><r><x v="0"/><n v="30"/></r></pivotCacheRecords>"#,
+                ),
+            ],
+        ))
+    }
 
-        Ok(workbook.save_to_buffer()?)
+    /// Generate a table with filled cells (solid RGB fills)
+    pub fn generate_fill_colors() -> Result<Vec<u8>, XlsxError> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        let yellow = Format::new().set_background_color(Color::RGB(0xFFFF00));
+        let header = Format::new()
+            .set_bold()
+            .set_background_color(Color::RGB(0xDDEBF7));
+
+        worksheet.write_string_with_format(0, 0, "Task", &header)?;
+        worksheet.write_string_with_format(0, 1, "Status", &header)?;
+        worksheet.write_string(1, 0, "Report")?;
+        worksheet.write_string_with_format(1, 1, "Overdue", &yellow)?;
+        worksheet.write_string(2, 0, "Review")?;
+        worksheet.write_string(2, 1, "Done")?;
+
+        workbook.save_to_buffer()
     }
 }
 
@@ -202,7 +993,7 @@ mod fixtures {
 #[test]
 fn test_simple_table_conversion() {
     let converter = ConverterBuilder::new().build().unwrap();
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -218,7 +1009,7 @@ fn test_simple_table_conversion() {
 #[test]
 fn test_multiple_sheets() {
     let converter = ConverterBuilder::new().build().unwrap();
-    let excel_data = fixtures::generate_multi_sheets().unwrap();
+    let excel_data = fixtures::multi_sheets().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -239,7 +1030,7 @@ fn test_merged_cells_data_duplication() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_merged_cells().unwrap();
+    let excel_data = fixtures::merged_cells().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -258,7 +1049,7 @@ fn test_merged_cells_html_fallback() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_merged_cells().unwrap();
+    let excel_data = fixtures::merged_cells().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -329,7 +1120,7 @@ fn test_sheet_selection_by_index() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_multi_sheets().unwrap();
+    let excel_data = fixtures::multi_sheets().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -347,7 +1138,7 @@ fn test_sheet_selection_by_name() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_multi_sheets().unwrap();
+    let excel_data = fixtures::multi_sheets().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -365,7 +1156,7 @@ fn test_range_restriction() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_large_table().unwrap();
+    let excel_data = fixtures::large_table(10, 10).unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -406,19 +1197,18 @@ fn test_nonexistent_sheet() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
     let result = converter.convert_to_string(input);
 
     assert!(result.is_err());
-    match result.unwrap_err() {
-        xlsxzero::XlsxToMdError::Config(msg) => {
-            assert!(
-                msg.contains("not found") || msg.contains("sheet"),
-                "Expected error message about sheet not found"
-            );
+    let error = result.unwrap_err();
+    assert_eq!(error.sheet(), Some("NonExistent"));
+    match error {
+        xlsxzero::XlsxToMdError::SheetNotFound { sheet } => {
+            assert_eq!(sheet, "NonExistent");
         }
-        e => panic!("Expected Config error, got {:?}", e),
+        e => panic!("Expected SheetNotFound error, got {:?}", e),
     }
 }
 
@@ -430,19 +1220,17 @@ fn test_sheet_index_out_of_range() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
     let result = converter.convert_to_string(input);
 
     assert!(result.is_err());
     match result.unwrap_err() {
-        xlsxzero::XlsxToMdError::Config(msg) => {
-            assert!(
-                msg.contains("out of range") || msg.contains("index") || msg.contains("sheet"),
-                "Expected error message about index out of range"
-            );
+        xlsxzero::XlsxToMdError::SheetIndexOutOfRange { index, count } => {
+            assert_eq!(index, 999);
+            assert_eq!(count, 1);
         }
-        e => panic!("Expected Config error, got {:?}", e),
+        e => panic!("Expected SheetIndexOutOfRange error, got {:?}", e),
     }
 }
 
@@ -468,7 +1256,7 @@ fn test_hidden_elements_exclusion() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_hidden_elements().unwrap();
+    let excel_data = fixtures::hidden_elements().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -498,7 +1286,7 @@ fn test_hidden_elements_inclusion() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_hidden_elements().unwrap();
+    let excel_data = fixtures::hidden_elements().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -523,7 +1311,7 @@ fn test_hidden_elements_inclusion() {
 fn test_hyperlink_conversion() {
     let converter = ConverterBuilder::new().build().unwrap();
 
-    let excel_data = fixtures::generate_hyperlinks().unwrap();
+    let excel_data = fixtures::hyperlinks().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -554,7 +1342,7 @@ fn test_hyperlink_conversion() {
 #[test]
 fn test_rich_text_conversion() {
     let converter = ConverterBuilder::new().build().unwrap();
-    let excel_data = fixtures::generate_rich_text().unwrap();
+    let excel_data = fixtures::rich_text().unwrap();
     let input = Cursor::new(excel_data);
 
     let markdown = converter.convert_to_string(input).unwrap();
@@ -587,15 +1375,27 @@ fn test_json_output_format() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
 
     let output = converter.convert_to_string(input).unwrap();
 
     // JSON output should contain JSON structure
-    assert!(output.contains("\"rows\""), "Expected JSON structure. Got: {}", output);
-    assert!(output.contains("Header1") || output.contains("Header2"), "Expected header data. Got: {}", output);
-    assert!(output.contains("Data1") || output.contains("Data2"), "Expected data. Got: {}", output);
+    assert!(
+        output.contains("\"rows\""),
+        "Expected JSON structure. Got: {}",
+        output
+    );
+    assert!(
+        output.contains("Header1") || output.contains("Header2"),
+        "Expected header data. Got: {}",
+        output
+    );
+    assert!(
+        output.contains("Data1") || output.contains("Data2"),
+        "Expected data. Got: {}",
+        output
+    );
 }
 
 // TC-I-016: CSV Output Format
@@ -606,16 +1406,28 @@ fn test_csv_output_format() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
 
     let output = converter.convert_to_string(input).unwrap();
 
     // CSV output should contain comma-separated values
-    assert!(output.contains("Header1") || output.contains("Header2"), "Expected header data. Got: {}", output);
-    assert!(output.contains("Data1") || output.contains("Data2"), "Expected data. Got: {}", output);
+    assert!(
+        output.contains("Header1") || output.contains("Header2"),
+        "Expected header data. Got: {}",
+        output
+    );
+    assert!(
+        output.contains("Data1") || output.contains("Data2"),
+        "Expected data. Got: {}",
+        output
+    );
     // CSV should have commas (unless escaped)
-    assert!(output.contains(",") || output.lines().count() >= 2, "Expected CSV format. Got: {}", output);
+    assert!(
+        output.contains(",") || output.lines().count() >= 2,
+        "Expected CSV format. Got: {}",
+        output
+    );
 }
 
 // TC-I-017: HTML Output Format
@@ -626,15 +1438,27 @@ fn test_html_output_format() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_simple_table().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
     let input = Cursor::new(excel_data);
 
     let output = converter.convert_to_string(input).unwrap();
 
     // HTML output should contain HTML table tags
-    assert!(output.contains("<table>"), "Expected HTML table tag. Got: {}", output);
-    assert!(output.contains("</table>"), "Expected HTML closing table tag. Got: {}", output);
-    assert!(output.contains("<td>") || output.contains("<th>"), "Expected HTML cell tags. Got: {}", output);
+    assert!(
+        output.contains("<table>"),
+        "Expected HTML table tag. Got: {}",
+        output
+    );
+    assert!(
+        output.contains("</table>"),
+        "Expected HTML closing table tag. Got: {}",
+        output
+    );
+    assert!(
+        output.contains("<td>") || output.contains("<th>"),
+        "Expected HTML cell tags. Got: {}",
+        output
+    );
 }
 
 // TC-I-018: HTML Output Format with Merged Cells
@@ -646,16 +1470,4281 @@ fn test_html_output_format_with_merged_cells() {
         .build()
         .unwrap();
 
-    let excel_data = fixtures::generate_merged_cells().unwrap();
+    let excel_data = fixtures::merged_cells().unwrap();
     let input = Cursor::new(excel_data);
 
     let output = converter.convert_to_string(input).unwrap();
 
     // HTML output with merged cells should contain rowspan or colspan
-    assert!(output.contains("<table>"), "Expected HTML table tag. Got: {}", output);
+    assert!(
+        output.contains("<table>"),
+        "Expected HTML table tag. Got: {}",
+        output
+    );
     assert!(
         output.contains("rowspan") || output.contains("colspan") || output.contains("Header"),
         "Expected merged cell attributes or header content. Got: {}",
         output
     );
 }
+
+// TC-I-019: Raw numeric values in machine-readable formats
+#[test]
+fn test_json_output_raw_numbers() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_numeric_table().unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["rows"][1]["B"], serde_json::json!(1234.56));
+    assert_eq!(json["rows"][2]["B"], serde_json::json!(42));
+    assert_eq!(json["rows"][1]["A"], serde_json::json!("Sales"));
+}
+
+// TC-I-020: Per-format number output override
+#[test]
+fn test_number_output_override() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_number_output(OutputFormat::Json, NumberOutput::Formatted)
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_numeric_table().unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(
+        json["rows"][1]["B"].is_string(),
+        "Expected formatted string. Got: {}",
+        output
+    );
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_numeric_table().unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    assert!(
+        output.contains("Sales,1234.56"),
+        "Expected raw number in CSV. Got: {}",
+        output
+    );
+}
+
+// TC-I-021: Typed JSON cells with raw and formatted values
+#[test]
+fn test_json_typed_cells() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_numeric_table().unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let amount = &json["rows"][1]["B"];
+    assert_eq!(amount["type"], "number");
+    assert_eq!(amount["raw"], serde_json::json!(1234.56));
+    assert!(
+        amount["formatted"].is_string(),
+        "Expected formatted string. Got: {}",
+        output
+    );
+
+    let item = &json["rows"][1]["A"];
+    assert_eq!(item["type"], "string");
+    assert_eq!(item["raw"], "Sales");
+    assert_eq!(item["formatted"], "Sales");
+}
+
+// TC-I-022: Sheet metadata with script detection
+#[test]
+fn test_include_metadata_script_detection() {
+    let mut workbook = Workbook::new();
+    let sheet1 = workbook.add_worksheet();
+    sheet1.set_name("Japanese").unwrap();
+    sheet1.write_string(0, 0, "市区町村").unwrap();
+    sheet1.write_string(0, 1, "店舗名").unwrap();
+    let sheet2 = workbook.add_worksheet();
+    sheet2.set_name("English").unwrap();
+    sheet2.write_string(0, 0, "Region").unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    assert!(
+        markdown.starts_with("---\nsheets:\n  - name: \"Japanese\"\n    script: cjk\n"),
+        "Expected front matter. Got: {}",
+        markdown
+    );
+    assert!(
+        markdown.contains("  - name: \"English\"\n    script: latin\n"),
+        "Expected front matter for second sheet. Got: {}",
+        markdown
+    );
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_sheet_selector(SheetSelector::Index(0))
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["metadata"]["sheet_name"], "Japanese");
+    assert_eq!(json["metadata"]["script"], "cjk");
+    assert!(json["metadata"]["content_hash"]
+        .as_str()
+        .unwrap()
+        .starts_with("xxh3:"));
+}
+
+// TC-I-023: Content hash is stable across identical sheets
+#[test]
+fn test_include_metadata_content_hash() {
+    let mut workbook = Workbook::new();
+    for name in ["Original", "Copy", "Changed"] {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(name).unwrap();
+        sheet.write_string(0, 0, "Region").unwrap();
+        sheet.write_number(1, 0, 100).unwrap();
+    }
+    workbook
+        .worksheet_from_name("Changed")
+        .unwrap()
+        .write_number(1, 0, 101)
+        .unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let hashes: Vec<&str> = markdown
+        .lines()
+        .filter_map(|line| line.strip_prefix("    content_hash: "))
+        .collect();
+    assert_eq!(hashes.len(), 3, "Expected 3 hashes. Got: {}", markdown);
+    assert!(hashes[0].starts_with("\"xxh3:"));
+    assert_eq!(hashes[0], hashes[1]);
+    assert_ne!(hashes[0], hashes[2]);
+}
+
+// TC-I-024: Conversion report with per-phase timings
+#[test]
+fn test_convert_with_report() {
+    let converter = ConverterBuilder::new().build().unwrap();
+
+    let excel_data = fixtures::multi_sheets().unwrap();
+    let input_size = excel_data.len() as u64;
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data), &mut output)
+        .unwrap();
+
+    assert_eq!(report.input_size, input_size);
+    assert_eq!(report.output_size, output.len() as u64);
+
+    let names: Vec<&str> = report.sheets.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Sheet1", "Sheet2", "Sheet3"]);
+    for sheet in &report.sheets {
+        assert_eq!(sheet.cells, 1);
+        assert_eq!((sheet.rows, sheet.cols), (1, 1));
+        assert!(sheet.output_size > 0);
+        assert!(sheet.memory_estimate > 0);
+    }
+
+    assert!(report.peak_memory_estimate >= report.input_size + report.output_size);
+    assert!(report.timings.total >= report.timings.sheets);
+    assert!(report.timings.total >= report.timings.metadata);
+}
+
+// TC-I-025: Date, time and datetime cells in typed JSON
+#[test]
+fn test_json_typed_date_time_cells() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_date_time_table().unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let row = &json["rows"][1];
+
+    assert_eq!(row["A"]["type"], "date", "Got: {}", output);
+    assert_eq!(row["A"]["raw"], serde_json::json!(45658));
+    assert_eq!(row["A"]["formatted"], "2025-01-02");
+
+    assert_eq!(row["B"]["type"], "time", "Got: {}", output);
+    assert_eq!(row["B"]["raw"], serde_json::json!(0.5));
+    assert_eq!(row["B"]["formatted"], "12:00:00");
+
+    assert_eq!(row["C"]["type"], "datetime", "Got: {}", output);
+    assert_eq!(row["C"]["raw"], serde_json::json!(45658.75));
+    assert_eq!(row["C"]["formatted"], "2025-01-02T18:00:00");
+
+    // 経過時間書式は時刻として扱い、24時間を超える値も保持する
+    assert_eq!(row["D"]["type"], "time", "Got: {}", output);
+    assert_eq!(row["D"]["raw"], serde_json::json!(1.5));
+    assert_eq!(row["D"]["formatted"], "36:00:00");
+}
+
+// TC-I-026: Formula cells without cached values
+#[test]
+fn test_null_formula_policy() {
+    // Default: indistinguishable from empty cells
+    let converter = ConverterBuilder::new().build().unwrap();
+    let excel_data = fixtures::generate_uncalculated_formulas().unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(!markdown.contains("#NOVALUE"));
+
+    // Marker: emitted for every formula cell without a value,
+    // including those outside the used value range
+    let converter = ConverterBuilder::new()
+        .with_null_formula_policy(NullFormulaPolicy::Marker("#NOVALUE".to_string()))
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert_eq!(markdown.matches("#NOVALUE").count(), 2, "Got: {}", markdown);
+    assert!(markdown.contains("10"));
+
+    // Flag: typed JSON carries formula_no_value
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .with_null_formula_policy(NullFormulaPolicy::Flag)
+        .build()
+        .unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        json["rows"][1]["B"]["formula_no_value"], true,
+        "Got: {}",
+        output
+    );
+    assert_eq!(json["rows"][1]["B"]["formatted"], "");
+    assert_eq!(
+        json["rows"][2]["B"]["formula_no_value"], true,
+        "Got: {}",
+        output
+    );
+    assert!(json["rows"][1]["A"].get("formula_no_value").is_none());
+
+    // Formula mode: formulas are aligned with their cells
+    let converter = ConverterBuilder::new()
+        .with_formula_mode(FormulaMode::Formula)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let second_row = markdown.lines().find(|line| line.contains("10")).unwrap();
+    assert!(second_row.contains("A2*2"), "Got: {}", markdown);
+    assert!(markdown.contains("SUM(B2)"), "Got: {}", markdown);
+}
+
+// TC-I-027: Used-range bloat is reported and optionally shrunk
+#[test]
+fn test_used_range_bloat() {
+    let excel_data = fixtures::generate_bloated_used_range().unwrap();
+
+    // Detection only (default)
+    let converter = ConverterBuilder::new().build().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].kind, WarningKind::UsedRangeBloat);
+    assert_eq!(report.warnings[0].sheet.as_deref(), Some("Sheet1"));
+    assert_eq!(report.sheets[0].rows, 200_000);
+
+    // Auto-shrink to the non-empty bounding box
+    let converter = ConverterBuilder::new()
+        .auto_shrink_used_range(true)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data), &mut output)
+        .unwrap();
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].message.contains("shrunk"));
+    assert_eq!(report.sheets[0].rows, 2);
+    assert_eq!(report.sheets[0].cols, 2);
+
+    let markdown = String::from_utf8(output).unwrap();
+    assert!(markdown.contains("Alpha"));
+    assert_eq!(markdown.lines().filter(|l| l.starts_with('|')).count(), 3);
+}
+
+// TC-I-028: Custom pipeline stages
+#[test]
+fn test_pipeline_stages() {
+    let converter = ConverterBuilder::new()
+        // Parse: unit conversion on raw values
+        .with_stage(
+            Stage::Parse,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                for cell in context.cells_mut() {
+                    if let CellValue::Number(n) = cell.value {
+                        cell.value = CellValue::Number(n * 100.0);
+                    }
+                }
+                Ok(())
+            },
+        )
+        // GridBuild: cell-level text transformation
+        .with_stage(
+            Stage::GridBuild,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                for cell in context.cells_mut() {
+                    if cell.row() == 0 {
+                        cell.text = cell.text.to_uppercase();
+                    }
+                }
+                Ok(())
+            },
+        )
+        // Render: append a trailer to each sheet
+        .with_stage(
+            Stage::Render,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                let name = context.sheet_name().to_string();
+                if let Some(output) = context.output_mut() {
+                    output.push_str(&format!("<!-- end of {} -->\n", name));
+                }
+                Ok(())
+            },
+        )
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::generate_numeric_table().unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    assert!(markdown.contains("ITEM"), "Got: {}", markdown);
+    assert!(markdown.contains("AMOUNT"), "Got: {}", markdown);
+    assert!(markdown.contains("4200"), "Got: {}", markdown);
+    assert!(
+        markdown.contains("<!-- end of Sheet1 -->"),
+        "Got: {}",
+        markdown
+    );
+}
+
+// TC-I-029: Errors from custom stages abort the conversion
+#[test]
+fn test_pipeline_stage_error() {
+    let converter = ConverterBuilder::new()
+        .with_stage(
+            Stage::Format,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                Err(XlsxToMdError::Validation {
+                    sheet: context.sheet_name().to_string(),
+                    message: "rejected by stage".to_string(),
+                })
+            },
+        )
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::simple_table().unwrap();
+    let result = converter.convert_to_string(Cursor::new(excel_data));
+    match result {
+        Err(XlsxToMdError::Validation { message, .. }) => assert_eq!(message, "rejected by stage"),
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+// TC-I-030: Batched text transformer keeps the table structure intact
+#[test]
+fn test_text_transformer() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_in_transformer = Arc::clone(&calls);
+    let converter = ConverterBuilder::new()
+        .with_text_transformer(
+            move |texts: &[&str]| -> Result<Vec<String>, XlsxToMdError> {
+                calls_in_transformer.fetch_add(1, Ordering::SeqCst);
+                // Receives plain cell texts, never Markdown
+                assert!(texts.iter().all(|t| !t.contains('|')));
+                Ok(texts
+                    .iter()
+                    .map(|t| format!("{} | translated\nline2", t))
+                    .collect())
+            },
+        )
+        .build()
+        .unwrap();
+
+    let excel_data = fixtures::simple_table().unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(
+        markdown.contains("Header1 \\| translated<br>line2"),
+        "Got: {}",
+        markdown
+    );
+    // Every table row still has exactly the original number of columns
+    for line in markdown.lines().filter(|l| l.starts_with('|')) {
+        let separators = line.matches('|').count() - line.matches("\\|").count();
+        assert_eq!(separators, 3, "Broken row: {}", line);
+    }
+}
+
+// TC-I-031: Per-sheet output format overrides with a per-sheet file sink
+#[test]
+fn test_sheet_format_override() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Markdown)
+        .with_sheet_override("Sheet2", OutputFormat::Csv)
+        .with_sheet_override("Sheet3", OutputFormat::Json)
+        .build()
+        .unwrap();
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    // One pass produces one file per sheet, each in its own format
+    let dir = tempfile::tempdir().unwrap();
+    let mut sink = DirectorySink::new(dir.path());
+    let report = converter
+        .convert_to_sink(Cursor::new(excel_data.clone()), &mut sink)
+        .unwrap();
+    assert_eq!(report.sheets.len(), 3);
+
+    let names: Vec<String> = sink
+        .written()
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, vec!["Sheet1.md", "Sheet2.csv", "Sheet3.json"]);
+
+    let markdown = std::fs::read_to_string(dir.path().join("Sheet1.md")).unwrap();
+    assert!(markdown.starts_with("| Sheet1_Data |"), "Got: {}", markdown);
+    let csv = std::fs::read_to_string(dir.path().join("Sheet2.csv")).unwrap();
+    assert_eq!(csv.trim(), "Sheet2_Data");
+    let json = std::fs::read_to_string(dir.path().join("Sheet3.json")).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["rows"][0]["A"], "Sheet3_Data");
+
+    let total: u64 = sink
+        .written()
+        .iter()
+        .map(|path| std::fs::metadata(path).unwrap().len())
+        .sum();
+    assert_eq!(report.output_size, total);
+
+    // The overrides also apply when converting to a single stream
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(output.contains("# Sheet1\n"));
+    assert!(output.contains("# Sheet: Sheet2\n"));
+    assert!(output.contains("\"rows\""));
+}
+
+// TC-I-032: Hyperlinks as a Markdown footnote section with stable numbering
+#[test]
+fn test_hyperlink_footnotes() {
+    let converter = ConverterBuilder::new()
+        .with_hyperlink_mode(HyperlinkMode::Footnote)
+        .build()
+        .unwrap();
+    let excel_data = fixtures::hyperlinks().unwrap();
+
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    // Cells keep only the display text and a footnote reference
+    assert!(markdown.contains("| Rust[^2] "), "Got: {}", markdown);
+    assert!(!markdown.contains("](https://"), "Got: {}", markdown);
+
+    // Footnotes are numbered in row-major order after the table
+    let section = markdown
+        .split("\n\n")
+        .last()
+        .expect("footnote section should follow the table");
+    assert_eq!(
+        section.trim_end(),
+        "[^1]: https://example.com\n[^2]: https://rust-lang.org\n[^3]: https://github.com"
+    );
+
+    // Deterministic output
+    let again = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert_eq!(markdown, again);
+
+    // Formats other than Markdown output the links as with Inline
+    let csv = ConverterBuilder::new()
+        .with_hyperlink_mode(HyperlinkMode::Footnote)
+        .with_output_format(OutputFormat::Csv)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(!csv.contains("[^"), "Got: {}", csv);
+}
+
+// TC-I-033: Version and configuration fingerprint in the output
+#[test]
+fn test_fingerprint_output() {
+    let excel_data = fixtures::simple_table().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .include_fingerprint(true)
+        .build()
+        .unwrap();
+    let fingerprint = converter.fingerprint();
+    assert!(
+        fingerprint.starts_with(&format!(
+            "xlsxzero {} config:xxh3:",
+            env!("CARGO_PKG_VERSION")
+        )),
+        "Got: {}",
+        fingerprint
+    );
+
+    // Markdown: HTML comment at the top of the document
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.starts_with(&format!("<!-- {} -->\n\n# Sheet1", fingerprint)),
+        "Got: {}",
+        markdown
+    );
+
+    // Not emitted unless requested
+    let plain = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(!plain.contains("xlsxzero"));
+
+    // Changing an option changes the fingerprint
+    let json_converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .include_fingerprint(true)
+        .build()
+        .unwrap();
+    assert_ne!(json_converter.fingerprint(), fingerprint);
+
+    // JSON: fingerprint object in the sheet output
+    let json = json_converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["fingerprint"]["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(
+        format!(
+            "xlsxzero {} config:{}",
+            env!("CARGO_PKG_VERSION"),
+            value["fingerprint"]["config_hash"].as_str().unwrap()
+        ),
+        json_converter.fingerprint()
+    );
+}
+
+// TC-I-034: Text-only hyperlinks skip hyperlink metadata entirely
+#[test]
+fn test_hyperlink_text_only() {
+    let converter = ConverterBuilder::new()
+        .with_hyperlink_mode(HyperlinkMode::TextOnly)
+        .build()
+        .unwrap();
+    let excel_data = fixtures::hyperlinks().unwrap();
+
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+
+    assert!(markdown.contains("| Rust "), "Got: {}", markdown);
+    assert!(!markdown.contains("](https://"), "Got: {}", markdown);
+    assert!(!markdown.contains("[^"), "Got: {}", markdown);
+}
+
+// TC-I-035: Chunked output concatenates to the same document as convert_to_string
+#[test]
+fn test_convert_to_chunks() {
+    let converter = ConverterBuilder::new()
+        .include_metadata(true)
+        .include_fingerprint(true)
+        .build()
+        .unwrap();
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    let chunks: Vec<String> = converter
+        .convert_to_chunks(Cursor::new(excel_data.clone()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    // 1シート1チャンク
+    assert_eq!(chunks.len(), 3, "Got: {:?}", chunks);
+    assert!(chunks[0].starts_with("---\n"), "Got: {}", chunks[0]);
+    assert!(chunks[1].starts_with("\n---\n"), "Got: {}", chunks[1]);
+    assert_eq!(chunks.concat(), markdown);
+
+    // 入力エラーはエラーのみを返す
+    let results: Vec<_> = converter
+        .convert_to_chunks(Cursor::new(Vec::new()))
+        .collect();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+
+    // シートの変換エラーは、それまでのシートのチャンクの後に返す
+    let converter = ConverterBuilder::new()
+        .with_stage(
+            Stage::Format,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                match context.sheet_name() {
+                    "Sheet3" => Err(XlsxToMdError::Validation {
+                        sheet: "Sheet3".to_string(),
+                        message: "rejected by stage".to_string(),
+                    }),
+                    _ => Ok(()),
+                }
+            },
+        )
+        .build()
+        .unwrap();
+    let results: Vec<_> = converter
+        .convert_to_chunks(Cursor::new(excel_data))
+        .collect();
+    assert_eq!(results.len(), 3, "Got: {:?}", results);
+    assert!(results[0].as_ref().unwrap().contains("# Sheet1"));
+    assert!(results[1].as_ref().unwrap().contains("# Sheet2"));
+    assert!(matches!(results[2], Err(XlsxToMdError::Validation { .. })));
+}
+
+// TC-I-036: Invalid UTF-8 in XML text is replaced when the encoding fallback is enabled
+#[test]
+fn test_encoding_fallback() {
+    let excel_data = fixtures::generate_invalid_utf8_shared_strings().unwrap();
+
+    // デフォルトでは変換全体が失敗する
+    let converter = ConverterBuilder::new().build().unwrap();
+    let result = converter.convert_to_string(Cursor::new(excel_data.clone()));
+    assert!(result.is_err());
+
+    // 置換文字に置き換えて変換を継続し、警告を記録する
+    let converter = ConverterBuilder::new()
+        .with_encoding_fallback(true)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+
+    assert!(markdown.contains("| Name "), "Got: {}", markdown);
+    assert!(markdown.contains("Caf\u{FFFD}"), "Got: {}", markdown);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].kind, WarningKind::InvalidEncoding);
+    assert_eq!(report.warnings[0].sheet, None);
+    assert!(report.warnings[0].message.contains("xl/sharedStrings.xml"));
+}
+
+// TC-I-037: Cell errors are normalized to Excel notation with a typed code in JSON
+#[test]
+fn test_cell_errors() {
+    let excel_data = fixtures::generate_error_cells().unwrap();
+
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(markdown.contains("| #DIV/0! |"), "Got: {}", markdown);
+    assert!(markdown.contains("| #N/A "), "Got: {}", markdown);
+    assert!(!markdown.contains("Div0"), "Got: {}", markdown);
+
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let cell = &value["rows"][1]["A"];
+    assert_eq!(cell["type"], "error");
+    assert_eq!(cell["raw"], "#DIV/0!");
+    assert_eq!(cell["error"], "div0");
+    assert_eq!(value["rows"][2]["A"]["error"], "na");
+
+    // PipelineStageからは型付きのエラー値として参照できる
+    let errors = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let collected = Arc::clone(&errors);
+    ConverterBuilder::new()
+        .with_stage(
+            Stage::Parse,
+            move |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                for cell in context.cells() {
+                    if let CellValue::Error(e) = cell.value {
+                        collected.lock().unwrap().push(e);
+                    }
+                }
+                Ok(())
+            },
+        )
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert_eq!(
+        *errors.lock().unwrap(),
+        vec![CellError::Div0, CellError::NA]
+    );
+}
+
+// TC-I-038: Consecutive identical rows are collapsed with a count
+#[test]
+fn test_row_dedupe() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Level").unwrap();
+    worksheet.write_string(0, 1, "Message").unwrap();
+    for row in 1..=1000 {
+        worksheet.write_string(row, 0, "WARN").unwrap();
+        worksheet.write_string(row, 1, "retrying").unwrap();
+    }
+    worksheet.write_string(1001, 0, "INFO").unwrap();
+    worksheet.write_string(1001, 1, "done").unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_row_dedupe(RowDedupe::ConsecutiveWithCount)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    assert!(
+        markdown.contains("| WARN  | retrying ×1000 |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(markdown.contains("| INFO  | done "), "Got: {}", markdown);
+    assert_eq!(markdown.lines().filter(|l| l.starts_with('|')).count(), 4);
+
+    // デフォルトでは集約しない
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert_eq!(
+        markdown.lines().filter(|l| l.starts_with('|')).count(),
+        1003
+    );
+}
+
+// TC-I-039: Constant columns are lifted out of the table and reported
+#[test]
+fn test_constant_column_folding() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Region").unwrap();
+    worksheet.write_string(0, 1, "Product").unwrap();
+    worksheet.write_string(0, 2, "Sales").unwrap();
+    for (row, (product, sales)) in [("Apple", 100.0), ("Orange", 200.0)].iter().enumerate() {
+        let row = row as u32 + 1;
+        worksheet.write_string(row, 0, "EMEA").unwrap();
+        worksheet.write_string(row, 1, *product).unwrap();
+        worksheet.write_number(row, 2, *sales).unwrap();
+    }
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_constant_column_folding(true)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+
+    assert!(
+        markdown.contains("- Region: EMEA\n\n| Product | Sales |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("| EMEA"), "Got: {}", markdown);
+    let folded = &report.sheets[0].folded_columns;
+    assert_eq!(folded.len(), 1);
+    assert_eq!(folded[0].column, "A");
+    assert_eq!(folded[0].header, "Region");
+    assert_eq!(folded[0].value, "EMEA");
+
+    // JSON形式では列名を維持するため適用しない
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_constant_column_folding(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["rows"][1]["A"], "EMEA");
+}
+
+// TC-I-040: Boilerplate rows are dropped by a content filter
+#[test]
+fn test_content_filter() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet
+        .write_string(0, 0, "Confidential — internal use only")
+        .unwrap();
+    worksheet.write_string(1, 0, "Region").unwrap();
+    worksheet.write_string(1, 1, "Sales").unwrap();
+    worksheet.write_string(2, 0, "EMEA").unwrap();
+    worksheet.write_number(2, 1, 100.0).unwrap();
+    worksheet.write_string(3, 0, "APAC").unwrap();
+    worksheet.write_number(3, 1, 200.0).unwrap();
+    worksheet
+        .write_string(4, 0, "Confidential — internal use only")
+        .unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_content_filter(ContentFilter {
+            include_rows_matching: None,
+            exclude_rows_matching: Some("^Confidential".to_string()),
+        })
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+
+    assert!(!markdown.contains("Confidential"), "Got: {}", markdown);
+    assert!(
+        markdown.contains("# Sheet1\n\n| Region | Sales |\n"),
+        "Got: {}",
+        markdown
+    );
+    assert_eq!(report.sheets[0].filtered_rows, 2);
+
+    // 出力条件は見出し以外の行に適用する
+    let markdown = ConverterBuilder::new()
+        .with_content_filter(ContentFilter {
+            include_rows_matching: Some("^APAC".to_string()),
+            exclude_rows_matching: Some("^Confidential".to_string()),
+        })
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(markdown.contains("| Region | Sales |"), "Got: {}", markdown);
+    assert!(markdown.contains("| APAC"), "Got: {}", markdown);
+    assert!(!markdown.contains("EMEA"), "Got: {}", markdown);
+}
+
+// TC-I-041: Link inventory lists unique URLs; footnotes are shared per URL
+#[test]
+fn test_link_inventory() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Name").unwrap();
+    worksheet.write_string(0, 1, "Docs").unwrap();
+    worksheet.write_string(1, 0, "xlsxzero").unwrap();
+    worksheet
+        .write_url_with_text(1, 1, "https://docs.rs", "docs")
+        .unwrap();
+    worksheet.write_string(2, 0, "calamine").unwrap();
+    worksheet
+        .write_url_with_text(2, 1, "https://docs.rs", "docs")
+        .unwrap();
+    worksheet
+        .write_url_with_text(3, 0, "https://github.com", "GitHub")
+        .unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    // 同じURLの脚注は1つにまとめる
+    let markdown = ConverterBuilder::new()
+        .with_hyperlink_mode(HyperlinkMode::Footnote)
+        .include_link_inventory(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert_eq!(markdown.matches("docs[^1]").count(), 2, "Got: {}", markdown);
+    assert!(markdown.contains("GitHub[^2]"), "Got: {}", markdown);
+    assert!(!markdown.contains("[^3]"), "Got: {}", markdown);
+    assert!(
+        markdown.contains(
+            "\n## Links\n\n- https://docs.rs: B2, B3\n- https://github.com: A4\n\n[^1]: https://docs.rs\n"
+        ),
+        "Got: {}",
+        markdown
+    );
+
+    // リンク一覧はTextOnlyでも出力され、JSONでは`links`配列になる
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_hyperlink_mode(HyperlinkMode::TextOnly)
+        .include_link_inventory(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["links"][0]["url"], "https://docs.rs");
+    assert_eq!(value["links"][0]["cells"], serde_json::json!(["B2", "B3"]));
+    assert_eq!(value["links"][1]["cells"], serde_json::json!(["A4"]));
+}
+
+// TC-I-042: Named cell styles are tagged in typed JSON and split Markdown into sections
+#[test]
+fn test_named_cell_styles() {
+    let excel_data = fixtures::generate_named_styles().unwrap();
+
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .include_cell_styles(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let rows = &value["rows"];
+    assert_eq!(rows[0]["A"]["style"], "Heading 1", "Got: {}", json);
+    assert_eq!(rows[2]["B"]["style"], "Input", "Got: {}", json);
+    // 既定のスタイル（Normal）のセルには含めない
+    assert!(rows[1]["A"].get("style").is_none(), "Got: {}", json);
+
+    // 指定しない場合はスタイル名を含めない
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(!json.contains("\"style\""), "Got: {}", json);
+
+    // 見出しスタイルの行で表を区切る
+    let markdown = ConverterBuilder::new()
+        .split_on_heading_styles(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.contains("# Sheet1\n\n## Quarterly Report\n\n| Region | Sales |\n"),
+        "Got: {}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| EMEA   | 100   |\n\n## Costs\n\n| Item | Cost |\n"),
+        "Got: {}",
+        markdown
+    );
+
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(!markdown.contains("## "), "Got: {}", markdown);
+}
+
+// TC-I-043: JSON keys derived from normalized headers, original headers kept in `columns`
+#[test]
+fn test_json_normalized_header_keys() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, " Product  Name ").unwrap();
+    worksheet.write_string(0, 1, "Weight (kg)").unwrap();
+    worksheet.write_string(1, 0, "Bolt").unwrap();
+    worksheet.write_number(1, 1, 0.5).unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_key_mode(JsonKeyMode::NormalizedHeader)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value["rows"],
+        serde_json::json!([{ "product_name": "Bolt", "weight": 0.5 }]),
+        "Got: {}",
+        json
+    );
+    assert_eq!(
+        value["columns"][1],
+        serde_json::json!({ "key": "weight", "header": "Weight (kg)", "column": "B" })
+    );
+
+    // 既定では列名をキーとし、`columns`は出力しない
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["rows"][1]["A"], "Bolt");
+    assert!(value.get("columns").is_none());
+}
+
+// TC-I-044: Units in headers are exposed per column and optionally stripped
+#[test]
+fn test_header_units() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(0, 0, "Item").unwrap();
+    worksheet.write_string(0, 1, "Weight (kg)").unwrap();
+    worksheet.write_string(0, 2, "Price [USD]").unwrap();
+    worksheet.write_string(1, 0, "Bolt").unwrap();
+    worksheet.write_number(1, 1, 0.5).unwrap();
+    worksheet.write_number(1, 2, 2).unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_key_mode(JsonKeyMode::NormalizedHeader)
+        .with_header_units(HeaderUnits::DetectAndStrip)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value["columns"],
+        serde_json::json!([
+            { "key": "item", "header": "Item", "column": "A", "unit": null },
+            { "key": "weight", "header": "Weight", "column": "B", "unit": "kg" },
+            { "key": "price", "header": "Price", "column": "C", "unit": "USD" },
+        ]),
+        "Got: {}",
+        json
+    );
+    assert_eq!(value["rows"][0]["weight"], 0.5);
+
+    // 列名をキーとする場合も`columns`に単位を出力し、見出しはそのまま
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_header_units(HeaderUnits::Detect)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["columns"][1]["unit"], "kg", "Got: {}", json);
+    assert_eq!(value["rows"][0]["B"], "Weight (kg)");
+
+    // 単位の除去はMarkdownの見出しにも適用される
+    let markdown = ConverterBuilder::new()
+        .with_header_units(HeaderUnits::DetectAndStrip)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(
+        markdown.contains("| Item | Weight | Price |"),
+        "Got: {}",
+        markdown
+    );
+}
+
+// TC-I-045: Multi-sheet JSON is a single document and the sheet separator is configurable
+#[test]
+fn test_document_layout() {
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let sheets = value["sheets"].as_array().expect("Got: {json}");
+    assert_eq!(sheets.len(), 3);
+    assert_eq!(sheets[2]["name"], "Sheet3");
+    assert_eq!(sheets[2]["rows"][0]["A"], "Sheet3_Data");
+    // チャンクを連結しても同じ文書になる
+    let chunks: Vec<String> = converter
+        .convert_to_chunks(Cursor::new(excel_data.clone()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(chunks.concat(), json);
+
+    let markdown = ConverterBuilder::new()
+        .with_sheet_separator("\n<!-- pagebreak -->\n\n")
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(
+        markdown.contains("\n<!-- pagebreak -->\n\n# Sheet2\n"),
+        "Got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("---\n\n# Sheet2"), "Got: {}", markdown);
+}
+
+// TC-I-046: NDJSON output emits one parseable JSON object per line
+#[test]
+fn test_json_lines_document() {
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    let ndjson = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_document_mode(JsonDocumentMode::Lines)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let names: Vec<String> = ndjson
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            value["name"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(names, ["Sheet1", "Sheet2", "Sheet3"], "Got: {}", ndjson);
+}
+
+// TC-I-047: Multi-sheet HTML is a single document with per-sheet sections
+#[test]
+fn test_html_document() {
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    let html = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Html)
+        .include_html_navigation(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>\n"), "Got: {}", html);
+    assert!(html.contains("<meta charset=\"utf-8\">"), "Got: {}", html);
+    assert!(
+        html.contains("<li><a href=\"#sheet2\">Sheet2</a></li>"),
+        "Got: {}",
+        html
+    );
+    assert!(
+        html.contains("<section id=\"sheet2\">\n<h1>Sheet2</h1>\n<table>"),
+        "Got: {}",
+        html
+    );
+    assert_eq!(html.matches("<section ").count(), 3);
+    assert_eq!(html.matches("</section>").count(), 3);
+    assert!(!html.contains("<!-- Sheet:"), "Got: {}", html);
+    assert!(html.ends_with("</body>\n</html>\n"), "Got: {}", html);
+}
+
+// TC-I-048: Custom front matter fields are added to Markdown output
+#[test]
+fn test_front_matter_extra() {
+    let excel_data = fixtures::simple_table().unwrap();
+    let extra = std::collections::HashMap::from([
+        ("tenant_id".to_string(), "acme".to_string()),
+        ("source_system".to_string(), "erp".to_string()),
+    ]);
+
+    let markdown = ConverterBuilder::new()
+        .with_front_matter_extra(extra.clone())
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.starts_with("---\nsource_system: \"erp\"\ntenant_id: \"acme\"\n---\n\n# "),
+        "Got: {}",
+        markdown
+    );
+
+    // メタデータと併用すると、追加の項目の後にシートの情報を出力する
+    let markdown = ConverterBuilder::new()
+        .include_metadata(true)
+        .with_front_matter_extra(extra)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(
+        markdown.starts_with("---\nsource_system: \"erp\"\ntenant_id: \"acme\"\nsheets:\n"),
+        "Got: {}",
+        markdown
+    );
+}
+
+// TC-I-049: Near-duplicate sheets are skipped or replaced with a note
+#[test]
+fn test_duplicate_sheets() {
+    let excel_data = fixtures::generate_duplicate_sheets().unwrap();
+
+    // デフォルトでは検出しない
+    let converter = ConverterBuilder::new().build().unwrap();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), Vec::new())
+        .unwrap();
+    assert_eq!(report.sheets.len(), 3);
+    assert!(report.duplicate_sheets.is_empty());
+
+    // Skip: 複製シートを出力から除外し、レポートに記録する
+    let converter = ConverterBuilder::new()
+        .with_duplicate_sheets(DuplicateSheets::Skip)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+    assert!(!markdown.contains("Sheet1 (2)"), "Got: {}", markdown);
+    assert!(markdown.contains("# Other"));
+    assert_eq!(report.sheets.len(), 2);
+    assert_eq!(report.duplicate_sheets.len(), 1);
+    let duplicate = &report.duplicate_sheets[0];
+    assert_eq!(duplicate.sheet, "Sheet1 (2)");
+    assert_eq!(duplicate.duplicate_of, "Sheet1");
+    assert_eq!(duplicate.similarity, 1.0);
+    assert!(duplicate.skipped);
+    assert!(
+        report
+            .warnings
+            .iter()
+            .any(|w| w.kind == WarningKind::DuplicateSheet
+                && w.sheet.as_deref() == Some("Sheet1 (2)"))
+    );
+
+    // Note: 複製シートの内容を注記に置き換える
+    let markdown = ConverterBuilder::new()
+        .with_duplicate_sheets(DuplicateSheets::Note)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(
+        markdown.contains(
+            "# Sheet1 (2)\n\n> Near-duplicate of sheet \"Sheet1\" (similarity: 100%); content omitted.\n"
+        ),
+        "Got: {}",
+        markdown
+    );
+    assert_eq!(markdown.matches("item19").count(), 1);
+}
+
+// TC-I-050: Output exceeding the maximum size aborts or is truncated with a marker
+#[test]
+fn test_max_output_size() {
+    let excel_data = fixtures::large_table(10, 10).unwrap();
+    let full = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let limit = (full.len() / 2) as u64;
+
+    // 上限以下の場合は影響しない
+    let output = ConverterBuilder::new()
+        .with_max_output_size(full.len() as u64)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert_eq!(output, full);
+
+    // デフォルトでは変換を中止する
+    let result = ConverterBuilder::new()
+        .with_max_output_size(limit)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()));
+    let err = result.unwrap_err();
+    assert!(matches!(err, XlsxToMdError::SecurityViolation(_)));
+    assert_eq!(err.kind(), xlsxzero::ErrorKind::ResourceLimit);
+
+    // Truncate: 行単位で打ち切り、注記と警告を付加する
+    let converter = ConverterBuilder::new()
+        .with_max_output_size(limit)
+        .with_output_overflow(OutputOverflow::Truncate)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+    let marker = format!(
+        "\n> Output truncated: exceeded the maximum output size of {} bytes.\n",
+        limit
+    );
+    assert!(markdown.ends_with(&marker), "Got: {}", markdown);
+    let body = &markdown[..markdown.len() - marker.len()];
+    assert!(full.starts_with(body));
+    assert!(body.ends_with("|\n"));
+    assert!(report
+        .warnings
+        .iter()
+        .any(|w| w.kind == WarningKind::OutputTruncated));
+}
+
+// TC-I-051: Images placed in cells are output as a placeholder or a reference
+#[test]
+fn test_cell_images() {
+    let excel_data = fixtures::generate_cell_image().unwrap();
+    let convert = |mode: Option<CellImageMode>| {
+        let mut builder = ConverterBuilder::new().with_output_format(OutputFormat::Csv);
+        if let Some(mode) = mode {
+            builder = builder.with_cell_image_mode(mode);
+        }
+        builder
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // デフォルトではプレースホルダーを出力する
+    assert_eq!(
+        convert(None),
+        "# Sheet: Sheet1\n\nProduct,Photo\nApple,[image]\n"
+    );
+    assert_eq!(
+        convert(Some(CellImageMode::Placeholder("(photo)".to_string()))),
+        "# Sheet: Sheet1\n\nProduct,Photo\nApple,(photo)\n"
+    );
+    assert_eq!(
+        convert(Some(CellImageMode::Reference)),
+        "# Sheet: Sheet1\n\nProduct,Photo\nApple,[image: xl/media/image1.png]\n"
+    );
+    // 保存された値（#VALUE!）をそのまま出力する
+    assert_eq!(
+        convert(Some(CellImageMode::Value)),
+        "# Sheet: Sheet1\n\nProduct,Photo\nApple,#VALUE!\n"
+    );
+}
+
+// TC-I-052: Dynamic array spill ranges keep cached values and are annotated in formula mode
+#[test]
+fn test_spill_ranges() {
+    let excel_data = fixtures::generate_spill_range().unwrap();
+    let convert = |mode: FormulaMode| {
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .with_formula_mode(mode)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // 値モードでは溢れた範囲の計算結果をそのまま出力する
+    assert_eq!(
+        convert(FormulaMode::CachedValue),
+        "# Sheet: Sheet1\n\nSeq,Label\n1,L1\n2,L2\n3,L3\n4,L4\n"
+    );
+    // 数式モードでは起点のセルに数式を、溢れた範囲のセルに起点のセルを示す注記を出力する
+    assert_eq!(
+        convert(FormulaMode::Formula),
+        "# Sheet: Sheet1\n\nSeq,Label\n_xlfn.SEQUENCE(4),L1\n↳ spill of A2,L2\n↳ spill of A2,L3\n↳ spill of A2,L4\n"
+    );
+}
+
+// TC-I-053: XML output escapes text, types cells and wraps multiple sheets in a workbook
+#[test]
+fn test_xml_output() {
+    let excel_data = fixtures::generate_xml_table().unwrap();
+    let convert = |selector: SheetSelector| {
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Xml)
+            .with_sheet_selector(selector)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // 空のセル・空の行は出力せず、数値はデフォルトで生の値を出力する
+    assert_eq!(
+        convert(SheetSelector::Index(0)),
+        "<!-- Sheet: R&D -->\n\n\
+         <sheet name=\"R&amp;D\">\n\
+         \x20 <row r=\"1\">\n\
+         \x20   <cell r=\"A1\" t=\"s\">Item</cell>\n\
+         \x20   <cell r=\"B1\" t=\"s\">Amount</cell>\n\
+         \x20   <cell r=\"C1\" t=\"s\">Done</cell>\n\
+         \x20 </row>\n\
+         \x20 <row r=\"2\">\n\
+         \x20   <cell r=\"A2\" t=\"s\">&lt;Tom &amp; &quot;Jerry&quot;&gt;</cell>\n\
+         \x20   <cell r=\"B2\" t=\"n\">1234.5</cell>\n\
+         \x20   <cell r=\"C2\" t=\"b\">TRUE</cell>\n\
+         \x20 </row>\n\
+         \x20 <row r=\"4\">\n\
+         \x20   <cell r=\"B4\" t=\"n\">42</cell>\n\
+         \x20 </row>\n\
+         </sheet>\n"
+    );
+
+    // 複数のシートは<workbook>要素で囲んだ1つの文書になる
+    let xml = convert(SheetSelector::All);
+    assert!(xml.starts_with("<workbook>\n<sheet name=\"R&amp;D\">\n"));
+    assert!(xml.contains("</sheet>\n<sheet name=\"Notes\">\n"));
+    // セルの改行は他のフォーマットと同じく<br>として出力され、エスケープされる
+    assert!(xml.contains("<cell r=\"A1\" t=\"s\">line1&lt;br&gt;line2</cell>"));
+    assert!(xml.ends_with("</sheet>\n</workbook>\n"));
+    assert!(!xml.contains("<!--"));
+}
+
+// TC-I-054: MessagePack output encodes the typed JSON structure of every sheet
+#[cfg(feature = "msgpack")]
+#[test]
+fn test_msgpack_output() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .build()
+        .unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_to_msgpack(Cursor::new(excel_data), &mut output)
+        .unwrap();
+    assert_eq!(report.output_size, output.len() as u64);
+
+    // {"sheets": [{"name": "Sheet1", "rows": [{"A": {"type": "string", ...}, ...}]}]}
+    let mut prefix = vec![0x81, 0xa6];
+    prefix.extend_from_slice(b"sheets");
+    prefix.push(0x91);
+    assert!(output.starts_with(&prefix));
+    let contains = |needle: &[u8]| output.windows(needle.len()).any(|w| w == needle);
+    assert!(contains(b"\xa4name\xa6Sheet1"));
+    assert!(contains(b"\xa4type\xa6string"));
+    assert!(contains(b"\xa5Data1"));
+}
+
+// TC-I-055: GridDocument edits cells after parsing and re-renders to any format
+#[test]
+fn test_grid_document() {
+    let converter = ConverterBuilder::new().build().unwrap();
+    let excel_data = fixtures::simple_table().unwrap();
+    let mut grids = converter.convert_to_grids(Cursor::new(excel_data)).unwrap();
+    assert_eq!(grids.len(), 1);
+
+    let grid = &mut grids[0];
+    assert_eq!(grid.name(), "Sheet1");
+    assert_eq!((grid.rows(), grid.cols()), (2, 2));
+    assert_eq!(grid.cell(1, 0), Some("Data1"));
+    assert_eq!(grid.cell(2, 0), None);
+
+    grid.set_cell(0, 0, "Fixed");
+    grid.push_column(["Extra", "a|b"]);
+    assert_eq!(grid.cell(1, 2), Some("a\\|b"));
+
+    // 同じグリッドを複数のフォーマットで出力できる
+    assert_eq!(
+        grid.render(OutputFormat::Markdown).unwrap(),
+        "| Fixed | Header2 | Extra |\n|-------|---------|-------|\n| Data1 | Data2   | a\\|b  |\n"
+    );
+    let csv = grid.render(OutputFormat::Csv).unwrap();
+    assert!(csv.starts_with("Fixed,Header2,Extra\n"));
+    assert!(csv.contains("Data1,Data2,"));
+
+    // グリッド外の座標を設定するとグリッドが拡張される
+    grid.set_cell(2, 0, "Total");
+    assert_eq!((grid.rows(), grid.cols()), (3, 3));
+    assert_eq!(grid.cell(2, 2), Some(""));
+}
+
+// TC-I-056: Computed columns are evaluated per row and skipped on sheets without the columns
+#[test]
+fn test_computed_columns() {
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .with_computed_column("total", "amount * (1 + [Tax Rate])")
+        .with_computed_column("name", "concat(first, ' ', upper(last))")
+        .build()
+        .unwrap();
+    let excel_data = fixtures::generate_order_lines().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data), &mut output)
+        .unwrap();
+    let csv = String::from_utf8(output).unwrap();
+
+    // 見出しは大文字小文字を区別せずに参照し、数値でない値の演算は#VALUE!になる
+    assert!(
+        csv.contains(
+            "first,last,Amount,Tax Rate,total,name\n\
+             Ada,Lovelace,120,0.1,132,Ada LOVELACE\n\
+             Alan,Turing,TBD,0.08,#VALUE!,Alan TURING\n"
+        ),
+        "Got: {}",
+        csv
+    );
+
+    // 参照する列がないシートには追加せず、警告を記録する
+    assert!(csv.contains("note\nno orders\n"), "Got: {}", csv);
+    assert_eq!(report.warnings.len(), 2);
+    assert!(report.warnings.iter().all(|w| {
+        w.kind == WarningKind::ComputedColumnSkipped && w.sheet.as_deref() == Some("Memo")
+    }));
+}
+
+// TC-I-057: Data rows are sorted by a column while the header row stays first
+#[test]
+fn test_sort_rows() {
+    let excel_data = fixtures::generate_order_lines().unwrap();
+    let convert = |column: ColumnSelector, order: SortOrder| {
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .with_sheet_selector(SheetSelector::Name("Orders".to_string()))
+            .with_sort(column, order, true)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    assert_eq!(
+        convert(
+            ColumnSelector::Header("FIRST".to_string()),
+            SortOrder::Descending
+        ),
+        "# Sheet: Orders\n\n\
+         first,last,Amount,Tax Rate\n\
+         Alan,Turing,TBD,0.08\n\
+         Ada,Lovelace,120,0.1\n"
+    );
+    // 数値は文字列より前に配置される
+    assert_eq!(
+        convert(ColumnSelector::Index(2), SortOrder::Ascending),
+        "# Sheet: Orders\n\n\
+         first,last,Amount,Tax Rate\n\
+         Ada,Lovelace,120,0.1\n\
+         Alan,Turing,TBD,0.08\n"
+    );
+}
+
+// TC-I-058: Protected sheets are converted by default, or skipped or flagged on request
+#[test]
+fn test_protected_sheets() {
+    let excel_data = fixtures::generate_protected_sheet().unwrap();
+    let convert = |mode: ProtectedSheets| {
+        let converter = ConverterBuilder::new()
+            .with_protected_sheets(mode)
+            .include_metadata(true)
+            .build()
+            .unwrap();
+        let mut output = Vec::new();
+        let report = converter
+            .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), report)
+    };
+
+    // 保護はデータを暗号化しないため、デフォルトでは通常どおり変換する
+    let (markdown, report) = convert(ProtectedSheets::Convert);
+    assert!(markdown.contains("| Locked |"), "Got: {}", markdown);
+    assert!(!markdown.contains("protected"), "Got: {}", markdown);
+    assert!(report.warnings.is_empty());
+
+    let (markdown, report) = convert(ProtectedSheets::Skip);
+    assert!(markdown.contains("| Open |"), "Got: {}", markdown);
+    assert!(!markdown.contains("Locked"), "Got: {}", markdown);
+    assert_eq!(report.sheets.len(), 1);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].kind, WarningKind::ProtectedSheet);
+    assert_eq!(report.warnings[0].sheet.as_deref(), Some("Sheet2"));
+
+    // フロントマターで保護されたシートを識別する
+    let (markdown, report) = convert(ProtectedSheets::Flag);
+    assert!(markdown.contains("| Locked |"), "Got: {}", markdown);
+    assert!(
+        markdown.contains("  - name: \"Sheet2\"\n    script: latin\n"),
+        "Got: {}",
+        markdown
+    );
+    assert_eq!(markdown.matches("    protected: true\n").count(), 1);
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(report.warnings[0].kind, WarningKind::ProtectedSheet);
+}
+
+// TC-I-059: Change history appendix from shared-workbook revisions
+#[test]
+fn test_change_history() {
+    let excel_data = fixtures::generate_revision_history().unwrap();
+
+    // デフォルトでは変更履歴を解析しない
+    let converter = ConverterBuilder::new().build().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    assert!(report.revisions.is_empty());
+    assert!(!String::from_utf8(output)
+        .unwrap()
+        .contains("Change history"));
+
+    let converter = ConverterBuilder::new()
+        .include_change_history(true)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let revisions: &[Revision] = &report.revisions;
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].user, "Ada");
+    assert_eq!(revisions[0].changes[0].sheet, "Budget");
+    assert_eq!(revisions[1].changes.len(), 2);
+
+    let markdown = String::from_utf8(output).unwrap();
+    assert!(markdown.ends_with(
+        "\n---\n\n# Change history\n\n\
+         | Date | User | Sheet | Cell | Old value | New value |\n\
+         |---|---|---|---|---|---|\n\
+         | 2024-05-01T09:30:00Z | Ada | Budget | B2 | 120 | 150 |\n\
+         | 2024-05-02T10:00:00Z | Alan | Budget | A3 |  | Power |\n\
+         | 2024-05-02T10:00:00Z | Alan | Budget | A3 | Power |  |\n"
+    ));
+
+    // JSONではシートが1つでも文書全体のオブジェクトの`changes`配列になる
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .include_change_history(true)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["sheets"][0]["name"], "Budget");
+    assert_eq!(value["changes"][0]["old"], "120");
+    assert_eq!(value["changes"][2]["cell"], "A3");
+    assert!(value["changes"][2]["new"].is_null());
+
+    // XMLでは<workbook>内の<changes>要素
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Xml)
+        .include_change_history(true)
+        .build()
+        .unwrap();
+    let xml = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(xml.starts_with("<workbook>\n<sheet name=\"Budget\">"));
+    assert!(xml.contains(
+        "<change date=\"2024-05-01T09:30:00Z\" user=\"Ada\" sheet=\"Budget\" cell=\"B2\">"
+    ));
+    assert!(xml.ends_with("</changes>\n</workbook>\n"));
+}
+
+// TC-I-060: Conditional formatting rules as metadata and cell annotations
+#[test]
+fn test_conditional_formats() {
+    let excel_data = fixtures::generate_conditional_formats().unwrap();
+
+    // デフォルトではルールを出力しない
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(value.get("conditional_formats").is_none());
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_conditional_formats(ConditionalFormats::Metadata)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let formats = value["conditional_formats"].as_array().unwrap();
+    assert_eq!(formats.len(), 2);
+    assert_eq!(formats[0]["range"], "A2:A5");
+    assert_eq!(formats[0]["type"], "duplicateValues");
+    assert_eq!(formats[1]["type"], "top10");
+    assert_eq!(formats[1]["rank"], 1);
+    // Metadataでは注記しない
+    assert!(!json.contains("highlights"));
+
+    let converter = ConverterBuilder::new()
+        .with_conditional_formats(ConditionalFormats::Annotate)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(markdown.contains("| INV-1[^1] | 120"));
+    assert!(markdown.contains("| INV-2 "));
+    assert!(markdown.contains("| INV-1[^1] | 300[^2]"));
+    assert!(markdown.contains("[^1]: Conditional format: duplicate value in A2:A5\n"));
+    assert!(markdown.contains("[^2]: Conditional format: top 1 in B2:B5\n"));
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .with_conditional_formats(ConditionalFormats::Annotate)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let rows = value["rows"].as_array().unwrap();
+    assert_eq!(rows[1]["A"]["highlights"][0], "duplicate value in A2:A5");
+    assert!(rows[2]["A"].get("highlights").is_none());
+    assert_eq!(rows[3]["B"]["highlights"][0], "top 1 in B2:B5");
+}
+
+// TC-I-061: Sparkline and data bar presence in sheet metadata
+#[test]
+fn test_visuals_metadata() {
+    let excel_data = fixtures::generate_visuals().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let metadata = &value["metadata"];
+    let sparklines = metadata["sparklines"].as_array().unwrap();
+    assert_eq!(sparklines.len(), 2);
+    let sparkline = |range: &str| sparklines.iter().find(|s| s["range"] == range).unwrap();
+    assert_eq!(sparkline("E2")["source"], "Sheet1!A2:C2");
+    assert_eq!(sparkline("E2")["type"], "column");
+    assert_eq!(sparkline("E3")["source"], "Sheet1!A3:C3");
+    assert_eq!(
+        metadata["data_bars"],
+        serde_json::json!([{ "range": "D2:D3", "source": "D2:D3" }])
+    );
+
+    let converter = ConverterBuilder::new()
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(markdown.contains(
+        "      - range: \"E2\"\n        source: \"Sheet1!A2:C2\"\n        type: \"column\"\n"
+    ));
+    assert!(
+        markdown.contains("    data_bars:\n      - range: \"D2:D3\"\n        source: \"D2:D3\"\n")
+    );
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Xml)
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let xml = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(xml.contains("    <sparkline range=\"E2\" source=\"Sheet1!A2:C2\" type=\"column\"/>\n"));
+    assert!(xml.contains("    <data_bar range=\"D2:D3\" source=\"D2:D3\"/>\n  </visuals>\n"));
+
+    // メタデータを出力しない場合は含めない
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap();
+    let json = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(!json.contains("sparklines"));
+    assert!(!json.contains("data_bars"));
+}
+
+// TC-I-062: Column width limit for Markdown tables
+#[test]
+fn test_column_width_limit() {
+    let excel_data = fixtures::generate_long_descriptions().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_column_width_limit(20, WrapStrategy::Wrap)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.contains("| A-1 | Stainless steel<br>water bottle with a<br>leak-proof lid |\n")
+    );
+    assert!(markdown.contains("| B-2 | Mug                 |\n"));
+
+    let converter = ConverterBuilder::new()
+        .with_column_width_limit(20, WrapStrategy::Truncate)
+        .build()
+        .unwrap();
+    let markdown = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(markdown.contains("| A-1 | Stainless steel wat… |\n"));
+
+    // 付加する文字列を指定し、切り詰めたセルの数をレポートに記録
+    let converter = ConverterBuilder::new()
+        .with_column_width_limit(20, WrapStrategy::Truncate)
+        .with_truncation_marker("[...]")
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+    assert!(markdown.contains("| A-1 | Stainless steel[...] |\n"));
+    assert_eq!(report.sheets[0].truncated_cells, 1);
+
+    // Markdown以外には適用しない
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .with_column_width_limit(20, WrapStrategy::Truncate)
+        .build()
+        .unwrap();
+    let csv = converter
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(csv.contains("Stainless steel water bottle with a leak-proof lid"));
+
+    let result = ConverterBuilder::new()
+        .with_column_width_limit(2, WrapStrategy::Wrap)
+        .build();
+    assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+    let result = ConverterBuilder::new()
+        .with_truncation_marker(" | ")
+        .build();
+    assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+}
+
+// TC-I-063: Pretty-printed and minified JSON
+#[test]
+fn test_json_style() {
+    let excel_data = fixtures::multi_sheets().unwrap();
+
+    let pretty = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(pretty.starts_with("{\n  \"sheets\": [\n"));
+
+    let minified = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_style(JsonStyle::Minified)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(minified.starts_with("{\"sheets\":[{\"name\":"));
+    assert_eq!(minified.lines().count(), 1);
+    assert!(minified.len() < pretty.len());
+    let pretty_value: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    let minified_value: serde_json::Value = serde_json::from_str(&minified).unwrap();
+    assert_eq!(pretty_value, minified_value);
+
+    // NDJSONは書式の指定にかかわらず1シート1行
+    let lines = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_document_mode(JsonDocumentMode::Lines)
+        .with_json_style(JsonStyle::Pretty)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert_eq!(
+        lines.lines().count(),
+        pretty_value["sheets"].as_array().unwrap().len()
+    );
+}
+
+// TC-I-064: Workbook inspection with the embedded thumbnail
+#[test]
+fn test_inspect_thumbnail() {
+    let converter = ConverterBuilder::new().build().unwrap();
+
+    let info = converter
+        .inspect(Cursor::new(fixtures::generate_with_thumbnail().unwrap()))
+        .unwrap();
+    assert_eq!(info.sheet_names, ["Summary", "Data"]);
+    let thumbnail = info.thumbnail.unwrap();
+    assert_eq!(thumbnail.mime, "image/jpeg");
+    assert_eq!(&thumbnail.bytes[..3], &[0xFF, 0xD8, 0xFF]);
+    assert!(thumbnail
+        .to_data_uri()
+        .starts_with("data:image/jpeg;base64,/9j/"));
+
+    // サムネイル画像のないワークブック
+    let info = converter
+        .inspect(Cursor::new(fixtures::simple_table().unwrap()))
+        .unwrap();
+    assert_eq!(info.sheet_names, ["Sheet1"]);
+    assert!(info.thumbnail.is_none());
+}
+
+// TC-I-065: Hidden and very hidden sheets are flagged in their headings
+#[test]
+fn test_hidden_sheet_headings() {
+    let excel_data = fixtures::generate_hidden_sheets().unwrap();
+
+    let output = ConverterBuilder::new()
+        .include_hidden(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(output.contains("# Public\n"));
+    assert!(output.contains("# Archive (hidden)\n"));
+    assert!(output.contains("# SecretSheet (very hidden)\n"));
+    assert!(output.contains("| Very hidden |"));
+
+    // 非表示シートを含めない設定では付記しない
+    let output = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(!output.contains("(hidden)"));
+    assert!(!output.contains("(very hidden)"));
+}
+
+// TC-I-066: Placement of vertically merged values with DataDuplication
+#[test]
+fn test_duplication_placement() {
+    let excel_data = fixtures::generate_vertical_merge().unwrap();
+    let convert = |placement: DuplicationPlacement| {
+        ConverterBuilder::new()
+            .with_duplication_placement(placement)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+    let category_column = |output: &str| -> Vec<String> {
+        output
+            .lines()
+            .filter(|line| line.starts_with("| ") && !line.starts_with("| Category"))
+            .map(|line| line.split('|').nth(1).unwrap().trim().to_string())
+            .collect()
+    };
+
+    let all = convert(DuplicationPlacement::All);
+    assert_eq!(
+        category_column(&all),
+        ["Fruit", "Fruit", "Fruit", "Vegetable"]
+    );
+    let first = convert(DuplicationPlacement::FirstOnly);
+    assert_eq!(category_column(&first), ["Fruit", "", "", "Vegetable"]);
+    assert!(first.contains("| Banana"));
+    let middle = convert(DuplicationPlacement::Middle);
+    assert_eq!(category_column(&middle), ["", "Fruit", "", "Vegetable"]);
+}
+
+// TC-I-067: Forward-filled group labels as flat NDJSON records
+#[test]
+fn test_forward_fill() {
+    let output = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_document_mode(JsonDocumentMode::Lines)
+        .with_json_key_mode(JsonKeyMode::Header)
+        .with_duplication_placement(DuplicationPlacement::FirstOnly)
+        .with_forward_fill(ColumnSelector::Header("region".to_string()))
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(fixtures::generate_grouped_labels().unwrap()))
+        .unwrap();
+
+    let sheet: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+    let regions: Vec<&str> = sheet["rows"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["Region"].as_str().unwrap())
+        .collect();
+    assert_eq!(regions, ["East", "East", "West", "West"]);
+}
+
+// TC-I-068: Number formats of the shared fixture workbook
+#[test]
+fn test_formats_matrix_fixture() {
+    let output = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(fixtures::formats_matrix().unwrap()))
+        .unwrap();
+
+    // 見出しと表示形式ごとの行
+    assert_eq!(
+        output.lines().filter(|line| line.starts_with("| ")).count(),
+        13
+    );
+    assert!(output.contains("| #,##0.00 "));
+    assert!(output.contains("| 0.00%    "));
+    // 日付・時刻の表示形式の値は日付・時刻として出力
+    assert!(output.contains("| 2025-01-02 "));
+    assert!(output.contains("| 18:00:00 "));
+    assert!(output.contains("| 2025-01-02T18:00:00 |"));
+}
+
+// TC-I-069: Re-export converted grids and JSON output as XLSX
+#[test]
+fn test_write_round_trip() {
+    let converter = ConverterBuilder::new().build().unwrap();
+
+    // グリッドを編集して書き出し、再変換すると編集後のグリッドと同じ出力になる
+    let mut grids = converter
+        .convert_to_grids(Cursor::new(fixtures::merged_cells().unwrap()))
+        .unwrap();
+    grids[0].set_cell(1, 1, "Cleaned");
+    let xlsx = xlsxzero::writer::grids_to_xlsx(&grids).unwrap();
+    let regenerated = converter.convert_to_grids(Cursor::new(xlsx)).unwrap();
+    let output = regenerated[0].render(OutputFormat::Markdown).unwrap();
+    assert_eq!(output, grids[0].render(OutputFormat::Markdown).unwrap());
+    assert!(output.contains("| Header | Header  | Header |"));
+    assert!(output.contains("| Data1  | Cleaned | Data3  |"));
+    // 結合セルは結合セルとして書き出される
+    let html = ConverterBuilder::new()
+        .with_merge_strategy(MergeStrategy::HtmlFallback)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(
+            xlsxzero::writer::grids_to_xlsx(&grids).unwrap(),
+        ))
+        .unwrap();
+    assert!(html.contains(r#"<td rowspan="1" colspan="3">Header</td>"#));
+
+    // 型付きJSONから書き出すと、値の種類と値が復元される
+    let typed_json = |input: Vec<u8>| -> serde_json::Value {
+        let json = ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_json_cell_mode(JsonCellMode::Typed)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(input))
+            .unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for row in value["rows"].as_array_mut().unwrap() {
+            for cell in row.as_object_mut().unwrap().values_mut() {
+                cell.as_object_mut().unwrap().remove("formatted");
+            }
+        }
+        value
+    };
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_cell_mode(JsonCellMode::Typed)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(fixtures::formats_matrix().unwrap()))
+        .unwrap();
+    let xlsx = xlsxzero::writer::json_to_xlsx(&json).unwrap();
+    assert_eq!(
+        typed_json(xlsx),
+        typed_json(fixtures::formats_matrix().unwrap())
+    );
+
+    // Excelで使用できないシート名はエラー
+    let result = xlsxzero::writer::json_to_xlsx(r#"{"name": "a/b", "rows": []}"#);
+    match result {
+        Err(error @ XlsxToMdError::InvalidWriteInput { .. }) => {
+            assert_eq!(error.sheet(), Some("a/b"));
+            assert_eq!(error.kind(), xlsxzero::ErrorKind::InvalidInput);
+        }
+        other => panic!("Expected InvalidWriteInput error, got {:?}", other),
+    }
+}
+
+// TC-I-070: Column validations reported as warnings or failing the conversion
+#[test]
+fn test_column_validations() {
+    let validations = || {
+        [
+            Validation::Type(
+                ColumnSelector::Header("Amount".to_string()),
+                ColumnType::Number,
+            ),
+            Validation::Unique(ColumnSelector::Header("first".to_string())),
+        ]
+    };
+
+    let converter = ConverterBuilder::new()
+        .with_validations(validations())
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(
+            Cursor::new(fixtures::generate_order_lines().unwrap()),
+            &mut output,
+        )
+        .unwrap();
+    let messages = |sheet: usize| -> Vec<&str> {
+        report.sheets[sheet]
+            .warnings
+            .iter()
+            .filter(|w| w.kind == WarningKind::ValidationFailed)
+            .map(|w| w.message.as_str())
+            .collect()
+    };
+    // 違反は警告として記録し、出力は変わらない
+    assert_eq!(
+        messages(0),
+        ["column 'Amount' (C): values are not number (1 row: 3)"]
+    );
+    assert_eq!(
+        messages(1),
+        ["column 'Amount' not found", "column 'first' not found"]
+    );
+    assert!(String::from_utf8(output)
+        .unwrap()
+        .contains("| Alan  | Turing   | TBD    |"));
+
+    // ValidationPolicy::Failの場合は変換をエラーにする
+    let result = ConverterBuilder::new()
+        .with_sheet_selector(SheetSelector::Name("Orders".to_string()))
+        .with_validations(validations())
+        .with_validation_policy(ValidationPolicy::Fail)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(fixtures::generate_order_lines().unwrap()));
+    match result {
+        Err(XlsxToMdError::Validation { sheet, message }) => {
+            assert_eq!(sheet, "Orders");
+            assert!(message.contains("values are not number"));
+        }
+        other => panic!("Expected Validation error, got {:?}", other),
+    }
+}
+
+// TC-I-071: Many sheets are emitted in workbook order by every output path
+#[test]
+fn test_many_sheets_ordering() {
+    const SHEETS: u32 = 32;
+    let input = fixtures::generate_many_sheets(SHEETS).unwrap();
+    let names: Vec<String> = (0..SHEETS).map(|i| format!("S{:03}", i)).collect();
+    let converter = ConverterBuilder::new().build().unwrap();
+
+    // 変換を繰り返しても、シートの見出しは常にワークブックの順
+    for _ in 0..3 {
+        let markdown = converter
+            .convert_to_string(Cursor::new(input.clone()))
+            .unwrap();
+        let headings: Vec<&str> = markdown
+            .lines()
+            .filter_map(|line| line.strip_prefix("# "))
+            .collect();
+        assert_eq!(headings, names);
+    }
+
+    // シート単位の出力先にも出力順に渡される
+    struct OrderSink(Vec<(usize, String)>);
+    impl OutputSink for OrderSink {
+        fn write_sheet(&mut self, doc: &SheetDocument<'_>) -> Result<(), XlsxToMdError> {
+            assert!(doc.content.contains(&format!("{}R0", doc.name)));
+            self.0.push((doc.index, doc.name.to_string()));
+            Ok(())
+        }
+    }
+    let mut sink = OrderSink(Vec::new());
+    let report = converter
+        .convert_to_sink(Cursor::new(input.clone()), &mut sink)
+        .unwrap();
+    let expected: Vec<(usize, String)> = names.iter().cloned().enumerate().collect();
+    assert_eq!(sink.0, expected);
+    let reported: Vec<&str> = report.sheets.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(reported, names);
+
+    let grids = converter.convert_to_grids(Cursor::new(input)).unwrap();
+    let grid_names: Vec<&str> = grids.iter().map(|g| g.name()).collect();
+    assert_eq!(grid_names, names);
+}
+
+// TC-I-072: Per-sheet limits truncate only the offending sheets
+#[test]
+fn test_per_sheet_limits() {
+    use std::time::Duration;
+
+    // S000は6行、S001は4行、S002は2行（各行2セル）
+    let input = fixtures::generate_many_sheets(3).unwrap();
+    let convert = |rows: usize, cells: usize, duration: Duration| {
+        let converter = ConverterBuilder::new()
+            .with_per_sheet_limits(rows, cells, duration)
+            .build()
+            .unwrap();
+        let mut output = Vec::new();
+        let report = converter
+            .convert_with_report(Cursor::new(input.clone()), &mut output)
+            .unwrap();
+        let warnings: Vec<String> = report
+            .warnings
+            .iter()
+            .filter(|w| w.kind == WarningKind::SheetTruncated)
+            .map(|w| w.to_string())
+            .collect();
+        (String::from_utf8(output).unwrap(), warnings)
+    };
+
+    // 行数の上限
+    let (markdown, warnings) = convert(3, usize::MAX, Duration::MAX);
+    assert_eq!(
+        warnings,
+        [
+            "Sheet 'S000': sheet truncated after 3 of 6 rows: exceeded the per-sheet limit of 3 rows",
+            "Sheet 'S001': sheet truncated after 3 of 4 rows: exceeded the per-sheet limit of 3 rows",
+        ]
+    );
+    assert!(markdown.contains("| S000R2 | 2   |"));
+    assert!(!markdown.contains("S000R3"));
+    assert!(markdown
+        .contains("> Sheet truncated after 3 of 6 rows: exceeded the per-sheet limit of 3 rows."));
+    // 上限に収まるシートはそのまま
+    assert!(markdown.contains("| S002R1 | 1   |"));
+
+    // セル数の上限（上限を超える行以降を打ち切る）
+    let (markdown, warnings) = convert(usize::MAX, 5, Duration::MAX);
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0]
+        .ends_with("truncated after 2 of 6 rows: exceeded the per-sheet limit of 5 cells"));
+    assert!(warnings[1]
+        .ends_with("truncated after 2 of 4 rows: exceeded the per-sheet limit of 5 cells"));
+    assert!(markdown.contains("| S002R1 | 1   |"));
+
+    // 処理時間の上限（すべてのシートで超えるため、どのシートも行を出力しない）
+    let (markdown, warnings) = convert(usize::MAX, usize::MAX, Duration::from_nanos(1));
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0]
+        .ends_with("truncated after 0 of 6 rows: exceeded the per-sheet time limit of 1ns"));
+    assert!(!markdown.contains("S000R0"));
+
+    // CSVではコメント行として注記を付加
+    let csv = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .with_sheet_selector(SheetSelector::Name("S001".to_string()))
+        .with_per_sheet_limits(2, usize::MAX, Duration::MAX)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(input))
+        .unwrap();
+    assert_eq!(
+        csv,
+        "# Sheet: S001\n\nS001R0,0\nS001R1,1\n# Sheet truncated after 2 of 4 rows: exceeded the per-sheet limit of 2 rows.\n"
+    );
+}
+
+// TC-I-073: In-memory document model matching the JSON output
+#[test]
+fn test_convert_to_model() {
+    let excel_data = fixtures::formats_matrix().unwrap();
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_key_mode(JsonKeyMode::Header)
+        .build()
+        .unwrap();
+    let model = converter
+        .convert_to_model(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    assert_eq!(model.sheets.len(), 1);
+    let sheet = &model.sheets[0];
+    assert_eq!(sheet.name, "Sheet1");
+    let keys: Vec<&str> = sheet.columns.iter().map(|c| c.key.as_str()).collect();
+    assert_eq!(keys, ["Format", "Value"]);
+    let first = &sheet.rows[0].cells;
+    assert_eq!(first[0].key, "Format");
+    assert_eq!(first[1].column, "B");
+    assert!(matches!(first[1].value, CellValue::Number(_)));
+
+    // JSON形式の出力と同じ行・セルの内容
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .with_json_key_mode(JsonKeyMode::Header)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let json_rows = json["rows"].as_array().unwrap();
+    assert_eq!(json_rows.len(), sheet.rows.len());
+    for (json_row, row) in json_rows.iter().zip(&sheet.rows) {
+        for cell in &row.cells {
+            let expected = match cell.raw_number() {
+                Some(n) => serde_json::json!(n),
+                None => serde_json::json!(cell.formatted),
+            };
+            assert_eq!(json_row[&cell.key].as_f64(), expected.as_f64());
+            assert_eq!(json_row[&cell.key].as_str(), expected.as_str());
+        }
+    }
+
+    // serdeでシリアライズ・デシリアライズできる
+    let serialized = serde_json::to_string(&model).unwrap();
+    assert!(serialized.contains(r#""value":{"number":"#));
+    let restored: xlsxzero::WorkbookModel = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(restored, model);
+}
+
+// TC-I-074: Downloading and converting remote workbooks
+#[test]
+fn test_convert_url() {
+    use std::future::Future;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    // 1回だけワークブックを返すHTTPサーバー
+    let serve = |body: Vec<u8>| {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(&body);
+        });
+        format!("http://{}/book.xlsx", addr)
+    };
+    let excel_data = fixtures::simple_table().unwrap();
+    let expected = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+
+    let converter = ConverterBuilder::new().build().unwrap();
+    let markdown = converter.convert_url(&serve(excel_data.clone())).unwrap();
+    assert_eq!(markdown, expected);
+
+    // 非同期版（ランタイムに依存しないため、最小限のexecutorで待機）
+    struct ThreadWaker(std::thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+    let url = serve(excel_data.clone());
+    let mut future = Box::pin(converter.convert_url_async(&url));
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let markdown = loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => break result.unwrap(),
+            Poll::Pending => std::thread::park(),
+        }
+    };
+    assert_eq!(markdown, expected);
+
+    // 入力サイズの上限はダウンロード時に適用
+    let result = ConverterBuilder::new()
+        .with_max_input_size(100)
+        .build()
+        .unwrap()
+        .convert_url(&serve(excel_data));
+    assert!(matches!(result, Err(XlsxToMdError::SecurityViolation(_))));
+}
+
+// TC-I-075: Output formats registered by plugins
+#[test]
+fn test_format_plugins() {
+    use std::io::Write;
+    use xlsxzero::linkme::distributed_slice;
+    use xlsxzero::{FormatCapabilities, FormatPlugin, SheetModel, FORMAT_PLUGINS};
+
+    /// 行をタブ区切りで出力し、結合範囲を`(行数x列数)`で示すフォーマット
+    struct Spans {
+        name: &'static str,
+        capabilities: FormatCapabilities,
+    }
+
+    impl FormatPlugin for Spans {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn capabilities(&self) -> FormatCapabilities {
+            self.capabilities
+        }
+
+        fn render(&self, sheet: &SheetModel, writer: &mut dyn Write) -> Result<(), XlsxToMdError> {
+            if self.capabilities.needs_header_row {
+                let headers: Vec<&str> = sheet.columns.iter().map(|c| c.header.as_str()).collect();
+                writeln!(writer, "H\t{}", headers.join("\t"))?;
+            }
+            for row in &sheet.rows {
+                let cells: Vec<String> = row
+                    .cells
+                    .iter()
+                    .map(|cell| match (cell.row_span, cell.col_span) {
+                        (1, 1) => cell.formatted.clone(),
+                        (rows, cols) => format!("{}({}x{})", cell.formatted, rows, cols),
+                    })
+                    .collect();
+                writeln!(writer, "R\t{}", cells.join("\t"))?;
+            }
+            Ok(())
+        }
+    }
+
+    // リンク時の登録
+    #[distributed_slice(FORMAT_PLUGINS)]
+    #[linkme(crate = xlsxzero::linkme)]
+    static TSV_SPANS: &dyn FormatPlugin = &Spans {
+        name: "tsv-spans",
+        capabilities: FormatCapabilities::new()
+            .with_merged_cells(true)
+            .with_header_row(true)
+            .with_layout(OutputFormat::Csv),
+    };
+
+    // 実行時の登録
+    xlsxzero::register_format(Spans {
+        name: "tsv-flat",
+        capabilities: FormatCapabilities::new(),
+    })
+    .unwrap();
+    let names: Vec<String> = xlsxzero::registered_formats()
+        .iter()
+        .map(|plugin| plugin.name().to_string())
+        .collect();
+    assert!(names.contains(&"tsv-spans".to_string()));
+    assert!(names.contains(&"tsv-flat".to_string()));
+
+    let excel_data = fixtures::generate_vertical_merge().unwrap();
+    let convert = |name: &str| {
+        ConverterBuilder::new()
+            .with_format_name(name)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // 結合セルに対応するフォーマットは見出し行と結合範囲を受け取り、CSVのレイアウトで出力される
+    let spans = convert("TSV_Spans");
+    assert!(spans.starts_with("# Sheet: Sheet1\n\nH\tCategory\tItem\n"));
+    assert!(spans.contains("R\tFruit(3x1)\tApple\nR\tBanana\n"));
+    // 結合セルに対応しないフォーマットは、値を複製したすべてのセルを受け取る
+    let flat = convert("tsv-flat");
+    assert!(flat.starts_with("# Sheet1\n\nR\tCategory\tItem\n"));
+    assert!(flat.contains("R\tFruit\tBanana\nR\tFruit\tCherry\n"));
+
+    // 組み込みのフォーマットも名前で指定でき、未知の名前はエラー
+    let json = convert("json");
+    assert!(json.trim_start().starts_with('{'));
+    assert!(matches!(
+        ConverterBuilder::new()
+            .with_format_name("no-such-format")
+            .build(),
+        Err(XlsxToMdError::Config(_))
+    ));
+}
+
+// TC-I-076: Removing blank separator rows with markers of the original row numbers
+#[test]
+fn test_blank_rows_removed_with_marker() {
+    // 2行目から始まる表（4行目と7〜8行目が空行）
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.write_string(1, 0, "Name").unwrap();
+    worksheet.write_string(1, 1, "Price").unwrap();
+    worksheet.write_string(2, 0, "Apple").unwrap();
+    worksheet.write_number(2, 1, 100).unwrap();
+    worksheet.write_string(4, 0, "Pear").unwrap();
+    worksheet.write_number(4, 1, 120).unwrap();
+    worksheet.write_string(5, 0, "Plum").unwrap();
+    worksheet.write_number(5, 1, 90).unwrap();
+    worksheet.write_string(8, 0, "Total").unwrap();
+    worksheet.write_number(8, 1, 310).unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let convert = |mode: BlankRows, format: OutputFormat| {
+        let mut output = Vec::new();
+        let report = ConverterBuilder::new()
+            .with_blank_rows(mode)
+            .with_output_format(format)
+            .build()
+            .unwrap()
+            .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), report)
+    };
+
+    let (markdown, report) = convert(BlankRows::RemoveWithMarker, OutputFormat::Markdown);
+    assert_eq!(report.sheets[0].removed_blank_rows, 3);
+    assert!(markdown.contains("| Apple | 100   |\n| Pear  | 120   |"));
+    assert!(markdown.contains("<!-- removed blank rows: 4, 7-8 -->"));
+
+    // 除去のみの場合は注記を出力しない
+    let (markdown, report) = convert(BlankRows::Remove, OutputFormat::Markdown);
+    assert_eq!(report.sheets[0].removed_blank_rows, 3);
+    assert!(!markdown.contains("removed blank rows"));
+
+    let (csv, _) = convert(BlankRows::RemoveWithMarker, OutputFormat::Csv);
+    assert!(csv.contains("# removed blank rows: 4, 7-8\n"));
+
+    let (json, _) = convert(BlankRows::RemoveWithMarker, OutputFormat::Json);
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["removed_blank_rows"], serde_json::json!([4, 7, 8]));
+    assert_eq!(json["rows"].as_array().unwrap().len(), 5);
+
+    // 既定では空行をそのまま出力
+    let (markdown, report) = convert(BlankRows::Keep, OutputFormat::Markdown);
+    assert_eq!(report.sheets[0].removed_blank_rows, 0);
+    assert!(!markdown.contains("removed blank rows"));
+}
+
+// TC-I-077: Merged regions exceeding the expansion limit fall back to the top-left cell
+#[test]
+fn test_merged_region_expansion_limit() {
+    // 2セル（A1:B1）と40セル（A2:D11）の結合範囲（E11までのデータ範囲に収まる）
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet
+        .merge_range(0, 0, 0, 1, "Title", &Format::new())
+        .unwrap();
+    worksheet
+        .merge_range(1, 0, 10, 3, "Body", &Format::new())
+        .unwrap();
+    worksheet.write_string(10, 4, "End").unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let convert = |builder: ConverterBuilder| {
+        let mut output = Vec::new();
+        let report = builder
+            .build()
+            .unwrap()
+            .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), report)
+    };
+
+    let (markdown, report) = convert(ConverterBuilder::new().with_max_merged_cells(10));
+    let warnings: Vec<_> = report
+        .warnings
+        .iter()
+        .filter(|w| w.kind == WarningKind::MergedRegionLimit)
+        .collect();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].message.contains("A2:D11"));
+    assert!(warnings[0].message.contains("40 cells (max: 10)"));
+    // 上限以下の結合範囲は複製し、上限を超える結合範囲は左上のセルのみに値を残す
+    assert!(markdown.contains("| Title | Title |"));
+    assert_eq!(markdown.matches("Body").count(), 1);
+
+    // 既定の上限では複製する
+    let (markdown, report) = convert(ConverterBuilder::new());
+    assert!(report
+        .warnings
+        .iter()
+        .all(|w| w.kind != WarningKind::MergedRegionLimit));
+    assert_eq!(markdown.matches("Body").count(), 40);
+}
+
+// TC-I-078: Zero-height rows and zero-width columns treated as hidden
+#[test]
+fn test_zero_size_as_hidden() {
+    let excel_data = fixtures::generate_zero_size().unwrap();
+
+    let convert = |builder: ConverterBuilder| {
+        let mut output = Vec::new();
+        let report = builder
+            .build()
+            .unwrap()
+            .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), report)
+    };
+
+    let (markdown, report) = convert(ConverterBuilder::new().with_zero_size_as_hidden(true));
+    assert!(!markdown.contains("Draft"));
+    assert!(!markdown.contains("Cost"));
+    assert!(markdown.contains("| Pear  | 120   |"));
+    assert_eq!(report.sheets[0].zero_size_rows, 1);
+    assert_eq!(report.sheets[0].zero_size_cols, 1);
+
+    // 既定では高さ・幅が0の行・列も出力に含める
+    let (markdown, report) = convert(ConverterBuilder::new());
+    assert!(markdown.contains("Draft"));
+    assert!(markdown.contains("Cost"));
+    assert_eq!(report.sheets[0].zero_size_rows, 0);
+    assert_eq!(report.sheets[0].zero_size_cols, 0);
+
+    // 非表示要素を含める場合は除外しない
+    let (markdown, _) = convert(
+        ConverterBuilder::new()
+            .with_zero_size_as_hidden(true)
+            .include_hidden(true),
+    );
+    assert!(markdown.contains("Draft"));
+}
+
+// TC-I-079: Legacy .xls (BIFF) workbooks convert cell values, dates, and merged cells
+#[test]
+fn test_legacy_xls_conversion() {
+    let excel_data = fixtures::generate_legacy_xls();
+
+    let converter = ConverterBuilder::new().build().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_with_report(Cursor::new(excel_data.clone()), &mut output)
+        .unwrap();
+    let markdown = String::from_utf8(output).unwrap();
+    assert!(markdown.contains("# Legacy"));
+    // 日付はXLSXと同じく日付として出力し、結合セルの値は複製する
+    assert!(markdown.contains("| Fruit | 2024-01-02 | 3   |"));
+    assert!(markdown.contains("| Fruit | 2024-01-03 | 7   |"));
+    assert!(report
+        .warnings
+        .iter()
+        .any(|w| w.kind == WarningKind::LegacyFormat));
+
+    let info = converter.inspect(Cursor::new(excel_data)).unwrap();
+    assert_eq!(info.sheet_names, vec!["Legacy".to_string()]);
+    assert!(info.thumbnail.is_none());
+}
+
+// TC-I-080: Sheet-by-sheet conversion writes the same document without buffering the input
+#[test]
+fn test_convert_sheet_by_sheet() {
+    let input = fixtures::generate_many_sheets(4).unwrap();
+
+    // 1シートの変換が完了するたびにフラッシュされる
+    struct FlushCounter {
+        data: Vec<u8>,
+        flushed_sizes: Vec<usize>,
+    }
+    impl std::io::Write for FlushCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushed_sizes.push(self.data.len());
+            Ok(())
+        }
+    }
+
+    for format in [
+        OutputFormat::Markdown,
+        OutputFormat::Csv,
+        OutputFormat::Json,
+        OutputFormat::Html,
+    ] {
+        let converter = ConverterBuilder::new()
+            .with_output_format(format)
+            .build()
+            .unwrap();
+        let expected = converter
+            .convert_to_string(Cursor::new(input.clone()))
+            .unwrap();
+
+        let mut output = FlushCounter {
+            data: Vec::new(),
+            flushed_sizes: Vec::new(),
+        };
+        let report = converter
+            .convert_sheet_by_sheet(Cursor::new(input.clone()), &mut output)
+            .unwrap();
+        assert_eq!(String::from_utf8(output.data).unwrap(), expected);
+        assert_eq!(report.sheets.len(), 4);
+        assert_eq!(report.input_size, input.len() as u64);
+        assert_eq!(report.output_size, expected.len() as u64);
+        // 各シートの書き出し後にフラッシュされ、書き出した量は単調に増加する
+        output.flushed_sizes.dedup();
+        assert!(output.flushed_sizes.len() >= 4);
+    }
+
+    // 旧形式（XLS）も同じ文書になる
+    let legacy = fixtures::generate_legacy_xls();
+    let converter = ConverterBuilder::new().build().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_sheet_by_sheet(Cursor::new(legacy.clone()), &mut output)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        converter.convert_to_string(Cursor::new(legacy)).unwrap()
+    );
+    assert!(report
+        .warnings
+        .iter()
+        .any(|w| w.kind == WarningKind::LegacyFormat));
+
+    // すべてのシートを必要とする設定は、すべてのシートを変換してから出力する
+    let duplicates = fixtures::generate_duplicate_sheets().unwrap();
+    for builder in [
+        ConverterBuilder::new().with_duplicate_sheets(DuplicateSheets::Skip),
+        ConverterBuilder::new()
+            .with_max_output_size(64)
+            .with_output_overflow(OutputOverflow::Truncate),
+        ConverterBuilder::new().include_metadata(true),
+    ] {
+        let converter = builder.build().unwrap();
+        let mut output = Vec::new();
+        let report = converter
+            .convert_sheet_by_sheet(Cursor::new(duplicates.clone()), &mut output)
+            .unwrap();
+        let mut expected = Vec::new();
+        let expected_report = converter
+            .convert_with_report(Cursor::new(duplicates.clone()), &mut expected)
+            .unwrap();
+        assert_eq!(output, expected);
+        assert_eq!(report.duplicate_sheets, expected_report.duplicate_sheets);
+    }
+
+    // 入力サイズの上限は読み込まずに判定する
+    let converter = ConverterBuilder::new()
+        .with_max_input_size(16)
+        .build()
+        .unwrap();
+    let result = converter.convert_sheet_by_sheet(Cursor::new(input), Vec::new());
+    assert!(matches!(result, Err(XlsxToMdError::SecurityViolation(_))));
+}
+
+// TC-I-108: Row-streamed CSV and Markdown sheets match the buffered conversion
+#[test]
+fn test_convert_sheet_by_sheet_streams_rows() {
+    let inputs = [
+        fixtures::simple_table().unwrap(),
+        fixtures::multi_sheets().unwrap(),
+        fixtures::merged_cells().unwrap(),
+        fixtures::hidden_elements().unwrap(),
+        fixtures::hyperlinks().unwrap(),
+        fixtures::rich_text().unwrap(),
+        fixtures::formats_matrix().unwrap(),
+        fixtures::generate_formulas().unwrap(),
+        fixtures::generate_uncalculated_formulas().unwrap(),
+        fixtures::generate_date_time_table().unwrap(),
+        fixtures::generate_error_cells().unwrap(),
+        fixtures::generate_cell_image().unwrap(),
+        fixtures::generate_offset_table().unwrap(),
+        fixtures::generate_zero_size().unwrap(),
+        fixtures::generate_hidden_sheets().unwrap(),
+        fixtures::generate_font_styles().unwrap(),
+    ];
+    let builders = || {
+        [
+            ConverterBuilder::new(),
+            ConverterBuilder::new().include_hidden(true),
+            ConverterBuilder::new().with_zero_size_as_hidden(true),
+            ConverterBuilder::new().with_range_a1("B2:C4"),
+            ConverterBuilder::new().include_font_styles(true),
+            ConverterBuilder::new().with_cell_image_mode(CellImageMode::Value),
+        ]
+    };
+    for format in [OutputFormat::Markdown, OutputFormat::Csv] {
+        for builder in builders() {
+            let converter = builder.with_output_format(format).build().unwrap();
+            for input in &inputs {
+                let mut expected = Vec::new();
+                let expected_report = converter
+                    .convert_with_report(Cursor::new(input.clone()), &mut expected)
+                    .unwrap();
+                let mut output = Vec::new();
+                let report = converter
+                    .convert_sheet_by_sheet(Cursor::new(input.clone()), &mut output)
+                    .unwrap();
+                assert_eq!(
+                    String::from_utf8(output).unwrap(),
+                    String::from_utf8(expected).unwrap()
+                );
+                assert_eq!(report.output_size, expected_report.output_size);
+                for (sheet, expected) in report.sheets.iter().zip(&expected_report.sheets) {
+                    assert_eq!(
+                        (sheet.rows, sheet.cols, sheet.cells, sheet.output_size),
+                        (
+                            expected.rows,
+                            expected.cols,
+                            expected.cells,
+                            expected.output_size
+                        )
+                    );
+                }
+            }
+        }
+    }
+
+    // 使用範囲が肥大化したシートは、グリッドを構築して変換する
+    let input = fixtures::generate_bloated_used_range().unwrap();
+    let converter = ConverterBuilder::new().build().unwrap();
+    let mut output = Vec::new();
+    let report = converter
+        .convert_sheet_by_sheet(Cursor::new(input.clone()), &mut output)
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        converter.convert_to_string(Cursor::new(input)).unwrap()
+    );
+    assert!(report
+        .warnings
+        .iter()
+        .any(|w| w.kind == WarningKind::UsedRangeBloat));
+
+    // 行単位で出力したシートは、シート全体のセルデータを保持しない
+    let input = fixtures::large_table(2000, 10).unwrap();
+    for format in [OutputFormat::Markdown, OutputFormat::Csv] {
+        let converter = ConverterBuilder::new()
+            .with_output_format(format)
+            .build()
+            .unwrap();
+        let buffered = converter
+            .convert_with_report(Cursor::new(input.clone()), Vec::new())
+            .unwrap();
+        let streamed = converter
+            .convert_sheet_by_sheet(Cursor::new(input.clone()), Vec::new())
+            .unwrap();
+        assert!(streamed.sheets[0].memory_estimate * 100 < buffered.sheets[0].memory_estimate);
+    }
+}
+
+// TC-I-081: Per-sheet structured output without re-splitting the combined document
+#[test]
+fn test_convert_sheets() {
+    let input = fixtures::generate_many_sheets(3).unwrap();
+    let converter = ConverterBuilder::new()
+        .with_sheet_override("S002", OutputFormat::Csv)
+        .build()
+        .unwrap();
+
+    let sheets = converter.convert_sheets(Cursor::new(input)).unwrap();
+    let names: Vec<&str> = sheets.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["S000", "S001", "S002"]);
+    for (idx, sheet) in sheets.iter().enumerate() {
+        assert_eq!(sheet.index, idx);
+        // シート見出しや区切りは含まない
+        assert!(!sheet.content.contains("# S"));
+        assert!(!sheet.content.lines().any(|line| line == "---"));
+        assert!(sheet.content.contains(&format!("{}R1", sheet.name)));
+        assert_eq!(sheet.rows, (3 - idx) * 2);
+        assert_eq!(sheet.cols, 2);
+    }
+    assert_eq!(sheets[0].format, OutputFormat::Markdown);
+    assert_eq!(sheets[2].format, OutputFormat::Csv);
+    assert!(sheets[2].content.starts_with("S002R0,0"));
+}
+
+// TC-I-082: JSON records keyed by header names
+#[test]
+fn test_json_records() {
+    let excel_data = fixtures::generate_people().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        let output = builder
+            .with_output_format(OutputFormat::Json)
+            .with_json_document_mode(JsonDocumentMode::Records)
+            .with_json_key_mode(JsonKeyMode::Header)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap();
+        serde_json::from_str::<serde_json::Value>(&output).unwrap()
+    };
+
+    // 1シートの場合は行オブジェクトの配列のみ
+    let records = convert(
+        ConverterBuilder::new().with_sheet_selector(SheetSelector::Name("People".to_string())),
+    );
+    assert_eq!(
+        records,
+        serde_json::json!([{"Name": "Alice", "Age": 30}, {"Name": "Bob", "Age": 25}])
+    );
+
+    // 複数シートはシート名をキーとするオブジェクト（1行で出力しても同じ構造）
+    let expected = serde_json::json!({
+        "People": [{"Name": "Alice", "Age": 30}, {"Name": "Bob", "Age": 25}],
+        "Empty": [],
+    });
+    assert_eq!(convert(ConverterBuilder::new()), expected);
+    assert_eq!(
+        convert(ConverterBuilder::new().with_json_style(JsonStyle::Minified)),
+        expected
+    );
+}
+
+// TC-I-083: Original A1 cell references in JSON and Markdown output
+#[test]
+fn test_cell_references() {
+    let excel_data = fixtures::generate_offset_table().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .with_cell_references(true)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // Markdown: 列名の行と行番号の列（非表示行を除外しても元の行番号）
+    let markdown = convert(ConverterBuilder::new());
+    assert!(markdown.contains("|     | B     | C   |"));
+    assert!(markdown.contains("| 2   | Name  | Age |"));
+    assert!(markdown.contains("| 3   | Alice | 30  |"));
+    assert!(markdown.contains("| 5   | Bob   | 25  |"));
+    assert!(!markdown.contains("Hidden"));
+
+    // JSON: 並べ替えた後も元のセル参照を保持
+    let json = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_json_key_mode(JsonKeyMode::Header)
+            .with_sort(
+                ColumnSelector::Header("Age".to_string()),
+                SortOrder::Ascending,
+                true,
+            ),
+    );
+    let sheet: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        sheet["rows"][0],
+        serde_json::json!({
+            "Name": {"value": "Bob", "ref": "B5"},
+            "Age": {"value": 25, "ref": "C5"},
+        })
+    );
+
+    let json = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_json_cell_mode(JsonCellMode::Typed),
+    );
+    let sheet: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(sheet["rows"][1]["A"]["ref"], "B3");
+    assert_eq!(sheet["rows"][1]["B"]["raw"], 30);
+
+    // 既定ではセル参照を含めない
+    let json = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(!json.contains("\"ref\""));
+}
+
+// TC-I-084: Hidden rows and hyperlinks of renamed sheets are resolved through workbook.xml
+#[test]
+fn test_renamed_sheet_metadata() {
+    let excel_data = fixtures::generate_renamed_sheets().unwrap();
+
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(
+        markdown.contains("[docs](https://docs.rs)"),
+        "Got: {}",
+        markdown
+    );
+    assert!(markdown.contains("Visible"), "Got: {}", markdown);
+    assert!(!markdown.contains("Secret"), "Got: {}", markdown);
+}
+
+// TC-I-085: Number formats from the cell styles are applied to the output
+#[test]
+fn test_cell_number_formats() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    // 値の範囲がA1から始まらない場合も、セルのスタイルはシート上の位置で対応付ける
+    worksheet.write_string(1, 1, "Item").unwrap();
+    worksheet.write_string(1, 2, "Price").unwrap();
+    worksheet.write_string(1, 3, "Rate").unwrap();
+    worksheet.write_string(2, 1, "Widget").unwrap();
+    worksheet
+        .write_number_with_format(2, 2, 1234.5, &Format::new().set_num_format("$#,##0.00"))
+        .unwrap();
+    worksheet
+        .write_number_with_format(2, 3, 0.125, &Format::new().set_num_format("0.0%"))
+        .unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.contains("| Widget | $1,234.50 | 12.5% |"),
+        "Got: {}",
+        markdown
+    );
+
+    // 整数・桁区切り・パーセント・日付の書式
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(fixtures::formats_matrix().unwrap()))
+        .unwrap();
+    for expected in [
+        "| 0                | 1235 ",
+        "| #,##0.00         | 1,234.57 ",
+        "| 0.00%            | 12.34% ",
+        "| yyyy-mm-dd       | 2025-01-02 ",
+    ] {
+        assert!(markdown.contains(expected), "Got: {}", markdown);
+    }
+
+    // 生の数値を出力する形式（CSV）には書式を適用しない
+    let csv = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Csv)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert!(csv.contains("Widget,1234.5,0.125"), "Got: {}", csv);
+}
+
+// TC-I-086: Converting only the region covered by a named range
+#[test]
+fn test_named_range() {
+    let mut workbook = Workbook::new();
+    workbook
+        .add_worksheet()
+        .set_name("Summary")
+        .unwrap()
+        .write_string(0, 0, "Overview")
+        .unwrap();
+    let worksheet = workbook.add_worksheet().set_name("Sales Data").unwrap();
+    worksheet.write_string(0, 0, "Report title").unwrap();
+    for (row, (region, amount)) in [("Region", "Amount"), ("East", "100"), ("West", "200")]
+        .into_iter()
+        .enumerate()
+    {
+        worksheet.write_string(row as u32 + 1, 1, region).unwrap();
+        worksheet.write_string(row as u32 + 1, 2, amount).unwrap();
+    }
+    worksheet.write_string(5, 0, "Footnote").unwrap();
+    workbook
+        .define_name("SalesTable", "='Sales Data'!$B$2:$C$4")
+        .unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    // 参照先のシートの範囲のみ（シートの選択より優先）
+    let markdown = ConverterBuilder::new()
+        .with_sheet_selector(SheetSelector::Index(0))
+        .with_named_range("salestable")
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(markdown.contains("# Sales Data"), "Got: {}", markdown);
+    assert!(
+        markdown.contains("| Region | Amount |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| West   | 200    |"),
+        "Got: {}",
+        markdown
+    );
+    for excluded in ["Overview", "Report title", "Footnote"] {
+        assert!(!markdown.contains(excluded), "Got: {}", markdown);
+    }
+
+    // 存在しない名前は設定エラー
+    let result = ConverterBuilder::new()
+        .with_named_range("Missing")
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data));
+    assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+}
+
+// TC-I-087: Restricting the range with A1 notation (workbook-wide and per sheet)
+#[test]
+fn test_range_a1() {
+    let mut workbook = Workbook::new();
+    for name in ["Sheet1", "Sheet2"] {
+        let worksheet = workbook.add_worksheet().set_name(name).unwrap();
+        for row in 0..4u32 {
+            for col in 0..4u16 {
+                let label = format!("{}-{}{}", name, (b'A' + col as u8) as char, row + 1);
+                worksheet.write_string(row, col, &label).unwrap();
+            }
+        }
+    }
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    // Sheet1はB2:C3、Sheet2はシート単位の指定A1:A2
+    let markdown = ConverterBuilder::new()
+        .with_range_a1("$B$2:$C$3")
+        .with_range_a1("Sheet2!A1:A2")
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    for included in ["Sheet1-B2", "Sheet1-C3", "Sheet2-A1", "Sheet2-A2"] {
+        assert!(markdown.contains(included), "Got: {}", markdown);
+    }
+    for excluded in ["Sheet1-A1", "Sheet1-D4", "Sheet2-B2", "Sheet2-A3"] {
+        assert!(!markdown.contains(excluded), "Got: {}", markdown);
+    }
+
+    // with_range()と同じ範囲になる
+    let tuple = ConverterBuilder::new()
+        .with_range((1, 1), (2, 2))
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let a1 = ConverterBuilder::new()
+        .with_range_a1("B2:C3")
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert_eq!(tuple, a1);
+
+    // 解析できない指定はbuild()時の設定エラー
+    for invalid in ["B2:", "Sheet2!", "ZZZZ1"] {
+        let result = ConverterBuilder::new().with_range_a1(invalid).build();
+        assert!(
+            matches!(result, Err(XlsxToMdError::Config(_))),
+            "{invalid:?} should be rejected"
+        );
+    }
+}
+
+// TC-I-088: Trimming empty leading/trailing rows and columns
+#[test]
+fn test_trim_empty() {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    // 内容を消去した（空白のみが残った）セルで使用範囲がA1:F10に広がったシート
+    worksheet.write_string(0, 0, " ").unwrap();
+    worksheet.write_string(9, 5, " ").unwrap();
+    worksheet.write_string(2, 1, "Name").unwrap();
+    worksheet.write_string(2, 2, "Price").unwrap();
+    worksheet.write_string(4, 1, "Apple").unwrap();
+    worksheet.write_number(4, 2, 100).unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let convert = |mode: TrimMode| {
+        ConverterBuilder::new()
+            .trim_empty(mode)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+    let table_rows = |markdown: &str| markdown.lines().filter(|l| l.starts_with('|')).count();
+
+    let untrimmed = convert(TrimMode::None);
+    assert_eq!(table_rows(&untrimmed), 11, "Got: {}", untrimmed);
+
+    // 末尾のみ除去（A1からの位置は維持）
+    let trailing = convert(TrimMode::Trailing);
+    assert_eq!(table_rows(&trailing), 6, "Got: {}", trailing);
+    assert!(
+        trailing.contains("|     | Name  | Price |"),
+        "Got: {}",
+        trailing
+    );
+
+    // 先頭・末尾と表の途中の空行を除去
+    let all = convert(TrimMode::All);
+    assert!(
+        all.contains("| Name  | Price |\n|-------|-------|\n| Apple | 100   |\n"),
+        "Got: {}",
+        all
+    );
+    assert_eq!(table_rows(&all), 3, "Got: {}", all);
+}
+
+// TC-I-089: Capping output rows/cells with a truncation notice
+#[test]
+fn test_max_rows_and_cells() {
+    // 1,300行 × 2列
+    let input = fixtures::large_table(1300, 2).unwrap();
+    let convert = |builder: ConverterBuilder| {
+        let mut output = Vec::new();
+        let report = builder
+            .build()
+            .unwrap()
+            .convert_with_report(Cursor::new(input.clone()), &mut output)
+            .unwrap();
+        (String::from_utf8(output).unwrap(), report)
+    };
+
+    // 行数の上限（見出しを含む）
+    let (markdown, report) = convert(ConverterBuilder::new().with_max_rows(3));
+    assert!(markdown.contains("| R3C1 | R3C2 |"), "Got: {}", markdown);
+    assert!(!markdown.contains("R4C1"), "Got: {}", markdown);
+    assert!(
+        markdown.contains("| R3C1 | R3C2 |\n\n> … 1,297 more rows omitted\n"),
+        "Got: {}",
+        markdown
+    );
+    assert_eq!(report.sheets[0].omitted_rows, 1297);
+    assert!(report.warnings.is_empty());
+
+    // セル数の上限（上限を超える行以降を省略）、より少ない行数になる上限を適用
+    let (markdown, report) = convert(ConverterBuilder::new().with_max_rows(100).with_max_cells(5));
+    assert!(markdown.contains("| R2C1 | R2C2 |"), "Got: {}", markdown);
+    assert!(!markdown.contains("R3C1"), "Got: {}", markdown);
+    assert_eq!(report.sheets[0].omitted_rows, 1298);
+
+    // 注記の変更（CSVではコメント行）と、上限に収まる場合
+    let (csv, _) = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .with_max_rows(2)
+            .with_truncation_notice("({count} rows not shown)"),
+    );
+    assert_eq!(
+        csv,
+        "# Sheet: Sheet1\n\nR1C1,R1C2\nR2C1,R2C2\n# (1,298 rows not shown)\n"
+    );
+    let (markdown, report) = convert(ConverterBuilder::new().with_max_rows(5000));
+    assert!(!markdown.contains("omitted"));
+    assert_eq!(report.sheets[0].omitted_rows, 0);
+
+    // 不正な上限・注記は設定エラー
+    for builder in [
+        ConverterBuilder::new().with_max_rows(0),
+        ConverterBuilder::new().with_max_cells(0),
+        ConverterBuilder::new().with_truncation_notice("a\nb"),
+    ] {
+        assert!(matches!(builder.build(), Err(XlsxToMdError::Config(_))));
+    }
+}
+
+// TC-I-090: Progress events per sheet and per N rows
+#[test]
+fn test_progress_events() {
+    use std::sync::Mutex;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorder = Arc::clone(&events);
+    let converter = ConverterBuilder::new()
+        .with_progress(move |event| {
+            let line = match event {
+                ProgressEvent::WorkbookLoaded { sheets } => format!("loaded {}", sheets),
+                ProgressEvent::SheetStarted { sheet, index } => {
+                    format!("started {} #{}", sheet, index)
+                }
+                ProgressEvent::RowsProcessed {
+                    sheet,
+                    rows,
+                    total_rows,
+                } => format!("rows {} {}/{}", sheet, rows, total_rows),
+                ProgressEvent::SheetFinished {
+                    sheet, index, rows, ..
+                } => format!("finished {} #{} {}", sheet, index, rows),
+                _ => unreachable!(),
+            };
+            recorder.lock().unwrap().push(line);
+        })
+        .with_progress_interval(1000)
+        .build()
+        .unwrap();
+
+    let input = fixtures::large_table(2500, 1).unwrap();
+    converter
+        .convert_to_string(Cursor::new(input.clone()))
+        .unwrap();
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "loaded 1",
+            "started Sheet1 #0",
+            "rows Sheet1 1000/2500",
+            "rows Sheet1 2000/2500",
+            "finished Sheet1 #0 2500",
+        ]
+    );
+
+    // グリッドの取得でも完了を通知する
+    events.lock().unwrap().clear();
+    converter.convert_to_grids(Cursor::new(input)).unwrap();
+    assert_eq!(
+        events.lock().unwrap().last().map(String::as_str),
+        Some("finished Sheet1 #0 2500")
+    );
+
+    // 複数のシートは並列に処理されても、シートごとに開始と完了を通知する
+    events.lock().unwrap().clear();
+    let input = fixtures::generate_many_sheets(3).unwrap();
+    converter.convert_to_string(Cursor::new(input)).unwrap();
+    let events = events.lock().unwrap();
+    assert_eq!(events[0], "loaded 3");
+    for (index, name) in ["S000", "S001", "S002"].iter().enumerate() {
+        let started = format!("started {} #{}", name, index);
+        let finished = format!("finished {} #{} ", name, index);
+        let start = events.iter().position(|e| *e == started).unwrap();
+        let end = events
+            .iter()
+            .position(|e| e.starts_with(&finished))
+            .unwrap();
+        assert!(start < end, "{:?}", events);
+    }
+
+    assert!(matches!(
+        ConverterBuilder::new().with_progress_interval(0).build(),
+        Err(XlsxToMdError::Config(_))
+    ));
+}
+
+// TC-I-091: Async conversion from AsyncRead + AsyncSeek to AsyncWrite
+#[test]
+fn test_convert_async() {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()
+        .unwrap();
+    let input = fixtures::large_table(3000, 4).unwrap();
+    let converter = ConverterBuilder::new().build().unwrap();
+    let expected = converter
+        .convert_to_string(Cursor::new(input.clone()))
+        .unwrap();
+
+    // 出力は複数のチャンクに分けて書き込まれる
+    let mut output = Vec::new();
+    runtime
+        .block_on(converter.convert_async(Cursor::new(input.clone()), &mut output))
+        .unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+    // 入力サイズの上限は読み込む前に判定する
+    let limited = ConverterBuilder::new()
+        .with_max_input_size(1024)
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    let result = runtime.block_on(limited.convert_async(Cursor::new(input), &mut output));
+    assert!(matches!(result, Err(XlsxToMdError::SecurityViolation(_))));
+    assert!(output.is_empty());
+
+    // 不正な入力は変換のエラーを返す
+    let mut output = Vec::new();
+    let result =
+        runtime.block_on(converter.convert_async(Cursor::new(b"not a zip".to_vec()), &mut output));
+    assert!(result.is_err());
+}
+
+// TC-I-092: Batch conversion with per-file error collection
+#[test]
+fn test_batch_converter() {
+    let converter = ConverterBuilder::new().build().unwrap();
+    let simple = fixtures::simple_table().unwrap();
+    let large = fixtures::large_table(200, 3).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("a_simple.xlsx"), &simple).unwrap();
+    std::fs::write(dir.path().join("b_corrupt.xlsx"), b"not a zip").unwrap();
+    std::fs::write(dir.path().join("c_large.xlsx"), &large).unwrap();
+    std::fs::write(dir.path().join("notes.txt"), b"ignored").unwrap();
+
+    // 破損したファイルや存在しないファイルがあっても、他のファイルは変換される（結果は入力の順）
+    let batch = BatchConverter::new(converter.clone());
+    let results = batch.convert(vec![
+        BatchInput::from(dir.path().join("a_simple.xlsx")),
+        BatchInput::from(dir.path().join("b_corrupt.xlsx")),
+        BatchInput::from(dir.path().join("missing.xlsx")),
+        BatchInput::reader("large", Cursor::new(large.clone())),
+    ]);
+    assert_eq!(results.len(), 4);
+    let first = results[0].as_ref().unwrap();
+    assert_eq!(
+        first.output,
+        converter.convert_to_string(Cursor::new(simple)).unwrap()
+    );
+    assert!(first.name.ends_with("a_simple.xlsx"));
+    assert_eq!(first.report.sheets.len(), 1);
+    assert!(results[1].is_err());
+    assert!(matches!(results[2], Err(XlsxToMdError::Io(_))));
+    let last = results[3].as_ref().unwrap();
+    assert_eq!(last.name, "large");
+    assert_eq!(
+        last.output,
+        converter.convert_to_string(Cursor::new(large)).unwrap()
+    );
+
+    // ディレクトリ単位の変換では、ワークブックのみを変換してファイルに書き出す
+    let out = dir.path().join("out");
+    let results = batch.convert_dir(dir.path(), &out).unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[1].is_err());
+    let written = results[0].as_ref().unwrap().output_path.clone().unwrap();
+    assert_eq!(written, out.join("a_simple.md"));
+    assert_eq!(std::fs::read_to_string(&written).unwrap(), first.output);
+    assert!(out.join("c_large.md").exists());
+    assert!(!out.join("b_corrupt.md").exists());
+
+    // 拡張子のみが異なるワークブックは、元の拡張子を残したファイル名で別々に書き出す
+    let dup_dir = tempfile::tempdir().unwrap();
+    std::fs::copy(
+        dir.path().join("a_simple.xlsx"),
+        dup_dir.path().join("report.xlsx"),
+    )
+    .unwrap();
+    std::fs::copy(
+        dir.path().join("c_large.xlsx"),
+        dup_dir.path().join("report.XLSM"),
+    )
+    .unwrap();
+    let out = dup_dir.path().join("out");
+    let results = batch.convert_dir(dup_dir.path(), &out).unwrap();
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(Result::is_ok));
+    assert!(!out.join("report.md").exists());
+    assert_eq!(
+        std::fs::read_to_string(out.join("report.xlsx.md")).unwrap(),
+        first.output
+    );
+    assert_eq!(
+        std::fs::read_to_string(out.join("report.XLSM.md")).unwrap(),
+        last.output
+    );
+}
+
+// TC-I-093: Cell comments inline and as footnotes
+#[test]
+fn test_include_comments() {
+    let excel_data = fixtures::generate_comments().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // コメントはデフォルトでは出力しない
+    let output = convert(ConverterBuilder::new());
+    assert!(!output.contains("Includes tax"), "Got: {}", output);
+
+    // インライン: セルの末尾に作成者名付きで付加（非表示の行のコメントは出力しない）
+    let output = convert(ConverterBuilder::new().include_comments(CommentMode::Inline));
+    assert!(
+        output.contains("| 1200 💬 Alice: Includes tax |"),
+        "Got: {}",
+        output
+    );
+    assert!(
+        output.contains("| Power 💬 Bob: Estimate \\| to be confirmed |"),
+        "Got: {}",
+        output
+    );
+    assert!(!output.contains("Hidden note"), "Got: {}", output);
+
+    // 脚注: セルには参照、本文はシート末尾の脚注セクション
+    let output = convert(ConverterBuilder::new().include_comments(CommentMode::Footnotes));
+    assert!(output.contains("| 1200[^1] |"), "Got: {}", output);
+    assert!(output.contains("| Power[^2] |"), "Got: {}", output);
+    assert!(
+        output.ends_with("[^1]: Alice: Includes tax\n[^2]: Bob: Estimate | to be confirmed\n"),
+        "Got: {}",
+        output
+    );
+
+    // Markdown以外ではインラインと同じ
+    let output = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .include_comments(CommentMode::Footnotes),
+    );
+    assert!(
+        output.contains("1200 💬 Alice: Includes tax"),
+        "Got: {}",
+        output
+    );
+}
+
+// TC-I-094: Data validation lists as header annotations and JSON metadata
+#[test]
+fn test_data_validations() {
+    let excel_data = fixtures::generate_data_validations().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .with_sheet_selector(SheetSelector::Name("Tasks".to_string()))
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // 入力規則はデフォルトでは出力しない
+    let output = convert(ConverterBuilder::new());
+    assert!(!output.contains("allowed"), "Got: {}", output);
+
+    // 見出しへの注記: 別の非表示のシートを参照するリストも解決する（数値の規則は注記しない）
+    let output = convert(ConverterBuilder::new().with_data_validations(DataValidations::Annotate));
+    for header in [
+        "| Status (allowed: Open, Closed, Pending) |",
+        "| Owner (allowed: Alice, Bob\\|Team, Carol) |",
+        "| Hours |",
+    ] {
+        assert!(output.contains(header), "Got: {}", output);
+    }
+
+    // JSON: シートの`data_validations`配列
+    let output = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_data_validations(DataValidations::Metadata),
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let validations = json["data_validations"].as_array().expect(&output);
+    assert_eq!(validations.len(), 3, "Got: {}", output);
+    assert_eq!(validations[0]["range"], "B2:B21");
+    assert_eq!(validations[0]["type"], "list");
+    assert_eq!(
+        validations[0]["allowed"],
+        serde_json::json!(["Open", "Closed", "Pending"])
+    );
+    assert_eq!(
+        validations[1]["allowed"],
+        serde_json::json!(["Alice", "Bob|Team", "Carol"])
+    );
+    assert_eq!(validations[2]["type"], "whole");
+    assert_eq!(validations[2]["operator"], "greaterThan");
+    assert_eq!(validations[2]["formulas"], serde_json::json!(["0"]));
+    // 見出しには注記しない
+    assert!(output.contains("\"Status\""), "Got: {}", output);
+}
+
+// TC-I-095: Conditional formatting markers on highlighted cells
+#[test]
+fn test_conditional_format_markers() {
+    let excel_data = fixtures::generate_conditional_formats().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .with_conditional_formats(ConditionalFormats::Marker)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // 該当するセルの末尾にマーカー（脚注は使わない）
+    let markdown = convert(ConverterBuilder::new());
+    assert!(
+        markdown.contains("| INV-1 ⚠ | 120    |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| INV-1 ⚠ | 300 ⚠  |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| INV-2   | 80     |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("[^"), "Got: {}", markdown);
+
+    // Markdown以外の出力フォーマットでも同じ表示
+    let csv = convert(ConverterBuilder::new().with_output_format(OutputFormat::Csv));
+    assert!(csv.contains("INV-1 ⚠,300 ⚠\n"), "Got: {}", csv);
+
+    // 型付きJSONでは表示文字列を変更せず、強調表示のフィールドを出力
+    let json = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_json_cell_mode(JsonCellMode::Typed),
+    );
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let rows = value["rows"].as_array().unwrap();
+    assert_eq!(rows[1]["A"]["formatted"], "INV-1");
+    assert_eq!(rows[1]["A"]["highlight"], true);
+    assert_eq!(rows[1]["A"]["highlights"][0], "duplicate value in A2:A5");
+    assert!(rows[2]["A"].get("highlight").is_none());
+    assert_eq!(rows[3]["B"]["highlight"], true);
+}
+
+// TC-I-096: Cell-level font styles in Markdown and HTML output
+#[test]
+fn test_font_styles() {
+    let excel_data = fixtures::generate_font_styles().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // デフォルトではフォントの書式を反映しない
+    let markdown = convert(ConverterBuilder::new());
+    assert!(!markdown.contains("**"), "Got: {}", markdown);
+
+    let markdown = convert(ConverterBuilder::new().include_font_styles(true));
+    assert!(
+        markdown.contains("| **Item** | **Amount** |"),
+        "Got: {}",
+        markdown
+    );
+    assert!(markdown.contains("| ~~Rent~~ "), "Got: {}", markdown);
+    assert!(markdown.contains("| *Total* "), "Got: {}", markdown);
+    assert!(markdown.contains("| **1200** "), "Got: {}", markdown);
+
+    // HTML: 表示文字列は変更せず、class属性で出力
+    let html = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Html)
+            .include_font_styles(true),
+    );
+    assert!(
+        html.contains("<td class=\"bold\">Item</td>"),
+        "Got: {}",
+        html
+    );
+    assert!(
+        html.contains("<td class=\"strike\">Rent</td>"),
+        "Got: {}",
+        html
+    );
+    assert!(html.contains("<td>1200</td>"), "Got: {}", html);
+    assert!(!html.contains("**"), "Got: {}", html);
+}
+
+// TC-I-097: Cell background colors in HTML output
+#[test]
+fn test_fill_colors() {
+    let excel_data = fixtures::generate_fill_colors().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .with_output_format(OutputFormat::Html)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // デフォルトでは背景色を出力しない
+    let html = convert(ConverterBuilder::new());
+    assert!(!html.contains("background-color"), "Got: {}", html);
+
+    let html = convert(ConverterBuilder::new().include_fill_colors(true));
+    assert!(
+        html.contains("<td style=\"background-color:#FFFF00\">Overdue</td>"),
+        "Got: {}",
+        html
+    );
+    assert!(
+        html.contains("<td style=\"background-color:#DDEBF7\">Task</td>"),
+        "Got: {}",
+        html
+    );
+    assert!(html.contains("<td>Done</td>"), "Got: {}", html);
+
+    // フォントの書式と組み合わせた場合は、class属性とstyle属性の両方を出力
+    let html = convert(
+        ConverterBuilder::new()
+            .include_font_styles(true)
+            .include_fill_colors(true),
+    );
+    assert!(
+        html.contains("<td class=\"bold\" style=\"background-color:#DDEBF7\">Status</td>"),
+        "Got: {}",
+        html
+    );
+}
+
+// TC-I-098: Format-aware hyperlink output
+#[test]
+fn test_hyperlinks_per_format() {
+    let excel_data = fixtures::hyperlinks().unwrap();
+    let convert = |builder: ConverterBuilder| {
+        builder
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // HTML: <a>要素
+    let html = convert(ConverterBuilder::new().with_output_format(OutputFormat::Html));
+    assert!(
+        html.contains("<td><a href=\"https://rust-lang.org\">Rust</a></td>"),
+        "Got: {}",
+        html
+    );
+    assert!(!html.contains("]("), "Got: {}", html);
+
+    // JSON: 表示テキストとURL
+    let json = convert(ConverterBuilder::new().with_output_format(OutputFormat::Json));
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value["rows"][2]["A"],
+        serde_json::json!({ "value": "Rust", "url": "https://rust-lang.org" })
+    );
+    assert_eq!(value["rows"][2]["B"], "Rust programming language");
+
+    // CSV: 表示テキストのみ、またはURLのみ
+    let csv = convert(ConverterBuilder::new().with_output_format(OutputFormat::Csv));
+    assert!(
+        csv.contains("\nRust,Rust programming language\n"),
+        "Got: {}",
+        csv
+    );
+    let csv = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Csv)
+            .with_hyperlink_mode(HyperlinkMode::UrlOnly),
+    );
+    assert!(
+        csv.contains("\nhttps://rust-lang.org,Rust programming language\n"),
+        "Got: {}",
+        csv
+    );
+
+    // TextOnly: JSONにもURLを出力しない
+    let json = convert(
+        ConverterBuilder::new()
+            .with_output_format(OutputFormat::Json)
+            .with_hyperlink_mode(HyperlinkMode::TextOnly),
+    );
+    assert!(!json.contains("\"url\""), "Got: {}", json);
+
+    // Markdownは従来どおりMarkdownリンク
+    let markdown = convert(ConverterBuilder::new());
+    assert!(
+        markdown.contains("[Rust](https://rust-lang.org)"),
+        "Got: {}",
+        markdown
+    );
+}
+
+// TC-I-099: Pictures placed over cells are extracted and referenced at their anchor cells
+#[test]
+fn test_drawing_images() {
+    use std::sync::Mutex;
+
+    let excel_data = fixtures::generate_drawing_images().unwrap();
+
+    // デフォルトでは画像を抽出しない
+    let markdown = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(!markdown.contains("!["), "Got: {}", markdown);
+
+    // コールバックで受け取る場合は`images/`のパスを出力
+    let extracted = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&extracted);
+    let markdown = ConverterBuilder::new()
+        .with_image_handler(move |image| {
+            sink.lock().unwrap().push((
+                image.cell.to_string(),
+                image.path.to_string(),
+                image.source.to_string(),
+                image.data.to_vec(),
+            ));
+            Ok(())
+        })
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(
+        markdown.contains("| Chair   | ![Front view](images/sheet1_img1.png) |"),
+        "Got: {}",
+        markdown
+    );
+    // 使用範囲の右側の画像は表を広げて出力
+    assert!(
+        markdown.contains("| ![](images/sheet1_img2.png) |"),
+        "Got: {}",
+        markdown
+    );
+    let extracted = extracted.lock().unwrap();
+    assert_eq!(extracted.len(), 2);
+    assert_eq!(extracted[0].0, "B2");
+    assert_eq!(extracted[0].1, "images/sheet1_img1.png");
+    assert!(
+        extracted[0].2.starts_with("xl/media/"),
+        "{:?}",
+        extracted[0].2
+    );
+    assert_eq!(extracted[0].3, fixtures::PNG);
+    assert_eq!(extracted[1].0, "D3");
+
+    // ディレクトリに書き出す場合は、指定したディレクトリのパスを出力
+    let dir = tempfile::tempdir().unwrap();
+    let images_dir = dir.path().join("images");
+    let html = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Html)
+        .extract_images_to(&images_dir)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert_eq!(
+        std::fs::read(images_dir.join("sheet1_img1.png")).unwrap(),
+        fixtures::PNG
+    );
+    assert!(images_dir.join("sheet1_img2.png").exists());
+    assert!(
+        html.contains(&format!(
+            "<img src=\"{}/sheet1_img1.png\" alt=\"Front view\">",
+            images_dir.to_string_lossy().replace('\\', "/")
+        )),
+        "Got: {}",
+        html
+    );
+}
+
+// TC-I-100: Charts are emitted as a placeholder or as a table of their cached series data
+#[test]
+fn test_charts() {
+    let excel_data = fixtures::generate_chart().unwrap();
+    let convert = |mode: ChartMode, format: OutputFormat| {
+        ConverterBuilder::new()
+            .with_output_format(format)
+            .include_charts(mode)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // デフォルトではグラフを出力しない
+    let markdown = convert(ChartMode::Skip, OutputFormat::Markdown);
+    assert!(!markdown.contains("Chart"), "Got: {}", markdown);
+
+    let markdown = convert(ChartMode::Placeholder, OutputFormat::Markdown);
+    assert!(
+        markdown.contains("\n> Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec\n"),
+        "Got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("|     | 2023 |"), "Got: {}", markdown);
+
+    let markdown = convert(ChartMode::Data, OutputFormat::Markdown);
+    assert!(
+        markdown.contains(
+            "|     | 2023 | 2024 |\n|-----|------|------|\n| Jan | 120  | 150  |\n| Dec | 130  | 170  |\n"
+        ),
+        "Got: {}",
+        markdown
+    );
+
+    let html = convert(ChartMode::Data, OutputFormat::Html);
+    assert!(
+        html.contains("<p>Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec</p>"),
+        "Got: {}",
+        html
+    );
+    assert_eq!(html.matches("<table>").count(), 2, "Got: {}", html);
+
+    // CSVでは注記のみ、JSONでは何も出力しない
+    let csv = convert(ChartMode::Data, OutputFormat::Csv);
+    assert!(
+        csv.ends_with("# Chart: 'Revenue by Quarter' — series: 2023, 2024 over Jan..Dec\n"),
+        "Got: {}",
+        csv
+    );
+    let json = convert(ChartMode::Data, OutputFormat::Json);
+    assert!(!json.contains("Chart"), "Got: {}", json);
+
+    // 範囲制限の外側に配置したグラフは出力しない
+    let markdown = ConverterBuilder::new()
+        .with_range_a1("A1:C3")
+        .include_charts(ChartMode::Placeholder)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    assert!(!markdown.contains("Chart"), "Got: {}", markdown);
+}
+
+// TC-I-101: Pivot tables can render their cached source data as a separate table
+#[test]
+fn test_pivot_tables() {
+    let excel_data = fixtures::generate_pivot_table().unwrap();
+    let convert = |mode: PivotMode, format: OutputFormat| {
+        ConverterBuilder::new()
+            .with_output_format(format)
+            .include_pivot_tables(mode)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(excel_data.clone()))
+            .unwrap()
+    };
+
+    // デフォルトではピボットテーブルの集計結果のセルのみを出力
+    let markdown = convert(PivotMode::Cells, OutputFormat::Markdown);
+    assert!(markdown.contains("| East       | 150"), "Got: {}", markdown);
+    assert!(!markdown.contains("Pivot:"), "Got: {}", markdown);
+
+    let markdown = convert(PivotMode::SourceData, OutputFormat::Markdown);
+    assert!(markdown.contains("| East       | 150"), "Got: {}", markdown);
+    assert!(
+        markdown.ends_with(
+            "\n## Pivot: SalesPivot\n\n\
+             | Region | Amount |\n\
+             |--------|--------|\n\
+             | East   | 120    |\n\
+             | West   | 80     |\n\
+             | East   | 30     |\n"
+        ),
+        "Got: {}",
+        markdown
+    );
+
+    let html = convert(PivotMode::SourceData, OutputFormat::Html);
+    assert!(html.contains("<h2>Pivot: SalesPivot</h2>"), "Got: {}", html);
+    assert_eq!(html.matches("<table>").count(), 2, "Got: {}", html);
+
+    let csv = convert(PivotMode::SourceData, OutputFormat::Csv);
+    assert!(
+        csv.ends_with("\n# Pivot: SalesPivot\nRegion,Amount\nEast,120\nWest,80\nEast,30\n"),
+        "Got: {}",
+        csv
+    );
+    let json = convert(PivotMode::SourceData, OutputFormat::Json);
+    assert!(!json.contains("SalesPivot"), "Got: {}", json);
+}
+
+// TC-I-102: Formula cells can render the cached value and the formula together
+#[test]
+fn test_formula_mode_both() {
+    let convert = |builder: ConverterBuilder| {
+        let converter = builder.build().unwrap();
+        let excel_data = fixtures::generate_formulas().unwrap();
+        converter
+            .convert_to_string(Cursor::new(excel_data))
+            .unwrap()
+    };
+
+    let markdown = convert(ConverterBuilder::new().with_formula_mode(FormulaMode::Both));
+    assert!(markdown.contains("| 10 "), "Got: {}", markdown);
+    assert!(markdown.contains(" (=SUM(A1:D1)) "), "Got: {}", markdown);
+    assert!(
+        markdown.contains(" (=AVERAGE(A1:D1)) "),
+        "Got: {}",
+        markdown
+    );
+    assert!(!markdown.contains("(=10)"), "Got: {}", markdown);
+
+    let markdown = convert(
+        ConverterBuilder::new()
+            .with_formula_mode(FormulaMode::Both)
+            .with_formula_template("{formula} → {value}"),
+    );
+    assert!(markdown.contains("| SUM(A1:D1) → "), "Got: {}", markdown);
+
+    let result = ConverterBuilder::new()
+        .with_formula_mode(FormulaMode::Both)
+        .with_formula_template("{value}\n{formula}")
+        .build();
+    assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+}
+
+// TC-I-104: Per-table content hashes in the sheet metadata
+#[test]
+fn test_include_metadata_table_hashes() {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    // A1:B2 and C5:D6 hold the same table; A8:B9 differs
+    for (row, col) in [(0, 0), (4, 2)] {
+        sheet.write_string(row, col, "Region").unwrap();
+        sheet.write_string(row, col + 1, "Sales").unwrap();
+        sheet.write_string(row + 1, col, "East").unwrap();
+        sheet.write_number(row + 1, col + 1, 100).unwrap();
+    }
+    sheet.write_string(7, 0, "Region").unwrap();
+    sheet.write_string(7, 1, "Sales").unwrap();
+    sheet.write_string(8, 0, "West").unwrap();
+    sheet.write_number(8, 1, 100).unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let converter = ConverterBuilder::new()
+        .with_output_format(OutputFormat::Json)
+        .include_metadata(true)
+        .build()
+        .unwrap();
+    let output = converter
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let tables = json["metadata"]["tables"].as_array().unwrap();
+    let ranges: Vec<&str> = tables
+        .iter()
+        .map(|table| table["range"].as_str().unwrap())
+        .collect();
+    assert_eq!(ranges, vec!["A1:B2", "C5:D6", "A8:B9"]);
+    assert!(tables[0]["content_hash"]
+        .as_str()
+        .unwrap()
+        .starts_with("xxh3:"));
+    assert_eq!(tables[0]["content_hash"], tables[1]["content_hash"]);
+    assert_ne!(tables[0]["content_hash"], tables[2]["content_hash"]);
+    assert_ne!(tables[0]["content_hash"], json["metadata"]["content_hash"]);
+
+    // Markdownのフロントマターにも同じ値を出力
+    let markdown = ConverterBuilder::new()
+        .include_metadata(true)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    let expected = format!(
+        "    tables:\n      - range: \"A1:B2\"\n        content_hash: \"{}\"\n",
+        tables[0]["content_hash"].as_str().unwrap()
+    );
+    assert!(markdown.contains(&expected), "Got: {}", markdown);
+}
+
+// TC-I-105: Per-sheet output keeps workbook order and stops at the first failing sheet
+//
+// `parallel`フィーチャーの有無にかかわらず同じ結果になることを確認する
+// （CIでは`--no-default-features --features std`でも実行し、逐次処理の経路を検証する）
+#[test]
+fn test_sheet_order_and_errors_with_or_without_parallel() {
+    const SHEETS: u32 = 12;
+    let input = fixtures::generate_many_sheets(SHEETS).unwrap();
+    let names: Vec<String> = (0..SHEETS).map(|i| format!("S{:03}", i)).collect();
+
+    let converter = ConverterBuilder::new().build().unwrap();
+    let sheets = converter
+        .convert_sheets(Cursor::new(input.clone()))
+        .unwrap();
+    let sheet_names: Vec<&str> = sheets.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(sheet_names, names);
+    let chunks: Vec<String> = converter
+        .convert_to_chunks(Cursor::new(input.clone()))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(chunks.len(), SHEETS as usize);
+    for (chunk, name) in chunks.iter().zip(&names) {
+        assert!(chunk.contains(&format!("# {}\n", name)), "Got: {}", chunk);
+    }
+
+    // 後方の2シートでエラーになる場合は、前のシートのエラーを返す
+    let failing = ConverterBuilder::new()
+        .with_stage(
+            Stage::GridBuild,
+            |context: &mut StageContext<'_>| -> Result<(), XlsxToMdError> {
+                match context.sheet_name() {
+                    "S007" | "S010" => Err(XlsxToMdError::Validation {
+                        sheet: context.sheet_name().to_string(),
+                        message: "rejected by stage".to_string(),
+                    }),
+                    _ => Ok(()),
+                }
+            },
+        )
+        .build()
+        .unwrap();
+    let is_s007 = |error: &XlsxToMdError| matches!(error, XlsxToMdError::Validation { sheet, .. } if sheet == "S007");
+
+    let error = failing
+        .convert_sheets(Cursor::new(input.clone()))
+        .unwrap_err();
+    assert!(is_s007(&error), "Got: {:?}", error);
+
+    let results: Vec<_> = failing.convert_to_chunks(Cursor::new(input)).collect();
+    assert_eq!(results.len(), 8, "Got: {:?}", results);
+    for (result, name) in results[..7].iter().zip(&names) {
+        assert!(result.as_ref().unwrap().contains(&format!("# {}\n", name)));
+    }
+    assert!(is_s007(results[7].as_ref().unwrap_err()));
+}
+
+// TC-I-106: The RAG preset splits long Markdown tables and repeats the header row
+#[test]
+fn test_rag_preset_table_chunks() {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    sheet.write_string(0, 0, "ID").unwrap();
+    sheet.write_string(0, 1, "Item").unwrap();
+    for row in 1..=120u32 {
+        sheet.write_number(row, 0, row).unwrap();
+        sheet.write_string(row, 1, format!("item-{}", row)).unwrap();
+    }
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let markdown = ConverterBuilder::preset(xlsxzero::Preset::Rag)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data.clone()))
+        .unwrap();
+    // 50行ごとに分割し、各表の見出し行に先頭行を繰り返す
+    let headers: Vec<&str> = markdown
+        .lines()
+        .filter(|line| line.starts_with("| ID"))
+        .collect();
+    assert_eq!(headers.len(), 3, "Got: {}", markdown);
+    // 列幅は表ごとに計算する
+    assert!(
+        markdown.contains("| 50  | item-50 |\n\n| ID  | Item     |\n"),
+        "Got: {}",
+        markdown
+    );
+    assert_eq!(
+        markdown
+            .lines()
+            .filter(|line| line.contains("item-"))
+            .count(),
+        120
+    );
+
+    // 個別の設定で上書きできる
+    let markdown = ConverterBuilder::preset(xlsxzero::Preset::Rag)
+        .with_table_chunk_rows(200, xlsxzero::ChunkHeader::Repeat)
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(excel_data))
+        .unwrap();
+    assert_eq!(
+        markdown
+            .lines()
+            .filter(|line| line.starts_with("| ID"))
+            .count(),
+        1
+    );
+
+    let result = ConverterBuilder::new()
+        .with_table_chunk_rows(0, xlsxzero::ChunkHeader::Repeat)
+        .build();
+    assert!(matches!(result, Err(XlsxToMdError::Config(_))));
+}
+
+// TC-I-107: A grid built for Markdown renders other formats from the original cell values
+#[test]
+fn test_grid_document_render_other_format() {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    let thousands = Format::new().set_num_format("#,##0.00");
+    sheet.write_string(0, 0, "Name").unwrap();
+    sheet.write_string(0, 1, "Qty").unwrap();
+    sheet.write_string(0, 2, "Site").unwrap();
+    sheet.write_string(1, 0, "a|b *c*").unwrap();
+    sheet
+        .write_number_with_format(1, 1, 1234.5, &thousands)
+        .unwrap();
+    sheet
+        .write_url_with_text(1, 2, "https://example.com/", "Example")
+        .unwrap();
+    sheet.merge_range(2, 0, 3, 0, "", &Format::new()).unwrap();
+    sheet
+        .write_number_with_format(2, 0, 9876.5, &thousands)
+        .unwrap();
+    sheet.write_string(2, 1, "x").unwrap();
+    sheet.write_string(3, 1, "y").unwrap();
+    let excel_data = workbook.save_to_buffer().unwrap();
+
+    let mut grids = ConverterBuilder::new()
+        .build()
+        .unwrap()
+        .convert_to_grids(Cursor::new(excel_data.clone()))
+        .unwrap();
+    let grid = &mut grids[0];
+    assert_eq!(grid.cell(1, 0), Some("a\\|b *c*"));
+    assert_eq!(grid.cell(1, 1), Some("1,234.50"));
+    assert_eq!(grid.cell(1, 2), Some("[Example](https://example.com/)"));
+    assert_eq!(grid.cell(3, 0), Some("9,876.50"));
+
+    // 変換時の出力フォーマットで直接変換した場合と同じ出力になる
+    let convert = |format: OutputFormat| {
+        ConverterBuilder::new()
+            .with_output_format(format)
+            .build()
+            .unwrap()
+            .convert_sheets(Cursor::new(excel_data.clone()))
+            .unwrap()
+            .remove(0)
+            .content
+    };
+    let csv = grid.render(OutputFormat::Csv).unwrap();
+    assert_eq!(csv, convert(OutputFormat::Csv));
+    assert!(
+        csv.contains("a\\|b *c*,1234.5,Example\n9876.5,x"),
+        "Got: {}",
+        csv
+    );
+    assert_eq!(
+        grid.render(OutputFormat::Json).unwrap(),
+        convert(OutputFormat::Json)
+    );
+    assert_eq!(
+        grid.render(OutputFormat::Markdown).unwrap(),
+        convert(OutputFormat::Markdown)
+    );
+
+    // 編集したセルは表示文字列をそのまま出力し、グリッドは変更されない
+    grid.set_cell(1, 1, "n/a");
+    let csv = grid.render(OutputFormat::Csv).unwrap();
+    assert!(csv.contains("a\\|b *c*,n/a,Example\n"), "Got: {}", csv);
+    assert_eq!(grid.cell(3, 0), Some("9,876.50"));
+}
@@ -4,7 +4,7 @@
 //! ZIP bomb攻撃、XXE攻撃、パストラバーサル攻撃などへの対策を検証します。
 
 use std::io::{Cursor, Write};
-use xlsxzero::{ConverterBuilder, XlsxToMdError};
+use xlsxzero::{ConverterBuilder, SecurityLimit, SecurityLimits, XlsxToMdError};
 use zip::write::{FileOptions, ZipWriter};
 use zip::CompressionMethod;
 
@@ -34,10 +34,10 @@ fn test_zip_bomb_too_many_files() {
     // セキュリティチェックはXlsxMetadataParser::new()内で行われるが、
     // calamineが先にエラーを返す可能性があるため、両方のエラーを許容
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(msg.contains("too many files"));
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::FileCount { count, max })) => {
+            assert_eq!((count, max), (10_001, 10_000));
         }
-        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip(_)) => {
+        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip { .. }) => {
             // calamineが先にエラーを返した場合も許容（セキュリティチェックは実行されている）
         }
         e => panic!("Unexpected error: {:?}", e),
@@ -67,9 +67,7 @@ fn test_zip_bomb_large_decompressed_size() {
 
     assert!(result.is_err());
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(msg.contains("decompressed size"));
-        }
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::DecompressedSize { .. })) => {}
         _ => panic!("Expected SecurityViolation error"),
     }
 }
@@ -96,10 +94,10 @@ fn test_path_traversal_dotdot() {
     // セキュリティチェックはXlsxMetadataParser::new()内で行われるが、
     // calamineが先にエラーを返す可能性があるため、両方のエラーを許容
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(msg.contains("Path traversal") || msg.contains("Invalid ZIP path"));
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InvalidPath { path, .. })) => {
+            assert!(path.contains(".."), "{}", path);
         }
-        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip(_)) => {
+        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip { .. }) => {
             // calamineが先にエラーを返した場合も許容（セキュリティチェックは実行されている）
         }
         e => panic!("Unexpected error: {:?}", e),
@@ -129,10 +127,8 @@ fn test_path_traversal_absolute_path() {
     // セキュリティチェックが実行されない場合もある
     // ただし、XLSXファイルとして認識されないため、パースエラーになる
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(msg.contains("Absolute path") || msg.contains("Invalid ZIP path"));
-        }
-        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip(_)) => {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InvalidPath { .. })) => {}
+        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip { .. }) => {
             // calamineが先にエラーを返した場合も許容
             // ZIPライブラリがパスを正規化した場合、セキュリティチェックは通過するが、
             // XLSXファイルとして認識されないため、パースエラーになる
@@ -164,14 +160,8 @@ fn test_path_traversal_windows_absolute_path() {
     // セキュリティチェックが実行されない場合もある
     // ただし、XLSXファイルとして認識されないため、パースエラーになる
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(
-                msg.contains("Absolute path")
-                    || msg.contains("Invalid ZIP path")
-                    || msg.contains("Backslash")
-            );
-        }
-        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip(_)) => {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InvalidPath { .. })) => {}
+        Err(XlsxToMdError::Parse(_)) | Err(XlsxToMdError::Zip { .. }) => {
             // calamineが先にエラーを返した場合も許容
             // ZIPライブラリがパスを正規化した場合、セキュリティチェックは通過するが、
             // XLSXファイルとして認識されないため、パースエラーになる
@@ -192,9 +182,7 @@ fn test_input_file_size_limit() {
 
     assert!(result.is_err());
     match result {
-        Err(XlsxToMdError::SecurityViolation(msg)) => {
-            assert!(msg.contains("file size") || msg.contains("Input file size"));
-        }
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InputSize { .. })) => {}
         _ => panic!("Expected SecurityViolation error"),
     }
 }
@@ -235,3 +223,185 @@ fn test_valid_file_processing() {
         }
     }
 }
+
+/// 入力サイズ制限のテスト: with_max_input_size()で指定した上限を超える入力
+#[test]
+fn test_max_input_size_exceeded() {
+    let converter = ConverterBuilder::new()
+        .with_max_input_size(16)
+        .build()
+        .unwrap();
+
+    let input = Cursor::new(vec![0u8; 1024]);
+    let result = converter.convert_to_string(input);
+
+    match result {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InputSize { size, max })) => {
+            assert!(size > max, "{} > {}", size, max);
+        }
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+}
+
+/// エラー分類のテスト: セキュリティ制限違反はResourceLimitに分類される
+#[test]
+fn test_security_violation_error_kind() {
+    let converter = ConverterBuilder::new()
+        .with_max_input_size(16)
+        .build()
+        .unwrap();
+
+    let error = converter
+        .convert_to_string(Cursor::new(vec![0u8; 1024]))
+        .unwrap_err();
+    assert_eq!(error.kind(), xlsxzero::ErrorKind::ResourceLimit);
+}
+
+/// セキュリティ制限の指定のテスト: with_security_limits()で指定した上限を適用する
+#[test]
+fn test_custom_security_limits() {
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    workbook
+        .add_worksheet()
+        .write_string(0, 0, "limited")
+        .unwrap();
+    let buffer = workbook.save_to_buffer().unwrap();
+
+    let convert = |limits: SecurityLimits| {
+        ConverterBuilder::new()
+            .with_security_limits(limits)
+            .build()
+            .unwrap()
+            .convert_to_string(Cursor::new(buffer.clone()))
+    };
+
+    // デフォルトの上限では変換できる
+    assert!(convert(SecurityLimits::default())
+        .unwrap()
+        .contains("limited"));
+
+    // ファイル数の上限を引き下げる
+    match convert(SecurityLimits {
+        max_file_count: 3,
+        ..SecurityLimits::default()
+    }) {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::FileCount { .. })) => {}
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+
+    // 単一ファイルの最大サイズを引き下げる
+    match convert(SecurityLimits {
+        max_file_size: 64,
+        ..SecurityLimits::default()
+    }) {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::FileSize {
+            size, max: 64, ..
+        })) => {
+            assert!(size > 64)
+        }
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+
+    // 入力ファイルの最大サイズを引き下げる
+    match convert(SecurityLimits {
+        max_input_file_size: 16,
+        ..SecurityLimits::default()
+    }) {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::InputSize { max: 16, .. })) => {}
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+}
+
+/// ZIP bomb攻撃のテスト: 宣言された展開後のサイズを偽装したZIPアーカイブ
+#[test]
+fn test_zip_bomb_forged_uncompressed_size() {
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    workbook.add_worksheet().write_string(0, 0, "bomb").unwrap();
+    let buffer = workbook.save_to_buffer().unwrap();
+
+    // 正常なワークブックに、2MBのゼロを圧縮したパーツを追加
+    let mut source = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+    let mut zip_data = Vec::new();
+    {
+        let mut zip = ZipWriter::new(Cursor::new(&mut zip_data));
+        for i in 0..source.len() {
+            zip.raw_copy_file(source.by_index(i).unwrap()).unwrap();
+        }
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        zip.start_file("xl/bomb.xml", options).unwrap();
+        zip.write_all(&vec![0u8; 2 * 1024 * 1024]).unwrap();
+        zip.finish().unwrap();
+    }
+
+    // ローカルヘッダーとセントラルディレクトリの展開後のサイズを10バイトに書き換える
+    let forged = 10u32.to_le_bytes();
+    let name = b"xl/bomb.xml";
+    for (signature, size_offset) in [(b"PK\x03\x04", 22), (b"PK\x01\x02", 24)] {
+        let header = zip_data
+            .windows(4)
+            .enumerate()
+            .filter(|(_, window)| window == signature)
+            .map(|(offset, _)| offset)
+            .find(|offset| {
+                let name_offset = if size_offset == 22 { 30 } else { 46 };
+                zip_data[offset + name_offset..].starts_with(name)
+            })
+            .unwrap();
+        zip_data[header + size_offset..header + size_offset + 4].copy_from_slice(&forged);
+    }
+    let declared = zip::ZipArchive::new(Cursor::new(zip_data.as_slice()))
+        .unwrap()
+        .by_name("xl/bomb.xml")
+        .unwrap()
+        .size();
+    assert_eq!(declared, 10);
+
+    let converter = ConverterBuilder::new().build().unwrap();
+    match converter.convert_to_string(Cursor::new(zip_data)) {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::CompressionRatio { part, .. })) => {
+            assert_eq!(part, "xl/bomb.xml");
+        }
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+}
+
+/// XXE攻撃のテスト: DOCTYPE宣言（外部エンティティ）を含むXMLパーツ
+#[test]
+fn test_xxe_doctype_rejected() {
+    use std::io::Read;
+
+    let mut workbook = rust_xlsxwriter::Workbook::new();
+    workbook.add_worksheet().write_string(0, 0, "xxe").unwrap();
+    let buffer = workbook.save_to_buffer().unwrap();
+
+    // xl/styles.xmlのXML宣言の直後に外部エンティティを宣言するDOCTYPEを挿入
+    let mut source = zip::ZipArchive::new(Cursor::new(buffer)).unwrap();
+    let mut zip_data = Vec::new();
+    {
+        let mut zip = ZipWriter::new(Cursor::new(&mut zip_data));
+        for i in 0..source.len() {
+            let mut file = source.by_index(i).unwrap();
+            let name = file.name().to_string();
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            if name == "xl/styles.xml" {
+                let end = content.find("?>").unwrap() + 2;
+                content.insert_str(
+                    end,
+                    "<!DOCTYPE styleSheet [<!ENTITY xxe SYSTEM \"file:///etc/passwd\">]>",
+                );
+            }
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    let converter = ConverterBuilder::new().build().unwrap();
+    match converter.convert_to_string(Cursor::new(zip_data)) {
+        Err(XlsxToMdError::SecurityViolation(SecurityLimit::XmlDoctype { part })) => {
+            assert_eq!(part, "xl/styles.xml");
+        }
+        other => panic!("Expected SecurityViolation error, got: {:?}", other),
+    }
+}
@@ -0,0 +1,183 @@
+//! Snapshot Tests for xlsxzero
+//!
+//! This module converts one canonical workbook with every output format and the
+//! options that change the rendered text (merge strategy, metadata, formula mode,
+//! JSON variants), and compares the results with the golden files in
+//! `tests/snapshots/`. Any change to escaping, headers or separators shows up as a
+//! snapshot diff.
+//!
+//! # Updating snapshots
+//!
+//! When an output change is intentional:
+//!
+//! 1. Run `cargo test --test snapshot_test`. Failing snapshots are written next to
+//!    the golden files as `*.snap.new`.
+//! 2. Review the diffs with `cargo insta review` (requires `cargo install cargo-insta`)
+//!    and accept or reject each one. Without cargo-insta, run
+//!    `INSTA_UPDATE=always cargo test --test snapshot_test` and review the changes
+//!    of `tests/snapshots/` with `git diff`.
+//! 3. Commit the updated `.snap` files together with the code change, and describe
+//!    the output change in CHANGELOG.md.
+//!
+//! New snapshots (for a new format or option) are created the same way.
+
+use rust_xlsxwriter::*;
+use std::io::Cursor;
+use xlsxzero::{
+    ConverterBuilder, FormulaMode, HyperlinkMode, JsonCellMode, JsonDocumentMode, JsonKeyMode,
+    JsonStyle, MergeStrategy, OutputFormat,
+};
+
+/// Output formats covered by the snapshots, with their snapshot name prefixes
+const FORMATS: &[(OutputFormat, &str)] = &[
+    (OutputFormat::Markdown, "markdown"),
+    (OutputFormat::Html, "html"),
+    (OutputFormat::Json, "json"),
+    (OutputFormat::Csv, "csv"),
+    (OutputFormat::Xml, "xml"),
+];
+
+/// Generate the canonical workbook
+///
+/// The `Orders` sheet contains the characters each format has to escape
+/// (pipes, line breaks, HTML and XML markup, quotes, commas), full-width text,
+/// numbers, dates, booleans, a formula and a hyperlink. The `Summary` sheet
+/// contains a horizontally and a vertically merged cell.
+fn generate_canonical() -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let date_format = Format::new().set_num_format("yyyy-mm-dd");
+
+    let orders = workbook.add_worksheet();
+    orders.set_name("Orders")?;
+    for (col, header) in (0u16..).zip(["Item", "Qty", "Price", "Total", "Date", "Notes"]) {
+        orders.write_string(0, col, header)?;
+    }
+    let rows: [(&str, f64, f64, f64, &str); 4] = [
+        ("Widget", 2.0, 9.5, 45658.0, "plain"),
+        ("Pipe | Fitting", 10.0, 0.25, 45659.0, "a, \"quoted\" note"),
+        (
+            "<b>Bold</b> & co",
+            1.0,
+            1234.5,
+            45660.0,
+            "line one\nline two",
+        ),
+        ("日本語の品目", 3.0, 100.0, 45661.0, ""),
+    ];
+    for (row, (item, qty, price, date, notes)) in (1u32..).zip(rows) {
+        orders.write_string(row, 0, item)?;
+        orders.write_number(row, 1, qty)?;
+        orders.write_number(row, 2, price)?;
+        let total =
+            Formula::new(format!("=B{0}*C{0}", row + 1)).set_result((qty * price).to_string());
+        orders.write_formula(row, 3, total)?;
+        orders.write_number_with_format(row, 4, date, &date_format)?;
+        if !notes.is_empty() {
+            orders.write_string(row, 5, notes)?;
+        }
+    }
+    orders.write_string(5, 0, "Shipping")?;
+    orders.write_boolean(5, 1, true)?;
+    orders.write_url_with_text(5, 5, "https://example.com/terms?a=1&b=2", "Terms")?;
+
+    let summary = workbook.add_worksheet();
+    summary.set_name("Summary")?;
+    summary.merge_range(0, 0, 0, 2, "Quarterly summary", &Format::new())?;
+    summary.write_string(1, 0, "Region")?;
+    summary.write_string(1, 1, "Quarter")?;
+    summary.write_string(1, 2, "Sales")?;
+    summary.merge_range(2, 0, 3, 0, "East", &Format::new())?;
+    for (row, quarter, sales) in [(2, "Q1", 120.0), (3, "Q2", 80.5)] {
+        summary.write_string(row, 1, quarter)?;
+        summary.write_number(row, 2, sales)?;
+    }
+
+    workbook.save_to_buffer()
+}
+
+/// Convert the canonical workbook with the given builder
+fn convert(builder: ConverterBuilder) -> String {
+    builder
+        .build()
+        .unwrap()
+        .convert_to_string(Cursor::new(generate_canonical().unwrap()))
+        .unwrap()
+}
+
+// TC-S-001: Every output format with the default options
+#[test]
+fn test_snapshot_defaults() {
+    for (format, prefix) in FORMATS {
+        let output = convert(ConverterBuilder::new().with_output_format(*format));
+        insta::assert_snapshot!(format!("{}_default", prefix), output);
+    }
+}
+
+// TC-S-002: Every output format with merged cells as HTML fallback
+#[test]
+fn test_snapshot_html_fallback() {
+    for (format, prefix) in FORMATS {
+        let output = convert(
+            ConverterBuilder::new()
+                .with_output_format(*format)
+                .with_merge_strategy(MergeStrategy::HtmlFallback),
+        );
+        insta::assert_snapshot!(format!("{}_html_fallback", prefix), output);
+    }
+}
+
+// TC-S-003: Every output format with sheet metadata
+#[test]
+fn test_snapshot_metadata() {
+    for (format, prefix) in FORMATS {
+        let output = convert(
+            ConverterBuilder::new()
+                .with_output_format(*format)
+                .include_metadata(true),
+        );
+        insta::assert_snapshot!(format!("{}_metadata", prefix), output);
+    }
+}
+
+// TC-S-004: Every output format with formulas instead of cached values
+#[test]
+fn test_snapshot_formulas() {
+    for (format, prefix) in FORMATS {
+        let output = convert(
+            ConverterBuilder::new()
+                .with_output_format(*format)
+                .with_formula_mode(FormulaMode::Formula),
+        );
+        insta::assert_snapshot!(format!("{}_formulas", prefix), output);
+    }
+}
+
+// TC-S-005: Markdown hyperlinks as footnotes
+#[test]
+fn test_snapshot_markdown_hyperlink_footnotes() {
+    let output = convert(ConverterBuilder::new().with_hyperlink_mode(HyperlinkMode::Footnote));
+    insta::assert_snapshot!("markdown_hyperlink_footnotes", output);
+}
+
+// TC-S-006: JSON cell, key and document variants
+#[test]
+fn test_snapshot_json_variants() {
+    let json = || ConverterBuilder::new().with_output_format(OutputFormat::Json);
+
+    insta::assert_snapshot!(
+        "json_typed",
+        convert(json().with_json_cell_mode(JsonCellMode::Typed))
+    );
+    insta::assert_snapshot!(
+        "json_header_keys",
+        convert(json().with_json_key_mode(JsonKeyMode::Header))
+    );
+    insta::assert_snapshot!(
+        "json_lines",
+        convert(json().with_json_document_mode(JsonDocumentMode::Lines))
+    );
+    insta::assert_snapshot!(
+        "json_minified",
+        convert(json().with_json_style(JsonStyle::Minified))
+    );
+}